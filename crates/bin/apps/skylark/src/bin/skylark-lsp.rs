@@ -0,0 +1,6 @@
+//! **skylark-lsp**, a language server for Starlark speaking the Language
+//! Server Protocol over stdio.
+
+fn main() -> anyhow::Result<()> {
+    skylark::lsp::run_stdio()
+}
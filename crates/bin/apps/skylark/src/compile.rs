@@ -1,7 +1,127 @@
-use crate::{ir::SourceProgram, parser::parse_statements, type_check::type_check_program};
+use crate::{
+    ir::{Diagnostic, Diagnostics, Item, SourceProgram, Span, Statement, StatementData},
+    lower::{lower_item, parse_program},
+    type_check::type_check_program,
+};
+#[cfg(test)]
+use expect_test::expect;
 
 #[salsa::tracked]
 pub fn compile(db: &dyn crate::Db, source_program: SourceProgram) {
-    let program = parse_statements(db, source_program);
-    type_check_program(db, program);
+    let program = parse_program(db, source_program);
+    for item in program.items(db) {
+        let offset = item.offset(db);
+        for (span, message) in check_control_flow(db, *item) {
+            Diagnostics::push(
+                db,
+                Diagnostic::new(offset + span.start, offset + span.end, message.clone()),
+            );
+        }
+    }
+
+    type_check_program(db, source_program);
+}
+
+/// The `return`, `break` and `continue` statements of the given item which
+/// are outside of a function or a loop, with spans relative to the item.
+#[salsa::tracked(return_ref)]
+pub fn check_control_flow(db: &dyn crate::Db, item: Item) -> Vec<(Span, String)> {
+    let mut errors = Vec::new();
+    check_statements(db, lower_item(db, item), false, false, &mut errors);
+
+    errors
+}
+
+fn check_statements(
+    db: &dyn crate::Db,
+    statements: &[Statement],
+    in_function: bool,
+    in_loop: bool,
+    errors: &mut Vec<(Span, String)>,
+) {
+    for statement in statements {
+        match &statement.data {
+            // A loop around a function doesn't apply to its body.
+            StatementData::Def(function) => {
+                check_statements(db, function.body(db), true, false, errors)
+            }
+            StatementData::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(db, then_branch, in_function, in_loop, errors);
+                check_statements(db, else_branch, in_function, in_loop, errors);
+            }
+            StatementData::For { body, .. } => {
+                check_statements(db, body, in_function, true, errors)
+            }
+            StatementData::Return(_) if !in_function => {
+                errors.push((statement.span, "`return` outside of a function".to_string()))
+            }
+            StatementData::Break if !in_loop => {
+                errors.push((statement.span, "`break` outside of a loop".to_string()))
+            }
+            StatementData::Continue if !in_loop => {
+                errors.push((statement.span, "`continue` outside of a loop".to_string()))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Compiles the given source text, returning the diagnostics.
+#[cfg(test)]
+fn compile_string(source_text: &str) -> Vec<Diagnostic> {
+    use crate::db::Database;
+
+    let db = Database::default();
+    let source_program = SourceProgram::new(&db, source_text.to_string());
+
+    compile::accumulated::<Diagnostics>(&db, source_program)
+}
+
+#[test]
+fn compile_starlark() {
+    expect![[r#"
+        []
+    "#]]
+    .assert_debug_eq(&compile_string(
+        "load(\":defs.bzl\", \"rule\")\n\ndef srcs(names: list[str]) -> list[str]:\n    return [name + \".rs\" for name in names]\n\nrule(name = \"foo\", srcs = srcs([\"a\", \"b\"]))\n",
+    ));
+}
+
+#[test]
+fn compile_control_flow() {
+    expect![[r#"
+        [
+            Diagnostic {
+                start: 0,
+                end: 8,
+                message: "`return` outside of a function",
+            },
+            Diagnostic {
+                start: 43,
+                end: 48,
+                message: "`break` outside of a loop",
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&compile_string(
+        "return 1\nfor x in []:\n    def f():\n        break\n    continue\n",
+    ));
+}
+
+#[test]
+fn compile_syntax_errors() {
+    expect![[r#"
+        [
+            Diagnostic {
+                start: 6,
+                end: 7,
+                message: "Syntax error encountered",
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&compile_string("f(1, 2\n"));
 }
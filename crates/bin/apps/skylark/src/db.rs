@@ -1,19 +1,44 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use salsa::DebugWithDb;
+use sky_syntax::TextEdit;
+
+use crate::{
+    workspace::{parse, FileChange, SourceFile, Workspace},
+    Db,
+};
 
 // ANCHOR: db_struct
-#[derive(Default)]
 #[salsa::db(crate::Jar)]
-pub(crate) struct Database {
+pub struct Database {
     storage: salsa::Storage<Self>,
 
     // The logs are only used for testing and demonstrating reuse:
     //
     logs: Option<Arc<Mutex<Vec<String>>>>,
+
+    /// The files of the workspace, only `None` while the database is created.
+    workspace: Option<Workspace>,
 }
 // ANCHOR_END: db_struct
 
+impl Default for Database {
+    fn default() -> Self {
+        let mut db = Self {
+            storage: Default::default(),
+            logs: None,
+            workspace: None,
+        };
+        db.workspace = Some(Workspace::new(&db, BTreeMap::new(), BTreeMap::new()));
+
+        db
+    }
+}
+
 impl Database {
     /// Enable logging of each salsa event.
     #[cfg(test)]
@@ -22,6 +47,7 @@ impl Database {
         Self {
             storage: self.storage,
             logs: Some(Default::default()),
+            workspace: self.workspace,
         }
     }
 
@@ -35,6 +61,83 @@ impl Database {
     }
 }
 
+// ANCHOR: workspace_impl
+impl Database {
+    /// **Applies** the given batch of changes to the files of the workspace,
+    /// returning the files which changed.
+    ///
+    /// Setting a file to its current text isn't a change, so the queries
+    /// depending on it aren't invalidated. Neither are the queries looking
+    /// files up by path, unless a file is added for the first time.
+    pub fn apply_changes(
+        &mut self,
+        changes: impl IntoIterator<Item = FileChange>,
+    ) -> Vec<SourceFile> {
+        let workspace = self.workspace();
+        let mut files = workspace.files(self).clone();
+        let mut added = false;
+        let mut changed = Vec::new();
+        for change in changes {
+            match files.get(&change.path) {
+                Some(&file) => {
+                    if file.text(self) != &change.text {
+                        file.set_text(self).to(change.text);
+                        file.set_reparsed(self).to(None);
+                        changed.push(file);
+                    }
+                }
+                None => {
+                    if change.text.is_some() {
+                        let file = SourceFile::new(self, change.path.clone(), change.text, None);
+                        files.insert(change.path, file);
+                        added = true;
+                        changed.push(file);
+                    }
+                }
+            }
+        }
+        if added {
+            workspace.set_files(self).to(files);
+        }
+
+        changed
+    }
+
+    /// Registers the root of the given **external repository**, relative to
+    /// the root of the workspace, so that its files are loaded by `@name//`
+    /// labels.
+    pub fn add_repository(&mut self, name: impl Into<String>, root: impl Into<PathBuf>) {
+        let workspace = self.workspace();
+        let mut repositories = workspace.repositories(self).clone();
+        repositories.insert(name.into(), root.into());
+        workspace.set_repositories(self).to(repositories);
+    }
+
+    /// **Applies** the given edits to the text of the given file, in order.
+    ///
+    /// Rather than parsing the edited text from scratch, its syntax tree is
+    /// reparsed incrementally (see [`Parse::reparse`](sky_syntax::Parse::reparse)),
+    /// only relexing and reparsing the tokens or blocks around each edit.
+    pub fn apply_edits(&mut self, file: SourceFile, edits: impl IntoIterator<Item = TextEdit>) {
+        let mut reparsed = parse(self, file).clone();
+        for edit in edits {
+            reparsed = reparsed.reparse(edit);
+        }
+
+        let text = reparsed.syntax_node().text().to_string();
+        file.set_text(self).to(Some(text));
+        file.set_reparsed(self).to(Some(reparsed));
+    }
+}
+
+impl Db for Database {
+    fn workspace(&self) -> Workspace {
+        self.workspace
+            .expect("the workspace should be created with the database")
+    }
+}
+// ANCHOR_END: workspace_impl
+
 // ANCHOR: db_impl
 impl salsa::Database for Database {
     fn salsa_event(&self, event: salsa::Event) {
@@ -57,6 +160,7 @@ impl salsa::ParallelDatabase for Database {
         salsa::Snapshot::new(Database {
             storage: self.storage.snapshot(),
             logs: self.logs.clone(),
+            workspace: self.workspace,
         })
     }
 }
@@ -0,0 +1,219 @@
+//! **Completion** of names: the names in scope (including loaded symbols)
+//! and the builtins, or the symbols exported by the loaded module within the
+//! strings of a `load` statement.
+
+#[cfg(test)]
+use super::fixture;
+use super::{follow_load, hover::signature, is_trivia, loaded_file, Definition};
+use crate::workspace::{module_exports, parse, resolution, SourceFile};
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use rowan::TextSize;
+use sky_analyzer::{builtins::BUILTINS, resolve::BindingKind};
+use sky_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind,
+};
+use std::{collections::HashSet, iter::successors};
+
+/// A **completion** of the name at the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// The signature of a function.
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompletionKind {
+    Function,
+    Variable,
+    Parameter,
+    /// A builtin constant (e.g. `True`) or function (e.g. `len`).
+    Builtin,
+}
+
+/// The **completions** at the given offset, in order.
+pub fn completions(db: &dyn crate::Db, file: SourceFile, offset: TextSize) -> Vec<Completion> {
+    let tree = parse(db, file).tree();
+    let Some(token) = tree.syntax().token_at_offset(offset).left_biased() else {
+        return Vec::new();
+    };
+
+    match token.kind() {
+        SyntaxKind::STRING => {
+            let symbol = token.parent().and_then(ast::LoadSymbol::cast);
+            let inside = token.text_range().start() < offset && offset < token.text_range().end();
+            match symbol
+                .and_then(|it| it.syntax().parent())
+                .and_then(ast::LoadStmt::cast)
+            {
+                Some(stmt) if inside => load_completions(db, file, &stmt),
+                _ => Vec::new(),
+            }
+        }
+        SyntaxKind::COMMENT => Vec::new(),
+        _ => {
+            // Attributes (e.g. of `native.`) aren't known.
+            let before = match token.kind() {
+                SyntaxKind::IDENTIFIER => token.prev_token(),
+                _ => Some(token),
+            };
+            let previous =
+                successors(before, |it| it.prev_token()).find(|it| !is_trivia(it.kind()));
+            if previous.map(|it| it.kind()) == Some(SyntaxKind::DOT) {
+                return Vec::new();
+            }
+
+            name_completions(db, file, offset)
+        }
+    }
+}
+
+/// The names in scope at the given offset, innermost first, followed by the
+/// builtins they don't shadow.
+fn name_completions(db: &dyn crate::Db, file: SourceFile, offset: TextSize) -> Vec<Completion> {
+    let resolution = resolution(db, file);
+    let mut seen = HashSet::new();
+    let mut completions = Vec::new();
+
+    let mut scope = Some(resolution.scope_at(offset));
+    while let Some(id) = scope {
+        for binding_id in &resolution.scope(id).bindings {
+            let binding = resolution.binding(*binding_id);
+            if !seen.insert(binding.name.clone()) {
+                continue;
+            }
+
+            let (kind, detail) = match follow_load(db, file, *binding_id) {
+                Definition::Binding(file, id)
+                    if crate::workspace::resolution(db, file).binding(id).kind
+                        == BindingKind::Function =>
+                {
+                    (CompletionKind::Function, Some(signature(db, file, id)))
+                }
+                _ if binding.kind == BindingKind::Parameter => (CompletionKind::Parameter, None),
+                _ => (CompletionKind::Variable, None),
+            };
+            completions.push(Completion {
+                label: binding.name.clone(),
+                kind,
+                detail,
+            });
+        }
+        scope = resolution.scope(id).parent;
+    }
+
+    completions.extend(
+        BUILTINS
+            .iter()
+            .filter(|name| !seen.contains(**name))
+            .map(|name| Completion {
+                label: name.to_string(),
+                kind: CompletionKind::Builtin,
+                detail: None,
+            }),
+    );
+
+    completions
+}
+
+/// The symbols exported by the module of the given `load` statement, other
+/// than those it already loads.
+fn load_completions(db: &dyn crate::Db, file: SourceFile, stmt: &ast::LoadStmt) -> Vec<Completion> {
+    let Some(loaded) = loaded_file(db, file, stmt) else {
+        return Vec::new();
+    };
+    let loaded_symbols: HashSet<_> = stmt
+        .load_symbols()
+        .filter_map(|it| it.symbol()?.value().ok())
+        .collect();
+
+    let resolution = resolution(db, loaded);
+    module_exports(db, loaded)
+        .iter()
+        .filter(|name| !loaded_symbols.contains(*name))
+        .map(|name| {
+            let id = resolution.lookup(resolution.module(), name);
+            let (kind, detail) = match id {
+                Some(id) if resolution.binding(id).kind == BindingKind::Function => {
+                    (CompletionKind::Function, Some(signature(db, loaded, id)))
+                }
+                _ => (CompletionKind::Variable, None),
+            };
+
+            Completion {
+                label: name.clone(),
+                kind,
+                detail,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn check(files: &[(&str, &str)], expect: Expect) {
+    let (db, file, offset) = fixture(files);
+    let actual: String = completions(&db, file, offset)
+        .into_iter()
+        .filter(|it| it.kind != CompletionKind::Builtin)
+        .map(|it| match it.detail {
+            Some(detail) => format!("{:?} {} {detail}\n", it.kind, it.label),
+            None => format!("{:?} {}\n", it.kind, it.label),
+        })
+        .collect();
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn complete_names() {
+    check(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", \"rule\")\n\nSRCS = [\"a.rs\"]\n\ndef f(x):\n    y = 1\n    return $0\n",
+            ),
+            ("foo/defs.bzl", "def rule(name, srcs = []):\n    pass\n"),
+        ],
+        expect![[r#"
+            Parameter x
+            Variable y
+            Function rule def rule(name, srcs = ...) -> None
+            Variable SRCS
+            Function f def f(x) -> None
+        "#]],
+    );
+    check(&[("BUCK", "x = native.$0\n")], expect![""]);
+}
+
+#[test]
+fn complete_builtins() {
+    let (db, file, offset) = fixture(&[("BUCK", "le$0\n")]);
+    let builtins: Vec<_> = completions(&db, file, offset)
+        .into_iter()
+        .filter(|it| it.kind == CompletionKind::Builtin)
+        .map(|it| it.label)
+        .collect();
+
+    assert!(builtins.contains(&"len".to_string()));
+    assert_eq!(builtins.len(), BUILTINS.len());
+}
+
+#[test]
+fn complete_loaded_symbols() {
+    check(
+        &[
+            ("foo/BUCK", "load(\":defs.bzl\", \"rule\", \"$0\")\n"),
+            (
+                "foo/defs.bzl",
+                "_private = 1\nVERSION = \"1.0\"\n\ndef rule(name):\n    pass\n\ndef macro(name):\n    pass\n",
+            ),
+        ],
+        expect![[r#"
+            Variable VERSION
+            Function macro def macro(name) -> None
+        "#]],
+    );
+}
@@ -0,0 +1,212 @@
+//! **Hover** information of names, i.e. the signature and docstring of
+//! functions, and the (inferred) type of variables.
+
+#[cfg(test)]
+use super::fixture;
+use super::{definition, load_symbol_at, string_value, Definition};
+use crate::workspace::{parse, resolution, SourceFile};
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use rowan::{TextRange, TextSize};
+use sky_analyzer::{
+    resolve::{BindingId, BindingKind},
+    types::{check, Globals, Type},
+};
+use sky_syntax::ast::{self, AstNode};
+
+/// The **hover** information of a name, as Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// The range of the name hovered.
+    pub range: TextRange,
+    pub markdown: String,
+}
+
+/// The **hover** information of the name at the given offset.
+pub fn hover(db: &dyn crate::Db, file: SourceFile, offset: TextSize) -> Option<Hover> {
+    let (range, definition) = definition(db, file, offset)?;
+    let markdown = match definition {
+        Definition::Binding(file, id) => describe(db, file, id),
+        Definition::Builtin(name) => format!("```starlark\n{name}\n```\n\nA builtin of Starlark."),
+    };
+
+    Some(Hover { range, markdown })
+}
+
+/// The **signature** of the given function binding, e.g. `def f(x: int) ->
+/// str`.
+pub(super) fn signature(db: &dyn crate::Db, file: SourceFile, id: BindingId) -> String {
+    let tree = parse(db, file).tree();
+    match check(&tree, &Globals::default()).binding_type(id) {
+        Type::Function(signature) => signature.to_string(),
+        _ => format!("def {}(...)", resolution(db, file).binding(id).name),
+    }
+}
+
+/// Describes the given binding as Markdown.
+fn describe(db: &dyn crate::Db, file: SourceFile, id: BindingId) -> String {
+    let binding = resolution(db, file).binding(id);
+    let tree = parse(db, file).tree();
+    match binding.kind {
+        BindingKind::Function => {
+            let docstring = tree
+                .syntax()
+                .covering_element(binding.range())
+                .ancestors()
+                .find_map(ast::DefStmt::cast)
+                .and_then(|it| docstring(&it));
+            let signature = signature(db, file, id);
+
+            match docstring {
+                Some(docstring) => format!("```starlark\n{signature}\n```\n\n{docstring}"),
+                None => format!("```starlark\n{signature}\n```"),
+            }
+        }
+        // A symbol whose module (or binding in its module) can't be found.
+        BindingKind::Load => {
+            let label = load_symbol_at(tree.syntax(), binding.range())
+                .and_then(|it| it.syntax().parent())
+                .and_then(ast::LoadStmt::cast)
+                .and_then(|it| it.module())
+                .map(|it| it.to_string())
+                .unwrap_or_default();
+
+            format!("```starlark\n{}\n```\n\nLoaded from {label}.", binding.name)
+        }
+        BindingKind::Parameter | BindingKind::Variable => {
+            let ty = check(&tree, &Globals::default()).binding_type(id).clone();
+            format!("```starlark\n{}: {ty}\n```", binding.name)
+        }
+    }
+}
+
+/// The **docstring** of the given function, i.e. the string literal its body
+/// starts with, without its indentation.
+fn docstring(def: &ast::DefStmt) -> Option<String> {
+    let body = def.body()?;
+    let first = match body.statements().next() {
+        Some(statement) => statement.syntax().clone(),
+        None => body.simple_stmt()?.syntax().clone(),
+    };
+    let (text, _) = string_value(&first)?;
+
+    // Like Python's `inspect.cleandoc`, the first line is stripped, and the
+    // common indentation of the other lines is removed.
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    let rest: Vec<_> = lines.collect();
+    let indent = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+
+    let lines: Vec<_> = std::iter::once(first)
+        .chain(
+            rest.iter()
+                .map(|line| line.get(indent..).unwrap_or_default().trim_end()),
+        )
+        .collect();
+    let docstring = lines.join("\n").trim().to_string();
+
+    (!docstring.is_empty()).then_some(docstring)
+}
+
+#[cfg(test)]
+fn check_hover(files: &[(&str, &str)], expect: Expect) {
+    let (db, file, offset) = fixture(files);
+    let actual = hover(&db, file, offset)
+        .map(|hover| format!("{:?}\n{}\n", hover.range, hover.markdown))
+        .unwrap_or_default();
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn hover_function() {
+    check_hover(
+        &[(
+            "defs.bzl",
+            "def f(x: int, y = 1) -> str:\n    \"\"\"Formats `x`.\n\n    Indented lines are kept.\n    \"\"\"\n    return str(x)\n\nz = f$0(1)\n",
+        )],
+        expect![[r#"
+            110..111
+            ```starlark
+            def f(x: int, y = ...) -> str
+            ```
+
+            Formats `x`.
+
+            Indented lines are kept.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_loaded_function() {
+    check_hover(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", \"rule\", \"missing\")\n\nrule$0(name = \"foo\")\n",
+            ),
+            (
+                "foo/defs.bzl",
+                "def rule(name):\n    \"Declares a target.\"\n    pass\n",
+            ),
+        ],
+        expect![[r#"
+            38..42
+            ```starlark
+            def rule(name) -> None
+            ```
+
+            Declares a target.
+        "#]],
+    );
+    check_hover(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", \"rule\", \"missing\")\n\nmissing$0()\n",
+            ),
+            ("foo/defs.bzl", "def rule(name):\n    pass\n"),
+        ],
+        expect![[r#"
+            38..45
+            ```starlark
+            missing
+            ```
+
+            Loaded from ":defs.bzl".
+        "#]],
+    );
+}
+
+#[test]
+fn hover_variables() {
+    check_hover(
+        &[(
+            "defs.bzl",
+            "srcs = [\"a.rs\"]\nnames = [s$0 for s in srcs]\n",
+        )],
+        expect![[r#"
+            25..26
+            ```starlark
+            s: str
+            ```
+        "#]],
+    );
+    check_hover(
+        &[("defs.bzl", "x = le$0n([])\n")],
+        expect![[r#"
+            4..7
+            ```starlark
+            len
+            ```
+
+            A builtin of Starlark.
+        "#]],
+    );
+}
@@ -0,0 +1,174 @@
+//! **IDE features** of the files of a workspace (e.g. go-to-definition, hover
+//! and rename), in terms of offsets in their text rather than of a protocol
+//! (see [`crate::lsp`] for the Language Server Protocol).
+//!
+//! Each feature is computed on demand from the queries of
+//! [`crate::workspace`], so that it only re-analyzes what changed since the
+//! previous request.
+
+use crate::workspace::{parse, resolution, resolve_load, LabelId, SourceFile};
+use rowan::{TextRange, TextSize};
+use sky_analyzer::resolve::{BindingId, BindingKind, Resolved};
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    SyntaxKind, SyntaxNode,
+};
+
+mod completion;
+mod hover;
+mod navigation;
+mod rename;
+mod symbols;
+
+pub use completion::{completions, Completion, CompletionKind};
+pub use hover::{hover, Hover};
+pub use navigation::{goto_definition, NavigationTarget};
+pub use rename::{rename, FileEdit, RenameError};
+pub use symbols::{document_symbols, Symbol, SymbolKind};
+
+/// What a name **refers to**.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Definition {
+    /// A binding of the given file. A loaded symbol refers to the binding
+    /// of the loaded module exporting it, if it can be resolved.
+    Binding(SourceFile, BindingId),
+    Builtin(String),
+}
+
+/// The **name** at the given offset (i.e. a reference, or a name binding
+/// it), along with its range and what it refers to.
+fn definition(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    offset: TextSize,
+) -> Option<(TextRange, Definition)> {
+    let resolution = resolution(db, file);
+    if let Some(reference) = resolution.reference_at(offset) {
+        let definition = match reference.resolved {
+            Resolved::Binding(id) => follow_load(db, file, id),
+            Resolved::Builtin => Definition::Builtin(reference.name.clone()),
+            Resolved::Predeclared | Resolved::Undefined => return None,
+        };
+
+        return Some((reference.range, definition));
+    }
+
+    let id = resolution.binding_at(offset)?;
+    let range = resolution
+        .binding(id)
+        .definitions
+        .iter()
+        .find(|it| it.contains_inclusive(offset))?;
+
+    Some((*range, follow_load(db, file, id)))
+}
+
+/// Follows the given binding to the binding exporting it, if it's a loaded
+/// symbol.
+fn follow_load(db: &dyn crate::Db, file: SourceFile, id: BindingId) -> Definition {
+    let binding = resolution(db, file).binding(id);
+    if binding.kind == BindingKind::Load {
+        if let Some((loaded, exported)) = loaded_binding(db, file, binding.range()) {
+            return Definition::Binding(loaded, exported);
+        }
+    }
+
+    Definition::Binding(file, id)
+}
+
+/// The binding exported by the loaded module under the symbol of the
+/// `load` statement at the given range (i.e. the range of its alias or of
+/// its string).
+fn loaded_binding(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    range: TextRange,
+) -> Option<(SourceFile, BindingId)> {
+    let symbol = load_symbol_at(parse(db, file).tree().syntax(), range)?;
+    let stmt = symbol.syntax().parent().and_then(ast::LoadStmt::cast)?;
+    let loaded = loaded_file(db, file, &stmt)?;
+    let name = symbol.symbol()?.value().ok()?;
+
+    let resolution = resolution(db, loaded);
+    let id = resolution.lookup(resolution.module(), &name)?;
+    (resolution.binding(id).kind != BindingKind::Load).then_some((loaded, id))
+}
+
+/// The symbol of a `load` statement at the given range.
+fn load_symbol_at(root: &SyntaxNode, range: TextRange) -> Option<ast::LoadSymbol> {
+    root.covering_element(range)
+        .ancestors()
+        .find_map(ast::LoadSymbol::cast)
+}
+
+/// The file loaded by the given `load` statement, if it can be resolved.
+fn loaded_file(db: &dyn crate::Db, file: SourceFile, stmt: &ast::LoadStmt) -> Option<SourceFile> {
+    let label = stmt.module()?.value().ok()?;
+    resolve_load(db, file, LabelId::new(db, label)).ok()
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE
+            | SyntaxKind::COMMENT
+            | SyntaxKind::NEWLINE
+            | SyntaxKind::INDENT
+            | SyntaxKind::OUTDENT
+    )
+}
+
+/// The **range** of the given node without its surrounding trivia (e.g. the
+/// leading comments and whitespace, which are part of the node).
+fn significant_range(node: &SyntaxNode) -> TextRange {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !is_trivia(it.kind()));
+    let Some(first) = tokens.next() else {
+        return TextRange::empty(node.text_range().start());
+    };
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    TextRange::new(first.text_range().start(), last.text_range().end())
+}
+
+/// The value of the given node if it's a lone **string literal** (e.g. the
+/// `name` of a target, or a docstring), along with the range of the literal.
+fn string_value(node: &SyntaxNode) -> Option<(String, TextRange)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !is_trivia(it.kind()));
+    let (Some(token), None) = (tokens.next(), tokens.next()) else {
+        return None;
+    };
+    let range = token.text_range();
+    let value = ast::String::cast(token)?.value().ok()?;
+
+    Some((value, range))
+}
+
+/// Create a new workspace database with the given files, returning the
+/// first one along with the offset of the `$0` marker in its text.
+#[cfg(test)]
+fn fixture(files: &[(&str, &str)]) -> (crate::db::Database, SourceFile, TextSize) {
+    use crate::{workspace::FileChange, Db};
+
+    let mut db = crate::db::Database::default();
+    let offset = files[0].1.find("$0").expect("missing `$0` marker");
+    db.apply_changes(files.iter().enumerate().map(|(i, (path, text))| {
+        let text = if i == 0 {
+            text.replacen("$0", "", 1)
+        } else {
+            text.to_string()
+        };
+        FileChange::new(path.into(), Some(text))
+    }));
+    let file = db
+        .workspace()
+        .file(&db, std::path::Path::new(files[0].0))
+        .expect("the file should be in the workspace");
+
+    (db, file, TextSize::try_from(offset).unwrap())
+}
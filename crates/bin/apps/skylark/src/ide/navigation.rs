@@ -0,0 +1,138 @@
+//! **Go-to-definition**, following loaded symbols across `load` statements.
+
+#[cfg(test)]
+use super::fixture;
+use super::{definition, loaded_file, Definition};
+use crate::workspace::{parse, resolution, SourceFile};
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use rowan::{TextRange, TextSize};
+use sky_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind,
+};
+
+/// A location to **navigate** to, e.g. the name of a definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavigationTarget {
+    pub file: SourceFile,
+    pub range: TextRange,
+}
+
+/// The **definition** of the name at the given offset. The label of a `load`
+/// statement navigates to the start of the loaded file.
+pub fn goto_definition(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    offset: TextSize,
+) -> Option<NavigationTarget> {
+    let tree = parse(db, file).tree();
+    let label = tree
+        .syntax()
+        .token_at_offset(offset)
+        .find(|it| it.kind() == SyntaxKind::STRING)
+        .and_then(|it| it.parent())
+        .and_then(ast::LoadStmt::cast);
+    if let Some(stmt) = label {
+        let loaded = loaded_file(db, file, &stmt)?;
+        return Some(NavigationTarget {
+            file: loaded,
+            range: TextRange::empty(0.into()),
+        });
+    }
+
+    match definition(db, file, offset)?.1 {
+        Definition::Binding(file, id) => Some(NavigationTarget {
+            file,
+            range: resolution(db, file).binding(id).range(),
+        }),
+        Definition::Builtin(_) => None,
+    }
+}
+
+#[cfg(test)]
+fn check(files: &[(&str, &str)], expect: Expect) {
+    let (db, file, offset) = fixture(files);
+    let actual = goto_definition(&db, file, offset)
+        .map(|target| {
+            let text = target.file.text(&db).as_deref().unwrap_or_default();
+            format!(
+                "{}@{:?} {:?}\n",
+                target.file.path(&db).display(),
+                target.range,
+                &text[target.range]
+            )
+        })
+        .unwrap_or_default();
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn goto_local_definition() {
+    check(
+        &[("defs.bzl", "def f(x):\n    return x + 1\n\ny = f$0(1)\n")],
+        expect![[r#"
+            defs.bzl@4..5 "f"
+        "#]],
+    );
+    check(
+        &[("defs.bzl", "def f(x):\n    return x$0 + 1\n")],
+        expect![[r#"
+            defs.bzl@6..7 "x"
+        "#]],
+    );
+    check(&[("defs.bzl", "y = len$0([])\n")], expect![""]);
+}
+
+#[test]
+fn goto_loaded_definition() {
+    let defs = (
+        "foo/defs.bzl",
+        "_private = 1\n\ndef rule(name):\n    pass\n",
+    );
+    check(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", \"rule\")\n\nrul$0e(name = \"foo\")\n",
+            ),
+            defs,
+        ],
+        expect![[r#"
+            foo/defs.bzl@18..22 "rule"
+        "#]],
+    );
+    check(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", my_rule = \"rule\")\n\nmy_rule$0(name = \"foo\")\n",
+            ),
+            defs,
+        ],
+        expect![[r#"
+            foo/defs.bzl@18..22 "rule"
+        "#]],
+    );
+    check(
+        &[("foo/BUCK", "load(\":defs.bzl$0\", \"rule\")\n"), defs],
+        expect![[r#"
+            foo/defs.bzl@0..0 ""
+        "#]],
+    );
+
+    // Symbols which can't be resolved navigate to the `load` statement.
+    check(
+        &[
+            (
+                "foo/BUCK",
+                "load(\":defs.bzl\", \"missing\")\n\nmissing$0()\n",
+            ),
+            defs,
+        ],
+        expect![[r#"
+            foo/BUCK@18..27 "\"missing\""
+        "#]],
+    );
+}
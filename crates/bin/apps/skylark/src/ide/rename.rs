@@ -0,0 +1,406 @@
+//! **Renaming** of names, across the files of the workspace loading them.
+
+#[cfg(test)]
+use super::fixture;
+use super::{load_symbol_at, loaded_binding, loaded_file};
+use crate::workspace::{parse, resolution, SourceFile};
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use rowan::{TextRange, TextSize};
+use sky_analyzer::resolve::{BindingId, BindingKind, Resolved};
+use sky_syntax::{
+    ast::{self, make, AstNode, AstToken},
+    TextEdit,
+};
+use std::{collections::HashMap, path::PathBuf};
+use thiserror::Error;
+
+/// The **edits** of a file, ordered by position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    pub file: SourceFile,
+    pub edits: Vec<TextEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RenameError {
+    #[error("No name to rename at the cursor")]
+    NoName,
+    #[error("The builtin `{0}` can't be renamed")]
+    Builtin(String),
+    #[error("`{0}` isn't a valid name")]
+    InvalidName(String),
+    #[error("`{name}` is already defined in `{}`", file.display())]
+    Conflict { name: String, file: PathBuf },
+}
+
+/// **Renames** the name at the given offset, returning the edits of each
+/// file, ordered by path.
+///
+/// Renaming a global also renames it in the files loading it, and renaming a
+/// loaded symbol renames the global it refers to (unless it's loaded under
+/// an alias, which is renamed instead).
+pub fn rename(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    offset: TextSize,
+    new_name: &str,
+) -> Result<Vec<FileEdit>, RenameError> {
+    if !make::is_identifier(new_name) {
+        return Err(RenameError::InvalidName(new_name.to_string()));
+    }
+
+    let resolution = resolution(db, file);
+    let id = match resolution.reference_at(offset) {
+        Some(reference) => match reference.resolved {
+            Resolved::Binding(id) => id,
+            Resolved::Builtin => return Err(RenameError::Builtin(reference.name.clone())),
+            Resolved::Predeclared | Resolved::Undefined => return Err(RenameError::NoName),
+        },
+        None => resolution.binding_at(offset).ok_or(RenameError::NoName)?,
+    };
+
+    let mut renamer = Renamer {
+        db,
+        new_name,
+        edits: HashMap::new(),
+    };
+    let binding = resolution.binding(id);
+    if binding.kind != BindingKind::Load {
+        renamer.rename_binding(file, id)?;
+    } else if let Some((loaded, exported)) =
+        loaded_binding(db, file, binding.range()).filter(|_| !has_alias(db, file, binding.range()))
+    {
+        renamer.rename_binding(loaded, exported)?;
+    } else {
+        renamer.rename_local_symbol(file, id)?;
+    }
+
+    let mut edits: Vec<_> = renamer
+        .edits
+        .into_iter()
+        .map(|(file, mut edits)| {
+            edits.sort_by_key(|it| it.delete.start());
+            edits.dedup();
+            FileEdit { file, edits }
+        })
+        .collect();
+    edits.sort_by(|a, b| a.file.path(db).cmp(b.file.path(db)));
+
+    Ok(edits)
+}
+
+/// Whether the symbol of the `load` statement at the given range has an
+/// alias (e.g. `my_rule = "rule"`).
+fn has_alias(db: &dyn crate::Db, file: SourceFile, range: TextRange) -> bool {
+    load_symbol_at(parse(db, file).tree().syntax(), range).is_some_and(|it| it.alias().is_some())
+}
+
+struct Renamer<'a> {
+    db: &'a dyn crate::Db,
+    new_name: &'a str,
+    edits: HashMap<SourceFile, Vec<TextEdit>>,
+}
+
+impl Renamer<'_> {
+    fn replace(&mut self, file: SourceFile, range: TextRange, text: String) {
+        self.edits
+            .entry(file)
+            .or_default()
+            .push(TextEdit::replace(range, text));
+    }
+
+    /// Fails if another binding of the scope of the given one already has the
+    /// new name.
+    fn check_conflict(&self, file: SourceFile, id: BindingId) -> Result<(), RenameError> {
+        let resolution = resolution(self.db, file);
+        let scope = resolution.scope(resolution.binding(id).scope);
+        let conflicts = scope
+            .bindings
+            .iter()
+            .any(|&it| it != id && resolution.binding(it).name == self.new_name);
+        if conflicts {
+            return Err(RenameError::Conflict {
+                name: self.new_name.to_string(),
+                file: file.path(self.db).clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Renames the references to the given binding.
+    fn rename_references(&mut self, file: SourceFile, id: BindingId) {
+        let ranges: Vec<_> = resolution(self.db, file)
+            .references_to(id)
+            .map(|it| it.range)
+            .collect();
+        for range in ranges {
+            self.replace(file, range, self.new_name.to_string());
+        }
+    }
+
+    /// Renames the given (non-loaded) binding, along with the symbols loading
+    /// it from other files if it's exported.
+    fn rename_binding(&mut self, file: SourceFile, id: BindingId) -> Result<(), RenameError> {
+        self.check_conflict(file, id)?;
+        let resolution = resolution(self.db, file);
+        let binding = resolution.binding(id);
+        for range in &binding.definitions {
+            self.replace(file, *range, self.new_name.to_string());
+        }
+        self.rename_references(file, id);
+
+        let is_exported = binding.scope == resolution.module() && !binding.name.starts_with('_');
+        if is_exported {
+            let db = self.db;
+            for &other in db.workspace().files(db).values() {
+                if other != file && other.text(self.db).is_some() {
+                    self.rename_loads(other, file, &binding.name)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames the symbols loading the given global of `loaded` in `file`,
+    /// along with the references to those which aren't aliased.
+    fn rename_loads(
+        &mut self,
+        file: SourceFile,
+        loaded: SourceFile,
+        name: &str,
+    ) -> Result<(), RenameError> {
+        let tree = parse(self.db, file).tree();
+        for stmt in tree.syntax().descendants().filter_map(ast::LoadStmt::cast) {
+            if loaded_file(self.db, file, &stmt) != Some(loaded) {
+                continue;
+            }
+
+            for symbol in stmt.load_symbols() {
+                let Some(string) = symbol.symbol() else {
+                    continue;
+                };
+                if string.value().ok().as_deref() != Some(name) {
+                    continue;
+                }
+
+                let range = string.syntax().text_range();
+                self.replace(file, range, format!("\"{}\"", self.new_name));
+                if symbol.alias().is_none() {
+                    if let Some(id) = resolution(self.db, file).binding_at(range.start()) {
+                        self.check_conflict(file, id)?;
+                        self.rename_references(file, id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames the given loaded symbol in its file only, aliasing it (e.g.
+    /// `"rule"` becomes `new_name = "rule"`) unless it already is.
+    fn rename_local_symbol(&mut self, file: SourceFile, id: BindingId) -> Result<(), RenameError> {
+        self.check_conflict(file, id)?;
+        let range = resolution(self.db, file).binding(id).range();
+        let symbol = load_symbol_at(parse(self.db, file).tree().syntax(), range);
+        match symbol.and_then(|it| it.alias()) {
+            Some(_) => self.replace(file, range, self.new_name.to_string()),
+            None => self.replace(
+                file,
+                TextRange::empty(range.start()),
+                format!("{} = ", self.new_name),
+            ),
+        }
+        self.rename_references(file, id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn check(files: &[(&str, &str)], new_name: &str, expect: Expect) {
+    let (db, file, offset) = fixture(files);
+    let actual = match rename(&db, file, offset, new_name) {
+        Ok(edits) => edits
+            .into_iter()
+            .map(|FileEdit { file, edits }| {
+                let mut text = file.text(&db).clone().unwrap_or_default();
+                for edit in edits.iter().rev() {
+                    edit.apply(&mut text);
+                }
+                format!("// {}\n{text}", file.path(&db).display())
+            })
+            .collect(),
+        Err(error) => format!("{error}\n"),
+    };
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn rename_local() {
+    check(
+        &[(
+            "defs.bzl",
+            "def f(x):\n    y = x$0 + 1\n    return [x for x in range(y)]\n",
+        )],
+        "value",
+        expect![[r#"
+            // defs.bzl
+            def f(value):
+                y = value + 1
+                return [x for x in range(y)]
+        "#]],
+    );
+}
+
+#[test]
+fn rename_across_loads() {
+    let files = [
+        (
+            "defs.bzl",
+            "def rule$0(name):\n    pass\n\ndef macro(name):\n    rule(name = name)\n",
+        ),
+        (
+            "a/BUCK",
+            "load(\"//:defs.bzl\", \"rule\")\n\nrule(name = \"a\")\n",
+        ),
+        (
+            "b/BUCK",
+            "load(\"//:defs.bzl\", my_rule = \"rule\")\n\nmy_rule(name = \"b\")\n",
+        ),
+    ];
+    check(
+        &files,
+        "target",
+        expect![[r#"
+            // a/BUCK
+            load("//:defs.bzl", "target")
+
+            target(name = "a")
+            // b/BUCK
+            load("//:defs.bzl", my_rule = "target")
+
+            my_rule(name = "b")
+            // defs.bzl
+            def target(name):
+                pass
+
+            def macro(name):
+                target(name = name)
+        "#]],
+    );
+}
+
+#[test]
+fn rename_loaded_symbol() {
+    let defs = ("defs.bzl", "def rule(name):\n    pass\n");
+
+    // The global is renamed, along with the files loading it.
+    check(
+        &[
+            (
+                "a/BUCK",
+                "load(\"//:defs.bzl\", \"rule\")\n\nru$0le(name = \"a\")\n",
+            ),
+            defs,
+        ],
+        "target",
+        expect![[r#"
+            // a/BUCK
+            load("//:defs.bzl", "target")
+
+            target(name = "a")
+            // defs.bzl
+            def target(name):
+                pass
+        "#]],
+    );
+
+    // Aliases, and symbols which can't be resolved, are renamed locally.
+    check(
+        &[
+            (
+                "a/BUCK",
+                "load(\"//:defs.bzl\", my_rule = \"rule\")\n\nmy_rule$0(name = \"a\")\n",
+            ),
+            defs,
+        ],
+        "target",
+        expect![[r#"
+            // a/BUCK
+            load("//:defs.bzl", target = "rule")
+
+            target(name = "a")
+        "#]],
+    );
+    check(
+        &[(
+            "a/BUCK",
+            "load(\"//:missing.bzl\", \"rule\")\n\nrule$0(name = \"a\")\n",
+        )],
+        "target",
+        expect![[r#"
+            // a/BUCK
+            load("//:missing.bzl", target = "rule")
+
+            target(name = "a")
+        "#]],
+    );
+}
+
+#[test]
+fn rename_errors() {
+    let files = [("defs.bzl", "x = le$0n([])\n")];
+    check(
+        &files,
+        "size",
+        expect!["The builtin `len` can't be renamed\n"],
+    );
+    check(
+        &[("defs.bzl", "x$0 = 1\n")],
+        "for",
+        expect!["`for` isn't a valid name\n"],
+    );
+    check(
+        &[("defs.bzl", "x = 1$0\n")],
+        "y",
+        expect!["No name to rename at the cursor\n"],
+    );
+}
+
+#[test]
+fn rename_conflicts() {
+    check(
+        &[("defs.bzl", "def f(x$0, y):\n    return x + y\n")],
+        "y",
+        expect!["`y` is already defined in `defs.bzl`\n"],
+    );
+    // The name of another scope can be shadowed.
+    check(
+        &[("defs.bzl", "y = 1\n\ndef f(x$0):\n    return x\n")],
+        "y",
+        expect![[r#"
+            // defs.bzl
+            y = 1
+
+            def f(y):
+                return y
+        "#]],
+    );
+    // The globals loaded under their own name are renamed in the files loading them.
+    check(
+        &[
+            ("defs.bzl", "def rule$0(name):\n    pass\n"),
+            (
+                "a/BUCK",
+                "load(\"//:defs.bzl\", \"rule\")\n\ntarget = rule(name = \"a\")\n",
+            ),
+        ],
+        "target",
+        expect!["`target` is already defined in `a/BUCK`\n"],
+    );
+}
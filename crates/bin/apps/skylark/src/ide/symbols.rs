@@ -0,0 +1,162 @@
+//! **Document symbols**, i.e. the outline of a file: its functions (along
+//! with the functions nested in them) and its targets.
+
+#[cfg(test)]
+use super::fixture;
+use super::{is_trivia, significant_range, string_value};
+use crate::workspace::{parse, SourceFile};
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use rowan::TextRange;
+use sky_syntax::ast::{self, AstNode, AstToken};
+
+/// A **symbol** of the outline of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    /// The kind of rule of a target (e.g. `rust_library`).
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// The range of the whole symbol (e.g. the `def` statement).
+    pub full_range: TextRange,
+    /// The range of the name of the symbol.
+    pub focus_range: TextRange,
+    pub children: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Function,
+    /// A target, i.e. a top-level call with a `name` (e.g.
+    /// `rust_library(name = "foo", ...)`).
+    Target,
+}
+
+/// The **symbols** of the given file, in order.
+pub fn document_symbols(db: &dyn crate::Db, file: SourceFile) -> Vec<Symbol> {
+    let tree = parse(db, file).tree();
+    let mut symbols = functions(tree.statements());
+    symbols.extend(tree.statements().filter_map(|statement| match statement {
+        ast::Statement::SimpleStmt(stmt) => target(&stmt),
+        _ => None,
+    }));
+    symbols.sort_by_key(|it| it.full_range.start());
+
+    symbols
+}
+
+/// The functions defined by the given statements (but not within their
+/// nested blocks, other than functions).
+fn functions(statements: impl Iterator<Item = ast::Statement>) -> Vec<Symbol> {
+    statements
+        .filter_map(|statement| match statement {
+            ast::Statement::DefStmt(def) => {
+                let name = def.name()?;
+                let children = def
+                    .body()
+                    .map(|body| functions(body.statements()))
+                    .unwrap_or_default();
+
+                Some(Symbol {
+                    name: name.text().to_string(),
+                    detail: None,
+                    kind: SymbolKind::Function,
+                    full_range: significant_range(def.syntax()),
+                    focus_range: name.syntax().text_range(),
+                    children,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The target declared by the given statement, if it's a lone call with a
+/// `name`.
+fn target(stmt: &ast::SimpleStmt) -> Option<Symbol> {
+    let mut small_stmts = stmt.small_stmts();
+    let (Some(ast::SmallStmt::ExprStmt(stmt)), None) = (small_stmts.next(), small_stmts.next())
+    else {
+        return None;
+    };
+    let mut tests = stmt.expression()?.tests();
+    let (Some(ast::Test::PrimaryExpr(primary)), None) = (tests.next(), tests.next()) else {
+        return None;
+    };
+    let call = primary.call_suffix()?;
+    let (name, focus_range) = string_value(call.keyword_argument("name")?.value()?.syntax())?;
+
+    // The kind is the callee, i.e. everything up to the call's arguments
+    // (e.g. `rust_library` or `native.genrule`).
+    let kind = primary
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .take_while(|it| it.text_range().start() < call.syntax().text_range().start())
+        .filter(|it| !is_trivia(it.kind()))
+        .map(|it| it.text().to_string())
+        .collect();
+
+    Some(Symbol {
+        name,
+        detail: Some(kind),
+        kind: SymbolKind::Target,
+        full_range: significant_range(primary.syntax()),
+        focus_range,
+        children: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+fn check(text: &str, expect: Expect) {
+    fn render(symbols: &[Symbol], depth: usize, out: &mut String) {
+        for symbol in symbols {
+            let detail = symbol.detail.as_deref().unwrap_or_default();
+            out.push_str(&format!(
+                "{}{:?} {} {detail} {:?} {:?}\n",
+                "  ".repeat(depth),
+                symbol.kind,
+                symbol.name,
+                symbol.full_range,
+                symbol.focus_range,
+            ));
+            render(&symbol.children, depth + 1, out);
+        }
+    }
+
+    let (db, file, _) = fixture(&[("BUCK", &format!("$0{text}"))]);
+    let mut actual = String::new();
+    render(&document_symbols(&db, file), 0, &mut actual);
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn symbols() {
+    check(
+        r#"load(":defs.bzl", "rust_library")
+
+# A helper.
+def srcs(names):
+    def suffix(name):
+        return name + ".rs"
+
+    return [suffix(name) for name in names]
+
+rust_library(
+    name = "foo",
+    srcs = srcs(["lib"]),
+)
+
+native.genrule(name = "bar", out = "bar.txt")
+
+print("not a target")
+"#,
+        expect![[r#"
+            Function srcs  47..158 51..55
+              Function suffix  68..113 72..78
+            Target foo rust_library 160..219 185..190
+            Target bar native.genrule 221..266 243..248
+        "#]],
+    );
+}
@@ -1,7 +1,18 @@
+//! The **high-level intermediate representation** (HIR) of Starlark source,
+//! lowered from the syntax tree of `sky_syntax` by [`crate::lower`].
+//!
+//! A module is split into its top-level [`Item`]s, each lowered separately:
+//! the spans of the HIR of an item are **relative to the start of the item**,
+//! so that editing an item leaves the HIR of the other items unchanged (even
+//! though their offsets in the module may change).
+
 #![allow(clippy::needless_borrow)]
 
+use crate::workspace::SourceFile;
 use derive_new::new;
+use num_bigint::BigInt;
 use ordered_float::OrderedFloat;
+pub use sky_syntax::ast::{BinaryOp, UnaryOp};
 
 // ANCHOR: input
 #[salsa::input]
@@ -12,14 +23,9 @@ pub struct SourceProgram {
 // ANCHOR_END: input
 
 // ANCHOR: interned_ids
+/// An interned **name**, e.g. of a variable, a function or an attribute.
 #[salsa::interned]
-pub struct VariableId {
-    #[return_ref]
-    pub text: String,
-}
-
-#[salsa::interned]
-pub struct FunctionId {
+pub struct Name {
     #[return_ref]
     pub text: String,
 }
@@ -29,10 +35,34 @@ pub struct FunctionId {
 #[salsa::tracked]
 pub struct Program {
     #[return_ref]
-    pub statements: Vec<Statement>,
+    pub items: Vec<Item>,
+
+    /// The file of the workspace the program was parsed from, against which
+    /// its `load` statements are resolved, if any.
+    pub file: Option<SourceFile>,
 }
 // ANCHOR_END: program
 
+// ANCHOR: items
+/// A top-level **item** of a module, i.e. a `def` statement or any other
+/// statement.
+#[salsa::tracked]
+pub struct Item {
+    /// The name of a function, or the text of any other statement.
+    #[id]
+    pub key: String,
+
+    #[return_ref]
+    pub text: String,
+
+    /// Whether the item is a `def` statement.
+    pub is_function: bool,
+
+    /// The offset of the item in the module.
+    pub offset: usize,
+}
+// ANCHOR_END: items
+
 // ANCHOR: statements_and_expressions
 #[derive(Eq, PartialEq, Debug, Hash, new)]
 pub struct Statement {
@@ -43,10 +73,50 @@ pub struct Statement {
 
 #[derive(Eq, PartialEq, Debug, Hash)]
 pub enum StatementData {
-    /// Defines `fn <name>(<args>) = <body>`
-    Function(Function),
-    /// Defines `print <expr>`
-    Print(Expression),
+    /// `def <name>(<parameters>): <body>`
+    Def(Function),
+    /// `if <condition>: <then_branch> else: <else_branch>`, where each `elif`
+    /// clause is an `if` statement nested in the `else` branch.
+    If {
+        condition: Expression,
+        then_branch: Vec<Statement>,
+        else_branch: Vec<Statement>,
+    },
+    /// `for <target> in <iterable>: <body>`
+    For {
+        target: Expression,
+        iterable: Expression,
+        body: Vec<Statement>,
+    },
+    Return(Option<Expression>),
+    Break,
+    Continue,
+    Pass,
+    /// `<target> = <value>`, or `<target> <op>= <value>` for augmented
+    /// assignments.
+    Assign {
+        target: Expression,
+        op: Option<BinaryOp>,
+        value: Expression,
+    },
+    Expression(Expression),
+    /// `load(<module>, <symbols>)`
+    Load {
+        module: String,
+        symbols: Vec<LoadSymbol>,
+    },
+}
+
+/// A symbol of a `load` statement, e.g. `"foo"` or `bar = "foo"`.
+#[derive(Eq, PartialEq, Debug, Hash, new)]
+pub struct LoadSymbol {
+    pub span: Span,
+
+    /// The name the symbol is bound to in the module loading it.
+    pub name: Name,
+
+    /// The name of the symbol in the loaded module.
+    pub symbol: String,
 }
 
 #[derive(Eq, PartialEq, Debug, Hash, new)]
@@ -58,18 +128,82 @@ pub struct Expression {
 
 #[derive(Eq, PartialEq, Debug, Hash)]
 pub enum ExpressionData {
-    Op(Box<Expression>, Op, Box<Expression>),
-    Number(OrderedFloat<f64>),
-    Variable(VariableId),
-    Call(FunctionId, Vec<Expression>),
+    /// An expression missing from the source, because of a syntax error.
+    Missing,
+    Name(Name),
+    Literal(Literal),
+    Tuple(Vec<Expression>),
+    List(Vec<Expression>),
+    Dict(Vec<(Expression, Expression)>),
+    /// `[<element> <clauses>]`
+    ListComp(Box<Expression>, Vec<Clause>),
+    /// `{<key>: <value> <clauses>}`
+    DictComp(Box<Expression>, Box<Expression>, Vec<Clause>),
+    Unary(UnaryOp, Box<Expression>),
+    Binary(Box<Expression>, BinaryOp, Box<Expression>),
+    /// `<then_branch> if <condition> else <else_branch>`
+    If {
+        condition: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Box<Expression>,
+    },
+    /// `lambda <parameters>: <body>`
+    Lambda(Vec<Parameter>, Box<Expression>),
+    /// `<value>.<name>`
+    Dot(Box<Expression>, Name),
+    /// `<callee>(<arguments>)`
+    Call(Box<Expression>, Vec<Argument>),
+    /// `<value>[<index>]`
+    Index(Box<Expression>, Box<Expression>),
+    /// `<value>[<start>:<end>:<step>]`
+    Slice {
+        value: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+        step: Option<Box<Expression>>,
+    },
+}
+
+#[derive(Eq, PartialEq, Debug, Hash)]
+pub enum Literal {
+    Int(i64),
+    /// An int which doesn't fit in 64 bits.
+    BigInt(BigInt),
+    Float(OrderedFloat<f64>),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+/// A clause of a comprehension.
+#[derive(Eq, PartialEq, Debug, Hash)]
+pub enum Clause {
+    /// `for <target> in <iterable>`
+    For {
+        target: Expression,
+        iterable: Expression,
+    },
+    /// `if <condition>`
+    If(Expression),
+}
+
+#[derive(Eq, PartialEq, Debug, Hash, new)]
+pub struct Argument {
+    pub span: Span,
+
+    pub kind: ArgumentKind,
+
+    pub value: Expression,
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
-pub enum Op {
-    Add,
-    Subtract,
-    Multiply,
-    Divide,
+pub enum ArgumentKind {
+    Positional,
+    /// `<name> = <value>`
+    Named(Name),
+    /// `*<value>`
+    Args,
+    /// `**<value>`
+    Kwargs,
 }
 // ANCHOR_END: statements_and_expressions
 
@@ -77,19 +211,47 @@ pub enum Op {
 #[salsa::tracked]
 pub struct Function {
     #[id]
-    pub name: FunctionId,
+    pub name: Name,
+
+    pub name_span: Span,
 
-    name_span: Span,
+    #[return_ref]
+    pub parameters: Vec<Parameter>,
 
+    /// The annotated return type, if any.
     #[return_ref]
-    pub args: Vec<VariableId>,
+    pub return_type: Option<Expression>,
 
     #[return_ref]
-    pub body: Expression,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Eq, PartialEq, Debug, Hash, new)]
+pub struct Parameter {
+    pub span: Span,
+
+    /// The name of the parameter, which is `None` for a bare `*`.
+    pub name: Option<Name>,
+
+    pub kind: ParameterKind,
+
+    pub annotation: Option<Expression>,
+
+    pub default: Option<Expression>,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum ParameterKind {
+    Normal,
+    /// `*args`, or a bare `*`.
+    Args,
+    /// `**kwargs`
+    Kwargs,
 }
 // ANCHOR_END: functions
 
-#[salsa::tracked]
+/// A **span** of source text, relative to the start of its [`Item`].
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug, new)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -99,7 +261,7 @@ pub struct Span {
 #[salsa::accumulator]
 pub struct Diagnostics(Diagnostic);
 
-#[derive(new, Clone, Debug)]
+#[derive(new, Clone, Debug, PartialEq, Eq)]
 pub struct Diagnostic {
     pub start: usize,
     pub end: usize,
@@ -0,0 +1,62 @@
+//! **skylark**, an incremental analyzer of Starlark code (e.g. `BUCK` files
+//! and `.bzl` extensions), built on salsa.
+//!
+//! The [`db::Database`] holds the files of a [`workspace`] as inputs, along
+//! with the queries analyzing them, so that an edit only re-runs the queries
+//! depending on what changed. On top of it:
+//!
+//! - [`ide`] implements the features of an editor (e.g. go-to-definition,
+//!   hover and rename), in terms of offsets in the text of files.
+//! - [`lsp`] serves those features over the Language Server Protocol (see the
+//!   `skylark-lsp` binary).
+
+// ANCHOR: jar_struct
+#[salsa::jar(db = Db)]
+pub struct Jar(
+    crate::compile::compile,
+    crate::compile::check_control_flow,
+    crate::ir::SourceProgram,
+    crate::ir::Name,
+    crate::ir::Program,
+    crate::ir::Item,
+    crate::ir::Function,
+    crate::ir::Diagnostics,
+    crate::lower::parse_program,
+    crate::lower::parse_item,
+    crate::lower::lower_item,
+    crate::type_check::item_globals,
+    crate::type_check::find_global,
+    crate::type_check::item_signature,
+    crate::type_check::item_types,
+    crate::type_check::global_type,
+    crate::type_check::type_check_item,
+    crate::type_check::type_check_program,
+    crate::workspace::SourceFile,
+    crate::workspace::Workspace,
+    crate::workspace::LabelId,
+    crate::workspace::parse,
+    crate::workspace::resolution,
+    crate::workspace::module_exports,
+    crate::workspace::resolve_load,
+    crate::workspace::load_module,
+    crate::workspace::file_program,
+    crate::workspace::diagnostics,
+    crate::lsp::line_index::line_index,
+);
+// ANCHOR_END: jar_struct
+
+// ANCHOR: jar_db
+pub trait Db: salsa::DbWithJar<Jar> {
+    /// The **workspace**, holding the files by path.
+    fn workspace(&self) -> workspace::Workspace;
+}
+// ANCHOR_END: jar_db
+
+pub mod compile;
+pub mod db;
+pub mod ide;
+pub mod ir;
+pub mod lower;
+pub mod lsp;
+pub mod type_check;
+pub mod workspace;
@@ -0,0 +1,764 @@
+//! **Lowering** of the syntax tree of `sky_syntax` into the HIR of
+//! [`crate::ir`].
+//!
+//! The syntax tree is lossless (it keeps the trivia, e.g. comments, and
+//! represents syntax errors as missing nodes), while the HIR only keeps the
+//! structure of the code: missing expressions are lowered to
+//! [`ExpressionData::Missing`], and the spans only cover the significant
+//! tokens of each node.
+
+use crate::ir::{
+    Argument, ArgumentKind, Clause, Diagnostic, Diagnostics, Expression, ExpressionData, Function,
+    Item, Literal, LoadSymbol, Name, Parameter, ParameterKind, Program, SourceProgram, Span,
+    Statement, StatementData,
+};
+use ordered_float::OrderedFloat;
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode, AstToken, BinaryOp},
+    File, Parse, SyntaxKind, SyntaxNode,
+};
+
+// ANCHOR: parse_program
+/// Parses the given source program into its top-level **items**, reporting
+/// its syntax errors.
+#[salsa::tracked]
+pub fn parse_program(db: &dyn crate::Db, source: SourceProgram) -> Program {
+    let parse = File::parse(source.text(db));
+    for error in parse.errors() {
+        let range = error.range();
+        Diagnostics::push(
+            db,
+            Diagnostic::new(range.start().into(), range.end().into(), error.to_string()),
+        );
+    }
+
+    Program::new(db, items(db, &parse.tree()), None)
+}
+// ANCHOR_END: parse_program
+
+/// Splits the given syntax tree into its top-level **items**.
+pub(crate) fn items(db: &dyn crate::Db, tree: &File) -> Vec<Item> {
+    tree.statements()
+        .map(|statement| {
+            let text = statement.syntax().to_string();
+            let name = match &statement {
+                ast::Statement::DefStmt(def) => def.name().map(|it| it.text().to_string()),
+                _ => None,
+            };
+            let offset = usize::from(statement.syntax().text_range().start());
+
+            Item::new(
+                db,
+                name.clone().unwrap_or_else(|| text.clone()),
+                text,
+                name.is_some(),
+                offset,
+            )
+        })
+        .collect()
+}
+
+/// The **syntax tree** of the given item, parsed on its own (so that its
+/// ranges are relative to the start of the item) once for all of its
+/// queries.
+#[salsa::tracked(return_ref)]
+pub fn parse_item(db: &dyn crate::Db, item: Item) -> Parse<File> {
+    File::parse(item.text(db))
+}
+
+// ANCHOR: lower_item
+/// Lowers the given item into **HIR statements**, with spans relative to
+/// the start of the item. A line of simple statements (e.g. `x = 1; y = 2`)
+/// is lowered to one statement for each of them.
+#[salsa::tracked(return_ref)]
+pub fn lower_item(db: &dyn crate::Db, item: Item) -> Vec<Statement> {
+    let file = parse_item(db, item).tree();
+    let lower = Lower { db };
+
+    file.statements()
+        .flat_map(|statement| lower.statement(&statement))
+        .collect()
+}
+// ANCHOR_END: lower_item
+
+struct Lower<'db> {
+    db: &'db dyn crate::Db,
+}
+
+impl Lower<'_> {
+    fn name(&self, text: &str) -> Name {
+        Name::new(self.db, text.to_string())
+    }
+
+    fn suite(&self, suite: Option<ast::Suite>) -> Vec<Statement> {
+        suite
+            .iter()
+            .flat_map(|it| it.statements())
+            .flat_map(|statement| self.statement(&statement))
+            .collect()
+    }
+
+    fn statement(&self, statement: &ast::Statement) -> Vec<Statement> {
+        let span = span(statement.syntax());
+        let data = match statement {
+            ast::Statement::DefStmt(def) => StatementData::Def(self.def(def)),
+            ast::Statement::IfStmt(stmt) => {
+                // Each `elif` clause is an `if` statement in the `else` branch
+                // of the previous one.
+                let mut else_branch = self.suite(stmt.else_clause().and_then(|it| it.body()));
+                let clauses: Vec<_> = stmt.elif_clauses().collect();
+                for clause in clauses.iter().rev() {
+                    let data = StatementData::If {
+                        condition: self.test(clause.condition()),
+                        then_branch: self.suite(clause.body()),
+                        else_branch,
+                    };
+                    else_branch = vec![Statement::new(span_after(clause.syntax(), span), data)];
+                }
+
+                StatementData::If {
+                    condition: self.test(stmt.condition()),
+                    then_branch: self.suite(stmt.body()),
+                    else_branch,
+                }
+            }
+            ast::Statement::ForStmt(stmt) => StatementData::For {
+                target: self.loop_variables(stmt.loop_variables()),
+                iterable: self.expression(stmt.iterable()),
+                body: self.suite(stmt.body()),
+            },
+            ast::Statement::SimpleStmt(stmt) => {
+                return stmt
+                    .small_stmts()
+                    .map(|small| self.small_statement(&small))
+                    .collect();
+            }
+        };
+
+        vec![Statement::new(span, data)]
+    }
+
+    fn small_statement(&self, statement: &ast::SmallStmt) -> Statement {
+        let data = match statement {
+            ast::SmallStmt::ReturnStmt(stmt) => {
+                StatementData::Return(stmt.expression().map(|it| self.expression(Some(it))))
+            }
+            ast::SmallStmt::BreakStmt(_) => StatementData::Break,
+            ast::SmallStmt::ContinueStmt(_) => StatementData::Continue,
+            ast::SmallStmt::PassStmt(_) => StatementData::Pass,
+            ast::SmallStmt::AssignStmt(stmt) => StatementData::Assign {
+                target: self.expression(stmt.lhs()),
+                op: assignment_op(stmt),
+                value: self.expression(stmt.rhs()),
+            },
+            ast::SmallStmt::ExprStmt(stmt) => {
+                StatementData::Expression(self.expression(stmt.expression()))
+            }
+            ast::SmallStmt::LoadStmt(stmt) => StatementData::Load {
+                module: stmt
+                    .module()
+                    .and_then(|it| it.value().ok())
+                    .unwrap_or_default(),
+                symbols: stmt
+                    .load_symbols()
+                    .filter_map(|symbol| {
+                        let value = symbol.symbol()?.value().ok()?;
+                        let name = match symbol.alias() {
+                            Some(alias) => alias.text().to_string(),
+                            None => value.clone(),
+                        };
+                        Some(LoadSymbol::new(
+                            span(symbol.syntax()),
+                            self.name(&name),
+                            value,
+                        ))
+                    })
+                    .collect(),
+            },
+        };
+
+        Statement::new(span(statement.syntax()), data)
+    }
+
+    fn def(&self, def: &ast::DefStmt) -> Function {
+        let (name, name_span) = match def.name() {
+            Some(name) => (
+                name.text().to_string(),
+                range_span(name.syntax().text_range()),
+            ),
+            None => (String::new(), span(def.syntax())),
+        };
+
+        Function::new(
+            self.db,
+            self.name(&name),
+            name_span,
+            self.parameters(def.parameters()),
+            def.return_type().map(|it| self.test(it.test())),
+            self.suite(def.body()),
+        )
+    }
+
+    fn parameters(&self, parameters: Option<ast::Parameters>) -> Vec<Parameter> {
+        parameters
+            .iter()
+            .flat_map(|it| it.parameters())
+            .map(|parameter| {
+                let kind = if parameter.starstar_token().is_some() {
+                    ParameterKind::Kwargs
+                } else if parameter.star_token().is_some() {
+                    ParameterKind::Args
+                } else {
+                    ParameterKind::Normal
+                };
+
+                Parameter::new(
+                    span(parameter.syntax()),
+                    parameter.name().map(|it| self.name(it.text())),
+                    kind,
+                    parameter.annotation().map(|it| self.test(it.test())),
+                    parameter.default().map(|it| self.test(Some(it))),
+                )
+            })
+            .collect()
+    }
+
+    /// Lowers an expression, which is a **tuple** if it has several elements
+    /// (or a trailing comma), e.g. `1, 2`.
+    fn expression(&self, expression: Option<ast::Expression>) -> Expression {
+        let Some(expression) = expression else {
+            return missing();
+        };
+        let tests: Vec<_> = expression.tests().collect();
+        if tests.len() == 1 && !has_comma(expression.syntax()) {
+            return self.test(tests.into_iter().next());
+        }
+
+        Expression::new(
+            span(expression.syntax()),
+            ExpressionData::Tuple(tests.into_iter().map(|it| self.test(Some(it))).collect()),
+        )
+    }
+
+    /// Lowers the loop variables of a `for` statement or clause, which are a
+    /// **tuple** if there are several of them, e.g. `k, v`.
+    fn loop_variables(&self, variables: Option<ast::LoopVariables>) -> Expression {
+        let Some(variables) = variables else {
+            return missing();
+        };
+        let targets: Vec<_> = variables.primary_exprs().collect();
+        if targets.len() == 1 && !has_comma(variables.syntax()) {
+            let target = targets.into_iter().next().map(ast::Test::PrimaryExpr);
+            return self.test(target);
+        }
+
+        Expression::new(
+            span(variables.syntax()),
+            ExpressionData::Tuple(
+                targets
+                    .into_iter()
+                    .map(|it| self.test(Some(ast::Test::PrimaryExpr(it))))
+                    .collect(),
+            ),
+        )
+    }
+
+    fn test(&self, test: Option<ast::Test>) -> Expression {
+        let Some(test) = test else {
+            return missing();
+        };
+        let data = match &test {
+            ast::Test::IfExpr(expr) => ExpressionData::If {
+                condition: Box::new(self.test(expr.condition())),
+                then_branch: Box::new(self.test(expr.then_branch())),
+                else_branch: Box::new(self.test(expr.else_branch())),
+            },
+            ast::Test::PrimaryExpr(expr) => return self.primary(expr),
+            ast::Test::UnaryExpr(expr) => match expr.op_kind() {
+                Some(op) => ExpressionData::Unary(op, Box::new(self.test(expr.operand()))),
+                None => ExpressionData::Missing,
+            },
+            ast::Test::BinaryExpr(expr) => match expr.op_kind() {
+                Some(op) => ExpressionData::Binary(
+                    Box::new(self.test(expr.lhs())),
+                    op,
+                    Box::new(self.test(expr.rhs())),
+                ),
+                None => ExpressionData::Missing,
+            },
+            ast::Test::LambdaExpr(expr) => ExpressionData::Lambda(
+                self.parameters(expr.parameters()),
+                Box::new(self.test(expr.body())),
+            ),
+        };
+
+        Expression::new(span(test.syntax()), data)
+    }
+
+    /// Lowers an operand followed by its **suffixes** (e.g. `.foo`, `(1)` or
+    /// `[0]`), each applying to the expression before it.
+    fn primary(&self, expr: &ast::PrimaryExpr) -> Expression {
+        let mut children = expr.syntax().children();
+        let mut value = match children.next().and_then(ast::Operand::cast) {
+            Some(operand) => self.operand(&operand),
+            None => missing(),
+        };
+
+        for suffix in children {
+            let span = Span::new(value.span.start, span(&suffix).end);
+            let value_box = Box::new(value);
+            let data = if let Some(dot) = ast::DotSuffix::cast(suffix.clone()) {
+                match dot.name() {
+                    Some(name) => ExpressionData::Dot(value_box, self.name(name.text())),
+                    None => ExpressionData::Missing,
+                }
+            } else if let Some(call) = ast::CallSuffix::cast(suffix.clone()) {
+                ExpressionData::Call(value_box, self.arguments(call.arguments()))
+            } else if let Some(slice) = ast::SliceSuffix::cast(suffix) {
+                match slice.index() {
+                    Some(index) => {
+                        ExpressionData::Index(value_box, Box::new(self.expression(Some(index))))
+                    }
+                    None => ExpressionData::Slice {
+                        value: value_box,
+                        start: slice.start().map(|it| Box::new(self.expression(Some(it)))),
+                        end: slice.end().map(|it| Box::new(self.test(Some(it)))),
+                        step: slice.step().map(|it| Box::new(self.test(Some(it)))),
+                    },
+                }
+            } else {
+                ExpressionData::Missing
+            };
+            value = Expression::new(span, data);
+        }
+
+        value
+    }
+
+    fn arguments(&self, arguments: Option<ast::Arguments>) -> Vec<Argument> {
+        arguments
+            .iter()
+            .flat_map(|it| it.arguments())
+            .map(|argument| {
+                let kind = if argument.starstar_token().is_some() {
+                    ArgumentKind::Kwargs
+                } else if argument.star_token().is_some() {
+                    ArgumentKind::Args
+                } else if let Some(name) = argument.name() {
+                    ArgumentKind::Named(self.name(name.text()))
+                } else {
+                    ArgumentKind::Positional
+                };
+
+                Argument::new(span(argument.syntax()), kind, self.test(argument.value()))
+            })
+            .collect()
+    }
+
+    fn operand(&self, operand: &ast::Operand) -> Expression {
+        let span = span(operand.syntax());
+        let data = if let Some(name) = operand.identifier_token() {
+            ExpressionData::Name(self.name(name.text()))
+        } else if let Some(list) = operand.list_expr() {
+            ExpressionData::List(
+                list.expression()
+                    .iter()
+                    .flat_map(|it| it.tests())
+                    .map(|it| self.test(Some(it)))
+                    .collect(),
+            )
+        } else if let Some(dict) = operand.dict_expr() {
+            ExpressionData::Dict(
+                dict.entries()
+                    .iter()
+                    .flat_map(|it| it.entries())
+                    .map(|entry| (self.test(entry.key()), self.test(entry.value())))
+                    .collect(),
+            )
+        } else if let Some(comp) = operand.list_comp() {
+            ExpressionData::ListComp(
+                Box::new(self.test(comp.element())),
+                self.clauses(comp.comp_clauses()),
+            )
+        } else if let Some(comp) = operand.dict_comp() {
+            let entry = comp.entry();
+            ExpressionData::DictComp(
+                Box::new(self.test(entry.as_ref().and_then(|it| it.key()))),
+                Box::new(self.test(entry.as_ref().and_then(|it| it.value()))),
+                self.clauses(comp.comp_clauses()),
+            )
+        } else if operand.l_paren_token().is_some() {
+            match operand.expression() {
+                // A trailing comma belongs to the parentheses, e.g. `(1,)`.
+                Some(expression) if has_comma(operand.syntax()) => ExpressionData::Tuple(
+                    expression.tests().map(|it| self.test(Some(it))).collect(),
+                ),
+                Some(expression) => return self.expression(Some(expression)),
+                None => ExpressionData::Tuple(Vec::new()),
+            }
+        } else {
+            literal(operand.syntax())
+                .map(ExpressionData::Literal)
+                .unwrap_or(ExpressionData::Missing)
+        };
+
+        Expression::new(span, data)
+    }
+
+    fn clauses(&self, clauses: ast::AstChildren<ast::CompClause>) -> Vec<Clause> {
+        clauses
+            .map(|clause| match clause.for_token() {
+                Some(_) => Clause::For {
+                    target: self.loop_variables(clause.loop_variables()),
+                    iterable: self.test(clause.iterable()),
+                },
+                None => Clause::If(self.test(clause.condition())),
+            })
+            .collect()
+    }
+}
+
+/// The **literal** of an operand, e.g. `1` or `"foo"`, if valid.
+fn literal(operand: &SyntaxNode) -> Option<Literal> {
+    let token = operand
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .find(|it| {
+            matches!(
+                it.kind(),
+                SyntaxKind::INT | SyntaxKind::FLOAT | SyntaxKind::STRING | SyntaxKind::BYTES
+            )
+        })?;
+
+    let literal = match token.kind() {
+        SyntaxKind::INT => {
+            let int = ast::Int::cast(token)?;
+            match int.value() {
+                Ok(value) => Literal::Int(value),
+                Err(_) => Literal::BigInt(int.big_value().ok()?),
+            }
+        }
+        SyntaxKind::FLOAT => Literal::Float(OrderedFloat(ast::Float::cast(token)?.value().ok()?)),
+        SyntaxKind::STRING => Literal::String(ast::String::cast(token)?.value().ok()?),
+        _ => Literal::Bytes(ast::Bytes::cast(token)?.value().ok()?),
+    };
+
+    Some(literal)
+}
+
+/// The operator of an **augmented assignment** (e.g. `+` for `x += 1`), or
+/// `None` for a plain assignment.
+fn assignment_op(stmt: &ast::AssignStmt) -> Option<BinaryOp> {
+    let op = if stmt.pluseq_token().is_some() {
+        BinaryOp::Add
+    } else if stmt.minuseq_token().is_some() {
+        BinaryOp::Sub
+    } else if stmt.stareq_token().is_some() {
+        BinaryOp::Mul
+    } else if stmt.slasheq_token().is_some() {
+        BinaryOp::Div
+    } else if stmt.slashslasheq_token().is_some() {
+        BinaryOp::FloorDiv
+    } else if stmt.percenteq_token().is_some() {
+        BinaryOp::Rem
+    } else if stmt.ampeq_token().is_some() {
+        BinaryOp::BitAnd
+    } else if stmt.pipeeq_token().is_some() {
+        BinaryOp::BitOr
+    } else if stmt.careteq_token().is_some() {
+        BinaryOp::BitXor
+    } else if stmt.shl_eq_token().is_some() {
+        BinaryOp::Shl
+    } else if stmt.shr_eq_token().is_some() {
+        BinaryOp::Shr
+    } else {
+        return None;
+    };
+
+    Some(op)
+}
+
+fn missing() -> Expression {
+    Expression::new(Span::new(0, 0), ExpressionData::Missing)
+}
+
+fn has_comma(node: &SyntaxNode) -> bool {
+    node.children_with_tokens()
+        .any(|it| it.kind() == SyntaxKind::COMMA)
+}
+
+/// The span of the given node without its surrounding trivia (e.g. the
+/// leading whitespace, which is part of the node).
+fn span(node: &SyntaxNode) -> Span {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| {
+            !matches!(
+                it.kind(),
+                SyntaxKind::WHITESPACE
+                    | SyntaxKind::COMMENT
+                    | SyntaxKind::NEWLINE
+                    | SyntaxKind::INDENT
+                    | SyntaxKind::OUTDENT
+            )
+        });
+    let Some(first) = tokens.next() else {
+        return range_span(TextRange::empty(node.text_range().start()));
+    };
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    range_span(TextRange::new(
+        first.text_range().start(),
+        last.text_range().end(),
+    ))
+}
+
+/// The span from the start of the given node to the end of the given span,
+/// e.g. from an `elif` clause to the end of its `if` statement.
+fn span_after(node: &SyntaxNode, end: Span) -> Span {
+    Span::new(span(node).start, end.end)
+}
+
+fn range_span(range: TextRange) -> Span {
+    Span::new(range.start().into(), range.end().into())
+}
+
+/// Create a new database with the given source text, and lower the item
+/// with the given index.
+#[cfg(test)]
+fn lower_string(
+    source_text: &str,
+    index: usize,
+    check: impl FnOnce(&crate::db::Database, &[Statement]),
+) {
+    let db = crate::db::Database::default();
+    let source_program = SourceProgram::new(&db, source_text.to_string());
+    let item = parse_program(&db, source_program).items(&db)[index];
+
+    check(&db, lower_item(&db, item));
+}
+
+#[test]
+fn lower_def() {
+    lower_string(
+        "x = 1\n\ndef f(a: int, *args, b = 2, **kwargs) -> str:\n    return a\n",
+        1,
+        |db, statements| {
+            let StatementData::Def(function) = &statements[0].data else {
+                panic!("Expected a `def` statement, got {statements:?}");
+            };
+            assert_eq!(function.name(db).text(db), "f");
+            // The item starts with the newline before `def`.
+            assert_eq!(function.name_span(db), Span::new(5, 6));
+
+            let parameters: Vec<_> = function
+                .parameters(db)
+                .iter()
+                .map(|it| {
+                    (
+                        it.name.map(|it| it.text(db).clone()),
+                        it.kind,
+                        it.annotation.is_some(),
+                        it.default.is_some(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                parameters,
+                vec![
+                    (Some("a".to_string()), ParameterKind::Normal, true, false),
+                    (Some("args".to_string()), ParameterKind::Args, false, false),
+                    (Some("b".to_string()), ParameterKind::Normal, false, true),
+                    (
+                        Some("kwargs".to_string()),
+                        ParameterKind::Kwargs,
+                        false,
+                        false
+                    ),
+                ]
+            );
+            assert!(function.return_type(db).is_some());
+            assert!(matches!(
+                &function.body(db)[..],
+                [Statement {
+                    span: Span { start: 51, end: 59 },
+                    data: StatementData::Return(Some(Expression {
+                        data: ExpressionData::Name(_),
+                        ..
+                    })),
+                }]
+            ));
+        },
+    );
+}
+
+#[test]
+fn lower_elif() {
+    lower_string(
+        "if a:\n    pass\nelif b:\n    break\nelse:\n    continue\n",
+        0,
+        |_, statements| {
+            let [Statement {
+                data: StatementData::If { else_branch, .. },
+                ..
+            }] = statements
+            else {
+                panic!("Expected an `if` statement, got {statements:?}");
+            };
+            assert!(matches!(
+                &else_branch[..],
+                [Statement {
+                    span: Span { start: 15, end: 51 },
+                    data: StatementData::If { then_branch, else_branch, .. },
+                }] if matches!(then_branch[..], [Statement { data: StatementData::Break, .. }])
+                    && matches!(else_branch[..], [Statement { data: StatementData::Continue, .. }])
+            ));
+        },
+    );
+}
+
+#[test]
+fn lower_simple_statements() {
+    lower_string(
+        "load(\":defs.bzl\", \"a\", b = \"c\"); x, y += 1, 2\n",
+        0,
+        |db, statements| {
+            let [load, assign] = statements else {
+                panic!("Expected 2 statements, got {statements:?}");
+            };
+
+            let StatementData::Load { module, symbols } = &load.data else {
+                panic!("Expected a `load` statement, got {load:?}");
+            };
+            assert_eq!(module, ":defs.bzl");
+            let symbols: Vec<_> = symbols
+                .iter()
+                .map(|it| (it.name.text(db).as_str(), it.symbol.as_str()))
+                .collect();
+            assert_eq!(symbols, vec![("a", "a"), ("b", "c")]);
+
+            assert!(matches!(
+                &assign.data,
+                StatementData::Assign {
+                    target: Expression { data: ExpressionData::Tuple(targets), .. },
+                    op: Some(BinaryOp::Add),
+                    value: Expression { span: Span { start: 41, end: 45 }, data: ExpressionData::Tuple(values) },
+                } if targets.len() == 2 && values.len() == 2
+            ));
+        },
+    );
+}
+
+#[test]
+fn lower_expressions() {
+    lower_string(
+        "x = [f(i, *a, k = 1) for i, _ in d.items() if i][1:]\n",
+        0,
+        |db, statements| {
+            let [Statement {
+                data: StatementData::Assign { value, .. },
+                ..
+            }] = statements
+            else {
+                panic!("Expected an assignment, got {statements:?}");
+            };
+
+            let ExpressionData::Slice {
+                value: comprehension,
+                start: Some(_),
+                end: None,
+                step: None,
+            } = &value.data
+            else {
+                panic!("Expected a slice, got {value:?}");
+            };
+            assert_eq!(value.span, Span::new(4, 52));
+
+            let ExpressionData::ListComp(element, clauses) = &comprehension.data else {
+                panic!("Expected a list comprehension, got {comprehension:?}");
+            };
+            let ExpressionData::Call(callee, arguments) = &element.data else {
+                panic!("Expected a call, got {element:?}");
+            };
+            assert!(matches!(callee.data, ExpressionData::Name(name) if name.text(db) == "f"));
+            let kinds: Vec<_> = arguments.iter().map(|it| it.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    ArgumentKind::Positional,
+                    ArgumentKind::Args,
+                    ArgumentKind::Named(Name::new(db, "k".to_string())),
+                ]
+            );
+
+            assert!(matches!(
+                &clauses[..],
+                [
+                    Clause::For {
+                        target: Expression {
+                            data: ExpressionData::Tuple(_),
+                            ..
+                        },
+                        iterable: Expression {
+                            data: ExpressionData::Call(..),
+                            ..
+                        },
+                    },
+                    Clause::If(_),
+                ]
+            ));
+        },
+    );
+}
+
+#[test]
+fn lower_big_int_literal() {
+    lower_string("x = 9223372036854775808\n", 0, |_, statements| {
+        let [Statement {
+            data: StatementData::Assign { value, .. },
+            ..
+        }] = statements
+        else {
+            panic!("Expected an assignment, got {statements:?}");
+        };
+        let ExpressionData::Literal(Literal::BigInt(int)) = &value.data else {
+            panic!("Expected a big int literal, got {value:?}");
+        };
+        assert_eq!(int.to_string(), "9223372036854775808");
+    });
+}
+
+#[test]
+fn lower_only_edited_items() {
+    use crate::db::Database;
+
+    let mut db = Database::default().enable_logging();
+    let source_program = SourceProgram::new(
+        &db,
+        "def f():\n    return 1\n\ndef g():\n    return 2\n".to_string(),
+    );
+    let lower_all = |db: &Database| {
+        parse_program(db, source_program)
+            .items(db)
+            .iter()
+            .map(|item| lower_item(db, *item).len())
+            .sum::<usize>()
+    };
+    assert_eq!(lower_all(&db), 2);
+    db.take_logs();
+
+    // `g` moves, but its HIR is unchanged since its spans are relative to it.
+    source_program
+        .set_text(&mut db)
+        .to("def f():\n    x = 1\n    return x\n\ndef g():\n    return 2\n".to_string());
+    assert_eq!(lower_all(&db), 2);
+
+    let lowered = db
+        .take_logs()
+        .iter()
+        .filter(|it| it.contains("lower_item"))
+        .count();
+    assert_eq!(lowered, 1);
+}
@@ -0,0 +1,164 @@
+//! **Line index** of a file, converting between offsets in its text and the
+//! positions of the Language Server Protocol, i.e. lines and UTF-16 columns.
+
+#[cfg(test)]
+use expect_test::{expect, Expect};
+use lsp_types::{Position, Range};
+use rowan::{TextRange, TextSize};
+
+use crate::workspace::SourceFile;
+
+/// The **line index** of a text, i.e. where its lines start, along with the
+/// characters which aren't a single UTF-16 code unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The offset each line starts at.
+    line_starts: Vec<TextSize>,
+    /// The characters of each line (by index) whose lengths differ in UTF-8
+    /// and UTF-16, i.e. their offset in the line and their UTF-8 length.
+    wide_chars: Vec<Vec<(TextSize, TextSize)>>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> LineIndex {
+        let mut line_starts = vec![TextSize::from(0)];
+        let mut wide_chars = vec![Vec::new()];
+        let mut line_start = TextSize::from(0);
+
+        for (offset, c) in text.char_indices() {
+            let offset = TextSize::try_from(offset).unwrap();
+            if c == '\n' {
+                line_start = offset + TextSize::of(c);
+                line_starts.push(line_start);
+                wide_chars.push(Vec::new());
+            } else if !c.is_ascii() {
+                wide_chars
+                    .last_mut()
+                    .unwrap()
+                    .push((offset - line_start, TextSize::of(c)));
+            }
+        }
+
+        LineIndex {
+            line_starts,
+            wide_chars,
+        }
+    }
+
+    /// The **position** of the given offset.
+    pub fn position(&self, offset: TextSize) -> Position {
+        let line = self.line_starts.partition_point(|it| *it <= offset) - 1;
+        let column = offset - self.line_starts[line];
+
+        // Each character is one or two UTF-16 code units, depending on
+        // whether it's outside of the Basic Multilingual Plane (4 bytes).
+        let mut utf16_column = u32::from(column);
+        for (start, len) in &self.wide_chars[line] {
+            if *start >= column {
+                break;
+            }
+            let utf16_len = if u32::from(*len) == 4 { 2 } else { 1 };
+            utf16_column = utf16_column - u32::from(*len) + utf16_len;
+        }
+
+        Position::new(line as u32, utf16_column)
+    }
+
+    /// The **offset** of the given position, clamped to its line (or to the
+    /// end of the text).
+    pub fn offset(&self, position: Position, text: &str) -> TextSize {
+        let Some(start) = self.line_starts.get(position.line as usize) else {
+            return TextSize::of(text);
+        };
+        let end = self
+            .line_starts
+            .get(position.line as usize + 1)
+            .copied()
+            .unwrap_or_else(|| TextSize::of(text));
+
+        let mut utf16_column = 0;
+        for (offset, c) in text[TextRange::new(*start, end)].char_indices() {
+            if utf16_column >= position.character || c == '\n' {
+                return *start + TextSize::try_from(offset).unwrap();
+            }
+            utf16_column += c.len_utf16() as u32;
+        }
+
+        end
+    }
+
+    pub fn range(&self, range: TextRange) -> Range {
+        Range::new(self.position(range.start()), self.position(range.end()))
+    }
+
+    pub fn text_range(&self, range: Range, text: &str) -> TextRange {
+        TextRange::new(self.offset(range.start, text), self.offset(range.end, text))
+    }
+}
+
+/// The **line index** of the given file.
+#[salsa::tracked(return_ref)]
+pub fn line_index(db: &dyn crate::Db, file: SourceFile) -> LineIndex {
+    LineIndex::new(file.text(db).as_deref().unwrap_or_default())
+}
+
+#[cfg(test)]
+fn check(text: &str, expect: Expect) {
+    let index = LineIndex::new(text);
+    let mut actual = String::new();
+    for (offset, _) in text.char_indices().chain([(text.len(), ' ')]) {
+        let offset = TextSize::try_from(offset).unwrap();
+        let position = index.position(offset);
+        assert_eq!(index.offset(position, text), offset);
+        actual.push_str(&format!(
+            "{offset:?} {}:{}\n",
+            position.line, position.character
+        ));
+    }
+
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn positions() {
+    check(
+        "x = 1\ny\n",
+        expect![[r#"
+            0 0:0
+            1 0:1
+            2 0:2
+            3 0:3
+            4 0:4
+            5 0:5
+            6 1:0
+            7 1:1
+            8 2:0
+        "#]],
+    );
+    check(
+        "s = \"é😀\"\nt",
+        expect![[r#"
+            0 0:0
+            1 0:1
+            2 0:2
+            3 0:3
+            4 0:4
+            5 0:5
+            7 0:6
+            11 0:8
+            12 0:9
+            13 1:0
+            14 1:1
+        "#]],
+    );
+}
+
+#[test]
+fn clamped_positions() {
+    let text = "x = 1\ny\n";
+    let index = LineIndex::new(text);
+
+    assert_eq!(index.offset(Position::new(0, 42), text), TextSize::from(5));
+    assert_eq!(index.offset(Position::new(1, 42), text), TextSize::from(7));
+    assert_eq!(index.offset(Position::new(42, 0), text), TextSize::from(8));
+}
@@ -0,0 +1,656 @@
+//! A **language server** for Starlark, speaking the Language Server Protocol
+//! over stdio (see the `skylark-lsp` binary).
+//!
+//! The server loads the Starlark files below the root of the workspace into
+//! a [`Database`], then keeps the files open in the editor in sync with it:
+//! each change is applied as an edit, so that the file is reparsed
+//! incrementally and only the queries depending on it are re-run. The
+//! requests are answered by the features of [`crate::ide`], converting
+//! between offsets and LSP positions with the [`line_index`] of each file,
+//! except for semantic tokens which come straight from
+//! [`sky_analyzer::highlight`].
+
+use crate::{
+    db::Database,
+    ide,
+    workspace::{self, FileChange, SourceFile},
+    Db,
+};
+use line_index::{line_index, LineIndex};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, LogMessage,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, Formatting, GotoDefinition, HoverRequest, Rename,
+        Request as _, SemanticTokensFullRequest,
+    },
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, DocumentFormattingParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, HoverProviderCapability, InitializeParams, InitializeResult, Location,
+    LogMessageParams, MarkupContent, MarkupKind, MessageType, OneOf, PublishDiagnosticsParams,
+    RenameParams, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams,
+    SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+    WorkspaceEdit,
+};
+use rowan::{TextRange, TextSize};
+use sky_analyzer::highlight::{self, HighlightOptions, TOKEN_MODIFIERS, TOKEN_TYPES};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+};
+use thiserror::Error;
+
+pub mod line_index;
+
+/// An **error** handling a request.
+#[derive(Debug, Error)]
+enum ServerError {
+    #[error("`{0}` isn't a file")]
+    NotAFile(Url),
+    #[error("`{0}` isn't part of the workspace")]
+    UnknownFile(Url),
+    #[error(transparent)]
+    Rename(#[from] ide::RenameError),
+}
+
+/// **Runs** the language server over stdio, until the client shuts it down.
+pub fn run_stdio() -> anyhow::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    run(connection)?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// **Runs** the language server over the given connection, until the client
+/// shuts it down.
+pub fn run(connection: Connection) -> anyhow::Result<()> {
+    let (id, params) = connection.initialize_start()?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    let result = InitializeResult {
+        capabilities: capabilities(),
+        server_info: Some(ServerInfo {
+            name: "skylark-lsp".to_string(),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        }),
+    };
+    connection.initialize_finish(id, serde_json::to_value(result)?)?;
+
+    let mut server = Server::new(connection, root(&params));
+    server.load_workspace();
+    server.main_loop()
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            // The symbols of `load` statements are strings.
+            trigger_characters: Some(vec!["\"".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: SemanticTokensLegend {
+                    token_types: TOKEN_TYPES.map(SemanticTokenType::new).to_vec(),
+                    token_modifiers: TOKEN_MODIFIERS.map(SemanticTokenModifier::new).to_vec(),
+                },
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..SemanticTokensOptions::default()
+            },
+        )),
+        ..ServerCapabilities::default()
+    }
+}
+
+/// The **root** of the workspace, i.e. its first folder. Without one, paths
+/// are kept absolute.
+#[allow(deprecated)]
+fn root(params: &InitializeParams) -> PathBuf {
+    let folder = params
+        .workspace_folders
+        .iter()
+        .flatten()
+        .map(|it| &it.uri)
+        .next();
+    folder
+        .or(params.root_uri.as_ref())
+        .and_then(|it| it.to_file_path().ok())
+        .unwrap_or_default()
+}
+
+/// The parameters of the given notification of type `N`.
+fn extract<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> anyhow::Result<N::Params> {
+    Ok(notification.extract(N::METHOD)?)
+}
+
+struct Server {
+    connection: Connection,
+    db: Database,
+    root: PathBuf,
+    /// The files open in the editor, whose text is owned by the editor rather
+    /// than by the disk.
+    open: HashSet<SourceFile>,
+    /// The diagnostics last published for each file.
+    published: HashMap<SourceFile, Vec<Diagnostic>>,
+}
+
+impl Server {
+    fn new(connection: Connection, root: PathBuf) -> Server {
+        Server {
+            connection,
+            db: Database::default(),
+            root,
+            open: HashSet::new(),
+            published: HashMap::new(),
+        }
+    }
+
+    /// Loads the Starlark files below the root of the workspace, so that the
+    /// files loaded by the open ones can be resolved.
+    fn load_workspace(&mut self) {
+        if !self.root.is_dir() {
+            return;
+        }
+
+        let changes: Vec<_> = buckaroo::find_files(std::slice::from_ref(&self.root))
+            .into_iter()
+            .filter_map(|path| {
+                let text = fs::read_to_string(&path).ok()?;
+                let path = path.strip_prefix(&self.root).ok()?.to_path_buf();
+                Some(FileChange::new(path, Some(text)))
+            })
+            .collect();
+        self.db.apply_changes(changes);
+    }
+
+    fn main_loop(&mut self) -> anyhow::Result<()> {
+        let receiver = self.connection.receiver.clone();
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => {
+                    // A notification can't be answered with an error, so it's
+                    // logged instead of shutting the server down.
+                    if let Err(error) = self.handle_notification(notification) {
+                        self.log_error(&error)?;
+                    }
+                    self.publish_diagnostics()?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            GotoDefinition::METHOD => self.dispatch::<GotoDefinition>(request, Server::definition),
+            HoverRequest::METHOD => self.dispatch::<HoverRequest>(request, Server::hover),
+            DocumentSymbolRequest::METHOD => {
+                self.dispatch::<DocumentSymbolRequest>(request, Server::document_symbols)
+            }
+            Completion::METHOD => self.dispatch::<Completion>(request, Server::completions),
+            Formatting::METHOD => self.dispatch::<Formatting>(request, Server::format),
+            Rename::METHOD => self.dispatch::<Rename>(request, Server::rename),
+            SemanticTokensFullRequest::METHOD => {
+                self.dispatch::<SemanticTokensFullRequest>(request, Server::semantic_tokens)
+            }
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unknown method `{method}`"),
+            ),
+        }
+    }
+
+    /// Handles the given request of type `R` with the given handler.
+    fn dispatch<R: lsp_types::request::Request>(
+        &self,
+        request: Request,
+        handler: fn(&Server, R::Params) -> Result<R::Result, ServerError>,
+    ) -> Response {
+        let id = request.id.clone();
+        let params = match request.extract::<R::Params>(R::METHOD) {
+            Ok((_, params)) => params,
+            Err(error) => {
+                return Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string())
+            }
+        };
+
+        match handler(self, params) {
+            Ok(result) => Response::new_ok(id, result),
+            Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> anyhow::Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = extract::<DidOpenTextDocument>(notification)?;
+                let path = self.path(&params.text_document.uri)?;
+                self.db
+                    .apply_changes([FileChange::new(path, Some(params.text_document.text))]);
+                self.open.insert(self.file(&params.text_document.uri)?);
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = extract::<DidChangeTextDocument>(notification)?;
+                let file = self.file(&params.text_document.uri)?;
+                for change in params.content_changes {
+                    let Some(range) = change.range else {
+                        let path = file.path(&self.db).clone();
+                        self.db
+                            .apply_changes([FileChange::new(path, Some(change.text))]);
+                        continue;
+                    };
+
+                    // Each change applies to the text left by the previous ones.
+                    let text = file.text(&self.db).as_deref().unwrap_or_default();
+                    let range = line_index(&self.db, file).text_range(range, text);
+                    self.db
+                        .apply_edits(file, [sky_syntax::TextEdit::replace(range, change.text)]);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = extract::<DidCloseTextDocument>(notification)?;
+                let file = self.file(&params.text_document.uri)?;
+                self.open.remove(&file);
+
+                // The editor may have discarded its changes.
+                let path = file.path(&self.db).clone();
+                let text = fs::read_to_string(self.root.join(&path)).ok();
+                self.db.apply_changes([FileChange::new(path, text)]);
+                if self.published.remove(&file).is_some() {
+                    self.send_diagnostics(file, Vec::new())?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Publishes the diagnostics of the open files which changed since they
+    /// were last published.
+    fn publish_diagnostics(&mut self) -> anyhow::Result<()> {
+        let mut open: Vec<_> = self.open.iter().copied().collect();
+        open.sort_by(|a, b| a.path(&self.db).cmp(b.path(&self.db)));
+
+        for file in open {
+            let index = line_index(&self.db, file);
+            let diagnostics: Vec<_> = workspace::diagnostics(&self.db, file)
+                .iter()
+                .map(|diagnostic| Diagnostic {
+                    range: index.range(TextRange::new(
+                        TextSize::try_from(diagnostic.start).unwrap(),
+                        TextSize::try_from(diagnostic.end).unwrap(),
+                    )),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("skylark".to_string()),
+                    message: diagnostic.message.clone(),
+                    ..Diagnostic::default()
+                })
+                .collect();
+
+            if self.published.get(&file) != Some(&diagnostics) {
+                self.published.insert(file, diagnostics.clone());
+                self.send_diagnostics(file, diagnostics)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_diagnostics(
+        &self,
+        file: SourceFile,
+        diagnostics: Vec<Diagnostic>,
+    ) -> anyhow::Result<()> {
+        let params = PublishDiagnosticsParams::new(self.uri(file), diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+
+        Ok(())
+    }
+
+    /// Logs the given error in the client.
+    fn log_error(&self, error: &anyhow::Error) -> anyhow::Result<()> {
+        let params = LogMessageParams {
+            typ: MessageType::ERROR,
+            message: error.to_string(),
+        };
+        let notification = Notification::new(LogMessage::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+
+        Ok(())
+    }
+
+    /// The path of the given document, relative to the root of the workspace.
+    fn path(&self, uri: &Url) -> Result<PathBuf, ServerError> {
+        let path = uri
+            .to_file_path()
+            .map_err(|()| ServerError::NotAFile(uri.clone()))?;
+        Ok(path.strip_prefix(&self.root).unwrap_or(&path).to_path_buf())
+    }
+
+    /// The file of the given document.
+    fn file(&self, uri: &Url) -> Result<SourceFile, ServerError> {
+        self.db
+            .workspace()
+            .file(&self.db, &self.path(uri)?)
+            .ok_or_else(|| ServerError::UnknownFile(uri.clone()))
+    }
+
+    fn uri(&self, file: SourceFile) -> Url {
+        let path = self.root.join(file.path(&self.db));
+        Url::from_file_path(&path).expect("paths of the workspace should be absolute")
+    }
+
+    /// The file and offset of the given position.
+    fn offset(
+        &self,
+        position: &TextDocumentPositionParams,
+    ) -> Result<(SourceFile, TextSize), ServerError> {
+        let file = self.file(&position.text_document.uri)?;
+        let text = file.text(&self.db).as_deref().unwrap_or_default();
+        let offset = line_index(&self.db, file).offset(position.position, text);
+
+        Ok((file, offset))
+    }
+
+    fn location(&self, file: SourceFile, range: TextRange) -> Location {
+        Location::new(self.uri(file), line_index(&self.db, file).range(range))
+    }
+
+    fn definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>, ServerError> {
+        let (file, offset) = self.offset(&params.text_document_position_params)?;
+        let target = ide::goto_definition(&self.db, file, offset);
+
+        Ok(target.map(|it| GotoDefinitionResponse::Scalar(self.location(it.file, it.range))))
+    }
+
+    fn hover(&self, params: HoverParams) -> Result<Option<Hover>, ServerError> {
+        let (file, offset) = self.offset(&params.text_document_position_params)?;
+        let hover = ide::hover(&self.db, file, offset).map(|hover| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: hover.markdown,
+            }),
+            range: Some(line_index(&self.db, file).range(hover.range)),
+        });
+
+        Ok(hover)
+    }
+
+    fn document_symbols(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>, ServerError> {
+        #[allow(deprecated)]
+        fn convert(symbol: ide::Symbol, index: &LineIndex) -> DocumentSymbol {
+            DocumentSymbol {
+                name: symbol.name,
+                detail: symbol.detail,
+                kind: match symbol.kind {
+                    ide::SymbolKind::Function => lsp_types::SymbolKind::FUNCTION,
+                    ide::SymbolKind::Target => lsp_types::SymbolKind::OBJECT,
+                },
+                tags: None,
+                deprecated: None,
+                range: index.range(symbol.full_range),
+                selection_range: index.range(symbol.focus_range),
+                children: Some(
+                    symbol
+                        .children
+                        .into_iter()
+                        .map(|it| convert(it, index))
+                        .collect(),
+                ),
+            }
+        }
+
+        let file = self.file(&params.text_document.uri)?;
+        let index = line_index(&self.db, file);
+        let symbols = ide::document_symbols(&self.db, file)
+            .into_iter()
+            .map(|it| convert(it, index))
+            .collect();
+
+        Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+    }
+
+    fn completions(
+        &self,
+        params: CompletionParams,
+    ) -> Result<Option<CompletionResponse>, ServerError> {
+        let (file, offset) = self.offset(&params.text_document_position)?;
+        let items = ide::completions(&self.db, file, offset)
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    ide::CompletionKind::Function => CompletionItemKind::FUNCTION,
+                    ide::CompletionKind::Variable | ide::CompletionKind::Parameter => {
+                        CompletionItemKind::VARIABLE
+                    }
+                    ide::CompletionKind::Builtin => CompletionItemKind::KEYWORD,
+                }),
+                detail: completion.detail,
+                ..CompletionItem::default()
+            })
+            .collect();
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    /// Formats the whole document, unless it has syntax errors.
+    fn format(
+        &self,
+        params: DocumentFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>, ServerError> {
+        let file = self.file(&params.text_document.uri)?;
+        let text = file.text(&self.db).as_deref().unwrap_or_default();
+        let Ok(formatted) = buckaroo::format(text, &buckaroo::FormatOptions::default()) else {
+            return Ok(None);
+        };
+        if formatted == text {
+            return Ok(Some(Vec::new()));
+        }
+
+        let range = line_index(&self.db, file).range(TextRange::up_to(TextSize::of(text)));
+        Ok(Some(vec![TextEdit::new(range, formatted)]))
+    }
+
+    fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>, ServerError> {
+        let (file, offset) = self.offset(&params.text_document_position)?;
+        let changes = ide::rename(&self.db, file, offset, &params.new_name)?
+            .into_iter()
+            .map(|ide::FileEdit { file, edits }| {
+                let index = line_index(&self.db, file);
+                let edits = edits
+                    .into_iter()
+                    .map(|it| TextEdit::new(index.range(it.delete), it.insert))
+                    .collect();
+                (self.uri(file), edits)
+            })
+            .collect();
+
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    fn semantic_tokens(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>, ServerError> {
+        let file = self.file(&params.text_document.uri)?;
+        let text = file.text(&self.db).as_deref().unwrap_or_default();
+        let highlights = highlight::highlight(
+            &workspace::parse(&self.db, file).tree(),
+            workspace::resolution(&self.db, file),
+            &HighlightOptions::default(),
+        );
+        let data = highlight::semantic_tokens(text, &highlights)
+            .chunks(5)
+            .map(|it| SemanticToken {
+                delta_line: it[0],
+                delta_start: it[1],
+                length: it[2],
+                token_type: it[3],
+                token_modifiers_bitset: it[4],
+            })
+            .collect();
+
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+}
+
+#[cfg(test)]
+fn uri(path: &str) -> Url {
+    Url::from_file_path(std::path::Path::new("/workspace").join(path)).unwrap()
+}
+
+#[test]
+fn serve() {
+    use expect_test::expect;
+    use lsp_types::{
+        notification::{Exit, Initialized},
+        request::{Initialize, Shutdown},
+        DidChangeTextDocumentParams, DidOpenTextDocumentParams, InitializedParams, Position, Range,
+        TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
+        VersionedTextDocumentIdentifier,
+    };
+
+    let (client, server) = Connection::memory();
+    let thread = std::thread::spawn(move || run(server));
+
+    let mut next_id = 0;
+    let mut request = |method: &str, params: serde_json::Value| {
+        next_id += 1;
+        let request = Request::new(next_id.into(), method.to_string(), params);
+        client.sender.send(request.into()).unwrap();
+        match client.receiver.recv().unwrap() {
+            Message::Response(response) => response.result.unwrap_or_default(),
+            message => panic!("unexpected message: {message:?}"),
+        }
+    };
+    let notify = |method: &str, params: serde_json::Value| {
+        let notification = Notification::new(method.to_string(), params);
+        client.sender.send(notification.into()).unwrap();
+    };
+    let published = || match client.receiver.recv().unwrap() {
+        Message::Notification(notification) => notification.params.to_string(),
+        message => panic!("unexpected message: {message:?}"),
+    };
+
+    let params = InitializeParams {
+        root_uri: Some(uri("")),
+        ..InitializeParams::default()
+    };
+    request(Initialize::METHOD, serde_json::to_value(params).unwrap());
+    notify(
+        Initialized::METHOD,
+        serde_json::to_value(InitializedParams {}).unwrap(),
+    );
+
+    let text = "def f(x):\n    \"Increments `x`.\"\n    return x + 1\n\ny = f(1\n";
+    let document = TextDocumentItem::new(uri("BUCK"), "starlark".to_string(), 0, text.to_string());
+    notify(
+        DidOpenTextDocument::METHOD,
+        serde_json::to_value(DidOpenTextDocumentParams {
+            text_document: document,
+        })
+        .unwrap(),
+    );
+    expect![[r#"
+        {"diagnostics":[{"message":"Syntax error encountered","range":{"end":{"character":0,"line":5},"start":{"character":7,"line":4}},"severity":1,"source":"skylark"}],"uri":"file:///workspace/BUCK"}"#]]
+    .assert_eq(&published());
+
+    // Documents outside of the workspace are reported, but don't stop the server.
+    let document = TextDocumentItem::new(
+        "untitled:Untitled-1".parse().unwrap(),
+        "starlark".to_string(),
+        0,
+        String::new(),
+    );
+    notify(
+        DidOpenTextDocument::METHOD,
+        serde_json::to_value(DidOpenTextDocumentParams {
+            text_document: document,
+        })
+        .unwrap(),
+    );
+    expect![[r#"{"message":"`untitled:Untitled-1` isn't a file","type":1}"#]]
+        .assert_eq(&published());
+
+    let position = TextDocumentPositionParams::new(
+        TextDocumentIdentifier::new(uri("BUCK")),
+        Position::new(4, 4),
+    );
+    let hover = request(
+        HoverRequest::METHOD,
+        serde_json::to_value(&position).unwrap(),
+    );
+    expect![[r#"{"contents":{"kind":"markdown","value":"```starlark\ndef f(x) -> Any\n```\n\nIncrements `x`."},"range":{"end":{"character":5,"line":4},"start":{"character":4,"line":4}}}"#]]
+    .assert_eq(&hover.to_string());
+
+    let tokens = request(
+        SemanticTokensFullRequest::METHOD,
+        serde_json::to_value(SemanticTokensParams {
+            text_document: TextDocumentIdentifier::new(uri("BUCK")),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap(),
+    );
+    expect![[r#"{"data":[0,0,3,0,0,0,4,1,6,1,0,2,1,4,1,1,4,17,8,0,1,4,6,0,0,0,7,1,4,0,0,2,1,11,0,0,2,1,9,0,2,0,1,6,1,0,2,1,11,0,0,2,1,6,0,0,2,1,9,0]}"#]]
+    .assert_eq(&tokens.to_string());
+
+    // Closing the parenthesis fixes the syntax error.
+    let change = TextDocumentContentChangeEvent {
+        range: Some(Range::new(Position::new(4, 7), Position::new(4, 7))),
+        range_length: None,
+        text: ")".to_string(),
+    };
+    notify(
+        DidChangeTextDocument::METHOD,
+        serde_json::to_value(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri("BUCK"), 1),
+            content_changes: vec![change],
+        })
+        .unwrap(),
+    );
+    expect![[r#"{"diagnostics":[],"uri":"file:///workspace/BUCK"}"#]].assert_eq(&published());
+
+    request(Shutdown::METHOD, serde_json::Value::Null);
+    notify(Exit::METHOD, serde_json::Value::Null);
+    thread.join().unwrap().unwrap();
+}
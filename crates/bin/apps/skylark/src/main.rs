@@ -1,103 +1,50 @@
-use std::io::{self, Write};
+//! **skylark**, checking the given Starlark files (or the standard input)
+//! and reporting their diagnostics.
 
-use ir::{Diagnostics, SourceProgram};
 use sky_syntax::init_logging;
+use skylark::{
+    compile,
+    db::Database,
+    ir::{Diagnostics, SourceProgram},
+};
+use std::{
+    fs,
+    io::{self, Read},
+    process::ExitCode,
+};
 use tracing::Level;
 
-// ANCHOR: jar_struct
-#[salsa::jar(db = Db)]
-pub struct Jar(
-    crate::compile::compile,
-    crate::ir::SourceProgram,
-    crate::ir::Program,
-    crate::ir::VariableId,
-    crate::ir::FunctionId,
-    crate::ir::Function,
-    crate::ir::Diagnostics,
-    crate::ir::Span,
-    crate::parser::parse_statements,
-    crate::type_check::type_check_program,
-    crate::type_check::type_check_function,
-    crate::type_check::find_function,
-);
-// ANCHOR_END: jar_struct
-
-// ANCHOR: jar_db
-pub trait Db: salsa::DbWithJar<Jar> {}
-// ANCHOR_END: jar_db
-
-// ANCHOR: jar_db_impl
-impl<DB> Db for DB where DB: ?Sized + salsa::DbWithJar<Jar> {}
-// ANCHOR_END: jar_db_impl
-
-mod compile;
-mod db;
-mod ir;
-mod parser;
-mod type_check;
-
-// pub fn main() {
-//     let mut db = db::Database::default();
-//     let mut source_code = String::new();
-
-//     loop {
-//         print!("> ");
-//         io::stdout().flush().unwrap();
-
-//         let mut input = String::new();
-//         match io::stdin().read_line(&mut input) {
-//             Ok(_) => {
-//                 source_code.push_str(&input);
-//                 let source_program = SourceProgram::new(&db, source_code.clone());
-//                 compile::compile(&db, source_program);
-//                 let diagnostics = compile::compile::accumulated::<Diagnostics>(&db, source_program);
-//                 if diagnostics.is_empty() {
-//                     println!("Compilation successful");
-//                 } else {
-//                     eprintln!("Diagnostics: {:#?}", diagnostics);
-//                 }
-//             }
-//             Err(error) => {
-//                 eprintln!("Error reading input: {}", error);
-//                 break;
-//             }
-//         }
-//     }
-// }
-// pub fn main() {
-//     let db = db::Database::default();
-
-//     let source_program = SourceProgram::new(&db, String::new());
-//     compile::compile(&db, source_program);
-//     let diagnostics = compile::compile::accumulated::<Diagnostics>(&db, source_program);
-//     eprintln!("{diagnostics:?}");
-// }
-fn main() {
-    let source = "asdf = 1";
-
-    let log_level = Level::DEBUG;
-    init_logging(log_level).expect("Failed to initialize logging");
-
-    tracing::debug!("Parsing source: {}", source);
-
-    let ast = sky_syntax::File::parse(source);
-
-    println!("{}", ast.debug_dump());
-
-    //     let mut lexer = sky_syntax::StarlarkLexer::new();
-
-    //     let token_sink = lexer.tokenize(source);
-
-    //     if token_sink.has_errors() {
-    //         println!("has errors");
-    //         lexer.emit_errors();
-    //     }
-
-    // println!("tokens: {:#?}", token_sink.tokens());
-
-    // let parser = sky_syntax::StarlarkParser::new();
-
-    // let ast = parser.parse(source);
-
-    // println!("ast: {:#?}", ast.debug_dump());
+fn main() -> anyhow::Result<ExitCode> {
+    init_logging(Level::INFO).expect("Failed to initialize logging");
+
+    let mut sources = Vec::new();
+    for path in std::env::args().skip(1) {
+        sources.push((path.clone(), fs::read_to_string(&path)?));
+    }
+    if sources.is_empty() {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        sources.push(("<stdin>".to_string(), text));
+    }
+
+    let db = Database::default();
+    let mut failed = false;
+    for (path, text) in sources {
+        tracing::debug!("Checking {path}");
+        let source_program = SourceProgram::new(&db, text);
+        compile::compile(&db, source_program);
+        for diagnostic in compile::compile::accumulated::<Diagnostics>(&db, source_program) {
+            eprintln!(
+                "{path}:{}..{}: {}",
+                diagnostic.start, diagnostic.end, diagnostic.message
+            );
+            failed = true;
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
 }
@@ -1,97 +1,172 @@
-use crate::ir::{
-    Diagnostic, Diagnostics, Expression, Function, FunctionId, Program, Span, StatementData,
-    VariableId,
+//! **Incremental type checking** of Starlark source, on top of
+//! `sky_analyzer::types`.
+//!
+//! A module is split into its top-level **items** (each `def` statement,
+//! and each other statement), which are checked separately. An item sees
+//! the globals bound by the other items through the types of their
+//! **signatures** (for functions) or of their bound names (for other
+//! statements, which only see the globals bound before them). Editing the
+//! body of a function thus re-checks the function itself, and only re-checks
+//! the items using it if its signature changed.
+//!
+//! In the files of a [`crate::workspace`], the symbols loaded from other
+//! files have the types of the globals they refer to.
+
+use crate::{
+    ir::{Diagnostic, Diagnostics, Item, Name, Program, SourceProgram},
+    lower::{parse_item, parse_program},
+    workspace::loaded_type,
 };
-use derive_new::new;
 #[cfg(test)]
 use expect_test::expect;
+use sky_analyzer::{
+    resolve::{resolve, ResolveOptions},
+    types::{check, Environment, Globals, Type, TypeError},
+};
 
-// ANCHOR: parse_statements
-#[salsa::tracked]
-pub fn type_check_program(db: &dyn crate::Db, program: Program) {
-    for statement in program.statements(db) {
-        match &statement.data {
-            StatementData::Function(f) => type_check_function(db, *f, program),
-            StatementData::Print(e) => CheckExpression::new(db, program, &[]).check(e),
-        }
-    }
+/// The names of the globals **bound** by the given item.
+#[salsa::tracked(return_ref)]
+pub fn item_globals(db: &dyn crate::Db, item: Item) -> Vec<String> {
+    let resolution = resolve(&parse_item(db, item).tree(), &ResolveOptions::default());
+    resolution
+        .scope(resolution.module())
+        .bindings
+        .iter()
+        .map(|id| resolution.binding(*id).name.clone())
+        .collect()
 }
 
+/// The item binding the global with the given name, if any.
 #[salsa::tracked]
-pub fn type_check_function(db: &dyn crate::Db, function: Function, program: Program) {
-    CheckExpression::new(db, program, function.args(db)).check(function.body(db))
+pub fn find_global(db: &dyn crate::Db, program: Program, name: Name) -> Option<Item> {
+    program
+        .items(db)
+        .iter()
+        .copied()
+        .find(|item| item_globals(db, *item).contains(name.text(db)))
 }
 
+/// The **signature** of the function of the given item, as annotated (and
+/// inferred from its body, for its return type). It doesn't depend on the
+/// other items, which unannotated code sees as `Any`.
 #[salsa::tracked]
-pub fn find_function(db: &dyn crate::Db, program: Program, name: FunctionId) -> Option<Function> {
-    program
-        .statements(db)
+pub fn item_signature(db: &dyn crate::Db, item: Item) -> Type {
+    let check = check(&parse_item(db, item).tree(), &Globals::default());
+    check.global(&item.key(db)).cloned().unwrap_or(Type::Any)
+}
+
+/// The types of the globals bound by the given (non-function) item.
+#[salsa::tracked(return_ref)]
+pub fn item_types(db: &dyn crate::Db, program: Program, item: Item) -> Vec<(String, Type)> {
+    let environment = ModuleEnvironment {
+        db,
+        program,
+        before: Some(item),
+    };
+    let check = check(&parse_item(db, item).tree(), &environment);
+
+    item_globals(db, item)
         .iter()
-        .flat_map(|s| match &s.data {
-            StatementData::Function(f) if f.name(db) == name => Some(*f),
-            _ => None,
+        .map(|name| {
+            (
+                name.clone(),
+                check.global(name).cloned().unwrap_or(Type::Any),
+            )
         })
-        .next()
+        .collect()
 }
 
-#[derive(new)]
-struct CheckExpression<'w> {
-    db: &'w dyn crate::Db,
+/// The type of the global with the given name, or `None` if it isn't bound
+/// in the module.
+#[salsa::tracked]
+pub fn global_type(db: &dyn crate::Db, program: Program, name: Name) -> Option<Type> {
+    let item = find_global(db, program, name)?;
+    if item.is_function(db) {
+        return Some(item_signature(db, item));
+    }
+
+    item_types(db, program, item)
+        .iter()
+        .find(|(global, _)| global == name.text(db))
+        .map(|(_, ty)| ty.clone())
+}
+
+/// The type errors of the given item, relative to its start.
+#[salsa::tracked(return_ref)]
+pub fn type_check_item(db: &dyn crate::Db, program: Program, item: Item) -> Vec<TypeError> {
+    // Functions are called after the module is loaded, when all of its
+    // globals are bound.
+    let before = (!item.is_function(db)).then_some(item);
+    let environment = ModuleEnvironment {
+        db,
+        program,
+        before,
+    };
+
+    check(&parse_item(db, item).tree(), &environment)
+        .errors()
+        .to_vec()
+}
+
+// ANCHOR: type_check_program
+#[salsa::tracked]
+pub fn type_check_program(db: &dyn crate::Db, source: SourceProgram) {
+    let program = parse_program(db, source);
+    for item in program.items(db) {
+        let offset = item.offset(db);
+        for error in type_check_item(db, program, *item) {
+            Diagnostics::push(
+                db,
+                Diagnostic::new(
+                    offset + usize::from(error.range.start()),
+                    offset + usize::from(error.range.end()),
+                    error.message.clone(),
+                ),
+            );
+        }
+    }
+}
+// ANCHOR_END: type_check_program
+
+/// The **environment** of an item, i.e. the globals bound by the other items
+/// of its module (only those bound before the given item, if any), and the
+/// symbols loaded from the other files of the workspace.
+struct ModuleEnvironment<'db> {
+    db: &'db dyn crate::Db,
     program: Program,
-    names_in_scope: &'w [VariableId],
+    before: Option<Item>,
 }
 
-impl CheckExpression<'_> {
-    fn check(&self, expression: &Expression) {
-        match &expression.data {
-            crate::ir::ExpressionData::Op(left, _, right) => {
-                self.check(left);
-                self.check(right);
-            }
-            crate::ir::ExpressionData::Number(_) => {}
-            crate::ir::ExpressionData::Variable(v) => {
-                if !self.names_in_scope.contains(v) {
-                    self.report_error(
-                        expression.span,
-                        format!("the variable `{}` is not declared", v.text(self.db)),
-                    );
-                }
-            }
-            crate::ir::ExpressionData::Call(f, args) => {
-                if self.find_function(*f).is_none() {
-                    self.report_error(
-                        expression.span,
-                        format!("the function `{}` is not declared", f.text(self.db)),
-                    );
-                }
-                for arg in args {
-                    self.check(arg);
-                }
+impl Environment for ModuleEnvironment<'_> {
+    fn lookup(&self, name: &str) -> Option<Type> {
+        let name = Name::new(self.db, name.to_string());
+        if let Some(before) = self.before {
+            let items = self.program.items(self.db);
+            let item = find_global(self.db, self.program, name)?;
+            let position = |item| items.iter().position(|it| *it == item);
+            if position(item) >= position(before) {
+                return None;
             }
         }
-    }
 
-    fn find_function(&self, f: FunctionId) -> Option<Function> {
-        find_function(self.db, self.program, f)
+        global_type(self.db, self.program, name)
     }
 
-    fn report_error(&self, span: Span, message: String) {
-        Diagnostics::push(
-            self.db,
-            Diagnostic::new(span.start(self.db), span.end(self.db), message),
-        );
+    fn load(&self, module: &str, symbol: &str) -> Option<Type> {
+        loaded_type(self.db, self.program.file(self.db)?, module, symbol)
     }
 }
 
-/// Create a new database with the given source text and parse the result.
-/// Returns the statements and the diagnostics generated.
+/// Create a new database with the given source text and type check it,
+/// checking the diagnostics after each edit along with the number of items
+/// checked again.
 #[cfg(test)]
 fn check_string(
     source_text: &str,
     expected_diagnostics: expect_test::Expect,
-    edits: &[(&str, expect_test::Expect, expect_test::Expect)],
+    edits: &[(&str, expect_test::Expect, usize)],
 ) {
-    use crate::{db::Database, ir::SourceProgram, parser::parse_statements};
+    use crate::db::Database;
 
     // Create the database
     let mut db = Database::default().enable_logging();
@@ -99,73 +174,44 @@ fn check_string(
     // Create the source program
     let source_program = SourceProgram::new(&db, source_text.to_string());
 
-    // Invoke the parser
-    let program = parse_statements(&db, source_program);
-
     // Read out any diagnostics
     expected_diagnostics.assert_debug_eq(&type_check_program::accumulated::<Diagnostics>(
-        &db, program,
+        &db,
+        source_program,
     ));
 
     // Clear logs
     db.take_logs();
 
     // Apply edits and check diagnostics/logs after each one
-    for (new_source_text, expected_diagnostics, expected_logs) in edits {
+    for (new_source_text, expected_diagnostics, expected_checks) in edits {
         source_program
             .set_text(&mut db)
             .to(new_source_text.to_string());
-        let program = parse_statements(&db, source_program);
         expected_diagnostics.assert_debug_eq(&type_check_program::accumulated::<Diagnostics>(
-            &db, program,
+            &db,
+            source_program,
         ));
-        expected_logs.assert_debug_eq(&db.take_logs());
-    }
-}
 
-#[test]
-fn check_print() {
-    check_string(
-        "print 1 + 2",
-        expect![[r#"
-            []
-        "#]],
-        &[],
-    );
-}
-
-#[test]
-fn check_bad_variable_in_program() {
-    check_string(
-        "print a + b",
-        expect![[r#"
-            [
-                Diagnostic {
-                    start: 6,
-                    end: 8,
-                    message: "the variable `a` is not declared",
-                },
-                Diagnostic {
-                    start: 10,
-                    end: 11,
-                    message: "the variable `b` is not declared",
-                },
-            ]
-        "#]],
-        &[],
-    );
+        let checks = db
+            .take_logs()
+            .iter()
+            .filter(|it| it.contains("type_check_item"))
+            .count();
+        assert_eq!(checks, *expected_checks);
+    }
 }
 
 #[test]
-fn check_bad_function_in_program() {
+fn check_annotations() {
     check_string(
-        "print a(22)",
+        "def f(x: int) -> str:\n    return x\n",
         expect![[r#"
             [
                 Diagnostic {
-                    start: 6,
-                    end: 11,
-                    message: "the function `a` is not declared",
+                    start: 33,
+                    end: 34,
+                    message: "Expected `str` to be returned from `f`, got `int`",
                 },
             ]
         "#]],
@@ -174,18 +220,15 @@ fn check_bad_function_in_program() {
 }
 
 #[test]
-fn check_bad_variable_in_function() {
+fn check_across_items() {
     check_string(
-        "
-            fn add_one(a) = a + b
-            print add_one(22)
-        ",
+        "def f(x: str):\n    pass\n\nf(1)\n",
         expect![[r#"
             [
                 Diagnostic {
-                    start: 33,
-                    end: 47,
-                    message: "the variable `b` is not declared",
+                    start: 27,
+                    end: 28,
+                    message: "Expected `str` for argument `x` of `f`, got `int`",
                 },
             ]
         "#]],
@@ -194,23 +237,15 @@ fn check_bad_variable_in_function() {
 }
 
 #[test]
-fn check_bad_function_in_function() {
+fn check_globals_in_order() {
     check_string(
-        "
-            fn add_one(a) = add_two(a) + b
-            print add_one(22)
-        ",
+        "x = 1\ny = x + 'a'\n",
         expect![[r#"
             [
                 Diagnostic {
-                    start: 29,
-                    end: 39,
-                    message: "the function `add_two` is not declared",
-                },
-                Diagnostic {
-                    start: 42,
-                    end: 56,
-                    message: "the variable `b` is not declared",
+                    start: 10,
+                    end: 17,
+                    message: "Unsupported operand types for `+`: `int` and `str`",
                 },
             ]
         "#]],
@@ -219,37 +254,35 @@ fn check_bad_function_in_function() {
 }
 
 #[test]
-fn fix_bad_variable_in_function() {
+fn recheck_only_dependents() {
     check_string(
-        "
-            fn double(a) = a * b
-            fn quadruple(a) = double(double(a))
-            print quadruple(2)
-        ",
+        "def f(x: int) -> int:\n    return x\n\ndef g():\n    return f(1)\n",
         expect![[r#"
-            [
-                Diagnostic {
-                    start: 32,
-                    end: 46,
-                    message: "the variable `b` is not declared",
-                },
-            ]
+            []
         "#]],
-        &[(
-            "
-                fn double(a) = a * 2
-                fn quadruple(a) = double(double(a))
-                print quadruple(2)
-            ",
-            expect![[r#"
-                []
-            "#]],
-            expect![[r#"
-                [
-                    "Event: Event { runtime_id: RuntimeId { counter: 0 }, kind: WillExecute { database_key: parse_statements(0) } }",
-                    "Event: Event { runtime_id: RuntimeId { counter: 0 }, kind: WillExecute { database_key: type_check_function(0) } }",
-                ]
-            "#]],
-        )],
+        &[
+            // The signature of `f` is unchanged, so `g` isn't checked again.
+            (
+                "def f(x: int) -> int:\n    return x + 1\n\ndef g():\n    return f(1)\n",
+                expect![[r#"
+                    []
+                "#]],
+                1,
+            ),
+            // The signature of `f` changed, so `g` is checked again.
+            (
+                "def f(x: str) -> int:\n    return 1\n\ndef g():\n    return f(1)\n",
+                expect![[r#"
+                    [
+                        Diagnostic {
+                            start: 58,
+                            end: 59,
+                            message: "Expected `str` for argument `x` of `f`, got `int`",
+                        },
+                    ]
+                "#]],
+                2,
+            ),
+        ],
     );
 }
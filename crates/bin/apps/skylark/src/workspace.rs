@@ -0,0 +1,757 @@
+//! A **workspace** of Starlark files (e.g. the `BUCK` files of a repository
+//! and the extensions they load), along with the queries analyzing each of
+//! them.
+//!
+//! Each file is a [`SourceFile`] input, keyed by its path relative to the
+//! root of the workspace in the [`Workspace`] input. The queries of a file
+//! only depend on the files it loads through their **exports**, so that
+//! editing a file only re-runs the queries of the files loading it if the
+//! names it exports changed.
+//!
+//! The files of **external repositories** (loaded by `@repo//` labels) are
+//! part of the workspace too, below the root registered for each repository
+//! (see [`Database::add_repository`](crate::db::Database::add_repository)).
+//!
+//! Modules are evaluated by [`load_module`], which caches them frozen (see
+//! [`FrozenModule`]), as the values of the interpreter can't be shared by
+//! the queries.
+
+#[cfg(test)]
+use crate::{db::Database, Db};
+use crate::{
+    ir::{Diagnostic, Name, Program},
+    lower::items,
+    type_check::{global_type, type_check_item},
+};
+use derive_new::new;
+#[cfg(test)]
+use expect_test::expect;
+#[cfg(test)]
+use rowan::TextRange;
+use rowan::TextSize;
+use sky_analyzer::{
+    labels::{Label, LabelError},
+    resolve::{resolve, BindingKind, NameError, Resolution, ResolveOptions},
+    types::Type,
+};
+use sky_interpreter::{FrozenModule, Interpreter};
+#[cfg(test)]
+use sky_syntax::TextEdit;
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    File, Parse,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+// ANCHOR: source_file
+/// A **file** of the workspace.
+#[salsa::input]
+pub struct SourceFile {
+    /// The path of the file, relative to the root of the workspace.
+    #[return_ref]
+    pub path: PathBuf,
+
+    /// The text of the file, or `None` if it was removed.
+    #[return_ref]
+    pub text: Option<String>,
+
+    /// The syntax tree of the file, if it was reparsed incrementally when it
+    /// was last edited (see
+    /// [`Database::apply_edits`](crate::db::Database::apply_edits)).
+    #[return_ref]
+    pub reparsed: Option<Parse<File>>,
+}
+// ANCHOR_END: source_file
+
+/// The **files** of the workspace, by path, along with the roots of the
+/// external repositories.
+///
+/// Removed files are kept without any text, so that the queries which looked
+/// them up are invalidated once they are added back.
+#[salsa::input]
+pub struct Workspace {
+    #[return_ref]
+    pub files: BTreeMap<PathBuf, SourceFile>,
+
+    /// The roots of the external repositories by name (e.g. `repo` for
+    /// `@repo//foo:defs.bzl`), relative to the root of the workspace.
+    #[return_ref]
+    pub repositories: BTreeMap<String, PathBuf>,
+}
+
+impl Workspace {
+    /// The file with the given path, relative to the root of the workspace,
+    /// if it was ever added.
+    pub fn file(self, db: &dyn crate::Db, path: &Path) -> Option<SourceFile> {
+        self.files(db).get(path).copied()
+    }
+
+    /// The **label** of the file with the given path, e.g. `//foo:BUCK` for
+    /// `foo/BUCK`, or `@repo//foo:defs.bzl` if `repo` is rooted at
+    /// `third-party/repo` and the path is `third-party/repo/foo/defs.bzl`.
+    pub fn label(self, db: &dyn crate::Db, path: &Path) -> Label {
+        let (repository, path) = self
+            .repositories(db)
+            .iter()
+            .find_map(|(name, root)| Some((Some(name.clone()), path.strip_prefix(root).ok()?)))
+            .unwrap_or((None, path));
+        let package = path
+            .parent()
+            .map(|it| it.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let name = path
+            .file_name()
+            .map(|it| it.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Label {
+            repository,
+            package,
+            name,
+        }
+    }
+}
+
+/// An interned **label**, as written in a `load` statement.
+#[salsa::interned]
+pub struct LabelId {
+    #[return_ref]
+    pub text: String,
+}
+
+/// A **change** to a file of the workspace (see
+/// [`Database::apply_changes`](crate::db::Database::apply_changes)).
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct FileChange {
+    pub path: PathBuf,
+
+    /// The new text of the file, or `None` if it was removed.
+    pub text: Option<String>,
+}
+
+/// An **error** resolving the label of a `load` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LoadError {
+    #[error(transparent)]
+    Label(#[from] LabelError),
+    #[error("Unknown repository `@{0}`")]
+    UnknownRepository(String),
+    #[error("Cannot find `{0}`")]
+    NotFound(Label),
+}
+
+/// An **error** evaluating the module of a file (see [`load_module`]).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ModuleError {
+    /// Modules loading each other, e.g. `//a:a.bzl -> //b:b.bzl -> //a:a.bzl`.
+    #[error("Load cycle: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(" -> "))]
+    Cycle(Vec<Label>),
+    #[error(transparent)]
+    Load(#[from] LoadError),
+    #[error(transparent)]
+    Eval(#[from] sky_interpreter::Error),
+    /// An error in the given module (rather than in the modules it loads).
+    #[error("Failed to load `{label}`: {error}")]
+    Module {
+        label: Label,
+        error: Box<ModuleError>,
+    },
+}
+
+// ANCHOR: parse
+#[salsa::tracked(return_ref)]
+pub fn parse(db: &dyn crate::Db, file: SourceFile) -> Parse<File> {
+    let text = file.text(db).as_deref().unwrap_or_default();
+
+    // Reparsing gives the same tree as parsing from scratch, so it can be
+    // reused as long as it's up to date.
+    match file.reparsed(db) {
+        Some(reparsed) if reparsed.syntax_node().text() == text => reparsed.clone(),
+        _ => File::parse(text),
+    }
+}
+// ANCHOR_END: parse
+
+/// The **resolution** of the names of the given file.
+#[salsa::tracked(return_ref)]
+pub fn resolution(db: &dyn crate::Db, file: SourceFile) -> Resolution {
+    resolve(&parse(db, file).tree(), &ResolveOptions::default())
+}
+
+/// The names **exported** by the module of the given file, i.e. its globals
+/// not starting with `_`, in the order they're bound. The symbols it loads
+/// aren't exported.
+#[salsa::tracked(return_ref)]
+pub fn module_exports(db: &dyn crate::Db, file: SourceFile) -> Vec<String> {
+    let resolution = resolution(db, file);
+    resolution
+        .scope(resolution.module())
+        .bindings
+        .iter()
+        .map(|id| resolution.binding(*id))
+        .filter(|binding| binding.kind != BindingKind::Load && !binding.name.starts_with('_'))
+        .map(|binding| binding.name.clone())
+        .collect()
+}
+
+/// The file loaded by the given label (e.g. `:defs.bzl`) of a `load`
+/// statement of the given file. Labels of external repositories (e.g.
+/// `@repo//foo:defs.bzl`) are resolved below the root of the repository.
+#[salsa::tracked]
+pub fn resolve_load(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    label: LabelId,
+) -> Result<SourceFile, LoadError> {
+    let workspace = db.workspace();
+    let label = Label::parse(label.text(db), Some(&workspace.label(db, file.path(db))))?;
+    let path = match &label.repository {
+        Some(repository) => workspace
+            .repositories(db)
+            .get(repository)
+            .ok_or_else(|| LoadError::UnknownRepository(repository.clone()))?
+            .join(label.path()),
+        None => label.path(),
+    };
+
+    match workspace.file(db, &path) {
+        Some(loaded) if loaded.text(db).is_some() => Ok(loaded),
+        _ => Err(LoadError::NotFound(label)),
+    }
+}
+
+/// The **module** of the given file, evaluated after the modules it loads
+/// and frozen. Editing a file only evaluates it again along with the modules
+/// loading it, directly or not.
+#[salsa::tracked]
+pub fn load_module(db: &dyn crate::Db, file: SourceFile) -> Result<Arc<FrozenModule>, ModuleError> {
+    if let Some(cycle) = load_cycle(db, file) {
+        return Err(ModuleError::Cycle(cycle));
+    }
+
+    let label = db.workspace().label(db, file.path(db));
+    let in_module = |error: ModuleError| ModuleError::Module {
+        label: label.clone(),
+        error: Box::new(error),
+    };
+    let parse = parse(db, file);
+    if !parse.errors().is_empty() {
+        let error = sky_interpreter::Error::Syntax(parse.errors().to_vec());
+        return Err(in_module(error.into()));
+    }
+
+    let interpreter = Interpreter::new();
+    let mut loaded = HashMap::new();
+    for (text, resolved) in loads(db, file) {
+        let module = load_module(db, resolved.map_err(|it| in_module(it.into()))?)?;
+        let module = interpreter
+            .thaw(&module)
+            .map_err(|it| in_module(sky_interpreter::Error::from(it).into()))?;
+        loaded.insert(text, module);
+    }
+
+    interpreter
+        .eval_loaded(&label.to_string(), parse.tree(), loaded)
+        .map_err(|it| in_module(it.into()))?
+        .freeze()
+        .map_err(|it| in_module(sky_interpreter::Error::from(it).into()))
+}
+
+/// The labels of the `load` statements of the given file, along with the
+/// files they load.
+fn loads(db: &dyn crate::Db, file: SourceFile) -> Vec<(String, Result<SourceFile, LoadError>)> {
+    let tree = parse(db, file).tree();
+    tree.syntax()
+        .descendants()
+        .filter_map(ast::LoadStmt::cast)
+        .filter_map(|stmt| stmt.module()?.value().ok())
+        .map(|label| {
+            let loaded = resolve_load(db, file, LabelId::new(db, label.clone()));
+            (label, loaded)
+        })
+        .collect()
+}
+
+/// The first **cycle** of `load` statements reachable from the given file,
+/// as the labels of the files forming it.
+fn load_cycle(db: &dyn crate::Db, file: SourceFile) -> Option<Vec<Label>> {
+    fn visit(
+        db: &dyn crate::Db,
+        file: SourceFile,
+        stack: &mut Vec<SourceFile>,
+        visited: &mut HashSet<SourceFile>,
+    ) -> Option<Vec<Label>> {
+        if let Some(start) = stack.iter().position(|it| *it == file) {
+            let cycle = stack[start..].iter().chain([&file]);
+            let workspace = db.workspace();
+            return Some(cycle.map(|it| workspace.label(db, it.path(db))).collect());
+        }
+        if !visited.insert(file) {
+            return None;
+        }
+
+        stack.push(file);
+        for (_, loaded) in loads(db, file) {
+            let Ok(loaded) = loaded else {
+                continue;
+            };
+            if let Some(cycle) = visit(db, loaded, stack, visited) {
+                return Some(cycle);
+            }
+        }
+        stack.pop();
+
+        None
+    }
+
+    visit(db, file, &mut Vec::new(), &mut HashSet::new())
+}
+
+/// The **program** of the given file, i.e. its top-level items, which are
+/// type checked separately (see [`crate::type_check`]).
+#[salsa::tracked]
+pub fn file_program(db: &dyn crate::Db, file: SourceFile) -> Program {
+    Program::new(db, items(db, &parse(db, file).tree()), Some(file))
+}
+
+/// The **type** of the given symbol, loaded by a `load` statement of the
+/// given file from the module with the given label, if the module exports
+/// it. The types of modules in a load cycle aren't known, as they depend on
+/// each other.
+pub fn loaded_type(
+    db: &dyn crate::Db,
+    file: SourceFile,
+    label: &str,
+    symbol: &str,
+) -> Option<Type> {
+    let loaded = resolve_load(db, file, LabelId::new(db, label.to_string())).ok()?;
+    if load_cycle(db, loaded).is_some() || !module_exports(db, loaded).iter().any(|it| it == symbol)
+    {
+        return None;
+    }
+
+    global_type(
+        db,
+        file_program(db, loaded),
+        Name::new(db, symbol.to_string()),
+    )
+}
+
+// ANCHOR: diagnostics
+/// The **diagnostics** of the given file: its syntax errors, its `load`
+/// statements which can't be resolved (or load symbols the loaded module
+/// doesn't export), its name errors (e.g. reassigned globals) and its type
+/// errors. Only the items of the file whose environment changed are type
+/// checked again.
+#[salsa::tracked(return_ref)]
+pub fn diagnostics(db: &dyn crate::Db, file: SourceFile) -> Vec<Diagnostic> {
+    let parse = parse(db, file);
+    let tree = parse.tree();
+    let mut diagnostics: Vec<_> = parse
+        .errors()
+        .iter()
+        .map(|error| {
+            let range = error.range();
+            Diagnostic::new(range.start().into(), range.end().into(), error.to_string())
+        })
+        .collect();
+    let mut report = |range: rowan::TextRange, message: String| {
+        diagnostics.push(Diagnostic::new(
+            range.start().into(),
+            range.end().into(),
+            message,
+        ))
+    };
+
+    for stmt in tree.syntax().descendants().filter_map(ast::LoadStmt::cast) {
+        let Some(module) = stmt.module() else {
+            continue;
+        };
+        let Ok(label) = module.value() else {
+            continue;
+        };
+        let loaded = match resolve_load(db, file, LabelId::new(db, label.clone())) {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                report(module.syntax().text_range(), error.to_string());
+                continue;
+            }
+        };
+
+        let exports = module_exports(db, loaded);
+        for symbol in stmt.load_symbols().filter_map(|it| it.symbol()) {
+            let Ok(name) = symbol.value() else {
+                continue;
+            };
+            if name.starts_with('_') {
+                report(
+                    symbol.syntax().text_range(),
+                    format!("Cannot load private symbol `{name}` from `{label}`"),
+                );
+            } else if !exports.contains(&name) {
+                report(
+                    symbol.syntax().text_range(),
+                    format!("Module `{label}` has no symbol `{name}`"),
+                );
+            }
+        }
+    }
+
+    // Undefined names aren't reported, as the names predeclared in `BUCK`
+    // files (e.g. the native rules) aren't known.
+    for error in resolution(db, file).errors() {
+        if !matches!(error, NameError::Undefined { .. }) {
+            report(error.range(), error.to_string());
+        }
+    }
+    let program = file_program(db, file);
+    for item in program.items(db) {
+        let offset = TextSize::try_from(item.offset(db)).unwrap();
+        for error in type_check_item(db, program, *item) {
+            report(error.range + offset, error.message.clone());
+        }
+    }
+
+    diagnostics.sort_by_key(|it| (it.start, it.end));
+    diagnostics
+}
+// ANCHOR_END: diagnostics
+
+/// The file of the workspace with the given path.
+#[cfg(test)]
+fn file(db: &Database, path: &str) -> SourceFile {
+    db.workspace()
+        .file(db, Path::new(path))
+        .expect("the file should be in the workspace")
+}
+
+/// Create a new workspace database with the given files.
+#[cfg(test)]
+fn workspace(files: &[(&str, &str)]) -> Database {
+    let mut db = Database::default().enable_logging();
+    db.apply_changes(
+        files
+            .iter()
+            .map(|(path, text)| FileChange::new(path.into(), Some(text.to_string()))),
+    );
+
+    db
+}
+
+/// The number of executions of the given query in the logs.
+#[cfg(test)]
+fn executions(db: &mut Database, query: &str) -> usize {
+    db.take_logs()
+        .iter()
+        .filter(|it| it.contains(query))
+        .count()
+}
+
+#[test]
+fn exports() {
+    let db = workspace(&[(
+        "defs.bzl",
+        "load(\":other.bzl\", \"loaded\")\n\nx = 1\n_private = 2\n\ndef rule(name):\n    pass\n",
+    )]);
+
+    let file = file(&db, "defs.bzl");
+    assert_eq!(module_exports(&db, file), &["x", "rule"]);
+}
+
+#[test]
+fn resolve_labels() {
+    let db = workspace(&[("foo/BUCK", ""), ("foo/defs.bzl", ""), ("bar/defs.bzl", "")]);
+    let file = file(&db, "foo/BUCK");
+    let resolve = |label: &str| {
+        resolve_load(&db, file, LabelId::new(&db, label.to_string()))
+            .map(|it| it.path(&db).clone())
+            .map_err(|it| it.to_string())
+    };
+
+    assert_eq!(resolve(":defs.bzl"), Ok(PathBuf::from("foo/defs.bzl")));
+    assert_eq!(resolve("//bar:defs.bzl"), Ok(PathBuf::from("bar/defs.bzl")));
+    assert_eq!(
+        resolve("//baz:defs.bzl"),
+        Err("Cannot find `//baz:defs.bzl`".to_string())
+    );
+    assert_eq!(
+        resolve("@other//foo:defs.bzl"),
+        Err("Unknown repository `@other`".to_string())
+    );
+}
+
+#[test]
+fn load_external_repository() {
+    let mut db = workspace(&[
+        (
+            "BUCK",
+            "load(\"@ext//rules:defs.bzl\", \"rule\")\n\nx = rule(\"x\")\n",
+        ),
+        (
+            "third-party/ext/rules/defs.bzl",
+            "load(\"//lib:util.bzl\", \"prefix\")\n\ndef rule(name):\n    return prefix + name\n",
+        ),
+        ("third-party/ext/lib/util.bzl", "prefix = \"ext:\"\n"),
+    ]);
+    db.add_repository("ext", "third-party/ext");
+
+    // Absolute labels in the repository refer to its root.
+    let defs = file(&db, "third-party/ext/rules/defs.bzl");
+    assert_eq!(
+        db.workspace().label(&db, defs.path(&db)).to_string(),
+        "@ext//rules:defs.bzl"
+    );
+    assert_eq!(diagnostics(&db, file(&db, "BUCK")), &[]);
+    assert_eq!(global(&db, "BUCK", "x"), "\"ext:x\"");
+}
+
+#[test]
+fn load_diagnostics() {
+    let db = workspace(&[
+        (
+            "foo/BUCK",
+            "load(\":defs.bzl\", \"rule\", \"_private\", \"missing\")\nload(\"//bar:defs.bzl\", \"other\")\n\nrule(name = \"foo\")\n",
+        ),
+        ("foo/defs.bzl", "_private = 1\n\ndef rule(name):\n    pass\n"),
+    ]);
+
+    expect![[r#"
+        [
+            Diagnostic {
+                start: 26,
+                end: 36,
+                message: "Cannot load private symbol `_private` from `:defs.bzl`",
+            },
+            Diagnostic {
+                start: 38,
+                end: 47,
+                message: "Module `:defs.bzl` has no symbol `missing`",
+            },
+            Diagnostic {
+                start: 54,
+                end: 70,
+                message: "Cannot find `//bar:defs.bzl`",
+            },
+        ]
+    "#]]
+    .assert_debug_eq(diagnostics(&db, file(&db, "foo/BUCK")));
+}
+
+#[test]
+fn type_check_loaded_symbols() {
+    let db = workspace(&[
+        ("BUCK", "load(\":defs.bzl\", \"rule\")\n\nrule(1)\n"),
+        ("defs.bzl", "def rule(name: str) -> str:\n    return name\n"),
+    ]);
+
+    expect![[r#"
+        [
+            Diagnostic {
+                start: 32,
+                end: 33,
+                message: "Expected `str` for argument `name` of `rule`, got `int`",
+            },
+        ]
+    "#]]
+    .assert_debug_eq(diagnostics(&db, file(&db, "BUCK")));
+}
+
+#[test]
+fn recheck_edited_items() {
+    let mut db = workspace(&[(
+        "BUCK",
+        "x = 1\n\ndef f(y: int) -> int:\n    return y\n\nz = f(x)\n",
+    )]);
+    let file = file(&db, "BUCK");
+    assert_eq!(diagnostics(&db, file), &[]);
+    db.take_logs();
+
+    // The signature of `f` is unchanged, so only `f` is checked again.
+    db.apply_edits(file, [TextEdit::insert(41.into(), " + 1")]);
+    assert_eq!(diagnostics(&db, file), &[]);
+    assert_eq!(executions(&mut db, "type_check_item"), 1);
+}
+
+#[test]
+fn apply_changes() {
+    let mut db = workspace(&[
+        (
+            "a/BUCK",
+            "load(\"//:defs.bzl\", \"rule\")\n\nrule(name = \"a\")\n",
+        ),
+        (
+            "b/BUCK",
+            "load(\"//:defs.bzl\", \"rule\")\n\nrule(name = \"b\")\n",
+        ),
+        ("defs.bzl", "def rule(name):\n    pass\n"),
+    ]);
+    let files = [file(&db, "a/BUCK"), file(&db, "b/BUCK")];
+    let check_all = |db: &Database| {
+        files
+            .iter()
+            .map(|file| diagnostics(db, *file).len())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(check_all(&db), vec![0, 0]);
+    db.take_logs();
+
+    // Editing a `BUCK` file only re-runs its own queries.
+    db.apply_changes([FileChange::new(
+        "a/BUCK".into(),
+        Some("load(\"//:defs.bzl\", \"rule\")\n\nrule(name = \"c\")\n".to_string()),
+    )]);
+    assert_eq!(check_all(&db), vec![0, 0]);
+    assert_eq!(executions(&mut db, "diagnostics"), 1);
+
+    // The exports of `defs.bzl` are unchanged, so neither file is checked
+    // again.
+    db.apply_changes([FileChange::new(
+        "defs.bzl".into(),
+        Some("def rule(name):\n    return None\n".to_string()),
+    )]);
+    assert_eq!(check_all(&db), vec![0, 0]);
+    assert_eq!(executions(&mut db, "diagnostics"), 0);
+
+    // Removing `defs.bzl` breaks both files.
+    db.apply_changes([FileChange::new("defs.bzl".into(), None)]);
+    assert_eq!(check_all(&db), vec![1, 1]);
+    assert_eq!(executions(&mut db, "diagnostics"), 2);
+
+    // Adding a file which is loaded but was never added fixes the files
+    // loading it.
+    db.apply_changes([
+        FileChange::new(
+            "a/BUCK".into(),
+            Some("load(\"//:rules.bzl\", \"rule\")\n\nrule(name = \"a\")\n".to_string()),
+        ),
+        FileChange::new(
+            "defs.bzl".into(),
+            Some("def rule(name):\n    pass\n".to_string()),
+        ),
+    ]);
+    assert_eq!(check_all(&db), vec![1, 0]);
+    db.apply_changes([FileChange::new(
+        "rules.bzl".into(),
+        Some("def rule(name):\n    pass\n".to_string()),
+    )]);
+    assert_eq!(check_all(&db), vec![0, 0]);
+}
+
+#[test]
+fn apply_edits() {
+    let mut db = workspace(&[("BUCK", "x = 1\n\ndef f(y):\n    return y\n")]);
+    let file = file(&db, "BUCK");
+    parse(&db, file);
+    db.take_logs();
+
+    // Each edit applies to the text left by the previous ones.
+    db.apply_edits(
+        file,
+        [
+            TextEdit::replace(TextRange::new(4.into(), 5.into()), "42"),
+            TextEdit::insert(30.into(), " + x"),
+        ],
+    );
+    let text = "x = 42\n\ndef f(y):\n    return y + x\n";
+    assert_eq!(file.text(&db).as_deref(), Some(text));
+
+    // The tree is reparsed incrementally, into the tree parsed from scratch.
+    assert_eq!(
+        parse(&db, file).debug_dump(),
+        File::parse(text).debug_dump()
+    );
+    assert_eq!(executions(&mut db, "parse"), 1);
+}
+
+/// A diamond of modules: `a` loads `b` and `c`, which both load `d`.
+#[cfg(test)]
+const DIAMOND: &[(&str, &str)] = &[
+    (
+        "pkg/a.bzl",
+        "load(\":b.bzl\", \"b\")\nload(\"//lib:c.bzl\", c = \"value\")\n\nresult = b + c\n",
+    ),
+    (
+        "pkg/b.bzl",
+        "load(\"//lib:d.bzl\", \"d\")\n\nb = [d, \"b\"]\n",
+    ),
+    (
+        "lib/c.bzl",
+        "load(\":d.bzl\", \"d\")\n\nvalue = [d, \"c\"]\n",
+    ),
+    ("lib/d.bzl", "d = \"d\"\n"),
+    ("lib/e.bzl", "e = 1\n"),
+];
+
+/// The representation of the given global of the module of the given file.
+#[cfg(test)]
+fn global(db: &Database, path: &str, name: &str) -> String {
+    let module = load_module(db, file(db, path)).unwrap();
+    let module = Interpreter::new().thaw(&module).unwrap();
+    module.get(name).unwrap().repr()
+}
+
+#[test]
+fn load_modules() {
+    let db = workspace(DIAMOND);
+
+    assert_eq!(
+        global(&db, "pkg/a.bzl", "result"),
+        "[\"d\", \"b\", \"d\", \"c\"]"
+    );
+}
+
+#[test]
+fn load_module_errors() {
+    let db = workspace(&[
+        ("cycle/a.bzl", "load(\":b.bzl\", \"b\")\na = b\n"),
+        ("cycle/b.bzl", "load(\":a.bzl\", \"a\")\nb = a\n"),
+        ("private.bzl", "load(\":lib.bzl\", \"_private\")\n"),
+        ("missing.bzl", "load(\"//:other.bzl\", \"x\")\n"),
+        ("loads_broken.bzl", "load(\":broken.bzl\", \"x\")\n"),
+        ("lib.bzl", "public = 1\n_private = 2\n"),
+        ("broken.bzl", "x = 1 // 0\n"),
+    ]);
+    let error = |path: &str| load_module(&db, file(&db, path)).unwrap_err().to_string();
+
+    assert_eq!(
+        error("cycle/a.bzl"),
+        "Load cycle: //cycle:a.bzl -> //cycle:b.bzl -> //cycle:a.bzl"
+    );
+    assert_eq!(
+        error("private.bzl"),
+        "Failed to load `//:private.bzl`: Cannot load private symbol `_private` from `:lib.bzl`"
+    );
+    assert_eq!(
+        error("missing.bzl"),
+        "Failed to load `//:missing.bzl`: Cannot find `//:other.bzl`"
+    );
+    assert_eq!(
+        error("loads_broken.bzl"),
+        "Failed to load `//:broken.bzl`: Division by zero"
+    );
+}
+
+#[test]
+fn load_module_reuse() {
+    let mut db = workspace(DIAMOND);
+    global(&db, "pkg/a.bzl", "result");
+    global(&db, "lib/e.bzl", "e");
+    assert_eq!(executions(&mut db, "load_module"), 5);
+
+    // Editing `c` only evaluates it again, along with `a` which loads it.
+    db.apply_changes([FileChange::new(
+        "lib/c.bzl".into(),
+        Some("value = [\"changed\"]\n".to_string()),
+    )]);
+    assert_eq!(
+        global(&db, "pkg/a.bzl", "result"),
+        "[\"d\", \"b\", \"changed\"]"
+    );
+    assert_eq!(global(&db, "lib/e.bzl", "e"), "1");
+    assert_eq!(executions(&mut db, "load_module"), 2);
+}
@@ -0,0 +1,67 @@
+// cli.rs
+
+use clap::{Args, Parser, Subcommand};
+use getset::Getters;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Getters, PartialEq, Eq, Hash)]
+#[command(
+    author = "pulanski <iopulanski@gmail.com>",
+    version = "0.1.0",
+    about = "A formatter for Starlark code.",
+    bin_name = "buckaroo"
+)]
+#[getset(get = "pub")]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq, Hash)]
+pub enum Command {
+    /// Format Starlark files (e.g. BUILD files and .bzl extensions)
+    #[command(
+        long_about = "Format Starlark files (e.g. BUILD files and .bzl extensions) in one canonical layout, preserving comments.\n\nWith --check, files are left as they are, and a diff is printed for each file which isn't formatted. Exits with 0 if all files are formatted, 1 if some aren't, and 2 on errors."
+    )]
+    Fmt(FmtArgs),
+
+    /// Lint Starlark files, reporting likely mistakes and style issues
+    #[command(
+        long_about = "Lint Starlark files, reporting likely mistakes and style issues (e.g. unused loads or unsorted deps).\n\nDiagnostics of a rule can be suppressed on a line with a `# buildifier: disable=<rule>` comment, either at the end of the line or on the line before. Exits with 0 if no diagnostics are reported, 1 if some are, and 2 on errors."
+    )]
+    Lint(LintArgs),
+}
+
+#[derive(Args, Debug, Getters, PartialEq, Eq, Hash)]
+#[getset(get = "pub")]
+pub struct FmtArgs {
+    /// Check that files are formatted, printing a diff for each file which
+    /// isn't, instead of rewriting them
+    #[clap(long)]
+    check: bool,
+
+    /// The width lines are wrapped at (where possible)
+    #[clap(long, default_value_t = 100)]
+    width: usize,
+
+    /// The files to format, along with the Starlark files below the given
+    /// directories [default: stdin, formatted to stdout]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Args, Debug, Getters, PartialEq, Eq, Hash)]
+#[getset(get = "pub")]
+pub struct LintArgs {
+    /// Apply the fixes of the diagnostics where possible, rewriting the files
+    #[clap(long)]
+    fix: bool,
+
+    /// Disable the given rule (e.g. `unsorted-deps`), which may be repeated
+    #[clap(long, value_name = "RULE")]
+    disable: Vec<String>,
+
+    /// The files to lint, along with the Starlark files below the given
+    /// directories
+    #[clap(required = true)]
+    paths: Vec<PathBuf>,
+}
@@ -0,0 +1,182 @@
+//! **Comments**, attached to the tokens they belong to.
+//!
+//! The parser leaves comments wherever they occur, as trivia. Since the
+//! formatter rebuilds all whitespace, each comment is first attached to a
+//! token, such that it's printed along with that token wherever it ends up:
+//!
+//! - A comment on the same line as the code before it **trails** that code
+//!   (e.g. `":foo",  # Needed for bar.`), and is attached to its last token.
+//! - A comment on a line of its own **leads** the code after it, and is
+//!   attached to its first token.
+//! - A comment at the end of an indented block, indented at least as far as
+//!   the block, **dangles** at the end of that block, and is attached to the
+//!   block's `SUITE`.
+
+use sky_syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// The text of the comment, including the `#`.
+    pub text: String,
+    /// Whether the comment is preceded by a blank line.
+    pub blank_before: bool,
+}
+
+/// The comments attached to a token.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attached {
+    /// Comments on lines of their own before the token.
+    pub leading: Vec<Comment>,
+    /// Comments after the token on the same line.
+    pub trailing: Vec<Comment>,
+    /// Whether the token (after its leading comments) is preceded by a
+    /// blank line.
+    pub blank_before: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Comments {
+    attached: HashMap<SyntaxToken, Attached>,
+    /// Comments dangling at the end of blocks, by `SUITE`. (Blocks ending
+    /// together have their `OUTDENT`s at the same offset, which are equal
+    /// tokens, so the comments can't be attached to those.)
+    dangling: HashMap<SyntaxNode, Vec<Comment>>,
+    /// Comments after the last token of the file.
+    end: Vec<Comment>,
+    /// The number of comments, and how many of them were taken.
+    count: usize,
+    taken: usize,
+}
+
+impl Comments {
+    /// Attaches all comments of the given tree to its tokens.
+    pub fn attach(root: &SyntaxNode) -> Comments {
+        let text = root.text().to_string();
+        let mut comments = Comments::default();
+
+        let mut previous: Option<SyntaxToken> = None;
+        let mut newlines = 0;
+        // Comments on lines of their own, with their columns, waiting for the
+        // token (or block end) they lead.
+        let mut pending: Vec<(Comment, usize)> = Vec::new();
+
+        for token in root
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+        {
+            match token.kind() {
+                SyntaxKind::WHITESPACE | SyntaxKind::NEWLINE => {
+                    newlines += token.text().matches('\n').count();
+                }
+                SyntaxKind::INDENT => {}
+                SyntaxKind::COMMENT => {
+                    comments.count += 1;
+                    let comment = Comment {
+                        text: token.text().trim_end().to_string(),
+                        blank_before: newlines > 1,
+                    };
+                    match &previous {
+                        Some(previous) if newlines == 0 && pending.is_empty() => comments
+                            .attached
+                            .entry(previous.clone())
+                            .or_default()
+                            .trailing
+                            .push(comment),
+                        _ => {
+                            let offset = usize::from(token.text_range().start());
+                            let column = offset - text[..offset].rfind('\n').map_or(0, |it| it + 1);
+                            pending.push((comment, column));
+                        }
+                    }
+                    newlines = 0;
+                }
+                SyntaxKind::OUTDENT => {
+                    let Some(suite) = token.parent() else {
+                        continue;
+                    };
+                    let indent = block_indent(&suite);
+                    let dangling = pending
+                        .iter()
+                        .take_while(|(_, column)| *column >= indent)
+                        .count();
+                    comments
+                        .dangling
+                        .entry(suite)
+                        .or_default()
+                        .extend(pending.drain(..dangling).map(|(it, _)| it));
+                }
+                _ => {
+                    let attached = comments.attached.entry(token.clone()).or_default();
+                    attached.leading.extend(pending.drain(..).map(|(it, _)| it));
+                    attached.blank_before = newlines > 1;
+
+                    previous = Some(token);
+                    newlines = 0;
+                }
+            }
+        }
+        comments.end = pending.into_iter().map(|(it, _)| it).collect();
+
+        comments
+    }
+
+    /// **Takes** the comments attached to the given token, such that each
+    /// comment is only printed once.
+    pub fn take(&mut self, token: &SyntaxToken) -> Attached {
+        let attached = self.attached.remove(token).unwrap_or_default();
+        self.taken += attached.leading.len() + attached.trailing.len();
+
+        attached
+    }
+
+    /// Takes the comments dangling at the end of the given block.
+    pub fn take_dangling(&mut self, suite: &SyntaxNode) -> Vec<Comment> {
+        let dangling = self.dangling.remove(suite).unwrap_or_default();
+        self.taken += dangling.len();
+
+        dangling
+    }
+
+    /// Takes the comments after the last token of the file.
+    pub fn take_end(&mut self) -> Vec<Comment> {
+        let end = std::mem::take(&mut self.end);
+        self.taken += end.len();
+
+        end
+    }
+
+    /// Whether the given token is preceded by a **blank line**, either before
+    /// its leading comments or before itself.
+    pub fn blank_before(&self, token: &SyntaxToken) -> bool {
+        match self.attached.get(token) {
+            Some(it) => match it.leading.first() {
+                Some(comment) => comment.blank_before,
+                None => it.blank_before,
+            },
+            None => false,
+        }
+    }
+
+    /// Whether any comments are attached to the given token.
+    pub fn has_comments(&self, token: &SyntaxToken) -> bool {
+        matches!(
+            self.attached.get(token),
+            Some(it) if !it.leading.is_empty() || !it.trailing.is_empty()
+        )
+    }
+
+    /// Whether any comments haven't been taken yet.
+    pub fn any_left(&self) -> bool {
+        self.taken < self.count
+    }
+}
+
+/// The **indentation** of the given block, i.e. the width of its `INDENT`.
+fn block_indent(suite: &SyntaxNode) -> usize {
+    suite
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .find(|it| it.kind() == SyntaxKind::INDENT)
+        .map_or(0, |indent| indent.text().len())
+}
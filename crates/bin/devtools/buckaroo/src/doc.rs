@@ -0,0 +1,247 @@
+//! A small **document algebra** for pretty printing, in the style of Wadler's
+//! _"A prettier printer"_ (as popularized by prettier).
+//!
+//! A [`Doc`] describes text along with the places it may be broken onto
+//! several lines. Each [`Doc::group`] is printed on a single line if it fits
+//! within the width, and otherwise has all of its own line breaks taken.
+
+/// The number of spaces per level of indentation.
+pub const INDENT_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Doc {
+    /// Literal text.
+    Text(String),
+    /// A sequence of documents.
+    Concat(Vec<Doc>),
+    /// A space, or a line break if the enclosing group is broken.
+    Line,
+    /// Nothing, or a line break if the enclosing group is broken.
+    SoftLine,
+    /// A line break, always, which breaks all enclosing groups.
+    HardLine,
+    /// A line break unless at the start of a line (e.g. before a comment on
+    /// a line of its own), which breaks all enclosing groups.
+    FreshLine,
+    /// A document whose line breaks are indented by one more level.
+    Indent(Box<Doc>),
+    /// A document printed on a single line if it fits, where `broken` is
+    /// whether it contains a hard line break (and so never fits).
+    Group { doc: Box<Doc>, broken: bool },
+    /// A document only printed if the enclosing group is broken.
+    IfBreak(Box<Doc>),
+    /// Text printed at the end of the current line (e.g. a trailing
+    /// comment), which breaks all enclosing groups.
+    LineSuffix(String),
+}
+
+impl Doc {
+    pub fn nil() -> Doc {
+        Doc::Concat(Vec::new())
+    }
+
+    pub fn text(text: impl Into<String>) -> Doc {
+        Doc::Text(text.into())
+    }
+
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Doc {
+        Doc::Concat(docs.into_iter().collect())
+    }
+
+    pub fn indent(doc: Doc) -> Doc {
+        Doc::Indent(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Doc {
+        let broken = doc.has_hard_break();
+        Doc::Group {
+            doc: Box::new(doc),
+            broken,
+        }
+    }
+
+    pub fn if_break(doc: Doc) -> Doc {
+        Doc::IfBreak(Box::new(doc))
+    }
+
+    /// Joins the given documents with the given separator.
+    pub fn join(docs: impl IntoIterator<Item = Doc>, separator: Doc) -> Doc {
+        let mut joined = Vec::new();
+        for (index, doc) in docs.into_iter().enumerate() {
+            if index > 0 {
+                joined.push(separator.clone());
+            }
+            joined.push(doc);
+        }
+
+        Doc::Concat(joined)
+    }
+
+    /// Whether this document contains a **hard line break**, i.e. a
+    /// [`Doc::HardLine`] or a [`Doc::LineSuffix`] (outside of
+    /// [`Doc::IfBreak`], which only matters once broken anyway).
+    pub fn has_hard_break(&self) -> bool {
+        match self {
+            Doc::HardLine | Doc::FreshLine | Doc::LineSuffix(_) => true,
+            Doc::Concat(docs) => docs.iter().any(Doc::has_hard_break),
+            Doc::Indent(doc) => doc.has_hard_break(),
+            Doc::Group { broken, .. } => *broken,
+            Doc::Text(_) | Doc::Line | Doc::SoftLine | Doc::IfBreak(_) => false,
+        }
+    }
+
+    /// **Prints** this document, breaking groups which don't fit within the
+    /// given width.
+    pub fn print(&self, width: usize) -> String {
+        Printer::new(width).print(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+struct Printer<'a> {
+    width: usize,
+    out: String,
+    /// The column the next text is printed at.
+    column: usize,
+    line_suffixes: Vec<String>,
+    stack: Vec<(usize, Mode, &'a Doc)>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(width: usize) -> Printer<'a> {
+        Printer {
+            width,
+            out: String::new(),
+            column: 0,
+            line_suffixes: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    fn print(mut self, doc: &'a Doc) -> String {
+        self.stack.push((0, Mode::Break, doc));
+
+        while let Some((indent, mode, doc)) = self.stack.pop() {
+            match doc {
+                Doc::Text(text) => self.text(text),
+                Doc::Concat(docs) => self
+                    .stack
+                    .extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => self.newline(indent),
+                Doc::Line => self.text(" "),
+                Doc::SoftLine => {}
+                Doc::HardLine => self.newline(indent),
+                Doc::FreshLine => {
+                    if !self.at_line_start() {
+                        self.newline(indent);
+                    }
+                }
+                Doc::Indent(doc) => self.stack.push((indent + INDENT_WIDTH, mode, doc)),
+                Doc::Group { doc, broken } => {
+                    let mode = if mode == Mode::Flat || (!broken && self.fits(doc)) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    self.stack.push((indent, mode, doc));
+                }
+                Doc::IfBreak(doc) => {
+                    if mode == Mode::Break {
+                        self.stack.push((indent, mode, doc));
+                    }
+                }
+                Doc::LineSuffix(text) => self.line_suffixes.push(text.clone()),
+            }
+        }
+
+        self.flush_line_suffixes();
+        self.trim_trailing_spaces();
+
+        self.out
+    }
+
+    fn text(&mut self, text: &str) {
+        self.out.push_str(text);
+        match text.rfind('\n') {
+            Some(index) => self.column = text.len() - index - 1,
+            None => self.column += text.len(),
+        }
+    }
+
+    fn newline(&mut self, indent: usize) {
+        self.flush_line_suffixes();
+        self.trim_trailing_spaces();
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(indent));
+        self.column = indent;
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.line_suffixes.is_empty()
+            && self
+                .out
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .is_empty()
+    }
+
+    fn flush_line_suffixes(&mut self) {
+        for suffix in std::mem::take(&mut self.line_suffixes) {
+            self.out.push_str(&suffix);
+        }
+    }
+
+    fn trim_trailing_spaces(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+    }
+
+    /// Whether the given document **fits** on the rest of the current line
+    /// when printed flat, along with whatever follows it up to the next line
+    /// break.
+    fn fits(&self, doc: &Doc) -> bool {
+        let mut remaining = self.width as isize - self.column as isize;
+        let mut stack = vec![(Mode::Flat, doc)];
+        let mut rest = self.stack.iter().rev();
+
+        while remaining >= 0 {
+            let (mode, doc) = match stack.pop() {
+                Some(it) => it,
+                None => match rest.next() {
+                    Some((_, mode, doc)) => (*mode, *doc),
+                    None => return true,
+                },
+            };
+
+            match doc {
+                Doc::Text(text) => match text.find('\n') {
+                    Some(index) => return remaining >= index as isize,
+                    None => remaining -= text.len() as isize,
+                },
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (mode, doc))),
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+                Doc::Line => remaining -= 1,
+                Doc::SoftLine | Doc::LineSuffix(_) => {}
+                Doc::HardLine | Doc::FreshLine => return true,
+                Doc::Indent(doc) => stack.push((mode, doc)),
+                Doc::Group { doc, broken } => {
+                    stack.push((if *broken { Mode::Break } else { mode }, doc))
+                }
+                Doc::IfBreak(doc) => {
+                    if mode == Mode::Break {
+                        stack.push((mode, doc));
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
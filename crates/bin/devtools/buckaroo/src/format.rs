@@ -0,0 +1,1030 @@
+//! The **formatter**, printing a syntax tree in its one canonical layout.
+//!
+//! The layout follows buildifier:
+//!
+//! - Statements are on lines of their own, indented by four spaces per
+//!   block, with at most one blank line between them.
+//! - Binary operators, `=` and `:` (in dicts) are surrounded by spaces, while
+//!   brackets have none inside them.
+//! - Lists of elements (e.g. call arguments, list elements or parameters)
+//!   are on a single line if they fit, and otherwise have one element per
+//!   line with a trailing comma. Calls of rules (i.e. top-level calls with a
+//!   `name` and further arguments) always have one argument per line.
+//! - Chains of binary operations within brackets which don't fit are broken
+//!   after each operator.
+//! - Strings use double quotes, unless that would need more escaping.
+//! - The symbols of `load` statements are sorted, as are the string lists of
+//!   attributes like `deps` and `srcs` (which are deduplicated as well).
+//! - Comments are kept with the code they belong to (see [`comments`]).
+//!
+//! [`comments`]: crate::comments
+
+use crate::{
+    comments::{Attached, Comment, Comments},
+    doc::Doc,
+};
+use sky_analyzer::labels::{self, SORTED_ATTRIBUTES};
+use sky_syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use std::{cmp::Ordering, collections::HashSet};
+
+/// Nodes which merely group the elements of a list within their parent
+/// (e.g. the `ARGUMENTS` between the parentheses of a call).
+const LIST_NODES: &[SyntaxKind] = &[
+    SyntaxKind::ARGUMENTS,
+    SyntaxKind::ENTRIES,
+    SyntaxKind::EXPRESSION,
+    SyntaxKind::PARAMETERS,
+];
+
+/// When to print a **trailing comma** after the last element of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrailingComma {
+    /// Never, as in a parenthesized expression.
+    Never,
+    /// When the list is broken over several lines.
+    IfBroken,
+    /// Always, as in a tuple of one element.
+    Always,
+}
+
+/// An **item** of a list, e.g. an argument of a call.
+struct Item {
+    element: SyntaxElement,
+    /// The comma following the element, if any.
+    comma: Option<SyntaxToken>,
+    blank_before: bool,
+}
+
+type Format = fn(&mut Formatter, &SyntaxElement) -> Doc;
+
+pub(crate) struct Formatter {
+    comments: Comments,
+}
+
+impl Formatter {
+    pub(crate) fn new(root: &SyntaxNode) -> Formatter {
+        Formatter {
+            comments: Comments::attach(root),
+        }
+    }
+
+    /// Whether any comments haven't been printed, i.e. would be **lost** by
+    /// formatting.
+    pub(crate) fn comments_left(&self) -> bool {
+        self.comments.any_left()
+    }
+
+    pub(crate) fn file(&mut self, file: &SyntaxNode) -> Doc {
+        let statements = file.children().collect::<Vec<_>>();
+        let mut docs = self.statements(&statements);
+
+        for comment in self.comments.take_end() {
+            if !docs.is_empty() {
+                docs.push(Doc::HardLine);
+                if comment.blank_before {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            docs.push(Doc::text(comment.text));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// **Statements**, on lines of their own, keeping single blank lines
+    /// between them.
+    fn statements(&mut self, statements: &[SyntaxNode]) -> Vec<Doc> {
+        let mut docs = Vec::new();
+        for (index, statement) in statements.iter().enumerate() {
+            if index > 0 {
+                docs.push(Doc::HardLine);
+                if matches!(first_token(statement), Some(it) if self.comments.blank_before(&it)) {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            docs.push(self.statement(statement));
+        }
+
+        docs
+    }
+
+    fn statement(&mut self, statement: &SyntaxNode) -> Doc {
+        match statement.kind() {
+            SyntaxKind::STATEMENT | SyntaxKind::SMALL_STMT => match statement.first_child() {
+                Some(it) => self.statement(&it),
+                None => Doc::nil(),
+            },
+            SyntaxKind::DEF_STMT => self.def_stmt(statement),
+            SyntaxKind::IF_STMT | SyntaxKind::FOR_STMT => self.compound(statement),
+            SyntaxKind::SIMPLE_STMT => self.simple_stmt(statement),
+            SyntaxKind::LOAD_STMT => self.load_stmt(statement),
+            SyntaxKind::EXPR_STMT => self.children(statement, ""),
+            _ => self.children(statement, " "),
+        }
+    }
+
+    fn def_stmt(&mut self, def: &SyntaxNode) -> Doc {
+        let elements = list_children(def);
+        let (Some(open), Some(close)) = (
+            position(&elements, SyntaxKind::LPAREN),
+            position(&elements, SyntaxKind::RPAREN),
+        ) else {
+            return self.verbatim(def);
+        };
+
+        let mut docs = Vec::new();
+        for (index, element) in elements[..open].iter().enumerate() {
+            if index > 0 {
+                docs.push(Doc::text(" "));
+            }
+            docs.push(self.element(element));
+        }
+        docs.push(self.delimited(
+            &elements[open..=close],
+            TrailingComma::IfBroken,
+            false,
+            Formatter::element,
+        ));
+        for element in &elements[close + 1..] {
+            // The return type, e.g. `) -> str:`.
+            if matches!(
+                element.kind(),
+                SyntaxKind::THIN_ARROW | SyntaxKind::TYPE_ANNOTATION
+            ) {
+                docs.push(Doc::text(" "));
+            }
+            docs.push(self.element(element));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// An `if`, `elif`, `else` or `for` **clause**, followed by its block.
+    fn compound(&mut self, node: &SyntaxNode) -> Doc {
+        let mut docs = Vec::new();
+        for (index, element) in significant_children(node).iter().enumerate() {
+            match element.kind() {
+                SyntaxKind::ELIF_CLAUSES | SyntaxKind::ELSE_CLAUSE => {
+                    docs.push(Doc::HardLine);
+                    docs.push(self.compound(element.as_node().unwrap()));
+                    continue;
+                }
+                SyntaxKind::ELIF_KW | SyntaxKind::ELSE_KW if index > 0 => docs.push(Doc::HardLine),
+                SyntaxKind::COLON | SyntaxKind::SUITE => {}
+                _ if index > 0 => docs.push(Doc::text(" ")),
+                _ => {}
+            }
+            docs.push(self.element(element));
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// An indented **block** of statements, which is put on lines of its own
+    /// even if written on the line of its clause (e.g. `if x: pass`).
+    fn suite(&mut self, suite: &SyntaxNode) -> Doc {
+        let statements = suite.children().collect::<Vec<_>>();
+        let mut docs = vec![Doc::HardLine];
+        docs.extend(self.statements(&statements));
+
+        // Comments dangling at the end of the block.
+        for comment in self.comments.take_dangling(suite) {
+            docs.push(Doc::HardLine);
+            if comment.blank_before {
+                docs.push(Doc::HardLine);
+            }
+            docs.push(Doc::text(comment.text));
+        }
+
+        Doc::indent(Doc::Concat(docs))
+    }
+
+    /// **Small statements**, which are put on lines of their own (rather than
+    /// separated by `;`).
+    fn simple_stmt(&mut self, node: &SyntaxNode) -> Doc {
+        let mut docs = Vec::new();
+        for element in significant_children(node) {
+            match element {
+                SyntaxElement::Node(it) => {
+                    if !docs.is_empty() {
+                        docs.push(Doc::HardLine);
+                    }
+                    docs.push(self.statement(&it));
+                }
+                SyntaxElement::Token(it) => docs.push(self.comments_of(&it)),
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// A `load` statement, with its symbols **sorted** by the names they're
+    /// bound to.
+    fn load_stmt(&mut self, load: &SyntaxNode) -> Doc {
+        let elements = significant_children(load);
+        let (Some(open), Some(close)) = (
+            position(&elements, SyntaxKind::LPAREN),
+            position(&elements, SyntaxKind::RPAREN),
+        ) else {
+            return self.verbatim(load);
+        };
+
+        let mut items = self.items(&elements[open + 1..close]);
+        if items.len() > 1 {
+            let mut symbols = items.split_off(1);
+            symbols.sort_by_cached_key(|it| load_symbol_name(&it.element));
+            self.dedup(&mut symbols, |it| Some(source(&it.element)));
+            for symbol in &mut symbols {
+                symbol.blank_before = false;
+            }
+            items.extend(symbols);
+        }
+
+        let mut docs = Vec::new();
+        for element in &elements[..open] {
+            docs.push(self.element(element));
+        }
+        docs.push(self.list(
+            &elements[open],
+            items,
+            &elements[close],
+            TrailingComma::IfBroken,
+            false,
+            Formatter::element,
+        ));
+
+        Doc::Concat(docs)
+    }
+
+    /// Any node of an **expression**.
+    fn node(&mut self, node: &SyntaxNode) -> Doc {
+        match node.kind() {
+            SyntaxKind::TEST
+            | SyntaxKind::PRIMARY_EXPR
+            | SyntaxKind::DOT_SUFFIX
+            | SyntaxKind::SLICE_SUFFIX
+            | SyntaxKind::TYPE_ANNOTATION => self.children(node, ""),
+            SyntaxKind::EXPRESSION | SyntaxKind::LOOP_VARIABLES | SyntaxKind::PARAMETERS => {
+                self.flat_list(node)
+            }
+            SyntaxKind::BINARY_EXPR => self.binary_expr(node),
+            SyntaxKind::BINOP
+            | SyntaxKind::IF_EXPR
+            | SyntaxKind::COMP_CLAUSE
+            | SyntaxKind::LOAD_SYMBOL => self.children(node, " "),
+            SyntaxKind::UNARY_EXPR => match first_token(node) {
+                Some(it) if it.kind() == SyntaxKind::NOT_KW => self.children(node, " "),
+                _ => self.children(node, ""),
+            },
+            SyntaxKind::ARGUMENT => match first_token(node) {
+                Some(it) if matches!(it.kind(), SyntaxKind::STAR | SyntaxKind::DSTAR) => {
+                    self.children(node, "")
+                }
+                _ => self.children(node, " "),
+            },
+            SyntaxKind::PARAMETER => {
+                // E.g. `x = 1`, `x: int = 1` or `*args: int`.
+                let mut docs = Vec::new();
+                let mut previous = None;
+                for element in significant_children(node) {
+                    let is_spaced =
+                        !matches!(previous, None | Some(SyntaxKind::STAR | SyntaxKind::DSTAR));
+                    if is_spaced && element.kind() != SyntaxKind::COLON {
+                        docs.push(Doc::text(" "));
+                    }
+                    previous = Some(element.kind());
+                    docs.push(self.element(&element));
+                }
+                Doc::Concat(docs)
+            }
+            SyntaxKind::ENTRY | SyntaxKind::LAMBDA_EXPR => {
+                // A space after the `:`, but not before it.
+                let mut docs = Vec::new();
+                for element in significant_children(node) {
+                    if !docs.is_empty() && element.kind() != SyntaxKind::COLON {
+                        docs.push(Doc::text(" "));
+                    }
+                    docs.push(self.element(&element));
+                }
+                Doc::Concat(docs)
+            }
+            SyntaxKind::OPERAND => self.operand(node),
+            SyntaxKind::CALL_SUFFIX => self.delimited(
+                &list_children(node),
+                TrailingComma::IfBroken,
+                is_rule_call(node),
+                Formatter::argument,
+            ),
+            SyntaxKind::LIST_EXPR | SyntaxKind::DICT_EXPR => self.delimited(
+                &list_children(node),
+                TrailingComma::IfBroken,
+                false,
+                Formatter::element,
+            ),
+            SyntaxKind::LIST_COMP | SyntaxKind::DICT_COMP => self.comprehension(node),
+            _ => self.verbatim(node),
+        }
+    }
+
+    fn operand(&mut self, operand: &SyntaxNode) -> Doc {
+        let elements = list_children(operand);
+        if !matches!(elements.first(), Some(it) if it.kind() == SyntaxKind::LPAREN) {
+            return self.children(operand, "");
+        }
+
+        // A tuple, unless a single parenthesized expression.
+        let tests = operand
+            .children()
+            .filter(|it| it.kind() == SyntaxKind::EXPRESSION)
+            .map(|it| it.children().count())
+            .sum::<usize>();
+        let trailing_comma = match (tests, position(&elements, SyntaxKind::COMMA)) {
+            (1, None) => TrailingComma::Never,
+            (1, Some(_)) => TrailingComma::Always,
+            _ => TrailingComma::IfBroken,
+        };
+
+        self.delimited(&elements, trailing_comma, false, Formatter::element)
+    }
+
+    /// A **binary expression**, whose chain of operations (e.g. `a + b + c`)
+    /// is broken after each operator if it doesn't fit, which is only
+    /// possible within brackets. A last operand in brackets (e.g. the list in
+    /// `glob(["*.h"]) + [...]`) is broken itself instead.
+    fn binary_expr(&mut self, node: &SyntaxNode) -> Doc {
+        if !is_in_brackets(node) {
+            return self.children(node, " ");
+        }
+        let elements = chain(node);
+        let [first, rest @ ..] = elements.as_slice() else {
+            return self.verbatim(node);
+        };
+        let hug = matches!(rest.last(), Some(it) if is_bracketed(it));
+
+        let first = self.element(first);
+        let mut docs = Vec::new();
+        for (index, element) in rest.iter().enumerate() {
+            if element.kind() != SyntaxKind::BINOP {
+                if !hug || index + 1 < rest.len() {
+                    docs.push(self.element(element));
+                }
+                continue;
+            }
+
+            docs.push(Doc::text(" "));
+            docs.push(self.element(element));
+            if hug && index + 2 == rest.len() {
+                docs.push(Doc::text(" "));
+            } else {
+                docs.push(Doc::Line);
+            }
+        }
+        let last = match rest.last() {
+            Some(it) if hug => self.element(it),
+            _ => Doc::nil(),
+        };
+
+        // The operands after the first are indented, unless the chain is all
+        // there is within parentheses.
+        let rest = if is_parenthesized(node) {
+            Doc::Concat(docs)
+        } else {
+            Doc::indent(Doc::Concat(docs))
+        };
+
+        Doc::concat([Doc::group(Doc::concat([first, rest])), last])
+    }
+
+    fn comprehension(&mut self, node: &SyntaxNode) -> Doc {
+        let elements = significant_children(node);
+        let [open, body @ .., close] = elements.as_slice() else {
+            return self.verbatim(node);
+        };
+
+        let mut docs = vec![Doc::SoftLine];
+        for (index, element) in body.iter().enumerate() {
+            if index > 0 {
+                docs.push(Doc::Line);
+            }
+            docs.push(self.element(element));
+        }
+        let open = self.element(open);
+        let (close, _, trailing) = self.close(close);
+
+        Doc::concat([
+            Doc::group(Doc::concat([
+                open,
+                Doc::indent(Doc::Concat(docs)),
+                Doc::SoftLine,
+                close,
+            ])),
+            trailing,
+        ])
+    }
+
+    /// An **argument** of a call, whose list is sorted if it's the value of
+    /// an attribute like `deps`.
+    fn argument(&mut self, element: &SyntaxElement) -> Doc {
+        let list = match element {
+            SyntaxElement::Node(it) => sorted_list(it),
+            SyntaxElement::Token(_) => None,
+        };
+        let Some(list) = list else {
+            return self.element(element);
+        };
+
+        // The `TEST`, `PRIMARY_EXPR` and `OPERAND` around the list print
+        // nothing but the list.
+        let mut docs = Vec::new();
+        for element in significant_children(element.as_node().unwrap()) {
+            match element {
+                SyntaxElement::Token(it) => {
+                    docs.push(self.token(&it));
+                    docs.push(Doc::text(" "));
+                }
+                SyntaxElement::Node(_) => docs.push(self.sorted_list(&list)),
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// A list of strings, **sorted** (as labels) and deduplicated. Each run
+    /// of elements, separated by blank lines, is sorted on its own.
+    fn sorted_list(&mut self, list: &SyntaxNode) -> Doc {
+        let elements = list_children(list);
+        let [open, inner @ .., close] = elements.as_slice() else {
+            return self.verbatim(list);
+        };
+
+        let mut runs: Vec<Vec<Item>> = Vec::new();
+        for item in self.items(inner) {
+            match runs.last_mut() {
+                Some(run) if !item.blank_before => run.push(item),
+                _ => runs.push(vec![item]),
+            }
+        }
+        let mut items = Vec::new();
+        for mut run in runs {
+            let blank_before = run[0].blank_before;
+            run.sort_by(|a, b| label_order(&a.element, &b.element));
+            for (index, item) in run.iter_mut().enumerate() {
+                item.blank_before = index == 0 && blank_before;
+            }
+            items.extend(run);
+        }
+        self.dedup(&mut items, |it| string_value(&it.element));
+
+        self.list(
+            open,
+            items,
+            close,
+            TrailingComma::IfBroken,
+            false,
+            Formatter::element,
+        )
+    }
+
+    /// Removes **duplicate** items (keeping the first), unless they have
+    /// comments, which would be lost.
+    fn dedup(&self, items: &mut Vec<Item>, key: impl Fn(&Item) -> Option<String>) {
+        let mut seen = HashSet::new();
+        items.retain(|item| match key(item) {
+            Some(key) => seen.insert(key) || self.has_comments(item),
+            None => true,
+        });
+    }
+
+    fn has_comments(&self, item: &Item) -> bool {
+        let tokens = match &item.element {
+            SyntaxElement::Node(it) => tokens(it),
+            SyntaxElement::Token(it) => vec![it.clone()],
+        };
+
+        tokens
+            .iter()
+            .chain(&item.comma)
+            .any(|it| self.comments.has_comments(it))
+    }
+
+    /// A list of elements between **delimiters**, i.e. the given elements
+    /// are the opening delimiter (e.g. `(`), the elements along with their
+    /// commas, and the closing delimiter (e.g. `)`).
+    fn delimited(
+        &mut self,
+        elements: &[SyntaxElement],
+        trailing_comma: TrailingComma,
+        force_break: bool,
+        format: Format,
+    ) -> Doc {
+        match elements {
+            [open, inner @ .., close] => {
+                let items = self.items(inner);
+                self.list(open, items, close, trailing_comma, force_break, format)
+            }
+            _ => Doc::Concat(elements.iter().map(|it| self.element(it)).collect()),
+        }
+    }
+
+    /// The **items** of a list, i.e. its elements along with their commas.
+    fn items(&self, elements: &[SyntaxElement]) -> Vec<Item> {
+        let mut items: Vec<Item> = Vec::new();
+        for element in elements {
+            match element {
+                SyntaxElement::Token(it) if it.kind() == SyntaxKind::COMMA => {
+                    if let Some(item) = items.last_mut() {
+                        item.comma = Some(it.clone());
+                    }
+                }
+                _ => items.push(Item {
+                    element: element.clone(),
+                    comma: None,
+                    blank_before: !items.is_empty()
+                        && matches!(
+                            first_token_of(element),
+                            Some(it) if self.comments.blank_before(&it)
+                        ),
+                }),
+            }
+        }
+
+        items
+    }
+
+    /// A **list** of items, on a single line if it fits (and isn't forced to
+    /// break), and otherwise with one item per line.
+    fn list(
+        &mut self,
+        open: &SyntaxElement,
+        items: Vec<Item>,
+        close: &SyntaxElement,
+        trailing_comma: TrailingComma,
+        force_break: bool,
+        format: Format,
+    ) -> Doc {
+        let open = self.element(open);
+        let count = items.len();
+
+        let mut docs = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            if index == 0 {
+                docs.push(Doc::SoftLine);
+            } else {
+                docs.push(Doc::text(","));
+                if item.blank_before {
+                    docs.push(Doc::HardLine);
+                }
+                docs.push(Doc::Line);
+            }
+            docs.push(format(self, &item.element));
+            if let Some(comma) = &item.comma {
+                docs.push(self.comments_of(comma));
+            }
+        }
+        match trailing_comma {
+            _ if count == 0 => {}
+            TrailingComma::Never => {}
+            TrailingComma::IfBroken => docs.push(Doc::if_break(Doc::text(","))),
+            TrailingComma::Always => docs.push(Doc::text(",")),
+        }
+
+        // Comments before the closing delimiter dangle at the end of the list.
+        let (close, dangling, trailing) = self.close(close);
+        for comment in dangling {
+            docs.push(Doc::HardLine);
+            if comment.blank_before && count > 0 {
+                docs.push(Doc::HardLine);
+            }
+            docs.push(Doc::text(comment.text));
+        }
+
+        if docs.is_empty() {
+            return Doc::concat([open, close, trailing]);
+        }
+
+        let list = Doc::concat([open, Doc::indent(Doc::Concat(docs)), Doc::SoftLine, close]);
+        let list = if force_break {
+            Doc::Group {
+                doc: Box::new(list),
+                broken: true,
+            }
+        } else {
+            Doc::group(list)
+        };
+
+        Doc::concat([list, trailing])
+    }
+
+    /// A list of elements on a **single line**, e.g. the parameters of a
+    /// lambda.
+    fn flat_list(&mut self, node: &SyntaxNode) -> Doc {
+        let mut docs = Vec::new();
+        for (index, item) in self.items(&significant_children(node)).iter().enumerate() {
+            if index > 0 {
+                docs.push(Doc::text(", "));
+            }
+            docs.push(self.element(&item.element));
+            if let Some(comma) = &item.comma {
+                docs.push(self.comments_of(comma));
+            }
+        }
+
+        Doc::Concat(docs)
+    }
+
+    /// The significant **children** of the given node, separated by the given
+    /// separator.
+    fn children(&mut self, node: &SyntaxNode, separator: &str) -> Doc {
+        let docs = significant_children(node)
+            .iter()
+            .map(|it| self.element(it))
+            .collect::<Vec<_>>();
+
+        Doc::join(docs, Doc::text(separator))
+    }
+
+    fn element(&mut self, element: &SyntaxElement) -> Doc {
+        match element {
+            SyntaxElement::Node(it) if it.kind() == SyntaxKind::SUITE => self.suite(it),
+            SyntaxElement::Node(it) => self.node(it),
+            SyntaxElement::Token(it) => self.token(it),
+        }
+    }
+
+    /// A **token**, along with its comments.
+    fn token(&mut self, token: &SyntaxToken) -> Doc {
+        let text = match token.kind() {
+            SyntaxKind::STRING | SyntaxKind::BYTES => normalize_quotes(token.text()),
+            _ => token.text().to_string(),
+        };
+        let attached = self.comments.take(token);
+
+        let mut docs = leading(&attached);
+        docs.push(Doc::text(text));
+        docs.extend(trailing(&attached.trailing));
+
+        Doc::Concat(docs)
+    }
+
+    /// The comments of a token which isn't printed itself (e.g. a comma,
+    /// which is printed wherever the layout needs one).
+    fn comments_of(&mut self, token: &SyntaxToken) -> Doc {
+        let attached = self.comments.take(token);
+        let comments = attached
+            .leading
+            .into_iter()
+            .chain(attached.trailing)
+            .collect::<Vec<_>>();
+
+        Doc::Concat(trailing(&comments))
+    }
+
+    /// A **closing delimiter**, its leading comments (which dangle at the end
+    /// of the list) and its trailing comments (which are printed after the
+    /// list, such that they don't break it).
+    fn close(&mut self, close: &SyntaxElement) -> (Doc, Vec<Comment>, Doc) {
+        match close {
+            SyntaxElement::Token(it) => {
+                let attached = self.comments.take(it);
+                (
+                    Doc::text(it.text()),
+                    attached.leading,
+                    Doc::Concat(trailing(&attached.trailing)),
+                )
+            }
+            SyntaxElement::Node(_) => (self.element(close), Vec::new(), Doc::nil()),
+        }
+    }
+
+    /// A node printed **verbatim**, e.g. one the formatter doesn't know.
+    fn verbatim(&mut self, node: &SyntaxNode) -> Doc {
+        for token in tokens(node) {
+            self.comments.take(&token);
+        }
+
+        Doc::text(source(&SyntaxElement::Node(node.clone())))
+    }
+}
+
+/// The **leading comments** of a token, each on a line of its own. Whether
+/// there's a blank line before the first one is up to the caller.
+fn leading(attached: &Attached) -> Vec<Doc> {
+    let mut docs = Vec::new();
+    if !attached.leading.is_empty() {
+        docs.push(Doc::FreshLine);
+    }
+    for (index, comment) in attached.leading.iter().enumerate() {
+        if index > 0 && comment.blank_before {
+            docs.push(Doc::HardLine);
+        }
+        docs.push(Doc::text(comment.text.clone()));
+        docs.push(Doc::HardLine);
+    }
+    if !attached.leading.is_empty() && attached.blank_before {
+        docs.push(Doc::HardLine);
+    }
+
+    docs
+}
+
+/// The **trailing comments** of a token, at the end of its line.
+fn trailing(comments: &[Comment]) -> Vec<Doc> {
+    comments
+        .iter()
+        .map(|it| Doc::LineSuffix(format!("  {}", it.text)))
+        .collect()
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE
+            | SyntaxKind::COMMENT
+            | SyntaxKind::NEWLINE
+            | SyntaxKind::INDENT
+            | SyntaxKind::OUTDENT
+    )
+}
+
+/// The children of the given node which aren't trivia.
+fn significant_children(node: &SyntaxNode) -> Vec<SyntaxElement> {
+    node.children_with_tokens()
+        .filter(|it| !is_trivia(it.kind()))
+        .collect()
+}
+
+/// The significant children of the given node, looking through the nodes
+/// which merely group the elements of a list within it.
+fn list_children(node: &SyntaxNode) -> Vec<SyntaxElement> {
+    let mut children = Vec::new();
+    for child in significant_children(node) {
+        match child {
+            SyntaxElement::Node(it) if LIST_NODES.contains(&it.kind()) => {
+                children.extend(significant_children(&it))
+            }
+            it => children.push(it),
+        }
+    }
+
+    children
+}
+
+fn position(elements: &[SyntaxElement], kind: SyntaxKind) -> Option<usize> {
+    elements.iter().position(|it| it.kind() == kind)
+}
+
+/// All tokens of the given node (including trivia).
+fn tokens(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .collect()
+}
+
+fn first_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    tokens(node).into_iter().find(|it| !is_trivia(it.kind()))
+}
+
+fn first_token_of(element: &SyntaxElement) -> Option<SyntaxToken> {
+    match element {
+        SyntaxElement::Node(it) => first_token(it),
+        SyntaxElement::Token(it) => Some(it.clone()),
+    }
+}
+
+/// The significant tokens of the given element.
+fn significant_tokens(element: &SyntaxElement) -> Vec<SyntaxToken> {
+    match element {
+        SyntaxElement::Node(it) => tokens(it)
+            .into_iter()
+            .filter(|it| !is_trivia(it.kind()))
+            .collect(),
+        SyntaxElement::Token(it) => vec![it.clone()],
+    }
+}
+
+/// The **source** of the given element, without surrounding trivia.
+fn source(element: &SyntaxElement) -> String {
+    element.to_string().trim().to_string()
+}
+
+/// The operands and operators of the **chain** of operations of the given
+/// binary expression, i.e. looking through the operands which are binary
+/// expressions with the same operator.
+fn chain(node: &SyntaxNode) -> Vec<SyntaxElement> {
+    let operator = |node: &SyntaxNode| {
+        node.children()
+            .find(|it| it.kind() == SyntaxKind::BINOP)
+            .and_then(|it| first_token(&it))
+            .map(|it| it.kind())
+    };
+
+    let mut elements = Vec::new();
+    for element in significant_children(node) {
+        match &element {
+            SyntaxElement::Node(it)
+                if it.kind() == SyntaxKind::BINARY_EXPR && operator(it) == operator(node) =>
+            {
+                elements.extend(chain(it))
+            }
+            _ => elements.push(element),
+        }
+    }
+
+    elements
+}
+
+/// Whether the given node is within **brackets**, where lines may be broken.
+fn is_in_brackets(node: &SyntaxNode) -> bool {
+    for ancestor in node.ancestors().skip(1) {
+        match ancestor.kind() {
+            SyntaxKind::CALL_SUFFIX
+            | SyntaxKind::SLICE_SUFFIX
+            | SyntaxKind::LIST_EXPR
+            | SyntaxKind::DICT_EXPR
+            | SyntaxKind::LIST_COMP
+            | SyntaxKind::DICT_COMP => return true,
+            SyntaxKind::OPERAND if matches!(first_token(&ancestor), Some(it) if it.kind() == SyntaxKind::LPAREN) => {
+                return true
+            }
+            SyntaxKind::PARAMETERS if matches!(ancestor.parent(), Some(it) if it.kind() == SyntaxKind::DEF_STMT) => {
+                return true
+            }
+            SyntaxKind::STATEMENT | SyntaxKind::SMALL_STMT | SyntaxKind::SUITE => return false,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Whether the given element is an operand ending in **brackets**, e.g. a
+/// list or a call.
+fn is_bracketed(element: &SyntaxElement) -> bool {
+    element.kind() == SyntaxKind::PRIMARY_EXPR
+        && matches!(
+            significant_tokens(element).last().map(|it| it.kind()),
+            Some(SyntaxKind::RPAREN | SyntaxKind::RBRACKET | SyntaxKind::RBRACE)
+        )
+}
+
+/// Whether the given expression is all there is within **parentheses**, e.g.
+/// `(a + b)` (unlike `(a + b, c)`).
+fn is_parenthesized(node: &SyntaxNode) -> bool {
+    let Some(expression) = node
+        .ancestors()
+        .skip(1)
+        .find(|it| it.kind() != SyntaxKind::TEST)
+    else {
+        return false;
+    };
+
+    expression.kind() == SyntaxKind::EXPRESSION
+        && expression.children().count() == 1
+        && matches!(
+            expression.parent(),
+            Some(it) if it.kind() == SyntaxKind::OPERAND
+                && matches!(first_token(&it), Some(it) if it.kind() == SyntaxKind::LPAREN)
+        )
+}
+
+/// Whether the given call is the call of a **rule**, i.e. a top-level call
+/// with a `name` and further arguments.
+fn is_rule_call(call: &SyntaxNode) -> bool {
+    let parent = call.ancestors().skip(1).find(|it| {
+        !matches!(
+            it.kind(),
+            SyntaxKind::PRIMARY_EXPR
+                | SyntaxKind::TEST
+                | SyntaxKind::EXPRESSION
+                | SyntaxKind::EXPR_STMT
+                | SyntaxKind::SMALL_STMT
+                | SyntaxKind::SIMPLE_STMT
+                | SyntaxKind::STATEMENT
+        )
+    });
+    let is_top_level = matches!(parent, Some(it) if it.kind() == SyntaxKind::FILE);
+    let arguments = call
+        .children()
+        .filter(|it| it.kind() == SyntaxKind::ARGUMENTS)
+        .flat_map(|it| it.children())
+        .collect::<Vec<_>>();
+
+    is_top_level
+        && call.next_sibling().is_none()
+        && arguments.len() > 1
+        && arguments
+            .iter()
+            .any(|it| keyword(it).as_deref() == Some("name"))
+}
+
+/// The keyword of the given argument, if any.
+fn keyword(argument: &SyntaxNode) -> Option<String> {
+    let tokens = significant_tokens(&SyntaxElement::Node(argument.clone()));
+    match tokens.as_slice() {
+        [name, eq, ..] if name.kind() == SyntaxKind::IDENTIFIER && eq.kind() == SyntaxKind::EQ => {
+            Some(name.text().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The list of the given argument, if it should be **sorted**, i.e. the
+/// argument is an attribute like `deps` whose value is a list of strings.
+fn sorted_list(argument: &SyntaxNode) -> Option<SyntaxNode> {
+    if argument.kind() != SyntaxKind::ARGUMENT
+        || !SORTED_ATTRIBUTES.contains(&keyword(argument)?.as_str())
+    {
+        return None;
+    }
+
+    let mut value = argument.last_child()?;
+    while matches!(
+        value.kind(),
+        SyntaxKind::TEST | SyntaxKind::PRIMARY_EXPR | SyntaxKind::OPERAND
+    ) {
+        let mut children = value.children();
+        let (Some(child), None) = (children.next(), children.next()) else {
+            return None;
+        };
+        value = child;
+    }
+    if value.kind() != SyntaxKind::LIST_EXPR {
+        return None;
+    }
+
+    list_children(&value)
+        .iter()
+        .filter(|it| {
+            !matches!(
+                it.kind(),
+                SyntaxKind::LBRACKET | SyntaxKind::RBRACKET | SyntaxKind::COMMA
+            )
+        })
+        .all(|it| string_value(it).is_some())
+        .then_some(value)
+}
+
+/// The value of the given element if it's a **string** literal (without its
+/// quotes and escapes resolved, which is all sorting needs).
+fn string_value(element: &SyntaxElement) -> Option<String> {
+    let tokens = significant_tokens(element);
+    let [token] = tokens.as_slice() else {
+        return None;
+    };
+    if token.kind() != SyntaxKind::STRING {
+        return None;
+    }
+
+    let text = token.text();
+    let quote = if text.ends_with("\"\"\"") || text.ends_with("'''") {
+        3
+    } else {
+        1
+    };
+    let start = text.find(['"', '\''])? + quote;
+
+    text.get(start..text.len() - quote).map(ToString::to_string)
+}
+
+/// The **order** of the given string literals, as labels.
+fn label_order(a: &SyntaxElement, b: &SyntaxElement) -> Ordering {
+    let (a, b) = (string_value(a), string_value(b));
+    labels::compare(
+        a.as_deref().unwrap_or_default(),
+        b.as_deref().unwrap_or_default(),
+    )
+}
+
+/// The name a `load` symbol is **bound** to, i.e. its alias or the symbol
+/// itself.
+fn load_symbol_name(symbol: &SyntaxElement) -> String {
+    let tokens = significant_tokens(symbol);
+    match tokens.first() {
+        Some(alias) if alias.kind() == SyntaxKind::IDENTIFIER => alias.text().to_string(),
+        _ => string_value(symbol).unwrap_or_default(),
+    }
+}
+
+/// Prefers **double quotes** for the given string literal, unless that would
+/// need more escaping.
+fn normalize_quotes(text: &str) -> String {
+    let Some(start) = text.find(['"', '\'']) else {
+        return text.to_string();
+    };
+    let (prefix, literal) = text.split_at(start);
+
+    for (single, double) in [("'''", "\"\"\""), ("'", "\"")] {
+        if literal.len() >= 2 * single.len()
+            && literal.starts_with(single)
+            && literal.ends_with(single)
+        {
+            let inner = &literal[single.len()..literal.len() - single.len()];
+            if inner.contains('"') || inner.contains("\\'") {
+                break;
+            }
+            return format!("{prefix}{double}{inner}{double}");
+        }
+    }
+
+    text.to_string()
+}
@@ -0,0 +1,204 @@
+//! **buckaroo** is a formatter for Starlark code (e.g. `BUILD` files and
+//! `.bzl` extensions), built on top of `sky_syntax`.
+//!
+//! Formatting prints the lossless syntax tree of a file in **one canonical
+//! layout** (much like buildifier), regardless of how the file was laid out
+//! before, with comments preserved. Formatting is **idempotent**, i.e.
+//! formatting a formatted file doesn't change it.
+//!
+//! ```sh
+//! buckaroo fmt foo/BUILD.bazel defs.bzl   # Rewrites the given files.
+//! buckaroo fmt --check .                  # Prints a diff for each unformatted file.
+//! buckaroo lint --fix .                   # Lints files, applying fixes where possible.
+//! ```
+//!
+//! See [`format`] for the layout, and [`lint`] for linting (with the rules
+//! of `sky_analyzer`).
+
+mod comments;
+pub mod doc;
+pub mod format;
+pub mod lint;
+
+pub use sky_analyzer::labels::SORTED_ATTRIBUTES;
+
+use crate::format::Formatter;
+use anyhow::{Context, Result};
+use similar::TextDiff;
+use sky_syntax::{ast::AstNode, File};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+/// The names of files which are Starlark, besides those with a
+/// [`STARLARK_EXTENSIONS`] extension.
+pub const STARLARK_FILE_NAMES: &[&str] = &[
+    "BUCK",
+    "BUILD",
+    "BUILD.bazel",
+    "MODULE.bazel",
+    "WORKSPACE",
+    "WORKSPACE.bazel",
+];
+
+/// The extensions of Starlark files.
+pub const STARLARK_EXTENSIONS: &[&str] = &["bzl", "star", "sky"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The width lines are wrapped at (where possible).
+    pub width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions { width: 100 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FormatError {
+    #[error("Failed to parse: {message}")]
+    Parse { message: String },
+    #[error("Formatting would lose comments, which is a bug in buckaroo")]
+    LostComments,
+}
+
+/// **Formats** the given Starlark source, refusing sources with syntax
+/// errors, as their layout can't be trusted.
+pub fn format(text: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let parse = File::parse(text);
+    if let Some(error) = parse.errors().first() {
+        let offset = usize::from(error.range().start()).min(text.len());
+        let line = text[..offset].matches('\n').count() + 1;
+
+        return Err(FormatError::Parse {
+            message: format!("{error} on line {line}"),
+        });
+    }
+
+    format_file(&parse.tree(), options)
+}
+
+/// **Formats** the given file.
+pub fn format_file(file: &File, options: &FormatOptions) -> Result<String, FormatError> {
+    let mut formatter = Formatter::new(file.syntax());
+    let doc = formatter.file(file.syntax());
+    if formatter.comments_left() {
+        return Err(FormatError::LostComments);
+    }
+
+    let mut text = doc.print(options.width);
+    if !text.is_empty() {
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+/// A unified **diff** between the given source and its formatted version.
+pub fn diff(path: &str, text: &str, formatted: &str) -> String {
+    TextDiff::from_lines(text, formatted)
+        .unified_diff()
+        .header(path, &format!("{path} (formatted)"))
+        .to_string()
+}
+
+/// Whether the given file is a **Starlark** file, judging by its name.
+pub fn is_starlark_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|it| it.to_string_lossy())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|it| it.to_string_lossy())
+        .unwrap_or_default();
+
+    STARLARK_FILE_NAMES.contains(&name.as_ref()) || STARLARK_EXTENSIONS.contains(&extension.as_ref())
+}
+
+/// The Starlark **files** at the given paths, i.e. the given files along with
+/// the Starlark files below the given directories (skipping hidden ones).
+pub fn find_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+
+        let mut found = WalkDir::new(path)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file() && is_starlark_file(entry.path()))
+            .map(|entry| entry.into_path())
+            .collect::<Vec<_>>();
+        found.sort();
+        files.extend(found);
+    }
+
+    files
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub format: FormatOptions,
+    /// Whether to only check that files are formatted, printing a diff for
+    /// each which isn't (instead of rewriting it).
+    pub check: bool,
+}
+
+/// The **outcome** of [`run`].
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The files which weren't formatted (and were rewritten, unless
+    /// checking).
+    pub unformatted: Vec<PathBuf>,
+    /// The errors encountered (e.g. files with syntax errors), which don't
+    /// stop the other files from being formatted.
+    pub errors: Vec<anyhow::Error>,
+}
+
+/// **Formats** the given files, rewriting those which aren't formatted or,
+/// when checking, printing their diffs to `out`.
+pub fn run(options: &Options, files: &[PathBuf], out: &mut dyn Write) -> Result<Outcome> {
+    let mut outcome = Outcome::default();
+
+    for path in files {
+        match format_path(options, path, out) {
+            Ok(true) => outcome.unformatted.push(path.clone()),
+            Ok(false) => {}
+            Err(error) => outcome.errors.push(error),
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Formats the given file, returning whether it wasn't formatted.
+fn format_path(options: &Options, path: &Path, out: &mut dyn Write) -> Result<bool> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read `{}`", path.display()))?;
+    let formatted = format(&text, &options.format)
+        .with_context(|| format!("Failed to format `{}`", path.display()))?;
+    if formatted == text {
+        return Ok(false);
+    }
+
+    if options.check {
+        write!(out, "{}", diff(&path.display().to_string(), &text, &formatted))?;
+    } else {
+        fs::write(path, formatted)
+            .with_context(|| format!("Failed to write `{}`", path.display()))?;
+    }
+
+    Ok(true)
+}
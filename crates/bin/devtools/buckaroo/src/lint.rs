@@ -0,0 +1,128 @@
+//! **Linting** Starlark files with the rules of `sky_analyzer`.
+
+use anyhow::{bail, Context, Result};
+use sky_analyzer::lint::{apply_fixes, Diagnostic, FileKind, Registry};
+use sky_syntax::File;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    /// Whether to apply the fixes of the diagnostics (where possible),
+    /// rewriting the files.
+    pub fix: bool,
+    /// The ids of the rules to disable.
+    pub disabled: Vec<String>,
+}
+
+/// The **outcome** of [`lint`].
+#[derive(Debug, Default)]
+pub struct LintOutcome {
+    /// The number of diagnostics reported (after applying fixes).
+    pub diagnostics: usize,
+    /// The number of fixes applied.
+    pub fixed: usize,
+    /// The errors encountered (e.g. files with syntax errors), which don't
+    /// stop the other files from being linted.
+    pub errors: Vec<anyhow::Error>,
+}
+
+/// **Lints** the given files, printing their diagnostics to `out` as
+/// `path:line:column: severity[rule]: message`.
+pub fn lint(options: &LintOptions, files: &[PathBuf], out: &mut dyn Write) -> Result<LintOutcome> {
+    let mut registry = Registry::default();
+    for id in &options.disabled {
+        registry.set_severity(id, None)?;
+    }
+
+    let mut outcome = LintOutcome::default();
+    for path in files {
+        if let Err(error) = lint_path(options, &registry, path, out, &mut outcome) {
+            outcome.errors.push(error);
+        }
+    }
+
+    Ok(outcome)
+}
+
+fn lint_path(
+    options: &LintOptions,
+    registry: &Registry,
+    path: &Path,
+    out: &mut dyn Write,
+    outcome: &mut LintOutcome,
+) -> Result<()> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read `{}`", path.display()))?;
+    let kind = FileKind::from_path(path);
+    let mut diagnostics = lint_text(registry, path, &text, kind)?;
+
+    if options.fix {
+        let (fixed, applied) = apply_fixes(&text, &diagnostics);
+        if applied > 0 {
+            fs::write(path, &fixed)
+                .with_context(|| format!("Failed to write `{}`", path.display()))?;
+            outcome.fixed += applied;
+            diagnostics = lint_text(registry, path, &fixed, kind)?;
+            return report(path, &fixed, &diagnostics, out, outcome);
+        }
+    }
+
+    report(path, &text, &diagnostics, out, outcome)
+}
+
+/// Lints the given text, refusing it if it has syntax errors.
+fn lint_text(
+    registry: &Registry,
+    path: &Path,
+    text: &str,
+    kind: FileKind,
+) -> Result<Vec<Diagnostic>> {
+    let parse = File::parse(text);
+    if let Some(error) = parse.errors().first() {
+        let (line, _) = line_column(text, usize::from(error.range().start()));
+        bail!(
+            "Failed to lint `{}`: {error} on line {line}",
+            path.display()
+        );
+    }
+
+    Ok(registry.lint(&parse, kind))
+}
+
+fn report(
+    path: &Path,
+    text: &str,
+    diagnostics: &[Diagnostic],
+    out: &mut dyn Write,
+    outcome: &mut LintOutcome,
+) -> Result<()> {
+    for diagnostic in diagnostics {
+        let (line, column) = line_column(text, usize::from(diagnostic.range.start()));
+        writeln!(
+            out,
+            "{}:{line}:{column}: {}[{}]: {}",
+            path.display(),
+            diagnostic.severity,
+            diagnostic.rule,
+            diagnostic.message
+        )?;
+    }
+    outcome.diagnostics += diagnostics.len();
+
+    Ok(())
+}
+
+/// The (one-based) **line and column** of the given offset.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |it| it + 1);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
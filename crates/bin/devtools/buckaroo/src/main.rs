@@ -0,0 +1,120 @@
+// main.rs
+
+mod cli;
+
+use anyhow::{Context, Result};
+use buckaroo::{
+    diff, find_files, format,
+    lint::{lint, LintOptions},
+    run, FormatOptions, Options,
+};
+use clap::Parser;
+use cli::{Cli, Command, FmtArgs, LintArgs};
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+/// Exit code when checking finds files which aren't formatted.
+const UNFORMATTED: u8 = 1;
+
+/// Exit code when linting reports diagnostics.
+const DIAGNOSTICS: u8 = 1;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command() {
+        Command::Fmt(args) => fmt(args),
+        Command::Lint(args) => lint_files(args),
+    };
+    match result {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn fmt(args: &FmtArgs) -> Result<u8> {
+    let options = Options {
+        format: FormatOptions {
+            width: *args.width(),
+        },
+        check: *args.check(),
+    };
+
+    if args.paths().is_empty() {
+        return fmt_stdin(&options);
+    }
+
+    let files = find_files(args.paths());
+    let outcome = run(&options, &files, &mut io::stdout().lock())?;
+    for path in &outcome.unformatted {
+        if !options.check {
+            eprintln!("formatted {}", path.display());
+        }
+    }
+    for error in &outcome.errors {
+        eprintln!("error: {error:#}");
+    }
+
+    let code = if !outcome.errors.is_empty() {
+        2
+    } else if options.check && !outcome.unformatted.is_empty() {
+        UNFORMATTED
+    } else {
+        0
+    };
+
+    Ok(code)
+}
+
+fn lint_files(args: &LintArgs) -> Result<u8> {
+    let options = LintOptions {
+        fix: *args.fix(),
+        disabled: args.disable().clone(),
+    };
+
+    let files = find_files(args.paths());
+    let outcome = lint(&options, &files, &mut io::stdout().lock())?;
+    if outcome.fixed > 0 {
+        eprintln!("fixed {} issue(s)", outcome.fixed);
+    }
+    for error in &outcome.errors {
+        eprintln!("error: {error:#}");
+    }
+
+    let code = if !outcome.errors.is_empty() {
+        2
+    } else if outcome.diagnostics > 0 {
+        DIAGNOSTICS
+    } else {
+        0
+    };
+
+    Ok(code)
+}
+
+/// Formats stdin to stdout (or, when checking, prints its diff).
+fn fmt_stdin(options: &Options) -> Result<u8> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .context("Failed to read stdin")?;
+    let formatted = format(&text, &options.format).context("Failed to format stdin")?;
+
+    let mut stdout = io::stdout().lock();
+    if !options.check {
+        write!(stdout, "{formatted}")?;
+        return Ok(0);
+    }
+
+    if formatted == text {
+        Ok(0)
+    } else {
+        write!(stdout, "{}", diff("<stdin>", &text, &formatted))?;
+        Ok(UNFORMATTED)
+    }
+}
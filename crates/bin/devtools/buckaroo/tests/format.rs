@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod format_test_suite {
+    use buckaroo::{format, FormatError, FormatOptions};
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+
+    /// Formats the given source, checking that formatting is idempotent.
+    fn fmt(text: &str, width: usize) -> String {
+        let options = FormatOptions { width };
+        let formatted = format(text, &options).unwrap();
+        assert_eq!(format(&formatted, &options).unwrap(), formatted);
+
+        formatted
+    }
+
+    #[rstest]
+    #[case::spacing("x=1+2*3\n", "x = 1 + 2 * 3\n")]
+    #[case::blank_lines("x = 1\n\n\n\ny = 2\n", "x = 1\n\ny = 2\n")]
+    #[case::final_newline("x = 1", "x = 1\n")]
+    #[case::empty("\n\n", "")]
+    #[case::fits("foo( 1,2 , 3, )\n", "foo(1, 2, 3)\n")]
+    #[case::wraps(
+        "foo(aaaaaaaaaaa, bbbbbbbbbbb, ccccccccccc)\n",
+        "foo(\n    aaaaaaaaaaa,\n    bbbbbbbbbbb,\n    ccccccccccc,\n)\n"
+    )]
+    #[case::wraps_innermost_list_last(
+        "x = foo(a, [bbbbbbbbbbbbbbb, ccccccccccccccc])\n",
+        "x = foo(\n    a,\n    [bbbbbbbbbbbbbbb, ccccccccccccccc],\n)\n"
+    )]
+    #[case::rule_call(
+        "rust_library(name = \"foo\", edition = \"2021\")\n",
+        "rust_library(\n    name = \"foo\",\n    edition = \"2021\",\n)\n"
+    )]
+    #[case::rule_call_with_name_only(
+        "rust_library(name = \"foo\")\n",
+        "rust_library(name = \"foo\")\n"
+    )]
+    #[case::dict("x = {'a':1,\n'b' : 2}\n", "x = {\"a\": 1, \"b\": 2}\n")]
+    #[case::tuples("x = (1,)\ny = (1)\nz = (1, 2,)\n", "x = (1,)\ny = (1)\nz = (1, 2)\n")]
+    #[case::def(
+        "def foo(a,b=1,*args,**kwargs):\n  return a\n",
+        "def foo(a, b = 1, *args, **kwargs):\n    return a\n"
+    )]
+    #[case::def_with_annotations(
+        "def f(x:int=1,*y : str)->str:\n  pass\n",
+        "def f(x: int = 1, *y: str) -> str:\n    pass\n"
+    )]
+    #[case::if_elif_else(
+        "if a: pass\nelif b:\n        x = 1\nelse:\n  y = not a\n",
+        "if a:\n    pass\nelif b:\n    x = 1\nelse:\n    y = not a\n"
+    )]
+    #[case::small_statements("x = 1; y = 2\n", "x = 1\ny = 2\n")]
+    #[case::binary_chain(
+        "x = (aaaaaaaaaaaaaaa | bbbbbbbbbbbbbbbbbb | ccccccccccc)\n",
+        "x = (\n    aaaaaaaaaaaaaaa |\n    bbbbbbbbbbbbbbbbbb |\n    ccccccccccc\n)\n"
+    )]
+    #[case::binary_chain_with_list(
+        "foo(srcs = glob([\"*.rs\"]) + [\"aaaaaaaaaa.rs\", \"bbbbbbbbbb.rs\"])\n",
+        "foo(\n    srcs = glob([\"*.rs\"]) + [\n        \"aaaaaaaaaa.rs\",\n        \"bbbbbbbbbb.rs\",\n    ],\n)\n"
+    )]
+    fn test_layout(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(fmt(text, 40), expected);
+    }
+
+    #[rstest]
+    #[case::double_quotes("x = 'a'\n", "x = \"a\"\n")]
+    #[case::keeps_single_quotes_around_double("x = 'say \"hi\"'\n", "x = 'say \"hi\"'\n")]
+    #[case::keeps_escaped_single_quotes("x = 'it\\'s'\n", "x = 'it\\'s'\n")]
+    #[case::prefixed("x = r'a'\n", "x = r\"a\"\n")]
+    #[case::triple_quotes("x = '''a\nb'''\n", "x = \"\"\"a\nb\"\"\"\n")]
+    fn test_strings(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(fmt(text, 100), expected);
+    }
+
+    #[rstest]
+    #[case::load(
+        "load(\":defs.bzl\", \"c\", \"a\", b = \"x\", \"a\")\n",
+        "load(\":defs.bzl\", \"a\", b = \"x\", \"c\")\n"
+    )]
+    #[case::deps(
+        "foo(\n    name = \"foo\",\n    deps = [\"@x//y\", \"//b:c\", \":z\", \"//a\", \":z\"],\n)\n",
+        "foo(\n    name = \"foo\",\n    deps = [\n        \":z\",\n        \"//a\",\n        \"//b:c\",\n        \"@x//y\",\n    ],\n)\n"
+    )]
+    #[case::runs_separated_by_blank_lines(
+        "foo(\n    srcs = [\n        \"b\",\n        \"a\",\n\n        \"d\",\n        \"c\",\n    ],\n)\n",
+        "foo(\n    srcs = [\n        \"a\",\n        \"b\",\n\n        \"c\",\n        \"d\",\n    ],\n)\n"
+    )]
+    #[case::not_only_strings("foo(deps = [\"b\", a, \"a\"])\n", "foo(deps = [\"b\", a, \"a\"])\n")]
+    #[case::other_attributes("foo(tags = [\"b\", \"a\"])\n", "foo(tags = [\"b\", \"a\"])\n")]
+    fn test_sorting(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(fmt(text, 40), expected);
+    }
+
+    #[rstest]
+    #[case::leading("# a\n\n# b\nx = 1\n", "# a\n\n# b\nx = 1\n")]
+    #[case::trailing("x = 1 # a\n", "x = 1  # a\n")]
+    #[case::end_of_file("x = 1\n\n# a\n", "x = 1\n\n# a\n")]
+    #[case::only_comments("# a\n", "# a\n")]
+    #[case::in_list(
+        "x = [\n    1,  # one\n    # two\n    2,\n]\n",
+        "x = [\n    1,  # one\n    # two\n    2,\n]\n"
+    )]
+    #[case::trailing_comment_breaks_list(
+        "x = [1,  # one\n 2]\n",
+        "x = [\n    1,  # one\n    2,\n]\n"
+    )]
+    #[case::dangling_in_list("x = [\n    1,\n    # end\n]\n", "x = [\n    1,\n    # end\n]\n")]
+    #[case::dangling_in_empty_list("x = {\n    # none\n}\n", "x = {\n    # none\n}\n")]
+    #[case::after_close("foo(\n    a,\n)  # a\n", "foo(a)  # a\n")]
+    #[case::end_of_block(
+        "def f():\n    pass\n    # a\n# b\n",
+        "def f():\n    pass\n    # a\n# b\n"
+    )]
+    #[case::mid_expression("x = (a +\n    # b\n    b)\n", "x = (\n    a +\n    # b\n    b\n)\n")]
+    #[case::keeps_commented_duplicates(
+        "foo(deps = [\n    \":a\",\n    \":a\",  # again\n])\n",
+        "foo(\n    deps = [\n        \":a\",\n        \":a\",  # again\n    ],\n)\n"
+    )]
+    fn test_comments(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(fmt(text, 40), expected);
+    }
+
+    #[test]
+    fn test_refuses_syntax_errors() {
+        let error = format("x = 1\nfoo(\n", &FormatOptions::default()).unwrap_err();
+
+        assert!(matches!(error, FormatError::Parse { .. }), "{error:?}");
+        assert!(error.to_string().ends_with("on line 2"), "{error}");
+    }
+}
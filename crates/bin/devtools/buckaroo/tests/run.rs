@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod run_test_suite {
+    use buckaroo::{
+        diff, find_files,
+        lint::{lint, LintOptions, LintOutcome},
+        run, Options,
+    };
+    use pretty_assertions_sorted::assert_eq;
+    use std::{fs, path::PathBuf, process};
+
+    /// A scratch **workspace** with the given files, removed when dropped.
+    struct Workspace {
+        root: PathBuf,
+    }
+
+    impl Workspace {
+        fn new(name: &str, files: &[(&str, &str)]) -> Workspace {
+            let root = std::env::temp_dir().join(format!("buckaroo-{name}-{}", process::id()));
+            let _ = fs::remove_dir_all(&root);
+            for (path, text) in files {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, text).unwrap();
+            }
+
+            Workspace { root }
+        }
+
+        fn read(&self, path: &str) -> String {
+            fs::read_to_string(self.root.join(path)).unwrap()
+        }
+
+        fn run(&self, options: &Options) -> (buckaroo::Outcome, String) {
+            let files = find_files(std::slice::from_ref(&self.root));
+            let mut out = Vec::new();
+            let outcome = run(options, &files, &mut out).unwrap();
+
+            (outcome, String::from_utf8(out).unwrap())
+        }
+
+        fn lint(&self, options: &LintOptions) -> (LintOutcome, String) {
+            let files = find_files(std::slice::from_ref(&self.root));
+            let mut out = Vec::new();
+            let outcome = lint(options, &files, &mut out).unwrap();
+
+            (outcome, String::from_utf8(out).unwrap())
+        }
+
+        fn path(&self, path: &str) -> PathBuf {
+            self.root.join(path)
+        }
+    }
+
+    impl Drop for Workspace {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    const FORMATTED: &str =
+        "rust_library(\n    name = \"foo\",\n    deps = [\"//base\"],\n)  # keep\n";
+    const UNFORMATTED: &str = "rust_library(name='foo', deps=['//base'])  # keep\n";
+
+    #[test]
+    fn test_find_files() {
+        let workspace = Workspace::new(
+            "find",
+            &[
+                ("BUCK", ""),
+                ("foo/BUILD.bazel", ""),
+                ("foo/defs.bzl", ""),
+                ("foo/lib.rs", ""),
+                ("foo/.hidden/BUILD", ""),
+            ],
+        );
+
+        assert_eq!(
+            find_files(&[workspace.root.clone(), workspace.path("foo/lib.rs")]),
+            vec![
+                workspace.path("BUCK"),
+                workspace.path("foo/BUILD.bazel"),
+                workspace.path("foo/defs.bzl"),
+                workspace.path("foo/lib.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrites_unformatted_files() {
+        let workspace = Workspace::new(
+            "rewrite",
+            &[("foo/BUILD", FORMATTED), ("bar/BUILD", UNFORMATTED)],
+        );
+
+        let (outcome, out) = workspace.run(&Options::default());
+
+        assert_eq!(outcome.unformatted, vec![workspace.path("bar/BUILD")]);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(out, "");
+        assert_eq!(workspace.read("bar/BUILD"), FORMATTED);
+        assert_eq!(workspace.read("foo/BUILD"), FORMATTED);
+    }
+
+    #[test]
+    fn test_check() {
+        let workspace = Workspace::new(
+            "check",
+            &[
+                ("foo/BUILD", FORMATTED),
+                ("bar/BUILD", UNFORMATTED),
+                ("baz/BUILD", "foo(\n"),
+            ],
+        );
+        let options = Options {
+            check: true,
+            ..Options::default()
+        };
+
+        let (outcome, out) = workspace.run(&options);
+
+        assert_eq!(outcome.unformatted, vec![workspace.path("bar/BUILD")]);
+        assert_eq!(outcome.errors.len(), 1);
+        assert!(format!("{:#}", outcome.errors[0]).starts_with(&format!(
+            "Failed to format `{}`: Failed to parse",
+            workspace.path("baz/BUILD").display()
+        )));
+        assert_eq!(
+            out,
+            diff(
+                &workspace.path("bar/BUILD").display().to_string(),
+                UNFORMATTED,
+                FORMATTED
+            )
+        );
+        assert_eq!(workspace.read("bar/BUILD"), UNFORMATTED);
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            diff("BUILD", "x=1\ny = 2\n", "x = 1\ny = 2\n"),
+            "--- BUILD\n+++ BUILD (formatted)\n@@ -1,2 +1,2 @@\n-x=1\n+x = 1\n y = 2\n"
+        );
+    }
+
+    const UNLINTED: &str =
+        "load(\":defs.bzl\", \"foo\", \"bar\")\n\nfoo(deps = [\"//b\", \"//a\"])\n";
+
+    #[test]
+    fn test_lint() {
+        let workspace = Workspace::new("lint", &[("BUILD", UNLINTED), ("bad/BUILD", "foo(\n")]);
+
+        let (outcome, out) = workspace.lint(&LintOptions::default());
+
+        let path = workspace.path("BUILD").display().to_string();
+        assert_eq!(
+            out,
+            format!(
+                "{path}:1:26: warning[unused-load]: `bar` is loaded but never used\n\
+                 {path}:3:12: warning[unsorted-deps]: The list of `deps` isn't sorted\n"
+            )
+        );
+        assert_eq!(outcome.diagnostics, 2);
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(workspace.read("BUILD"), UNLINTED);
+    }
+
+    #[test]
+    fn test_lint_fix() {
+        let workspace = Workspace::new("lint-fix", &[("BUILD", UNLINTED)]);
+        let options = LintOptions {
+            fix: true,
+            disabled: vec!["unsorted-deps".to_string()],
+        };
+
+        let (outcome, out) = workspace.lint(&options);
+
+        assert_eq!(out, "");
+        assert_eq!((outcome.diagnostics, outcome.fixed), (0, 1));
+        assert_eq!(
+            workspace.read("BUILD"),
+            "load(\":defs.bzl\", \"foo\")\n\nfoo(deps = [\"//b\", \"//a\"])\n"
+        );
+    }
+}
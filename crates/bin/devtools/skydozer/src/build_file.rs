@@ -0,0 +1,572 @@
+// build_file.rs
+
+use sky_syntax::{
+    ast::{self, make, AstNode},
+    ted::{self, Position},
+    File, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use std::io::{self, Write};
+use thiserror::Error;
+
+use crate::{
+    command::{Command, Placement},
+    label::Target,
+    value,
+};
+
+#[derive(Debug, Error)]
+pub enum EditError {
+    #[error("Failed to parse the BUILD file of `//{package}`: {message}")]
+    Parse { package: String, message: String },
+    #[error("No rule named `{name}` in `//{package}`")]
+    NoSuchRule { package: String, name: String },
+    #[error("A rule named `{name}` already exists in `//{package}`")]
+    RuleExists { package: String, name: String },
+    #[error("Attribute `{attr}` of `//{package}:{name}` is not a list")]
+    NotAList {
+        package: String,
+        name: String,
+        attr: String,
+    },
+    #[error("`{command}` applies to {expected} (e.g. `{example}`)")]
+    InvalidTarget {
+        command: &'static str,
+        expected: &'static str,
+        example: &'static str,
+    },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// A **BUILD file**, edited in place (i.e. losslessly, so that it only
+/// changes where commands are applied).
+#[derive(Debug)]
+pub struct BuildFile {
+    package: String,
+    original: String,
+    file: File,
+}
+
+/// A **rule** (i.e. target) of a BUILD file: a top-level call with a `name`
+/// (e.g. `rust_library(name = "foo", ...)`).
+#[derive(Debug, Clone)]
+pub struct Rule {
+    /// The (`STATEMENT`) node of the whole rule, including its leading
+    /// comments.
+    statement: SyntaxNode,
+    call: ast::CallSuffix,
+    kind: String,
+}
+
+impl BuildFile {
+    /// Parses the BUILD file of the given package, refusing files with
+    /// **syntax errors**, as editing those might mangle them further.
+    pub fn parse(package: &str, text: &str) -> Result<BuildFile, EditError> {
+        let parse = File::parse(text);
+        if let Some(error) = parse.errors().first() {
+            let offset = usize::from(error.range().start()).min(text.len());
+            let line = text[..offset].matches('\n').count() + 1;
+
+            return Err(EditError::Parse {
+                package: package.to_string(),
+                message: format!("{error} on line {line}"),
+            });
+        }
+
+        Ok(BuildFile {
+            package: package.to_string(),
+            original: text.to_string(),
+            file: parse.tree().clone_for_update(),
+        })
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// The (current) **text** of the file.
+    pub fn text(&self) -> String {
+        self.file.syntax().to_string()
+    }
+
+    /// Whether the file has been **modified** since it was parsed.
+    pub fn is_modified(&self) -> bool {
+        self.text() != self.original
+    }
+
+    /// All **rules** of the file, in order.
+    pub fn rules(&self) -> Vec<Rule> {
+        self.file
+            .syntax()
+            .children()
+            .filter_map(Rule::cast)
+            .collect()
+    }
+
+    /// The rule with the given **name**, if any.
+    pub fn rule(&self, name: &str) -> Option<Rule> {
+        self.rules()
+            .into_iter()
+            .find(|rule| rule.name().as_deref() == Some(name))
+    }
+
+    /// **Applies** the given command to the given target(s) of the file,
+    /// returning whether it modified the file. Printed output is written to
+    /// `out`.
+    pub fn apply(
+        &mut self,
+        command: &Command,
+        target: &Target,
+        out: &mut dyn Write,
+    ) -> Result<bool, EditError> {
+        if let Command::New {
+            kind,
+            name,
+            placement,
+        } = command
+        {
+            if *target != Target::Package {
+                return Err(EditError::InvalidTarget {
+                    command: "new",
+                    expected: "packages",
+                    example: "//foo:__pkg__",
+                });
+            }
+            return self.new_rule(kind, name, placement.as_ref());
+        }
+
+        let rules = match target {
+            Target::Name(name) => vec![self.rule(name).ok_or_else(|| EditError::NoSuchRule {
+                package: self.package.clone(),
+                name: name.clone(),
+            })?],
+            Target::All => self.rules(),
+            Target::Kind(kind) => self
+                .rules()
+                .into_iter()
+                .filter(|rule| rule.kind() == kind)
+                .collect(),
+            Target::Package => {
+                return Err(EditError::InvalidTarget {
+                    command: command.name(),
+                    expected: "rules",
+                    example: "//foo:bar",
+                })
+            }
+        };
+
+        let mut modified = false;
+        for rule in rules {
+            modified |= self.apply_to_rule(command, &rule, out)?;
+        }
+
+        Ok(modified)
+    }
+
+    fn apply_to_rule(
+        &mut self,
+        command: &Command,
+        rule: &Rule,
+        out: &mut dyn Write,
+    ) -> Result<bool, EditError> {
+        let package = self.package.clone();
+        let modified = match command {
+            Command::Add { attr, values } => match rule.attr(attr) {
+                Some(existing) => {
+                    let list = self.list(rule, attr, &existing)?;
+                    let mut modified = false;
+                    for value in values {
+                        let present = value::elements(&list)
+                            .iter()
+                            .any(|it| value::matches(it, value, &package));
+                        if !present {
+                            list.push_element(value::parse(value, &package));
+                            modified = true;
+                        }
+                    }
+                    modified
+                }
+                None => {
+                    let values = values.iter().map(|it| value::parse(it, &package));
+                    rule.call
+                        .set_keyword_argument(attr, make::list_expr(values).into());
+                    true
+                }
+            },
+            Command::Remove { attr, values } if values.is_empty() => {
+                rule.call.remove_keyword_argument(attr).is_some()
+            }
+            Command::Remove { attr, values } => {
+                let Some(list) = rule.attr(attr).as_ref().and_then(value::as_list) else {
+                    return Ok(false);
+                };
+                let modified = remove_elements(&list, |it| {
+                    values
+                        .iter()
+                        .any(|value| value::matches(it, value, &package))
+                });
+                if modified && value::elements(&list).is_empty() {
+                    rule.call.remove_keyword_argument(attr);
+                }
+                modified
+            }
+            Command::Set { attr, values } => {
+                let new = value::parse_all(attr, values, &package);
+                let unchanged = matches!(
+                    rule.attr(attr),
+                    Some(old) if value::source(old.syntax()) == value::source(new.syntax())
+                );
+                if !unchanged {
+                    rule.call.set_keyword_argument(attr, new);
+                }
+                !unchanged
+            }
+            Command::Replace { attr, old, new } => match rule.attr(attr) {
+                Some(existing) => match value::as_list(&existing) {
+                    Some(list) => {
+                        let mut modified = false;
+                        for element in value::elements(&list) {
+                            if value::matches(&element, old, &package) {
+                                list.replace_element(&element, value::parse(new, &package));
+                                modified = true;
+                            }
+                        }
+                        modified
+                    }
+                    None if value::matches(&existing, old, &package) => {
+                        rule.call
+                            .set_keyword_argument(attr, value::parse(new, &package));
+                        true
+                    }
+                    None => false,
+                },
+                None => false,
+            },
+            Command::Delete => {
+                delete_statement(&rule.statement);
+                true
+            }
+            Command::Move { from, to, values } => self.move_values(rule, from, to, values)?,
+            Command::Print { attrs } => {
+                let line = rule.print(attrs, &package);
+                writeln!(out, "{line}")?;
+                false
+            }
+            Command::New { .. } => unreachable!("`new` applies to packages"),
+        };
+
+        Ok(modified)
+    }
+
+    /// The given (existing) attribute of the rule as a **list**.
+    fn list(&self, rule: &Rule, attr: &str, test: &ast::Test) -> Result<ast::ListExpr, EditError> {
+        value::as_list(test).ok_or_else(|| EditError::NotAList {
+            package: self.package.clone(),
+            name: rule.name().unwrap_or_default(),
+            attr: attr.to_string(),
+        })
+    }
+
+    fn move_values(
+        &self,
+        rule: &Rule,
+        from: &str,
+        to: &str,
+        values: &[String],
+    ) -> Result<bool, EditError> {
+        let Some(existing) = rule.attr(from) else {
+            return Ok(false);
+        };
+        let source = self.list(rule, from, &existing)?;
+        let moved = value::elements(&source)
+            .into_iter()
+            .filter(|it| {
+                values == ["*"]
+                    || values
+                        .iter()
+                        .any(|value| value::matches(it, value, &self.package))
+            })
+            .collect::<Vec<_>>();
+        if moved.is_empty() {
+            return Ok(false);
+        }
+
+        // The moved elements keep their comments (e.g. `# keep sorted` above
+        // them, or a `# TODO` trailing them).
+        let moved = moved
+            .into_iter()
+            .map(|element| {
+                let comments = source.remove_element_with_comments(&element);
+                (element, comments)
+            })
+            .collect::<Vec<_>>();
+        if value::elements(&source).is_empty() {
+            rule.call.remove_keyword_argument(from);
+        }
+
+        let existing = match rule.attr(to) {
+            Some(existing) => existing,
+            None => {
+                rule.call
+                    .set_keyword_argument(to, make::list_expr([]).into());
+                rule.attr(to).unwrap()
+            }
+        };
+        let target = self.list(rule, to, &existing)?;
+        for (element, comments) in moved {
+            let present = value::elements(&target)
+                .iter()
+                .any(|it| value::source(it.syntax()) == value::source(element.syntax()));
+            if !present {
+                target.push_element_with_comments(element, comments);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Creates a **new** rule of the given kind, separated from the rules
+    /// around it by blank lines.
+    fn new_rule(
+        &mut self,
+        kind: &str,
+        name: &str,
+        placement: Option<&Placement>,
+    ) -> Result<bool, EditError> {
+        if self.rule(name).is_some() {
+            return Err(EditError::RuleExists {
+                package: self.package.clone(),
+                name: name.to_string(),
+            });
+        }
+
+        let next_to = match placement {
+            Some(Placement::Before(other) | Placement::After(other)) => {
+                let other = self.rule(other).ok_or_else(|| EditError::NoSuchRule {
+                    package: self.package.clone(),
+                    name: other.clone(),
+                })?;
+                Some(other.statement)
+            }
+            None => None,
+        };
+
+        let mut callee = kind.split('.');
+        let receiver = make::name_expr(callee.next().unwrap_or_default());
+        let callee = callee.fold(receiver, make::dot_expr);
+        let call = make::call_expr(
+            callee,
+            [make::keyword_argument(
+                "name",
+                make::string_expr(name).into(),
+            )],
+        );
+        let statement = make::file([make::expr_stmt(call.into())])
+            .syntax()
+            .first_child()
+            .unwrap()
+            .clone_for_update();
+
+        match (placement, next_to) {
+            (Some(Placement::Before(_)), Some(other)) => {
+                // Comments which don't belong to the rule the new one is
+                // inserted before (e.g. a file header) stay above both.
+                let comments = detach_comments(&other);
+                ted::insert(Position::before(other), statement.clone());
+                let comments = comments.into_iter().map(Into::into).collect();
+                ted::insert_all(Position::before(statement.first_token().unwrap()), comments);
+            }
+            (_, Some(other)) => ted::insert(Position::after(other), statement.clone()),
+            (_, None) => match self.file.syntax().last_child() {
+                Some(last) => ted::insert(Position::after(last), statement.clone()),
+                None => ted::insert(
+                    Position::first_child_of(self.file.syntax()),
+                    statement.clone(),
+                ),
+            },
+        }
+
+        let previous = statement
+            .prev_sibling()
+            .filter(|_| !statement.to_string().starts_with('\n'));
+        if let Some(previous) = previous {
+            let separator = if previous.to_string().ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            ted::insert(
+                Position::before(statement.first_token().unwrap()),
+                make::tokens::whitespace(separator),
+            );
+        }
+        if let Some(next) = statement.next_sibling() {
+            if !next.to_string().starts_with('\n') {
+                ted::insert(
+                    Position::before(next.first_token().unwrap()),
+                    make::tokens::whitespace("\n"),
+                );
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Rule {
+    fn cast(statement: SyntaxNode) -> Option<Rule> {
+        let ast::Statement::SimpleStmt(simple) = ast::Statement::cast(statement.clone())? else {
+            return None;
+        };
+        let mut small_stmts = simple.small_stmts();
+        let (Some(ast::SmallStmt::ExprStmt(stmt)), None) = (small_stmts.next(), small_stmts.next())
+        else {
+            return None;
+        };
+        let mut tests = stmt.expression()?.tests();
+        let (Some(ast::Test::PrimaryExpr(primary)), None) = (tests.next(), tests.next()) else {
+            return None;
+        };
+        let call = primary.call_suffix()?;
+
+        // The kind is the callee, i.e. everything up to the call's arguments
+        // (e.g. `rust_library` or `native.genrule`).
+        let kind = primary
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .take_while(|it| it.text_range().start() < call.syntax().text_range().start())
+            .filter(|it| !value::is_trivia(it.kind()))
+            .map(|it| it.text().to_string())
+            .collect();
+
+        let rule = Rule {
+            statement,
+            call,
+            kind,
+        };
+        rule.name().is_some().then_some(rule)
+    }
+
+    /// The **kind** of the rule (e.g. `rust_library`).
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// The **name** of the rule, i.e. the value of its `name` attribute.
+    pub fn name(&self) -> Option<String> {
+        value::as_string(&self.attr("name")?)
+    }
+
+    /// The value of the given **attribute**, if it's set.
+    pub fn attr(&self, attr: &str) -> Option<ast::Test> {
+        self.call.keyword_argument(attr)?.value()
+    }
+
+    /// Renders the given attributes of the rule for `print`, separated by
+    /// spaces, or the whole rule if none are given.
+    fn print(&self, attrs: &[String], package: &str) -> String {
+        if attrs.is_empty() {
+            return value::source(&self.statement);
+        }
+
+        attrs
+            .iter()
+            .map(|attr| match attr.as_str() {
+                "kind" => self.kind.clone(),
+                "label" => format!("//{package}:{}", self.name().unwrap_or_default()),
+                "rule" => value::source(&self.statement),
+                attr => self
+                    .attr(attr)
+                    .map(|it| value::render(&it))
+                    .unwrap_or_else(|| "(missing)".to_string()),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// **Removes** all elements of the given list matching the predicate,
+/// returning whether any were removed.
+fn remove_elements(list: &ast::ListExpr, predicate: impl Fn(&ast::Test) -> bool) -> bool {
+    let removed = value::elements(list)
+        .into_iter()
+        .filter(|it| predicate(it))
+        .collect::<Vec<_>>();
+    for element in &removed {
+        list.remove_element(element);
+    }
+
+    !removed.is_empty()
+}
+
+/// **Deletes** the given statement, along with its leading comments, unless
+/// they're separated from it by a blank line (e.g. a file header), in which
+/// case they're kept in front of the next statement.
+fn delete_statement(statement: &SyntaxNode) {
+    let parent = statement.parent();
+    let next = statement.next_sibling();
+    let is_first = statement.prev_sibling().is_none();
+
+    let detached = detach_comments(statement);
+    ted::remove(statement.clone());
+
+    let elements = detached.iter().cloned().map(Into::into).collect();
+    let Some(next) = next else {
+        if let Some(parent) = parent.filter(|_| !detached.is_empty()) {
+            ted::insert_all(Position::last_child_of(&parent), elements);
+        }
+        return;
+    };
+
+    // The next statement's blank lines are dropped if it's now the first
+    // statement, or if the detached comments already end with one.
+    if is_first || !detached.is_empty() {
+        for token in leading_trivia(&next)
+            .into_iter()
+            .take_while(|it| it.kind() == SyntaxKind::WHITESPACE)
+        {
+            ted::remove(token);
+        }
+    }
+    if let Some(first) = next.first_token() {
+        ted::insert_all(Position::before(first), elements);
+    }
+}
+
+/// **Detaches** the comments at the start of the given statement which are
+/// separated from it by a blank line (e.g. a file header), i.e. which don't
+/// belong to the statement itself, returning them.
+fn detach_comments(statement: &SyntaxNode) -> Vec<SyntaxToken> {
+    let trivia = leading_trivia(statement);
+    let detached = trivia
+        .iter()
+        .rposition(|token| {
+            token.kind() == SyntaxKind::WHITESPACE && token.text().contains('\n') && {
+                let previous = token.prev_token();
+                !matches!(previous, Some(it) if it.kind() == SyntaxKind::COMMENT)
+            }
+        })
+        .filter(|&blank| {
+            trivia[..blank]
+                .iter()
+                .any(|it| it.kind() == SyntaxKind::COMMENT)
+        })
+        .map(|blank| trivia[..=blank].to_vec())
+        .unwrap_or_default();
+
+    for token in &detached {
+        ted::remove(token.clone());
+    }
+
+    detached
+}
+
+/// The **trivia** (i.e. whitespace and comments) at the start of the given
+/// node.
+fn leading_trivia(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .take_while(|it| value::is_trivia(it.kind()))
+        .collect()
+}
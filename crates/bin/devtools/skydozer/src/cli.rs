@@ -0,0 +1,38 @@
+// cli.rs
+
+use clap::Parser;
+use getset::Getters;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Getters, PartialEq, Eq, Hash)]
+#[command(
+    author = "pulanski <iopulanski@gmail.com>",
+    version = "0.1.0",
+    about = "A buildozer-compatible tool for refactoring BUILD files.",
+    long_about = "A buildozer-compatible tool for refactoring BUILD files. Commands (e.g. `'add deps :foo'`) are applied to the targets selected by the labels following them (e.g. `//foo:bar`, `//foo:*` or `//foo/...:%rust_library`), rewriting BUILD files losslessly, i.e. only where edited.\n\nExits with 0 if files were modified, 3 if there was nothing to modify, and 2 on errors.",
+    bin_name = "skydozer"
+)]
+#[getset(get = "pub")]
+pub struct Cli {
+    /// The root of the workspace, which packages are relative to
+    /// [default: the closest parent directory with a WORKSPACE, MODULE.bazel or .buckconfig file]
+    #[clap(long, required = false)]
+    root: Option<PathBuf>,
+
+    /// Print the modified BUILD files to stdout instead of rewriting them
+    #[clap(long)]
+    stdout: bool,
+
+    /// Keep going when a command fails for some target
+    #[clap(short = 'k', long)]
+    keep_going: bool,
+
+    /// The names of BUILD files, in order of precedence
+    #[clap(long, value_delimiter = ',', default_value = "BUILD.bazel,BUILD,BUCK")]
+    build_file_names: Vec<String>,
+
+    /// The commands (e.g. `'add deps :foo'`), followed by the labels of the
+    /// targets to apply them to (e.g. `//foo:bar`)
+    #[clap(required = true)]
+    args: Vec<String>,
+}
@@ -0,0 +1,201 @@
+// command.rs
+
+use thiserror::Error;
+
+/// A buildozer-style **command**, applied to each of the targets selected by
+/// the labels it's given along with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `add <attr> <value(s)>`: Appends the values to the list attribute,
+    /// unless they're already present, creating the attribute if needed.
+    Add { attr: String, values: Vec<String> },
+    /// `remove <attr> [<value(s)>]`: Removes the values from the list
+    /// attribute (and the attribute itself once it's empty), or the attribute
+    /// altogether if no values are given.
+    Remove { attr: String, values: Vec<String> },
+    /// `set <attr> <value(s)>`: Sets the attribute, replacing its old value.
+    Set { attr: String, values: Vec<String> },
+    /// `replace <attr> <old_value> <new_value>`: Replaces the old value of the
+    /// attribute (or of an element of the list attribute) with the new one.
+    Replace {
+        attr: String,
+        old: String,
+        new: String,
+    },
+    /// `new <kind> <name> [(before|after) <rule_name>]`: Creates a new rule in
+    /// the package, at the end of the file or next to the given rule.
+    New {
+        kind: String,
+        name: String,
+        placement: Option<Placement>,
+    },
+    /// `delete`: Deletes the rule.
+    Delete,
+    /// `move <old_attr> <new_attr> <value(s)>`: Moves the values (or all of
+    /// them, given `*`) from one list attribute to another.
+    Move {
+        from: String,
+        to: String,
+        values: Vec<String>,
+    },
+    /// `print [<attr(s)>]`: Prints the attributes of the rule (or the whole
+    /// rule), with the special attributes `kind`, `name`, `label` and `rule`.
+    Print { attrs: Vec<String> },
+}
+
+/// Where a **new** rule is placed, relative to an existing one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    Before(String),
+    After(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CommandError {
+    #[error("Empty command")]
+    Empty,
+    #[error("Unknown command `{0}`. Expected one of: {}", Command::NAMES.join(", "))]
+    Unknown(String),
+    #[error("Invalid arguments for `{command}`. Usage: `{usage}`")]
+    Usage {
+        command: String,
+        usage: &'static str,
+    },
+}
+
+impl Command {
+    /// The **names** of all commands.
+    pub const NAMES: &'static [&'static str] = &[
+        "add", "remove", "set", "replace", "new", "delete", "move", "print",
+    ];
+
+    /// Whether the given command-line argument is a **command** (rather than
+    /// a label), judging by its first word.
+    pub fn is_command(arg: &str) -> bool {
+        matches!(arg.split_whitespace().next(), Some(name) if Command::NAMES.contains(&name))
+    }
+
+    /// Parses all commands of a command-line argument, which are separated by
+    /// `|` (e.g. `add deps :foo|remove tags manual`). Whitespace (and `|`) can
+    /// be escaped with a backslash (e.g. `set name hello\ world`).
+    pub fn parse_all(text: &str) -> Result<Vec<Command>, CommandError> {
+        split(text)
+            .into_iter()
+            .filter(|words| !words.is_empty())
+            .map(|words| Command::from_words(&words))
+            .collect()
+    }
+
+    fn from_words(words: &[String]) -> Result<Command, CommandError> {
+        let (name, args) = words.split_first().ok_or(CommandError::Empty)?;
+        let usage = |usage| CommandError::Usage {
+            command: name.clone(),
+            usage,
+        };
+
+        let command = match (name.as_str(), args) {
+            ("add", [attr, values @ ..]) if !values.is_empty() => Command::Add {
+                attr: attr.clone(),
+                values: values.to_vec(),
+            },
+            ("add", _) => return Err(usage("add <attr> <value(s)>")),
+            ("remove", [attr, values @ ..]) => Command::Remove {
+                attr: attr.clone(),
+                values: values.to_vec(),
+            },
+            ("remove", _) => return Err(usage("remove <attr> [<value(s)>]")),
+            ("set", [attr, values @ ..]) if !values.is_empty() => Command::Set {
+                attr: attr.clone(),
+                values: values.to_vec(),
+            },
+            ("set", _) => return Err(usage("set <attr> <value(s)>")),
+            ("replace", [attr, old, new]) => Command::Replace {
+                attr: attr.clone(),
+                old: old.clone(),
+                new: new.clone(),
+            },
+            ("replace", _) => return Err(usage("replace <attr> <old_value> <new_value>")),
+            ("new", [kind, name, rest @ ..]) => {
+                let placement = match rest {
+                    [] => None,
+                    [position, rule] if position == "before" => {
+                        Some(Placement::Before(rule.clone()))
+                    }
+                    [position, rule] if position == "after" => Some(Placement::After(rule.clone())),
+                    _ => return Err(usage(NEW_USAGE)),
+                };
+
+                Command::New {
+                    kind: kind.clone(),
+                    name: name.clone(),
+                    placement,
+                }
+            }
+            ("new", _) => return Err(usage(NEW_USAGE)),
+            ("delete", []) => Command::Delete,
+            ("delete", _) => return Err(usage("delete")),
+            ("move", [from, to, values @ ..]) if !values.is_empty() => Command::Move {
+                from: from.clone(),
+                to: to.clone(),
+                values: values.to_vec(),
+            },
+            ("move", _) => return Err(usage("move <old_attr> <new_attr> <value(s)>")),
+            ("print", attrs) => Command::Print {
+                attrs: attrs.to_vec(),
+            },
+            (name, _) => return Err(CommandError::Unknown(name.to_string())),
+        };
+
+        Ok(command)
+    }
+
+    /// The **name** of the command (e.g. `add`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Add { .. } => "add",
+            Command::Remove { .. } => "remove",
+            Command::Set { .. } => "set",
+            Command::Replace { .. } => "replace",
+            Command::New { .. } => "new",
+            Command::Delete => "delete",
+            Command::Move { .. } => "move",
+            Command::Print { .. } => "print",
+        }
+    }
+
+    /// Whether the command **modifies** rules (i.e. isn't `print`).
+    pub fn is_edit(&self) -> bool {
+        !matches!(self, Command::Print { .. })
+    }
+}
+
+const NEW_USAGE: &str = "new <kind> <name> [(before|after) <rule_name>]";
+
+/// Splits the given text into **commands** (separated by `|`) made up of
+/// **words** (separated by whitespace), unescaping backslash escapes.
+fn split(text: &str) -> Vec<Vec<String>> {
+    let mut commands = vec![Vec::new()];
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars.next().unwrap_or('\\');
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            '|' => {
+                let command = commands.last_mut().unwrap();
+                command.extend(word.take());
+                commands.push(Vec::new());
+            }
+            c if c.is_whitespace() => {
+                commands.last_mut().unwrap().extend(word.take());
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    commands.last_mut().unwrap().extend(word.take());
+
+    commands
+}
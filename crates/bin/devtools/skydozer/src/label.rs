@@ -0,0 +1,146 @@
+// label.rs
+
+use std::fmt;
+use thiserror::Error;
+
+/// A **target pattern**, selecting the targets of one or more packages which
+/// commands are applied to (e.g. `//foo:bar`, `:bar`, `//foo/...:%rust_library`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    /// The package, relative to the workspace root (e.g. `foo/bar`).
+    package: String,
+    /// Whether the pattern applies to all packages **below** the package as
+    /// well (e.g. `//foo/...`).
+    recursive: bool,
+    target: Target,
+}
+
+/// The **target(s)** selected within a package.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// The target with the given name (e.g. `:bar`).
+    Name(String),
+    /// All targets (i.e. `:*` or `:all`).
+    All,
+    /// All targets of the given rule kind (e.g. `:%rust_library`).
+    Kind(String),
+    /// The package itself (i.e. `:__pkg__`), which is what e.g. `new` applies to.
+    Package,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LabelError {
+    #[error("Invalid label `{0}`. Labels must either be absolute (e.g. `//foo:bar`) or relative to the current package (e.g. `:bar`)")]
+    Invalid(String),
+    #[error("Unsupported label `{0}`. Labels of external repositories can't be edited")]
+    ExternalRepository(String),
+}
+
+impl Label {
+    /// Parses a target pattern, resolving relative labels (e.g. `:bar`)
+    /// against the given package.
+    pub fn parse(text: &str, current_package: &str) -> Result<Label, LabelError> {
+        if text.starts_with('@') {
+            return Err(LabelError::ExternalRepository(text.to_string()));
+        }
+
+        let (package, target) = if let Some(rest) = text.strip_prefix("//") {
+            match rest.split_once(':') {
+                Some((package, target)) => (package, Some(target)),
+                None => (rest, None),
+            }
+        } else if let Some(target) = text.strip_prefix(':') {
+            (current_package, Some(target))
+        } else {
+            return Err(LabelError::Invalid(text.to_string()));
+        };
+
+        let (package, recursive) = match package.strip_suffix("...") {
+            Some(package) => (package.trim_end_matches('/'), true),
+            None => (package, false),
+        };
+
+        let target = match target {
+            Some("" | "*" | "all") => Target::All,
+            Some("__pkg__") => Target::Package,
+            Some(target) => match target.strip_prefix('%') {
+                Some(kind) => Target::Kind(kind.to_string()),
+                None => Target::Name(target.to_string()),
+            },
+            // `//foo/bar` is short for `//foo/bar:bar`, while `//foo/...` is
+            // short for `//foo/...:all`.
+            None if recursive => Target::All,
+            None => match package.rsplit('/').next() {
+                Some(name) if !name.is_empty() => Target::Name(name.to_string()),
+                _ => return Err(LabelError::Invalid(text.to_string())),
+            },
+        };
+
+        Ok(Label {
+            package: package.to_string(),
+            recursive,
+            target,
+        })
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "//{}", self.package)?;
+        if self.recursive {
+            let separator = if self.package.is_empty() { "" } else { "/" };
+            write!(f, "{separator}...")?;
+        }
+
+        match &self.target {
+            Target::Name(name) => write!(f, ":{name}"),
+            Target::All => write!(f, ":all"),
+            Target::Kind(kind) => write!(f, ":%{kind}"),
+            Target::Package => write!(f, ":__pkg__"),
+        }
+    }
+}
+
+/// **Normalizes** a label-like value (e.g. a dependency) relative to the given
+/// package, so that different spellings of the same label compare equal (e.g.
+/// `:bar`, `//foo:bar` and `//foo/bar` from within the package `foo/bar`).
+///
+/// Values which aren't labels (e.g. source files) are returned as is.
+pub fn normalize(value: &str, package: &str) -> String {
+    if let Some(name) = value.strip_prefix(':') {
+        return format!("//{package}:{name}");
+    }
+
+    match value.strip_prefix("//") {
+        Some(rest) if !rest.contains(':') => {
+            let name = rest.rsplit('/').next().unwrap_or_default();
+            format!("//{rest}:{name}")
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// **Shortens** a label-like value relative to the given package, i.e.
+/// labels of targets within the package are made relative (e.g. `:bar`).
+pub fn shorten(value: &str, package: &str) -> String {
+    let normalized = normalize(value, package);
+    match normalized
+        .strip_prefix("//")
+        .and_then(|rest| rest.split_once(':'))
+    {
+        Some((value_package, name)) if value_package == package => format!(":{name}"),
+        _ => value.to_string(),
+    }
+}
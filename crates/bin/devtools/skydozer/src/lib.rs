@@ -0,0 +1,205 @@
+//! **skydozer** is a [buildozer]-compatible command-line tool for refactoring
+//! `BUILD` files, built on top of `sky_syntax`.
+//!
+//! Commands (e.g. `add deps :foo`) are applied to the targets selected by
+//! labels (e.g. `//foo:bar`, `//foo:*` or `//foo/...:%rust_library`), where a
+//! target is a top-level call with a `name` (e.g. `rust_library(name = "bar")`).
+//! Files are edited **losslessly**, i.e. they only change where edited, with
+//! formatting and comments preserved everywhere else.
+//!
+//! ```sh
+//! skydozer 'add deps //base' 'set visibility //visibility:public' //foo:bar
+//! skydozer 'new rust_test bar_test after bar' //foo:__pkg__
+//! skydozer 'print kind name srcs' //foo/...:all
+//! ```
+//!
+//! [buildozer]: https://github.com/bazelbuild/buildtools/tree/master/buildozer
+
+pub mod build_file;
+pub mod command;
+pub mod label;
+pub mod value;
+
+pub use crate::{
+    build_file::{BuildFile, EditError, Rule},
+    command::{Command, CommandError, Placement},
+    label::{Label, LabelError, Target},
+};
+
+use anyhow::{Context, Result};
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// The names of BUILD files, in order of precedence.
+pub const BUILD_FILE_NAMES: &[&str] = &["BUILD.bazel", "BUILD", "BUCK"];
+
+/// Files marking the **root** of a workspace.
+const WORKSPACE_FILE_NAMES: &[&str] = &[
+    "WORKSPACE",
+    "WORKSPACE.bazel",
+    "MODULE.bazel",
+    ".buckconfig",
+];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The root of the workspace, which packages are relative to.
+    pub root: PathBuf,
+    /// The names of BUILD files, in order of precedence.
+    pub build_file_names: Vec<String>,
+    /// Whether to print modified files to `out` instead of rewriting them.
+    pub stdout: bool,
+    /// Whether to keep going when a command fails for some target.
+    pub keep_going: bool,
+}
+
+impl Options {
+    pub fn new(root: impl Into<PathBuf>) -> Options {
+        Options {
+            root: root.into(),
+            build_file_names: BUILD_FILE_NAMES.iter().map(ToString::to_string).collect(),
+            stdout: false,
+            keep_going: false,
+        }
+    }
+}
+
+/// The **outcome** of [`run`].
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The BUILD files which were modified.
+    pub modified: Vec<PathBuf>,
+    /// The errors encountered while keeping going.
+    pub errors: Vec<anyhow::Error>,
+}
+
+/// **Applies** the given commands to each of the targets selected by the given
+/// labels, and then writes back the modified BUILD files (or prints them).
+///
+/// Unless keeping going, nothing is written if a command fails.
+pub fn run(
+    options: &Options,
+    commands: &[Command],
+    labels: &[Label],
+    out: &mut dyn Write,
+) -> Result<Outcome> {
+    let mut files = BTreeMap::new();
+    let mut outcome = Outcome::default();
+
+    for label in labels {
+        if let Err(error) = apply(options, commands, label, &mut files, out) {
+            if !options.keep_going {
+                return Err(error);
+            }
+            outcome.errors.push(error);
+        }
+    }
+
+    for (path, file) in files {
+        if !file.is_modified() {
+            continue;
+        }
+
+        if options.stdout {
+            write!(out, "{}", file.text())?;
+        } else {
+            fs::write(&path, file.text())
+                .with_context(|| format!("Failed to write `{}`", path.display()))?;
+        }
+        outcome.modified.push(path);
+    }
+
+    Ok(outcome)
+}
+
+fn apply(
+    options: &Options,
+    commands: &[Command],
+    label: &Label,
+    files: &mut BTreeMap<PathBuf, BuildFile>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let packages = if label.is_recursive() {
+        packages_below(options, label.package())
+    } else {
+        vec![label.package().to_string()]
+    };
+
+    for package in packages {
+        let path = build_file(options, &package)
+            .with_context(|| format!("No BUILD file found for `{label}`"))?;
+        let file = match files.entry(path) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let text = fs::read_to_string(entry.key())
+                    .with_context(|| format!("Failed to read `{}`", entry.key().display()))?;
+                entry.insert(BuildFile::parse(&package, &text)?)
+            }
+        };
+
+        for command in commands {
+            match file.apply(command, label.target(), out) {
+                // Patterns spanning several packages select whichever of them
+                // have the target.
+                Err(EditError::NoSuchRule { .. }) if label.is_recursive() => {}
+                result => {
+                    result.with_context(|| format!("Failed to edit `{label}`"))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The **BUILD file** of the given package, if any.
+fn build_file(options: &Options, package: &str) -> Option<PathBuf> {
+    let dir = options.root.join(package);
+    options
+        .build_file_names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// All **packages** at or below the given one, i.e. all directories with a
+/// BUILD file (skipping hidden directories).
+fn packages_below(options: &Options, package: &str) -> Vec<String> {
+    let mut packages = WalkDir::new(options.root.join(package))
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&options.root).ok()?;
+            let package = relative
+                .components()
+                .map(|it| it.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            build_file(options, &package).map(|_| package)
+        })
+        .collect::<Vec<_>>();
+    packages.sort();
+
+    packages
+}
+
+/// Finds the **root** of the workspace containing the given directory, i.e.
+/// the closest ancestor with a `WORKSPACE`, `MODULE.bazel` or `.buckconfig`.
+pub fn find_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|it| {
+            WORKSPACE_FILE_NAMES
+                .iter()
+                .any(|name| it.join(name).exists())
+        })
+        .map(Path::to_path_buf)
+}
@@ -0,0 +1,87 @@
+// main.rs
+
+mod cli;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use cli::Cli;
+use skydozer::{find_root, run, Command, Label, Options};
+use std::{env, io, process::ExitCode};
+
+/// Exit code when nothing needed to be modified (as with buildozer).
+const NOTHING_MODIFIED: u8 = 3;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match skydozer(&cli) {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn skydozer(cli: &Cli) -> Result<u8> {
+    let cwd = env::current_dir().context("Failed to determine the current directory")?;
+    let root = match cli.root() {
+        Some(root) => root.clone(),
+        None => find_root(&cwd).unwrap_or_else(|| cwd.clone()),
+    };
+    let current_package = cwd
+        .strip_prefix(&root)
+        .map(|it| {
+            it.components()
+                .map(|it| it.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default();
+
+    // Commands come first, followed by the labels they're applied to.
+    let split = cli
+        .args()
+        .iter()
+        .position(|arg| !Command::is_command(arg))
+        .unwrap_or(cli.args().len());
+    let (commands, labels) = cli.args().split_at(split);
+    if commands.is_empty() || labels.is_empty() {
+        bail!("Expected one or more commands, followed by one or more labels (e.g. `skydozer 'add deps :foo' //bar:baz`)");
+    }
+
+    let commands = commands
+        .iter()
+        .map(|it| Command::parse_all(it))
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+    let labels = labels
+        .iter()
+        .map(|it| Label::parse(it, &current_package))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut options = Options::new(root);
+    options.build_file_names = cli.build_file_names().clone();
+    options.stdout = *cli.stdout();
+    options.keep_going = *cli.keep_going();
+
+    let outcome = run(&options, &commands, &labels, &mut io::stdout().lock())?;
+    for path in &outcome.modified {
+        if !options.stdout {
+            eprintln!("fixed {}", path.display());
+        }
+    }
+    for error in &outcome.errors {
+        eprintln!("error: {error:#}");
+    }
+
+    let code = if !outcome.errors.is_empty() {
+        2
+    } else if outcome.modified.is_empty() && commands.iter().any(Command::is_edit) {
+        NOTHING_MODIFIED
+    } else {
+        0
+    };
+
+    Ok(code)
+}
@@ -0,0 +1,185 @@
+// value.rs
+
+use sky_syntax::{
+    ast::{self, make, AstNode, AstToken},
+    File, SyntaxKind, SyntaxNode,
+};
+
+use crate::label;
+
+/// Attributes which are **lists** (of labels, files, etc.) by convention, so
+/// that a single value is still set as a list (e.g. `set deps :foo`).
+const LIST_ATTRIBUTES: &[&str] = &[
+    "args",
+    "compatible_with",
+    "copts",
+    "data",
+    "defines",
+    "deps",
+    "exec_compatible_with",
+    "exports",
+    "features",
+    "hdrs",
+    "includes",
+    "licenses",
+    "linkopts",
+    "outs",
+    "resources",
+    "restricted_to",
+    "runtime_deps",
+    "srcs",
+    "tags",
+    "target_compatible_with",
+    "textual_hdrs",
+    "tools",
+    "visibility",
+];
+
+pub fn is_list_attribute(attr: &str) -> bool {
+    LIST_ATTRIBUTES.contains(&attr)
+}
+
+/// Converts a **command-line value** into an expression.
+///
+/// Values which look like Starlark expressions (e.g. `True`, `42`, `["a"]`
+/// or `glob(["*.rs"])`) are parsed as such, while anything else becomes a
+/// **string** (e.g. `//foo:bar` or `foo.rs`). Labels of targets within the
+/// given package are shortened (e.g. `//foo:bar` becomes `:bar` in `foo`).
+pub fn parse(text: &str, package: &str) -> ast::Test {
+    expression(text).unwrap_or_else(|| make::string_expr(&label::shorten(text, package)).into())
+}
+
+/// Converts the **command-line values** of the given attribute into an
+/// expression, i.e. a list if there are several values (or the attribute is
+/// a list by convention), and a single value otherwise.
+pub fn parse_all(attr: &str, values: &[String], package: &str) -> ast::Test {
+    match values {
+        [value] if !is_list_attribute(attr) || expression(value).is_some() => parse(value, package),
+        _ => make::list_expr(values.iter().map(|value| parse(value, package))).into(),
+    }
+}
+
+/// The given value as an **expression**, if it looks like one.
+fn expression(text: &str) -> Option<ast::Test> {
+    looks_like_expression(text)
+        .then(|| parse_expression(text))
+        .flatten()
+}
+
+fn looks_like_expression(text: &str) -> bool {
+    let call = text.ends_with(')')
+        && matches!(text.split_once('('), Some((callee, _)) if !callee.is_empty()
+            && callee.chars().all(|c| c == '_' || c == '.' || c.is_ascii_alphanumeric()));
+
+    matches!(text, "True" | "False" | "None")
+        || text.parse::<i64>().is_ok()
+        || text.starts_with(['[', '{', '(', '"', '\''])
+        || call
+}
+
+/// Parses the given text as a **single expression**, if it is one.
+fn parse_expression(text: &str) -> Option<ast::Test> {
+    let parse = File::parse(&format!("_ = {text}\n"));
+    if !parse.errors().is_empty() {
+        return None;
+    }
+
+    let rhs = parse
+        .syntax_node()
+        .descendants()
+        .find_map(ast::AssignStmt::cast)?
+        .rhs()?;
+    let mut tests = rhs.tests();
+    match (tests.next(), tests.next()) {
+        (Some(test), None) => ast::Test::cast(test.syntax().clone_subtree()),
+        _ => None,
+    }
+}
+
+/// A **copy** of the given expression without its leading trivia (e.g. the
+/// comment on the line above an element of a list).
+pub fn copy(test: &ast::Test) -> ast::Test {
+    parse_expression(&source(test.syntax())).unwrap_or_else(|| test.clone())
+}
+
+/// The value of the given expression if it's a **string literal**.
+pub fn as_string(test: &ast::Test) -> Option<String> {
+    let operand = plain_operand(test)?;
+    operand
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .find_map(ast::String::cast)?
+        .value()
+        .ok()
+}
+
+/// The given expression if it's a **list literal**.
+pub fn as_list(test: &ast::Test) -> Option<ast::ListExpr> {
+    plain_operand(test)?.list_expr()
+}
+
+/// The **elements** of the given list.
+pub fn elements(list: &ast::ListExpr) -> Vec<ast::Test> {
+    list.expression()
+        .map(|it| it.tests().collect())
+        .unwrap_or_default()
+}
+
+/// The operand of the given expression if it's **only** an operand (e.g. not
+/// a call or a field access).
+fn plain_operand(test: &ast::Test) -> Option<ast::Operand> {
+    let ast::Test::PrimaryExpr(primary) = test else {
+        return None;
+    };
+    let plain = primary.primary_expr().is_none()
+        && primary.dot_suffix().is_none()
+        && primary.call_suffix().is_none()
+        && primary.slice_suffix().is_none();
+
+    plain.then(|| primary.operand()).flatten()
+}
+
+/// Whether the given expression **matches** the given command-line value,
+/// i.e. is a string with the same value (comparing labels in their
+/// normalized form) or has the same source text.
+pub fn matches(test: &ast::Test, value: &str, package: &str) -> bool {
+    match as_string(test) {
+        Some(string) => label::normalize(&string, package) == label::normalize(value, package),
+        None => source(test.syntax()) == value,
+    }
+}
+
+/// **Renders** the given expression for `print`: strings without quotes,
+/// lists as their space-separated elements in brackets, and anything else
+/// as its source text.
+pub fn render(test: &ast::Test) -> String {
+    if let Some(string) = as_string(test) {
+        return string;
+    }
+
+    match as_list(test) {
+        Some(list) => {
+            let elements = elements(&list).iter().map(render).collect::<Vec<_>>();
+            format!("[{}]", elements.join(" "))
+        }
+        None => source(test.syntax()),
+    }
+}
+
+/// The **source text** of the given node, without its leading trivia.
+pub fn source(node: &SyntaxNode) -> String {
+    let text = node.to_string();
+    let trivia = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .take_while(|it| is_trivia(it.kind()))
+        .map(|it| it.text().len())
+        .sum::<usize>();
+
+    text[trivia..].trim_end().to_string()
+}
+
+pub fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::WHITESPACE | SyntaxKind::COMMENT)
+}
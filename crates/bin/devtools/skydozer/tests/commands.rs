@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod commands_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::File;
+    use skydozer::{BuildFile, Command, CommandError, EditError, Label, Placement};
+
+    const SOURCE: &str = r#"# Header.
+
+load("@rules_rust//rust:defs.bzl", "rust_library", "rust_test")
+
+# The library.
+rust_library(
+    name = "foo",
+    srcs = ["lib.rs"],
+    deps = [
+        "//base",  # core
+        ":util",
+    ],
+)
+
+rust_library(name = "util", srcs = ["util.rs"])
+"#;
+
+    /// Applies the given commands to the given label of `SOURCE` (in the
+    /// package `pkg`), returning the edited text and whether it was modified.
+    /// The edited text must parse without errors.
+    fn edit(commands: &str, label: &str) -> Result<(String, bool), EditError> {
+        let mut file = BuildFile::parse("pkg", SOURCE)?;
+        let label = Label::parse(label, "pkg").unwrap();
+
+        let mut modified = false;
+        for command in Command::parse_all(commands).unwrap() {
+            modified |= file.apply(&command, label.target(), &mut Vec::new())?;
+        }
+
+        let text = file.text();
+        let parse = File::parse(&text);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+        assert_eq!(file.is_modified(), text != SOURCE);
+
+        Ok((text, modified))
+    }
+
+    /// `SOURCE`, with the given text replaced.
+    fn expected(from: &str, to: &str) -> String {
+        assert!(SOURCE.contains(from), "{from:?}");
+        SOURCE.replacen(from, to, 1)
+    }
+
+    #[rstest]
+    #[case::add(
+        "add deps //other :extra",
+        "        \":util\",\n",
+        "        \":util\",\n        \"//other\",\n        \":extra\",\n"
+    )]
+    #[case::add_shortens_labels(
+        "add deps //pkg:extra",
+        "        \":util\",\n",
+        "        \":util\",\n        \":extra\",\n"
+    )]
+    #[case::add_missing(
+        "add data data.txt",
+        "        \":util\",\n    ],\n",
+        "        \":util\",\n    ],\n    data = [\"data.txt\"],\n"
+    )]
+    #[case::remove(
+        "remove deps //pkg:util",
+        "        \"//base\",  # core\n        \":util\",\n",
+        "        \"//base\",  # core\n"
+    )]
+    #[case::remove_last_value("remove srcs lib.rs", "    srcs = [\"lib.rs\"],\n", "")]
+    #[case::remove_attribute(
+        "remove deps",
+        "    deps = [\n        \"//base\",  # core\n        \":util\",\n    ],\n",
+        ""
+    )]
+    #[case::set("set srcs main.rs", "srcs = [\"lib.rs\"]", "srcs = [\"main.rs\"]")]
+    #[case::set_expression(
+        "set srcs glob([\"*.rs\"])",
+        "srcs = [\"lib.rs\"]",
+        "srcs = glob([\"*.rs\"])"
+    )]
+    #[case::set_new(
+        "set testonly True",
+        "        \":util\",\n    ],\n",
+        "        \":util\",\n    ],\n    testonly = True,\n"
+    )]
+    #[case::set_escaped(
+        "set srcs a\\ b.rs c.rs",
+        "srcs = [\"lib.rs\"]",
+        "srcs = [\"a b.rs\", \"c.rs\"]"
+    )]
+    #[case::replace(
+        "replace deps //base:base //base:core",
+        "\"//base\",",
+        "\"//base:core\","
+    )]
+    #[case::move_values(
+        "move deps runtime_deps :util",
+        "        \":util\",\n    ],\n",
+        "    ],\n    runtime_deps = [\":util\"],\n"
+    )]
+    #[case::several("set srcs main.rs|remove deps", "    srcs = [\"lib.rs\"],\n    deps = [\n        \"//base\",  # core\n        \":util\",\n    ],\n", "    srcs = [\"main.rs\"],\n")]
+    fn test_edit(#[case] commands: &str, #[case] from: &str, #[case] to: &str) {
+        let (text, modified) = edit(commands, ":foo").unwrap();
+
+        assert_eq!(text, expected(from, to));
+        assert!(modified);
+    }
+
+    #[rstest]
+    #[case::add_present("add deps //pkg:util //base")]
+    #[case::remove_missing_value("remove deps //other")]
+    #[case::remove_missing_attribute("remove data")]
+    #[case::set_same("set srcs lib.rs")]
+    #[case::replace_missing("replace deps //other //another")]
+    #[case::print("print")]
+    fn test_unmodified(#[case] commands: &str) {
+        let (text, modified) = edit(commands, ":foo").unwrap();
+
+        assert_eq!(text, SOURCE);
+        assert!(!modified);
+    }
+
+    #[test]
+    fn test_move_all() {
+        let (text, _) = edit("move deps runtime_deps *", ":foo").unwrap();
+
+        assert_eq!(
+            text,
+            expected(
+                "    deps = [\n        \"//base\",  # core\n        \":util\",\n    ],\n",
+                "    runtime_deps = [\n        \"//base\",  # core\n        \":util\",\n    ],\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_move_keeps_comments() {
+        let source = "rust_library(\n    name = \"foo\",\n    deps = [\n        # keep sorted\n        \"//a\",  # TODO\n        \"//b\",\n    ],\n    runtime_deps = [\"//c\"],\n)\n";
+        let mut file = BuildFile::parse("pkg", source).unwrap();
+        file.apply(
+            &Command::parse_all("move deps runtime_deps *").unwrap()[0],
+            &skydozer::Target::Name("foo".to_string()),
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.text(),
+            "rust_library(\n    name = \"foo\",\n    runtime_deps = [\n        \"//c\",\n        # keep sorted\n        \"//a\",  # TODO\n        \"//b\",\n    ],\n)\n"
+        );
+    }
+
+    #[test]
+    fn test_edit_single_line_rule() {
+        let (text, _) = edit(
+            "add srcs extra.rs|set visibility //visibility:public",
+            ":util",
+        )
+        .unwrap();
+
+        assert_eq!(
+            text,
+            expected(
+                "rust_library(name = \"util\", srcs = [\"util.rs\"])",
+                "rust_library(name = \"util\", srcs = [\"util.rs\", \"extra.rs\"], visibility = [\"//visibility:public\"])"
+            )
+        );
+    }
+
+    #[test]
+    fn test_edit_all_rules_of_a_kind() {
+        let (text, modified) = edit("add tags manual", "//pkg:%rust_library").unwrap();
+
+        assert!(modified);
+        assert_eq!(text.matches("tags = [\"manual\"]").count(), 2);
+    }
+
+    #[rstest]
+    #[case::first(
+        ":foo",
+        "# The library.\nrust_library(\n    name = \"foo\",\n    srcs = [\"lib.rs\"],\n    deps = [\n        \"//base\",  # core\n        \":util\",\n    ],\n)\n\n"
+    )]
+    #[case::last(":util", "\nrust_library(name = \"util\", srcs = [\"util.rs\"])\n")]
+    fn test_delete(#[case] label: &str, #[case] deleted: &str) {
+        let (text, _) = edit("delete", label).unwrap();
+
+        assert_eq!(text, expected(deleted, ""));
+    }
+
+    #[test]
+    fn test_delete_keeps_file_header() {
+        let mut file = BuildFile::parse(
+            "pkg",
+            "# Header.\n\n# Foo.\nfoo(name = \"foo\")\n\nbar(name = \"bar\")\n",
+        )
+        .unwrap();
+        file.apply(
+            &Command::Delete,
+            &skydozer::Target::Name("foo".to_string()),
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(file.text(), "# Header.\n\nbar(name = \"bar\")\n");
+    }
+
+    #[rstest]
+    #[case::end(
+        "new rust_test foo_test",
+        "\nrust_library(name = \"util\", srcs = [\"util.rs\"])\n",
+        "\nrust_library(name = \"util\", srcs = [\"util.rs\"])\n\nrust_test(name = \"foo_test\")\n"
+    )]
+    #[case::after(
+        "new rust_test foo_test after foo",
+        "\nrust_library(name = \"util\"",
+        "\nrust_test(name = \"foo_test\")\n\nrust_library(name = \"util\""
+    )]
+    #[case::before(
+        "new rust_test foo_test before foo",
+        "# The library.\n",
+        "rust_test(name = \"foo_test\")\n\n# The library.\n"
+    )]
+    #[case::dotted_kind(
+        "new native.genrule gen",
+        "srcs = [\"util.rs\"])\n",
+        "srcs = [\"util.rs\"])\n\nnative.genrule(name = \"gen\")\n"
+    )]
+    fn test_new(#[case] commands: &str, #[case] from: &str, #[case] to: &str) {
+        let (text, modified) = edit(commands, "//pkg:__pkg__").unwrap();
+
+        assert_eq!(text, expected(from, to));
+        assert!(modified);
+    }
+
+    #[test]
+    fn test_print() {
+        let mut file = BuildFile::parse("pkg", SOURCE).unwrap();
+        let mut out = Vec::new();
+        for (command, label) in [
+            ("print kind name label srcs deps data", "//pkg:all"),
+            ("print", ":util"),
+        ] {
+            let label = Label::parse(label, "pkg").unwrap();
+            for command in Command::parse_all(command).unwrap() {
+                file.apply(&command, label.target(), &mut out).unwrap();
+            }
+        }
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "rust_library foo //pkg:foo [lib.rs] [//base :util] (missing)\n\
+             rust_library util //pkg:util [util.rs] (missing) (missing)\n\
+             rust_library(name = \"util\", srcs = [\"util.rs\"])\n"
+        );
+    }
+
+    #[rstest]
+    #[case::missing_rule("set srcs a.rs", ":missing", "No rule named `missing` in `//pkg`")]
+    #[case::not_a_list(
+        "add name bar",
+        ":foo",
+        "Attribute `name` of `//pkg:foo` is not a list"
+    )]
+    #[case::existing_rule(
+        "new rust_test util",
+        "//pkg:__pkg__",
+        "A rule named `util` already exists in `//pkg`"
+    )]
+    #[case::new_on_rule(
+        "new rust_test bar",
+        ":foo",
+        "`new` applies to packages (e.g. `//foo:__pkg__`)"
+    )]
+    #[case::edit_on_package(
+        "delete",
+        "//pkg:__pkg__",
+        "`delete` applies to rules (e.g. `//foo:bar`)"
+    )]
+    fn test_errors(#[case] commands: &str, #[case] label: &str, #[case] message: &str) {
+        assert_eq!(edit(commands, label).unwrap_err().to_string(), message);
+    }
+
+    #[test]
+    fn test_refuses_files_with_syntax_errors() {
+        let error = BuildFile::parse("pkg", "foo(name = \"foo\"\nbar(\n").unwrap_err();
+
+        assert!(matches!(error, EditError::Parse { .. }), "{error}");
+    }
+
+    #[rstest]
+    #[case::add("add deps :foo", Command::Add { attr: "deps".to_string(), values: vec![":foo".to_string()] })]
+    #[case::remove("remove deps", Command::Remove { attr: "deps".to_string(), values: vec![] })]
+    #[case::replace("replace deps :a :b", Command::Replace { attr: "deps".to_string(), old: ":a".to_string(), new: ":b".to_string() })]
+    #[case::new("new cc_library foo before bar", Command::New { kind: "cc_library".to_string(), name: "foo".to_string(), placement: Some(Placement::Before("bar".to_string())) })]
+    #[case::delete("  delete  ", Command::Delete)]
+    #[case::print("print", Command::Print { attrs: vec![] })]
+    fn test_parse_command(#[case] text: &str, #[case] command: Command) {
+        assert_eq!(Command::parse_all(text), Ok(vec![command]));
+    }
+
+    #[rstest]
+    #[case::unknown("frobnicate deps", "Unknown command `frobnicate`. Expected one of: add, remove, set, replace, new, delete, move, print")]
+    #[case::missing_values(
+        "add deps",
+        "Invalid arguments for `add`. Usage: `add <attr> <value(s)>`"
+    )]
+    #[case::bad_placement(
+        "new cc_library foo beside bar",
+        "Invalid arguments for `new`. Usage: `new <kind> <name> [(before|after) <rule_name>]`"
+    )]
+    #[case::extra_arguments("delete now", "Invalid arguments for `delete`. Usage: `delete`")]
+    fn test_parse_invalid_command(#[case] text: &str, #[case] message: &str) {
+        let error: CommandError = Command::parse_all(text).unwrap_err();
+
+        assert_eq!(error.to_string(), message);
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod labels_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use skydozer::{
+        label::{normalize, shorten},
+        Label, LabelError, Target,
+    };
+
+    fn name(name: &str) -> Target {
+        Target::Name(name.to_string())
+    }
+
+    #[rstest]
+    #[case::absolute("//foo/bar:baz", "foo/bar", false, name("baz"))]
+    #[case::relative(":baz", "pkg", false, name("baz"))]
+    #[case::implicit_name("//foo/bar", "foo/bar", false, name("bar"))]
+    #[case::root_package("//:baz", "", false, name("baz"))]
+    #[case::all("//foo:all", "foo", false, Target::All)]
+    #[case::star("//foo:*", "foo", false, Target::All)]
+    #[case::kind("//foo:%cc_library", "foo", false, Target::Kind("cc_library".to_string()))]
+    #[case::package("//foo:__pkg__", "foo", false, Target::Package)]
+    #[case::recursive("//foo/...", "foo", true, Target::All)]
+    #[case::recursive_kind("//foo/...:%cc_test", "foo", true, Target::Kind("cc_test".to_string()))]
+    #[case::everything("//...", "", true, Target::All)]
+    fn test_parse(
+        #[case] text: &str,
+        #[case] package: &str,
+        #[case] recursive: bool,
+        #[case] target: Target,
+    ) {
+        let label = Label::parse(text, "pkg").unwrap();
+
+        assert_eq!(label.package(), package);
+        assert_eq!(label.is_recursive(), recursive);
+        assert_eq!(label.target(), &target);
+    }
+
+    #[rstest]
+    #[case::absolute("//foo/bar:baz")]
+    #[case::package("//foo:__pkg__")]
+    #[case::recursive("//foo/...:all")]
+    #[case::root_recursive("//...:%cc_library")]
+    fn test_display_roundtrips(#[case] text: &str) {
+        assert_eq!(Label::parse(text, "").unwrap().to_string(), text);
+    }
+
+    #[rstest]
+    #[case::bare("foo", LabelError::Invalid("foo".to_string()))]
+    #[case::external("@repo//foo:bar", LabelError::ExternalRepository("@repo//foo:bar".to_string()))]
+    #[case::root_without_name("//", LabelError::Invalid("//".to_string()))]
+    fn test_invalid(#[case] text: &str, #[case] error: LabelError) {
+        assert_eq!(Label::parse(text, "pkg"), Err(error));
+    }
+
+    #[rstest]
+    #[case::relative(":bar", "//foo:bar")]
+    #[case::implicit_name("//foo/bar", "//foo/bar:bar")]
+    #[case::absolute("//foo:bar", "//foo:bar")]
+    #[case::file("bar.rs", "bar.rs")]
+    fn test_normalize(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(normalize(value, "foo"), expected);
+    }
+
+    #[rstest]
+    #[case::same_package("//foo:bar", ":bar")]
+    #[case::other_package("//baz:bar", "//baz:bar")]
+    #[case::implicit_name("//foo", ":foo")]
+    #[case::file("bar.rs", "bar.rs")]
+    fn test_shorten(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(shorten(value, "foo"), expected);
+    }
+}
@@ -0,0 +1,3 @@
+mod commands;
+mod labels;
+mod run;
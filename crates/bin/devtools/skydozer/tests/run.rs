@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod run_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use skydozer::{find_root, run, Command, Label, Options};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        process,
+    };
+
+    /// A scratch **workspace** with the given files, removed when dropped.
+    struct Workspace {
+        root: PathBuf,
+    }
+
+    impl Workspace {
+        fn new(name: &str, files: &[(&str, &str)]) -> Workspace {
+            let root = std::env::temp_dir().join(format!("skydozer-{name}-{}", process::id()));
+            let _ = fs::remove_dir_all(&root);
+            for (path, text) in files {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, text).unwrap();
+            }
+
+            Workspace { root }
+        }
+
+        fn read(&self, path: &str) -> String {
+            fs::read_to_string(self.root.join(path)).unwrap()
+        }
+
+        fn run(
+            &self,
+            options: Options,
+            commands: &str,
+            labels: &[&str],
+        ) -> (skydozer::Outcome, String) {
+            let commands = Command::parse_all(commands).unwrap();
+            let labels = labels
+                .iter()
+                .map(|it| Label::parse(it, "").unwrap())
+                .collect::<Vec<_>>();
+
+            let mut out = Vec::new();
+            let outcome = run(&options, &commands, &labels, &mut out).unwrap();
+
+            (outcome, String::from_utf8(out).unwrap())
+        }
+
+        fn path(&self, path: &str) -> PathBuf {
+            self.root.join(path)
+        }
+    }
+
+    impl Drop for Workspace {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    const FOO: &str = "rust_library(\n    name = \"foo\",\n    deps = [\"//base\"],  # keep\n)\n\n# Tests.\nrust_test(name = \"foo_test\")\n";
+
+    #[test]
+    fn test_rewrites_only_modified_files() {
+        let workspace = Workspace::new(
+            "rewrite",
+            &[
+                ("WORKSPACE", ""),
+                ("foo/BUILD.bazel", FOO),
+                ("bar/BUILD", "rust_library(name = \"bar\")\n"),
+            ],
+        );
+
+        let (outcome, out) = workspace.run(
+            Options::new(&workspace.root),
+            "add deps //base:util",
+            &["//foo:foo", "//bar:bar"],
+        );
+
+        assert_eq!(
+            outcome.modified,
+            vec![
+                workspace.path("bar/BUILD"),
+                workspace.path("foo/BUILD.bazel")
+            ]
+        );
+        assert_eq!(out, "");
+        assert_eq!(
+            workspace.read("foo/BUILD.bazel"),
+            FOO.replace("[\"//base\"]", "[\"//base\", \"//base:util\"]")
+        );
+        assert_eq!(
+            workspace.read("bar/BUILD"),
+            "rust_library(name = \"bar\", deps = [\"//base:util\"])\n"
+        );
+    }
+
+    #[test]
+    fn test_stdout_and_recursive_patterns() {
+        let workspace = Workspace::new(
+            "stdout",
+            &[
+                ("foo/BUILD.bazel", FOO),
+                ("foo/sub/BUCK", "rust_test(name = \"sub_test\")\n"),
+                ("foo/.hidden/BUILD", "rust_test(name = \"hidden_test\")\n"),
+            ],
+        );
+        let mut options = Options::new(&workspace.root);
+        options.stdout = true;
+
+        let (outcome, out) = workspace.run(options, "set size small", &["//foo/...:%rust_test"]);
+
+        assert_eq!(outcome.modified.len(), 2);
+        assert_eq!(
+            out,
+            format!(
+                "{}rust_test(name = \"sub_test\", size = \"small\")\n",
+                FOO.replace(
+                    "name = \"foo_test\"",
+                    "name = \"foo_test\", size = \"small\""
+                )
+            )
+        );
+        assert_eq!(workspace.read("foo/BUILD.bazel"), FOO);
+    }
+
+    #[test]
+    fn test_keep_going() {
+        let workspace = Workspace::new("keep_going", &[("foo/BUILD", FOO)]);
+        let commands = Command::parse_all("add tags manual").unwrap();
+        let labels = ["//foo:missing", "//foo:foo_test"]
+            .iter()
+            .map(|it| Label::parse(it, "").unwrap())
+            .collect::<Vec<_>>();
+
+        let error = run(
+            &Options::new(&workspace.root),
+            &commands,
+            &labels,
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to edit `//foo:missing`: No rule named `missing` in `//foo`"
+        );
+        assert_eq!(workspace.read("foo/BUILD"), FOO);
+
+        let mut options = Options::new(&workspace.root);
+        options.keep_going = true;
+        let outcome = run(&options, &commands, &labels, &mut Vec::new()).unwrap();
+
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(outcome.modified, vec![workspace.path("foo/BUILD")]);
+        assert!(workspace
+            .read("foo/BUILD")
+            .contains("rust_test(name = \"foo_test\", tags = [\"manual\"])"));
+    }
+
+    #[test]
+    fn test_find_root() {
+        let workspace = Workspace::new("root", &[("MODULE.bazel", ""), ("foo/bar/BUILD", "")]);
+
+        assert_eq!(
+            find_root(&workspace.path("foo/bar")).as_deref(),
+            Some(workspace.root.as_path())
+        );
+        assert_eq!(find_root(Path::new("/")), None);
+    }
+}
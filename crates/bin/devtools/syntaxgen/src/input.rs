@@ -27,17 +27,38 @@ pub struct AstNodeSrc {
     pub fields: Vec<Field>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Field {
     Token(String),
+    /// A token with a `label:` in the grammar (e.g. `name:'identifier'`).
+    LabeledToken {
+        name: String,
+        token: String,
+    },
     Node {
         name: String,
         ty: String,
         cardinality: Cardinality,
+        /// The **position** of the node among the other nodes of the same
+        /// type `ty` in the same alternative of the rule (e.g. `1` for `rhs` in
+        /// `lhs:Test Binop rhs:Test`).
+        position: usize,
+    },
+    /// An optional node which can only be told apart from the others by the
+    /// number of `separator` tokens before it (e.g. `end` in `x[1:2]`).
+    Separated {
+        name: String,
+        ty: String,
+        separator: String,
+        count: usize,
+        /// Whether the alternative of the node has any separators at all, as
+        /// e.g. `start` in `x[1:]` needs a colon whereas `index` in `x[1]`
+        /// mustn't have one.
+        separated: bool,
     },
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Cardinality {
     Optional,
     Many,
@@ -60,6 +81,7 @@ pub const STARLARK_KINDS_SRC: KindsSrc = KindsSrc {
     // <    >    >=   <=   ==   !=
     // +=   -=   *=   //=  %=
     // &=   |=   ^=   <<=  >>=
+    // ->
     punct: &[
         ("+", "PLUS"),
         ("-", "MINUS"),
@@ -95,13 +117,14 @@ pub const STARLARK_KINDS_SRC: KindsSrc = KindsSrc {
         ("-=", "MINUSEQ"),
         ("*=", "STAREQ"),
         ("/=", "SLASHEQ"),
-        // ("//=", "DSLASHEQ"),
+        ("//=", "DSLASHEQ"),
         ("%=", "PERCENTEQ"),
         ("&=", "AMPEQ"),
         ("|=", "PIPEEQ"),
         ("^=", "CARETEQ"),
         ("<<=", "LSHIFTEQ"),
         (">>=", "RSHIFTEQ"),
+        ("->", "THIN_ARROW"),
     ],
     keywords: &[
         "and", "else", "load", "break", "for", "not", "continue", "if", "or", "def", "in", "pass",
@@ -137,6 +160,7 @@ pub const STARLARK_KINDS_SRC: KindsSrc = KindsSrc {
         "AssignStmt",
         "ExprStmt",
         "LoadStmt",
+        "LoadSymbol",
         "Test",
         "BinOp",
         "IfExpr",
@@ -160,5 +184,7 @@ pub const STARLARK_KINDS_SRC: KindsSrc = KindsSrc {
         "LambdaExpr",
         "Expression",
         "LoopVariables",
+        "TypeAnnotation",
+        "Error",
     ],
 };
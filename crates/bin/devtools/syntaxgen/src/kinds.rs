@@ -100,7 +100,13 @@ pub fn generate_kinds(kinds_src: &KindsSrc) -> String {
                     #(TokenKind::#punctuation => #punctuation,)*
                     #(TokenKind::#all_keywords => #all_keywords,)*
                     #(TokenKind::#literals => #literals,)*
-                    _ => unreachable!(),
+                    TokenKind::NEWLINE => NEWLINE,
+                    TokenKind::INDENT => INDENT,
+                    TokenKind::OUTDENT => OUTDENT,
+                    TokenKind::COMMENT => COMMENT,
+                    TokenKind::WHITESPACE => WHITESPACE,
+                    TokenKind::UNKNOWN => UNKNOWN,
+                    TokenKind::EOF => EOF,
                 }
             }
         }
@@ -115,6 +121,13 @@ pub fn generate_kinds(kinds_src: &KindsSrc) -> String {
             [indent] => { $crate::SyntaxKind::INDENT };
             [outdent] => { $crate::SyntaxKind::OUTDENT };
             [dslash] => { $crate::SyntaxKind::DSLASH };
+            [string] => { $crate::SyntaxKind::STRING };
+            [bytes] => { $crate::SyntaxKind::BYTES };
+            [int] => { $crate::SyntaxKind::INT };
+            [float] => { $crate::SyntaxKind::FLOAT };
+            [comment] => { $crate::SyntaxKind::COMMENT };
+            [whitespace] => { $crate::SyntaxKind::WHITESPACE };
+            [error] => { $crate::SyntaxKind::ERROR };
         }
         pub use T;
     };
@@ -155,6 +168,9 @@ pub fn generate_punctuation<'a>(kinds_src: &'a KindsSrc<'a>) -> (Vec<TokenStream
                 quote! { #delimiter }
             } else if token.eq(&"//") {
                 quote! { dslash }
+            } else if token.eq(&"//=") {
+                // Like `//`, which would otherwise start a comment
+                quote! { dslasheq }
             } else {
                 let cs = token.chars().map(|c| Punct::new(c, Spacing::Joint));
                 quote! { #(#cs)* }
@@ -6,8 +6,8 @@ use std::fmt::Write;
 use crate::{add_preamble, reformat, sourcegen::to_upper_snake_case};
 
 use super::{
-    input::{AstSrc, KindsSrc},
-    sourcegen::{to_pascal_case, GeneratorKind},
+    input::{AstSrc, Field, KindsSrc},
+    sourcegen::{to_pascal_case, token_ty, GeneratorKind},
 };
 
 pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
@@ -45,22 +45,32 @@ pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
                 }
 
                 if field.is_many() {
-                    if method_name == "binops" {
+                    if method_name == "statements" {
                         quote! {
-                            pub fn #method_name(&self) -> AstChildren<Binop> {
+                            pub fn #method_name(&self) -> AstChildren<Statement> {
                                 support::children(&self.syntax)
                             }
                         }
-                    } else if method_name == "statements" {
+                    } else {
                         quote! {
-                            pub fn #method_name(&self) -> AstChildren<Statement> {
+                            pub fn #method_name(&self) -> AstChildren<#ty> {
                                 support::children(&self.syntax)
                             }
                         }
+                    }
+                } else if let Field::LabeledToken { token, .. } = field {
+                    let token_kind = field.token_kind();
+
+                    if token_ty(token).is_some() {
+                        quote! {
+                            pub fn #method_name(&self) -> Option<ast::#ty> {
+                                support::token(&self.syntax, #token_kind).and_then(ast::#ty::cast)
+                            }
+                        }
                     } else {
                         quote! {
-                            pub fn #method_name(&self) -> AstChildren<#ty> {
-                                support::children(&self.syntax)
+                            pub fn #method_name(&self) -> Option<#ty> {
+                                support::token(&self.syntax, #token_kind)
                             }
                         }
                     }
@@ -92,6 +102,44 @@ pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
                             }
                         }
                     }
+                } else if let Field::Separated {
+                    separator,
+                    count,
+                    separated,
+                    ..
+                } = field
+                {
+                    let separator: proc_macro2::TokenStream =
+                        separator.parse().expect("Invalid token");
+                    // The first node either needs a separator after it (e.g. the
+                    // start of a slice), or mustn't have any (e.g. an index)
+                    let guard = match (count, separated) {
+                        (0, true) => quote! { support::token(&self.syntax, T![#separator])?; },
+                        (0, false) => quote! {
+                            if support::token(&self.syntax, T![#separator]).is_some() {
+                                return None;
+                            }
+                        },
+                        _ => quote! {},
+                    };
+                    let count = proc_macro2::Literal::usize_unsuffixed(*count);
+                    quote! {
+                        pub fn #method_name(&self) -> Option<#ty> {
+                            #guard
+                            support::child_after(&self.syntax, T![#separator], #count)
+                        }
+                    }
+                } else if let Field::Node {
+                    position: position @ 1..,
+                    ..
+                } = field
+                {
+                    let position = proc_macro2::Literal::usize_unsuffixed(*position);
+                    quote! {
+                        pub fn #method_name(&self) -> Option<#ty> {
+                            support::children(&self.syntax).nth(#position)
+                        }
+                    }
                 } else {
                     quote! {
                         pub fn #method_name(&self) -> Option<#ty> {
@@ -129,6 +177,7 @@ pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
         })
         .unzip();
 
+    let wrapper_nodes = kinds.nodes;
     let (enum_defs, enum_boilerplate_impls): (Vec<_>, Vec<_>) = grammar
         .enums
         .iter()
@@ -148,16 +197,27 @@ pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
                 quote!(impl ast::#trait_name for #name {})
             });
 
+            // Some enums (e.g. `Test`) also have a node of their own, wrapping the
+            // node of the actual variant, which is looked through when casting
+            let wrapper = wrapper_nodes
+                .contains(&en.name.as_str())
+                .then(|| format_ident!("{}", to_upper_snake_case(&en.name)));
+            let wrapper_kind = wrapper.iter();
+            let wrapper_cast = wrapper.iter();
+
             let ast_node = if en.name == "Stmt" {
                 quote! {}
             } else {
                 quote! {
                     impl AstNode for #name {
                         fn can_cast(kind: SyntaxKind) -> bool {
-                            matches!(kind, #(#kinds)|*)
+                            matches!(kind, #(#wrapper_kind |)* #(#kinds)|*)
                         }
                         fn cast(syntax: SyntaxNode) -> Option<Self> {
                             let res = match syntax.kind() {
+                                #(
+                                #wrapper_cast => return syntax.children().find_map(Self::cast),
+                                )*
                                 #(
                                 #kinds => #name::#variants(#variants { syntax }),
                                 )*
@@ -282,7 +342,7 @@ pub(crate) fn generate_nodes(kinds: KindsSrc<'_>, grammar: &AstSrc) -> String {
         #![allow(non_snake_case)]
         use crate::{
             SyntaxNode, SyntaxToken, SyntaxKind::{self, *},
-            ast::{self, AstNode, AstChildren, support},
+            ast::{self, AstNode, AstChildren, AstToken, support},
             T,
         };
 
@@ -42,7 +42,10 @@ pub(crate) fn lower(grammar: &Grammar) -> AstSrc {
             None => {
                 // tracing::debug!!("Lowering rule: {} = {:?}", name, rule);
                 let mut fields = Vec::new();
-                lower_rule(&mut fields, grammar, None, rule);
+                match separator(&name) {
+                    Some(separator) => lower_separated(&mut fields, grammar, rule, separator),
+                    None => lower_rule(&mut fields, grammar, None, rule),
+                }
 
                 // tracing::debug!!(
                 //     "Lowered rule: {} = {:?} with fields {:?}",
@@ -85,6 +88,7 @@ fn extract_enums(ast: &mut AstSrc) {
                     name,
                     ty,
                     cardinality: Cardinality::Optional,
+                    position: 0,
                 });
             }
         }
@@ -167,14 +171,16 @@ impl Field {
 
     pub(crate) fn token_kind(&self) -> Option<proc_macro2::TokenStream> {
         match self {
-            Field::Token(token) => {
+            Field::Token(token) | Field::LabeledToken { token, .. } => {
+                // `//` and `//=` would start a comment, so are named instead
+                match token.as_str() {
+                    "//" => return Some(quote! { T![dslash] }),
+                    "//=" => return Some(quote! { T![dslasheq] }),
+                    _ => {}
+                }
                 let token: proc_macro2::TokenStream = token.parse().expect("Invalid token");
 
                 tracing::debug!("Generating token for field: {}", token);
-                // if the token is empty, then replace with dslash <-- this is a hack
-                if token.is_empty() {
-                    return Some(quote! { T![dslash] });
-                }
                 Some(quote! { T![#token] })
             }
             _ => None,
@@ -241,6 +247,9 @@ impl Field {
                 };
                 format_ident!("{}_token", name)
             }
+            Field::LabeledToken { name, .. } | Field::Separated { name, .. } => {
+                format_ident!("{}", name)
+            }
             Field::Node { name, .. } => {
                 if name == "type" {
                     format_ident!("ty")
@@ -254,11 +263,42 @@ impl Field {
     pub(crate) fn ty(&self) -> proc_macro2::Ident {
         match self {
             Field::Token(_) => format_ident!("SyntaxToken"),
-            Field::Node { ty, .. } => format_ident!("{}", ty),
+            Field::LabeledToken { token, .. } => match token_ty(token) {
+                Some(ty) => format_ident!("{}", ty),
+                None => format_ident!("SyntaxToken"),
+            },
+            Field::Node { ty, .. } | Field::Separated { ty, .. } => format_ident!("{}", ty),
         }
     }
 }
 
+/// The **typed token** (i.e. `ast::AstToken`) for the given token, if it has
+/// one which is worth exposing (e.g. `ast::String` for `'string'`, to decode
+/// its value).
+pub(crate) fn token_ty(token: &str) -> Option<&'static str> {
+    match token {
+        "identifier" => Some("Identifier"),
+        "string" => Some("String"),
+        "bytes" => Some("Bytes"),
+        "int" => Some("Int"),
+        "float" => Some("Float"),
+        _ => None,
+    }
+}
+
+/// The **position** a node of type `ty` would have among the nodes of the
+/// same type lowered into `acc` so far.
+fn position(acc: &[Field], ty: &str) -> usize {
+    acc.iter()
+        .filter(|field| {
+            matches!(
+                field,
+                Field::Node { ty: other, cardinality: Cardinality::Optional, .. } if other == ty
+            )
+        })
+        .count()
+}
+
 fn deduplicate_fields(ast: &mut AstSrc) {
     for node in &mut ast.nodes {
         let mut i = 0;
@@ -303,17 +343,23 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
         Rule::Node(node) => {
             let ty = grammar[*node].name.clone();
             let name = label.cloned().unwrap_or_else(|| to_lower_snake_case(&ty));
+            let position = position(acc, &ty);
             let field = Field::Node {
                 name,
                 ty,
                 cardinality: Cardinality::Optional,
+                position,
             };
             acc.push(field);
         }
         Rule::Token(token) => {
-            assert!(label.is_none());
             let mut name = grammar[*token].name.clone();
-            if name != "int" && name != "float" && name != "string" && name != "bytes" {
+            if let Some(label) = label {
+                acc.push(Field::LabeledToken {
+                    name: label.clone(),
+                    token: name,
+                });
+            } else if name != "int" && name != "float" && name != "string" && name != "bytes" {
                 if "[]{}()".contains(&name) {
                     name = format!("'{name}'");
                 }
@@ -341,6 +387,7 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
                     name,
                     ty,
                     cardinality: Cardinality::Many,
+                    position: 0,
                 };
                 acc.push(field);
                 return;
@@ -371,6 +418,7 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
                     name,
                     ty: "SyntaxNode".to_string(),
                     cardinality: Cardinality::Many,
+                    position: 0,
                 };
                 acc.push(field);
                 return;
@@ -383,6 +431,25 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
                 for rule in seq {
                     lower_rule(&mut fields, grammar, None, rule);
                 }
+
+                // A repeated sequence of a single node and some separators (e.g.
+                // `(',' LoadSymbol)*`) is simply a list of such nodes
+                let mut nodes = fields
+                    .iter()
+                    .filter(|field| matches!(field, Field::Node { .. }));
+                if let (Some(Field::Node { ty, .. }), None) = (nodes.next(), nodes.next()) {
+                    let name = label
+                        .cloned()
+                        .unwrap_or_else(|| pluralize(&to_lower_snake_case(ty)));
+                    acc.push(Field::Node {
+                        name,
+                        ty: ty.clone(),
+                        cardinality: Cardinality::Many,
+                        position: 0,
+                    });
+                    return;
+                }
+
                 let name = label.cloned().unwrap_or_else(|| {
                     pluralize(&to_lower_snake_case(&fields[0].method_name().to_string()))
                 });
@@ -390,6 +457,7 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
                     name,
                     ty: "SyntaxNode".to_string(),
                     cardinality: Cardinality::Many,
+                    position: 0,
                 };
                 acc.push(field);
                 return;
@@ -399,24 +467,103 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
         }
         Rule::Labeled { label: l, rule } => {
             assert!(label.is_none());
-            let manually_implemented = matches!(
-                l.as_str(),
-                "lhs" | "rhs" | "condition" | "body" | "key" | "value" | "args" | "elements"
-            );
-            if manually_implemented {
-                return;
-            }
             lower_rule(acc, grammar, Some(l), rule);
         }
-        Rule::Seq(rules) | Rule::Alt(rules) => {
+        Rule::Seq(rules) => {
             for rule in rules {
                 lower_rule(acc, grammar, label, rule);
             }
         }
+        Rule::Alt(rules) => {
+            // Each alternative is lowered on top of the fields preceding it, such
+            // that the positions of its nodes don't count those of the other
+            // alternatives (e.g. both `condition` and `iterable` are the first
+            // `Test` in `'for' ... 'in' iterable:Test | 'if' condition:Test`)
+            let prefix = acc.len();
+            for rule in rules {
+                let mut alternative = acc[..prefix].to_vec();
+                lower_rule(&mut alternative, grammar, label, rule);
+                acc.extend(alternative.drain(prefix..));
+            }
+        }
         Rule::Opt(rule) => lower_rule(acc, grammar, label, rule),
     }
 }
 
+/// The **separator** of the nodes of the given rule, if they're all optional
+/// and as such can only be told apart by the separators around them (e.g. the
+/// bounds of a slice).
+fn separator(node: &str) -> Option<&'static str> {
+    match node {
+        "SliceSuffix" => Some(":"),
+        _ => None,
+    }
+}
+
+fn lower_separated(acc: &mut Vec<Field>, grammar: &Grammar, rule: &Rule, separator: &str) {
+    let alternatives = match rule {
+        Rule::Alt(it) => it.as_slice(),
+        it => std::slice::from_ref(it),
+    };
+    for alternative in alternatives {
+        let mut fields = Vec::new();
+        let mut count = 0;
+        lower_separated_rule(
+            &mut fields,
+            &mut count,
+            grammar,
+            None,
+            alternative,
+            separator,
+        );
+        for field in &mut fields {
+            if let Field::Separated { separated, .. } = field {
+                *separated = count > 0;
+            }
+        }
+        acc.extend(fields);
+    }
+}
+
+fn lower_separated_rule(
+    acc: &mut Vec<Field>,
+    count: &mut usize,
+    grammar: &Grammar,
+    label: Option<&String>,
+    rule: &Rule,
+    separator: &str,
+) {
+    match rule {
+        Rule::Node(node) => {
+            let name = label.expect("separated nodes must be labeled").clone();
+            acc.push(Field::Separated {
+                name,
+                ty: grammar[*node].name.clone(),
+                separator: separator.to_string(),
+                count: *count,
+                separated: true,
+            });
+        }
+        Rule::Token(token) => {
+            if grammar[*token].name == separator {
+                *count += 1;
+            }
+            lower_rule(acc, grammar, label, rule);
+        }
+        Rule::Labeled { label: l, rule } => {
+            assert!(label.is_none());
+            lower_separated_rule(acc, count, grammar, Some(l), rule, separator);
+        }
+        Rule::Seq(rules) => {
+            for rule in rules {
+                lower_separated_rule(acc, count, grammar, label, rule, separator);
+            }
+        }
+        Rule::Opt(rule) => lower_separated_rule(acc, count, grammar, label, rule, separator),
+        Rule::Alt(_) | Rule::Rep(_) => panic!("unhandled rule: {rule:?}"),
+    }
+}
+
 fn lower_comma_list(
     acc: &mut Vec<Field>,
     grammar: &Grammar,
@@ -439,8 +586,9 @@ fn lower_comma_list(
     let (node, repeat, trailing_comma) = match rule.as_slice() {
         [Rule::Node(node), Rule::Rep(repeat), Rule::Opt(trailing_comma)] => {
             // tracing::debug!!("Found node, repeat, trailing_comma sequence");
-            (node, repeat, trailing_comma)
+            (node, repeat, Some(trailing_comma))
         }
+        [Rule::Node(node), Rule::Rep(repeat)] => (node, repeat, None),
         _ => {
             // tracing::debug!!("Not a node, repeat, trailing_comma sequence");
             return false;
@@ -451,7 +599,8 @@ fn lower_comma_list(
         _ => return false,
     };
     match repeat.as_slice() {
-        [comma, Rule::Node(n)] if comma == &**trailing_comma && n == node => (),
+        [comma @ Rule::Token(_), Rule::Node(n)]
+            if n == node && trailing_comma.map_or(true, |it| **it == *comma) => {}
         _ => return false,
     }
     let ty = grammar[*node].name.clone();
@@ -462,6 +611,7 @@ fn lower_comma_list(
         name,
         ty,
         cardinality: Cardinality::Many,
+        position: 0,
     };
     acc.push(field);
     true
@@ -483,6 +633,11 @@ fn to_lower_snake_case(name: &str) -> String {
 fn pluralize(name: &str) -> String {
     if name.ends_with('s') {
         format!("{name}es")
+    } else if let Some(stem) = name
+        .strip_suffix('y')
+        .filter(|stem| !stem.ends_with(['a', 'e', 'o', 'u']))
+    {
+        format!("{stem}ies")
     } else {
         format!("{name}s")
     }
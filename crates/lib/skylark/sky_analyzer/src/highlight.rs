@@ -0,0 +1,528 @@
+//! **Semantic highlighting**, i.e. the classification of the tokens of a
+//! file by what they mean rather than by how they're spelled (e.g. a call of
+//! a rule rather than an identifier), using the [resolution](crate::resolve)
+//! of its names.
+//!
+//! The highlights can be encoded as the semantic tokens of the Language
+//! Server Protocol (see [`semantic_tokens`]), or as HTML spans (see
+//! [`html`]).
+//!
+//! ```
+//! use sky_analyzer::{
+//!     highlight::{highlight, html, HighlightOptions},
+//!     resolve::{resolve, ResolveOptions},
+//! };
+//! use sky_syntax::File;
+//!
+//! let text = "x = len([])\n";
+//! let file = File::parse(text).tree();
+//! let resolution = resolve(&file, &ResolveOptions::default());
+//! let highlights = highlight(&file, &resolution, &HighlightOptions::default());
+//!
+//! assert_eq!(
+//!     html(text, &highlights),
+//!     "<span class=\"global definition\">x</span> <span class=\"operator\">=</span> \
+//!      <span class=\"builtinFunction\">len</span>([])\n"
+//! );
+//! ```
+
+use crate::resolve::{BindingId, BindingKind, Reference, Resolution, Resolved};
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    File, SyntaxKind, SyntaxNode,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{BitOr, BitOrAssign},
+};
+
+/// The **kind** of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HighlightKind {
+    /// A keyword, or one of the builtin constants (`True`, `False` and
+    /// `None`).
+    Keyword,
+    BuiltinFunction,
+    /// The callee of a call declaring a target, i.e. with a `name` (e.g.
+    /// `rust_library(name = "foo")`).
+    RuleCall,
+    /// A symbol of a `load` statement, or a reference to it.
+    LoadedSymbol,
+    /// A parameter, or the name of a keyword argument.
+    Parameter,
+    Local,
+    /// A global of the module, or a name predeclared by the environment.
+    Global,
+    String,
+    /// The string literal a module or function starts with.
+    Docstring,
+    Number,
+    Comment,
+    Operator,
+}
+
+impl HighlightKind {
+    /// The **name** of the kind, as an LSP token type and an HTML class.
+    pub fn name(self) -> &'static str {
+        TOKEN_TYPES[self as usize]
+    }
+}
+
+/// A set of **modifiers** of a highlighted token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u32);
+
+impl Modifiers {
+    /// The name binds the binding (e.g. the target of an assignment).
+    pub const DEFINITION: Modifiers = Modifiers(1 << 0);
+    /// The binding is never referenced (locals, parameters and loaded
+    /// symbols only, as globals may be loaded by other modules).
+    pub const UNUSED: Modifiers = Modifiers(1 << 1);
+    /// The docstring of the function starts a line with `Deprecated`, or
+    /// the name was given as deprecated (see [`HighlightOptions`]).
+    pub const DEPRECATED: Modifiers = Modifiers(1 << 2);
+
+    /// The names of the modifiers, in the order of their bits, as LSP token
+    /// modifiers and HTML classes.
+    pub const NAMES: [&'static str; 3] = ["definition", "unused", "deprecated"];
+
+    pub fn empty() -> Modifiers {
+        Modifiers(0)
+    }
+
+    /// The bits of the set, as LSP token modifiers.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The names of the modifiers of the set.
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        Modifiers::NAMES
+            .into_iter()
+            .enumerate()
+            .filter(move |(i, _)| self.0 & (1 << i) != 0)
+            .map(|(_, name)| name)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, other: Modifiers) -> Modifiers {
+        Modifiers(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, other: Modifiers) {
+        self.0 |= other.0;
+    }
+}
+
+/// The token types of the LSP legend, in the order of the variants of
+/// [`HighlightKind`].
+pub const TOKEN_TYPES: [&str; 12] = [
+    "keyword",
+    "builtinFunction",
+    "ruleCall",
+    "loadedSymbol",
+    "parameter",
+    "local",
+    "global",
+    "string",
+    "docstring",
+    "number",
+    "comment",
+    "operator",
+];
+
+/// The token modifiers of the LSP legend, indexed by the bits of
+/// [`Modifiers`].
+pub const TOKEN_MODIFIERS: [&str; 3] = Modifiers::NAMES;
+
+/// A **highlighted** token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    pub range: TextRange,
+    pub kind: HighlightKind,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighlightOptions {
+    /// The names known to be deprecated, besides the functions of the file
+    /// documented as such (e.g. predeclared rules, or symbols loaded from
+    /// deprecated functions of other modules).
+    pub deprecated: HashSet<String>,
+}
+
+/// The builtins highlighted as keywords.
+const CONSTANTS: &[&str] = &["False", "None", "True"];
+
+/// **Highlights** the tokens of the given file, using the given resolution
+/// of its names. The highlights are ordered by position, and only cover
+/// tokens which are classified (e.g. not brackets, or undefined names).
+pub fn highlight(
+    file: &File,
+    resolution: &Resolution,
+    options: &HighlightOptions,
+) -> Vec<Highlight> {
+    let highlighter = Highlighter::new(file, resolution, options);
+    file.syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter_map(|token| {
+            let range = token.text_range();
+            let (kind, modifiers) = match token.kind() {
+                SyntaxKind::COMMENT => (HighlightKind::Comment, Modifiers::empty()),
+                SyntaxKind::INT | SyntaxKind::FLOAT => (HighlightKind::Number, Modifiers::empty()),
+                SyntaxKind::STRING | SyntaxKind::BYTES => highlighter.string(range),
+                SyntaxKind::IDENTIFIER => highlighter.identifier(range)?,
+                kind if kind.is_keyword() => (HighlightKind::Keyword, Modifiers::empty()),
+                kind if is_operator(kind) => (HighlightKind::Operator, Modifiers::empty()),
+                _ => return None,
+            };
+
+            Some(Highlight {
+                range,
+                kind,
+                modifiers,
+            })
+        })
+        .collect()
+}
+
+struct Highlighter<'a> {
+    resolution: &'a Resolution,
+    options: &'a HighlightOptions,
+    /// The binding defined by each name.
+    definitions: HashMap<TextRange, BindingId>,
+    references: HashMap<TextRange, &'a Reference>,
+    /// The bindings which are referenced.
+    used: HashSet<BindingId>,
+    /// The functions whose docstring marks them as deprecated.
+    deprecated: HashSet<BindingId>,
+    docstrings: HashSet<TextRange>,
+    /// The callees of the calls declaring targets.
+    rule_calls: HashSet<TextRange>,
+    /// The names of keyword arguments.
+    keyword_arguments: HashSet<TextRange>,
+}
+
+impl<'a> Highlighter<'a> {
+    fn new(
+        file: &File,
+        resolution: &'a Resolution,
+        options: &'a HighlightOptions,
+    ) -> Highlighter<'a> {
+        let definitions: HashMap<_, _> = resolution
+            .bindings()
+            .flat_map(|(id, binding)| binding.definitions.iter().map(move |range| (*range, id)))
+            .collect();
+        let references = resolution
+            .references()
+            .iter()
+            .map(|it| (it.range, it))
+            .collect();
+        let used = resolution
+            .references()
+            .iter()
+            .filter_map(|it| match it.resolved {
+                Resolved::Binding(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+
+        let mut docstrings = HashSet::new();
+        let mut deprecated = HashSet::new();
+        if let Some(docstring) = file
+            .statements()
+            .next()
+            .and_then(|it| docstring(it.syntax()))
+        {
+            docstrings.insert(docstring.syntax().text_range());
+        }
+        for def in file.syntax().descendants().filter_map(ast::DefStmt::cast) {
+            let Some(body) = def.body() else {
+                continue;
+            };
+            let first = match body.statements().next() {
+                Some(statement) => statement.syntax().clone(),
+                None => match body.simple_stmt() {
+                    Some(stmt) => stmt.syntax().clone(),
+                    None => continue,
+                },
+            };
+            let Some(docstring) = docstring(&first) else {
+                continue;
+            };
+
+            docstrings.insert(docstring.syntax().text_range());
+            let is_deprecated = docstring.value().is_ok_and(|value| {
+                value
+                    .lines()
+                    .any(|line| line.trim_start().starts_with("Deprecated"))
+            });
+            let id = def
+                .name()
+                .and_then(|name| definitions.get(&name.syntax().text_range()));
+            if let (true, Some(id)) = (is_deprecated, id) {
+                deprecated.insert(*id);
+            }
+        }
+
+        let mut rule_calls = HashSet::new();
+        let mut keyword_arguments = HashSet::new();
+        for call in file
+            .syntax()
+            .descendants()
+            .filter_map(ast::CallSuffix::cast)
+        {
+            let callee = call
+                .syntax()
+                .first_token()
+                .and_then(|it| it.prev_token())
+                .filter(|it| it.kind() == SyntaxKind::IDENTIFIER);
+            if let (Some(callee), Some(_)) = (callee, call.keyword_argument("name")) {
+                rule_calls.insert(callee.text_range());
+            }
+        }
+        for argument in file.syntax().descendants().filter_map(ast::Argument::cast) {
+            if let (Some(name), Some(_)) = (argument.name(), argument.eq_token()) {
+                keyword_arguments.insert(name.syntax().text_range());
+            }
+        }
+
+        Highlighter {
+            resolution,
+            options,
+            definitions,
+            references,
+            used,
+            deprecated,
+            docstrings,
+            rule_calls,
+            keyword_arguments,
+        }
+    }
+
+    fn string(&self, range: TextRange) -> (HighlightKind, Modifiers) {
+        if self.docstrings.contains(&range) {
+            return (HighlightKind::Docstring, Modifiers::empty());
+        }
+
+        // The symbols of `load` statements without an alias.
+        match self.definitions.get(&range) {
+            Some(id) => self.definition(*id),
+            None => (HighlightKind::String, Modifiers::empty()),
+        }
+    }
+
+    fn identifier(&self, range: TextRange) -> Option<(HighlightKind, Modifiers)> {
+        if let Some(id) = self.definitions.get(&range) {
+            return Some(self.definition(*id));
+        }
+
+        let Some(reference) = self.references.get(&range) else {
+            // The attribute called by e.g. `native.genrule(name = "foo")`.
+            if self.rule_calls.contains(&range) {
+                return Some((HighlightKind::RuleCall, Modifiers::empty()));
+            }
+            if self.keyword_arguments.contains(&range) {
+                return Some((HighlightKind::Parameter, Modifiers::empty()));
+            }
+            return None;
+        };
+
+        let mut modifiers = Modifiers::empty();
+        if self.options.deprecated.contains(&reference.name) {
+            modifiers |= Modifiers::DEPRECATED;
+        }
+        let kind = match reference.resolved {
+            Resolved::Binding(id) => {
+                if self.deprecated.contains(&id) {
+                    modifiers |= Modifiers::DEPRECATED;
+                }
+                self.binding_kind(id)
+            }
+            Resolved::Builtin if CONSTANTS.contains(&reference.name.as_str()) => {
+                return Some((HighlightKind::Keyword, modifiers));
+            }
+            Resolved::Builtin => return Some((HighlightKind::BuiltinFunction, modifiers)),
+            Resolved::Predeclared => HighlightKind::Global,
+            Resolved::Undefined => return None,
+        };
+
+        if self.rule_calls.contains(&range) {
+            return Some((HighlightKind::RuleCall, modifiers));
+        }
+        Some((kind, modifiers))
+    }
+
+    /// The highlight of a name binding the given binding.
+    fn definition(&self, id: BindingId) -> (HighlightKind, Modifiers) {
+        let binding = self.resolution.binding(id);
+        let kind = self.binding_kind(id);
+
+        let mut modifiers = Modifiers::DEFINITION;
+        let is_global =
+            binding.scope == self.resolution.module() && kind != HighlightKind::LoadedSymbol;
+        if !is_global && !binding.name.starts_with('_') && !self.used.contains(&id) {
+            modifiers |= Modifiers::UNUSED;
+        }
+        if self.deprecated.contains(&id) || self.options.deprecated.contains(&binding.name) {
+            modifiers |= Modifiers::DEPRECATED;
+        }
+
+        (kind, modifiers)
+    }
+
+    fn binding_kind(&self, id: BindingId) -> HighlightKind {
+        let binding = self.resolution.binding(id);
+        match binding.kind {
+            BindingKind::Load => HighlightKind::LoadedSymbol,
+            BindingKind::Parameter => HighlightKind::Parameter,
+            BindingKind::Function | BindingKind::Variable
+                if binding.scope == self.resolution.module() =>
+            {
+                HighlightKind::Global
+            }
+            BindingKind::Function | BindingKind::Variable => HighlightKind::Local,
+        }
+    }
+}
+
+/// The **docstring** of the given statement, if it's a lone string literal.
+fn docstring(statement: &SyntaxNode) -> Option<ast::String> {
+    let mut tokens = statement
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| {
+            !matches!(
+                it.kind(),
+                SyntaxKind::WHITESPACE
+                    | SyntaxKind::COMMENT
+                    | SyntaxKind::NEWLINE
+                    | SyntaxKind::INDENT
+                    | SyntaxKind::OUTDENT
+            )
+        });
+    let (Some(token), None) = (tokens.next(), tokens.next()) else {
+        return None;
+    };
+
+    ast::String::cast(token)
+}
+
+/// Whether the given punctuation is an **operator** (including assignments),
+/// rather than a delimiter.
+fn is_operator(kind: SyntaxKind) -> bool {
+    kind.is_punct()
+        && !matches!(
+            kind,
+            SyntaxKind::DOT
+                | SyntaxKind::COMMA
+                | SyntaxKind::SEMICOLON
+                | SyntaxKind::COLON
+                | SyntaxKind::LPAREN
+                | SyntaxKind::RPAREN
+                | SyntaxKind::LBRACKET
+                | SyntaxKind::RBRACKET
+                | SyntaxKind::LBRACE
+                | SyntaxKind::RBRACE
+                | SyntaxKind::THIN_ARROW
+        )
+}
+
+/// **Encodes** the given highlights as the data of LSP semantic tokens, i.e.
+/// five integers per token: its line and start column (relative to the
+/// previous token), its length, the index of its kind in [`TOKEN_TYPES`] and
+/// the bits of its modifiers in [`TOKEN_MODIFIERS`].
+///
+/// Columns and lengths are in UTF-16 code units, and tokens spanning
+/// several lines (e.g. docstrings) are split into a token per line.
+pub fn semantic_tokens(text: &str, highlights: &[Highlight]) -> Vec<u32> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let utf16_len = |text: &str| text.encode_utf16().count() as u32;
+
+    let mut data = Vec::new();
+    let (mut previous_line, mut previous_column) = (0, 0);
+    for highlight in highlights {
+        let kind = highlight.kind as u32;
+        let mut start = usize::from(highlight.range.start());
+        let end = usize::from(highlight.range.end());
+        while start < end {
+            let line = line_starts.partition_point(|it| *it <= start) - 1;
+            let line_end = text[start..end].find('\n').map_or(end, |i| start + i);
+            let column = utf16_len(&text[line_starts[line]..start]);
+            let length = utf16_len(&text[start..line_end]);
+
+            if length > 0 {
+                let line = line as u32;
+                let delta_column = if line == previous_line {
+                    column - previous_column
+                } else {
+                    column
+                };
+                data.extend([
+                    line - previous_line,
+                    delta_column,
+                    length,
+                    kind,
+                    highlight.modifiers.bits(),
+                ]);
+                (previous_line, previous_column) = (line, column);
+            }
+            start = line_end + 1;
+        }
+    }
+
+    data
+}
+
+/// **Renders** the given text as HTML, with a `span` per highlight whose
+/// classes are the name of its kind and of its modifiers (e.g. `<span
+/// class="local definition unused">x</span>`). The text is escaped, but not
+/// wrapped (e.g. in a `pre` element).
+pub fn html(text: &str, highlights: &[Highlight]) -> String {
+    fn escape(text: &str, html: &mut String) {
+        for c in text.chars() {
+            match c {
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                c => html.push(c),
+            }
+        }
+    }
+
+    let mut html = String::new();
+    let mut offset = 0;
+    for highlight in highlights {
+        let range = usize::from(highlight.range.start())..usize::from(highlight.range.end());
+        escape(&text[offset..range.start], &mut html);
+
+        html.push_str("<span class=\"");
+        html.push_str(highlight.kind.name());
+        for modifier in highlight.modifiers.names() {
+            html.push(' ');
+            html.push_str(modifier);
+        }
+        html.push_str("\">");
+        escape(&text[range.clone()], &mut html);
+        html.push_str("</span>");
+
+        offset = range.end;
+    }
+    escape(&text[offset..], &mut html);
+
+    html
+}
@@ -0,0 +1,155 @@
+//! **Labels** (e.g. `"//foo:bar"`), as found in the attributes of rules and
+//! in `load` statements.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Attributes whose lists of strings (e.g. labels) are kept **sorted**.
+pub const SORTED_ATTRIBUTES: &[&str] = &[
+    "compatible_with",
+    "data",
+    "deps",
+    "exec_compatible_with",
+    "exports",
+    "hdrs",
+    "runtime_deps",
+    "srcs",
+    "target_compatible_with",
+    "textual_hdrs",
+    "visibility",
+];
+
+/// The **order** of labels, as with buildifier: relative labels (e.g.
+/// `":foo"`) first, then absolute ones (e.g. `"//foo"`), then external ones
+/// (e.g. `"@foo//bar"`), each ordered by package and then name. Any other
+/// strings (e.g. file names) come before all labels.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    fn key(label: &str) -> (u8, &str, &str) {
+        let phase = if label.starts_with(':') {
+            1
+        } else if label.starts_with("//") {
+            2
+        } else if label.starts_with('@') {
+            3
+        } else {
+            0
+        };
+        let (package, name) = label.split_once(':').unwrap_or((label, ""));
+
+        (phase, package, name)
+    }
+
+    key(a).cmp(&key(b))
+}
+
+/// A **label** of a file, e.g. `//foo/bar:baz.bzl` or `@repo//foo:baz.bzl`,
+/// as loaded by a `load` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Label {
+    /// The external repository of the file (e.g. `repo` in
+    /// `@repo//foo:baz.bzl`), or `None` for the main repository.
+    pub repository: Option<String>,
+    /// The package of the file, i.e. its directory relative to the root of
+    /// its repository (e.g. `foo/bar`, or an empty string for the root).
+    pub package: String,
+    /// The name of the file, relative to its package (e.g. `baz.bzl`).
+    pub name: String,
+}
+
+/// An **error** parsing a label.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LabelError {
+    #[error("Invalid label `{label}`: {reason}")]
+    Invalid { label: String, reason: &'static str },
+    #[error("Relative label `{label}` outside of a package")]
+    Relative { label: String },
+}
+
+impl Label {
+    /// **Parses** the given label. Relative labels (e.g. `:baz.bzl`) are
+    /// resolved against the package of the given label, and absolute labels
+    /// (e.g. `//foo:baz.bzl`) refer to its repository.
+    pub fn parse(label: &str, current: Option<&Label>) -> Result<Label, LabelError> {
+        let invalid = |reason| LabelError::Invalid {
+            label: label.to_string(),
+            reason,
+        };
+
+        let (repository, rest) = match label.strip_prefix('@') {
+            Some(rest) => {
+                let (repository, rest) = rest
+                    .split_once("//")
+                    .ok_or_else(|| invalid("expected `//` after the repository"))?;
+                if repository.is_empty() {
+                    return Err(invalid("empty repository name"));
+                }
+                (Some(repository.to_string()), rest)
+            }
+            None => match label.strip_prefix("//") {
+                Some(rest) => (current.and_then(|it| it.repository.clone()), rest),
+                None if label.starts_with(':') => {
+                    let current = current.ok_or_else(|| LabelError::Relative {
+                        label: label.to_string(),
+                    })?;
+                    let name = &label[1..];
+                    check_path(name).map_err(invalid)?;
+                    return Ok(Label {
+                        repository: current.repository.clone(),
+                        package: current.package.clone(),
+                        name: name.to_string(),
+                    });
+                }
+                None => return Err(invalid("expected `//`, `@` or `:`")),
+            },
+        };
+
+        let (package, name) = rest
+            .split_once(':')
+            .ok_or_else(|| invalid("expected `:` before the file name"))?;
+        if !package.is_empty() {
+            check_path(package).map_err(invalid)?;
+        }
+        check_path(name).map_err(invalid)?;
+
+        Ok(Label {
+            repository,
+            package: package.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    /// The **path** of the file, relative to the root of its repository
+    /// (e.g. `foo/bar/baz.bzl`).
+    pub fn path(&self) -> PathBuf {
+        Path::new(&self.package).join(&self.name)
+    }
+}
+
+/// Checks that the given (relative) path has only **normal** segments, e.g.
+/// neither `..` nor empty segments.
+fn check_path(path: &str) -> Result<(), &'static str> {
+    if path.is_empty() {
+        return Err("empty file name");
+    }
+    if path
+        .split('/')
+        .any(|it| it.is_empty() || it == "." || it == "..")
+    {
+        return Err("invalid path segment");
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(repository) = &self.repository {
+            write!(f, "@{repository}")?;
+        }
+        write!(f, "//{}:{}", self.package, self.name)
+    }
+}
@@ -0,0 +1,35 @@
+//! **Static analysis** of Starlark code, built on top of `sky_syntax`.
+//!
+//! The analyzer examines the syntax tree of a file (rather than evaluating
+//! it) to find likely mistakes and style issues:
+//!
+//! - [`lint`] runs a registry of **lint rules** over a parsed file, each
+//!   reporting diagnostics (optionally along with an automatic fix), which
+//!   can be suppressed line by line with `# buildifier: disable=<rule>`
+//!   comments.
+//! - [`resolve`] resolves each identifier to the binding it refers to (e.g.
+//!   a parameter, a global or a `load`ed symbol) following Starlark's
+//!   scoping rules, reporting undefined names and reassigned globals.
+//! - [`highlight`] classifies each token by what it means (e.g. a rule call,
+//!   a loaded symbol or an unused local) for semantic highlighting, as LSP
+//!   semantic tokens or HTML.
+//! - [`types`] checks the types of values against the annotations of
+//!   functions (e.g. `def f(x: str) -> int:`), inferring them elsewhere, and
+//!   reports mismatches such as `"a" + 1` or calling a non-callable.
+//!
+//! ```
+//! use sky_analyzer::lint::{FileKind, Registry};
+//! use sky_syntax::File;
+//!
+//! let parse = File::parse("load(\":defs.bzl\", \"foo\")\n");
+//! let diagnostics = Registry::default().lint(&parse, FileKind::Build);
+//!
+//! assert_eq!(diagnostics[0].rule, "unused-load");
+//! ```
+
+pub mod builtins;
+pub mod highlight;
+pub mod labels;
+pub mod lint;
+pub mod resolve;
+pub mod types;
@@ -0,0 +1,313 @@
+//! The **lint engine**, running a registry of rules over a parsed file.
+//!
+//! Each [`Rule`] has an id (e.g. `unused-load`), a default [`Severity`] and
+//! a check, which walks the syntax tree and reports [`Diagnostic`]s, each
+//! optionally along with a [`Fix`]. The [`Registry`] holds the rules along
+//! with their configured severities, and runs the enabled ones over a file.
+//!
+//! Diagnostics of a rule can be **suppressed** on a single line with a
+//! comment, either at the end of the line or on the line before:
+//!
+//! ```text
+//! load(":defs.bzl", "unused")  # buildifier: disable=unused-load
+//!
+//! # buildifier: disable=unsorted-deps,print
+//! deps = ["//b", "//a"],
+//! ```
+
+pub mod rules;
+mod suppression;
+
+use crate::lint::suppression::Suppressions;
+use rowan::TextRange;
+use sky_syntax::{ast::AstNode, File, Parse, TextEdit};
+use std::{fmt, path::Path};
+use thiserror::Error;
+
+/// How **severe** a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The **kind** of a Starlark file, which some rules only apply to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A `BUILD` (or `BUCK`) file, declaring targets.
+    Build,
+    /// A `WORKSPACE` or `MODULE.bazel` file, declaring external repositories.
+    Workspace,
+    /// An extension (e.g. a `.bzl` file), defining macros and rules.
+    Extension,
+}
+
+impl FileKind {
+    /// The kind of the file at the given path, judging by its name.
+    pub fn from_path(path: &Path) -> FileKind {
+        let name = path
+            .file_name()
+            .map(|it| it.to_string_lossy())
+            .unwrap_or_default();
+
+        match name.as_ref() {
+            "BUILD" | "BUILD.bazel" | "BUCK" => FileKind::Build,
+            "WORKSPACE" | "WORKSPACE.bazel" | "MODULE.bazel" => FileKind::Workspace,
+            _ => FileKind::Extension,
+        }
+    }
+}
+
+/// An automatic **fix** of a diagnostic, as edits of the file's text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What the fix does, e.g. "Remove the load".
+    pub message: String,
+    /// The edits, which don't overlap.
+    pub edits: Vec<TextEdit>,
+}
+
+/// A **diagnostic** reported by a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The id of the rule which reported the diagnostic.
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub range: TextRange,
+    pub fix: Option<Fix>,
+}
+
+/// A **lint rule**, checking a file for one kind of issue.
+pub trait Rule: Send + Sync {
+    /// The id of the rule (e.g. `unused-load`), as used to configure it and
+    /// in suppression comments.
+    fn id(&self) -> &'static str;
+
+    /// A short description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// The severity of the rule's diagnostics, unless configured otherwise.
+    fn default_severity(&self) -> Severity;
+
+    /// **Checks** the file of the given context, reporting any issues found.
+    fn check(&self, cx: &mut Context<'_>);
+}
+
+/// The **context** a rule is checked in, i.e. the file being linted and the
+/// diagnostics reported so far.
+pub struct Context<'a> {
+    file: &'a File,
+    text: &'a str,
+    kind: FileKind,
+    rule: &'static str,
+    severity: Severity,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Context<'a> {
+    pub fn file(&self) -> &'a File {
+        self.file
+    }
+
+    /// The text of the file.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn kind(&self) -> FileKind {
+        self.kind
+    }
+
+    /// **Reports** a diagnostic of the current rule.
+    pub fn report(&mut self, range: TextRange, message: impl Into<String>, fix: Option<Fix>) {
+        self.diagnostics.push(Diagnostic {
+            rule: self.rule,
+            severity: self.severity,
+            message: message.into(),
+            range,
+            fix,
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LintError {
+    #[error("Unknown lint rule `{id}`")]
+    UnknownRule { id: String },
+    #[error("A lint rule `{id}` is already registered")]
+    DuplicateRule { id: String },
+}
+
+struct Entry {
+    rule: Box<dyn Rule>,
+    /// The configured severity, or `None` if the rule is disabled.
+    severity: Option<Severity>,
+}
+
+/// A **registry** of lint rules, along with their configured severities.
+///
+/// The default registry holds all [builtin rules](rules), each with its
+/// default severity.
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    /// An empty registry.
+    pub fn new() -> Registry {
+        Registry {
+            entries: Vec::new(),
+        }
+    }
+
+    /// **Registers** the given rule, enabled with its default severity.
+    pub fn register(&mut self, rule: Box<dyn Rule>) -> Result<(), LintError> {
+        if self.rule(rule.id()).is_some() {
+            return Err(LintError::DuplicateRule {
+                id: rule.id().to_string(),
+            });
+        }
+
+        self.entries.push(Entry {
+            severity: Some(rule.default_severity()),
+            rule,
+        });
+
+        Ok(())
+    }
+
+    /// All registered rules, in the order they're run.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.entries.iter().map(|it| it.rule.as_ref())
+    }
+
+    /// The rule with the given id, if registered.
+    pub fn rule(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules().find(|it| it.id() == id)
+    }
+
+    /// The configured severity of the given rule, or `None` if it's
+    /// disabled (or unknown).
+    pub fn severity(&self, id: &str) -> Option<Severity> {
+        self.entries
+            .iter()
+            .find(|it| it.rule.id() == id)
+            .and_then(|it| it.severity)
+    }
+
+    /// Configures the **severity** of the given rule, where `None` disables
+    /// the rule.
+    pub fn set_severity(&mut self, id: &str, severity: Option<Severity>) -> Result<(), LintError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|it| it.rule.id() == id)
+            .ok_or_else(|| LintError::UnknownRule { id: id.to_string() })?;
+        entry.severity = severity;
+
+        Ok(())
+    }
+
+    /// **Lints** the given file, running all enabled rules over it. The
+    /// diagnostics are ordered by position, without those suppressed by
+    /// comments.
+    ///
+    /// Rules run over the syntax tree even if it has syntax errors, so
+    /// callers may want to refuse such files instead.
+    pub fn lint(&self, parse: &Parse<File>, kind: FileKind) -> Vec<Diagnostic> {
+        let file = parse.tree();
+        let text = file.syntax().to_string();
+        let suppressions = Suppressions::collect(file.syntax(), &text);
+
+        let mut diagnostics = Vec::new();
+        for entry in &self.entries {
+            let Some(severity) = entry.severity else {
+                continue;
+            };
+
+            let mut cx = Context {
+                file: &file,
+                text: &text,
+                kind,
+                rule: entry.rule.id(),
+                severity,
+                diagnostics: Vec::new(),
+            };
+            entry.rule.check(&mut cx);
+            diagnostics.extend(cx.diagnostics);
+        }
+        diagnostics.retain(|it| !suppressions.is_suppressed(it.rule, it.range.start()));
+        diagnostics.sort_by_key(|it| (it.range.start(), it.rule));
+
+        diagnostics
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        for rule in rules::all() {
+            registry
+                .register(rule)
+                .expect("builtin rules have unique ids");
+        }
+
+        registry
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|it| (it.rule.id(), it.severity)))
+            .finish()
+    }
+}
+
+/// **Applies** the fixes of the given diagnostics to the given text,
+/// returning the fixed text along with the number of fixes applied. Fixes
+/// overlapping an earlier one are skipped (and may apply once the file is
+/// linted again).
+pub fn apply_fixes(text: &str, diagnostics: &[Diagnostic]) -> (String, usize) {
+    let mut edits: Vec<&TextEdit> = Vec::new();
+    let mut applied = 0;
+    for fix in diagnostics.iter().filter_map(|it| it.fix.as_ref()) {
+        let conflicts = fix.edits.iter().any(|edit| {
+            edits
+                .iter()
+                .any(|other| overlaps(edit.delete, other.delete))
+        });
+        if conflicts {
+            continue;
+        }
+
+        edits.extend(&fix.edits);
+        applied += 1;
+    }
+
+    let mut text = text.to_string();
+    edits.sort_by_key(|it| it.delete.start());
+    for edit in edits.iter().rev() {
+        edit.apply(&mut text);
+    }
+
+    (text, applied)
+}
+
+/// Whether the given ranges **overlap**, such that editing both is
+/// ambiguous (including insertions at the same offset).
+fn overlaps(a: TextRange, b: TextRange) -> bool {
+    a.start() == b.start() || (a.start() < b.end() && b.start() < a.end())
+}
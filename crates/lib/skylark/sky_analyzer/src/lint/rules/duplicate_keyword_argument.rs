@@ -0,0 +1,38 @@
+use crate::lint::{Context, Rule, Severity};
+use sky_syntax::ast::{self, AstNode, AstToken};
+use std::collections::HashSet;
+
+/// Calls passing the same **keyword argument** more than once, which fail at
+/// runtime.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DuplicateKeywordArgument;
+
+impl Rule for DuplicateKeywordArgument {
+    fn id(&self) -> &'static str {
+        "duplicate-keyword-argument"
+    }
+
+    fn description(&self) -> &'static str {
+        "Keyword arguments given more than once in a call"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let root = cx.file().syntax().clone();
+        for arguments in root.descendants().filter_map(ast::Arguments::cast) {
+            let mut seen = HashSet::new();
+            for name in arguments.arguments().filter_map(|it| it.name()) {
+                if !seen.insert(name.text().to_string()) {
+                    cx.report(
+                        name.syntax().text_range(),
+                        format!("Keyword argument `{}` is given more than once", name.text()),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! The **builtin rules**, along with helpers shared between them.
+
+mod duplicate_keyword_argument;
+mod mutable_default_parameter;
+mod print;
+mod shadowed_builtin;
+mod unreachable;
+mod unsorted_deps;
+mod unused_load;
+
+pub use self::{
+    duplicate_keyword_argument::DuplicateKeywordArgument,
+    mutable_default_parameter::MutableDefaultParameter, print::Print,
+    shadowed_builtin::ShadowedBuiltin, unreachable::Unreachable, unsorted_deps::UnsortedDeps,
+    unused_load::UnusedLoad,
+};
+
+use crate::lint::Rule;
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    SyntaxKind, SyntaxNode, SyntaxToken, TextEdit,
+};
+
+/// All builtin rules, in the order they're run.
+pub(crate) fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(UnusedLoad),
+        Box::new(DuplicateKeywordArgument),
+        Box::new(UnsortedDeps),
+        Box::new(MutableDefaultParameter),
+        Box::new(ShadowedBuiltin),
+        Box::new(Unreachable),
+        Box::new(Print),
+    ]
+}
+
+pub(crate) fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE
+            | SyntaxKind::COMMENT
+            | SyntaxKind::NEWLINE
+            | SyntaxKind::INDENT
+            | SyntaxKind::OUTDENT
+    )
+}
+
+/// The significant (i.e. non-trivia) tokens of the given node.
+fn significant_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !is_trivia(it.kind()))
+}
+
+/// The **range** of the given node without its surrounding trivia (e.g. the
+/// leading comments and whitespace, which are part of the node).
+pub(crate) fn significant_range(node: &SyntaxNode) -> TextRange {
+    let mut tokens = significant_tokens(node);
+    let Some(first) = tokens.next() else {
+        return TextRange::empty(node.text_range().start());
+    };
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    TextRange::new(first.text_range().start(), last.text_range().end())
+}
+
+fn previous_significant_token(token: &SyntaxToken) -> Option<SyntaxToken> {
+    let mut previous = token.prev_token();
+    while let Some(token) = previous {
+        if !is_trivia(token.kind()) {
+            return Some(token);
+        }
+        previous = token.prev_token();
+    }
+
+    None
+}
+
+pub(crate) fn next_significant_token(token: &SyntaxToken) -> Option<SyntaxToken> {
+    let mut next = token.next_token();
+    while let Some(token) = next {
+        if !is_trivia(token.kind()) {
+            return Some(token);
+        }
+        next = token.next_token();
+    }
+
+    None
+}
+
+/// The **string literal** the given expression consists of, if any.
+fn string_literal(test: &ast::Test) -> Option<ast::String> {
+    let mut tokens = significant_tokens(test.syntax());
+    match (tokens.next(), tokens.next()) {
+        (Some(token), None) => ast::String::cast(token),
+        _ => None,
+    }
+}
+
+/// The name of the function called by the given call, if it's a plain name
+/// (e.g. `glob` in `glob(["*.h"])`, but not `native.glob`).
+fn callee(call: &ast::CallSuffix) -> Option<String> {
+    let operand = call.syntax().prev_sibling().and_then(ast::Operand::cast)?;
+    if operand.syntax().prev_sibling().is_some() {
+        return None;
+    }
+
+    operand.identifier_token().map(|it| it.text().to_string())
+}
+
+/// The range to delete to **remove** the given element from its list (e.g. a
+/// symbol of a `load`), along with its comma.
+fn element_removal(element: &SyntaxNode) -> TextRange {
+    let range = significant_range(element);
+    let (Some(first), Some(last)) = (
+        significant_tokens(element).next(),
+        significant_tokens(element).last(),
+    ) else {
+        return range;
+    };
+    let comma = next_significant_token(&last).filter(|it| it.kind() == SyntaxKind::COMMA);
+    let end = comma
+        .as_ref()
+        .map_or(range.end(), |it| it.text_range().end());
+
+    match previous_significant_token(&first) {
+        // Along with the previous comma if it's the last element, e.g.
+        // `, "b"` in `("a", "b")`.
+        Some(previous) if previous.kind() == SyntaxKind::COMMA && comma.is_none() => {
+            TextRange::new(previous.text_range().start(), end)
+        }
+        // From the previous comma, e.g. ` "b",` in `("a", "b", "c")`.
+        Some(previous) if previous.kind() == SyntaxKind::COMMA => {
+            TextRange::new(previous.text_range().end(), end)
+        }
+        // Up to the next element, e.g. `"a", ` in `("a", "b")`.
+        _ => {
+            let next = comma.as_ref().and_then(next_significant_token);
+            let end = match next {
+                Some(next) if comma.is_some() => next.text_range().start(),
+                _ => end,
+            };
+            TextRange::new(range.start(), end)
+        }
+    }
+}
+
+/// An edit **removing** the given statement (e.g. a `LOAD_STMT`) along with
+/// its line, unless it shares its line with other code or is the only
+/// statement of its block.
+fn statement_removal(text: &str, statement: &SyntaxNode) -> Option<TextEdit> {
+    let simple = statement
+        .ancestors()
+        .find(|it| it.kind() == SyntaxKind::SIMPLE_STMT)?;
+    if simple
+        .children()
+        .filter(|it| it.kind() == SyntaxKind::SMALL_STMT)
+        .count()
+        != 1
+    {
+        return None;
+    }
+
+    let block = simple
+        .ancestors()
+        .find(|it| matches!(it.kind(), SyntaxKind::SUITE | SyntaxKind::FILE))?;
+    if block.kind() == SyntaxKind::SUITE && ast::Suite::cast(block)?.statements().count() < 2 {
+        return None;
+    }
+
+    let start = usize::from(significant_range(&simple).start());
+    let line_start = text[..start].rfind('\n').map_or(0, |it| it + 1);
+    if !text[line_start..start].trim().is_empty() {
+        return None;
+    }
+
+    Some(TextEdit::delete(TextRange::new(
+        (line_start as u32).into(),
+        simple.text_range().end(),
+    )))
+}
@@ -0,0 +1,59 @@
+use super::significant_range;
+use crate::lint::{Context, Rule, Severity};
+use sky_syntax::ast::{self, AstNode, AstToken};
+
+/// Parameters whose **default value is mutable** (e.g. `deps = []`). The
+/// value is shared between all calls, and frozen once its module is loaded,
+/// so mutating it fails.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MutableDefaultParameter;
+
+impl Rule for MutableDefaultParameter {
+    fn id(&self) -> &'static str {
+        "mutable-default-parameter"
+    }
+
+    fn description(&self) -> &'static str {
+        "Parameters with a mutable default value (e.g. a list)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let root = cx.file().syntax().clone();
+        for parameter in root.descendants().filter_map(ast::Parameter::cast) {
+            let (Some(name), Some(default)) = (parameter.name(), parameter.default()) else {
+                continue;
+            };
+            if is_mutable(&default) {
+                cx.report(
+                    significant_range(default.syntax()),
+                    format!(
+                        "The default value of `{}` is mutable; use `None` and create the value in the body instead",
+                        name.text()
+                    ),
+                    None,
+                );
+            }
+        }
+    }
+}
+
+/// Whether the given expression is a **mutable** literal, i.e. a list or
+/// dict (or a comprehension of one).
+fn is_mutable(test: &ast::Test) -> bool {
+    let ast::Test::PrimaryExpr(primary) = test else {
+        return false;
+    };
+    let Some(operand) = primary.operand() else {
+        return false;
+    };
+
+    primary.syntax().children().count() == 1
+        && (operand.list_expr().is_some()
+            || operand.list_comp().is_some()
+            || operand.dict_expr().is_some()
+            || operand.dict_comp().is_some())
+}
@@ -0,0 +1,59 @@
+use super::{callee, significant_range, statement_removal};
+use crate::lint::{Context, FileKind, Fix, Rule, Severity};
+use sky_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind,
+};
+
+/// Calls of **`print`** in BUILD files, which are only useful for debugging.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Print;
+
+impl Rule for Print {
+    fn id(&self) -> &'static str {
+        "print"
+    }
+
+    fn description(&self) -> &'static str {
+        "Calls of `print` in BUILD files"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        if cx.kind() != FileKind::Build {
+            return;
+        }
+
+        let root = cx.file().syntax().clone();
+        for call in root.descendants().filter_map(ast::CallSuffix::cast) {
+            if callee(&call).as_deref() != Some("print") {
+                continue;
+            }
+            let Some(expr) = call.syntax().parent() else {
+                continue;
+            };
+
+            // Only a call which is a statement of its own can be removed.
+            let statement = expr
+                .ancestors()
+                .skip(1)
+                .find(|it| !matches!(it.kind(), SyntaxKind::TEST | SyntaxKind::EXPRESSION))
+                .filter(|it| it.kind() == SyntaxKind::EXPR_STMT);
+            let fix = statement
+                .and_then(|it| statement_removal(cx.text(), &it))
+                .map(|edit| Fix {
+                    message: "Remove the call".to_string(),
+                    edits: vec![edit],
+                });
+
+            cx.report(
+                significant_range(&expr),
+                "`print` in a BUILD file is only useful for debugging",
+                fix,
+            );
+        }
+    }
+}
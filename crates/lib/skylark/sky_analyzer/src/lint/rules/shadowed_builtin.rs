@@ -0,0 +1,41 @@
+use crate::{
+    builtins::is_builtin,
+    lint::{Context, Rule, Severity},
+    resolve::{resolve, ResolveOptions},
+};
+
+/// Bindings which **shadow a builtin** (e.g. a parameter named `list`), making
+/// the builtin unavailable in their scope.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShadowedBuiltin;
+
+impl Rule for ShadowedBuiltin {
+    fn id(&self) -> &'static str {
+        "shadowed-builtin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bindings shadowing a builtin (e.g. a variable named `len`)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let resolution = resolve(cx.file(), &ResolveOptions::default());
+        for (_, binding) in resolution.bindings() {
+            if !is_builtin(&binding.name) {
+                continue;
+            }
+
+            for range in &binding.definitions {
+                cx.report(
+                    *range,
+                    format!("`{}` shadows the builtin of the same name", binding.name),
+                    None,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use super::{callee, significant_range};
+use crate::lint::{Context, Rule, Severity};
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode},
+    SyntaxKind, SyntaxNode,
+};
+
+/// **Unreachable code**, i.e. statements after a `return`, `break`,
+/// `continue` or call of `fail` in the same block.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unreachable;
+
+impl Rule for Unreachable {
+    fn id(&self) -> &'static str {
+        "unreachable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Statements after a `return`, `break`, `continue` or `fail`"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let root = cx.file().syntax().clone();
+        let blocks = root
+            .descendants()
+            .filter(|it| matches!(it.kind(), SyntaxKind::FILE | SyntaxKind::SUITE));
+
+        for block in blocks {
+            let statements = small_statements(&block);
+            let Some(index) = statements.iter().position(|it| exit(it).is_some()) else {
+                continue;
+            };
+            let (Some(first), Some(last)) = (statements.get(index + 1), statements.last()) else {
+                continue;
+            };
+
+            let exit = exit(&statements[index]).unwrap_or_default();
+            cx.report(
+                TextRange::new(
+                    significant_range(first).start(),
+                    significant_range(last).end(),
+                ),
+                format!("Unreachable code after `{exit}`"),
+                None,
+            );
+        }
+    }
+}
+
+/// The statements of the given block, with simple statements split into
+/// their small statements (e.g. `return; x = 1`).
+fn small_statements(block: &SyntaxNode) -> Vec<SyntaxNode> {
+    let mut statements = Vec::new();
+    for statement in block.children().filter_map(ast::Statement::cast) {
+        match statement {
+            ast::Statement::SimpleStmt(simple) => statements.extend(
+                simple
+                    .syntax()
+                    .children()
+                    .filter(|it| it.kind() == SyntaxKind::SMALL_STMT),
+            ),
+            _ => statements.push(statement.syntax().clone()),
+        }
+    }
+
+    statements
+}
+
+/// How the given statement **exits** its block, if it does (e.g. `return`).
+fn exit(statement: &SyntaxNode) -> Option<&'static str> {
+    match ast::SmallStmt::cast(statement.clone())? {
+        ast::SmallStmt::ReturnStmt(_) => Some("return"),
+        ast::SmallStmt::BreakStmt(_) => Some("break"),
+        ast::SmallStmt::ContinueStmt(_) => Some("continue"),
+        ast::SmallStmt::ExprStmt(expr) => {
+            let mut tests = expr.expression()?.tests();
+            let (Some(ast::Test::PrimaryExpr(primary)), None) = (tests.next(), tests.next()) else {
+                return None;
+            };
+            let call = primary.call_suffix()?;
+            (callee(&call).as_deref() == Some("fail")).then_some("fail")
+        }
+        _ => None,
+    }
+}
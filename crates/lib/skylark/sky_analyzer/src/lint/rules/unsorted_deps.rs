@@ -0,0 +1,139 @@
+use super::{significant_range, string_literal};
+use crate::{
+    labels::{self, SORTED_ATTRIBUTES},
+    lint::{Context, Fix, Rule, Severity},
+};
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    SyntaxKind, TextEdit,
+};
+use std::cmp::Ordering;
+
+/// **Unsorted** string lists of attributes like `deps` and `srcs` (see
+/// [`SORTED_ATTRIBUTES`]). Blank lines split a list into runs, each of which
+/// is sorted on its own.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnsortedDeps;
+
+impl Rule for UnsortedDeps {
+    fn id(&self) -> &'static str {
+        "unsorted-deps"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unsorted lists of labels in attributes like `deps` and `srcs`"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let root = cx.file().syntax().clone();
+        for argument in root.descendants().filter_map(ast::Argument::cast) {
+            let Some(name) = argument.name() else {
+                continue;
+            };
+            if !SORTED_ATTRIBUTES.contains(&name.text()) {
+                continue;
+            }
+            let Some((list, elements)) = string_list(&argument) else {
+                continue;
+            };
+
+            let runs = runs(cx.text(), elements);
+            let is_sorted = runs.iter().all(|run| {
+                run.windows(2)
+                    .all(|it| labels::compare(&it[0].1, &it[1].1) != Ordering::Greater)
+            });
+            if is_sorted {
+                continue;
+            }
+
+            // Elements are swapped along with their text, which would leave
+            // any comments behind.
+            let has_comments = list
+                .syntax()
+                .descendants_with_tokens()
+                .any(|it| it.kind() == SyntaxKind::COMMENT);
+            let fix = (!has_comments).then(|| Fix {
+                message: "Sort the list".to_string(),
+                edits: sort(cx.text(), runs),
+            });
+
+            cx.report(
+                significant_range(list.syntax()),
+                format!("The list of `{}` isn't sorted", name.text()),
+                fix,
+            );
+        }
+    }
+}
+
+/// The list of the given argument along with its elements and their values,
+/// if it's a list of string literals.
+fn string_list(argument: &ast::Argument) -> Option<(ast::ListExpr, Vec<(ast::Test, String)>)> {
+    let ast::Test::PrimaryExpr(primary) = argument.value()? else {
+        return None;
+    };
+    if primary.syntax().children().count() != 1 {
+        return None;
+    }
+    let list = primary.operand()?.list_expr()?;
+
+    let elements = match list.expression() {
+        Some(expression) => expression
+            .tests()
+            .map(|test| {
+                let value = string_literal(&test)?.value().ok()?;
+                Some((test, value))
+            })
+            .collect::<Option<Vec<_>>>()?,
+        None => Vec::new(),
+    };
+
+    Some((list, elements))
+}
+
+/// The **runs** of the given elements, i.e. the elements split at blank
+/// lines.
+fn runs(text: &str, elements: Vec<(ast::Test, String)>) -> Vec<Vec<(ast::Test, String)>> {
+    let mut runs: Vec<Vec<(ast::Test, String)>> = Vec::new();
+    for element in elements {
+        let blank_before = match runs.last().and_then(|run| run.last()) {
+            Some((previous, _)) => {
+                let between = &text[usize::from(significant_range(previous.syntax()).end())
+                    ..usize::from(significant_range(element.0.syntax()).start())];
+                between.matches('\n').count() > 1
+            }
+            None => true,
+        };
+
+        match runs.last_mut() {
+            Some(run) if !blank_before => run.push(element),
+            _ => runs.push(vec![element]),
+        }
+    }
+
+    runs
+}
+
+/// The edits **sorting** each of the given runs, by replacing each element
+/// by the one which belongs in its place.
+fn sort(text: &str, runs: Vec<Vec<(ast::Test, String)>>) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    for run in runs {
+        let mut sorted = run.clone();
+        sorted.sort_by(|a, b| labels::compare(&a.1, &b.1));
+
+        for ((element, _), (replacement, _)) in run.iter().zip(&sorted) {
+            if element != replacement {
+                let range = significant_range(element.syntax());
+                let replacement = &text[significant_range(replacement.syntax())];
+                edits.push(TextEdit::replace(range, replacement));
+            }
+        }
+    }
+
+    edits
+}
@@ -0,0 +1,91 @@
+use super::{element_removal, significant_range, statement_removal};
+use crate::lint::{Context, Fix, Rule, Severity};
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    SyntaxKind,
+};
+use std::collections::HashSet;
+
+/// Symbols which are **loaded but never used**.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnusedLoad;
+
+impl Rule for UnusedLoad {
+    fn id(&self) -> &'static str {
+        "unused-load"
+    }
+
+    fn description(&self) -> &'static str {
+        "Symbols loaded but never used"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        let root = cx.file().syntax().clone();
+        let used = root
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| {
+                it.kind() == SyntaxKind::IDENTIFIER
+                    && matches!(it.parent(), Some(parent) if parent.kind() == SyntaxKind::OPERAND)
+            })
+            .map(|it| it.text().to_string())
+            .collect::<HashSet<_>>();
+
+        for load in root.descendants().filter_map(ast::LoadStmt::cast) {
+            let symbols = load.load_symbols().collect::<Vec<_>>();
+            let unused = symbols
+                .iter()
+                .filter_map(|symbol| {
+                    let name = bound_name(symbol)?;
+                    (!used.contains(&name)).then_some((symbol, name))
+                })
+                .collect::<Vec<_>>();
+            if unused.is_empty() {
+                continue;
+            }
+
+            if unused.len() == symbols.len() {
+                let module = load
+                    .module()
+                    .map_or_else(String::new, |it| it.value().unwrap_or_default());
+                let fix = statement_removal(cx.text(), load.syntax()).map(|edit| Fix {
+                    message: "Remove the load".to_string(),
+                    edits: vec![edit],
+                });
+                cx.report(
+                    significant_range(load.syntax()),
+                    format!("None of the symbols loaded from `{module}` are used"),
+                    fix,
+                );
+                continue;
+            }
+
+            for (symbol, name) in unused {
+                let fix = Fix {
+                    message: format!("Remove `{name}`"),
+                    edits: vec![sky_syntax::TextEdit::delete(element_removal(
+                        symbol.syntax(),
+                    ))],
+                };
+                cx.report(
+                    significant_range(symbol.syntax()),
+                    format!("`{name}` is loaded but never used"),
+                    Some(fix),
+                );
+            }
+        }
+    }
+}
+
+/// The name the given symbol is **bound** to, i.e. its alias or the symbol
+/// itself.
+fn bound_name(symbol: &ast::LoadSymbol) -> Option<String> {
+    match symbol.alias() {
+        Some(alias) => Some(alias.text().to_string()),
+        None => symbol.symbol()?.value().ok(),
+    }
+}
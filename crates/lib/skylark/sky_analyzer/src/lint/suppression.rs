@@ -0,0 +1,97 @@
+//! **Suppression comments**, disabling rules on a single line.
+//!
+//! A comment like `# buildifier: disable=unused-load,print` applies to its
+//! own line if it trails code, and otherwise to the next line of code.
+
+use crate::lint::rules::next_significant_token;
+use rowan::TextSize;
+use sky_syntax::{SyntaxKind, SyntaxNode};
+use std::collections::HashMap;
+
+/// The prefix of suppression comments (after the `#`), as with buildifier.
+const PREFIX: &str = "buildifier:";
+
+#[derive(Debug, Default)]
+pub(crate) struct Suppressions {
+    /// The offsets at which lines start.
+    line_starts: Vec<TextSize>,
+    /// The rules suppressed on each line (by index).
+    lines: HashMap<usize, Vec<String>>,
+}
+
+impl Suppressions {
+    /// Collects the suppression comments of the given tree.
+    pub(crate) fn collect(root: &SyntaxNode, text: &str) -> Suppressions {
+        let mut line_starts = vec![TextSize::from(0)];
+        line_starts.extend(
+            text.match_indices('\n')
+                .map(|(index, _)| TextSize::from(index as u32 + 1)),
+        );
+        let mut suppressions = Suppressions {
+            line_starts,
+            lines: HashMap::new(),
+        };
+
+        for comment in root
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| it.kind() == SyntaxKind::COMMENT)
+        {
+            let Some(rules) = parse(comment.text()) else {
+                continue;
+            };
+
+            let start = comment.text_range().start();
+            let line = suppressions.line(start);
+            let line_start = usize::from(suppressions.line_starts[line]);
+            let trails_code = !text[line_start..usize::from(start)].trim().is_empty();
+            let line = if trails_code {
+                line
+            } else {
+                match next_significant_token(&comment) {
+                    Some(it) => suppressions.line(it.text_range().start()),
+                    None => continue,
+                }
+            };
+
+            suppressions.lines.entry(line).or_default().extend(rules);
+        }
+
+        suppressions
+    }
+
+    /// Whether the given rule is suppressed on the line of the given offset.
+    pub(crate) fn is_suppressed(&self, rule: &str, offset: TextSize) -> bool {
+        matches!(
+            self.lines.get(&self.line(offset)),
+            Some(rules) if rules.iter().any(|it| it == rule)
+        )
+    }
+
+    /// The (zero-based) **line** of the given offset.
+    fn line(&self, offset: TextSize) -> usize {
+        self.line_starts.partition_point(|it| *it <= offset) - 1
+    }
+}
+
+/// The rules disabled by the given comment, if it's a suppression comment.
+fn parse(comment: &str) -> Option<Vec<String>> {
+    let rules = comment
+        .trim_start_matches('#')
+        .trim()
+        .strip_prefix(PREFIX)?
+        .trim_start()
+        .strip_prefix("disable=")?;
+
+    // Anything after the rules (e.g. a reason) is ignored.
+    Some(
+        rules
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|it| it.trim().to_string())
+            .filter(|it| !it.is_empty())
+            .collect(),
+    )
+}
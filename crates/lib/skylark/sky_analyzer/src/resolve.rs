@@ -0,0 +1,541 @@
+//! **Name resolution**, i.e. which binding each identifier refers to.
+//!
+//! Resolution follows the scoping rules of Starlark:
+//!
+//! - The **module** scope holds the globals of a file, i.e. the names bound
+//!   at the top level (including within top-level `if` and `for` statements)
+//!   and the symbols of `load` statements. A global may only be bound
+//!   **once**.
+//! - Each **function** (i.e. `def` statement or `lambda`) has a scope of its
+//!   own, holding its parameters and any name bound within its body, which is
+//!   local to the whole function (even where used before being bound). The
+//!   default values of parameters are resolved in the enclosing scope.
+//! - Each **comprehension** has a scope of its own, holding its loop
+//!   variables, which aren't visible outside of it. The iterable of its
+//!   first `for` clause is resolved in the enclosing scope.
+//!
+//! Names which aren't bound in any enclosing scope refer to the predeclared
+//! names of the environment (e.g. `rust_library` in a `BUCK` file) or the
+//! [builtins](crate::builtins), and are undefined otherwise. The names of type
+//! annotations aren't resolved, as they refer to types (see
+//! [`types`](crate::types)).
+
+use crate::builtins::is_builtin;
+use rowan::{TextRange, TextSize};
+use sky_syntax::{
+    ast::{self, AstNode, AstToken},
+    File, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// The names predeclared by the environment, besides the builtins (e.g.
+    /// the rules available in a `BUCK` file).
+    pub predeclared: HashSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BindingId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeKind {
+    Module,
+    Function,
+    Comprehension,
+}
+
+/// A **scope**, holding the names bound within it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// The enclosing scope, or `None` for the module scope.
+    pub parent: Option<ScopeId>,
+    /// The range of the node introducing the scope (e.g. the `def`).
+    pub range: TextRange,
+    /// The bindings of the scope, in the order they're first bound.
+    pub bindings: Vec<BindingId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    /// A symbol of a `load` statement.
+    Load,
+    /// The name of a `def` statement.
+    Function,
+    Parameter,
+    /// A name bound by an assignment or a loop (of a `for` statement or a
+    /// comprehension).
+    Variable,
+}
+
+/// A **binding** of a name in a scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub scope: ScopeId,
+    /// The ranges of the names binding it (e.g. the targets of assignments),
+    /// in order. Only locals are bound more than once.
+    pub definitions: Vec<TextRange>,
+}
+
+impl Binding {
+    /// The range of the name first binding it.
+    pub fn range(&self) -> TextRange {
+        self.definitions[0]
+    }
+}
+
+/// What a name **resolves** to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolved {
+    Binding(BindingId),
+    /// A name predeclared by the environment (see [`ResolveOptions`]).
+    Predeclared,
+    Builtin,
+    Undefined,
+}
+
+/// A **reference** to a name, i.e. a use of an identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub name: String,
+    pub range: TextRange,
+    pub scope: ScopeId,
+    pub resolved: Resolved,
+}
+
+/// An **error** found while resolving names.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum NameError {
+    #[error("`{name}` is not defined")]
+    Undefined { name: String, range: TextRange },
+    #[error("`{name}` is already defined at the top level, and globals can't be reassigned")]
+    ReassignedGlobal {
+        name: String,
+        range: TextRange,
+        /// The range of the name first binding it.
+        previous: TextRange,
+    },
+    #[error("Duplicate parameter `{name}`")]
+    DuplicateParameter {
+        name: String,
+        range: TextRange,
+        previous: TextRange,
+    },
+}
+
+impl NameError {
+    /// The range of the offending name.
+    pub fn range(&self) -> TextRange {
+        match self {
+            NameError::Undefined { range, .. }
+            | NameError::ReassignedGlobal { range, .. }
+            | NameError::DuplicateParameter { range, .. } => *range,
+        }
+    }
+}
+
+/// The **resolution** of the names of a file, i.e. its scopes, the bindings
+/// of each scope, and what each reference resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    /// The references, ordered by position.
+    references: Vec<Reference>,
+    /// The errors, ordered by position.
+    errors: Vec<NameError>,
+}
+
+impl Resolution {
+    /// The module scope, which encloses all other scopes.
+    pub fn module(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0 as usize]
+    }
+
+    pub fn scopes(&self) -> impl Iterator<Item = (ScopeId, &Scope)> {
+        (0..).map(ScopeId).zip(&self.scopes)
+    }
+
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0 as usize]
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (BindingId, &Binding)> {
+        (0..).map(BindingId).zip(&self.bindings)
+    }
+
+    /// All references, ordered by position.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// The errors (e.g. undefined names), ordered by position.
+    pub fn errors(&self) -> &[NameError] {
+        &self.errors
+    }
+
+    /// The **innermost scope** at the given offset.
+    pub fn scope_at(&self, offset: TextSize) -> ScopeId {
+        self.scopes()
+            .filter(|(_, scope)| scope.range.contains_inclusive(offset))
+            .min_by_key(|(_, scope)| scope.range.len())
+            .map_or(self.module(), |(id, _)| id)
+    }
+
+    /// The reference at the given offset, if any.
+    pub fn reference_at(&self, offset: TextSize) -> Option<&Reference> {
+        let index = self
+            .references
+            .partition_point(|it| it.range.end() < offset);
+
+        self.references
+            .get(index)
+            .filter(|it| it.range.contains_inclusive(offset))
+    }
+
+    /// The binding defined or referenced at the given offset, if any.
+    pub fn binding_at(&self, offset: TextSize) -> Option<BindingId> {
+        if let Some(Reference {
+            resolved: Resolved::Binding(id),
+            ..
+        }) = self.reference_at(offset)
+        {
+            return Some(*id);
+        }
+
+        self.bindings()
+            .find(|(_, binding)| {
+                binding
+                    .definitions
+                    .iter()
+                    .any(|it| it.contains_inclusive(offset))
+            })
+            .map(|(id, _)| id)
+    }
+
+    /// The references **resolving to** the given binding.
+    pub fn references_to(&self, binding: BindingId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |it| it.resolved == Resolved::Binding(binding))
+    }
+
+    /// What the given name **resolves to** in the given scope.
+    pub fn lookup(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        let mut scope = Some(scope);
+        while let Some(id) = scope {
+            let found = self
+                .scope(id)
+                .bindings
+                .iter()
+                .find(|it| self.binding(**it).name == name);
+            if let Some(binding) = found {
+                return Some(*binding);
+            }
+            scope = self.scope(id).parent;
+        }
+
+        None
+    }
+}
+
+/// **Resolves** the names of the given file.
+pub fn resolve(file: &File, options: &ResolveOptions) -> Resolution {
+    let mut resolver = Resolver {
+        resolution: Resolution {
+            scopes: Vec::new(),
+            bindings: Vec::new(),
+            references: Vec::new(),
+            errors: Vec::new(),
+        },
+        scopes: HashMap::new(),
+        targets: HashSet::new(),
+    };
+    resolver.collect_scopes(file.syntax());
+    resolver.collect_bindings(file.syntax());
+    resolver.resolve_references(file.syntax(), options);
+
+    let mut resolution = resolver.resolution;
+    resolution.errors.sort_by_key(|it| it.range().start());
+
+    resolution
+}
+
+struct Resolver {
+    resolution: Resolution,
+    /// The scopes of the nodes introducing them.
+    scopes: HashMap<SyntaxNode, ScopeId>,
+    /// The ranges of identifiers which bind names rather than reference them
+    /// (e.g. the targets of assignments).
+    targets: HashSet<TextRange>,
+}
+
+impl Resolver {
+    fn collect_scopes(&mut self, root: &SyntaxNode) {
+        for node in root.descendants() {
+            let kind = match node.kind() {
+                SyntaxKind::FILE => ScopeKind::Module,
+                SyntaxKind::DEF_STMT | SyntaxKind::LAMBDA_EXPR => ScopeKind::Function,
+                SyntaxKind::LIST_COMP | SyntaxKind::DICT_COMP => ScopeKind::Comprehension,
+                _ => continue,
+            };
+            let parent = (kind != ScopeKind::Module).then(|| self.enclosing_scope(&node));
+
+            let id = ScopeId(self.resolution.scopes.len() as u32);
+            self.resolution.scopes.push(Scope {
+                kind,
+                parent,
+                range: node.text_range(),
+                bindings: Vec::new(),
+            });
+            self.scopes.insert(node, id);
+        }
+    }
+
+    fn collect_bindings(&mut self, root: &SyntaxNode) {
+        for node in root.descendants() {
+            match node.kind() {
+                SyntaxKind::LOAD_SYMBOL => {
+                    let Some(symbol) = ast::LoadSymbol::cast(node) else {
+                        continue;
+                    };
+                    let (name, range) = match (symbol.alias(), symbol.symbol()) {
+                        (Some(alias), _) => (alias.text().to_string(), alias.syntax().text_range()),
+                        (None, Some(symbol)) => match symbol.value() {
+                            Ok(value) => (value, symbol.syntax().text_range()),
+                            Err(_) => continue,
+                        },
+                        (None, None) => continue,
+                    };
+                    let module = ScopeId(0);
+                    self.bind(module, name, BindingKind::Load, range);
+                }
+                SyntaxKind::DEF_STMT => {
+                    let Some(name) = ast::DefStmt::cast(node.clone()).and_then(|it| it.name())
+                    else {
+                        continue;
+                    };
+                    let scope = self.enclosing_scope(&node);
+                    let range = name.syntax().text_range();
+                    self.bind(scope, name.text().to_string(), BindingKind::Function, range);
+                }
+                SyntaxKind::PARAMETER => {
+                    let Some(function) = node.ancestors().find(|it| {
+                        matches!(it.kind(), SyntaxKind::DEF_STMT | SyntaxKind::LAMBDA_EXPR)
+                    }) else {
+                        continue;
+                    };
+                    let Some(name) = ast::Parameter::cast(node).and_then(|it| it.name()) else {
+                        continue;
+                    };
+                    let scope = self.scopes[&function];
+                    let range = name.syntax().text_range();
+                    self.bind(
+                        scope,
+                        name.text().to_string(),
+                        BindingKind::Parameter,
+                        range,
+                    );
+                }
+                SyntaxKind::ASSIGN_STMT => {
+                    let Some(lhs) = ast::AssignStmt::cast(node.clone()).and_then(|it| it.lhs())
+                    else {
+                        continue;
+                    };
+                    let scope = self.enclosing_scope(&node);
+                    self.bind_targets(scope, lhs.syntax());
+                }
+                SyntaxKind::LOOP_VARIABLES => {
+                    // The loop variables of a comprehension belong to it,
+                    // even though its first iterable doesn't.
+                    let scope = match node.parent() {
+                        Some(clause) if clause.kind() == SyntaxKind::COMP_CLAUSE => {
+                            match clause.parent().and_then(|it| self.scopes.get(&it).copied()) {
+                                Some(scope) => scope,
+                                None => continue,
+                            }
+                        }
+                        _ => self.enclosing_scope(&node),
+                    };
+                    self.bind_targets(scope, &node);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve_references(&mut self, root: &SyntaxNode, options: &ResolveOptions) {
+        let identifiers = root
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| it.kind() == SyntaxKind::IDENTIFIER);
+
+        for token in identifiers {
+            let Some(operand) = token.parent().filter(|it| it.kind() == SyntaxKind::OPERAND) else {
+                continue;
+            };
+            if self.targets.contains(&token.text_range()) {
+                continue;
+            }
+            // Type annotations name types rather than values (e.g. `Optional`),
+            // which are left to the type checker.
+            if operand
+                .ancestors()
+                .any(|it| it.kind() == SyntaxKind::TYPE_ANNOTATION)
+            {
+                continue;
+            }
+
+            let name = token.text().to_string();
+            let scope = self.enclosing_scope(&operand);
+            let resolved = match self.resolution.lookup(scope, &name) {
+                Some(binding) => Resolved::Binding(binding),
+                None if options.predeclared.contains(&name) => Resolved::Predeclared,
+                None if is_builtin(&name) => Resolved::Builtin,
+                None => {
+                    self.resolution.errors.push(NameError::Undefined {
+                        name: name.clone(),
+                        range: token.text_range(),
+                    });
+                    Resolved::Undefined
+                }
+            };
+
+            self.resolution.references.push(Reference {
+                name,
+                range: token.text_range(),
+                scope,
+                resolved,
+            });
+        }
+    }
+
+    /// **Binds** the given name in the given scope.
+    fn bind(&mut self, scope: ScopeId, name: String, kind: BindingKind, range: TextRange) {
+        let existing = self
+            .resolution
+            .scope(scope)
+            .bindings
+            .iter()
+            .copied()
+            .find(|it| self.resolution.binding(*it).name == name);
+
+        let Some(existing) = existing else {
+            let id = BindingId(self.resolution.bindings.len() as u32);
+            self.resolution.bindings.push(Binding {
+                name,
+                kind,
+                scope,
+                definitions: vec![range],
+            });
+            self.resolution.scopes[scope.0 as usize].bindings.push(id);
+            return;
+        };
+
+        let binding = &mut self.resolution.bindings[existing.0 as usize];
+        let previous = binding.definitions[0];
+        let is_parameter = binding.kind == BindingKind::Parameter;
+        binding.definitions.push(range);
+
+        let scope = self.resolution.scope(scope);
+        if kind == BindingKind::Parameter && is_parameter {
+            self.resolution.errors.push(NameError::DuplicateParameter {
+                name,
+                range,
+                previous,
+            });
+        } else if scope.kind == ScopeKind::Module {
+            self.resolution.errors.push(NameError::ReassignedGlobal {
+                name,
+                range,
+                previous,
+            });
+        }
+    }
+
+    /// Binds the names of the given **target** (e.g. `a` and `b` in
+    /// `[a, (b, c.d)]`, but not `c`).
+    fn bind_targets(&mut self, scope: ScopeId, node: &SyntaxNode) {
+        for name in targets(node) {
+            self.targets.insert(name.text_range());
+            self.bind(
+                scope,
+                name.text().to_string(),
+                BindingKind::Variable,
+                name.text_range(),
+            );
+        }
+    }
+
+    /// The innermost scope **enclosing** the given node (excluding the scope
+    /// the node introduces itself, if any).
+    fn enclosing_scope(&self, node: &SyntaxNode) -> ScopeId {
+        let mut child = node.clone();
+        while let Some(parent) = child.parent() {
+            let is_enclosed = match parent.kind() {
+                // Default values belong to the enclosing scope.
+                SyntaxKind::DEF_STMT | SyntaxKind::LAMBDA_EXPR => {
+                    child.kind() != SyntaxKind::PARAMETERS
+                }
+                // The first iterable belongs to the enclosing scope.
+                SyntaxKind::LIST_COMP | SyntaxKind::DICT_COMP => {
+                    parent
+                        .children()
+                        .find(|it| it.kind() == SyntaxKind::COMP_CLAUSE)
+                        != Some(child.clone())
+                }
+                _ => false,
+            };
+            if is_enclosed {
+                if let Some(scope) = self.scopes.get(&parent) {
+                    return *scope;
+                }
+            }
+
+            child = parent;
+        }
+
+        ScopeId(0)
+    }
+}
+
+/// The identifiers **bound** by assigning to the given target (e.g. `a` and
+/// `b` in `[a, (b, c.d)]`, but not `c`).
+fn targets(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    let mut targets = Vec::new();
+    collect_targets(node, &mut targets);
+
+    targets
+}
+
+fn collect_targets(node: &SyntaxNode, targets: &mut Vec<SyntaxToken>) {
+    match node.kind() {
+        // An attribute or index (e.g. `c.d`) doesn't bind a name.
+        SyntaxKind::PRIMARY_EXPR if node.children().count() > 1 => {}
+        SyntaxKind::OPERAND => {
+            match ast::Operand::cast(node.clone()).and_then(|it| it.identifier_token()) {
+                Some(name) => targets.push(name),
+                None => node.children().for_each(|it| collect_targets(&it, targets)),
+            }
+        }
+        SyntaxKind::EXPRESSION
+        | SyntaxKind::TEST
+        | SyntaxKind::PRIMARY_EXPR
+        | SyntaxKind::LIST_EXPR
+        | SyntaxKind::LOOP_VARIABLES => {
+            node.children().for_each(|it| collect_targets(&it, targets))
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,258 @@
+//! The **types** of the builtins, i.e. of the builtin functions and of the
+//! methods of the builtin types.
+
+use crate::{
+    builtins::BUILTINS,
+    types::{is_assignable, join, Type},
+};
+
+const STRING_METHODS: &[&str] = &[
+    "capitalize",
+    "codepoint_ords",
+    "codepoints",
+    "count",
+    "elem_ords",
+    "elems",
+    "endswith",
+    "find",
+    "format",
+    "index",
+    "isalnum",
+    "isalpha",
+    "isdigit",
+    "islower",
+    "isspace",
+    "istitle",
+    "isupper",
+    "join",
+    "lower",
+    "lstrip",
+    "partition",
+    "removeprefix",
+    "removesuffix",
+    "replace",
+    "rfind",
+    "rindex",
+    "rpartition",
+    "rsplit",
+    "rstrip",
+    "split",
+    "splitlines",
+    "startswith",
+    "strip",
+    "title",
+    "upper",
+];
+
+const BYTES_METHODS: &[&str] = &["elems"];
+
+const LIST_METHODS: &[&str] = &[
+    "append", "clear", "extend", "index", "insert", "pop", "remove",
+];
+
+const DICT_METHODS: &[&str] = &[
+    "clear",
+    "get",
+    "items",
+    "keys",
+    "pop",
+    "popitem",
+    "setdefault",
+    "update",
+    "values",
+];
+
+/// The type of the **builtin** with the given name.
+pub(super) fn builtin(name: &str) -> Type {
+    match name {
+        "None" => Type::None,
+        "True" | "False" => Type::Bool,
+        _ => match BUILTINS.binary_search(&name) {
+            Ok(i) => Type::Builtin(BUILTINS[i]),
+            Err(_) => Type::Any,
+        },
+    }
+}
+
+/// The name of the **method** of the given (known) type with the given
+/// name, if any.
+pub(super) fn method(ty: &Type, name: &str) -> Option<&'static str> {
+    let methods = match ty {
+        Type::String => STRING_METHODS,
+        Type::Bytes => BYTES_METHODS,
+        Type::List(_) => LIST_METHODS,
+        Type::Dict(..) => DICT_METHODS,
+        _ => &[],
+    };
+
+    methods
+        .binary_search(&name)
+        .ok()
+        .map(|index| methods[index])
+}
+
+/// The type of the **elements** of the given type, or `None` if it isn't
+/// iterable. Strings and bytes aren't iterable in Starlark (but their
+/// `elems()` are).
+pub(super) fn element_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::List(element) => Some((**element).clone()),
+        Type::Dict(key, _) => Some((**key).clone()),
+        Type::Tuple(Some(elements)) => Some(join_all(elements)),
+        Type::Tuple(None) | Type::Any | Type::Union(_) => Some(Type::Any),
+        _ => None,
+    }
+}
+
+/// The join of the given types, which is `Any` if there are none.
+pub(super) fn join_all(types: &[Type]) -> Type {
+    match types.split_first() {
+        Some((first, rest)) => rest.iter().fold(first.clone(), |it, ty| join(&it, ty)),
+        None => Type::Any,
+    }
+}
+
+/// The type returned by **calling** the builtin function with the given name
+/// with positional arguments of the given types, or an error message if the
+/// arguments are of the wrong types.
+pub(super) fn call(name: &str, arguments: &[Type]) -> Result<Type, String> {
+    let first = arguments.first().unwrap_or(&Type::Any);
+    let ty = match name {
+        "abs" => match first {
+            Type::Int | Type::Float | Type::Any => first.clone(),
+            ty => return Err(format!("Unsupported operand type for `abs`: `{ty}`")),
+        },
+        "all" | "any" => {
+            elements(name, first)?;
+            Type::Bool
+        }
+        "bool" | "hasattr" => Type::Bool,
+        "bytes" => Type::Bytes,
+        "dict" => match first {
+            Type::Dict(..) => first.clone(),
+            _ => Type::Dict(Box::new(Type::Any), Box::new(Type::Any)),
+        },
+        "dir" => Type::List(Box::new(Type::String)),
+        "enumerate" => {
+            let element = elements(name, first)?;
+            Type::List(Box::new(Type::Tuple(Some(vec![Type::Int, element]))))
+        }
+        "fail" | "print" => Type::None,
+        "float" => Type::Float,
+        "getattr" => Type::Any,
+        "hash" | "int" => Type::Int,
+        "len" => match first {
+            Type::String
+            | Type::Bytes
+            | Type::List(_)
+            | Type::Dict(..)
+            | Type::Tuple(_)
+            | Type::Union(_)
+            | Type::Any => Type::Int,
+            ty => return Err(format!("`{ty}` has no length")),
+        },
+        "list" | "reversed" | "sorted" => Type::List(Box::new(elements(name, first)?)),
+        "max" | "min" if arguments.len() == 1 => elements(name, first)?,
+        "max" | "min" => join_all(arguments),
+        "range" => {
+            if let Some(ty) = arguments.iter().find(|it| !is_assignable(it, &Type::Int)) {
+                return Err(format!(
+                    "Expected `int` for the arguments of `range`, got `{ty}`"
+                ));
+            }
+            Type::List(Box::new(Type::Int))
+        }
+        "repr" | "str" | "type" => Type::String,
+        "tuple" => {
+            elements(name, first)?;
+            Type::Tuple(None)
+        }
+        "zip" => {
+            let elements = arguments
+                .iter()
+                .map(|it| elements(name, it))
+                .collect::<Result<_, _>>()?;
+            Type::List(Box::new(Type::Tuple(Some(elements))))
+        }
+        _ => Type::Any,
+    };
+
+    Ok(ty)
+}
+
+/// The type returned by **calling** the method with the given name of a
+/// value of the given type, with positional arguments of the given types.
+pub(super) fn call_method(ty: &Type, name: &str, arguments: &[Type]) -> Result<Type, String> {
+    let argument = |index: usize| arguments.get(index).cloned().unwrap_or(Type::Any);
+    let ty = match (ty, name) {
+        (Type::String, "join") => {
+            let element = elements(name, &argument(0))?;
+            if !is_assignable(&element, &Type::String) {
+                return Err(format!(
+                    "Expected an iterable of `str` for the argument of `join`, got `{}`",
+                    argument(0)
+                ));
+            }
+            Type::String
+        }
+        (Type::String, "count" | "find" | "index" | "rfind" | "rindex") => Type::Int,
+        (Type::String, "endswith" | "startswith") => Type::Bool,
+        (Type::String, name) if name.starts_with("is") => Type::Bool,
+        (Type::String, "split" | "rsplit" | "splitlines" | "elems" | "codepoints") => {
+            Type::List(Box::new(Type::String))
+        }
+        (Type::String, "partition" | "rpartition") => Type::Tuple(Some(vec![Type::String; 3])),
+        (Type::String, "elem_ords" | "codepoint_ords") | (Type::Bytes, "elems") => {
+            Type::List(Box::new(Type::Int))
+        }
+        (Type::String, _) => Type::String,
+        (Type::List(element), "append" | "remove") => {
+            expect(name, &argument(0), element)?;
+            Type::None
+        }
+        (Type::List(element), "insert") => {
+            expect(name, &argument(1), element)?;
+            Type::None
+        }
+        (Type::List(element), "extend") => {
+            expect(name, &elements(name, &argument(0))?, element)?;
+            Type::None
+        }
+        (Type::List(_), "index") => Type::Int,
+        (Type::List(element), "pop") => (**element).clone(),
+        (Type::Dict(_, value), "get") => match arguments.get(1) {
+            Some(default) => Type::union([(**value).clone(), default.clone()]),
+            None => Type::union([(**value).clone(), Type::None]),
+        },
+        (Type::Dict(key, value), "items") => Type::List(Box::new(Type::Tuple(Some(vec![
+            (**key).clone(),
+            (**value).clone(),
+        ])))),
+        (Type::Dict(key, _), "keys") => Type::List(key.clone()),
+        (Type::Dict(_, value), "values") => Type::List(value.clone()),
+        (Type::Dict(_, value), "pop" | "setdefault") => (**value).clone(),
+        (Type::Dict(key, value), "popitem") => {
+            Type::Tuple(Some(vec![(**key).clone(), (**value).clone()]))
+        }
+        (Type::List(_) | Type::Dict(..), _) => Type::None,
+        _ => Type::Any,
+    };
+
+    Ok(ty)
+}
+
+/// The type of the elements of the given argument of the given function.
+fn elements(function: &str, ty: &Type) -> Result<Type, String> {
+    element_type(ty)
+        .ok_or_else(|| format!("Expected an iterable for the argument of `{function}`, got `{ty}`"))
+}
+
+fn expect(function: &str, ty: &Type, expected: &Type) -> Result<(), String> {
+    if is_assignable(ty, expected) {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Expected `{expected}` for the argument of `{function}`, got `{ty}`"
+    ))
+}
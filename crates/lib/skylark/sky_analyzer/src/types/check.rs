@@ -0,0 +1,1271 @@
+//! The **checker**, walking a file to infer the types of its bindings and
+//! report the type errors.
+
+use crate::{
+    lint::rules::significant_range,
+    resolve::{resolve, BindingId, Resolution, ResolveOptions, Resolved},
+    types::{
+        builtins, is_assignable, join, Environment, Parameter, ParameterKind, Signature, Type,
+        TypeCheck, TypeError,
+    },
+};
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode, AstToken, BinaryOp, UnaryOp},
+    File, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use std::collections::{HashMap, HashSet};
+
+pub(super) struct Checker<'a> {
+    file: File,
+    environment: &'a dyn Environment,
+    resolution: Resolution,
+    /// What each identifier resolves to, by range.
+    references: HashMap<TextRange, Resolved>,
+    /// The binding of each name binding it (e.g. the target of an
+    /// assignment), by range.
+    definitions: HashMap<TextRange, BindingId>,
+    types: HashMap<BindingId, Type>,
+    /// The bindings whose types are **declared** (e.g. by annotations)
+    /// rather than inferred.
+    declared: HashSet<BindingId>,
+    /// Whether errors are reported, i.e. in the second pass.
+    reporting: bool,
+    errors: Vec<TypeError>,
+    /// The functions being checked, innermost last.
+    functions: Vec<Function>,
+}
+
+/// A **function** being checked.
+struct Function {
+    name: String,
+    /// The annotated return type, if any.
+    return_type: Option<Type>,
+    /// The types of the values returned so far.
+    returns: Vec<Type>,
+}
+
+/// The (inferred) **arguments** of a call.
+#[derive(Default)]
+struct Arguments {
+    positional: Vec<(Type, TextRange)>,
+    named: Vec<(String, Type, TextRange)>,
+    /// Whether any arguments are unpacked (e.g. `*args`), in which case the
+    /// arguments passed to each parameter aren't known.
+    unpacked: bool,
+}
+
+impl<'a> Checker<'a> {
+    pub(super) fn new(file: &File, environment: &'a dyn Environment) -> Checker<'a> {
+        let resolution = resolve(file, &ResolveOptions::default());
+        let references = resolution
+            .references()
+            .iter()
+            .map(|it| (it.range, it.resolved))
+            .collect();
+        let definitions = resolution
+            .bindings()
+            .flat_map(|(id, binding)| binding.definitions.iter().map(move |range| (*range, id)))
+            .collect();
+
+        Checker {
+            file: file.clone(),
+            environment,
+            resolution,
+            references,
+            definitions,
+            types: HashMap::new(),
+            declared: HashSet::new(),
+            reporting: false,
+            errors: Vec::new(),
+            functions: Vec::new(),
+        }
+    }
+
+    pub(super) fn run(mut self) -> TypeCheck {
+        let file = self.file.clone();
+        self.declare(file.syntax());
+
+        // The first pass infers the types of the bindings, which the second
+        // one checks the uses of.
+        self.check_statements(file.statements());
+        self.reporting = true;
+        self.check_statements(file.statements());
+
+        self.errors
+            .sort_by_key(|it| (it.range.start(), it.range.end()));
+        self.errors.dedup();
+
+        TypeCheck {
+            resolution: self.resolution,
+            types: self.types,
+            errors: self.errors,
+        }
+    }
+
+    /// **Declares** the types of the functions (from their annotations) and
+    /// of the symbols of `load` statements, before anything is inferred.
+    fn declare(&mut self, root: &SyntaxNode) {
+        for node in root.descendants() {
+            if let Some(def) = ast::DefStmt::cast(node.clone()) {
+                let signature = self.signature(&def);
+                for (parameter, name) in signature.parameters.iter().zip(parameter_names(&def)) {
+                    let ty = match parameter.kind {
+                        ParameterKind::Args => Type::Tuple(None),
+                        ParameterKind::Kwargs => {
+                            Type::Dict(Box::new(Type::String), Box::new(parameter.ty.clone()))
+                        }
+                        _ => parameter.ty.clone(),
+                    };
+                    self.declare_binding(name.text_range(), ty);
+                }
+                if let Some(id) = def
+                    .name()
+                    .and_then(|it| self.definitions.get(&it.syntax().text_range()).copied())
+                {
+                    self.types.insert(id, Type::Function(Box::new(signature)));
+                }
+            } else if let Some(load) = ast::LoadStmt::cast(node) {
+                let Some(Ok(module)) = load.module().map(|it| it.value()) else {
+                    continue;
+                };
+                for symbol in load.load_symbols() {
+                    let Some(Ok(name)) = symbol.symbol().map(|it| it.value()) else {
+                        continue;
+                    };
+                    let range = match (symbol.alias(), symbol.symbol()) {
+                        (Some(alias), _) => alias.syntax().text_range(),
+                        (None, Some(symbol)) => symbol.syntax().text_range(),
+                        (None, None) => continue,
+                    };
+                    let ty = self.environment.load(&module, &name).unwrap_or(Type::Any);
+                    self.declare_binding(range, ty);
+                }
+            }
+        }
+    }
+
+    fn declare_binding(&mut self, range: TextRange, ty: Type) {
+        if let Some(id) = self.definitions.get(&range).copied() {
+            self.types.insert(id, ty);
+            self.declared.insert(id);
+        }
+    }
+
+    /// The **signature** of the given function, as annotated. The return
+    /// type of an unannotated function is inferred later on.
+    fn signature(&mut self, def: &ast::DefStmt) -> Signature {
+        // Annotations are only parsed once, so their errors are reported
+        // right away.
+        let reporting = std::mem::replace(&mut self.reporting, true);
+
+        let mut keyword_only = false;
+        let mut parameters = Vec::new();
+        for parameter in def.parameters().iter().flat_map(|it| it.parameters()) {
+            let kind = if parameter.starstar_token().is_some() {
+                ParameterKind::Kwargs
+            } else if parameter.star_token().is_some() {
+                keyword_only = true;
+                ParameterKind::Args
+            } else if keyword_only {
+                ParameterKind::KeywordOnly
+            } else {
+                ParameterKind::Normal
+            };
+            // A bare `*` only marks the following parameters as keyword-only.
+            let Some(name) = parameter.name() else {
+                continue;
+            };
+
+            parameters.push(Parameter {
+                name: name.text().to_string(),
+                kind,
+                ty: self.annotation(parameter.annotation()),
+                has_default: parameter.default().is_some(),
+            });
+        }
+        let return_type = self.annotation(def.return_type());
+
+        self.reporting = reporting;
+
+        Signature {
+            name: def
+                .name()
+                .map(|it| it.text().to_string())
+                .unwrap_or_default(),
+            parameters,
+            return_type,
+        }
+    }
+
+    /// The type of the given **annotation**, which is `Any` if missing.
+    fn annotation(&mut self, annotation: Option<ast::TypeAnnotation>) -> Type {
+        match annotation.and_then(|it| it.test()) {
+            Some(test) => self.annotation_type(&test),
+            None => Type::Any,
+        }
+    }
+
+    fn annotation_type(&mut self, test: &ast::Test) -> Type {
+        let range = significant_range(test.syntax());
+        match test {
+            // e.g. `int | None`
+            ast::Test::BinaryExpr(expr) if expr.op_kind() == Some(BinaryOp::BitOr) => {
+                let lhs = expr.lhs().map(|it| self.annotation_type(&it));
+                let rhs = expr.rhs().map(|it| self.annotation_type(&it));
+                Type::union([lhs.unwrap_or(Type::Any), rhs.unwrap_or(Type::Any)])
+            }
+            ast::Test::PrimaryExpr(expr) => {
+                let name = expr.operand().and_then(|it| it.identifier_token());
+                let suffixes: Vec<_> = expr.syntax().children().skip(1).collect();
+                let Some(name) = name else {
+                    return self.error(range, "Unsupported type annotation");
+                };
+
+                match suffixes.as_slice() {
+                    [] => self.named_type(name.text(), range),
+                    [suffix] => {
+                        match ast::SliceSuffix::cast(suffix.clone()).and_then(|it| it.index()) {
+                            Some(index) => {
+                                let arguments =
+                                    index.tests().map(|it| self.annotation_type(&it)).collect();
+                                self.generic_type(name.text(), arguments, range)
+                            }
+                            None => self.error(range, "Unsupported type annotation"),
+                        }
+                    }
+                    _ => self.error(range, "Unsupported type annotation"),
+                }
+            }
+            _ => self.error(range, "Unsupported type annotation"),
+        }
+    }
+
+    fn named_type(&mut self, name: &str, range: TextRange) -> Type {
+        match name {
+            "Any" => Type::Any,
+            "None" | "NoneType" => Type::None,
+            "bool" => Type::Bool,
+            "int" => Type::Int,
+            "float" => Type::Float,
+            "str" => Type::String,
+            "bytes" => Type::Bytes,
+            "list" => Type::List(Box::new(Type::Any)),
+            "dict" => Type::Dict(Box::new(Type::Any), Box::new(Type::Any)),
+            "tuple" => Type::Tuple(None),
+            "Callable" | "function" => Type::Function(Box::new(Signature {
+                name: name.to_string(),
+                parameters: vec![
+                    Parameter {
+                        name: "args".to_string(),
+                        kind: ParameterKind::Args,
+                        ty: Type::Any,
+                        has_default: false,
+                    },
+                    Parameter {
+                        name: "kwargs".to_string(),
+                        kind: ParameterKind::Kwargs,
+                        ty: Type::Any,
+                        has_default: false,
+                    },
+                ],
+                return_type: Type::Any,
+            })),
+            name => self.error(range, format!("Unknown type `{name}`")),
+        }
+    }
+
+    /// The **generic** type with the given name and type arguments, e.g.
+    /// `dict[str, int]`.
+    fn generic_type(&mut self, name: &str, mut arguments: Vec<Type>, range: TextRange) -> Type {
+        let expected = match name {
+            "list" | "Optional" => 1,
+            "dict" => 2,
+            "tuple" => return Type::Tuple(Some(arguments)),
+            "Union" => return Type::union(arguments),
+            name => return self.error(range, format!("Type `{name}` takes no type arguments")),
+        };
+        if arguments.len() != expected {
+            return self.error(
+                range,
+                format!(
+                    "Type `{name}` takes {expected} type argument(s), got {}",
+                    arguments.len()
+                ),
+            );
+        }
+
+        match name {
+            "list" => Type::List(Box::new(arguments.remove(0))),
+            "Optional" => Type::union([arguments.remove(0), Type::None]),
+            _ => {
+                let value = arguments.remove(1);
+                Type::Dict(Box::new(arguments.remove(0)), Box::new(value))
+            }
+        }
+    }
+
+    fn check_statements(&mut self, statements: impl Iterator<Item = ast::Statement>) {
+        for statement in statements {
+            self.check_statement(&statement);
+        }
+    }
+
+    fn check_suite(&mut self, suite: Option<ast::Suite>) {
+        if let Some(suite) = suite {
+            self.check_statements(suite.statements());
+        }
+    }
+
+    fn check_statement(&mut self, statement: &ast::Statement) {
+        match statement {
+            ast::Statement::DefStmt(def) => self.check_def(def),
+            ast::Statement::IfStmt(stmt) => {
+                self.infer_condition(stmt.condition());
+                self.check_suite(stmt.body());
+                for clause in stmt.elif_clauses() {
+                    self.infer_condition(clause.condition());
+                    self.check_suite(clause.body());
+                }
+                self.check_suite(stmt.else_clause().and_then(|it| it.body()));
+            }
+            ast::Statement::ForStmt(stmt) => {
+                let element = match stmt.iterable() {
+                    Some(iterable) => {
+                        let ty = self.infer_expression(&iterable);
+                        self.iterate(&ty, significant_range(iterable.syntax()))
+                    }
+                    None => Type::Any,
+                };
+                if let Some(variables) = stmt.loop_variables() {
+                    self.assign(variables.syntax(), element);
+                }
+                self.check_suite(stmt.body());
+            }
+            ast::Statement::SimpleStmt(stmt) => {
+                for small in stmt.small_stmts() {
+                    self.check_small_statement(&small);
+                }
+            }
+        }
+    }
+
+    fn check_small_statement(&mut self, statement: &ast::SmallStmt) {
+        match statement {
+            ast::SmallStmt::ReturnStmt(stmt) => {
+                let (ty, range) = match stmt.expression() {
+                    Some(expression) => (
+                        self.infer_expression(&expression),
+                        significant_range(expression.syntax()),
+                    ),
+                    None => (Type::None, significant_range(stmt.syntax())),
+                };
+                let Some(function) = self.functions.last_mut() else {
+                    return;
+                };
+                function.returns.push(ty.clone());
+
+                if let Some(expected) = &function.return_type {
+                    if !is_assignable(&ty, expected) {
+                        let message = format!(
+                            "Expected `{expected}` to be returned from `{}`, got `{ty}`",
+                            function.name
+                        );
+                        self.error(range, message);
+                    }
+                }
+            }
+            ast::SmallStmt::AssignStmt(stmt) => self.check_assignment(stmt),
+            ast::SmallStmt::ExprStmt(stmt) => {
+                if let Some(expression) = stmt.expression() {
+                    self.infer_expression(&expression);
+                }
+            }
+            ast::SmallStmt::BreakStmt(_)
+            | ast::SmallStmt::ContinueStmt(_)
+            | ast::SmallStmt::PassStmt(_)
+            | ast::SmallStmt::LoadStmt(_) => {}
+        }
+    }
+
+    fn check_def(&mut self, def: &ast::DefStmt) {
+        let Some(id) = def
+            .name()
+            .and_then(|it| self.definitions.get(&it.syntax().text_range()).copied())
+        else {
+            return;
+        };
+        let Some(Type::Function(signature)) = self.types.get(&id).cloned() else {
+            return;
+        };
+
+        // Default values are evaluated in the enclosing scope, when the
+        // function is defined. `None` is accepted for any type, as the
+        // parameter is then typically set within the function.
+        let parameters = def.parameters().into_iter().flat_map(|it| it.parameters());
+        for parameter in parameters {
+            let Some(default) = parameter.default() else {
+                continue;
+            };
+            let ty = self.infer_test(&default);
+            let expected = parameter.name().and_then(|name| {
+                signature
+                    .parameters
+                    .iter()
+                    .find(|it| it.name == name.text())
+            });
+            if let Some(expected) = expected {
+                if ty != Type::None && !is_assignable(&ty, &expected.ty) {
+                    let message = format!(
+                        "Expected `{}` for the default value of `{}`, got `{ty}`",
+                        expected.ty, expected.name
+                    );
+                    self.error(significant_range(default.syntax()), message);
+                }
+            }
+        }
+
+        self.functions.push(Function {
+            name: signature.name.clone(),
+            return_type: def.return_type().map(|_| signature.return_type.clone()),
+            returns: Vec::new(),
+        });
+        self.check_suite(def.body());
+        let function = self.functions.pop().unwrap();
+
+        // The return type of an unannotated function is the join of the
+        // types of the values it returns.
+        if !self.reporting && function.return_type.is_none() {
+            let return_type = match function.returns.split_first() {
+                Some((first, rest)) => rest.iter().fold(first.clone(), |it, ty| join(&it, ty)),
+                None => Type::None,
+            };
+            let signature = Signature {
+                return_type,
+                ..*signature
+            };
+            self.types.insert(id, Type::Function(Box::new(signature)));
+        }
+    }
+
+    fn check_assignment(&mut self, stmt: &ast::AssignStmt) {
+        let Some(lhs) = stmt.lhs() else {
+            return;
+        };
+        let rhs = match stmt.rhs() {
+            Some(rhs) => self.infer_expression(&rhs),
+            None => Type::Any,
+        };
+
+        let op = if stmt.eq_token().is_some() {
+            return self.assign(lhs.syntax(), rhs);
+        } else if stmt.pluseq_token().is_some() {
+            BinaryOp::Add
+        } else if stmt.minuseq_token().is_some() {
+            BinaryOp::Sub
+        } else if stmt.stareq_token().is_some() {
+            BinaryOp::Mul
+        } else if stmt.slasheq_token().is_some() {
+            BinaryOp::Div
+        } else if stmt.slashslasheq_token().is_some() {
+            BinaryOp::FloorDiv
+        } else if stmt.percenteq_token().is_some() {
+            BinaryOp::Rem
+        } else if stmt.ampeq_token().is_some() {
+            BinaryOp::BitAnd
+        } else if stmt.pipeeq_token().is_some() {
+            BinaryOp::BitOr
+        } else if stmt.careteq_token().is_some() {
+            BinaryOp::BitXor
+        } else if stmt.shl_eq_token().is_some() {
+            BinaryOp::Shl
+        } else if stmt.shr_eq_token().is_some() {
+            BinaryOp::Shr
+        } else {
+            return;
+        };
+
+        let range = significant_range(stmt.syntax());
+        let current = self.infer_expression(&lhs);
+        let ty = match (op, &current) {
+            // `x += y` extends a list with any iterable.
+            (BinaryOp::Add, Type::List(element)) => {
+                let items = self.iterate(&rhs, range);
+                if !is_assignable(&items, element) {
+                    let message = format!("Cannot extend `{current}` with `{rhs}`");
+                    self.error(range, message);
+                }
+                current.clone()
+            }
+            _ => self.binary(op, &current, &rhs, range),
+        };
+        self.assign(lhs.syntax(), ty);
+    }
+
+    /// **Assigns** a value of the given type to the given target, unpacking
+    /// it for tuples and lists of targets (e.g. `a, [b, c] = 1, [2, 3]`).
+    fn assign(&mut self, target: &SyntaxNode, ty: Type) {
+        let targets = match target.kind() {
+            SyntaxKind::EXPRESSION | SyntaxKind::LOOP_VARIABLES => {
+                let targets: Vec<_> = target
+                    .children()
+                    .filter(|it| ast::Test::can_cast(it.kind()))
+                    .collect();
+                if targets.len() == 1 && !has_comma(target) {
+                    return self.assign(&targets[0], ty);
+                }
+                targets
+            }
+            SyntaxKind::TEST => match target.first_child() {
+                Some(child) => return self.assign(&child, ty),
+                None => return,
+            },
+            SyntaxKind::PRIMARY_EXPR if target.children().count() == 1 => {
+                let Some(operand) = target.first_child().and_then(ast::Operand::cast) else {
+                    return;
+                };
+                if let Some(name) = operand.identifier_token() {
+                    return self.bind(&name, ty);
+                } else if let Some(list) = operand.list_expr() {
+                    list.expression()
+                        .map(|it| it.tests().map(|it| it.syntax().clone()).collect())
+                        .unwrap_or_default()
+                } else {
+                    match operand.expression() {
+                        Some(expression) if has_comma(operand.syntax()) => {
+                            expression.tests().map(|it| it.syntax().clone()).collect()
+                        }
+                        Some(expression) => return self.assign(expression.syntax(), ty),
+                        None => Vec::new(),
+                    }
+                }
+            }
+            SyntaxKind::PRIMARY_EXPR => return self.assign_element(target, ty),
+            _ => return,
+        };
+
+        let range = significant_range(target);
+        let types = match &ty {
+            Type::Tuple(Some(elements)) if elements.len() == targets.len() => elements.clone(),
+            Type::Tuple(Some(elements)) => {
+                let message = format!(
+                    "Cannot unpack {} values into {} targets",
+                    elements.len(),
+                    targets.len()
+                );
+                self.error(range, message);
+                vec![Type::Any; targets.len()]
+            }
+            ty => vec![self.iterate(ty, range); targets.len()],
+        };
+        for (target, ty) in targets.iter().zip(types) {
+            self.assign(target, ty);
+        }
+    }
+
+    /// Assigns a value of the given type to an **element** of a list or a
+    /// dict (e.g. `x[0] = 1`), or to a field (e.g. `x.y = 1`).
+    fn assign_element(&mut self, target: &SyntaxNode, ty: Type) {
+        let Some(expr) = ast::PrimaryExpr::cast(target.clone()) else {
+            return;
+        };
+        let suffixes = expr.syntax().children().count() - 1;
+        let container = self.infer_primary(&expr, suffixes - 1);
+        let Some(index) = target
+            .last_child()
+            .and_then(ast::SliceSuffix::cast)
+            .and_then(|it| it.index())
+        else {
+            return;
+        };
+        let key = self.infer_expression(&index);
+
+        let range = significant_range(target);
+        let element = match &container {
+            Type::List(element) => {
+                self.expect_index(&key, significant_range(index.syntax()));
+                (**element).clone()
+            }
+            Type::Dict(expected, value) => {
+                if !is_assignable(&key, expected) {
+                    let message = format!("Expected a key of type `{expected}`, got `{key}`");
+                    self.error(significant_range(index.syntax()), message);
+                }
+                (**value).clone()
+            }
+            Type::Any | Type::Union(_) => return,
+            container => {
+                let message = format!("`{container}` doesn't support assigning to its elements");
+                self.error(range, message);
+                return;
+            }
+        };
+        if !is_assignable(&ty, &element) {
+            let message = format!("Cannot assign `{ty}` to an element of `{container}`");
+            self.error(range, message);
+        }
+    }
+
+    /// **Binds** a value of the given type to the given name, which must be
+    /// compatible with the declared type of the binding (if any).
+    fn bind(&mut self, name: &SyntaxToken, ty: Type) {
+        let Some(id) = self.definitions.get(&name.text_range()).copied() else {
+            return;
+        };
+
+        if self.declared.contains(&id) {
+            let declared = self.types[&id].clone();
+            if !is_assignable(&ty, &declared) {
+                let message = format!(
+                    "Cannot assign `{ty}` to `{}`, of type `{declared}`",
+                    name.text()
+                );
+                self.error(name.text_range(), message);
+            }
+        } else if !self.reporting {
+            let ty = match self.types.get(&id) {
+                Some(previous) => join(previous, &ty),
+                None => ty,
+            };
+            self.types.insert(id, ty);
+        }
+    }
+
+    fn infer_condition(&mut self, condition: Option<ast::Test>) {
+        if let Some(condition) = condition {
+            self.infer_test(&condition);
+        }
+    }
+
+    /// Infers the type of the given expression, which is a **tuple** if it
+    /// has several elements or a trailing comma (e.g. `1, 2` or `1,`).
+    fn infer_expression(&mut self, expression: &ast::Expression) -> Type {
+        let tests: Vec<_> = expression.tests().collect();
+        if tests.len() == 1 && !has_comma(expression.syntax()) {
+            return self.infer_test(&tests[0]);
+        }
+
+        Type::Tuple(Some(tests.iter().map(|it| self.infer_test(it)).collect()))
+    }
+
+    fn infer_test(&mut self, test: &ast::Test) -> Type {
+        let range = significant_range(test.syntax());
+        match test {
+            ast::Test::IfExpr(expr) => {
+                self.infer_condition(expr.condition());
+                let then_branch = self.infer_optional(expr.then_branch());
+                let else_branch = self.infer_optional(expr.else_branch());
+                join(&then_branch, &else_branch)
+            }
+            ast::Test::PrimaryExpr(expr) => {
+                let suffixes = expr.syntax().children().count() - 1;
+                self.infer_primary(expr, suffixes)
+            }
+            ast::Test::UnaryExpr(expr) => {
+                let operand = self.infer_optional(expr.operand());
+                match expr.op_kind() {
+                    Some(op) => self.unary(op, &operand, range),
+                    None => Type::Any,
+                }
+            }
+            ast::Test::BinaryExpr(expr) => {
+                let lhs = self.infer_optional(expr.lhs());
+                let rhs = self.infer_optional(expr.rhs());
+                match expr.op_kind() {
+                    Some(op) => self.binary(op, &lhs, &rhs, range),
+                    None => Type::Any,
+                }
+            }
+            ast::Test::LambdaExpr(expr) => {
+                let mut parameters = Vec::new();
+                for parameter in expr.parameters().iter().flat_map(|it| it.parameters()) {
+                    if let Some(default) = parameter.default() {
+                        self.infer_test(&default);
+                    }
+                    let Some(name) = parameter.name() else {
+                        continue;
+                    };
+                    let kind = if parameter.starstar_token().is_some() {
+                        ParameterKind::Kwargs
+                    } else if parameter.star_token().is_some() {
+                        ParameterKind::Args
+                    } else {
+                        ParameterKind::Normal
+                    };
+                    parameters.push(Parameter {
+                        name: name.text().to_string(),
+                        kind,
+                        ty: Type::Any,
+                        has_default: parameter.default().is_some(),
+                    });
+                }
+
+                Type::Function(Box::new(Signature {
+                    name: "lambda".to_string(),
+                    parameters,
+                    return_type: self.infer_optional(expr.body()),
+                }))
+            }
+        }
+    }
+
+    fn infer_optional(&mut self, test: Option<ast::Test>) -> Type {
+        match test {
+            Some(test) => self.infer_test(&test),
+            None => Type::Any,
+        }
+    }
+
+    /// Infers the type of the operand of the given primary expression,
+    /// followed by the given number of its **suffixes** (e.g. `.foo`, `(1)`
+    /// or `[0]`).
+    fn infer_primary(&mut self, expr: &ast::PrimaryExpr, suffixes: usize) -> Type {
+        let mut ty = match expr.operand() {
+            Some(operand) => self.infer_operand(&operand),
+            None => Type::Any,
+        };
+        for suffix in expr.syntax().children().skip(1).take(suffixes) {
+            let range = TextRange::new(
+                significant_range(expr.syntax()).start(),
+                suffix.text_range().end(),
+            );
+            ty = self.infer_suffix(ty, &suffix, range);
+        }
+
+        ty
+    }
+
+    fn infer_suffix(&mut self, ty: Type, suffix: &SyntaxNode, range: TextRange) -> Type {
+        if let Some(dot) = ast::DotSuffix::cast(suffix.clone()) {
+            let Some(name) = dot.name() else {
+                return Type::Any;
+            };
+            return match ty {
+                Type::Any | Type::Union(_) => Type::Any,
+                ty => match builtins::method(&ty, name.text()) {
+                    Some(method) => Type::Method(Box::new(ty), method),
+                    None => {
+                        let message = format!("`{ty}` has no attribute `{}`", name.text());
+                        self.error(range, message)
+                    }
+                },
+            };
+        }
+
+        if let Some(call) = ast::CallSuffix::cast(suffix.clone()) {
+            let arguments = self.infer_arguments(call.arguments());
+            return self.call(&ty, &arguments, range);
+        }
+
+        let Some(slice) = ast::SliceSuffix::cast(suffix.clone()) else {
+            return Type::Any;
+        };
+        if let Some(index) = slice.index() {
+            let key = self.infer_expression(&index);
+            return self.index(&ty, &key, significant_range(index.syntax()), range);
+        }
+
+        let start = slice
+            .start()
+            .map(|it| (self.infer_expression(&it), it.syntax().clone()));
+        let end = slice
+            .end()
+            .map(|it| (self.infer_test(&it), it.syntax().clone()));
+        let step = slice
+            .step()
+            .map(|it| (self.infer_test(&it), it.syntax().clone()));
+        for (bound, node) in [start, end, step].into_iter().flatten() {
+            if !is_assignable(&bound, &Type::union([Type::Int, Type::None])) {
+                let message = format!("Slice indices must be `int`, not `{bound}`");
+                self.error(significant_range(&node), message);
+            }
+        }
+
+        match ty {
+            Type::List(_) | Type::String | Type::Bytes | Type::Any | Type::Union(_) => ty,
+            Type::Tuple(_) => Type::Tuple(None),
+            ty => self.error(range, format!("`{ty}` is not sliceable")),
+        }
+    }
+
+    /// The type of the element of a value of the given type at the given
+    /// **index** (or key).
+    fn index(&mut self, ty: &Type, key: &Type, key_range: TextRange, range: TextRange) -> Type {
+        match ty {
+            Type::List(element) => {
+                self.expect_index(key, key_range);
+                (**element).clone()
+            }
+            Type::Tuple(elements) => {
+                self.expect_index(key, key_range);
+                match elements {
+                    Some(elements) => builtins::join_all(elements),
+                    None => Type::Any,
+                }
+            }
+            Type::String => {
+                self.expect_index(key, key_range);
+                Type::String
+            }
+            Type::Bytes => {
+                self.expect_index(key, key_range);
+                Type::Int
+            }
+            Type::Dict(expected, value) => {
+                if !is_assignable(key, expected) {
+                    let message = format!("Expected a key of type `{expected}`, got `{key}`");
+                    self.error(key_range, message);
+                }
+                (**value).clone()
+            }
+            Type::Any | Type::Union(_) => Type::Any,
+            ty => self.error(range, format!("`{ty}` is not indexable")),
+        }
+    }
+
+    fn expect_index(&mut self, key: &Type, range: TextRange) {
+        if !is_assignable(key, &Type::Int) {
+            self.error(range, format!("Indices must be `int`, not `{key}`"));
+        }
+    }
+
+    fn infer_arguments(&mut self, arguments: Option<ast::Arguments>) -> Arguments {
+        let mut result = Arguments::default();
+        for argument in arguments.iter().flat_map(|it| it.arguments()) {
+            let ty = self.infer_optional(argument.value());
+            let range = significant_range(argument.syntax());
+
+            if argument.star_token().is_some() {
+                self.iterate(&ty, range);
+                result.unpacked = true;
+            } else if argument.starstar_token().is_some() {
+                if !is_assignable(
+                    &ty,
+                    &Type::Dict(Box::new(Type::String), Box::new(Type::Any)),
+                ) {
+                    let message = format!("Argument after ** must be a dict, not `{ty}`");
+                    self.error(range, message);
+                }
+                result.unpacked = true;
+            } else if let Some(name) = argument.name() {
+                result.named.push((name.text().to_string(), ty, range));
+            } else {
+                result.positional.push((ty, range));
+            }
+        }
+
+        result
+    }
+
+    /// The type returned by **calling** a value of the given type.
+    fn call(&mut self, ty: &Type, arguments: &Arguments, range: TextRange) -> Type {
+        let positional: Vec<_> = arguments
+            .positional
+            .iter()
+            .map(|(ty, _)| ty.clone())
+            .collect();
+        let result = match ty {
+            Type::Any | Type::Union(_) => Ok(Type::Any),
+            Type::Function(signature) => {
+                self.check_arguments(signature, arguments, range);
+                Ok(signature.return_type.clone())
+            }
+            Type::Builtin(name) => builtins::call(name, &positional),
+            Type::Method(receiver, name) => builtins::call_method(receiver, name, &positional),
+            ty => Err(format!("`{ty}` is not callable")),
+        };
+
+        result.unwrap_or_else(|message| self.error(range, message))
+    }
+
+    /// Checks the given arguments against the parameters of the given
+    /// **signature**, like binding them when calling the function.
+    fn check_arguments(&mut self, signature: &Signature, arguments: &Arguments, range: TextRange) {
+        let name = &signature.name;
+        let mut bound = HashSet::new();
+
+        let normal: Vec<_> = signature
+            .parameters
+            .iter()
+            .filter(|it| it.kind == ParameterKind::Normal)
+            .collect();
+        let args = signature
+            .parameters
+            .iter()
+            .find(|it| it.kind == ParameterKind::Args);
+        for (i, (ty, range)) in arguments.positional.iter().enumerate() {
+            let parameter = match (normal.get(i), args) {
+                (Some(parameter), _) => {
+                    bound.insert(parameter.name.as_str());
+                    parameter
+                }
+                (None, Some(args)) => args,
+                (None, None) => {
+                    let message = format!(
+                        "`{name}` accepts at most {} positional argument(s) ({} given)",
+                        normal.len(),
+                        arguments.positional.len()
+                    );
+                    self.error(*range, message);
+                    break;
+                }
+            };
+            self.check_argument(name, parameter, ty, *range);
+        }
+
+        let kwargs = signature
+            .parameters
+            .iter()
+            .find(|it| it.kind == ParameterKind::Kwargs);
+        for (argument, ty, range) in &arguments.named {
+            let parameter = signature.parameters.iter().find(|it| {
+                it.name == *argument
+                    && matches!(it.kind, ParameterKind::Normal | ParameterKind::KeywordOnly)
+            });
+            let parameter = match (parameter, kwargs) {
+                (Some(parameter), _) => {
+                    if !bound.insert(parameter.name.as_str()) && !arguments.unpacked {
+                        let message =
+                            format!("`{name}` got multiple values for parameter `{argument}`");
+                        self.error(*range, message);
+                    }
+                    parameter
+                }
+                (None, Some(kwargs)) => kwargs,
+                (None, None) => {
+                    let message =
+                        format!("`{name}` got an unexpected keyword argument `{argument}`");
+                    self.error(*range, message);
+                    continue;
+                }
+            };
+            self.check_argument(name, parameter, ty, *range);
+        }
+
+        // With unpacked arguments, any parameter may have been passed.
+        if arguments.unpacked {
+            return;
+        }
+        let missing = signature.parameters.iter().find(|it| {
+            matches!(it.kind, ParameterKind::Normal | ParameterKind::KeywordOnly)
+                && !it.has_default
+                && !bound.contains(it.name.as_str())
+        });
+        if let Some(missing) = missing {
+            let message = format!("`{name}` is missing the argument `{}`", missing.name);
+            self.error(range, message);
+        }
+    }
+
+    fn check_argument(
+        &mut self,
+        function: &str,
+        parameter: &Parameter,
+        ty: &Type,
+        range: TextRange,
+    ) {
+        if !is_assignable(ty, &parameter.ty) {
+            let message = format!(
+                "Expected `{}` for argument `{}` of `{function}`, got `{ty}`",
+                parameter.ty, parameter.name
+            );
+            self.error(range, message);
+        }
+    }
+
+    fn infer_operand(&mut self, operand: &ast::Operand) -> Type {
+        if let Some(name) = operand.identifier_token() {
+            return self.name_type(&name);
+        }
+
+        if let Some(list) = operand.list_expr() {
+            let items: Vec<_> = list
+                .expression()
+                .iter()
+                .flat_map(|it| it.tests())
+                .map(|it| self.infer_test(&it))
+                .collect();
+            return Type::List(Box::new(builtins::join_all(&items)));
+        }
+
+        if let Some(dict) = operand.dict_expr() {
+            let (keys, values): (Vec<_>, Vec<_>) = dict
+                .entries()
+                .iter()
+                .flat_map(|it| it.entries())
+                .map(|it| self.infer_entry(&it))
+                .unzip();
+            return Type::Dict(
+                Box::new(builtins::join_all(&keys)),
+                Box::new(builtins::join_all(&values)),
+            );
+        }
+
+        if let Some(comp) = operand.list_comp() {
+            self.infer_clauses(comp.comp_clauses());
+            return Type::List(Box::new(self.infer_optional(comp.element())));
+        }
+
+        if let Some(comp) = operand.dict_comp() {
+            self.infer_clauses(comp.comp_clauses());
+            let (key, value) = match comp.entry() {
+                Some(entry) => self.infer_entry(&entry),
+                None => (Type::Any, Type::Any),
+            };
+            return Type::Dict(Box::new(key), Box::new(value));
+        }
+
+        if operand.l_paren_token().is_some() {
+            return match operand.expression() {
+                // A trailing comma belongs to the parentheses, e.g. `(1,)`.
+                Some(expression) if has_comma(operand.syntax()) => Type::Tuple(Some(
+                    expression.tests().map(|it| self.infer_test(&it)).collect(),
+                )),
+                Some(expression) => self.infer_expression(&expression),
+                None => Type::Tuple(Some(Vec::new())),
+            };
+        }
+
+        let literal = operand
+            .syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .map(|it| it.kind())
+            .find(|it| {
+                matches!(
+                    it,
+                    SyntaxKind::INT | SyntaxKind::FLOAT | SyntaxKind::STRING | SyntaxKind::BYTES
+                )
+            });
+        match literal {
+            Some(SyntaxKind::INT) => Type::Int,
+            Some(SyntaxKind::FLOAT) => Type::Float,
+            Some(SyntaxKind::STRING) => Type::String,
+            Some(SyntaxKind::BYTES) => Type::Bytes,
+            _ => Type::Any,
+        }
+    }
+
+    fn infer_entry(&mut self, entry: &ast::Entry) -> (Type, Type) {
+        (
+            self.infer_optional(entry.key()),
+            self.infer_optional(entry.value()),
+        )
+    }
+
+    /// Infers the types of the loop variables of the **clauses** of a
+    /// comprehension, checking their conditions.
+    fn infer_clauses(&mut self, clauses: impl Iterator<Item = ast::CompClause>) {
+        for clause in clauses {
+            if clause.for_token().is_none() {
+                self.infer_condition(clause.condition());
+                continue;
+            }
+
+            let element = match clause.iterable() {
+                Some(iterable) => {
+                    let ty = self.infer_test(&iterable);
+                    self.iterate(&ty, significant_range(iterable.syntax()))
+                }
+                None => Type::Any,
+            };
+            if let Some(variables) = clause.loop_variables() {
+                self.assign(variables.syntax(), element);
+            }
+        }
+    }
+
+    /// The type of the value the given identifier refers to.
+    fn name_type(&self, name: &SyntaxToken) -> Type {
+        let range = name.text_range();
+        let resolved = match self.references.get(&range) {
+            Some(resolved) => *resolved,
+            // The target of an augmented assignment (e.g. `x` in `x += 1`)
+            // is a definition rather than a reference.
+            None => match self.definitions.get(&range) {
+                Some(id) => Resolved::Binding(*id),
+                None => Resolved::Undefined,
+            },
+        };
+
+        match resolved {
+            Resolved::Binding(id) => self.types.get(&id).cloned().unwrap_or(Type::Any),
+            Resolved::Builtin => builtins::builtin(name.text()),
+            Resolved::Predeclared | Resolved::Undefined => {
+                self.environment.lookup(name.text()).unwrap_or(Type::Any)
+            }
+        }
+    }
+
+    /// The type of the elements of a value of the given type, which must be
+    /// **iterable**.
+    fn iterate(&mut self, ty: &Type, range: TextRange) -> Type {
+        match builtins::element_type(ty) {
+            Some(element) => element,
+            None => self.error(range, format!("`{ty}` is not iterable")),
+        }
+    }
+
+    fn unary(&mut self, op: UnaryOp, operand: &Type, range: TextRange) -> Type {
+        match (op, operand) {
+            (UnaryOp::Not, _) => Type::Bool,
+            (_, Type::Any | Type::Union(_)) => Type::Any,
+            (UnaryOp::Plus | UnaryOp::Minus, Type::Int | Type::Float)
+            | (UnaryOp::Invert, Type::Int) => operand.clone(),
+            (op, operand) => {
+                let op = match op {
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Invert => "~",
+                    UnaryOp::Not => "not",
+                };
+                self.error(
+                    range,
+                    format!("Unsupported operand type for `{op}`: `{operand}`"),
+                )
+            }
+        }
+    }
+
+    fn binary(&mut self, op: BinaryOp, lhs: &Type, rhs: &Type, range: TextRange) -> Type {
+        use Type::{Any, Bool, Bytes, Dict, Float, Int, List, Tuple, Union};
+
+        let is_unknown = |ty: &Type| matches!(ty, Any | Union(_));
+        let is_number = |ty: &Type| matches!(ty, Int | Float);
+        let number = || {
+            if lhs == &Int && rhs == &Int {
+                Int
+            } else {
+                Float
+            }
+        };
+
+        let ty = match (op, lhs, rhs) {
+            // `and` and `or` evaluate to either operand.
+            (BinaryOp::And | BinaryOp::Or, _, _) => Some(join(lhs, rhs)),
+            (BinaryOp::Eq | BinaryOp::NotEq, _, _) => Some(Bool),
+            (BinaryOp::In | BinaryOp::NotIn, _, rhs) => match rhs {
+                List(_) | Tuple(_) | Dict(..) | Any | Union(_) => Some(Bool),
+                Type::String if matches!(lhs, Type::String | Any | Union(_)) => Some(Bool),
+                Bytes if matches!(lhs, Bytes | Int | Any | Union(_)) => Some(Bool),
+                _ => None,
+            },
+            (op, lhs, rhs) if op.is_comparison() => match (lhs, rhs) {
+                (lhs, rhs) if is_unknown(lhs) || is_unknown(rhs) => Some(Bool),
+                (lhs, rhs) if is_number(lhs) && is_number(rhs) => Some(Bool),
+                (Type::String, Type::String)
+                | (Bytes, Bytes)
+                | (Bool, Bool)
+                | (List(_), List(_))
+                | (Tuple(_), Tuple(_)) => Some(Bool),
+                _ => None,
+            },
+            (_, lhs, rhs) if is_unknown(lhs) || is_unknown(rhs) => Some(Any),
+            (BinaryOp::Add, lhs, rhs) if is_number(lhs) && is_number(rhs) => Some(number()),
+            (BinaryOp::Add, Type::String, Type::String) => Some(Type::String),
+            (BinaryOp::Add, Bytes, Bytes) => Some(Bytes),
+            (BinaryOp::Add, List(a), List(b)) => Some(List(Box::new(join(a, b)))),
+            (BinaryOp::Add, Tuple(Some(a)), Tuple(Some(b))) => {
+                Some(Tuple(Some(a.iter().chain(b).cloned().collect())))
+            }
+            (BinaryOp::Add, Tuple(_), Tuple(_)) => Some(Tuple(None)),
+            (BinaryOp::Sub | BinaryOp::Mul | BinaryOp::FloorDiv, lhs, rhs)
+                if is_number(lhs) && is_number(rhs) =>
+            {
+                Some(number())
+            }
+            (BinaryOp::Mul, Type::String, Int) | (BinaryOp::Mul, Int, Type::String) => {
+                Some(Type::String)
+            }
+            (BinaryOp::Mul, Bytes, Int) | (BinaryOp::Mul, Int, Bytes) => Some(Bytes),
+            (BinaryOp::Mul, List(element), Int) | (BinaryOp::Mul, Int, List(element)) => {
+                Some(List(element.clone()))
+            }
+            (BinaryOp::Mul, Tuple(_), Int) | (BinaryOp::Mul, Int, Tuple(_)) => Some(Tuple(None)),
+            (BinaryOp::Div, lhs, rhs) if is_number(lhs) && is_number(rhs) => Some(Float),
+            (BinaryOp::Rem, lhs, rhs) if is_number(lhs) && is_number(rhs) => Some(number()),
+            // `%` formats strings (and bytes).
+            (BinaryOp::Rem, Type::String, _) => Some(Type::String),
+            (BinaryOp::Rem, Bytes, _) => Some(Bytes),
+            (BinaryOp::BitOr, Dict(a_key, a_value), Dict(b_key, b_value)) => Some(Dict(
+                Box::new(join(a_key, b_key)),
+                Box::new(join(a_value, b_value)),
+            )),
+            (
+                BinaryOp::BitOr
+                | BinaryOp::BitXor
+                | BinaryOp::BitAnd
+                | BinaryOp::Shl
+                | BinaryOp::Shr,
+                Int,
+                Int,
+            ) => Some(Int),
+            _ => None,
+        };
+
+        ty.unwrap_or_else(|| {
+            let message = format!(
+                "Unsupported operand types for `{}`: `{lhs}` and `{rhs}`",
+                symbol(op)
+            );
+            self.error(range, message)
+        })
+    }
+
+    /// Reports an **error** (in the second pass), returning `Any` as the type
+    /// of the erroneous expression.
+    fn error(&mut self, range: TextRange, message: impl Into<String>) -> Type {
+        if self.reporting {
+            self.errors.push(TypeError {
+                message: message.into(),
+                range,
+            });
+        }
+
+        Type::Any
+    }
+}
+
+/// The names of the parameters of the given function, in the order of its
+/// signature.
+fn parameter_names(def: &ast::DefStmt) -> Vec<SyntaxToken> {
+    def.parameters()
+        .iter()
+        .flat_map(|it| it.parameters())
+        .filter_map(|it| it.name())
+        .map(|it| it.syntax().clone())
+        .collect()
+}
+
+/// Whether the given node has a **comma** of its own, e.g. `1,` (a tuple)
+/// as opposed to `1`.
+fn has_comma(node: &SyntaxNode) -> bool {
+    node.children_with_tokens()
+        .any(|it| it.kind() == SyntaxKind::COMMA)
+}
+
+fn symbol(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Or => "or",
+        BinaryOp::And => "and",
+        BinaryOp::Eq => "==",
+        BinaryOp::NotEq => "!=",
+        BinaryOp::Less => "<",
+        BinaryOp::Greater => ">",
+        BinaryOp::LessEq => "<=",
+        BinaryOp::GreaterEq => ">=",
+        BinaryOp::In => "in",
+        BinaryOp::NotIn => "not in",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::Shl => "<<",
+        BinaryOp::Shr => ">>",
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::FloorDiv => "//",
+        BinaryOp::Rem => "%",
+    }
+}
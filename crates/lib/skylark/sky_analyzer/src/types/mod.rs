@@ -0,0 +1,305 @@
+//! A **gradual type checker** for Starlark.
+//!
+//! Types come from the **annotations** of functions, e.g.
+//!
+//! ```text
+//! def f(x: str, y: list[int] = []) -> dict[str, int]:
+//! ```
+//!
+//! and are otherwise **inferred**, from literals, comprehensions, operators
+//! and the [builtins](crate::builtins). Anything the checker can't tell
+//! (e.g. an unannotated parameter) has type [`Type::Any`], which is
+//! compatible with every other type, so unannotated code is never reported.
+//!
+//! Each binding has a single type: the join of the types assigned to it,
+//! which is `Any` if they differ. The checker runs over a file twice: first
+//! to infer the types of the bindings (including the return types of
+//! unannotated functions), then to report the mismatches, e.g. `"a" + 1`,
+//! calling an `int` or passing a `str` to a parameter annotated `int`.
+//!
+//! ```
+//! use sky_analyzer::types::{check, Globals};
+//! use sky_syntax::File;
+//!
+//! let file = File::parse("def f(x: int) -> str:\n    return x + 1\n").tree();
+//! let check = check(&file, &Globals::default());
+//!
+//! assert_eq!(
+//!     check.errors()[0].message,
+//!     "Expected `str` to be returned from `f`, got `int`"
+//! );
+//! ```
+
+mod builtins;
+mod check;
+
+use crate::resolve::{BindingId, Resolution};
+use rowan::TextRange;
+use sky_syntax::File;
+use std::{collections::HashMap, fmt};
+use thiserror::Error;
+
+/// A **type** of Starlark values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// Any type, i.e. a type the checker doesn't know.
+    Any,
+    None,
+    Bool,
+    Int,
+    Float,
+    String,
+    Bytes,
+    List(Box<Type>),
+    Dict(Box<Type>, Box<Type>),
+    /// A tuple of the given element types, or of unknown length (`None`).
+    Tuple(Option<Vec<Type>>),
+    /// Any of the given types, e.g. `Optional[int]`.
+    Union(Vec<Type>),
+    Function(Box<Signature>),
+    /// A builtin function, e.g. `len`, whose result depends on its arguments.
+    Builtin(&'static str),
+    /// A method of a value of the given type, e.g. `"a".upper`.
+    Method(Box<Type>, &'static str),
+}
+
+impl Type {
+    /// The **union** of the given types, flattening nested unions and
+    /// dropping duplicates. It's `Any` if any of the types is.
+    pub fn union(types: impl IntoIterator<Item = Type>) -> Type {
+        let mut members: Vec<Type> = Vec::new();
+        for ty in types {
+            let flattened = match ty {
+                Type::Any => return Type::Any,
+                Type::Union(types) => types,
+                ty => vec![ty],
+            };
+            for ty in flattened {
+                if !members.contains(&ty) {
+                    members.push(ty);
+                }
+            }
+        }
+
+        match members.len() {
+            1 => members.remove(0),
+            _ => Type::Union(members),
+        }
+    }
+
+    /// Whether values of this type are **callable**.
+    pub fn is_callable(&self) -> bool {
+        matches!(
+            self,
+            Type::Any | Type::Function(_) | Type::Builtin(_) | Type::Method(..)
+        )
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Any => write!(f, "Any"),
+            Type::None => write!(f, "None"),
+            Type::Bool => write!(f, "bool"),
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::String => write!(f, "str"),
+            Type::Bytes => write!(f, "bytes"),
+            Type::List(element) => write!(f, "list[{element}]"),
+            Type::Dict(key, value) => write!(f, "dict[{key}, {value}]"),
+            Type::Tuple(None) => write!(f, "tuple"),
+            Type::Tuple(Some(elements)) if elements.is_empty() => write!(f, "tuple[()]"),
+            Type::Tuple(Some(elements)) => {
+                write!(f, "tuple[")?;
+                write_separated(f, elements, ", ")?;
+                write!(f, "]")
+            }
+            Type::Union(types) => write_separated(f, types, " | "),
+            Type::Function(_) => write!(f, "function"),
+            Type::Builtin(_) | Type::Method(..) => write!(f, "builtin_function_or_method"),
+        }
+    }
+}
+
+fn write_separated(f: &mut fmt::Formatter<'_>, types: &[Type], separator: &str) -> fmt::Result {
+    for (i, ty) in types.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{separator}")?;
+        }
+        write!(f, "{ty}")?;
+    }
+
+    Ok(())
+}
+
+/// The **signature** of a function, i.e. the types of its parameters and of
+/// the value it returns.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Type,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParameterKind {
+    Normal,
+    /// A parameter following `*` or `*args`, which can only be passed by
+    /// name.
+    KeywordOnly,
+    /// `*args`, holding the extra positional arguments.
+    Args,
+    /// `**kwargs`, holding the extra named arguments.
+    Kwargs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: ParameterKind,
+    /// The type of the argument(s) passed to the parameter, e.g. `int` for
+    /// `*args: int`.
+    pub ty: Type,
+    pub has_default: bool,
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "def {}(", self.name)?;
+        let mut keyword_only = false;
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match parameter.kind {
+                ParameterKind::KeywordOnly if !keyword_only => write!(f, "*, ")?,
+                ParameterKind::Args => write!(f, "*")?,
+                ParameterKind::Kwargs => write!(f, "**")?,
+                _ => {}
+            }
+            keyword_only |= matches!(
+                parameter.kind,
+                ParameterKind::Args | ParameterKind::KeywordOnly
+            );
+
+            write!(f, "{}", parameter.name)?;
+            if parameter.ty != Type::Any {
+                write!(f, ": {}", parameter.ty)?;
+            }
+            if parameter.has_default {
+                write!(f, " = ...")?;
+            }
+        }
+
+        write!(f, ") -> {}", self.return_type)
+    }
+}
+
+/// A **type error**, i.e. a mismatch between the types of values and the way
+/// they're used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+#[error("{message}")]
+pub struct TypeError {
+    pub message: String,
+    pub range: TextRange,
+}
+
+/// The **environment** of a file, providing the types of the names it uses
+/// without binding them (e.g. the rules predeclared in a `BUCK` file, or the
+/// globals of the other parts of a module checked separately).
+pub trait Environment {
+    /// The type of the given name, which isn't bound in the file.
+    fn lookup(&self, name: &str) -> Option<Type>;
+
+    /// The type of the given symbol loaded from the module with the given
+    /// label, which is `Any` by default.
+    fn load(&self, _module: &str, _symbol: &str) -> Option<Type> {
+        None
+    }
+}
+
+/// An [`Environment`] with the given **globals**.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Globals(pub HashMap<String, Type>);
+
+impl Environment for Globals {
+    fn lookup(&self, name: &str) -> Option<Type> {
+        self.0.get(name).cloned()
+    }
+}
+
+/// The result of **type checking** a file.
+#[derive(Debug, Clone)]
+pub struct TypeCheck {
+    resolution: Resolution,
+    types: HashMap<BindingId, Type>,
+    errors: Vec<TypeError>,
+}
+
+impl TypeCheck {
+    /// The name resolution the types were checked against.
+    pub fn resolution(&self) -> &Resolution {
+        &self.resolution
+    }
+
+    /// The type of the given binding.
+    pub fn binding_type(&self, id: BindingId) -> &Type {
+        self.types.get(&id).unwrap_or(&Type::Any)
+    }
+
+    /// The type of the **global** with the given name, if bound.
+    pub fn global(&self, name: &str) -> Option<&Type> {
+        let id = self.resolution.lookup(self.resolution.module(), name)?;
+        Some(self.binding_type(id))
+    }
+
+    /// The errors, in the order they appear in the file.
+    pub fn errors(&self) -> &[TypeError] {
+        &self.errors
+    }
+}
+
+/// **Type checks** the given file, in the given environment.
+pub fn check(file: &File, environment: &dyn Environment) -> TypeCheck {
+    check::Checker::new(file, environment).run()
+}
+
+/// Whether a value of type `value` can be used where type `target` is
+/// expected (e.g. passed to a parameter annotated `target`).
+pub fn is_assignable(value: &Type, target: &Type) -> bool {
+    match (value, target) {
+        (Type::Any, _) | (_, Type::Any) => true,
+        (Type::Union(values), _) => values.iter().all(|it| is_assignable(it, target)),
+        (_, Type::Union(targets)) => targets.iter().any(|it| is_assignable(value, it)),
+        (Type::Int, Type::Float) => true,
+        (Type::List(value), Type::List(target)) => is_assignable(value, target),
+        (Type::Dict(key, value), Type::Dict(target_key, target_value)) => {
+            is_assignable(key, target_key) && is_assignable(value, target_value)
+        }
+        (Type::Tuple(_), Type::Tuple(None)) | (Type::Tuple(None), Type::Tuple(_)) => true,
+        (Type::Tuple(Some(values)), Type::Tuple(Some(targets))) => {
+            values.len() == targets.len()
+                && values
+                    .iter()
+                    .zip(targets)
+                    .all(|(value, target)| is_assignable(value, target))
+        }
+        (value, target) if value.is_callable() && target.is_callable() => true,
+        (value, target) => value == target,
+    }
+}
+
+/// The **join** of the given types, i.e. the type of a value which may be of
+/// either: the type itself if they're the same, and `Any` otherwise.
+pub fn join(a: &Type, b: &Type) -> Type {
+    match (a, b) {
+        (a, b) if a == b => a.clone(),
+        (Type::List(a), Type::List(b)) => Type::List(Box::new(join(a, b))),
+        (Type::Dict(a_key, a_value), Type::Dict(b_key, b_value)) => Type::Dict(
+            Box::new(join(a_key, b_key)),
+            Box::new(join(a_value, b_value)),
+        ),
+        _ => Type::Any,
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod highlight_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_analyzer::{
+        highlight::{
+            highlight, html, semantic_tokens, Highlight, HighlightKind, HighlightOptions,
+            Modifiers, TOKEN_MODIFIERS, TOKEN_TYPES,
+        },
+        resolve::{resolve, ResolveOptions},
+    };
+    use sky_syntax::File;
+
+    fn highlight_text(text: &str, deprecated: &[&str]) -> Vec<Highlight> {
+        let parse = File::parse(text);
+        assert_eq!(parse.errors(), &[], "{text}");
+        let options = ResolveOptions {
+            predeclared: ["native", "rust_library"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        };
+        let resolution = resolve(&parse.tree(), &options);
+        let options = HighlightOptions {
+            deprecated: deprecated.iter().map(ToString::to_string).collect(),
+        };
+
+        highlight(&parse.tree(), &resolution, &options)
+    }
+
+    /// Each highlighted token of the given file, e.g. `x: local definition`.
+    fn tokens(text: &str, deprecated: &[&str]) -> Vec<String> {
+        highlight_text(text, deprecated)
+            .into_iter()
+            .map(|highlight| {
+                let mut classes = vec![highlight.kind.name()];
+                classes.extend(highlight.modifiers.names());
+                format!("{}: {}", &text[highlight.range], classes.join(" "))
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::literals(
+        "x = [1, 2.5, 'a', b'b']  # Comment\n",
+        &[
+            "x: global definition",
+            "=: operator",
+            "1: number",
+            "2.5: number",
+            "'a': string",
+            "b'b': string",
+            "# Comment: comment",
+        ]
+    )]
+    #[case::keywords_and_builtins(
+        "if not True:\n    print(len([]))\n",
+        &["if: keyword", "not: keyword", "True: keyword", "print: builtinFunction", "len: builtinFunction"]
+    )]
+    #[case::scopes(
+        "x = 1\ndef f(a, b = x):\n    y = a\n    return y\n",
+        &[
+            "x: global definition",
+            "=: operator",
+            "1: number",
+            "def: keyword",
+            "f: global definition",
+            "a: parameter definition",
+            "b: parameter definition unused",
+            "=: operator",
+            "x: global",
+            "y: local definition",
+            "=: operator",
+            "a: parameter",
+            "return: keyword",
+            "y: local",
+        ]
+    )]
+    #[case::unused(
+        "def f(_a):\n    [b for b in []]\n    c = 1\n",
+        &[
+            "def: keyword",
+            "f: global definition",
+            "_a: parameter definition",
+            "b: local",
+            "for: keyword",
+            "b: local definition",
+            "in: keyword",
+            "c: local definition unused",
+            "=: operator",
+            "1: number",
+        ]
+    )]
+    #[case::loads(
+        "load(':defs.bzl', 'a', b = 'c', 'd')\n\nx = a + b\n",
+        &[
+            "load: keyword",
+            "':defs.bzl': string",
+            "'a': loadedSymbol definition",
+            "b: loadedSymbol definition",
+            "=: operator",
+            "'c': string",
+            "'d': loadedSymbol definition unused",
+            "x: global definition",
+            "=: operator",
+            "a: loadedSymbol",
+            "+: operator",
+            "b: loadedSymbol",
+        ]
+    )]
+    #[case::rule_calls(
+        "rust_library(name = 'a', srcs = [])\nnative.genrule(name = 'b')\nnative.glob([])\n",
+        &[
+            "rust_library: ruleCall",
+            "name: parameter",
+            "=: operator",
+            "'a': string",
+            "srcs: parameter",
+            "=: operator",
+            "native: global",
+            "genrule: ruleCall",
+            "name: parameter",
+            "=: operator",
+            "'b': string",
+            "native: global",
+        ]
+    )]
+    #[case::docstrings(
+        "'''Module.'''\n\ndef f():\n    'Function.'\n    return 'Not a docstring.'\n",
+        &[
+            "'''Module.''': docstring",
+            "def: keyword",
+            "f: global definition",
+            "'Function.': docstring",
+            "return: keyword",
+            "'Not a docstring.': string",
+        ]
+    )]
+    #[case::undefined("x = y\n", &["x: global definition", "=: operator"])]
+    fn test_highlight(#[case] text: &str, #[case] expected: &[&str]) {
+        assert_eq!(tokens(text, &[]), expected);
+    }
+
+    #[rstest]
+    #[case::docstring(
+        "def f():\n    \"\"\"Does nothing.\n\n    Deprecated: use `g` instead.\n    \"\"\"\n\nf()\n",
+        &["f: global definition deprecated", "f: global deprecated"]
+    )]
+    #[case::options(
+        "load(':defs.bzl', 'old')\n\nrust_library(name = 'a')\nold()\n",
+        &["'old': loadedSymbol definition deprecated", "rust_library: ruleCall deprecated", "old: loadedSymbol deprecated"]
+    )]
+    fn test_deprecated(#[case] text: &str, #[case] expected: &[&str]) {
+        let deprecated: Vec<_> = tokens(text, &["old", "rust_library"])
+            .into_iter()
+            .filter(|it| it.ends_with("deprecated"))
+            .collect();
+
+        assert_eq!(deprecated, expected);
+    }
+
+    #[test]
+    fn test_legend() {
+        assert_eq!(HighlightKind::Operator.name(), "operator");
+        assert_eq!(TOKEN_TYPES[HighlightKind::RuleCall as usize], "ruleCall");
+        assert_eq!(
+            (Modifiers::DEFINITION | Modifiers::DEPRECATED).bits(),
+            (1 << TOKEN_MODIFIERS
+                .iter()
+                .position(|it| *it == "definition")
+                .unwrap())
+                | (1 << TOKEN_MODIFIERS
+                    .iter()
+                    .position(|it| *it == "deprecated")
+                    .unwrap())
+        );
+    }
+
+    #[test]
+    fn test_semantic_tokens() {
+        let text = "s = 'é'\ndef f():\n    '''A\n    docstring.'''\n";
+        let highlights = highlight_text(text, &[]);
+        let data: Vec<_> = semantic_tokens(text, &highlights)
+            .chunks(5)
+            .map(|it| it.to_vec())
+            .collect();
+
+        let global = HighlightKind::Global as u32;
+        let definition = Modifiers::DEFINITION.bits();
+        assert_eq!(
+            data,
+            [
+                vec![0, 0, 1, global, definition],
+                vec![0, 2, 1, HighlightKind::Operator as u32, 0],
+                // The length of `é` is one UTF-16 code unit.
+                vec![0, 2, 3, HighlightKind::String as u32, 0],
+                vec![1, 0, 3, HighlightKind::Keyword as u32, 0],
+                vec![0, 4, 1, global, definition],
+                // The docstring spans two lines, so is split into two tokens.
+                vec![1, 4, 4, HighlightKind::Docstring as u32, 0],
+                vec![1, 0, 17, HighlightKind::Docstring as u32, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let text = "def f(x):\n    return x < 1 and \"&\"\n";
+        let highlights = highlight_text(text, &[]);
+
+        assert_eq!(
+            html(text, &highlights),
+            "<span class=\"keyword\">def</span> <span class=\"global definition\">f</span>(\
+             <span class=\"parameter definition\">x</span>):\n    \
+             <span class=\"keyword\">return</span> <span class=\"parameter\">x</span> \
+             <span class=\"operator\">&lt;</span> <span class=\"number\">1</span> \
+             <span class=\"keyword\">and</span> <span class=\"string\">&quot;&amp;&quot;</span>\n"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod labels_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_analyzer::labels::{compare, Label, LabelError};
+    use std::cmp::Ordering;
+
+    #[rstest]
+    #[case::local_before_absolute(":b", "//a", Ordering::Less)]
+    #[case::absolute_before_external("//b", "@a//a", Ordering::Less)]
+    #[case::other_first("a.rs", ":a", Ordering::Less)]
+    #[case::package_before_name("//a:z", "//a/b:a", Ordering::Less)]
+    #[case::names(":b", ":a", Ordering::Greater)]
+    fn test_compare(#[case] a: &str, #[case] b: &str, #[case] expected: Ordering) {
+        assert_eq!(compare(a, b), expected);
+    }
+
+    fn label(repository: Option<&str>, package: &str, name: &str) -> Label {
+        Label {
+            repository: repository.map(ToString::to_string),
+            package: package.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::absolute("//foo/bar:baz.bzl", None, label(None, "foo/bar", "baz.bzl"))]
+    #[case::root("//:defs.bzl", None, label(None, "", "defs.bzl"))]
+    #[case::external("@repo//foo:baz.bzl", None, label(Some("repo"), "foo", "baz.bzl"))]
+    #[case::relative(
+        ":lib/util.bzl",
+        Some("current"),
+        label(Some("current"), "pkg", "lib/util.bzl")
+    )]
+    #[case::absolute_in_repository(
+        "//foo:baz.bzl",
+        Some("current"),
+        label(Some("current"), "foo", "baz.bzl")
+    )]
+    fn test_parse(#[case] text: &str, #[case] repository: Option<&str>, #[case] expected: Label) {
+        let current = label(repository, "pkg", "BUCK");
+        assert_eq!(Label::parse(text, Some(&current)).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::no_name("//foo")]
+    #[case::empty_name("//foo:")]
+    #[case::parent("//foo/..:baz.bzl")]
+    #[case::no_prefix("foo:baz.bzl")]
+    #[case::empty_repository("@//foo:baz.bzl")]
+    fn test_parse_invalid(#[case] text: &str) {
+        assert!(matches!(
+            Label::parse(text, None),
+            Err(LabelError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_relative_without_package() {
+        assert_eq!(
+            Label::parse(":baz.bzl", None),
+            Err(LabelError::Relative {
+                label: ":baz.bzl".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_display_and_path() {
+        let label = Label::parse("@repo//foo/bar:baz.bzl", None).unwrap();
+
+        assert_eq!(label.to_string(), "@repo//foo/bar:baz.bzl");
+        assert_eq!(label.path(), std::path::Path::new("foo/bar/baz.bzl"));
+    }
+}
@@ -0,0 +1,5 @@
+mod highlight;
+mod labels;
+mod lint;
+mod resolve;
+mod types;
@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod lint_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_analyzer::lint::{apply_fixes, FileKind, LintError, Registry, Severity};
+    use sky_syntax::File;
+
+    /// The diagnostics of the given file, as `(rule, message, text)`.
+    fn lint(text: &str, kind: FileKind) -> Vec<(&'static str, String, String)> {
+        Registry::default()
+            .lint(&File::parse(text), kind)
+            .into_iter()
+            .map(|it| (it.rule, it.message, text[it.range].to_string()))
+            .collect()
+    }
+
+    /// The given file after applying all fixes.
+    fn fix(text: &str, kind: FileKind) -> String {
+        let diagnostics = Registry::default().lint(&File::parse(text), kind);
+        apply_fixes(text, &diagnostics).0
+    }
+
+    #[rstest]
+    #[case::all_unused(
+        "load(\":a.bzl\", \"a\")\nx = 1\n",
+        "None of the symbols loaded from `:a.bzl` are used",
+        "load(\":a.bzl\", \"a\")",
+        "x = 1\n"
+    )]
+    #[case::first_unused(
+        "load(\":a.bzl\", \"a\", \"b\")\nb()\n",
+        "`a` is loaded but never used",
+        "\"a\"",
+        "load(\":a.bzl\", \"b\")\nb()\n"
+    )]
+    #[case::last_unused(
+        "load(\":a.bzl\", \"a\", c = \"b\")\na()\n",
+        "`c` is loaded but never used",
+        "c = \"b\"",
+        "load(\":a.bzl\", \"a\")\na()\n"
+    )]
+    fn test_unused_load(
+        #[case] text: &str,
+        #[case] message: &str,
+        #[case] range: &str,
+        #[case] fixed: &str,
+    ) {
+        assert_eq!(
+            lint(text, FileKind::Extension),
+            vec![("unused-load", message.to_string(), range.to_string())]
+        );
+        assert_eq!(fix(text, FileKind::Extension), fixed);
+    }
+
+    #[test]
+    fn test_used_loads() {
+        let text = "load(\":a.bzl\", \"a\", b = \"c\")\nx = a(b)\n";
+
+        assert_eq!(lint(text, FileKind::Extension), vec![]);
+    }
+
+    #[test]
+    fn test_duplicate_keyword_argument() {
+        let text = "foo(name = \"a\", srcs = [], name = \"b\")\n";
+
+        assert_eq!(
+            lint(text, FileKind::Build),
+            vec![(
+                "duplicate-keyword-argument",
+                "Keyword argument `name` is given more than once".to_string(),
+                "name".to_string(),
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case::labels(
+        "foo(deps = [\"//b\", \":c\", \"@d//e\", \"//a\"])\n",
+        "foo(deps = [\":c\", \"//a\", \"//b\", \"@d//e\"])\n"
+    )]
+    #[case::runs(
+        "foo(srcs = [\n    \"b\",\n    \"a\",\n\n    \"d\",\n    \"c\",\n])\n",
+        "foo(srcs = [\n    \"a\",\n    \"b\",\n\n    \"c\",\n    \"d\",\n])\n"
+    )]
+    fn test_unsorted_deps(#[case] text: &str, #[case] fixed: &str) {
+        let diagnostics = lint(text, FileKind::Build);
+
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].0, "unsorted-deps");
+        assert_eq!(fix(text, FileKind::Build), fixed);
+    }
+
+    #[rstest]
+    #[case::sorted("foo(deps = [\":a\", \"//a\"])\n")]
+    #[case::sorted_runs("foo(srcs = [\n    \"b\",\n\n    \"a\",\n])\n")]
+    #[case::other_attribute("foo(tags = [\"b\", \"a\"])\n")]
+    #[case::not_only_strings("foo(deps = [\"b\", a, \"a\"])\n")]
+    fn test_sorted_deps(#[case] text: &str) {
+        assert_eq!(lint(text, FileKind::Build), vec![]);
+    }
+
+    #[test]
+    fn test_unsorted_deps_with_comments_has_no_fix() {
+        let text = "foo(deps = [\n    \"b\",  # b\n    \"a\",\n])\n";
+
+        assert_eq!(lint(text, FileKind::Build).len(), 1);
+        assert_eq!(fix(text, FileKind::Build), text);
+    }
+
+    #[rstest]
+    #[case::list("def f(a = []):\n    return a\n", "a")]
+    #[case::dict("def f(a, b = {}):\n    return b\n", "b")]
+    #[case::list_comprehension("def f(a = [x for x in y]):\n    return a\n", "a")]
+    fn test_mutable_default_parameter(#[case] text: &str, #[case] name: &str) {
+        let diagnostics = lint(text, FileKind::Extension);
+
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].0, "mutable-default-parameter");
+        assert!(diagnostics[0].1.contains(&format!("`{name}`")));
+    }
+
+    #[test]
+    fn test_immutable_default_parameters() {
+        let text = "def f(a = None, b = (1, 2), c = \"\", d = list()):\n    return a\n";
+
+        assert_eq!(lint(text, FileKind::Extension), vec![]);
+    }
+
+    #[rstest]
+    #[case::assignment("len = 1\nx = len\n", "len")]
+    #[case::parameter("def f(list):\n    return list\n", "list")]
+    #[case::function("def str():\n    pass\n", "str")]
+    #[case::load("load(\":a.bzl\", \"dict\")\nx = dict\n", "\"dict\"")]
+    #[case::load_alias("load(\":a.bzl\", min = \"a\")\nx = min\n", "min")]
+    #[case::loop_variable("for type in x:\n    print(type)\n", "type")]
+    fn test_shadowed_builtin(#[case] text: &str, #[case] range: &str) {
+        let diagnostics = lint(text, FileKind::Extension);
+        let shadowed = diagnostics
+            .into_iter()
+            .filter(|it| it.0 == "shadowed-builtin")
+            .map(|it| it.2)
+            .collect::<Vec<_>>();
+
+        assert_eq!(shadowed, vec![range.to_string()]);
+    }
+
+    #[rstest]
+    #[case::return_(
+        "def f():\n    return 1\n    x = 2\n    y = 3\n",
+        "Unreachable code after `return`",
+        "x = 2\n    y = 3"
+    )]
+    #[case::break_(
+        "for x in y:\n    break\n    if x:\n        pass\n",
+        "Unreachable code after `break`",
+        "if x:\n        pass"
+    )]
+    #[case::fail(
+        "def f():\n    fail(\"no\")\n    return 1\n",
+        "Unreachable code after `fail`",
+        "return 1"
+    )]
+    #[case::same_line(
+        "def f():\n    return; x = 1\n",
+        "Unreachable code after `return`",
+        "x = 1"
+    )]
+    fn test_unreachable(#[case] text: &str, #[case] message: &str, #[case] range: &str) {
+        assert_eq!(
+            lint(text, FileKind::Extension),
+            vec![("unreachable", message.to_string(), range.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_reachable() {
+        let text = "def f(x):\n    if x:\n        return 1\n    return 2\n";
+
+        assert_eq!(lint(text, FileKind::Extension), vec![]);
+    }
+
+    #[rstest]
+    #[case::build(FileKind::Build, 1)]
+    #[case::extension(FileKind::Extension, 0)]
+    fn test_print(#[case] kind: FileKind, #[case] expected: usize) {
+        let text = "x = 1\nprint(x)\n";
+
+        assert_eq!(lint(text, kind).len(), expected);
+        if expected > 0 {
+            assert_eq!(fix(text, kind), "x = 1\n");
+        }
+    }
+
+    #[rstest]
+    #[case::trailing("load(\":a.bzl\", \"a\")  # buildifier: disable=unused-load\n")]
+    #[case::previous_line("# buildifier: disable=unused-load\nload(\":a.bzl\", \"a\")\n")]
+    #[case::several_rules("# buildifier: disable=print,unused-load\nload(\":a.bzl\", \"a\")\n")]
+    #[case::with_reason("load(\":a.bzl\", \"a\")  # buildifier: disable=unused-load for tests\n")]
+    fn test_suppressed(#[case] text: &str) {
+        assert_eq!(lint(text, FileKind::Extension), vec![]);
+    }
+
+    #[rstest]
+    #[case::other_rule("load(\":a.bzl\", \"a\")  # buildifier: disable=print\n")]
+    #[case::other_line("# buildifier: disable=unused-load\nx = 1\nload(\":a.bzl\", \"a\")\n")]
+    fn test_not_suppressed(#[case] text: &str) {
+        assert_eq!(lint(text, FileKind::Extension).len(), 1);
+    }
+
+    #[test]
+    fn test_configuring_severities() {
+        let text = "load(\":a.bzl\", \"a\")\nprint(1)\n";
+        let mut registry = Registry::default();
+        registry.set_severity("print", None).unwrap();
+        registry
+            .set_severity("unused-load", Some(Severity::Error))
+            .unwrap();
+
+        let diagnostics = registry.lint(&File::parse(text), FileKind::Build);
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|it| (it.rule, it.severity))
+                .collect::<Vec<_>>(),
+            vec![("unused-load", Severity::Error)]
+        );
+        assert_eq!(registry.severity("print"), None);
+        assert_eq!(
+            registry.set_severity("nope", None),
+            Err(LintError::UnknownRule {
+                id: "nope".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_registering_duplicates() {
+        let mut registry = Registry::default();
+        let rule = Box::new(sky_analyzer::lint::rules::Print);
+
+        assert_eq!(
+            registry.register(rule),
+            Err(LintError::DuplicateRule {
+                id: "print".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_overlapping() {
+        let text = "load(\":a.bzl\", \"a\", \"b\")\nb()\nprint(1)\n";
+        let mut diagnostics = Registry::default().lint(&File::parse(text), FileKind::Build);
+        diagnostics.extend(diagnostics.clone());
+
+        assert_eq!(
+            apply_fixes(text, &diagnostics),
+            ("load(\":a.bzl\", \"b\")\nb()\n".to_string(), 2)
+        );
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let text = "load(\":a.bzl\", \"a\", \"b\", \"c\")\nc()\n";
+        let diagnostics = Registry::default().lint(&File::parse(text), FileKind::Extension);
+
+        assert_eq!(
+            apply_fixes(text, &diagnostics),
+            ("load(\":a.bzl\", \"c\")\nc()\n".to_string(), 2)
+        );
+    }
+}
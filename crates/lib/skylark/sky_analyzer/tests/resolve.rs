@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod resolve_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_analyzer::resolve::{
+        resolve, BindingKind, NameError, Resolution, ResolveOptions, Resolved, ScopeKind,
+    };
+    use sky_syntax::File;
+
+    fn resolve_text(text: &str, predeclared: &[&str]) -> Resolution {
+        let parse = File::parse(text);
+        assert_eq!(parse.errors(), &[], "{text}");
+        let options = ResolveOptions {
+            predeclared: predeclared.iter().map(ToString::to_string).collect(),
+        };
+
+        resolve(&parse.tree(), &options)
+    }
+
+    /// What each reference of the given file resolves to, e.g. `x` to a
+    /// `Variable in Module`.
+    fn references(text: &str, predeclared: &[&str]) -> Vec<(String, String)> {
+        let resolution = resolve_text(text, predeclared);
+        resolution
+            .references()
+            .iter()
+            .map(|reference| {
+                let resolved = match reference.resolved {
+                    Resolved::Binding(id) => {
+                        let binding = resolution.binding(id);
+                        let scope = resolution.scope(binding.scope);
+                        format!("{:?} in {:?}", binding.kind, scope.kind)
+                    }
+                    resolved => format!("{resolved:?}"),
+                };
+                (reference.name.clone(), resolved)
+            })
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(name, resolved)| (name.to_string(), resolved.to_string()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::global_in_function(
+        "x = 1\ndef f():\n    return x\n",
+        &[("x", "Variable in Module")]
+    )]
+    #[case::local_shadows_global(
+        "x = 1\ndef f():\n    x = 2\n    return x\n",
+        &[("x", "Variable in Function")]
+    )]
+    #[case::local_before_binding(
+        "x = 1\ndef f():\n    y = x\n    x = 2\n",
+        &[("x", "Variable in Function")]
+    )]
+    #[case::default_in_enclosing_scope(
+        "a = 1\ndef f(a, b = a):\n    return b\n",
+        &[("a", "Variable in Module"), ("b", "Parameter in Function")]
+    )]
+    #[case::closure(
+        "def f(a):\n    def g():\n        return a\n    return g\n",
+        &[("a", "Parameter in Function"), ("g", "Function in Function")]
+    )]
+    #[case::function_before_definition(
+        "def f():\n    return g()\ndef g():\n    pass\n",
+        &[("g", "Function in Module")]
+    )]
+    #[case::lambda("f = lambda a: a + b\n", &[("a", "Parameter in Function"), ("b", "Undefined")])]
+    #[case::comprehension(
+        "y = [x for x in xs]\nz = x\n",
+        &[("x", "Variable in Comprehension"), ("xs", "Undefined"), ("x", "Undefined")]
+    )]
+    #[case::first_iterable_in_enclosing_scope(
+        "x = [1]\ny = [x for x in x if x]\n",
+        &[
+            ("x", "Variable in Comprehension"),
+            ("x", "Variable in Module"),
+            ("x", "Variable in Comprehension"),
+        ]
+    )]
+    #[case::load(
+        "load(\":a.bzl\", \"a\", b = \"c\")\nx = a(b)\n",
+        &[("a", "Load in Module"), ("b", "Load in Module")]
+    )]
+    #[case::for_loop(
+        "for i in range(3):\n    print(i)\n",
+        &[("range", "Builtin"), ("print", "Builtin"), ("i", "Variable in Module")]
+    )]
+    #[case::predeclared(
+        "rust_library(name = str(True))\n",
+        &[("rust_library", "Predeclared"), ("str", "Builtin"), ("True", "Builtin")]
+    )]
+    #[case::attributes_and_keywords("x = native.glob(include = [])\n", &[("native", "Predeclared")])]
+    #[case::type_annotations(
+        "def f(a: Optional[int] = b) -> str:\n    return a\n",
+        &[("b", "Undefined"), ("a", "Parameter in Function")]
+    )]
+    fn test_references(#[case] text: &str, #[case] expected: &[(&str, &str)]) {
+        assert_eq!(
+            references(text, &["native", "rust_library"]),
+            pairs(expected)
+        );
+    }
+
+    #[rstest]
+    #[case::reassigned("x = 1\nx = 2\n", &["`x` is already defined at the top level, and globals can't be reassigned"])]
+    #[case::reassigned_load(
+        "load(\":a.bzl\", \"f\")\ndef f():\n    pass\n",
+        &["`f` is already defined at the top level, and globals can't be reassigned"]
+    )]
+    #[case::reassigned_in_branches(
+        "if c:\n    x = 1\nelse:\n    x = 2\n",
+        &["`x` is already defined at the top level, and globals can't be reassigned"]
+    )]
+    #[case::duplicate_parameter("def f(a, a):\n    pass\n", &["Duplicate parameter `a`"])]
+    #[case::undefined("x = y + z\n", &["`y` is not defined", "`z` is not defined"])]
+    #[case::locals_can_be_reassigned("def f():\n    x = 1\n    x = 2\n    return x\n", &[])]
+    #[case::locals_shadow_globals("x = 1\ndef f(x):\n    return x\n", &[])]
+    fn test_errors(#[case] text: &str, #[case] expected: &[&str]) {
+        let resolution = resolve_text(text, &["c"]);
+
+        assert_eq!(
+            resolution
+                .errors()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_error_ranges() {
+        let text = "x = 1\nx = 2\n";
+        let resolution = resolve_text(text, &[]);
+
+        let NameError::ReassignedGlobal {
+            range, previous, ..
+        } = &resolution.errors()[0]
+        else {
+            panic!("{:?}", resolution.errors());
+        };
+        assert_eq!(
+            (u32::from(range.start()), u32::from(previous.start())),
+            (6, 0)
+        );
+        assert_eq!(resolution.errors()[0].range(), *range);
+    }
+
+    #[test]
+    fn test_bindings_and_scopes() {
+        let text = "x = 1\ndef f(a):\n    return a + x\ny = x\n";
+        let resolution = resolve_text(text, &[]);
+
+        let x = resolution.binding_at(0.into()).unwrap();
+        assert_eq!(resolution.binding(x).name, "x");
+        assert_eq!(resolution.binding(x).kind, BindingKind::Variable);
+        assert_eq!(
+            resolution
+                .references_to(x)
+                .map(|it| u32::from(it.range.start()))
+                .collect::<Vec<_>>(),
+            vec![31, 37]
+        );
+
+        // From a reference to its binding.
+        assert_eq!(resolution.binding_at(37.into()), Some(x));
+
+        let function = resolution.scope_at(27.into());
+        assert_eq!(resolution.scope(function).kind, ScopeKind::Function);
+        assert_eq!(resolution.scope(function).parent, Some(resolution.module()));
+        let a = resolution.lookup(function, "a").unwrap();
+        assert_eq!(resolution.binding(a).kind, BindingKind::Parameter);
+        assert_eq!(resolution.lookup(resolution.module(), "a"), None);
+        assert_eq!(resolution.scope_at(37.into()), resolution.module());
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod types_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rowan::{TextRange, TextSize};
+    use rstest::rstest;
+    use sky_analyzer::types::{
+        check, Globals, Parameter, ParameterKind, Signature, Type, TypeCheck,
+    };
+    use sky_syntax::File;
+
+    fn check_text(text: &str, globals: &Globals) -> TypeCheck {
+        let parse = File::parse(text);
+        assert_eq!(parse.errors(), &[], "{text}");
+
+        check(&parse.tree(), globals)
+    }
+
+    /// The messages of the type errors of the given file.
+    fn errors(text: &str) -> Vec<String> {
+        check_text(text, &Globals::default())
+            .errors()
+            .iter()
+            .map(|error| error.message.clone())
+            .collect()
+    }
+
+    /// The type of the given global, showing the signature of functions.
+    fn global_type(text: &str, name: &str) -> String {
+        let check = check_text(text, &Globals::default());
+        match check.global(name).unwrap() {
+            Type::Function(signature) => signature.to_string(),
+            ty => ty.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::int("x", "x = 1\n", "int")]
+    #[case::string("x", "x = 'a' * 2\n", "str")]
+    #[case::list("x", "x = [1, 2]\n", "list[int]")]
+    #[case::mixed_list("x", "x = [1, 'a']\n", "list[Any]")]
+    #[case::dict("x", "x = {'a': 1}\n", "dict[str, int]")]
+    #[case::tuple("x", "x = 1, 'a'\n", "tuple[int, str]")]
+    #[case::unpacked("x", "a, x = 1, 'a'\n", "str")]
+    #[case::list_comp("x", "x = [str(i) for i in range(3)]\n", "list[str]")]
+    #[case::dict_comp("x", "x = {k: v for k, v in {'a': 1.5}.items()}\n", "dict[str, float]")]
+    #[case::builtin("x", "x = sorted([1.5])\n", "list[float]")]
+    #[case::method("x", "x = 'a b'.split()\n", "list[str]")]
+    #[case::dict_get("x", "x = {'a': 1}.get('a')\n", "int | None")]
+    #[case::conditional("x", "x = 1 if y else 2\ny = True\n", "int")]
+    #[case::inferred_return("x", "def f():\n    return [1]\nx = f()\n", "list[int]")]
+    #[case::joined_locals(
+        "f",
+        "def f(a):\n    x = 1\n    if a:\n        x = 'a'\n    return x\n",
+        "def f(a) -> Any"
+    )]
+    #[case::signature(
+        "f",
+        "def f(x: str, y: list[int] = []) -> dict[str, int]:\n    return {}\n",
+        "def f(x: str, y: list[int] = ...) -> dict[str, int]"
+    )]
+    #[case::optional(
+        "f",
+        "def f(x: Optional[int] = None, *args: str, k: bool, **kwargs: int):\n    pass\n",
+        "def f(x: int | None = ..., *args: str, k: bool, **kwargs: int) -> None"
+    )]
+    #[case::lambda(
+        "f",
+        "f = lambda x, y = 1: [x]\n",
+        "def lambda(x, y = ...) -> list[Any]"
+    )]
+    fn test_inference(#[case] name: &str, #[case] text: &str, #[case] expected: &str) {
+        assert_eq!(global_type(text, name), expected);
+    }
+
+    #[rstest]
+    #[case::operands("x = 'a' + 1\n", &["Unsupported operand types for `+`: `str` and `int`"])]
+    #[case::unary("x = -'a'\n", &["Unsupported operand type for `-`: `str`"])]
+    #[case::not_callable("x = 1\ny = x()\n", &["`int` is not callable"])]
+    #[case::return_type(
+        "def f() -> str:\n    return 1\n",
+        &["Expected `str` to be returned from `f`, got `int`"]
+    )]
+    #[case::optional_return(
+        "def f(x: Optional[int]) -> int:\n    return x\n",
+        &["Expected `int` to be returned from `f`, got `int | None`"]
+    )]
+    #[case::argument(
+        "def f(x: int):\n    pass\nf('a')\n",
+        &["Expected `int` for argument `x` of `f`, got `str`"]
+    )]
+    #[case::missing_argument("def f(x: int):\n    pass\nf()\n", &["`f` is missing the argument `x`"])]
+    #[case::too_many_arguments(
+        "def f(x):\n    pass\nf(1, 2)\n",
+        &["`f` accepts at most 1 positional argument(s) (2 given)"]
+    )]
+    #[case::unexpected_keyword(
+        "def f(x):\n    pass\nf(1, y = 2)\n",
+        &["`f` got an unexpected keyword argument `y`"]
+    )]
+    #[case::function_defined_later(
+        "def g():\n    return f(1)\ndef f(x: str):\n    pass\n",
+        &["Expected `str` for argument `x` of `f`, got `int`"]
+    )]
+    #[case::inferred_return(
+        "def f():\n    return 1\nx = f() + 'a'\n",
+        &["Unsupported operand types for `+`: `int` and `str`"]
+    )]
+    #[case::default_value(
+        "def f(x: str = 1):\n    pass\n",
+        &["Expected `str` for the default value of `x`, got `int`"]
+    )]
+    #[case::none_default("def f(x: str = None):\n    pass\n", &[])]
+    #[case::reassigned_parameter(
+        "def f(x: int):\n    x = 'a'\n",
+        &["Cannot assign `str` to `x`, of type `int`"]
+    )]
+    #[case::unknown_type("def f(x: Foo):\n    pass\n", &["Unknown type `Foo`"])]
+    #[case::type_arguments(
+        "def f(x: dict[str]):\n    pass\n",
+        &["Type `dict` takes 2 type argument(s), got 1"]
+    )]
+    #[case::attribute("x = [].foo\n", &["`list[Any]` has no attribute `foo`"])]
+    #[case::not_iterable("for x in 1:\n    pass\n", &["`int` is not iterable"])]
+    #[case::string_not_iterable("x = [c for c in 'abc']\n", &["`str` is not iterable"])]
+    #[case::index("x = [1]['a']\n", &["Indices must be `int`, not `str`"])]
+    #[case::key("d = {'a': 1}\nx = d[1]\n", &["Expected a key of type `str`, got `int`"])]
+    #[case::element(
+        "x = [1]\nx[0] = 'a'\n",
+        &["Cannot assign `str` to an element of `list[int]`"]
+    )]
+    #[case::unpack("a, b = 1, 2, 3\n", &["Cannot unpack 3 values into 2 targets"])]
+    #[case::append(
+        "x = [1]\nx.append('a')\n",
+        &["Expected `int` for the argument of `append`, got `str`"]
+    )]
+    #[case::len("x = len(1)\n", &["`int` has no length"])]
+    #[case::augmented("def f(x: int):\n    x += 'a'\n", &["Unsupported operand types for `+`: `int` and `str`"])]
+    #[case::floor_division_assignment("def f(x: int):\n    x //= 'a'\n", &["Unsupported operand types for `//`: `int` and `str`"])]
+    #[case::unannotated("def f(x):\n    return x + 1\ny = f('a') + 1\n", &[])]
+    #[case::unpacked_arguments("def f(x: int, y: int):\n    pass\nf(*[1, 2])\n", &[])]
+    #[case::string_formatting("x = '%d' % 1\n", &[])]
+    #[case::unknown_names("x = foo + 1\n", &[])]
+    fn test_errors(#[case] text: &str, #[case] expected: &[&str]) {
+        assert_eq!(errors(text), expected);
+    }
+
+    #[test]
+    fn test_error_ranges() {
+        let check = check_text("x = 1\ny = 'a' + x\n", &Globals::default());
+
+        assert_eq!(
+            check.errors()[0].range,
+            TextRange::new(TextSize::from(10), TextSize::from(17))
+        );
+    }
+
+    #[test]
+    fn test_environment() {
+        let rule = Signature {
+            name: "rule".to_string(),
+            parameters: vec![Parameter {
+                name: "name".to_string(),
+                kind: ParameterKind::KeywordOnly,
+                ty: Type::String,
+                has_default: false,
+            }],
+            return_type: Type::None,
+        };
+        let globals = Globals(
+            [("rule".to_string(), Type::Function(Box::new(rule)))]
+                .into_iter()
+                .collect(),
+        );
+
+        let check = check_text("rule(name = 1)\n", &globals);
+        assert_eq!(
+            check
+                .errors()
+                .iter()
+                .map(|error| error.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Expected `str` for argument `name` of `rule`, got `int`"]
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! The **builtins** of Starlark, i.e. the constants and functions of its
+//! universe, along with `struct`.
+
+use crate::{
+    error::EvalError,
+    eval::Evaluator,
+    function::{Arguments, Builtin},
+    methods::update_dict,
+    ops,
+    value::{float_to_big_int, Dict, Struct, Value},
+};
+use num_bigint::BigInt;
+use num_traits::{Num, Signed};
+use std::{cmp::Ordering, collections::HashMap, rc::Rc};
+
+/// The upper bound on the length of the list returned by `range`.
+const MAX_RANGE: i64 = 10_000_000;
+
+type NativeFn = fn(&mut Evaluator, Arguments) -> Result<Value, EvalError>;
+
+const FUNCTIONS: &[(&str, NativeFn)] = &[
+    ("abs", abs),
+    ("all", all),
+    ("any", any),
+    ("bool", bool),
+    ("bytes", bytes),
+    ("dict", dict),
+    ("dir", dir),
+    ("enumerate", enumerate),
+    ("fail", fail),
+    ("float", float),
+    ("getattr", getattr),
+    ("hasattr", hasattr),
+    ("hash", hash),
+    ("int", int),
+    ("len", len),
+    ("list", list),
+    ("max", max),
+    ("min", min),
+    ("print", print),
+    ("range", range),
+    ("repr", repr),
+    ("reversed", reversed),
+    ("sorted", sorted),
+    ("str", str),
+    ("struct", r#struct),
+    ("tuple", tuple),
+    ("type", r#type),
+    ("zip", zip),
+];
+
+/// The **universe**, i.e. the values of the names predeclared in every
+/// module.
+pub(crate) fn universe() -> HashMap<String, Value> {
+    let mut universe: HashMap<String, Value> = FUNCTIONS
+        .iter()
+        .map(|(name, function)| (name.to_string(), Builtin::new(*name, *function).into()))
+        .collect();
+    universe.insert("None".to_string(), Value::None);
+    universe.insert("True".to_string(), Value::Bool(true));
+    universe.insert("False".to_string(), Value::Bool(false));
+
+    universe
+}
+
+/// Unwraps a **required** argument, which [`Arguments::unpack`] guarantees is
+/// present.
+fn required(value: Option<Value>) -> Value {
+    value.unwrap_or(Value::None)
+}
+
+fn int_argument(function: &str, value: &Value) -> Result<i64, EvalError> {
+    match value {
+        Value::Int(value) => Ok(*value),
+        Value::BigInt(value) => Err(EvalError::new(format!(
+            "{function}() argument out of range: {value}"
+        ))),
+        value => Err(EvalError::new(format!(
+            "{function}() expects an int, not {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn abs(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("abs", ["$x"])?;
+    match required(x) {
+        Value::Int(value) => Ok(match value.checked_abs() {
+            Some(value) => Value::Int(value),
+            None => Value::int(BigInt::from(value).abs()),
+        }),
+        Value::BigInt(value) => Ok(Value::int(value.abs())),
+        Value::Float(value) => Ok(Value::Float(value.abs())),
+        value => Err(EvalError::new(format!(
+            "abs() expects a number, not {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn all(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("all", ["$iterable"])?;
+    let items = ops::iterate(&required(iterable))?;
+
+    Ok(Value::Bool(items.iter().all(Value::truth)))
+}
+
+fn any(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("any", ["$iterable"])?;
+    let items = ops::iterate(&required(iterable))?;
+
+    Ok(Value::Bool(items.iter().any(Value::truth)))
+}
+
+fn bool(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("bool", ["$x?"])?;
+    Ok(Value::Bool(matches!(x, Some(x) if x.truth())))
+}
+
+fn bytes(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("bytes", ["$x"])?;
+    match required(x) {
+        Value::Bytes(bytes) => Ok(Value::Bytes(bytes)),
+        Value::String(string) => Ok(Value::Bytes(string.as_bytes().into())),
+        x => {
+            let bytes = ops::iterate(&x)
+                .map_err(|_| EvalError::new(format!("bytes() can't convert a {}", x.type_name())))?
+                .iter()
+                .map(|it| {
+                    let byte = int_argument("bytes", it)?;
+                    u8::try_from(byte)
+                        .map_err(|_| EvalError::new(format!("Byte {byte} out of range")))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Value::Bytes(bytes.into()))
+        }
+    }
+}
+
+fn dict(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let Arguments { positional, named } = arguments;
+    let pairs = Arguments::positional(positional).positional_only("dict", 0, 1)?;
+    let dict = Dict::default();
+    update_dict(&dict, pairs.into_iter().next(), named)?;
+
+    Ok(Value::Dict(Rc::new(dict)))
+}
+
+fn dir(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("dir", ["$x"])?;
+    let names = ops::dir(&required(x))
+        .into_iter()
+        .map(Value::from)
+        .collect();
+
+    Ok(Value::list(names))
+}
+
+fn enumerate(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable, start] = arguments.unpack("enumerate", ["$iterable", "start?"])?;
+    let start = match start {
+        Some(start) => int_argument("enumerate", &start)?,
+        None => 0,
+    };
+
+    let pairs = ops::iterate(&required(iterable))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let index = match start.checked_add(index as i64) {
+                Some(index) => Value::Int(index),
+                None => Value::int(BigInt::from(start) + index),
+            };
+            Value::tuple(vec![index, item])
+        })
+        .collect();
+
+    Ok(Value::list(pairs))
+}
+
+/// Joins the `str` of the given values with the `sep` named argument (a
+/// space by default), as `print` and `fail` do.
+fn join_arguments(function: &str, arguments: Arguments) -> Result<String, EvalError> {
+    let mut separator = " ".to_string();
+    for (name, value) in arguments.named {
+        match (name.as_str(), value) {
+            ("sep", Value::String(value)) => separator = value.to_string(),
+            ("sep", value) => {
+                return Err(EvalError::new(format!(
+                    "{function}() expects a string for `sep`, not {}",
+                    value.type_name()
+                )))
+            }
+            (name, _) => {
+                return Err(EvalError::new(format!(
+                    "{function}() got an unexpected keyword argument `{name}`"
+                )))
+            }
+        }
+    }
+
+    let parts: Vec<String> = arguments.positional.iter().map(Value::to_string).collect();
+
+    Ok(parts.join(&separator))
+}
+
+fn fail(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let message = join_arguments("fail", arguments)?;
+    Err(EvalError::new(format!("fail: {message}")))
+}
+
+fn print(evaluator: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let message = join_arguments("print", arguments)?;
+    evaluator.print(&message);
+
+    Ok(Value::None)
+}
+
+fn float(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("float", ["$x?"])?;
+    let float = match x {
+        None => 0.0,
+        Some(Value::Bool(value)) => f64::from(u8::from(value)),
+        Some(value @ (Value::Int(_) | Value::BigInt(_))) => value.int_to_float()?,
+        Some(Value::Float(value)) => value,
+        Some(Value::String(string)) => parse_float(&string)
+            .ok_or_else(|| EvalError::new(format!("Invalid float literal: {string}")))?,
+        Some(value) => {
+            return Err(EvalError::new(format!(
+                "float() can't convert a {}",
+                value.type_name()
+            )))
+        }
+    };
+
+    Ok(Value::Float(float))
+}
+
+/// Parses a float as `float` does, i.e. a decimal literal or (case
+/// insensitively) `inf`, `infinity` or `nan`, with an optional sign.
+fn parse_float(string: &str) -> Option<f64> {
+    let (sign, digits) = match string.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, string.strip_prefix('+').unwrap_or(string)),
+    };
+
+    let value = match digits.to_ascii_lowercase().as_str() {
+        "inf" | "infinity" => f64::INFINITY,
+        "nan" => f64::NAN,
+        digits if digits.starts_with(|it: char| it.is_ascii_digit() || it == '.') => {
+            digits.parse().ok()?
+        }
+        _ => return None,
+    };
+
+    Some(sign * value)
+}
+
+fn getattr(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x, name, default] = arguments.unpack("getattr", ["$x", "$name", "$default?"])?;
+    let name = match required(name) {
+        Value::String(name) => name,
+        name => {
+            return Err(EvalError::new(format!(
+                "getattr() expects a string, not {}",
+                name.type_name()
+            )))
+        }
+    };
+
+    match (ops::getattr(&required(x), &name), default) {
+        (Ok(value), _) => Ok(value),
+        (Err(_), Some(default)) => Ok(default),
+        (Err(error), None) => Err(error),
+    }
+}
+
+fn hasattr(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x, name] = arguments.unpack("hasattr", ["$x", "$name"])?;
+    let name = match required(name) {
+        Value::String(name) => name,
+        name => {
+            return Err(EvalError::new(format!(
+                "hasattr() expects a string, not {}",
+                name.type_name()
+            )))
+        }
+    };
+
+    Ok(Value::Bool(ops::getattr(&required(x), &name).is_ok()))
+}
+
+/// Hashes a string or bytes as Java's `String.hashCode` does, like the Java
+/// implementation of Starlark.
+fn hash(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("hash", ["$x"])?;
+    let hash = match required(x) {
+        Value::String(string) => string.encode_utf16().fold(0i32, |hash, it| {
+            hash.wrapping_mul(31).wrapping_add(it.into())
+        }),
+        Value::Bytes(bytes) => bytes.iter().fold(0i32, |hash, it| {
+            hash.wrapping_mul(31).wrapping_add((*it).into())
+        }),
+        x => {
+            return Err(EvalError::new(format!(
+                "hash() expects a string or bytes, not {}",
+                x.type_name()
+            )))
+        }
+    };
+
+    Ok(Value::Int(hash.into()))
+}
+
+fn int(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x, base] = arguments.unpack("int", ["$x?", "base?"])?;
+    let base = match &base {
+        Some(base) => Some(int_argument("int", base)?),
+        None => None,
+    };
+
+    let int = match (x, base) {
+        (None, None) => Value::Int(0),
+        (Some(Value::String(string)), base) => parse_int(&string, base.unwrap_or(10))?,
+        (Some(_), Some(_)) | (None, Some(_)) => {
+            return Err(EvalError::new(
+                "int() can't convert a non-string with an explicit base",
+            ))
+        }
+        (Some(Value::Bool(value)), None) => Value::Int(value.into()),
+        (Some(value @ (Value::Int(_) | Value::BigInt(_))), None) => value,
+        (Some(Value::Float(value)), None) => float_to_big_int(value.trunc())
+            .map(Value::int)
+            .ok_or_else(|| EvalError::new(format!("int() can't convert {value} to an int")))?,
+        (Some(value), None) => {
+            return Err(EvalError::new(format!(
+                "int() can't convert a {}",
+                value.type_name()
+            )))
+        }
+    };
+
+    Ok(int)
+}
+
+/// Parses an int as `int` does, with an optional sign and (if the base is
+/// 0 or matches) a `0x`, `0o` or `0b` prefix.
+fn parse_int(string: &str, base: i64) -> Result<Value, EvalError> {
+    let invalid = || EvalError::new(format!("Invalid int literal: {string}"));
+    if base != 0 && !(2..=36).contains(&base) {
+        return Err(EvalError::new(format!(
+            "int() base must be 0 or between 2 and 36, not {base}"
+        )));
+    }
+
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+    let lowercase = digits.to_ascii_lowercase();
+    let prefixed = [("0x", 16u32), ("0o", 8), ("0b", 2)]
+        .into_iter()
+        .find(|(prefix, radix)| {
+            lowercase.starts_with(prefix) && (base == 0 || base == i64::from(*radix))
+        });
+    let (digits, base) = match prefixed {
+        Some((prefix, radix)) => (&digits[prefix.len()..], radix),
+        // Without a prefix, base 0 means decimal, but leading zeros are
+        // ambiguous (e.g. `010`).
+        None if base == 0 => {
+            if digits.len() > 1
+                && digits.starts_with('0')
+                && !digits.trim_start_matches('0').is_empty()
+            {
+                return Err(invalid());
+            }
+            (digits, 10)
+        }
+        None => (digits, base as u32),
+    };
+    if digits.is_empty() || !digits.chars().all(|it| it.is_digit(base)) {
+        return Err(invalid());
+    }
+
+    let magnitude = BigInt::from_str_radix(digits, base).map_err(|_| invalid())?;
+
+    Ok(Value::int(if negative { -magnitude } else { magnitude }))
+}
+
+fn len(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("len", ["$x"])?;
+    let len = match required(x) {
+        Value::String(string) => string.chars().count(),
+        Value::Bytes(bytes) => bytes.len(),
+        Value::List(list) => list.items().len(),
+        Value::Tuple(items) => items.len(),
+        Value::Dict(dict) => dict.entries().len(),
+        x => {
+            return Err(EvalError::new(format!(
+                "Value of type {} has no len",
+                x.type_name()
+            )))
+        }
+    };
+
+    Ok(Value::Int(len as i64))
+}
+
+fn list(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("list", ["$iterable?"])?;
+    let items = match iterable {
+        Some(iterable) => ops::iterate(&iterable)?,
+        None => Vec::new(),
+    };
+
+    Ok(Value::list(items))
+}
+
+fn tuple(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("tuple", ["$iterable?"])?;
+    let items = match iterable {
+        Some(Value::Tuple(items)) => return Ok(Value::Tuple(items)),
+        Some(iterable) => ops::iterate(&iterable)?,
+        None => Vec::new(),
+    };
+
+    Ok(Value::tuple(items))
+}
+
+/// The greatest (or least) of the given values, or of the items of the given
+/// iterable, as `max` (or `min`) does.
+fn extremum(
+    function: &str,
+    evaluator: &mut Evaluator,
+    arguments: Arguments,
+    wanted: Ordering,
+) -> Result<Value, EvalError> {
+    let mut key = None;
+    for (name, value) in arguments.named {
+        match name.as_str() {
+            "key" => key = Some(value).filter(|it| !matches!(it, Value::None)),
+            name => {
+                return Err(EvalError::new(format!(
+                    "{function}() got an unexpected keyword argument `{name}`"
+                )))
+            }
+        }
+    }
+
+    let items = match <[Value; 1]>::try_from(arguments.positional) {
+        Ok([iterable]) => ops::iterate(&iterable)?,
+        Err(items) if items.is_empty() => {
+            return Err(EvalError::new(format!(
+                "{function}() expects at least one argument"
+            )))
+        }
+        Err(items) => items,
+    };
+
+    let mut best: Option<(Value, Value)> = None;
+    for item in items {
+        let item_key = match &key {
+            Some(key) => evaluator.call(key, Arguments::positional(vec![item.clone()]))?,
+            None => item.clone(),
+        };
+        best = match best {
+            Some((best, best_key)) if item_key.compare(&best_key)? != wanted => {
+                Some((best, best_key))
+            }
+            _ => Some((item, item_key)),
+        };
+    }
+
+    best.map(|(item, _)| item)
+        .ok_or_else(|| EvalError::new(format!("{function}() of an empty sequence")))
+}
+
+fn max(evaluator: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    extremum("max", evaluator, arguments, Ordering::Greater)
+}
+
+fn min(evaluator: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    extremum("min", evaluator, arguments, Ordering::Less)
+}
+
+/// The ints of the given range, as a list (unlike the spec's lazy `range`).
+fn range(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let bounds = arguments.positional_only("range", 1, 3)?;
+    let bounds = bounds
+        .iter()
+        .map(|it| int_argument("range", it))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (start, stop, step) = match bounds[..] {
+        [stop] => (0, stop, 1),
+        [start, stop] => (start, stop, 1),
+        [start, stop, step] => (start, stop, step),
+        _ => unreachable!("range() takes between 1 and 3 arguments"),
+    };
+    if step == 0 {
+        return Err(EvalError::new("range() step argument must not be zero"));
+    }
+
+    let span = if step > 0 {
+        i128::from(stop) - i128::from(start)
+    } else {
+        i128::from(start) - i128::from(stop)
+    };
+    let step_size = i128::from(step).abs();
+    let len = ((span + step_size - 1) / step_size).max(0);
+    if len > i128::from(MAX_RANGE) {
+        return Err(EvalError::new(format!(
+            "range() of more than {MAX_RANGE} elements"
+        )));
+    }
+
+    let items = (0..len)
+        .map(|index| Value::Int((i128::from(start) + index * i128::from(step)) as i64))
+        .collect();
+
+    Ok(Value::list(items))
+}
+
+fn repr(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("repr", ["$x"])?;
+    Ok(Value::from(required(x).repr()))
+}
+
+fn str(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("str", ["$x"])?;
+    match required(x) {
+        Value::String(string) => Ok(Value::String(string)),
+        x => Ok(Value::from(x.to_string())),
+    }
+}
+
+fn reversed(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [sequence] = arguments.unpack("reversed", ["$sequence"])?;
+    let mut items = ops::iterate(&required(sequence))?;
+    items.reverse();
+
+    Ok(Value::list(items))
+}
+
+fn sorted(evaluator: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [iterable, key, reverse] = arguments.unpack("sorted", ["$iterable", "key?", "reverse?"])?;
+    let items = ops::iterate(&required(iterable))?;
+    let reverse = matches!(reverse, Some(reverse) if reverse.truth());
+
+    let keys = match key {
+        None | Some(Value::None) => items.clone(),
+        Some(key) => items
+            .iter()
+            .map(|item| evaluator.call(&key, Arguments::positional(vec![item.clone()])))
+            .collect::<Result<_, _>>()?,
+    };
+
+    // The sort is stable (even in reverse), and fails with the first
+    // comparison failing.
+    let mut error = None;
+    let mut indices: Vec<usize> = (0..items.len()).collect();
+    indices.sort_by(|a, b| {
+        let (a, b) = if reverse { (b, a) } else { (a, b) };
+        keys[*a].compare(&keys[*b]).unwrap_or_else(|it| {
+            error.get_or_insert(it);
+            Ordering::Equal
+        })
+    });
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    Ok(Value::list(
+        indices.into_iter().map(|it| items[it].clone()).collect(),
+    ))
+}
+
+fn r#struct(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    if !arguments.positional.is_empty() {
+        return Err(EvalError::new("struct() accepts only keyword arguments"));
+    }
+
+    let fields = arguments.named.into_iter().collect();
+
+    Ok(Value::Struct(Rc::new(Struct { fields })))
+}
+
+fn r#type(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let [x] = arguments.unpack("type", ["$x"])?;
+    Ok(Value::from(required(x).type_name()))
+}
+
+fn zip(_: &mut Evaluator, arguments: Arguments) -> Result<Value, EvalError> {
+    let iterables = arguments.positional_only("zip", 0, usize::MAX)?;
+    let iterables = iterables
+        .iter()
+        .map(ops::iterate)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let len = iterables.iter().map(Vec::len).min().unwrap_or(0);
+    let tuples = (0..len)
+        .map(|index| Value::tuple(iterables.iter().map(|it| it[index].clone()).collect()))
+        .collect();
+
+    Ok(Value::list(tuples))
+}
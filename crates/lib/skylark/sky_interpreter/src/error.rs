@@ -0,0 +1,55 @@
+//! The **errors** of the interpreter.
+
+use rowan::TextRange;
+use sky_analyzer::resolve::NameError;
+use sky_syntax::SyntaxError;
+use thiserror::Error;
+
+/// An **error** raised while evaluating Starlark code (e.g. a type mismatch,
+/// a missing key or a call to `fail`), which aborts the evaluation.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct EvalError {
+    pub message: String,
+    /// The range of the code raising the error, if known.
+    pub range: Option<TextRange>,
+}
+
+impl EvalError {
+    pub fn new(message: impl Into<String>) -> EvalError {
+        EvalError {
+            message: message.into(),
+            range: None,
+        }
+    }
+
+    /// Sets the **range** of the error, unless it's already known (i.e. the
+    /// innermost range wins).
+    pub fn at(mut self, range: TextRange) -> EvalError {
+        self.range.get_or_insert(range);
+        self
+    }
+}
+
+/// An **error** preventing a module from being evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error("Failed to parse: {}", .0[0])]
+    Syntax(Vec<SyntaxError>),
+    /// Static errors found by name resolution, e.g. undefined names.
+    #[error("{}", .0[0])]
+    Name(Vec<NameError>),
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+}
+
+impl Error {
+    /// The range of the (first) error, if known.
+    pub fn range(&self) -> Option<TextRange> {
+        match self {
+            Error::Syntax(errors) => errors.first().map(SyntaxError::range),
+            Error::Name(errors) => errors.first().map(NameError::range),
+            Error::Eval(error) => error.range,
+        }
+    }
+}
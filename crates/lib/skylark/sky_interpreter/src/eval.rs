@@ -0,0 +1,1336 @@
+//! The **evaluator**, executing a module by walking its syntax tree.
+//!
+//! Before a module is executed, its names are resolved by
+//! [`sky_analyzer::resolve`], reporting undefined names and reassigned globals
+//! as static errors. Each identifier is then looked up in the **frame** of the
+//! scope binding it: the module has a frame holding its globals, and each
+//! call of a function (or evaluation of a comprehension) has a frame holding
+//! its locals, linked to the frame of the enclosing scope.
+
+use crate::{
+    builtins,
+    error::{Error, EvalError},
+    frozen::FrozenModule,
+    function::{Arguments, Function, Parameter, ParameterKind},
+    ops,
+    value::{Dict, IterationGuard, Value},
+};
+use indexmap::IndexMap;
+use rowan::{TextRange, TextSize};
+use sky_analyzer::{
+    builtins::is_builtin,
+    resolve::{resolve, BindingId, BindingKind, Resolution, ResolveOptions, Resolved, ScopeId},
+};
+use sky_syntax::{
+    ast::{self, AstNode, AstToken, BinaryOp, UnaryOp},
+    File, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc, sync::Arc};
+
+/// The **interpreter**, evaluating modules in an environment of predeclared
+/// names (by default, the builtins of Starlark and `struct`).
+///
+/// ```
+/// use sky_interpreter::{Interpreter, Value};
+///
+/// let module = Interpreter::new()
+///     .eval("example", "squares = [x * x for x in range(4)]\n")
+///     .unwrap();
+///
+/// assert_eq!(module.get("squares").unwrap().repr(), "[0, 1, 4, 9]");
+/// ```
+pub struct Interpreter {
+    pub(crate) predeclared: HashMap<String, Value>,
+    print: Rc<dyn Fn(&str)>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Interpreter {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    /// An interpreter predeclaring the builtins, and printing to stderr.
+    pub fn new() -> Interpreter {
+        Interpreter {
+            predeclared: builtins::universe(),
+            print: Rc::new(|message| eprintln!("{message}")),
+        }
+    }
+
+    /// **Predeclares** the given name in the modules evaluated from now on
+    /// (e.g. the rules available in a `BUCK` file), overriding any builtin
+    /// of the same name.
+    pub fn predeclare(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.predeclared.insert(name.into(), value.into());
+    }
+
+    /// Sets the function handling the messages of `print`.
+    pub fn set_print(&mut self, print: impl Fn(&str) + 'static) {
+        self.print = Rc::new(print);
+    }
+
+    /// **Evaluates** the given module, whose globals are frozen afterwards.
+    pub fn eval(&self, name: &str, text: &str) -> Result<Module, Error> {
+        let parse = File::parse(text);
+        if !parse.errors().is_empty() {
+            return Err(Error::Syntax(parse.errors().to_vec()));
+        }
+
+        self.eval_file(name, parse.tree())
+    }
+
+    /// **Evaluates** the given (parsed) module, whose globals are frozen
+    /// afterwards.
+    pub fn eval_file(&self, name: &str, file: File) -> Result<Module, Error> {
+        self.eval_loaded(name, file, HashMap::new())
+    }
+
+    /// **Evaluates** the given (parsed) module, whose `load` statements load
+    /// the given modules, by the labels they're loaded with (e.g.
+    /// `:defs.bzl`).
+    pub fn eval_loaded(
+        &self,
+        name: &str,
+        file: File,
+        loaded: HashMap<String, Module>,
+    ) -> Result<Module, Error> {
+        let program = Rc::new(self.program(name, file, loaded)?);
+        let env = Env {
+            frame: Rc::new(Frame::new(program.resolution.module(), None)),
+            program,
+        };
+        self.evaluator().exec_module(&env)?;
+
+        let module = Module::new(env.program, env.frame, None);
+        module.globals.values().for_each(Value::freeze);
+
+        Ok(module)
+    }
+
+    /// The **program** of the given module, i.e. its resolved names, in the
+    /// environment of the interpreter.
+    pub(crate) fn program(
+        &self,
+        name: &str,
+        file: File,
+        loaded: HashMap<String, Module>,
+    ) -> Result<Program, Error> {
+        let options = ResolveOptions {
+            predeclared: self
+                .predeclared
+                .keys()
+                .filter(|it| !is_builtin(it))
+                .cloned()
+                .collect(),
+        };
+        let resolution = resolve(&file, &options);
+        if !resolution.errors().is_empty() {
+            return Err(Error::Name(resolution.errors().to_vec()));
+        }
+
+        Ok(Program::new(
+            name.to_string(),
+            file,
+            resolution,
+            Rc::new(self.predeclared.clone()),
+            loaded,
+        ))
+    }
+
+    /// **Calls** the given function (e.g. one defined by a module) with the
+    /// given arguments.
+    pub fn call(&self, function: &Value, arguments: Arguments) -> Result<Value, EvalError> {
+        self.evaluator().call(function, arguments)
+    }
+
+    fn evaluator(&self) -> Evaluator {
+        Evaluator {
+            print: self.print.clone(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+/// An **evaluated** module, i.e. the (frozen) values of its globals.
+#[derive(Clone)]
+pub struct Module {
+    pub(crate) program: Rc<Program>,
+    /// The frame of the module, also holding the symbols it loads.
+    pub(crate) frame: Rc<Frame>,
+    globals: IndexMap<String, Value>,
+    /// The frozen module it was thawed from, if any.
+    pub(crate) frozen: Option<Arc<FrozenModule>>,
+}
+
+impl Module {
+    pub(crate) fn new(
+        program: Rc<Program>,
+        frame: Rc<Frame>,
+        frozen: Option<Arc<FrozenModule>>,
+    ) -> Module {
+        let resolution = &program.resolution;
+        let globals = resolution
+            .scope(resolution.module())
+            .bindings
+            .iter()
+            .map(|id| (*id, resolution.binding(*id)))
+            .filter(|(_, binding)| binding.kind != BindingKind::Load)
+            .filter_map(|(id, binding)| Some((binding.name.clone(), frame.get(id)?)))
+            .collect();
+
+        Module {
+            program,
+            frame,
+            globals,
+            frozen,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.program.name
+    }
+
+    /// The value of the given global, if bound.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.globals.get(name)
+    }
+
+    /// The globals, in the order they're first bound, excluding the symbols
+    /// loaded from other modules.
+    pub fn globals(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.globals
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+impl fmt::Debug for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Module")
+            .field("name", &self.name())
+            .field("globals", &self.globals)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of an **evaluation**, passed to native functions so that they
+/// can call back into Starlark (e.g. `sorted` calling its `key`).
+pub struct Evaluator {
+    print: Rc<dyn Fn(&str)>,
+    /// The functions being called, innermost last.
+    stack: Vec<Rc<Function>>,
+}
+
+impl Evaluator {
+    /// **Prints** the given message, as `print` does.
+    pub fn print(&self, message: &str) {
+        (self.print)(message)
+    }
+
+    /// **Calls** the given function with the given arguments.
+    pub fn call(&mut self, function: &Value, arguments: Arguments) -> Result<Value, EvalError> {
+        match function {
+            Value::Function(function) => self.call_function(function, arguments),
+            Value::Builtin(builtin) => builtin.call(self, arguments),
+            _ => Err(EvalError::new(format!(
+                "Value of type {} is not callable",
+                function.type_name()
+            ))),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        function: &Rc<Function>,
+        arguments: Arguments,
+    ) -> Result<Value, EvalError> {
+        // Starlark forbids recursion (even through distinct closures of the
+        // same function), which also bounds the depth of calls.
+        if self.stack.iter().any(|it| it.node == function.node) {
+            return Err(EvalError::new(format!(
+                "Function `{}` called recursively",
+                function.name
+            )));
+        }
+
+        let frame = Rc::new(Frame::new(function.scope, Some(function.parent.clone())));
+        bind_arguments(function, &frame, arguments)?;
+        let env = Env {
+            program: function.program.clone(),
+            frame,
+        };
+
+        self.stack.push(function.clone());
+        let result = self.exec_function(&env, &function.node);
+        self.stack.pop();
+
+        result
+    }
+
+    fn exec_function(&mut self, env: &Env, node: &SyntaxNode) -> Result<Value, EvalError> {
+        if let Some(lambda) = ast::LambdaExpr::cast(node.clone()) {
+            return self.eval_test(env, &expect(lambda.body(), "lambda body")?);
+        }
+
+        let def = expect(ast::DefStmt::cast(node.clone()), "function")?;
+        match self.exec_suite(env, def.body())? {
+            Flow::Return(value) => Ok(value),
+            Flow::Next => Ok(Value::None),
+            Flow::Break | Flow::Continue => Err(outside_loop(node)),
+        }
+    }
+
+    fn exec_module(&mut self, env: &Env) -> Result<(), EvalError> {
+        let file = env.program.file.clone();
+        for statement in file.statements() {
+            match self.exec_statement(env, &statement)? {
+                Flow::Next => {}
+                Flow::Return(_) => {
+                    return Err(EvalError::new("`return` outside of a function")
+                        .at(significant_range(statement.syntax())))
+                }
+                Flow::Break | Flow::Continue => return Err(outside_loop(statement.syntax())),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exec_suite(&mut self, env: &Env, suite: Option<ast::Suite>) -> Result<Flow, EvalError> {
+        let Some(suite) = suite else {
+            return Ok(Flow::Next);
+        };
+        for statement in suite.statements() {
+            let flow = self.exec_statement(env, &statement)?;
+            if !matches!(flow, Flow::Next) {
+                return Ok(flow);
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    fn exec_statement(&mut self, env: &Env, statement: &ast::Statement) -> Result<Flow, EvalError> {
+        match statement {
+            ast::Statement::DefStmt(def) => {
+                let name = expect(def.name(), "function name")?;
+                let function = self.make_function(
+                    env,
+                    def.syntax(),
+                    name.text().to_string(),
+                    def.parameters(),
+                )?;
+                env.bind(name.syntax(), function)?;
+
+                Ok(Flow::Next)
+            }
+            ast::Statement::IfStmt(stmt) => {
+                if self.eval_condition(env, stmt.condition())? {
+                    return self.exec_suite(env, stmt.body());
+                }
+                for clause in stmt.elif_clauses() {
+                    if self.eval_condition(env, clause.condition())? {
+                        return self.exec_suite(env, clause.body());
+                    }
+                }
+
+                self.exec_suite(env, stmt.else_clause().and_then(|it| it.body()))
+            }
+            ast::Statement::ForStmt(stmt) => {
+                let iterable = self.eval_expression(env, &expect(stmt.iterable(), "iterable")?)?;
+                let variables = expect(stmt.loop_variables(), "loop variables")?;
+                let items = ops::iterate(&iterable)
+                    .map_err(|it| it.at(significant_range(stmt.syntax())))?;
+
+                let _guard = IterationGuard::new(&iterable);
+                for item in items {
+                    self.assign(env, variables.syntax(), item)?;
+                    match self.exec_suite(env, stmt.body())? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Next | Flow::Continue => {}
+                    }
+                }
+
+                Ok(Flow::Next)
+            }
+            ast::Statement::SimpleStmt(stmt) => {
+                for small in stmt.small_stmts() {
+                    let flow = self
+                        .exec_small_statement(env, &small)
+                        .map_err(|it| it.at(significant_range(small.syntax())))?;
+                    if !matches!(flow, Flow::Next) {
+                        return Ok(flow);
+                    }
+                }
+
+                Ok(Flow::Next)
+            }
+        }
+    }
+
+    fn exec_small_statement(
+        &mut self,
+        env: &Env,
+        statement: &ast::SmallStmt,
+    ) -> Result<Flow, EvalError> {
+        match statement {
+            ast::SmallStmt::ReturnStmt(stmt) => {
+                let value = match stmt.expression() {
+                    Some(expression) => self.eval_expression(env, &expression)?,
+                    None => Value::None,
+                };
+
+                return Ok(Flow::Return(value));
+            }
+            ast::SmallStmt::BreakStmt(_) => return Ok(Flow::Break),
+            ast::SmallStmt::ContinueStmt(_) => return Ok(Flow::Continue),
+            ast::SmallStmt::PassStmt(_) => {}
+            ast::SmallStmt::AssignStmt(stmt) => self.exec_assignment(env, stmt)?,
+            ast::SmallStmt::ExprStmt(stmt) => {
+                self.eval_expression(env, &expect(stmt.expression(), "expression")?)?;
+            }
+            ast::SmallStmt::LoadStmt(stmt) => self.exec_load(env, stmt)?,
+        }
+
+        Ok(Flow::Next)
+    }
+
+    /// Binds the symbols of a `load` statement to the values **exported** by
+    /// the loaded module, i.e. its globals not starting with `_`.
+    fn exec_load(&mut self, env: &Env, stmt: &ast::LoadStmt) -> Result<(), EvalError> {
+        let label = expect(stmt.module(), "module")?
+            .value()
+            .map_err(|it| syntax_error(&it[0]))?;
+        let module =
+            env.program.loaded.get(&label).ok_or_else(|| {
+                EvalError::new(format!("Cannot load `{label}`, as it wasn't loaded"))
+            })?;
+
+        for symbol in stmt.load_symbols() {
+            let range = significant_range(symbol.syntax());
+            let name = expect(symbol.symbol(), "symbol")?;
+            let exported = name.value().map_err(|it| syntax_error(&it[0]))?;
+            if exported.starts_with('_') {
+                return Err(EvalError::new(format!(
+                    "Cannot load private symbol `{exported}` from `{label}`"
+                ))
+                .at(range));
+            }
+            let value = module.get(&exported).ok_or_else(|| {
+                EvalError::new(format!("Module `{label}` has no symbol `{exported}`")).at(range)
+            })?;
+
+            let bound = match symbol.alias() {
+                Some(alias) => alias.syntax().clone(),
+                None => name.syntax().clone(),
+            };
+            env.bind(&bound, value.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn exec_assignment(&mut self, env: &Env, stmt: &ast::AssignStmt) -> Result<(), EvalError> {
+        let lhs = expect(stmt.lhs(), "assignment target")?;
+        let rhs = self.eval_expression(env, &expect(stmt.rhs(), "assigned value")?)?;
+
+        let op = if stmt.eq_token().is_some() {
+            return self.assign(env, lhs.syntax(), rhs);
+        } else if stmt.pluseq_token().is_some() {
+            BinaryOp::Add
+        } else if stmt.minuseq_token().is_some() {
+            BinaryOp::Sub
+        } else if stmt.stareq_token().is_some() {
+            BinaryOp::Mul
+        } else if stmt.slasheq_token().is_some() {
+            BinaryOp::Div
+        } else if stmt.slashslasheq_token().is_some() {
+            BinaryOp::FloorDiv
+        } else if stmt.percenteq_token().is_some() {
+            BinaryOp::Rem
+        } else if stmt.ampeq_token().is_some() {
+            BinaryOp::BitAnd
+        } else if stmt.pipeeq_token().is_some() {
+            BinaryOp::BitOr
+        } else if stmt.careteq_token().is_some() {
+            BinaryOp::BitXor
+        } else if stmt.shl_eq_token().is_some() {
+            BinaryOp::Shl
+        } else if stmt.shr_eq_token().is_some() {
+            BinaryOp::Shr
+        } else {
+            return Err(EvalError::new("Unsupported assignment operator"));
+        };
+
+        // The target is only evaluated once, e.g. `f()` in `f()[0] += 1`.
+        let place = self.place(env, lhs.syntax())?;
+        let value = self.load_place(env, &place)?;
+        if let (BinaryOp::Add, Value::List(list)) = (op, &value) {
+            // `x += y` extends a list in place.
+            let items = ops::iterate(&rhs)?;
+            list.items_mut()?.extend(items);
+            return self.store_place(env, place, value);
+        }
+
+        let value = ops::binary(op, &value, &rhs)?;
+        self.store_place(env, place, value)
+    }
+
+    /// **Assigns** the given value to the given target, unpacking it for
+    /// tuples and lists of targets (e.g. `a, [b, c] = 1, [2, 3]`).
+    fn assign(&mut self, env: &Env, target: &SyntaxNode, value: Value) -> Result<(), EvalError> {
+        let targets = match target.kind() {
+            SyntaxKind::EXPRESSION | SyntaxKind::LOOP_VARIABLES => {
+                let targets: Vec<_> = target
+                    .children()
+                    .filter(|it| ast::Test::can_cast(it.kind()))
+                    .collect();
+                if targets.len() == 1 && !has_comma(target) {
+                    return self.assign(env, &targets[0], value);
+                }
+                targets
+            }
+            SyntaxKind::TEST => match target.first_child() {
+                Some(child) => return self.assign(env, &child, value),
+                None => return Err(EvalError::new("Missing assignment target")),
+            },
+            SyntaxKind::PRIMARY_EXPR if target.children().count() == 1 => {
+                let operand = expect(target.first_child().and_then(ast::Operand::cast), "operand")?;
+                if let Some(list) = operand.list_expr() {
+                    list.expression()
+                        .map(|it| it.tests().map(|it| it.syntax().clone()).collect())
+                        .unwrap_or_default()
+                } else if operand.l_paren_token().is_some() {
+                    match operand.expression() {
+                        Some(expression) if has_comma(operand.syntax()) => {
+                            expression.tests().map(|it| it.syntax().clone()).collect()
+                        }
+                        Some(expression) => return self.assign(env, expression.syntax(), value),
+                        None => Vec::new(),
+                    }
+                } else {
+                    let place = self.place(env, target)?;
+                    return self.store_place(env, place, value);
+                }
+            }
+            _ => {
+                let place = self.place(env, target)?;
+                return self.store_place(env, place, value);
+            }
+        };
+
+        let items = ops::iterate(&value)?;
+        if items.len() != targets.len() {
+            let problem = if items.len() > targets.len() {
+                "Too many"
+            } else {
+                "Not enough"
+            };
+            return Err(EvalError::new(format!(
+                "{problem} values to unpack (got {}, want {})",
+                items.len(),
+                targets.len()
+            ))
+            .at(significant_range(target)));
+        }
+        for (target, item) in targets.iter().zip(items) {
+            self.assign(env, target, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// The **place** designated by the given (single) target, evaluating the
+    /// operand of an index or a field.
+    fn place(&mut self, env: &Env, target: &SyntaxNode) -> Result<Place, EvalError> {
+        let error =
+            || EvalError::new("Cannot assign to this expression").at(significant_range(target));
+        let primary = match target.kind() {
+            SyntaxKind::EXPRESSION | SyntaxKind::TEST => {
+                let mut children = target
+                    .children()
+                    .filter(|it| ast::Test::can_cast(it.kind()));
+                match (children.next(), children.next()) {
+                    (Some(child), None) if !has_comma(target) => return self.place(env, &child),
+                    _ => return Err(error()),
+                }
+            }
+            _ => ast::PrimaryExpr::cast(target.clone()).ok_or_else(error)?,
+        };
+
+        let suffixes: Vec<_> = primary.syntax().children().skip(1).collect();
+        let Some((last, rest)) = suffixes.split_last() else {
+            let operand = expect(primary.operand(), "operand")?;
+            if let Some(name) = operand.identifier_token() {
+                return Ok(Place::Name(name));
+            }
+            if let Some(expression) = operand
+                .expression()
+                .filter(|_| operand.l_paren_token().is_some())
+            {
+                return self.place(env, expression.syntax());
+            }
+            return Err(error());
+        };
+
+        let value = self.eval_primary(env, &primary, rest.len())?;
+        if let Some(slice) = ast::SliceSuffix::cast(last.clone()) {
+            if let Some(index) = slice.index() {
+                let key = self.eval_expression(env, &index)?;
+                return Ok(Place::Index(value, key));
+            }
+        }
+
+        Err(error())
+    }
+
+    fn load_place(&mut self, env: &Env, place: &Place) -> Result<Value, EvalError> {
+        match place {
+            Place::Name(name) => env.lookup_target(name),
+            Place::Index(value, key) => ops::index(value, key),
+        }
+    }
+
+    fn store_place(&mut self, env: &Env, place: Place, value: Value) -> Result<(), EvalError> {
+        match place {
+            Place::Name(name) => env.bind(&name, value),
+            Place::Index(target, key) => ops::set_index(&target, key, value),
+        }
+    }
+
+    fn make_function(
+        &mut self,
+        env: &Env,
+        node: &SyntaxNode,
+        name: String,
+        parameters: Option<ast::Parameters>,
+    ) -> Result<Value, EvalError> {
+        let scope = env.program.scope(node)?;
+        let parameters = env
+            .program
+            .parameters(parameters, |default| self.eval_test(env, default))?;
+
+        Ok(Value::Function(Rc::new(Function {
+            name,
+            program: env.program.clone(),
+            node: node.clone(),
+            scope,
+            parameters,
+            parent: env.frame.clone(),
+        })))
+    }
+
+    fn eval_condition(
+        &mut self,
+        env: &Env,
+        condition: Option<ast::Test>,
+    ) -> Result<bool, EvalError> {
+        let condition = expect(condition, "condition")?;
+        Ok(self.eval_test(env, &condition)?.truth())
+    }
+
+    /// Evaluates the given expression, which is a **tuple** if it has several
+    /// elements or a trailing comma (e.g. `1, 2` or `1,`).
+    fn eval_expression(
+        &mut self,
+        env: &Env,
+        expression: &ast::Expression,
+    ) -> Result<Value, EvalError> {
+        let tests: Vec<_> = expression.tests().collect();
+        if tests.len() == 1 && !has_comma(expression.syntax()) {
+            return self.eval_test(env, &tests[0]);
+        }
+
+        let items = tests
+            .iter()
+            .map(|it| self.eval_test(env, it))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Value::tuple(items))
+    }
+
+    fn eval_test(&mut self, env: &Env, test: &ast::Test) -> Result<Value, EvalError> {
+        let value = match test {
+            ast::Test::IfExpr(expr) => {
+                if self.eval_condition(env, expr.condition())? {
+                    self.eval_test(env, &expect(expr.then_branch(), "expression")?)
+                } else {
+                    self.eval_test(env, &expect(expr.else_branch(), "expression")?)
+                }
+            }
+            ast::Test::PrimaryExpr(expr) => {
+                let suffixes = expr.syntax().children().count() - 1;
+                self.eval_primary(env, expr, suffixes)
+            }
+            ast::Test::UnaryExpr(expr) => {
+                let op = expect(expr.op_kind(), "operator")?;
+                let operand = self.eval_test(env, &expect(expr.operand(), "operand")?)?;
+                match op {
+                    UnaryOp::Not => Ok(Value::Bool(!operand.truth())),
+                    op => ops::unary(op, &operand),
+                }
+            }
+            ast::Test::BinaryExpr(expr) => {
+                let op = expect(expr.op_kind(), "operator")?;
+                let lhs = self.eval_test(env, &expect(expr.lhs(), "operand")?)?;
+                // `and` and `or` only evaluate their right operand if needed,
+                // and evaluate to either operand.
+                match op {
+                    BinaryOp::And if !lhs.truth() => return Ok(lhs),
+                    BinaryOp::Or if lhs.truth() => return Ok(lhs),
+                    _ => {}
+                }
+                let rhs = self.eval_test(env, &expect(expr.rhs(), "operand")?)?;
+                match op {
+                    BinaryOp::And | BinaryOp::Or => Ok(rhs),
+                    op => ops::binary(op, &lhs, &rhs),
+                }
+            }
+            ast::Test::LambdaExpr(expr) => {
+                self.make_function(env, expr.syntax(), "lambda".to_string(), expr.parameters())
+            }
+        };
+
+        value.map_err(|it| it.at(significant_range(test.syntax())))
+    }
+
+    /// Evaluates the operand of the given primary expression, followed by
+    /// the given number of its **suffixes** (e.g. `.foo`, `(1)` or `[0]`).
+    fn eval_primary(
+        &mut self,
+        env: &Env,
+        expr: &ast::PrimaryExpr,
+        suffixes: usize,
+    ) -> Result<Value, EvalError> {
+        let mut value = self.eval_operand(env, &expect(expr.operand(), "operand")?)?;
+        for suffix in expr.syntax().children().skip(1).take(suffixes) {
+            let range = TextRange::new(
+                significant_range(expr.syntax()).start(),
+                suffix.text_range().end(),
+            );
+            value = self
+                .eval_suffix(env, value, &suffix)
+                .map_err(|it| it.at(range))?;
+        }
+
+        Ok(value)
+    }
+
+    fn eval_suffix(
+        &mut self,
+        env: &Env,
+        value: Value,
+        suffix: &SyntaxNode,
+    ) -> Result<Value, EvalError> {
+        if let Some(dot) = ast::DotSuffix::cast(suffix.clone()) {
+            let name = expect(dot.name(), "attribute name")?;
+            return ops::getattr(&value, name.text());
+        }
+
+        if let Some(call) = ast::CallSuffix::cast(suffix.clone()) {
+            let arguments = self.eval_arguments(env, call.arguments())?;
+            return self.call(&value, arguments);
+        }
+
+        let slice = expect(ast::SliceSuffix::cast(suffix.clone()), "suffix")?;
+        if let Some(index) = slice.index() {
+            let key = self.eval_expression(env, &index)?;
+            return ops::index(&value, &key);
+        }
+
+        let start = match slice.start() {
+            Some(start) => self.eval_expression(env, &start)?,
+            None => Value::None,
+        };
+        let end = match slice.end() {
+            Some(end) => self.eval_test(env, &end)?,
+            None => Value::None,
+        };
+        let step = match slice.step() {
+            Some(step) => self.eval_test(env, &step)?,
+            None => Value::None,
+        };
+
+        ops::slice(&value, &start, &end, &step)
+    }
+
+    fn eval_arguments(
+        &mut self,
+        env: &Env,
+        arguments: Option<ast::Arguments>,
+    ) -> Result<Arguments, EvalError> {
+        let mut result = Arguments::default();
+        for argument in arguments.iter().flat_map(|it| it.arguments()) {
+            let value = self.eval_test(env, &expect(argument.value(), "argument")?)?;
+            let range = significant_range(argument.syntax());
+
+            if argument.star_token().is_some() {
+                result
+                    .positional
+                    .extend(ops::iterate(&value).map_err(|it| it.at(range))?);
+            } else if argument.starstar_token().is_some() {
+                let Value::Dict(dict) = &value else {
+                    return Err(EvalError::new(format!(
+                        "Argument after ** must be a dict, not {}",
+                        value.type_name()
+                    ))
+                    .at(range));
+                };
+                for (key, value) in dict.entries().values() {
+                    let Value::String(name) = key else {
+                        return Err(EvalError::new(format!(
+                            "Keywords must be strings, not {}",
+                            key.type_name()
+                        ))
+                        .at(range));
+                    };
+                    push_named(&mut result, name.to_string(), value.clone())
+                        .map_err(|it| it.at(range))?;
+                }
+            } else if let Some(name) = argument.name() {
+                push_named(&mut result, name.text().to_string(), value)
+                    .map_err(|it| it.at(range))?;
+            } else {
+                result.positional.push(value);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn eval_operand(&mut self, env: &Env, operand: &ast::Operand) -> Result<Value, EvalError> {
+        if let Some(name) = operand.identifier_token() {
+            return env.lookup(&name);
+        }
+
+        if let Some(list) = operand.list_expr() {
+            let items = match list.expression() {
+                Some(expression) => expression
+                    .tests()
+                    .map(|it| self.eval_test(env, &it))
+                    .collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            };
+            return Ok(Value::list(items));
+        }
+
+        if let Some(dict) = operand.dict_expr() {
+            let result = Dict::default();
+            for entry in dict.entries().iter().flat_map(|it| it.entries()) {
+                let (key, value) = self.eval_entry(env, &entry)?;
+                result
+                    .insert(key, value)
+                    .map_err(|it| it.at(significant_range(entry.syntax())))?;
+            }
+            return Ok(Value::Dict(Rc::new(result)));
+        }
+
+        if let Some(comp) = operand.list_comp() {
+            let element = expect(comp.element(), "element")?;
+            let clauses: Vec<_> = comp.comp_clauses().collect();
+            let mut items = Vec::new();
+            self.eval_comprehension(env, comp.syntax(), &clauses, &mut |evaluator, env| {
+                items.push(evaluator.eval_test(env, &element)?);
+                Ok(())
+            })?;
+            return Ok(Value::list(items));
+        }
+
+        if let Some(comp) = operand.dict_comp() {
+            let entry = expect(comp.entry(), "entry")?;
+            let clauses: Vec<_> = comp.comp_clauses().collect();
+            let result = Dict::default();
+            self.eval_comprehension(env, comp.syntax(), &clauses, &mut |evaluator, env| {
+                let (key, value) = evaluator.eval_entry(env, &entry)?;
+                result
+                    .insert(key, value)
+                    .map_err(|it| it.at(significant_range(entry.syntax())))
+            })?;
+            return Ok(Value::Dict(Rc::new(result)));
+        }
+
+        if operand.l_paren_token().is_some() {
+            return match operand.expression() {
+                // A trailing comma belongs to the parentheses, e.g. `(1,)`.
+                Some(expression) if has_comma(operand.syntax()) => {
+                    let items = expression
+                        .tests()
+                        .map(|it| self.eval_test(env, &it))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Value::tuple(items))
+                }
+                Some(expression) => self.eval_expression(env, &expression),
+                None => Ok(Value::tuple(Vec::new())),
+            };
+        }
+
+        let literal = operand
+            .syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .find(|it| {
+                matches!(
+                    it.kind(),
+                    SyntaxKind::INT | SyntaxKind::FLOAT | SyntaxKind::STRING | SyntaxKind::BYTES
+                )
+            });
+        let literal = expect(literal, "literal")?;
+        let value = match literal.kind() {
+            SyntaxKind::INT => ast::Int::cast(literal)
+                .map(|it| {
+                    it.value()
+                        .map(Value::Int)
+                        .or_else(|_| it.big_value().map(Value::int))
+                        .map_err(|it| syntax_error(&it))
+                })
+                .unwrap_or(Ok(Value::None))?,
+            SyntaxKind::FLOAT => ast::Float::cast(literal)
+                .map(|it| it.value().map(Value::Float).map_err(|it| syntax_error(&it)))
+                .unwrap_or(Ok(Value::None))?,
+            SyntaxKind::STRING => ast::String::cast(literal)
+                .map(|it| {
+                    it.value()
+                        .map(Value::from)
+                        .map_err(|it| syntax_error(&it[0]))
+                })
+                .unwrap_or(Ok(Value::None))?,
+            _ => ast::Bytes::cast(literal)
+                .map(|it| {
+                    it.value()
+                        .map(|it| Value::Bytes(it.into()))
+                        .map_err(|it| syntax_error(&it[0]))
+                })
+                .unwrap_or(Ok(Value::None))?,
+        };
+
+        Ok(value)
+    }
+
+    fn eval_entry(&mut self, env: &Env, entry: &ast::Entry) -> Result<(Value, Value), EvalError> {
+        let key = self.eval_test(env, &expect(entry.key(), "key")?)?;
+        let value = self.eval_test(env, &expect(entry.value(), "value")?)?;
+
+        Ok((key, value))
+    }
+
+    /// Evaluates a **comprehension** in a frame of its own, calling `element`
+    /// for each combination of its loop variables passing its conditions.
+    fn eval_comprehension(
+        &mut self,
+        env: &Env,
+        node: &SyntaxNode,
+        clauses: &[ast::CompClause],
+        element: &mut dyn FnMut(&mut Evaluator, &Env) -> Result<(), EvalError>,
+    ) -> Result<(), EvalError> {
+        let env = Env {
+            program: env.program.clone(),
+            frame: Rc::new(Frame::new(
+                env.program.scope(node)?,
+                Some(env.frame.clone()),
+            )),
+        };
+
+        self.eval_clauses(&env, clauses, element)
+    }
+
+    fn eval_clauses(
+        &mut self,
+        env: &Env,
+        clauses: &[ast::CompClause],
+        element: &mut dyn FnMut(&mut Evaluator, &Env) -> Result<(), EvalError>,
+    ) -> Result<(), EvalError> {
+        let Some((clause, rest)) = clauses.split_first() else {
+            return element(self, env);
+        };
+
+        if clause.for_token().is_none() {
+            if self.eval_condition(env, clause.condition())? {
+                self.eval_clauses(env, rest, element)?;
+            }
+            return Ok(());
+        }
+
+        let iterable = self.eval_test(env, &expect(clause.iterable(), "iterable")?)?;
+        let variables = expect(clause.loop_variables(), "loop variables")?;
+        let items =
+            ops::iterate(&iterable).map_err(|it| it.at(significant_range(clause.syntax())))?;
+
+        let _guard = IterationGuard::new(&iterable);
+        for item in items {
+            self.assign(env, variables.syntax(), item)?;
+            self.eval_clauses(env, rest, element)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Evaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Evaluator")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A **resolved** module, shared by the functions it defines.
+pub(crate) struct Program {
+    pub(crate) name: String,
+    pub(crate) file: File,
+    pub(crate) resolution: Resolution,
+    /// The values of the predeclared names (including the builtins).
+    pub(crate) predeclared: Rc<HashMap<String, Value>>,
+    /// What each reference resolves to, by the start of its identifier.
+    references: HashMap<TextSize, Resolved>,
+    /// The binding of each name binding one, by the start of its identifier.
+    definitions: HashMap<TextSize, BindingId>,
+    /// The scopes of the nodes introducing them, by their ranges.
+    scopes: HashMap<TextRange, ScopeId>,
+    /// The modules loaded by `load` statements, by their labels.
+    pub(crate) loaded: HashMap<String, Module>,
+}
+
+impl Program {
+    fn new(
+        name: String,
+        file: File,
+        resolution: Resolution,
+        predeclared: Rc<HashMap<String, Value>>,
+        loaded: HashMap<String, Module>,
+    ) -> Program {
+        let references = resolution
+            .references()
+            .iter()
+            .map(|it| (it.range.start(), it.resolved))
+            .collect();
+        let definitions = resolution
+            .bindings()
+            .flat_map(|(id, binding)| binding.definitions.iter().map(move |it| (it.start(), id)))
+            .collect();
+        let scopes = resolution
+            .scopes()
+            .map(|(id, scope)| (scope.range, id))
+            .collect();
+
+        Program {
+            name,
+            file,
+            resolution,
+            predeclared,
+            references,
+            definitions,
+            scopes,
+            loaded,
+        }
+    }
+
+    /// The scope introduced by the given node (e.g. a `def`).
+    pub(crate) fn scope(&self, node: &SyntaxNode) -> Result<ScopeId, EvalError> {
+        self.scopes
+            .get(&node.text_range())
+            .copied()
+            .ok_or_else(|| EvalError::new("Unresolved scope").at(node.text_range()))
+    }
+
+    /// The **parameters** of a function, evaluating their default values with
+    /// `default`.
+    pub(crate) fn parameters(
+        &self,
+        parameters: Option<ast::Parameters>,
+        mut default: impl FnMut(&ast::Test) -> Result<Value, EvalError>,
+    ) -> Result<Vec<Parameter>, EvalError> {
+        let mut keyword_only = false;
+        let mut values = Vec::new();
+        for parameter in parameters.iter().flat_map(|it| it.parameters()) {
+            let kind = if parameter.starstar_token().is_some() {
+                ParameterKind::Kwargs
+            } else if parameter.star_token().is_some() {
+                keyword_only = true;
+                ParameterKind::Args
+            } else if keyword_only {
+                ParameterKind::KeywordOnly
+            } else {
+                ParameterKind::Normal
+            };
+            // A bare `*` only marks the following parameters as keyword-only.
+            let Some(name) = parameter.name() else {
+                continue;
+            };
+
+            let default = match parameter.default() {
+                Some(it) => Some(default(&it)?),
+                None => None,
+            };
+            values.push(Parameter {
+                name: name.text().to_string(),
+                binding: self.binding(name.syntax())?,
+                kind,
+                default,
+            });
+        }
+
+        Ok(values)
+    }
+
+    /// The binding of the given name binding it (e.g. an assignment target).
+    fn binding(&self, name: &SyntaxToken) -> Result<BindingId, EvalError> {
+        self.definitions
+            .get(&name.text_range().start())
+            .copied()
+            .ok_or_else(|| {
+                EvalError::new(format!("Unresolved name `{}`", name.text())).at(name.text_range())
+            })
+    }
+}
+
+/// The **frame** of a scope, holding the values of its bindings.
+pub(crate) struct Frame {
+    pub(crate) scope: ScopeId,
+    values: RefCell<HashMap<BindingId, Value>>,
+    /// The frame of the enclosing scope, if any.
+    parent: Option<Rc<Frame>>,
+}
+
+impl Frame {
+    pub(crate) fn new(scope: ScopeId, parent: Option<Rc<Frame>>) -> Frame {
+        Frame {
+            scope,
+            values: RefCell::new(HashMap::new()),
+            parent,
+        }
+    }
+
+    pub(crate) fn get(&self, binding: BindingId) -> Option<Value> {
+        self.values.borrow().get(&binding).cloned()
+    }
+
+    pub(crate) fn set(&self, binding: BindingId, value: Value) {
+        self.values.borrow_mut().insert(binding, value);
+    }
+
+    /// The frame of the given scope, i.e. this frame or an enclosing one.
+    fn find(&self, scope: ScopeId) -> Option<&Frame> {
+        let mut frame = self;
+        while frame.scope != scope {
+            frame = frame.parent.as_deref()?;
+        }
+
+        Some(frame)
+    }
+}
+
+/// The **environment** of the code being evaluated.
+struct Env {
+    program: Rc<Program>,
+    frame: Rc<Frame>,
+}
+
+impl Env {
+    /// **Looks up** the value of the given identifier.
+    fn lookup(&self, name: &SyntaxToken) -> Result<Value, EvalError> {
+        let resolved = self.program.references.get(&name.text_range().start());
+        let value = match resolved {
+            Some(Resolved::Binding(id)) => self.get(*id, name),
+            Some(Resolved::Predeclared | Resolved::Builtin) => self
+                .program
+                .predeclared
+                .get(name.text())
+                .cloned()
+                .ok_or_else(|| EvalError::new(format!("`{}` is not defined", name.text()))),
+            Some(Resolved::Undefined) | None => {
+                Err(EvalError::new(format!("`{}` is not defined", name.text())))
+            }
+        };
+
+        value.map_err(|it| it.at(name.text_range()))
+    }
+
+    /// **Looks up** the current value of the given assignment target, e.g. `x`
+    /// in `x += 1`.
+    fn lookup_target(&self, name: &SyntaxToken) -> Result<Value, EvalError> {
+        let id = self.program.binding(name)?;
+        self.get(id, name).map_err(|it| it.at(name.text_range()))
+    }
+
+    fn get(&self, id: BindingId, name: &SyntaxToken) -> Result<Value, EvalError> {
+        let binding = self.program.resolution.binding(id);
+        let value = self.frame.find(binding.scope).and_then(|it| it.get(id));
+        value.ok_or_else(|| {
+            let kind = if binding.scope == self.program.resolution.module() {
+                "Global"
+            } else {
+                "Local"
+            };
+            EvalError::new(format!(
+                "{kind} variable `{}` referenced before assignment",
+                name.text()
+            ))
+        })
+    }
+
+    /// **Binds** the given name (e.g. an assignment target) to the given value.
+    fn bind(&self, name: &SyntaxToken, value: Value) -> Result<(), EvalError> {
+        let id = self.program.binding(name)?;
+        let scope = self.program.resolution.binding(id).scope;
+        let frame = self
+            .frame
+            .find(scope)
+            .ok_or_else(|| EvalError::new("Unresolved scope").at(name.text_range()))?;
+        frame.set(id, value);
+
+        Ok(())
+    }
+}
+
+/// How the execution of statements **continues**.
+enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+/// A **place** values can be assigned to, e.g. `x` or `x[0]`.
+enum Place {
+    Name(SyntaxToken),
+    Index(Value, Value),
+}
+
+/// **Binds** the parameters of the given function to the given arguments in
+/// its frame.
+fn bind_arguments(
+    function: &Function,
+    frame: &Frame,
+    arguments: Arguments,
+) -> Result<(), EvalError> {
+    let name = &function.name;
+    let parameters = &function.parameters;
+    let mut values: Vec<Option<Value>> = vec![None; parameters.len()];
+
+    let mut positional = arguments.positional.into_iter();
+    let normal = parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, it)| it.kind == ParameterKind::Normal);
+    for ((index, _), value) in normal.zip(positional.by_ref()) {
+        values[index] = Some(value);
+    }
+
+    let extra: Vec<Value> = positional.collect();
+    match parameters
+        .iter()
+        .position(|it| it.kind == ParameterKind::Args)
+    {
+        Some(index) => values[index] = Some(Value::tuple(extra)),
+        None if !extra.is_empty() => {
+            let count = parameters
+                .iter()
+                .filter(|it| it.kind == ParameterKind::Normal)
+                .count();
+            return Err(EvalError::new(format!(
+                "{name}() accepts at most {count} positional argument{} ({} given)",
+                if count == 1 { "" } else { "s" },
+                count + extra.len()
+            )));
+        }
+        None => {}
+    }
+
+    let kwargs = parameters
+        .iter()
+        .position(|it| it.kind == ParameterKind::Kwargs)
+        .map(|index| (index, Dict::default()));
+    for (key, value) in arguments.named {
+        let index = parameters.iter().position(|it| {
+            it.name == key && matches!(it.kind, ParameterKind::Normal | ParameterKind::KeywordOnly)
+        });
+        match (index, &kwargs) {
+            (Some(index), _) => {
+                if values[index].replace(value).is_some() {
+                    return Err(EvalError::new(format!(
+                        "{name}() got multiple values for parameter `{key}`"
+                    )));
+                }
+            }
+            (None, Some((_, dict))) => dict.insert(Value::from(key), value)?,
+            (None, None) => {
+                return Err(EvalError::new(format!(
+                    "{name}() got an unexpected keyword argument `{key}`"
+                )))
+            }
+        }
+    }
+    if let Some((index, dict)) = kwargs {
+        values[index] = Some(Value::Dict(Rc::new(dict)));
+    }
+
+    for (parameter, value) in parameters.iter().zip(values) {
+        let value = value.or_else(|| parameter.default.clone()).ok_or_else(|| {
+            EvalError::new(format!(
+                "{name}() missing required argument `{}`",
+                parameter.name
+            ))
+        })?;
+        frame.set(parameter.binding, value);
+    }
+
+    Ok(())
+}
+
+/// Adds a named argument, which must be **unique**.
+fn push_named(arguments: &mut Arguments, name: String, value: Value) -> Result<(), EvalError> {
+    if arguments.named.iter().any(|(it, _)| *it == name) {
+        return Err(EvalError::new(format!(
+            "Keyword argument `{name}` given more than once"
+        )));
+    }
+    arguments.named.push((name, value));
+
+    Ok(())
+}
+
+/// Whether the given node has a **comma** of its own, e.g. `1,` (a tuple)
+/// as opposed to `1`.
+fn has_comma(node: &SyntaxNode) -> bool {
+    node.children_with_tokens()
+        .any(|it| it.kind() == SyntaxKind::COMMA)
+}
+
+/// The **range** of the given node without its surrounding trivia (e.g. the
+/// leading whitespace, which is part of the node).
+fn significant_range(node: &SyntaxNode) -> TextRange {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| {
+            !matches!(
+                it.kind(),
+                SyntaxKind::WHITESPACE
+                    | SyntaxKind::COMMENT
+                    | SyntaxKind::NEWLINE
+                    | SyntaxKind::INDENT
+                    | SyntaxKind::OUTDENT
+            )
+        });
+    let Some(first) = tokens.next() else {
+        return TextRange::empty(node.text_range().start());
+    };
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    TextRange::new(first.text_range().start(), last.text_range().end())
+}
+
+fn expect<T>(node: Option<T>, what: &str) -> Result<T, EvalError> {
+    node.ok_or_else(|| EvalError::new(format!("Missing {what}")))
+}
+
+fn outside_loop(node: &SyntaxNode) -> EvalError {
+    EvalError::new("`break` or `continue` outside of a loop").at(significant_range(node))
+}
+
+fn syntax_error(error: &sky_syntax::SyntaxError) -> EvalError {
+    EvalError::new(error.to_string()).at(error.range())
+}
@@ -0,0 +1,297 @@
+//! The **formatting** of values into strings, i.e. the `%` operator of strings,
+//! `string.format` and the representation of floats.
+
+use crate::{
+    error::EvalError,
+    function::Arguments,
+    ops,
+    value::{float_to_big_int, Value},
+};
+
+/// Formats a **float** as `str` does, i.e. the shortest representation which
+/// reads back as the same float (e.g. `0.1`, `1.0` or `1e+20`).
+pub(crate) fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "+inf" } else { "-inf" }.to_string();
+    }
+
+    let scientific = format!("{value:e}");
+    let (mantissa, exponent) = split_exponent(&scientific);
+    if !(-4..16).contains(&exponent) {
+        return format!("{mantissa}e{}", exponent_suffix(exponent));
+    }
+
+    let plain = value.to_string();
+    if plain.contains('.') {
+        plain
+    } else {
+        format!("{plain}.0")
+    }
+}
+
+/// Formats the given format string with the given **operand** of `%` (e.g.
+/// `"%s: %d" % ("x", 1)`), which is either a tuple of values, a dict for named
+/// conversions (e.g. `%(name)s`), or a single value.
+pub(crate) fn percent(format: &str, operand: &Value) -> Result<String, EvalError> {
+    let values = match operand {
+        Value::Tuple(items) => items.to_vec(),
+        value => vec![value.clone()],
+    };
+    let mapping = matches!(operand, Value::Dict(_)).then_some(operand);
+
+    let mut out = String::with_capacity(format.len());
+    let mut next = 0;
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        let mut conversion = chars.next();
+        let value = if conversion == Some('(') {
+            let key: String = chars.by_ref().take_while(|it| *it != ')').collect();
+            let mapping = mapping.ok_or_else(|| EvalError::new("Format requires a mapping"))?;
+            conversion = chars.next();
+            Some(ops::index(mapping, &Value::from(key))?)
+        } else {
+            None
+        };
+
+        let conversion = conversion.ok_or_else(|| EvalError::new("Incomplete format"))?;
+        if conversion == '%' {
+            out.push('%');
+            continue;
+        }
+        let value = match value {
+            Some(value) => value,
+            None => {
+                let value = values
+                    .get(next)
+                    .cloned()
+                    .ok_or_else(|| EvalError::new("Not enough arguments for format string"))?;
+                next += 1;
+                value
+            }
+        };
+
+        out.push_str(&convert(conversion, &value)?);
+    }
+
+    if mapping.is_none() && next < values.len() {
+        return Err(EvalError::new("Too many arguments for format string"));
+    }
+
+    Ok(out)
+}
+
+/// Applies the given **conversion** of `%` (e.g. `d` for `%d`) to the given
+/// value.
+fn convert(conversion: char, value: &Value) -> Result<String, EvalError> {
+    let invalid = || {
+        EvalError::new(format!(
+            "Invalid argument for %{conversion}: {}",
+            value.type_name()
+        ))
+    };
+    let int = || match value {
+        Value::Int(_) | Value::BigInt(_) => value.big_int().ok_or_else(invalid),
+        Value::Float(float) => float_to_big_int(float.trunc()).ok_or_else(invalid),
+        _ => Err(invalid()),
+    };
+    let float = || match value {
+        Value::Int(_) | Value::BigInt(_) => value.int_to_float(),
+        Value::Float(value) => Ok(*value),
+        _ => Err(invalid()),
+    };
+
+    let converted = match conversion {
+        's' => value.to_string(),
+        'r' => value.repr(),
+        'd' | 'i' => int()?.to_string(),
+        'o' => format!("{:o}", int()?),
+        'x' => format!("{:x}", int()?),
+        'X' => format!("{:X}", int()?),
+        'e' | 'E' | 'f' | 'F' | 'g' | 'G' => {
+            let formatted = format_float_with(conversion.to_ascii_lowercase(), float()?, 6);
+            if conversion.is_ascii_uppercase() {
+                formatted.to_uppercase()
+            } else {
+                formatted
+            }
+        }
+        'c' => match value {
+            Value::Int(code) => u32::try_from(*code)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| EvalError::new(format!("Invalid code point {code}")))?
+                .to_string(),
+            Value::BigInt(code) => {
+                return Err(EvalError::new(format!("Invalid code point {code}")))
+            }
+            Value::String(string) if string.chars().count() == 1 => string.to_string(),
+            _ => return Err(invalid()),
+        },
+        conversion => {
+            return Err(EvalError::new(format!(
+                "Unsupported format character `{conversion}`"
+            )))
+        }
+    };
+
+    Ok(converted)
+}
+
+/// Formats a float as the `e`, `f` or `g` conversions of `%` do, with the
+/// given precision.
+fn format_float_with(conversion: char, value: f64, precision: usize) -> String {
+    if !value.is_finite() {
+        return match value {
+            value if value.is_nan() => "nan".to_string(),
+            value if value > 0.0 => "inf".to_string(),
+            _ => "-inf".to_string(),
+        };
+    }
+
+    match conversion {
+        'e' => {
+            let scientific = format!("{value:.precision$e}");
+            let (mantissa, exponent) = split_exponent(&scientific);
+            format!("{mantissa}e{}", exponent_suffix(exponent))
+        }
+        'f' => format!("{value:.precision$}"),
+        _ => {
+            // `g` uses the scientific notation for small and large exponents,
+            // without trailing zeros.
+            let significant = precision.max(1);
+            let scientific = format!("{value:.*e}", significant - 1);
+            let (mantissa, exponent) = split_exponent(&scientific);
+            if exponent < -4 || exponent >= significant as i32 {
+                let mantissa = trim_zeros(mantissa);
+                format!("{mantissa}e{}", exponent_suffix(exponent))
+            } else {
+                let decimals = (significant as i32 - 1 - exponent).max(0) as usize;
+                trim_zeros(&format!("{value:.decimals$}")).to_string()
+            }
+        }
+    }
+}
+
+/// Formats the given format string as `string.format` does, replacing each
+/// **field** (e.g. `{}`, `{0}`, `{name}` or `{!r}`) with the matching
+/// argument.
+pub(crate) fn format_fields(format: &str, arguments: Arguments) -> Result<String, EvalError> {
+    let mut out = String::with_capacity(format.len());
+    // Whether fields are numbered automatically (`{}`) or manually (`{0}`).
+    let mut automatic = None;
+    let mut next = 0;
+
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '}' => return Err(EvalError::new("Single `}` in format string")),
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(EvalError::new("Unmatched `{` in format string")),
+                    }
+                }
+
+                let (field, conversion) = match field.split_once('!') {
+                    Some((field, conversion)) => (field.to_string(), Some(conversion.to_string())),
+                    None => (field, None),
+                };
+                if field.contains(':') || matches!(&conversion, Some(it) if it.contains(':')) {
+                    return Err(EvalError::new("Format specs aren't supported"));
+                }
+
+                let value = if field.is_empty() {
+                    if automatic == Some(false) {
+                        return Err(EvalError::new(
+                            "Cannot switch from manual to automatic field numbering",
+                        ));
+                    }
+                    automatic = Some(true);
+                    next += 1;
+                    positional(&arguments, next - 1)?
+                } else if let Ok(index) = field.parse::<usize>() {
+                    if automatic == Some(true) {
+                        return Err(EvalError::new(
+                            "Cannot switch from automatic to manual field numbering",
+                        ));
+                    }
+                    automatic = Some(false);
+                    positional(&arguments, index)?
+                } else {
+                    arguments
+                        .named
+                        .iter()
+                        .find(|(name, _)| *name == field)
+                        .map(|(_, value)| value.clone())
+                        .ok_or_else(|| EvalError::new(format!("Keyword `{field}` not found")))?
+                };
+
+                match conversion.as_deref() {
+                    None | Some("s") => out.push_str(&value.to_string()),
+                    Some("r") => out.push_str(&value.repr()),
+                    Some(conversion) => {
+                        return Err(EvalError::new(format!(
+                            "Unknown conversion `!{conversion}`"
+                        )))
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    Ok(out)
+}
+
+fn positional(arguments: &Arguments, index: usize) -> Result<Value, EvalError> {
+    arguments
+        .positional
+        .get(index)
+        .cloned()
+        .ok_or_else(|| EvalError::new(format!("Index {index} out of range in format string")))
+}
+
+/// Splits the scientific notation of Rust (e.g. `1.5e-7`) into its mantissa
+/// and exponent.
+fn split_exponent(scientific: &str) -> (&str, i32) {
+    match scientific.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap_or(0)),
+        None => (scientific, 0),
+    }
+}
+
+/// The exponent as Python prints it, i.e. signed and with at least two
+/// digits (e.g. `+20` or `-07`).
+fn exponent_suffix(exponent: i32) -> String {
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{sign}{:02}", exponent.unsigned_abs())
+}
+
+/// Trims the trailing zeros of the decimals of a number (e.g. `1.50` to
+/// `1.5` and `2.00` to `2`).
+fn trim_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
@@ -0,0 +1,370 @@
+//! **Frozen** modules, i.e. evaluated modules turned into plain data which
+//! can be compared and shared across threads (e.g. cached by a salsa
+//! database), and **thawed** back into modules.
+//!
+//! Functions are frozen as the range of their definition in the source of
+//! their module, whose names are resolved again when thawing them, and
+//! builtins as the name they're predeclared with.
+
+use crate::{
+    error::EvalError,
+    eval::{Frame, Interpreter, Module},
+    function::Function,
+    methods,
+    value::{Struct, Value},
+};
+use num_bigint::BigInt;
+use rowan::TextRange;
+use sky_syntax::{
+    ast::{self, AstNode},
+    File,
+};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+/// A **frozen** module (see [`Module::freeze`] and [`Interpreter::thaw`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenModule {
+    name: String,
+    /// The source of the module, defining its functions.
+    text: Arc<str>,
+    /// The values of its globals, including the symbols it loads.
+    bindings: Vec<(String, FrozenValue)>,
+    /// The modules it loads, sorted by name, which define the functions it
+    /// loads.
+    loaded: Vec<Arc<FrozenModule>>,
+}
+
+impl FrozenModule {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A **frozen** value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FrozenValue {
+    None,
+    Bool(bool),
+    Int(i64),
+    BigInt(BigInt),
+    /// The bits of the float, so that frozen values can be compared.
+    Float(u64),
+    String(Arc<str>),
+    Bytes(Arc<[u8]>),
+    List(Vec<FrozenValue>),
+    Dict(Vec<(FrozenValue, FrozenValue)>),
+    Tuple(Vec<FrozenValue>),
+    /// A function defined by the `def` statement (or `lambda` expression) at
+    /// the given range of the given module, along with the default values of
+    /// its parameters.
+    Function {
+        module: String,
+        name: String,
+        range: TextRange,
+        defaults: Vec<FrozenValue>,
+    },
+    /// A builtin function, by the name it's predeclared with.
+    Builtin(String),
+    /// The method `name` bound to the given receiver (e.g. `x.append`).
+    Method {
+        receiver: Box<FrozenValue>,
+        name: String,
+    },
+    Struct(Vec<(String, FrozenValue)>),
+}
+
+impl Module {
+    /// **Freezes** the module, along with the modules it loads.
+    ///
+    /// Fails if one of its globals can't be frozen, i.e. a function nested
+    /// in another one, a builtin which isn't predeclared, or a list or dict
+    /// containing itself.
+    pub fn freeze(&self) -> Result<Arc<FrozenModule>, EvalError> {
+        if let Some(frozen) = &self.frozen {
+            return Ok(frozen.clone());
+        }
+
+        let mut freezer = Freezer {
+            predeclared: &self.program.predeclared,
+            seen: Vec::new(),
+        };
+        let resolution = &self.program.resolution;
+        let bindings = resolution
+            .scope(resolution.module())
+            .bindings
+            .iter()
+            .filter_map(|id| Some((&resolution.binding(*id).name, self.frame.get(*id)?)))
+            .map(|(name, value)| Ok((name.clone(), freezer.value(&value)?)))
+            .collect::<Result<_, EvalError>>()?;
+        let mut loaded = self
+            .program
+            .loaded
+            .values()
+            .map(Module::freeze)
+            .collect::<Result<Vec<_>, _>>()?;
+        loaded.sort_by(|a, b| a.name.cmp(&b.name));
+        loaded.dedup_by(|a, b| a.name == b.name);
+
+        Ok(Arc::new(FrozenModule {
+            name: self.name().to_string(),
+            text: self.program.file.syntax().to_string().into(),
+            bindings,
+            loaded,
+        }))
+    }
+}
+
+struct Freezer<'a> {
+    predeclared: &'a HashMap<String, Value>,
+    /// The addresses of the lists and dicts being frozen.
+    seen: Vec<usize>,
+}
+
+impl Freezer<'_> {
+    fn value(&mut self, value: &Value) -> Result<FrozenValue, EvalError> {
+        let address = match value {
+            Value::List(list) => Rc::as_ptr(list) as *const () as usize,
+            Value::Dict(dict) => Rc::as_ptr(dict) as *const () as usize,
+            _ => 0,
+        };
+        if address != 0 {
+            if self.seen.contains(&address) {
+                return Err(EvalError::new(format!(
+                    "Cannot freeze a {} containing itself",
+                    value.type_name()
+                )));
+            }
+            self.seen.push(address);
+        }
+
+        let frozen = match value {
+            Value::None => FrozenValue::None,
+            Value::Bool(value) => FrozenValue::Bool(*value),
+            Value::Int(value) => FrozenValue::Int(*value),
+            Value::BigInt(value) => FrozenValue::BigInt(BigInt::clone(value)),
+            Value::Float(value) => FrozenValue::Float(value.to_bits()),
+            Value::String(value) => FrozenValue::String(Arc::from(&**value)),
+            Value::Bytes(value) => FrozenValue::Bytes(Arc::from(&**value)),
+            Value::List(list) => FrozenValue::List(self.values(&list.items())?),
+            Value::Dict(dict) => FrozenValue::Dict(
+                dict.entries()
+                    .values()
+                    .map(|(key, value)| Ok((self.value(key)?, self.value(value)?)))
+                    .collect::<Result<_, EvalError>>()?,
+            ),
+            Value::Tuple(items) => FrozenValue::Tuple(self.values(items)?),
+            Value::Function(function) => self.function(function)?,
+            Value::Builtin(builtin) => match builtin.receiver() {
+                Some(receiver) => FrozenValue::Method {
+                    receiver: Box::new(self.value(receiver)?),
+                    name: builtin.name().to_string(),
+                },
+                None => self
+                    .predeclared
+                    .iter()
+                    .find(|(_, it)| matches!(it, Value::Builtin(it) if Rc::ptr_eq(it, builtin)))
+                    .map(|(name, _)| FrozenValue::Builtin(name.clone()))
+                    .ok_or_else(|| {
+                        EvalError::new(format!(
+                            "Cannot freeze `{}`, as it isn't predeclared",
+                            builtin.name()
+                        ))
+                    })?,
+            },
+            Value::Struct(value) => FrozenValue::Struct(
+                value
+                    .fields
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), self.value(value)?)))
+                    .collect::<Result<_, EvalError>>()?,
+            ),
+        };
+
+        if address != 0 {
+            self.seen.pop();
+        }
+        Ok(frozen)
+    }
+
+    fn values(&mut self, values: &[Value]) -> Result<Vec<FrozenValue>, EvalError> {
+        values.iter().map(|it| self.value(it)).collect()
+    }
+
+    fn function(&mut self, function: &Function) -> Result<FrozenValue, EvalError> {
+        // Only the frame of the module can be rebuilt when thawing.
+        if function.parent.scope != function.program.resolution.module() {
+            return Err(EvalError::new(format!(
+                "Cannot freeze `{}`, as it's nested in another function",
+                function.name
+            )));
+        }
+
+        Ok(FrozenValue::Function {
+            module: function.program.name.clone(),
+            name: function.name.clone(),
+            range: function.node.text_range(),
+            defaults: function
+                .parameters
+                .iter()
+                .filter_map(|it| it.default.as_ref())
+                .map(|it| self.value(it))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Interpreter {
+    /// **Thaws** the given frozen module, along with the modules it loads,
+    /// resolving the names of their functions in the environment of the
+    /// interpreter.
+    pub fn thaw(&self, module: &Arc<FrozenModule>) -> Result<Module, EvalError> {
+        Thawer {
+            interpreter: self,
+            modules: HashMap::new(),
+        }
+        .module(module)
+    }
+}
+
+struct Thawer<'a> {
+    interpreter: &'a Interpreter,
+    /// The modules thawed so far, by name.
+    modules: HashMap<String, Module>,
+}
+
+impl Thawer<'_> {
+    fn module(&mut self, frozen: &Arc<FrozenModule>) -> Result<Module, EvalError> {
+        if let Some(module) = self.modules.get(&frozen.name) {
+            return Ok(module.clone());
+        }
+        let mut loaded = HashMap::new();
+        for module in &frozen.loaded {
+            loaded.insert(module.name.clone(), self.module(module)?);
+        }
+
+        let file = File::parse(&frozen.text).tree();
+        let program = self
+            .interpreter
+            .program(&frozen.name, file, loaded)
+            .map_err(|it| EvalError::new(format!("Cannot thaw `{}`: {it}", frozen.name)))?;
+        let program = Rc::new(program);
+        let frame = Rc::new(Frame::new(program.resolution.module(), None));
+        // The functions of the module are thawed in its (still empty) frame.
+        self.modules.insert(
+            frozen.name.clone(),
+            Module::new(program.clone(), frame.clone(), None),
+        );
+
+        let resolution = &program.resolution;
+        let bindings: HashMap<_, _> = resolution
+            .scope(resolution.module())
+            .bindings
+            .iter()
+            .map(|id| (resolution.binding(*id).name.as_str(), *id))
+            .collect();
+        for (name, value) in &frozen.bindings {
+            let binding = bindings.get(name.as_str()).ok_or_else(|| {
+                EvalError::new(format!("Cannot thaw `{name}` of `{}`", frozen.name))
+            })?;
+            let value = self.value(value)?;
+            value.freeze();
+            frame.set(*binding, value);
+        }
+
+        let module = Module::new(program, frame, Some(frozen.clone()));
+        self.modules.insert(frozen.name.clone(), module.clone());
+
+        Ok(module)
+    }
+
+    fn value(&mut self, frozen: &FrozenValue) -> Result<Value, EvalError> {
+        let value = match frozen {
+            FrozenValue::None => Value::None,
+            FrozenValue::Bool(value) => Value::Bool(*value),
+            FrozenValue::Int(value) => Value::Int(*value),
+            FrozenValue::BigInt(value) => Value::BigInt(Rc::new(value.clone())),
+            FrozenValue::Float(bits) => Value::Float(f64::from_bits(*bits)),
+            FrozenValue::String(value) => Value::String(Rc::from(&**value)),
+            FrozenValue::Bytes(value) => Value::Bytes(Rc::from(&**value)),
+            FrozenValue::List(items) => Value::list(self.values(items)?),
+            FrozenValue::Dict(entries) => Value::dict(
+                entries
+                    .iter()
+                    .map(|(key, value)| Ok((self.value(key)?, self.value(value)?)))
+                    .collect::<Result<_, EvalError>>()?,
+            )?,
+            FrozenValue::Tuple(items) => Value::tuple(self.values(items)?),
+            FrozenValue::Function {
+                module,
+                name,
+                range,
+                defaults,
+            } => self.function(module, name, *range, defaults)?,
+            FrozenValue::Builtin(name) => self
+                .interpreter
+                .predeclared
+                .get(name)
+                .cloned()
+                .ok_or_else(|| {
+                    EvalError::new(format!("Cannot thaw `{name}`, as it isn't predeclared"))
+                })?,
+            FrozenValue::Method { receiver, name } => {
+                let receiver = self.value(receiver)?;
+                methods::method(&receiver, name).ok_or_else(|| {
+                    EvalError::new(format!(
+                        "Cannot thaw `{name}`, as `{}` has no such method",
+                        receiver.type_name()
+                    ))
+                })?
+            }
+            FrozenValue::Struct(fields) => Value::Struct(Rc::new(Struct {
+                fields: fields
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), self.value(value)?)))
+                    .collect::<Result<_, EvalError>>()?,
+            })),
+        };
+
+        Ok(value)
+    }
+
+    fn values(&mut self, values: &[FrozenValue]) -> Result<Vec<Value>, EvalError> {
+        values.iter().map(|it| self.value(it)).collect()
+    }
+
+    fn function(
+        &mut self,
+        module: &str,
+        name: &str,
+        range: TextRange,
+        defaults: &[FrozenValue],
+    ) -> Result<Value, EvalError> {
+        let error = || EvalError::new(format!("Cannot thaw `{name}` of `{module}`"));
+        let module = self.modules.get(module).ok_or_else(error)?;
+        let (program, frame) = (module.program.clone(), module.frame.clone());
+
+        let node = program
+            .file
+            .syntax()
+            .descendants()
+            .filter(|it| it.text_range() == range)
+            .find(|it| ast::DefStmt::can_cast(it.kind()) || ast::LambdaExpr::can_cast(it.kind()))
+            .ok_or_else(error)?;
+        let parameters = ast::DefStmt::cast(node.clone())
+            .and_then(|it| it.parameters())
+            .or_else(|| ast::LambdaExpr::cast(node.clone()).and_then(|it| it.parameters()));
+        let mut defaults = defaults.iter();
+        let parameters = program.parameters(parameters, |_| {
+            self.value(defaults.next().ok_or_else(error)?)
+        })?;
+
+        Ok(Value::Function(Rc::new(Function {
+            name: name.to_string(),
+            scope: program.scope(&node)?,
+            program,
+            node,
+            parameters,
+            parent: frame,
+        })))
+    }
+}
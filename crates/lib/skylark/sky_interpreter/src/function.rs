@@ -0,0 +1,235 @@
+//! **Callable** values, i.e. functions defined in Starlark and builtins
+//! implemented in Rust, and the **arguments** they're called with.
+
+use crate::{
+    error::EvalError,
+    eval::{Evaluator, Frame, Program},
+    value::Value,
+};
+use sky_analyzer::resolve::{BindingId, ScopeId};
+use sky_syntax::SyntaxNode;
+use std::{fmt, rc::Rc};
+
+/// A function defined in Starlark, by a `def` statement or a `lambda`.
+pub struct Function {
+    pub(crate) name: String,
+    pub(crate) program: Rc<Program>,
+    /// The `def` statement or the `lambda` expression.
+    pub(crate) node: SyntaxNode,
+    pub(crate) scope: ScopeId,
+    pub(crate) parameters: Vec<Parameter>,
+    /// The frame the function was defined in, holding the variables of the
+    /// enclosing functions it may refer to.
+    pub(crate) parent: Rc<Frame>,
+}
+
+impl Function {
+    /// The name of the function, or `lambda` for lambdas.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn freeze(&self) {
+        self.parameters
+            .iter()
+            .filter_map(|it| it.default.as_ref())
+            .for_each(Value::freeze);
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<function {}>", self.name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParameterKind {
+    /// A parameter which can be passed by position or by name.
+    Normal,
+    /// A parameter after `*` or `*args`, which can only be passed by name.
+    KeywordOnly,
+    /// `*args`
+    Args,
+    /// `**kwargs`
+    Kwargs,
+}
+
+#[derive(Debug)]
+pub(crate) struct Parameter {
+    pub(crate) name: String,
+    pub(crate) binding: BindingId,
+    pub(crate) kind: ParameterKind,
+    /// The default value, evaluated when the function was defined.
+    pub(crate) default: Option<Value>,
+}
+
+/// The signature of a **native function**, implemented in Rust.
+pub type NativeFunction = dyn Fn(&mut Evaluator, Arguments) -> Result<Value, EvalError>;
+
+/// The signature of a **method** of a builtin type (e.g. `list.append`).
+pub(crate) type Method = fn(&mut Evaluator, &Value, Arguments) -> Result<Value, EvalError>;
+
+/// A **builtin** function or method, implemented in Rust.
+pub struct Builtin {
+    name: String,
+    native: Native,
+}
+
+enum Native {
+    Function(Box<NativeFunction>),
+    Method { receiver: Value, method: Method },
+}
+
+impl Builtin {
+    /// A builtin **function** named `name`, e.g. to predeclare it in a
+    /// [`Interpreter`](crate::Interpreter).
+    pub fn new(
+        name: impl Into<String>,
+        function: impl Fn(&mut Evaluator, Arguments) -> Result<Value, EvalError> + 'static,
+    ) -> Builtin {
+        Builtin {
+            name: name.into(),
+            native: Native::Function(Box::new(function)),
+        }
+    }
+
+    /// The method `name` **bound** to the given receiver (e.g. `x.append`).
+    pub(crate) fn method(name: &str, receiver: Value, method: Method) -> Builtin {
+        Builtin {
+            name: name.to_string(),
+            native: Native::Method { receiver, method },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The value the method is bound to, or `None` for functions.
+    pub fn receiver(&self) -> Option<&Value> {
+        match &self.native {
+            Native::Function(_) => None,
+            Native::Method { receiver, .. } => Some(receiver),
+        }
+    }
+
+    pub(crate) fn call(
+        &self,
+        evaluator: &mut Evaluator,
+        arguments: Arguments,
+    ) -> Result<Value, EvalError> {
+        match &self.native {
+            Native::Function(function) => function(evaluator, arguments),
+            Native::Method { receiver, method } => method(evaluator, receiver, arguments),
+        }
+    }
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<built-in function {}>", self.name)
+    }
+}
+
+impl From<Builtin> for Value {
+    fn from(builtin: Builtin) -> Value {
+        Value::Builtin(Rc::new(builtin))
+    }
+}
+
+/// The **arguments** of a call, e.g. `1` and `sep = ","` in `f(1, sep = ",")`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Arguments {
+    pub positional: Vec<Value>,
+    /// The named arguments, in order.
+    pub named: Vec<(String, Value)>,
+}
+
+impl Arguments {
+    pub fn positional(values: Vec<Value>) -> Arguments {
+        Arguments {
+            positional: values,
+            named: Vec::new(),
+        }
+    }
+
+    /// **Unpacks** the arguments into the given parameters, which can be
+    /// passed by position or by name. The names of optional parameters end
+    /// with `?` (e.g. `key?`), and those of positional-only parameters start
+    /// with `$`.
+    pub fn unpack<const N: usize>(
+        self,
+        function: &str,
+        parameters: [&str; N],
+    ) -> Result<[Option<Value>; N], EvalError> {
+        let mut values: [Option<Value>; N] = std::array::from_fn(|_| None);
+        if self.positional.len() > N {
+            return Err(EvalError::new(format!(
+                "{function}() accepts at most {N} positional argument{} ({} given)",
+                if N == 1 { "" } else { "s" },
+                self.positional.len()
+            )));
+        }
+
+        let names = parameters.map(|it| it.trim_start_matches('$').trim_end_matches('?'));
+        for (index, value) in self.positional.into_iter().enumerate() {
+            values[index] = Some(value);
+        }
+        for (name, value) in self.named {
+            let index = names
+                .iter()
+                .zip(parameters)
+                .position(|(it, parameter)| *it == name && !parameter.starts_with('$'))
+                .ok_or_else(|| {
+                    EvalError::new(format!(
+                        "{function}() got an unexpected keyword argument `{name}`"
+                    ))
+                })?;
+            if values[index].replace(value).is_some() {
+                return Err(EvalError::new(format!(
+                    "{function}() got multiple values for argument `{name}`"
+                )));
+            }
+        }
+
+        for (index, parameter) in parameters.iter().enumerate() {
+            if values[index].is_none() && !parameter.ends_with('?') {
+                return Err(EvalError::new(format!(
+                    "{function}() missing required argument `{}`",
+                    names[index]
+                )));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// The positional arguments, of which there must be between `min` and
+    /// `max`, failing if there are named arguments.
+    pub fn positional_only(
+        self,
+        function: &str,
+        min: usize,
+        max: usize,
+    ) -> Result<Vec<Value>, EvalError> {
+        if let Some((name, _)) = self.named.first() {
+            return Err(EvalError::new(format!(
+                "{function}() got an unexpected keyword argument `{name}`"
+            )));
+        }
+        let count = self.positional.len();
+        if count < min || count > max {
+            let expected = match (min, max) {
+                (min, max) if min == max => format!("{min}"),
+                (min, usize::MAX) => format!("at least {min}"),
+                (min, max) => format!("between {min} and {max}"),
+            };
+            return Err(EvalError::new(format!(
+                "{function}() takes {expected} positional arguments ({count} given)"
+            )));
+        }
+
+        Ok(self.positional)
+    }
+}
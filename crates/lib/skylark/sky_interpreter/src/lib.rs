@@ -0,0 +1,49 @@
+//! An **interpreter** for Starlark, built on top of `sky_syntax` and
+//! `sky_analyzer`.
+//!
+//! The interpreter evaluates a module by walking its syntax tree, after
+//! resolving its names with [`sky_analyzer::resolve`]:
+//!
+//! - [`Interpreter`] evaluates modules in an environment of predeclared
+//!   names (the builtins of Starlark, `struct`, and any value predeclared by
+//!   the embedder), returning a [`Module`] holding its frozen globals.
+//! - [`Value`] is a Starlark value, i.e. `None`, a bool, an int, a float, a
+//!   string, bytes, a list, a dict, a tuple, a function or a struct.
+//! - [`Builtin`] wraps a Rust closure as a Starlark function, to extend the
+//!   environment of the modules (e.g. with the rules of a `BUCK` file).
+//! - [`FrozenModule`] is an evaluated module frozen into plain data, e.g. to
+//!   cache it in a salsa database, which can be thawed back into a module.
+//!
+//! ```
+//! use sky_interpreter::{Arguments, Interpreter, Value};
+//!
+//! let interpreter = Interpreter::new();
+//! let module = interpreter
+//!     .eval("greet.star", "def greet(name):\n    return \"Hello, %s!\" % name\n")
+//!     .unwrap();
+//!
+//! let greeting = interpreter
+//!     .call(
+//!         module.get("greet").unwrap(),
+//!         Arguments::positional(vec![Value::from("world")]),
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(greeting.to_string(), "Hello, world!");
+//! ```
+
+mod builtins;
+mod error;
+mod eval;
+mod format;
+mod frozen;
+mod function;
+mod methods;
+mod ops;
+mod value;
+
+pub use error::{Error, EvalError};
+pub use eval::{Evaluator, Interpreter, Module};
+pub use frozen::FrozenModule;
+pub use function::{Arguments, Builtin, Function, NativeFunction};
+pub use value::{Dict, Entries, Key, List, Struct, Value};
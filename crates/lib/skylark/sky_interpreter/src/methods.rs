@@ -0,0 +1,1094 @@
+//! The **methods** of the builtin types, e.g. `string.split` or
+//! `list.append`.
+
+use crate::{
+    error::EvalError,
+    eval::Evaluator,
+    format::format_fields,
+    function::{Arguments, Builtin, Method},
+    ops,
+    value::{Dict, Value},
+};
+
+const STRING_METHODS: &[(&str, Method)] = &[
+    ("capitalize", string_capitalize),
+    ("codepoint_ords", string_codepoint_ords),
+    ("codepoints", string_codepoints),
+    ("count", string_count),
+    ("elem_ords", string_elem_ords),
+    ("elems", string_elems),
+    ("endswith", string_endswith),
+    ("find", string_find),
+    ("format", string_format),
+    ("index", string_index),
+    ("isalnum", string_isalnum),
+    ("isalpha", string_isalpha),
+    ("isdigit", string_isdigit),
+    ("islower", string_islower),
+    ("isspace", string_isspace),
+    ("istitle", string_istitle),
+    ("isupper", string_isupper),
+    ("join", string_join),
+    ("lower", string_lower),
+    ("lstrip", string_lstrip),
+    ("partition", string_partition),
+    ("removeprefix", string_removeprefix),
+    ("removesuffix", string_removesuffix),
+    ("replace", string_replace),
+    ("rfind", string_rfind),
+    ("rindex", string_rindex),
+    ("rpartition", string_rpartition),
+    ("rsplit", string_rsplit),
+    ("rstrip", string_rstrip),
+    ("split", string_split),
+    ("splitlines", string_splitlines),
+    ("startswith", string_startswith),
+    ("strip", string_strip),
+    ("title", string_title),
+    ("upper", string_upper),
+];
+
+const BYTES_METHODS: &[(&str, Method)] = &[("elems", bytes_elems)];
+
+const LIST_METHODS: &[(&str, Method)] = &[
+    ("append", list_append),
+    ("clear", list_clear),
+    ("extend", list_extend),
+    ("index", list_index),
+    ("insert", list_insert),
+    ("pop", list_pop),
+    ("remove", list_remove),
+];
+
+const DICT_METHODS: &[(&str, Method)] = &[
+    ("clear", dict_clear),
+    ("get", dict_get),
+    ("items", dict_items),
+    ("keys", dict_keys),
+    ("pop", dict_pop),
+    ("popitem", dict_popitem),
+    ("setdefault", dict_setdefault),
+    ("update", dict_update),
+    ("values", dict_values),
+];
+
+/// The methods of the type of the given value.
+pub(crate) fn methods(value: &Value) -> &'static [(&'static str, Method)] {
+    match value {
+        Value::String(_) => STRING_METHODS,
+        Value::Bytes(_) => BYTES_METHODS,
+        Value::List(_) => LIST_METHODS,
+        Value::Dict(_) => DICT_METHODS,
+        _ => &[],
+    }
+}
+
+/// The method `name` of the given value, **bound** to it.
+pub(crate) fn method(value: &Value, name: &str) -> Option<Value> {
+    let (name, method) = methods(value).iter().find(|(it, _)| *it == name)?;
+    Some(Builtin::method(name, value.clone(), *method).into())
+}
+
+/// **Updates** the given dict with the given pairs (a dict or an iterable of
+/// key/value pairs) followed by the given named arguments, as `dict` and
+/// `dict.update` do.
+pub(crate) fn update_dict(
+    dict: &Dict,
+    pairs: Option<Value>,
+    named: Vec<(String, Value)>,
+) -> Result<(), EvalError> {
+    match pairs {
+        Some(Value::Dict(other)) => {
+            let entries: Vec<_> = other.entries().values().cloned().collect();
+            for (key, value) in entries {
+                dict.insert(key, value)?;
+            }
+        }
+        Some(pairs) => {
+            for (index, pair) in ops::iterate(&pairs)?.into_iter().enumerate() {
+                let pair = ops::iterate(&pair).map_err(|_| {
+                    EvalError::new(format!(
+                        "Dictionary update sequence element #{index} is not iterable"
+                    ))
+                })?;
+                let [key, value]: [Value; 2] = pair.try_into().map_err(|it: Vec<Value>| {
+                    EvalError::new(format!(
+                        "Dictionary update sequence element #{index} has length {}, not 2",
+                        it.len()
+                    ))
+                })?;
+                dict.insert(key, value)?;
+            }
+        }
+        None => {}
+    }
+
+    for (name, value) in named {
+        dict.insert(Value::from(name), value)?;
+    }
+
+    Ok(())
+}
+
+fn string(receiver: &Value) -> &str {
+    match receiver {
+        Value::String(string) => string,
+        _ => "",
+    }
+}
+
+fn string_argument(function: &str, value: &Value) -> Result<String, EvalError> {
+    match value {
+        Value::String(string) => Ok(string.to_string()),
+        value => Err(EvalError::new(format!(
+            "{function}() expects a string, not {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn int_argument(function: &str, value: &Value) -> Result<i64, EvalError> {
+    match value {
+        Value::Int(value) => Ok(*value),
+        Value::BigInt(value) => Err(EvalError::new(format!(
+            "{function}() argument out of range: {value}"
+        ))),
+        value => Err(EvalError::new(format!(
+            "{function}() expects an int, not {}",
+            value.type_name()
+        ))),
+    }
+}
+
+/// The part of the receiver between the given (optional) `start` and `end`
+/// **indices** of code points, along with the index of its first code point.
+fn substring(
+    function: &str,
+    receiver: &str,
+    start: Option<Value>,
+    end: Option<Value>,
+) -> Result<(String, usize), EvalError> {
+    let len = receiver.chars().count() as i64;
+    let bound = |value: Option<Value>, default: i64| -> Result<i64, EvalError> {
+        let index = match value {
+            None | Some(Value::None) => return Ok(default),
+            Some(value) => int_argument(function, &value)?,
+        };
+        Ok(if index < 0 {
+            (index + len).max(0)
+        } else {
+            index.min(len)
+        })
+    };
+    let start = bound(start, 0)?;
+    let end = bound(end, len)?.max(start);
+
+    let substring = receiver
+        .chars()
+        .skip(start as usize)
+        .take((end - start) as usize)
+        .collect();
+
+    Ok((substring, start as usize))
+}
+
+/// The index of the first (or last) occurrence of `sub` in the receiver,
+/// counted in code points.
+fn find(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    last: bool,
+) -> Result<Option<usize>, EvalError> {
+    let [sub, start, end] = arguments.unpack(function, ["sub", "start?", "end?"])?;
+    let sub = string_argument(function, &sub.unwrap_or(Value::None))?;
+    let (substring, offset) = substring(function, string(receiver), start, end)?;
+
+    let found = if last {
+        substring.rfind(&sub)
+    } else {
+        substring.find(&sub)
+    };
+
+    Ok(found.map(|it| offset + substring[..it].chars().count()))
+}
+
+fn string_capitalize(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("capitalize", [])?;
+    let mut chars = string(receiver).chars();
+    let capitalized = match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    };
+
+    Ok(Value::from(capitalized))
+}
+
+fn string_count(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [sub, start, end] = arguments.unpack("count", ["sub", "start?", "end?"])?;
+    let sub = string_argument("count", &sub.unwrap_or(Value::None))?;
+    let (substring, _) = substring("count", string(receiver), start, end)?;
+    let count = if sub.is_empty() {
+        substring.chars().count() + 1
+    } else {
+        substring.matches(&sub).count()
+    };
+
+    Ok(Value::Int(count as i64))
+}
+
+fn string_codepoints(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("codepoints", [])?;
+    Ok(code_points(receiver))
+}
+
+fn string_codepoint_ords(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("codepoint_ords", [])?;
+    Ok(code_point_ords(receiver))
+}
+
+/// The ords of the elements of the receiver, which are its code points as
+/// strings are sequences of code points.
+fn string_elem_ords(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("elem_ords", [])?;
+    Ok(code_point_ords(receiver))
+}
+
+/// The code points of the receiver, as strings of one code point.
+fn code_points(receiver: &Value) -> Value {
+    let code_points = string(receiver)
+        .chars()
+        .map(|it| Value::from(it.to_string()))
+        .collect();
+
+    Value::list(code_points)
+}
+
+fn code_point_ords(receiver: &Value) -> Value {
+    let ords = string(receiver)
+        .chars()
+        .map(|it| Value::Int(u32::from(it).into()))
+        .collect();
+
+    Value::list(ords)
+}
+
+fn string_elems(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("elems", [])?;
+    Ok(code_points(receiver))
+}
+
+/// Whether the receiver has the given **affix** (a string or a tuple of
+/// strings), at its start or its end.
+fn has_affix(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    start: bool,
+) -> Result<Value, EvalError> {
+    let [affix, from, to] = arguments.unpack(function, ["affix", "start?", "end?"])?;
+    let (substring, _) = substring(function, string(receiver), from, to)?;
+    let affixes = match affix.unwrap_or(Value::None) {
+        Value::Tuple(items) => items
+            .iter()
+            .map(|it| string_argument(function, it))
+            .collect::<Result<Vec<_>, _>>()?,
+        affix => vec![string_argument(function, &affix)?],
+    };
+
+    let found = affixes.iter().any(|affix| {
+        if start {
+            substring.starts_with(affix.as_str())
+        } else {
+            substring.ends_with(affix.as_str())
+        }
+    });
+
+    Ok(Value::Bool(found))
+}
+
+fn string_endswith(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    has_affix("endswith", receiver, arguments, false)
+}
+
+fn string_startswith(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    has_affix("startswith", receiver, arguments, true)
+}
+
+fn string_find(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let index = find("find", receiver, arguments, false)?;
+    Ok(Value::Int(index.map_or(-1, |it| it as i64)))
+}
+
+fn string_rfind(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let index = find("rfind", receiver, arguments, true)?;
+    Ok(Value::Int(index.map_or(-1, |it| it as i64)))
+}
+
+fn string_index(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let index = find("index", receiver, arguments, false)?;
+    index
+        .map(|it| Value::Int(it as i64))
+        .ok_or_else(|| EvalError::new("Substring not found"))
+}
+
+fn string_rindex(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let index = find("rindex", receiver, arguments, true)?;
+    index
+        .map(|it| Value::Int(it as i64))
+        .ok_or_else(|| EvalError::new("Substring not found"))
+}
+
+fn string_format(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    format_fields(string(receiver), arguments).map(Value::from)
+}
+
+/// Whether the receiver is non-empty and all of its code points pass the
+/// given test.
+fn all_chars(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    test: fn(char) -> bool,
+) -> Result<Value, EvalError> {
+    arguments.unpack(function, [])?;
+    let string = string(receiver);
+    Ok(Value::Bool(!string.is_empty() && string.chars().all(test)))
+}
+
+fn string_isalnum(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    all_chars("isalnum", receiver, arguments, char::is_alphanumeric)
+}
+
+fn string_isalpha(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    all_chars("isalpha", receiver, arguments, char::is_alphabetic)
+}
+
+fn string_isdigit(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    all_chars("isdigit", receiver, arguments, |it| it.is_ascii_digit())
+}
+
+fn string_isspace(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    all_chars("isspace", receiver, arguments, char::is_whitespace)
+}
+
+/// Whether the receiver has **cased** code points, all of which pass the
+/// given test.
+fn cased(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    test: fn(char) -> bool,
+) -> Result<Value, EvalError> {
+    arguments.unpack(function, [])?;
+    let mut cased = string(receiver)
+        .chars()
+        .filter(|it| it.is_lowercase() || it.is_uppercase())
+        .peekable();
+    let is_cased = cased.peek().is_some();
+
+    Ok(Value::Bool(is_cased && cased.all(test)))
+}
+
+fn string_islower(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    cased("islower", receiver, arguments, char::is_lowercase)
+}
+
+fn string_isupper(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    cased("isupper", receiver, arguments, char::is_uppercase)
+}
+
+fn string_istitle(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("istitle", [])?;
+    // Uppercase code points may only follow uncased ones, and lowercase code
+    // points only cased ones.
+    let mut previous_cased = false;
+    let mut has_cased = false;
+    for c in string(receiver).chars() {
+        if c.is_uppercase() {
+            if previous_cased {
+                return Ok(Value::Bool(false));
+            }
+            previous_cased = true;
+            has_cased = true;
+        } else if c.is_lowercase() {
+            if !previous_cased {
+                return Ok(Value::Bool(false));
+            }
+            previous_cased = true;
+            has_cased = true;
+        } else {
+            previous_cased = false;
+        }
+    }
+
+    Ok(Value::Bool(has_cased))
+}
+
+fn string_join(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("join", ["iterable"])?;
+    let items = ops::iterate(&iterable.unwrap_or(Value::None))?
+        .iter()
+        .map(|it| string_argument("join", it))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Value::from(items.join(string(receiver))))
+}
+
+fn string_lower(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("lower", [])?;
+    Ok(Value::from(string(receiver).to_lowercase()))
+}
+
+fn string_upper(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("upper", [])?;
+    Ok(Value::from(string(receiver).to_uppercase()))
+}
+
+fn string_title(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("title", [])?;
+    let mut previous_cased = false;
+    let mut title = String::new();
+    for c in string(receiver).chars() {
+        if previous_cased {
+            title.extend(c.to_lowercase());
+        } else {
+            title.extend(c.to_uppercase());
+        }
+        previous_cased = c.is_lowercase() || c.is_uppercase();
+    }
+
+    Ok(Value::from(title))
+}
+
+/// **Strips** the given code points (or whitespace) from the start and/or
+/// the end of the receiver.
+fn strip(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    start: bool,
+    end: bool,
+) -> Result<Value, EvalError> {
+    let [chars] = arguments.unpack(function, ["chars?"])?;
+    let chars = match chars {
+        None | Some(Value::None) => None,
+        Some(chars) => Some(string_argument(function, &chars)?),
+    };
+    let strip = |c: char| match &chars {
+        Some(chars) => chars.contains(c),
+        None => c.is_whitespace(),
+    };
+
+    let mut stripped = string(receiver);
+    if start {
+        stripped = stripped.trim_start_matches(strip);
+    }
+    if end {
+        stripped = stripped.trim_end_matches(strip);
+    }
+
+    Ok(Value::from(stripped))
+}
+
+fn string_strip(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    strip("strip", receiver, arguments, true, true)
+}
+
+fn string_lstrip(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    strip("lstrip", receiver, arguments, true, false)
+}
+
+fn string_rstrip(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    strip("rstrip", receiver, arguments, false, true)
+}
+
+/// **Partitions** the receiver around the first (or last) occurrence of the
+/// given separator.
+fn partition(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    last: bool,
+) -> Result<Value, EvalError> {
+    let [separator] = arguments.unpack(function, ["sep"])?;
+    let separator = string_argument(function, &separator.unwrap_or(Value::None))?;
+    if separator.is_empty() {
+        return Err(EvalError::new("Empty separator"));
+    }
+
+    let string = string(receiver);
+    let split = if last {
+        string.rsplit_once(separator.as_str())
+    } else {
+        string.split_once(separator.as_str())
+    };
+    let parts = match split {
+        Some((before, after)) => [before, &separator, after],
+        None if last => ["", "", string],
+        None => [string, "", ""],
+    };
+
+    Ok(Value::tuple(parts.into_iter().map(Value::from).collect()))
+}
+
+fn string_partition(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    partition("partition", receiver, arguments, false)
+}
+
+fn string_rpartition(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    partition("rpartition", receiver, arguments, true)
+}
+
+fn string_removeprefix(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [prefix] = arguments.unpack("removeprefix", ["prefix"])?;
+    let prefix = string_argument("removeprefix", &prefix.unwrap_or(Value::None))?;
+    let string = string(receiver);
+
+    Ok(Value::from(
+        string.strip_prefix(prefix.as_str()).unwrap_or(string),
+    ))
+}
+
+fn string_removesuffix(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [suffix] = arguments.unpack("removesuffix", ["suffix"])?;
+    let suffix = string_argument("removesuffix", &suffix.unwrap_or(Value::None))?;
+    let string = string(receiver);
+
+    Ok(Value::from(
+        string.strip_suffix(suffix.as_str()).unwrap_or(string),
+    ))
+}
+
+fn string_replace(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [old, new, count] = arguments.unpack("replace", ["old", "new", "count?"])?;
+    let old = string_argument("replace", &old.unwrap_or(Value::None))?;
+    let new = string_argument("replace", &new.unwrap_or(Value::None))?;
+    let string = string(receiver);
+
+    let replaced = match count {
+        Some(count) => match int_argument("replace", &count)? {
+            count if count < 0 => string.replace(&old, &new),
+            count => string.replacen(&old, &new, count as usize),
+        },
+        None => string.replace(&old, &new),
+    };
+
+    Ok(Value::from(replaced))
+}
+
+/// **Splits** the receiver around the given separator (or whitespace), at
+/// most `maxsplit` times from its start or its end.
+fn split(
+    function: &str,
+    receiver: &Value,
+    arguments: Arguments,
+    from_end: bool,
+) -> Result<Value, EvalError> {
+    let [separator, maxsplit] = arguments.unpack(function, ["sep?", "maxsplit?"])?;
+    let limit = match maxsplit {
+        None | Some(Value::None) => usize::MAX,
+        Some(maxsplit) => match int_argument(function, &maxsplit)? {
+            maxsplit if maxsplit < 0 => usize::MAX,
+            maxsplit => maxsplit as usize + 1,
+        },
+    };
+    let string = string(receiver);
+
+    let mut parts: Vec<&str> = match separator {
+        None | Some(Value::None) => split_whitespace(string, limit, from_end),
+        Some(separator) => {
+            let separator = string_argument(function, &separator)?;
+            if separator.is_empty() {
+                return Err(EvalError::new("Empty separator"));
+            }
+            if from_end {
+                string.rsplitn(limit, separator.as_str()).collect()
+            } else {
+                string.splitn(limit, separator.as_str()).collect()
+            }
+        }
+    };
+    if from_end {
+        parts.reverse();
+    }
+
+    Ok(Value::list(parts.into_iter().map(Value::from).collect()))
+}
+
+/// Splits the given string around runs of whitespace into at most `limit`
+/// parts, ignoring leading and trailing whitespace (except in the last part).
+fn split_whitespace(string: &str, limit: usize, from_end: bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = if from_end {
+        string.trim_end()
+    } else {
+        string.trim_start()
+    };
+    while !rest.is_empty() {
+        if parts.len() + 1 == limit {
+            parts.push(rest);
+            break;
+        }
+        if from_end {
+            let (before, part) = match rest.rfind(char::is_whitespace) {
+                Some(index) => rest.split_at(index),
+                None => ("", rest),
+            };
+            parts.push(part.trim_start());
+            rest = before.trim_end();
+        } else {
+            let (part, after) = match rest.find(char::is_whitespace) {
+                Some(index) => rest.split_at(index),
+                None => (rest, ""),
+            };
+            parts.push(part);
+            rest = after.trim_start();
+        }
+    }
+
+    parts
+}
+
+fn string_split(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    split("split", receiver, arguments, false)
+}
+
+fn string_rsplit(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    split("rsplit", receiver, arguments, true)
+}
+
+fn string_splitlines(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [keepends] = arguments.unpack("splitlines", ["keepends?"])?;
+    let keepends = matches!(keepends, Some(keepends) if keepends.truth());
+
+    let lines = string(receiver)
+        .split_inclusive('\n')
+        .map(|line| {
+            if keepends {
+                line
+            } else {
+                line.trim_end_matches('\n').trim_end_matches('\r')
+            }
+        })
+        .map(Value::from)
+        .collect();
+
+    Ok(Value::list(lines))
+}
+
+fn bytes_elems(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("elems", [])?;
+    let elems = match receiver {
+        Value::Bytes(bytes) => bytes.iter().map(|it| Value::Int((*it).into())).collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(Value::list(elems))
+}
+
+fn list_append(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [item] = arguments.unpack("append", ["$item"])?;
+    if let Value::List(list) = receiver {
+        list.items_mut()?.push(item.unwrap_or(Value::None));
+    }
+
+    Ok(Value::None)
+}
+
+fn list_clear(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("clear", [])?;
+    if let Value::List(list) = receiver {
+        list.items_mut()?.clear();
+    }
+
+    Ok(Value::None)
+}
+
+fn list_extend(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [iterable] = arguments.unpack("extend", ["$iterable"])?;
+    let items = ops::iterate(&iterable.unwrap_or(Value::None))?;
+    if let Value::List(list) = receiver {
+        list.items_mut()?.extend(items);
+    }
+
+    Ok(Value::None)
+}
+
+fn list_index(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [item, start, end] = arguments.unpack("index", ["$item", "$start?", "$end?"])?;
+    let item = item.unwrap_or(Value::None);
+    let Value::List(list) = receiver else {
+        return Ok(Value::None);
+    };
+
+    let items = list.items();
+    let len = items.len() as i64;
+    let bound = |value: Option<Value>, default: i64| -> Result<usize, EvalError> {
+        let index = match value {
+            None | Some(Value::None) => default,
+            Some(value) => int_argument("index", &value)?,
+        };
+        let index = if index < 0 {
+            (index + len).max(0)
+        } else {
+            index.min(len)
+        };
+        Ok(index as usize)
+    };
+    let (start, end) = (bound(start, 0)?, bound(end, len)?);
+
+    (start..end.max(start))
+        .find(|index| items[*index] == item)
+        .map(|index| Value::Int(index as i64))
+        .ok_or_else(|| EvalError::new(format!("Value {} not found in list", item.repr())))
+}
+
+fn list_insert(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [index, item] = arguments.unpack("insert", ["$index", "$item"])?;
+    let index = int_argument("insert", &index.unwrap_or(Value::None))?;
+    if let Value::List(list) = receiver {
+        let mut items = list.items_mut()?;
+        let len = items.len() as i64;
+        let index = if index < 0 {
+            (index + len).max(0)
+        } else {
+            index.min(len)
+        };
+        items.insert(index as usize, item.unwrap_or(Value::None));
+    }
+
+    Ok(Value::None)
+}
+
+fn list_pop(_: &mut Evaluator, receiver: &Value, arguments: Arguments) -> Result<Value, EvalError> {
+    let [index] = arguments.unpack("pop", ["$index?"])?;
+    let Value::List(list) = receiver else {
+        return Ok(Value::None);
+    };
+
+    let mut items = list.items_mut()?;
+    let len = items.len() as i64;
+    let index = match index {
+        Some(index) => int_argument("pop", &index)?,
+        None => -1,
+    };
+    let resolved = if index < 0 { index + len } else { index };
+    if resolved < 0 || resolved >= len {
+        return Err(EvalError::new(format!(
+            "Index {index} out of range for list of length {len}"
+        )));
+    }
+
+    Ok(items.remove(resolved as usize))
+}
+
+fn list_remove(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [item] = arguments.unpack("remove", ["$item"])?;
+    let item = item.unwrap_or(Value::None);
+    if let Value::List(list) = receiver {
+        let mut items = list.items_mut()?;
+        let index = items
+            .iter()
+            .position(|it| *it == item)
+            .ok_or_else(|| EvalError::new(format!("Value {} not found in list", item.repr())))?;
+        items.remove(index);
+    }
+
+    Ok(Value::None)
+}
+
+fn dict(receiver: &Value) -> &Dict {
+    match receiver {
+        Value::Dict(dict) => dict,
+        _ => unreachable!("dict methods are only bound to dicts"),
+    }
+}
+
+fn dict_clear(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("clear", [])?;
+    dict(receiver).entries_mut()?.clear();
+
+    Ok(Value::None)
+}
+
+fn dict_get(_: &mut Evaluator, receiver: &Value, arguments: Arguments) -> Result<Value, EvalError> {
+    let [key, default] = arguments.unpack("get", ["$key", "$default?"])?;
+    let value = dict(receiver).get(&key.unwrap_or(Value::None))?;
+
+    Ok(value.or(default).unwrap_or(Value::None))
+}
+
+fn dict_items(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("items", [])?;
+    let items = dict(receiver)
+        .entries()
+        .values()
+        .map(|(key, value)| Value::tuple(vec![key.clone(), value.clone()]))
+        .collect();
+
+    Ok(Value::list(items))
+}
+
+fn dict_keys(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("keys", [])?;
+    Ok(Value::list(dict(receiver).keys()))
+}
+
+fn dict_values(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("values", [])?;
+    let values = dict(receiver)
+        .entries()
+        .values()
+        .map(|(_, value)| value.clone())
+        .collect();
+
+    Ok(Value::list(values))
+}
+
+fn dict_pop(_: &mut Evaluator, receiver: &Value, arguments: Arguments) -> Result<Value, EvalError> {
+    let [key, default] = arguments.unpack("pop", ["$key", "$default?"])?;
+    let key = key.unwrap_or(Value::None);
+    let hash = key.key()?;
+
+    let removed = dict(receiver).entries_mut()?.shift_remove(&hash);
+    match (removed, default) {
+        (Some((_, value)), _) => Ok(value),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(EvalError::new(format!(
+            "Key {} not found in dict",
+            key.repr()
+        ))),
+    }
+}
+
+fn dict_popitem(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    arguments.unpack("popitem", [])?;
+    let (_, (key, value)) = dict(receiver)
+        .entries_mut()?
+        .shift_remove_index(0)
+        .ok_or_else(|| EvalError::new("popitem() on an empty dict"))?;
+
+    Ok(Value::tuple(vec![key, value]))
+}
+
+fn dict_setdefault(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let [key, default] = arguments.unpack("setdefault", ["$key", "$default?"])?;
+    let key = key.unwrap_or(Value::None);
+    let dict = dict(receiver);
+    if let Some(value) = dict.get(&key)? {
+        return Ok(value);
+    }
+
+    let default = default.unwrap_or(Value::None);
+    dict.insert(key, default.clone())?;
+
+    Ok(default)
+}
+
+fn dict_update(
+    _: &mut Evaluator,
+    receiver: &Value,
+    arguments: Arguments,
+) -> Result<Value, EvalError> {
+    let Arguments { positional, named } = arguments;
+    let pairs = Arguments::positional(positional).positional_only("update", 0, 1)?;
+    update_dict(dict(receiver), pairs.into_iter().next(), named)?;
+
+    Ok(Value::None)
+}
@@ -0,0 +1,561 @@
+//! The **operators** of Starlark, e.g. arithmetic, membership, indexing and
+//! slicing.
+
+use crate::{
+    error::EvalError,
+    format::percent,
+    methods,
+    value::{Dict, Value},
+};
+use num_bigint::{BigInt, Sign};
+use num_traits::{Signed, ToPrimitive, Zero};
+use sky_syntax::ast::{BinaryOp, UnaryOp};
+use std::{cmp::Ordering, rc::Rc};
+
+/// Applies the given **binary operator**, other than `and` and `or` (which
+/// only evaluate their right operand if needed).
+pub(crate) fn binary(op: BinaryOp, lhs: &Value, rhs: &Value) -> Result<Value, EvalError> {
+    let value = match (op, lhs, rhs) {
+        (BinaryOp::Eq, _, _) => Value::Bool(lhs == rhs),
+        (BinaryOp::NotEq, _, _) => Value::Bool(lhs != rhs),
+        (BinaryOp::Less, _, _) => Value::Bool(lhs.compare(rhs)? == Ordering::Less),
+        (BinaryOp::Greater, _, _) => Value::Bool(lhs.compare(rhs)? == Ordering::Greater),
+        (BinaryOp::LessEq, _, _) => Value::Bool(lhs.compare(rhs)? != Ordering::Greater),
+        (BinaryOp::GreaterEq, _, _) => Value::Bool(lhs.compare(rhs)? != Ordering::Less),
+        (BinaryOp::In, _, _) => Value::Bool(contains(rhs, lhs)?),
+        (BinaryOp::NotIn, _, _) => Value::Bool(!contains(rhs, lhs)?),
+
+        (BinaryOp::Add, Value::String(a), Value::String(b)) => Value::from(format!("{a}{b}")),
+        (BinaryOp::Add, Value::Bytes(a), Value::Bytes(b)) => {
+            Value::Bytes([&a[..], b].concat().into())
+        }
+        (BinaryOp::Add, Value::List(a), Value::List(b)) => {
+            let mut items = a.items().clone();
+            items.extend(b.items().iter().cloned());
+            Value::list(items)
+        }
+        (BinaryOp::Add, Value::Tuple(a), Value::Tuple(b)) => Value::tuple([&a[..], b].concat()),
+
+        (BinaryOp::Mul, Value::String(a), n @ (Value::Int(_) | Value::BigInt(_)))
+        | (BinaryOp::Mul, n @ (Value::Int(_) | Value::BigInt(_)), Value::String(a)) => {
+            Value::from(a.repeat(repetitions(a.len(), n)?))
+        }
+        (BinaryOp::Mul, Value::Bytes(a), n @ (Value::Int(_) | Value::BigInt(_)))
+        | (BinaryOp::Mul, n @ (Value::Int(_) | Value::BigInt(_)), Value::Bytes(a)) => {
+            Value::Bytes(a.repeat(repetitions(a.len(), n)?).into())
+        }
+        (BinaryOp::Mul, Value::List(a), n @ (Value::Int(_) | Value::BigInt(_)))
+        | (BinaryOp::Mul, n @ (Value::Int(_) | Value::BigInt(_)), Value::List(a)) => {
+            let items = a.items();
+            Value::list(repeat(&items, repetitions(items.len(), n)?))
+        }
+        (BinaryOp::Mul, Value::Tuple(a), n @ (Value::Int(_) | Value::BigInt(_)))
+        | (BinaryOp::Mul, n @ (Value::Int(_) | Value::BigInt(_)), Value::Tuple(a)) => {
+            Value::tuple(repeat(a, repetitions(a.len(), n)?))
+        }
+
+        (BinaryOp::Rem, Value::String(format), _) => Value::from(percent(format, rhs)?),
+
+        (BinaryOp::BitOr, Value::Dict(a), Value::Dict(b)) => {
+            let union = Dict::default();
+            for (key, value) in a.entries().values().chain(b.entries().values()) {
+                union.insert(key.clone(), value.clone())?;
+            }
+            Value::Dict(Rc::new(union))
+        }
+
+        (_, Value::Int(a), Value::Int(b)) => int_binary(op, *a, *b)?,
+        (_, Value::Int(_) | Value::BigInt(_), Value::Int(_) | Value::BigInt(_)) => {
+            big_int_binary(op, &big_int(lhs), &big_int(rhs))?
+        }
+        (
+            _,
+            Value::Int(_) | Value::BigInt(_) | Value::Float(_),
+            Value::Int(_) | Value::BigInt(_) | Value::Float(_),
+        ) => float_binary(op, to_float(lhs)?, to_float(rhs)?)?,
+        _ => return Err(unsupported(op, lhs, rhs)),
+    };
+
+    Ok(value)
+}
+
+/// Applies a binary operator to ints, falling back to big ints on overflow.
+fn int_binary(op: BinaryOp, a: i64, b: i64) -> Result<Value, EvalError> {
+    let value = match op {
+        BinaryOp::Add => a.checked_add(b),
+        BinaryOp::Sub => a.checked_sub(b),
+        BinaryOp::Mul => a.checked_mul(b),
+        BinaryOp::Div => return float_binary(op, a as f64, b as f64),
+        BinaryOp::FloorDiv => {
+            check_divisor(b == 0)?;
+            a.checked_div(b).map(|quotient| {
+                let remainder = a % b;
+                if remainder != 0 && (remainder < 0) != (b < 0) {
+                    quotient - 1
+                } else {
+                    quotient
+                }
+            })
+        }
+        BinaryOp::Rem => {
+            check_divisor(b == 0)?;
+            a.checked_rem(b).map(|remainder| {
+                if remainder != 0 && (remainder < 0) != (b < 0) {
+                    remainder + b
+                } else {
+                    remainder
+                }
+            })
+        }
+        BinaryOp::BitOr => Some(a | b),
+        BinaryOp::BitXor => Some(a ^ b),
+        BinaryOp::BitAnd => Some(a & b),
+        BinaryOp::Shl | BinaryOp::Shr if b < 0 => {
+            return Err(EvalError::new(format!("Negative shift count: {b}")))
+        }
+        BinaryOp::Shl => match b {
+            0..=63 => a.checked_shl(b as u32).filter(|it| it >> b == a),
+            _ if a == 0 => Some(0),
+            _ => None,
+        },
+        BinaryOp::Shr => Some(a >> b.min(63)),
+        _ => return Err(unsupported(op, &Value::Int(a), &Value::Int(b))),
+    };
+
+    match value {
+        Some(value) => Ok(Value::Int(value)),
+        None => big_int_binary(op, &a.into(), &b.into()),
+    }
+}
+
+/// Applies a binary operator to ints which may not fit in 64 bits.
+fn big_int_binary(op: BinaryOp, a: &BigInt, b: &BigInt) -> Result<Value, EvalError> {
+    let value = match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => {
+            let (a, b) = (Value::int(a.clone()), Value::int(b.clone()));
+            return float_binary(op, a.int_to_float()?, b.int_to_float()?);
+        }
+        BinaryOp::FloorDiv => {
+            check_divisor(b.is_zero())?;
+            let (quotient, remainder) = (a / b, a % b);
+            if !remainder.is_zero() && remainder.is_negative() != b.is_negative() {
+                quotient - 1
+            } else {
+                quotient
+            }
+        }
+        BinaryOp::Rem => {
+            check_divisor(b.is_zero())?;
+            let remainder = a % b;
+            if !remainder.is_zero() && remainder.is_negative() != b.is_negative() {
+                remainder + b
+            } else {
+                remainder
+            }
+        }
+        BinaryOp::BitOr => a | b,
+        BinaryOp::BitXor => a ^ b,
+        BinaryOp::BitAnd => a & b,
+        BinaryOp::Shl | BinaryOp::Shr if b.is_negative() => {
+            return Err(EvalError::new(format!("Negative shift count: {b}")))
+        }
+        BinaryOp::Shl if a.is_zero() => BigInt::zero(),
+        BinaryOp::Shl => match b.to_usize() {
+            Some(count) if count <= MAX_SHIFT => a << count,
+            _ => return Err(EvalError::new(format!("Shift count too large: {b}"))),
+        },
+        // Shifting by more bits than the int has gives `0` (or `-1`).
+        BinaryOp::Shr => a >> b.to_u64().map_or(a.bits(), |it| it.min(a.bits())),
+        _ => {
+            return Err(unsupported(
+                op,
+                &Value::int(a.clone()),
+                &Value::int(b.clone()),
+            ))
+        }
+    };
+
+    Ok(Value::int(value))
+}
+
+fn float_binary(op: BinaryOp, a: f64, b: f64) -> Result<Value, EvalError> {
+    let value = match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => {
+            check_divisor(b == 0.0)?;
+            a / b
+        }
+        BinaryOp::FloorDiv => {
+            check_divisor(b == 0.0)?;
+            (a / b).floor()
+        }
+        BinaryOp::Rem => {
+            check_divisor(b == 0.0)?;
+            let remainder = a % b;
+            if remainder != 0.0 && (remainder < 0.0) != (b < 0.0) {
+                remainder + b
+            } else {
+                remainder
+            }
+        }
+        _ => return Err(unsupported(op, &Value::Float(a), &Value::Float(b))),
+    };
+
+    Ok(Value::Float(value))
+}
+
+/// Applies the given **unary operator**, other than `not`.
+pub(crate) fn unary(op: UnaryOp, value: &Value) -> Result<Value, EvalError> {
+    let result = match (op, value) {
+        (UnaryOp::Plus, Value::Int(_) | Value::BigInt(_) | Value::Float(_)) => value.clone(),
+        (UnaryOp::Minus, Value::Int(value)) => match value.checked_neg() {
+            Some(value) => Value::Int(value),
+            None => Value::int(-BigInt::from(*value)),
+        },
+        (UnaryOp::Minus, Value::BigInt(value)) => Value::int(-BigInt::clone(value)),
+        (UnaryOp::Minus, Value::Float(value)) => Value::Float(-value),
+        (UnaryOp::Invert, Value::Int(value)) => Value::Int(!value),
+        (UnaryOp::Invert, Value::BigInt(value)) => Value::int(!BigInt::clone(value)),
+        (UnaryOp::Not, value) => Value::Bool(!value.truth()),
+        _ => {
+            let op = match op {
+                UnaryOp::Plus => "+",
+                UnaryOp::Minus => "-",
+                UnaryOp::Invert => "~",
+                UnaryOp::Not => "not",
+            };
+            return Err(EvalError::new(format!(
+                "Unsupported operand type for unary {op}: {}",
+                value.type_name()
+            )));
+        }
+    };
+
+    Ok(result)
+}
+
+/// Whether the given container **contains** the given item, as tested by
+/// `in`.
+pub(crate) fn contains(container: &Value, item: &Value) -> Result<bool, EvalError> {
+    let contains = match (container, item) {
+        (Value::List(list), _) => list.items().contains(item),
+        (Value::Tuple(items), _) => items.contains(item),
+        (Value::Dict(dict), _) => dict.get(item)?.is_some(),
+        (Value::String(string), Value::String(substring)) => string.contains(&**substring),
+        (Value::Bytes(bytes), Value::Bytes(subsequence)) => {
+            subsequence.is_empty()
+                || bytes
+                    .windows(subsequence.len())
+                    .any(|it| it == &subsequence[..])
+        }
+        (Value::Bytes(bytes), Value::Int(_) | Value::BigInt(_)) => {
+            let byte = saturating_int(item)
+                .and_then(|it| u8::try_from(it).ok())
+                .ok_or_else(|| EvalError::new(format!("Byte value {item} out of range")))?;
+            bytes.contains(&byte)
+        }
+        _ => {
+            return Err(EvalError::new(format!(
+                "Unsupported operand types for in: {} and {}",
+                item.type_name(),
+                container.type_name()
+            )))
+        }
+    };
+
+    Ok(contains)
+}
+
+/// The **items** of the given iterable, i.e. the elements of a list or a
+/// tuple, or the keys of a dict. Strings aren't iterable (see
+/// `string.elems`).
+pub(crate) fn iterate(value: &Value) -> Result<Vec<Value>, EvalError> {
+    match value {
+        Value::List(list) => Ok(list.items().clone()),
+        Value::Tuple(items) => Ok(items.to_vec()),
+        Value::Dict(dict) => Ok(dict.keys()),
+        _ => Err(EvalError::new(format!(
+            "Value of type {} is not iterable",
+            value.type_name()
+        ))),
+    }
+}
+
+/// The element at the given **index** (e.g. `x[0]`), or the value of the
+/// given key for dicts.
+pub(crate) fn index(value: &Value, key: &Value) -> Result<Value, EvalError> {
+    if let Value::Dict(dict) = value {
+        return dict
+            .get(key)?
+            .ok_or_else(|| EvalError::new(format!("Key {} not found in dict", key.repr())));
+    }
+
+    let len = match value {
+        Value::List(list) => list.items().len(),
+        Value::Tuple(items) => items.len(),
+        Value::String(string) => string.chars().count(),
+        Value::Bytes(bytes) => bytes.len(),
+        _ => {
+            return Err(EvalError::new(format!(
+                "Value of type {} is not subscriptable",
+                value.type_name()
+            )))
+        }
+    };
+    let index = element_index(value, key, len)?;
+
+    let element = match value {
+        Value::List(list) => list.items()[index].clone(),
+        Value::Tuple(items) => items[index].clone(),
+        Value::String(string) => string
+            .chars()
+            .nth(index)
+            .map(|it| Value::from(it.to_string()))
+            .unwrap_or(Value::None),
+        Value::Bytes(bytes) => Value::Int(bytes[index].into()),
+        _ => Value::None,
+    };
+
+    Ok(element)
+}
+
+/// **Assigns** to the element at the given index (e.g. `x[0] = 1`), or to the
+/// given key for dicts.
+pub(crate) fn set_index(target: &Value, key: Value, value: Value) -> Result<(), EvalError> {
+    match target {
+        Value::List(list) => {
+            let index = element_index(target, &key, list.items().len())?;
+            list.items_mut()?[index] = value;
+            Ok(())
+        }
+        Value::Dict(dict) => dict.insert(key, value),
+        _ => Err(EvalError::new(format!(
+            "Value of type {} does not support item assignment",
+            target.type_name()
+        ))),
+    }
+}
+
+/// The index of the element at the given (possibly negative) index in a
+/// sequence of the given length.
+fn element_index(sequence: &Value, index: &Value, len: usize) -> Result<usize, EvalError> {
+    let Some(resolved) = saturating_int(index) else {
+        return Err(EvalError::new(format!(
+            "Indices of {} must be ints, not {}",
+            sequence.type_name(),
+            index.type_name()
+        )));
+    };
+
+    let resolved = if resolved < 0 {
+        resolved + len as i64
+    } else {
+        resolved
+    };
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(EvalError::new(format!(
+            "Index {index} out of range for {} of length {len}",
+            sequence.type_name()
+        )));
+    }
+
+    Ok(resolved as usize)
+}
+
+/// The **slice** of the given sequence (e.g. `x[1:-1]` or `x[::2]`), where
+/// `None` bounds are omitted.
+pub(crate) fn slice(
+    value: &Value,
+    start: &Value,
+    end: &Value,
+    step: &Value,
+) -> Result<Value, EvalError> {
+    let len = match value {
+        Value::List(list) => list.items().len(),
+        Value::Tuple(items) => items.len(),
+        Value::String(string) => string.chars().count(),
+        Value::Bytes(bytes) => bytes.len(),
+        _ => {
+            return Err(EvalError::new(format!(
+                "Value of type {} can't be sliced",
+                value.type_name()
+            )))
+        }
+    };
+    let indices = slice_indices(len, start, end, step)?;
+
+    let slice = match value {
+        Value::List(list) => {
+            let items = list.items();
+            Value::list(indices.map(|it| items[it].clone()).collect())
+        }
+        Value::Tuple(items) => Value::tuple(indices.map(|it| items[it].clone()).collect()),
+        Value::String(string) => {
+            let chars: Vec<char> = string.chars().collect();
+            Value::from(indices.map(|it| chars[it]).collect::<String>())
+        }
+        Value::Bytes(bytes) => Value::Bytes(indices.map(|it| bytes[it]).collect()),
+        _ => Value::None,
+    };
+
+    Ok(slice)
+}
+
+/// The indices selected by a slice of a sequence of the given length,
+/// following the semantics of Python.
+fn slice_indices(
+    len: usize,
+    start: &Value,
+    end: &Value,
+    step: &Value,
+) -> Result<impl Iterator<Item = usize>, EvalError> {
+    let bound = |value: &Value, what: &str| match value {
+        Value::None => Ok(None),
+        Value::Int(_) | Value::BigInt(_) => Ok(saturating_int(value)),
+        value => Err(EvalError::new(format!(
+            "Slice {what} must be an int, not {}",
+            value.type_name()
+        ))),
+    };
+    let step = bound(step, "step")?.unwrap_or(1);
+    if step == 0 {
+        return Err(EvalError::new("Slice step cannot be zero"));
+    }
+
+    let len = len as i64;
+    let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+    let clamp = |index: Option<i64>, default: i64| match index {
+        None => default,
+        Some(index) if index < 0 => (index + len).max(lower),
+        Some(index) => index.min(upper),
+    };
+    let start = clamp(bound(start, "start")?, if step > 0 { lower } else { upper });
+    let end = clamp(bound(end, "end")?, if step > 0 { upper } else { lower });
+
+    let indices = std::iter::successors(Some(start), move |it| it.checked_add(step))
+        .take_while(move |it| if step > 0 { *it < end } else { *it > end })
+        .map(|it| it as usize);
+
+    Ok(indices)
+}
+
+/// The **attribute** of the given value (e.g. a field of a struct or a
+/// method of a list).
+pub(crate) fn getattr(value: &Value, name: &str) -> Result<Value, EvalError> {
+    if let Value::Struct(value) = value {
+        if let Some(field) = value.fields.get(name) {
+            return Ok(field.clone());
+        }
+    }
+
+    methods::method(value, name).ok_or_else(|| {
+        EvalError::new(format!(
+            "Value of type {} has no attribute `{name}`",
+            value.type_name()
+        ))
+    })
+}
+
+/// The **names** of the attributes of the given value, sorted.
+pub(crate) fn dir(value: &Value) -> Vec<String> {
+    let mut names: Vec<String> = methods::methods(value)
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    if let Value::Struct(value) = value {
+        names.extend(value.fields.keys().cloned());
+    }
+    names.sort();
+
+    names
+}
+
+/// The largest number of bits a non-zero int can be shifted left by, so that
+/// ints stay reasonably small.
+const MAX_SHIFT: usize = 512;
+
+fn to_float(value: &Value) -> Result<f64, EvalError> {
+    match value {
+        Value::Float(value) => Ok(*value),
+        _ => value.int_to_float(),
+    }
+}
+
+/// The value of an int as a big int.
+fn big_int(value: &Value) -> BigInt {
+    value.big_int().unwrap_or_default()
+}
+
+/// The value of an int, saturated to 64 bits (e.g. for slice bounds, where
+/// any big int is out of range).
+fn saturating_int(value: &Value) -> Option<i64> {
+    match value {
+        Value::Int(value) => Some(*value),
+        Value::BigInt(value) if value.sign() == Sign::Minus => Some(i64::MIN),
+        Value::BigInt(_) => Some(i64::MAX),
+        _ => None,
+    }
+}
+
+fn check_divisor(is_zero: bool) -> Result<(), EvalError> {
+    if is_zero {
+        Err(EvalError::new("Division by zero"))
+    } else {
+        Ok(())
+    }
+}
+
+/// The number of repetitions of a sequence of the given length, failing if
+/// the result would be unreasonably large.
+fn repetitions(len: usize, count: &Value) -> Result<usize, EvalError> {
+    let count = saturating_int(count).unwrap_or(0).max(0) as usize;
+    match len.checked_mul(count) {
+        // Repeating an empty sequence gives an empty sequence, however many
+        // times.
+        Some(0) => Ok(0),
+        Some(total) if total <= isize::MAX as usize / 16 => Ok(count),
+        _ => Err(EvalError::new("Repeated sequence is too large")),
+    }
+}
+
+/// The given items repeated `count` times.
+fn repeat(items: &[Value], count: usize) -> Vec<Value> {
+    (0..count).flat_map(|_| items.iter().cloned()).collect()
+}
+
+fn unsupported(op: BinaryOp, lhs: &Value, rhs: &Value) -> EvalError {
+    let op = match op {
+        BinaryOp::Or => "or",
+        BinaryOp::And => "and",
+        BinaryOp::Eq => "==",
+        BinaryOp::NotEq => "!=",
+        BinaryOp::Less => "<",
+        BinaryOp::Greater => ">",
+        BinaryOp::LessEq => "<=",
+        BinaryOp::GreaterEq => ">=",
+        BinaryOp::In => "in",
+        BinaryOp::NotIn => "not in",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::Shl => "<<",
+        BinaryOp::Shr => ">>",
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::FloorDiv => "//",
+        BinaryOp::Rem => "%",
+    };
+
+    EvalError::new(format!(
+        "Unsupported operand types for {op}: {} and {}",
+        lhs.type_name(),
+        rhs.type_name()
+    ))
+}
@@ -0,0 +1,653 @@
+//! The **values** of Starlark.
+//!
+//! Immutable values (e.g. strings and tuples) are shared by reference
+//! counting, while lists and dicts are shared **mutable** values which can be
+//! **frozen**, after which any attempt to mutate them fails. The globals of a
+//! module are frozen once it's been evaluated, making them safe to share
+//! with the modules loading it.
+
+use crate::{
+    error::EvalError,
+    format::format_float,
+    function::{Builtin, Function},
+};
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt,
+    rc::Rc,
+};
+
+/// A Starlark **value**.
+#[derive(Clone)]
+pub enum Value {
+    None,
+    Bool(bool),
+    /// A signed integer which fits in 64 bits.
+    Int(i64),
+    /// A signed integer which doesn't fit in 64 bits, as ints are unbounded.
+    /// Ints are only big when they have to be (see [`Value::int`]), so that
+    /// each int has a single representation.
+    BigInt(Rc<BigInt>),
+    Float(f64),
+    /// A string, i.e. a sequence of Unicode code points.
+    String(Rc<str>),
+    Bytes(Rc<[u8]>),
+    List(Rc<List>),
+    Dict(Rc<Dict>),
+    Tuple(Rc<[Value]>),
+    Function(Rc<Function>),
+    Builtin(Rc<Builtin>),
+    Struct(Rc<Struct>),
+}
+
+impl Value {
+    /// The int of the given value, which is only big if it doesn't fit in 64
+    /// bits.
+    pub fn int(value: BigInt) -> Value {
+        match value.to_i64() {
+            Some(value) => Value::Int(value),
+            None => Value::BigInt(Rc::new(value)),
+        }
+    }
+
+    pub fn list(items: Vec<Value>) -> Value {
+        Value::List(Rc::new(List::new(items)))
+    }
+
+    pub fn tuple(items: Vec<Value>) -> Value {
+        Value::Tuple(items.into())
+    }
+
+    /// A dict of the given entries, where later entries override earlier ones
+    /// with the same key.
+    pub fn dict(entries: Vec<(Value, Value)>) -> Result<Value, EvalError> {
+        let dict = Dict::default();
+        for (key, value) in entries {
+            dict.insert(key, value)?;
+        }
+
+        Ok(Value::Dict(Rc::new(dict)))
+    }
+
+    /// The **name** of the type of the value, as returned by `type`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::None => "NoneType",
+            Value::Bool(_) => "bool",
+            Value::Int(_) | Value::BigInt(_) => "int",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Bytes(_) => "bytes",
+            Value::List(_) => "list",
+            Value::Dict(_) => "dict",
+            Value::Tuple(_) => "tuple",
+            Value::Function(_) => "function",
+            Value::Builtin(_) => "builtin_function_or_method",
+            Value::Struct(_) => "struct",
+        }
+    }
+
+    /// The **truth value** of the value, e.g. `False` for `0`, `""` and empty
+    /// collections.
+    pub fn truth(&self) -> bool {
+        match self {
+            Value::None => false,
+            Value::Bool(value) => *value,
+            Value::Int(value) => *value != 0,
+            Value::BigInt(_) => true,
+            Value::Float(value) => *value != 0.0,
+            Value::String(value) => !value.is_empty(),
+            Value::Bytes(value) => !value.is_empty(),
+            Value::List(list) => !list.items().is_empty(),
+            Value::Dict(dict) => !dict.entries().is_empty(),
+            Value::Tuple(items) => !items.is_empty(),
+            Value::Function(_) | Value::Builtin(_) | Value::Struct(_) => true,
+        }
+    }
+
+    /// The **representation** of the value, i.e. Starlark code evaluating to
+    /// it where possible (e.g. strings are quoted).
+    pub fn repr(&self) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self, true, &mut Vec::new());
+        out
+    }
+
+    /// Returns `true` if the value can't be mutated, either because its type
+    /// is immutable or because it's been frozen.
+    pub fn is_frozen(&self) -> bool {
+        match self {
+            Value::List(list) => list.frozen.get(),
+            Value::Dict(dict) => dict.frozen.get(),
+            _ => true,
+        }
+    }
+
+    /// **Freezes** the value and any value reachable from it, after which
+    /// they can't be mutated.
+    pub fn freeze(&self) {
+        match self {
+            Value::List(list) => {
+                if !list.frozen.replace(true) {
+                    list.items().iter().for_each(Value::freeze);
+                }
+            }
+            Value::Dict(dict) => {
+                if !dict.frozen.replace(true) {
+                    for (key, value) in dict.entries().values() {
+                        key.freeze();
+                        value.freeze();
+                    }
+                }
+            }
+            Value::Tuple(items) => items.iter().for_each(Value::freeze),
+            Value::Struct(value) => value.fields.values().for_each(Value::freeze),
+            Value::Function(function) => function.freeze(),
+            Value::Builtin(builtin) => {
+                if let Some(receiver) = builtin.receiver() {
+                    receiver.freeze();
+                }
+            }
+            Value::None
+            | Value::Bool(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::Float(_)
+            | Value::String(_)
+            | Value::Bytes(_) => {}
+        }
+    }
+
+    /// **Compares** the value with another of a compatible type, e.g. for
+    /// `<` or `sorted`.
+    pub fn compare(&self, other: &Value) -> Result<Ordering, EvalError> {
+        let ordering = match (self, other) {
+            (Value::None, Value::None) => Ordering::Equal,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::Int(_) | Value::BigInt(_), Value::Int(_) | Value::BigInt(_)) => {
+                self.big_int().cmp(&other.big_int())
+            }
+            (Value::Int(a), Value::Float(b)) => compare_floats(*a as f64, *b),
+            (Value::Float(a), Value::Int(b)) => compare_floats(*a, *b as f64),
+            (Value::BigInt(a), Value::Float(b)) => compare_big_int_float(a, *b),
+            (Value::Float(a), Value::BigInt(b)) => compare_big_int_float(b, *a).reverse(),
+            (Value::Float(a), Value::Float(b)) => compare_floats(*a, *b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => compare_sequences(&a.items(), &b.items())?,
+            (Value::Tuple(a), Value::Tuple(b)) => compare_sequences(a, b)?,
+            _ => {
+                return Err(EvalError::new(format!(
+                    "Unsupported comparison of {} and {}",
+                    self.type_name(),
+                    other.type_name()
+                )))
+            }
+        };
+
+        Ok(ordering)
+    }
+
+    /// The **hash key** of the value, if it's hashable (i.e. immutable).
+    pub(crate) fn key(&self) -> Result<Key, EvalError> {
+        let key = match self {
+            Value::None => Key::None,
+            Value::Bool(value) => Key::Bool(*value),
+            Value::Int(value) => Key::Int(*value),
+            Value::BigInt(value) => Key::BigInt(BigInt::clone(value)),
+            // Floats equal to an int must have the same key (e.g. `1.0` and
+            // `1`).
+            Value::Float(value) => match float_to_int(*value) {
+                Some(value) => Key::Int(value),
+                None if value.is_nan() => Key::Float(f64::NAN.to_bits()),
+                None => match float_to_big_int(*value) {
+                    Some(value) => Key::BigInt(value),
+                    None => Key::Float(value.to_bits()),
+                },
+            },
+            Value::String(value) => Key::String(value.clone()),
+            Value::Bytes(value) => Key::Bytes(value.clone()),
+            Value::Tuple(items) => {
+                Key::Tuple(items.iter().map(Value::key).collect::<Result<_, _>>()?)
+            }
+            Value::Struct(value) => Key::Struct(
+                value
+                    .fields
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), value.key()?)))
+                    .collect::<Result<_, EvalError>>()?,
+            ),
+            Value::Function(function) => Key::Identity(Rc::as_ptr(function) as *const () as usize),
+            Value::Builtin(builtin) => Key::Identity(Rc::as_ptr(builtin) as *const () as usize),
+            Value::List(_) | Value::Dict(_) => {
+                return Err(EvalError::new(format!(
+                    "Unhashable type: {}",
+                    self.type_name()
+                )))
+            }
+        };
+
+        Ok(key)
+    }
+
+    /// The value of an int as a big int, if the value is an int.
+    pub(crate) fn big_int(&self) -> Option<BigInt> {
+        match self {
+            Value::Int(value) => Some((*value).into()),
+            Value::BigInt(value) => Some(BigInt::clone(value)),
+            _ => None,
+        }
+    }
+
+    /// The nearest float to an int, failing if the int is too big for
+    /// floats.
+    pub(crate) fn int_to_float(&self) -> Result<f64, EvalError> {
+        let value = match self {
+            Value::Int(value) => *value as f64,
+            Value::BigInt(value) => value.to_f64().unwrap_or(f64::INFINITY),
+            _ => f64::NAN,
+        };
+        if value.is_infinite() {
+            return Err(EvalError::new("Int too large to convert to float"));
+        }
+
+        Ok(value)
+    }
+}
+
+/// **Equality** as defined by `==`, which compares numbers by value and
+/// collections element-wise.
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::None, Value::None) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Int(a), Value::Float(b)) | (Value::Float(b), Value::Int(a)) => {
+                *a as f64 == *b && float_to_int(*b) == Some(*a)
+            }
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::BigInt(a), Value::Float(b)) | (Value::Float(b), Value::BigInt(a)) => {
+                float_to_big_int(*b).as_ref() == Some(a)
+            }
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::List(a), Value::List(b)) => Rc::ptr_eq(a, b) || *a.items() == *b.items(),
+            (Value::Dict(a), Value::Dict(b)) => {
+                Rc::ptr_eq(a, b) || {
+                    let (a, b) = (a.entries(), b.entries());
+                    a.len() == b.len()
+                        && a.iter().all(
+                            |(key, (_, value))| matches!(b.get(key), Some((_, it)) if it == value),
+                        )
+                }
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            (Value::Builtin(a), Value::Builtin(b)) => Rc::ptr_eq(a, b),
+            (Value::Struct(a), Value::Struct(b)) => a.fields == b.fields,
+            _ => false,
+        }
+    }
+}
+
+/// Formats the value as `str` does, i.e. strings aren't quoted.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(value) => f.write_str(value),
+            _ => f.write_str(&self.repr()),
+        }
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.repr())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Value {
+        Value::Int(value)
+    }
+}
+
+impl From<BigInt> for Value {
+    fn from(value: BigInt) -> Value {
+        Value::int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Value {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::String(value.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::String(value.into())
+    }
+}
+
+/// A **list**, i.e. a mutable sequence.
+#[derive(Debug, Default)]
+pub struct List {
+    items: RefCell<Vec<Value>>,
+    frozen: Cell<bool>,
+    /// The number of loops iterating over the list, during which it can't be
+    /// mutated.
+    iterators: Cell<usize>,
+}
+
+impl List {
+    pub fn new(items: Vec<Value>) -> List {
+        List {
+            items: RefCell::new(items),
+            ..List::default()
+        }
+    }
+
+    pub fn items(&self) -> Ref<'_, Vec<Value>> {
+        self.items.borrow()
+    }
+
+    /// The items, for **mutating** them, provided the list isn't frozen or
+    /// being iterated over.
+    pub fn items_mut(&self) -> Result<RefMut<'_, Vec<Value>>, EvalError> {
+        check_mutable("list", self.frozen.get(), self.iterators.get())?;
+        Ok(self.items.borrow_mut())
+    }
+}
+
+/// The **entries** of a dict, i.e. its keys and values by the hash keys of
+/// their keys.
+pub type Entries = IndexMap<Key, (Value, Value)>;
+
+/// A **dict**, i.e. a mutable mapping which preserves the insertion order
+/// of its keys.
+#[derive(Debug, Default)]
+pub struct Dict {
+    entries: RefCell<Entries>,
+    frozen: Cell<bool>,
+    iterators: Cell<usize>,
+}
+
+impl Dict {
+    pub fn entries(&self) -> Ref<'_, Entries> {
+        self.entries.borrow()
+    }
+
+    /// The entries, for **mutating** them, provided the dict isn't frozen or
+    /// being iterated over.
+    pub fn entries_mut(&self) -> Result<RefMut<'_, Entries>, EvalError> {
+        check_mutable("dict", self.frozen.get(), self.iterators.get())?;
+        Ok(self.entries.borrow_mut())
+    }
+
+    pub fn get(&self, key: &Value) -> Result<Option<Value>, EvalError> {
+        let key = key.key()?;
+        Ok(self.entries().get(&key).map(|(_, value)| value.clone()))
+    }
+
+    /// **Inserts** the given entry, keeping the position of an existing
+    /// entry with the same key.
+    pub fn insert(&self, key: Value, value: Value) -> Result<(), EvalError> {
+        let hash = key.key()?;
+        let mut entries = self.entries_mut()?;
+        match entries.get_mut(&hash) {
+            Some(entry) => entry.1 = value,
+            None => {
+                entries.insert(hash, (key, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn keys(&self) -> Vec<Value> {
+        self.entries()
+            .values()
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+/// A **struct**, i.e. an immutable record of named fields (e.g.
+/// `struct(name = "foo")`).
+#[derive(Debug, Default, PartialEq)]
+pub struct Struct {
+    pub fields: BTreeMap<String, Value>,
+}
+
+/// The **hash key** of a hashable value, for looking up dict entries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    None,
+    Bool(bool),
+    Int(i64),
+    BigInt(BigInt),
+    /// The bits of a float which isn't equal to any int.
+    Float(u64),
+    String(Rc<str>),
+    Bytes(Rc<[u8]>),
+    Tuple(Vec<Key>),
+    Struct(Vec<(String, Key)>),
+    /// The address of a value compared by identity, e.g. a function.
+    Identity(usize),
+}
+
+/// Tracks a **loop** over a list or a dict, which can't be mutated until the
+/// loop is over.
+pub(crate) struct IterationGuard(Value);
+
+impl IterationGuard {
+    pub(crate) fn new(value: &Value) -> IterationGuard {
+        match value {
+            Value::List(list) => list.iterators.set(list.iterators.get() + 1),
+            Value::Dict(dict) => dict.iterators.set(dict.iterators.get() + 1),
+            _ => {}
+        }
+
+        IterationGuard(value.clone())
+    }
+}
+
+impl Drop for IterationGuard {
+    fn drop(&mut self) {
+        match &self.0 {
+            Value::List(list) => list.iterators.set(list.iterators.get() - 1),
+            Value::Dict(dict) => dict.iterators.set(dict.iterators.get() - 1),
+            _ => {}
+        }
+    }
+}
+
+fn check_mutable(type_name: &str, frozen: bool, iterators: usize) -> Result<(), EvalError> {
+    if frozen {
+        Err(EvalError::new(format!("Cannot mutate frozen {type_name}")))
+    } else if iterators > 0 {
+        Err(EvalError::new(format!(
+            "Cannot mutate {type_name} during iteration"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// The int equal to the given float, if any.
+pub(crate) fn float_to_int(value: f64) -> Option<i64> {
+    let in_range = value >= i64::MIN as f64 && value < i64::MAX as f64;
+    (value.fract() == 0.0 && in_range).then_some(value as i64)
+}
+
+/// The int equal to the given float, as a big int, if any.
+pub(crate) fn float_to_big_int(value: f64) -> Option<BigInt> {
+    (value.fract() == 0.0).then(|| BigInt::from_f64(value))?
+}
+
+/// Compares floats in the total order of Starlark, where `NaN` is greater
+/// than any other float (including infinity) and equal to itself.
+fn compare_floats(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Compares a big int with a float exactly, in the total order of floats
+/// (see [`compare_floats`]).
+fn compare_big_int_float(a: &BigInt, b: f64) -> Ordering {
+    if b.is_nan() {
+        return Ordering::Less;
+    }
+    match BigInt::from_f64(b.floor()) {
+        Some(floor) => a.cmp(&floor).then(if b.fract() == 0.0 {
+            Ordering::Equal
+        } else {
+            Ordering::Less
+        }),
+        // The float is infinite.
+        None => compare_floats(0.0, b),
+    }
+}
+
+fn compare_sequences(a: &[Value], b: &[Value]) -> Result<Ordering, EvalError> {
+    for (a, b) in a.iter().zip(b) {
+        if a != b {
+            return a.compare(b);
+        }
+    }
+
+    Ok(a.len().cmp(&b.len()))
+}
+
+/// Writes the representation of the given value, printing `...` for values
+/// containing themselves (e.g. a list appended to itself).
+fn write_value(out: &mut String, value: &Value, quoted: bool, seen: &mut Vec<usize>) {
+    let address = match value {
+        Value::List(list) => Rc::as_ptr(list) as *const () as usize,
+        Value::Dict(dict) => Rc::as_ptr(dict) as *const () as usize,
+        _ => 0,
+    };
+    if address != 0 {
+        if seen.contains(&address) {
+            out.push_str("...");
+            return;
+        }
+        seen.push(address);
+    }
+
+    match value {
+        Value::None => out.push_str("None"),
+        Value::Bool(true) => out.push_str("True"),
+        Value::Bool(false) => out.push_str("False"),
+        Value::Int(value) => out.push_str(&value.to_string()),
+        Value::BigInt(value) => out.push_str(&value.to_string()),
+        Value::Float(value) => out.push_str(&format_float(*value)),
+        Value::String(value) if quoted => write_quoted(out, value),
+        Value::String(value) => out.push_str(value),
+        Value::Bytes(value) => write_bytes(out, value),
+        Value::List(list) => write_items(out, "[", &list.items(), "]", seen),
+        Value::Tuple(items) if items.len() == 1 => write_items(out, "(", items, ",)", seen),
+        Value::Tuple(items) => write_items(out, "(", items, ")", seen),
+        Value::Dict(dict) => {
+            out.push('{');
+            for (index, (key, value)) in dict.entries().values().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, key, true, seen);
+                out.push_str(": ");
+                write_value(out, value, true, seen);
+            }
+            out.push('}');
+        }
+        Value::Function(function) => out.push_str(&format!("<function {}>", function.name())),
+        Value::Builtin(builtin) => match builtin.receiver() {
+            Some(receiver) => out.push_str(&format!(
+                "<built-in method {} of {} value>",
+                builtin.name(),
+                receiver.type_name()
+            )),
+            None => out.push_str(&format!("<built-in function {}>", builtin.name())),
+        },
+        Value::Struct(value) => {
+            out.push_str("struct(");
+            for (index, (name, value)) in value.fields.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(name);
+                out.push_str(" = ");
+                write_value(out, value, true, seen);
+            }
+            out.push(')');
+        }
+    }
+
+    if address != 0 {
+        seen.pop();
+    }
+}
+
+fn write_items(out: &mut String, open: &str, items: &[Value], close: &str, seen: &mut Vec<usize>) {
+    out.push_str(open);
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        write_value(out, item, true, seen);
+    }
+    out.push_str(close);
+}
+
+fn write_quoted(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_bytes(out: &mut String, value: &[u8]) {
+    out.push_str("b\"");
+    for byte in value {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(char::from(*byte)),
+            byte => out.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod builtins_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_interpreter::Interpreter;
+
+    /// The representation of the value of the given expression.
+    fn eval(expression: &str) -> String {
+        let text = format!("x = {expression}\n");
+        let module = Interpreter::new()
+            .eval("test.star", &text)
+            .unwrap_or_else(|error| panic!("{expression}: {error}"));
+
+        module.get("x").expect("`x` should be bound").repr()
+    }
+
+    #[rstest]
+    #[case::abs("abs(-3)", "3")]
+    #[case::abs_big_int("abs(-9223372036854775807 - 1)", "9223372036854775808")]
+    #[case::all("[all([]), all([1, 0])]", "[True, False]")]
+    #[case::any("[any([]), any([0, 1])]", "[False, True]")]
+    #[case::bool("[bool(), bool(\"\"), bool([0])]", "[False, False, True]")]
+    #[case::bytes("bytes(\"hi\")", "b\"hi\"")]
+    #[case::dict("dict([(\"a\", 1)], b = 2)", "{\"a\": 1, \"b\": 2}")]
+    #[case::dir("dir(struct(z = 1))", "[\"z\"]")]
+    #[case::enumerate("enumerate([\"a\", \"b\"], 1)", "[(1, \"a\"), (2, \"b\")]")]
+    #[case::float("[float(1), float(\"1.5\"), float(\"-inf\")]", "[1.0, 1.5, -inf]")]
+    #[case::getattr("getattr(struct(a = 1), \"b\", 2)", "2")]
+    #[case::hasattr("[hasattr([], \"append\"), hasattr(1, \"append\")]", "[True, False]")]
+    #[case::hash("hash(\"a\")", "97")]
+    #[case::int(
+        "[int(\"-42\"), int(\"0x1f\", 0), int(\"ff\", 16), int(3.9), int(True)]",
+        "[-42, 31, 255, 3, 1]"
+    )]
+    #[case::int_big(
+        "[int(\"18446744073709551616\"), int(-1e19), \"%x\" % -(1 << 64)]",
+        "[18446744073709551616, -10000000000000000000, \"-10000000000000000\"]"
+    )]
+    #[case::len("[len(\"héllo\"), len([1]), len({})]", "[5, 1, 0]")]
+    #[case::list("list((1, 2))", "[1, 2]")]
+    #[case::max("max(1, 3, 2)", "3")]
+    #[case::max_with_key("max([\"aa\", \"b\"], key = len)", "\"aa\"")]
+    #[case::min("min([3, 1, 2])", "1")]
+    #[case::range(
+        "[range(3), range(1, 7, 2), range(3, 0, -1)]",
+        "[[0, 1, 2], [1, 3, 5], [3, 2, 1]]"
+    )]
+    #[case::repr("repr(\"a\")", "\"\\\"a\\\"\"")]
+    #[case::reversed("reversed([1, 2, 3])", "[3, 2, 1]")]
+    #[case::sorted("sorted([3, 1, 2])", "[1, 2, 3]")]
+    #[case::sorted_stable(
+        "sorted([(\"b\", 1), (\"a\", 2), (\"b\", 0)], key = lambda p: p[0], reverse = True)",
+        "[(\"b\", 1), (\"b\", 0), (\"a\", 2)]"
+    )]
+    #[case::str("[str(1), str(\"a\"), str(None)]", "[\"1\", \"a\", \"None\"]")]
+    #[case::struct_fields("struct(b = 1, a = [])", "struct(a = [], b = 1)")]
+    #[case::tuple("tuple([1])", "(1,)")]
+    #[case::type_names(
+        "[type(1), type(\"\"), type(struct()), type(len)]",
+        "[\"int\", \"string\", \"struct\", \"builtin_function_or_method\"]"
+    )]
+    #[case::zip("zip([1, 2, 3], \"ab\".elems())", "[(1, \"a\"), (2, \"b\")]")]
+    fn builtin_functions(#[case] expression: &str, #[case] expected: &str) {
+        assert_eq!(eval(expression), expected);
+    }
+
+    #[rstest]
+    #[case::capitalize("\"hELLO\".capitalize()", "\"Hello\"")]
+    #[case::codepoints("\"hé\".codepoints()", "[\"h\", \"é\"]")]
+    #[case::codepoint_ords("\"hé\".codepoint_ords()", "[104, 233]")]
+    #[case::elem_ords("\"hé\".elem_ords()", "[104, 233]")]
+    #[case::count("\"banana\".count(\"a\")", "3")]
+    #[case::endswith("\"file.bzl\".endswith((\".star\", \".bzl\"))", "True")]
+    #[case::find(
+        "[\"hello\".find(\"l\"), \"hello\".rfind(\"l\"), \"hello\".find(\"z\")]",
+        "[2, 3, -1]"
+    )]
+    #[case::format(
+        "\"{0} {name}: {0!r}\".format(\"a\", name = \"b\")",
+        "\"a b: \\\"a\\\"\""
+    )]
+    #[case::format_automatic("\"{}-{}\".format(1, \"a\")", "\"1-a\"")]
+    #[case::format_named("\"{a}{{}}\".format(a = 1)", "\"1{}\"")]
+    #[case::join("\", \".join([\"a\", \"b\"])", "\"a, b\"")]
+    #[case::lower_upper("[\"Ab\".lower(), \"Ab\".upper()]", "[\"ab\", \"AB\"]")]
+    #[case::partition("\"a=b=c\".partition(\"=\")", "(\"a\", \"=\", \"b=c\")")]
+    #[case::rpartition("\"a=b=c\".rpartition(\"=\")", "(\"a=b\", \"=\", \"c\")")]
+    #[case::removeprefix("\"foo.bzl\".removeprefix(\"foo\")", "\".bzl\"")]
+    #[case::replace("\"aaa\".replace(\"a\", \"b\", 2)", "\"bba\"")]
+    #[case::split("\" a  b \".split()", "[\"a\", \"b\"]")]
+    #[case::split_with_separator("\"a,b,,c\".split(\",\", 2)", "[\"a\", \"b\", \",c\"]")]
+    #[case::rsplit("\"a b c\".rsplit(\" \", 1)", "[\"a b\", \"c\"]")]
+    #[case::splitlines("\"a\\nb\\r\\n\".splitlines()", "[\"a\", \"b\"]")]
+    #[case::strip(
+        "[\" a \".strip(), \"xxaxx\".lstrip(\"x\"), \"xxaxx\".rstrip(\"x\")]",
+        "[\"a\", \"axx\", \"xxa\"]"
+    )]
+    #[case::title("\"hello world\".title()", "\"Hello World\"")]
+    #[case::predicates(
+        "[\"12\".isdigit(), \"ab\".isalpha(), \"aB\".islower(), \" \".isspace()]",
+        "[True, True, False, True]"
+    )]
+    fn string_methods(#[case] expression: &str, #[case] expected: &str) {
+        assert_eq!(eval(expression), expected);
+    }
+
+    #[rstest]
+    #[case::append("[1].append(2)", "None")]
+    #[case::index("[\"a\", \"b\"].index(\"b\")", "1")]
+    #[case::pop("[1, 2, 3].pop(0)", "1")]
+    #[case::dict_get(
+        "[{\"a\": 1}.get(\"a\"), {}.get(\"a\"), {}.get(\"a\", 2)]",
+        "[1, None, 2]"
+    )]
+    #[case::dict_items("{\"a\": 1, \"b\": 2}.items()", "[(\"a\", 1), (\"b\", 2)]")]
+    #[case::dict_keys_values("({\"a\": 1}.keys(), {\"a\": 1}.values())", "([\"a\"], [1])")]
+    #[case::dict_pop("{\"a\": 1}.pop(\"b\", 0)", "0")]
+    #[case::dict_popitem("{\"a\": 1, \"b\": 2}.popitem()", "(\"a\", 1)")]
+    #[case::dict_setdefault("{}.setdefault(\"a\", [])", "[]")]
+    #[case::bytes_elems("b\"ab\".elems()", "[97, 98]")]
+    fn collection_methods(#[case] expression: &str, #[case] expected: &str) {
+        assert_eq!(eval(expression), expected);
+    }
+
+    #[test]
+    fn mutating_methods() {
+        let text = "x = [3, 1]\nx.append(2)\nx.extend([5])\nx.insert(0, 0)\nx.remove(1)\nd = {\"a\": 1}\nd.update(b = 2)\nd.update([(\"a\", 3)])\nx.append(d)\n";
+        let module = Interpreter::new().eval("test.star", text).unwrap();
+
+        assert_eq!(
+            module.get("x").unwrap().repr(),
+            "[0, 3, 2, 5, {\"a\": 3, \"b\": 2}]"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod eval_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_interpreter::{Arguments, Builtin, Error, Interpreter, Value};
+    use std::{cell::RefCell, rc::Rc};
+
+    /// The representation of the global `x` after evaluating the given
+    /// module.
+    fn eval(text: &str) -> String {
+        let module = Interpreter::new()
+            .eval("test.star", text)
+            .unwrap_or_else(|error| panic!("{text}: {error}"));
+
+        module.get("x").expect("`x` should be bound").repr()
+    }
+
+    /// The message of the error evaluating the given module.
+    fn eval_error(text: &str) -> String {
+        match Interpreter::new().eval("test.star", text) {
+            Ok(_) => panic!("{text} should fail"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::int("x = 1 + 2 * 3\n", "7")]
+    #[case::floor_division("x = (-7 // 2, -7 % 2, 7 // -2, 7 % -2)\n", "(-4, 1, -4, -1)")]
+    #[case::division("x = 7 / 2\n", "3.5")]
+    #[case::float("x = 1.0 + 2\n", "3.0")]
+    #[case::large_float("x = 1e20\n", "1e+20")]
+    #[case::big_int_shift("x = 1 << 65\n", "36893488147419103232")]
+    #[case::big_int_addition("x = 9223372036854775807 + 1\n", "9223372036854775808")]
+    #[case::big_int_multiplication("x = 4294967296 * 4294967296 * -3\n", "-55340232221128654848")]
+    #[case::big_int_literal("x = 9223372036854775808\n", "9223372036854775808")]
+    #[case::big_int_demotion(
+        "x = (9223372036854775808 - 1, type(1 << 64), (1 << 64) >> 63)\n",
+        "(9223372036854775807, \"int\", 2)"
+    )]
+    #[case::big_int_floor_division(
+        "x = (-(1 << 70) // 3, -(1 << 70) % 3, (1 << 70) // -(1 << 69))\n",
+        "(-393530540239137101142, 2, -2)"
+    )]
+    #[case::big_int_comparison(
+        "x = [1 << 64 > 1 << 63, 1 << 64 == 18446744073709551616.0, 1 << 64 < 1.5e19, {1 << 64: 1}[18446744073709551616.0]]\n",
+        "[True, True, False, 1]"
+    )]
+    #[case::bitwise(
+        "x = (6 & 3, 6 | 3, 6 ^ 3, ~6, 1 << 4, -16 >> 2)\n",
+        "(2, 7, 5, -7, 16, -4)"
+    )]
+    #[case::comparison(
+        "x = [1 < 2, 1 == 1.0, \"a\" >= \"b\", (1, 2) < (1, 3)]\n",
+        "[True, True, False, True]"
+    )]
+    #[case::membership(
+        "x = [1 in [1, 2], \"b\" in \"abc\", 3 not in {3: 4}]\n",
+        "[True, True, False]"
+    )]
+    #[case::short_circuit("x = 0 or \"default\"\n", "\"default\"")]
+    #[case::conditional("x = \"yes\" if [] else \"no\"\n", "\"no\"")]
+    #[case::string_concatenation("x = \"a\" + \"b\" * 3\n", "\"abbb\"")]
+    #[case::list_concatenation("x = [1] + [2] * 2\n", "[1, 2, 2]")]
+    #[case::dict_union("x = {\"a\": 1} | {\"b\": 2}\n", "{\"a\": 1, \"b\": 2}")]
+    #[case::tuple("x = 1, 2\n", "(1, 2)")]
+    #[case::singleton_tuple("x = (1,)\n", "(1,)")]
+    #[case::index("x = [\"a\", \"b\", \"c\"][-1]\n", "\"c\"")]
+    #[case::slice("x = [0, 1, 2, 3, 4][1:4]\n", "[1, 2, 3]")]
+    #[case::slice_with_step("x = \"abcdef\"[::-2]\n", "\"fdb\"")]
+    #[case::percent_format("x = \"%s=%d (%r)\" % (\"a\", 1, \"b\")\n", "\"a=1 (\\\"b\\\")\"")]
+    #[case::percent_format_mapping("x = \"%(a)s\" % {\"a\": 1}\n", "\"1\"")]
+    #[case::unpacking("a, [b, c] = 1, [2, 3]\nx = (a, b, c)\n", "(1, 2, 3)")]
+    #[case::augmented_assignment(
+        "def f():\n    y = 1\n    y += 2\n    y *= 3\n    return y\nx = f()\n",
+        "9"
+    )]
+    #[case::floor_division_assignment(
+        "def f():\n    a = 7\n    a //= 2\n    return a\nx = f()\n",
+        "3"
+    )]
+    #[case::augmented_list_assignment("x = [1]\ndef f(y):\n    y += [2]\nf(x)\n", "[1, 2]")]
+    #[case::index_assignment("x = {}\nx[\"a\"] = 1\nx[\"a\"] += 1\n", "{\"a\": 2}")]
+    #[case::list_comprehension("x = [i * i for i in range(5) if i % 2 == 0]\n", "[0, 4, 16]")]
+    #[case::nested_comprehension("x = [(i, j) for i in range(2) for j in range(i)]\n", "[(1, 0)]")]
+    #[case::dict_comprehension(
+        "x = {k: len(k) for k in [\"a\", \"bb\"]}\n",
+        "{\"a\": 1, \"bb\": 2}"
+    )]
+    #[case::for_loop(
+        "def f():\n    total = 0\n    for i in range(10):\n        if i == 5:\n            break\n        if i % 2:\n            continue\n        total += i\n    return total\nx = f()\n",
+        "6"
+    )]
+    #[case::if_elif_else(
+        "def f(n):\n    if n == 1:\n        return \"one\"\n    elif n == 2:\n        return \"two\"\n    else:\n        return \"many\"\nx = [f(1), f(2), f(3)]\n",
+        "[\"one\", \"two\", \"many\"]"
+    )]
+    #[case::function("def f(a, b = 2):\n    return a * b\nx = f(3)\n", "6")]
+    #[case::keyword_arguments("def f(a, b):\n    return a - b\nx = f(b = 1, a = 3)\n", "2")]
+    #[case::variadic(
+        "def f(a, *args, **kwargs):\n    return (a, args, kwargs)\nx = f(1, 2, 3, c = 4)\n",
+        "(1, (2, 3), {\"c\": 4})"
+    )]
+    #[case::keyword_only("def f(a, *, b):\n    return a + b\nx = f(1, b = 2)\n", "3")]
+    #[case::unpacked_arguments(
+        "def f(a, b, c):\n    return [a, b, c]\nx = f(*[1, 2], **{\"c\": 3})\n",
+        "[1, 2, 3]"
+    )]
+    #[case::closure("def adder(n):\n    return lambda x: x + n\nx = adder(2)(3)\n", "5")]
+    #[case::no_return("def f():\n    pass\nx = f()\n", "None")]
+    #[case::function_repr("def f():\n    pass\nx = f\n", "<function f>")]
+    #[case::method_repr("x = [].append\n", "<built-in method append of list value>")]
+    #[case::global_in_function("y = [1]\ndef f():\n    return y[0]\nx = f()\n", "1")]
+    fn evaluates(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(eval(text), expected);
+    }
+
+    #[rstest]
+    #[case::syntax_error("x = (\n", "Failed to parse")]
+    #[case::undefined("x = y\n", "`y` is not defined")]
+    #[case::division_by_zero("x = 1 // 0\n", "Division by zero")]
+    #[case::shift_too_large("x = 1 << 1000\n", "Shift count too large: 1000")]
+    #[case::unsupported_operands(
+        "x = 1 + \"a\"\n",
+        "Unsupported operand types for +: int and string"
+    )]
+    #[case::index_out_of_range("x = [1][1]\n", "Index 1 out of range")]
+    #[case::missing_key("x = {}[\"a\"]\n", "Key \"a\" not found in dict")]
+    #[case::unhashable("x = {[]: 1}\n", "Unhashable type: list")]
+    #[case::not_callable("x = 1()\n", "Value of type int is not callable")]
+    #[case::string_not_iterable(
+        "x = [c for c in \"abc\"]\n",
+        "Value of type string is not iterable"
+    )]
+    #[case::missing_argument("def f(a):\n    pass\nx = f()\n", "missing")]
+    #[case::unexpected_keyword(
+        "def f(a):\n    pass\nx = f(b = 1)\n",
+        "unexpected keyword argument `b`"
+    )]
+    #[case::recursion(
+        "def f(n):\n    return f(n - 1) if n else 0\nx = f(3)\n",
+        "Function `f` called recursively"
+    )]
+    #[case::local_before_assignment(
+        "def f():\n    y = x\n    x = 1\nf()\n",
+        "Local variable `x` referenced before assignment"
+    )]
+    #[case::mutation_during_iteration(
+        "x = [1]\nfor i in x:\n    x.append(i)\n",
+        "Cannot mutate list during iteration"
+    )]
+    #[case::fail("fail(\"oops\", 1)\n", "fail: oops 1")]
+    #[case::load(
+        "load(\":defs.bzl\", \"f\")\n",
+        "Cannot load `:defs.bzl`, as it wasn't loaded"
+    )]
+    fn fails(#[case] text: &str, #[case] expected: &str) {
+        let error = eval_error(text);
+        assert!(
+            error.contains(expected),
+            "{error:?} should contain {expected:?}"
+        );
+    }
+
+    #[test]
+    fn error_has_range() {
+        let text = "x = 1\ny = x // 0\n";
+        let error = Interpreter::new().eval("test.star", text).unwrap_err();
+
+        let range = error.range().expect("the error should have a range");
+        assert_eq!(&text[range], "x // 0");
+    }
+
+    #[test]
+    fn name_errors_are_static() {
+        let text = "print(\"unreachable\")\nx = y\n";
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let mut interpreter = Interpreter::new();
+        let sink = printed.clone();
+        interpreter.set_print(move |message| sink.borrow_mut().push(message.to_string()));
+
+        let error = interpreter.eval("test.star", text).unwrap_err();
+
+        assert!(matches!(error, Error::Name(_)));
+        assert_eq!(*printed.borrow(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn globals_are_frozen() {
+        let text = "x = [1]\ndef add(item):\n    x.append(item)\n";
+        let interpreter = Interpreter::new();
+        let module = interpreter.eval("test.star", text).unwrap();
+
+        assert!(module.get("x").unwrap().is_frozen());
+        let error = interpreter
+            .call(
+                module.get("add").unwrap(),
+                Arguments::positional(vec![Value::Int(2)]),
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Cannot mutate frozen list");
+    }
+
+    #[test]
+    fn globals_in_binding_order() {
+        let text = "b = 1\na = 2\ndef c():\n    pass\n";
+        let module = Interpreter::new().eval("test.star", text).unwrap();
+
+        let names: Vec<_> = module.globals().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn predeclared_values() {
+        let mut interpreter = Interpreter::new();
+        interpreter.predeclare("version", "1.0");
+        interpreter.predeclare(
+            "double",
+            Builtin::new("double", |_, arguments| {
+                let [x] = arguments.unpack("double", ["x"])?;
+                match x {
+                    Some(Value::Int(x)) => Ok(Value::Int(x * 2)),
+                    _ => Ok(Value::None),
+                }
+            }),
+        );
+
+        let module = interpreter
+            .eval("test.star", "x = (version, double(x = 21))\n")
+            .unwrap();
+
+        assert_eq!(module.get("x").unwrap().repr(), "(\"1.0\", 42)");
+    }
+
+    #[test]
+    fn print() {
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let mut interpreter = Interpreter::new();
+        let sink = printed.clone();
+        interpreter.set_print(move |message| sink.borrow_mut().push(message.to_string()));
+
+        interpreter
+            .eval(
+                "test.star",
+                "print(\"a\", 1, [\"b\"])\nprint(1, 2, sep = \"-\")\n",
+            )
+            .unwrap();
+
+        assert_eq!(*printed.borrow(), vec!["a 1 [\"b\"]", "1-2"]);
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod frozen_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_interpreter::{Arguments, FrozenModule, Interpreter, Module, Value};
+    use std::{collections::HashMap, sync::Arc};
+
+    const LIB: &str = "\
+_greeting = \"Hello\"
+
+def greet(name, punctuation = \"!\"):
+    return \"%s, %s%s\" % (_greeting, name, punctuation)
+
+shout = lambda name: greet(name.upper())
+";
+
+    fn freeze(name: &str, text: &str, loaded: HashMap<String, Module>) -> Arc<FrozenModule> {
+        let file = sky_syntax::File::parse(text).tree();
+        Interpreter::new()
+            .eval_loaded(name, file, loaded)
+            .unwrap()
+            .freeze()
+            .unwrap()
+    }
+
+    fn call(interpreter: &Interpreter, module: &Module, function: &str, name: &str) -> String {
+        let arguments = Arguments::positional(vec![Value::from(name)]);
+        interpreter
+            .call(module.get(function).unwrap(), arguments)
+            .unwrap()
+            .to_string()
+    }
+
+    #[rstest]
+    #[case::none("x = None\n", "None")]
+    #[case::float("x = (1.5, float(\"nan\"))\n", "(1.5, nan)")]
+    #[case::collections(
+        "x = {\"a\": [1, (2, b\"3\")], \"b\": struct(c = True)}\n",
+        "{\"a\": [1, (2, b\"3\")], \"b\": struct(c = True)}"
+    )]
+    #[case::builtin("x = len\n", "<built-in function len>")]
+    #[case::method("x = [1].index\n", "<built-in method index of list value>")]
+    fn test_thaw_values(#[case] text: &str, #[case] expected: &str) {
+        let frozen = freeze("//:test.bzl", text, HashMap::new());
+
+        let module = Interpreter::new().thaw(&frozen).unwrap();
+
+        let x = module.get("x").unwrap();
+        assert_eq!(x.repr(), expected);
+        assert!(x.is_frozen());
+    }
+
+    #[test]
+    fn test_thaw_functions() {
+        let frozen = freeze("//:lib.bzl", LIB, HashMap::new());
+
+        let interpreter = Interpreter::new();
+        let module = interpreter.thaw(&frozen).unwrap();
+
+        assert_eq!(module.name(), "//:lib.bzl");
+        assert_eq!(
+            call(&interpreter, &module, "greet", "world"),
+            "Hello, world!"
+        );
+        assert_eq!(
+            call(&interpreter, &module, "shout", "world"),
+            "Hello, WORLD!"
+        );
+    }
+
+    #[test]
+    fn test_thaw_loaded() {
+        let interpreter = Interpreter::new();
+        let lib = interpreter
+            .thaw(&freeze("//:lib.bzl", LIB, HashMap::new()))
+            .unwrap();
+        let loaded = HashMap::from([(":lib.bzl".to_string(), lib)]);
+        let frozen = freeze(
+            "//:main.bzl",
+            "load(\":lib.bzl\", hello = \"greet\")\n\ndef main():\n    return hello(\"main\", \"?\")\n",
+            loaded,
+        );
+
+        let module = interpreter.thaw(&frozen).unwrap();
+
+        // The loaded symbols aren't globals, but the functions still see them.
+        let globals: Vec<_> = module.globals().map(|(name, _)| name).collect();
+        assert_eq!(globals, vec!["main"]);
+        let result = interpreter.call(module.get("main").unwrap(), Arguments::default());
+        assert_eq!(result.unwrap().to_string(), "Hello, main?");
+        // Thawed modules freeze into the module they were thawed from.
+        assert_eq!(module.freeze().unwrap(), frozen);
+    }
+
+    #[test]
+    fn test_freeze_equality() {
+        let frozen = freeze("//:lib.bzl", LIB, HashMap::new());
+
+        assert_eq!(freeze("//:lib.bzl", LIB, HashMap::new()), frozen);
+        assert!(freeze("//:lib.bzl", &LIB.replace("Hello", "Hi"), HashMap::new()) != frozen);
+    }
+
+    #[rstest]
+    #[case::nested_function(
+        "def f():\n    def g():\n        pass\n    return g\n\ng = f()\n",
+        "Cannot freeze `g`, as it's nested in another function"
+    )]
+    #[case::recursive_list("x = []\nx.append(x)\n", "Cannot freeze a list containing itself")]
+    fn test_freeze_errors(#[case] text: &str, #[case] expected: &str) {
+        let module = Interpreter::new().eval("//:test.bzl", text).unwrap();
+
+        let error = module.freeze().unwrap_err();
+
+        assert_eq!(error.to_string(), expected);
+    }
+}
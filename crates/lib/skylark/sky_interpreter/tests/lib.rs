@@ -0,0 +1,3 @@
+mod builtins;
+mod eval;
+mod frozen;
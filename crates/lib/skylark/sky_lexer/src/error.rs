@@ -0,0 +1,108 @@
+//! **Lexical errors**, i.e. problems detected while tokenizing a source.
+//!
+//! Errors are plain data. They're only turned into (optionally colored)
+//! **rustc-style** diagnostics when rendered, see [`LexError::to_diagnostic`]
+//! and [`TokenSink::emit_errors`](crate::TokenSink::emit_errors).
+
+use crate::{unescape::EscapeError, FileId, Span};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use thiserror::Error;
+
+/// An error encountered while **lexing**.
+///
+/// Each kind of error has a **stable** error code (see [`LexError::code`]),
+/// which is never reused for a different kind of error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+pub enum LexError {
+    /// A run of characters which don't begin any valid token (e.g. `$`).
+    #[error("Unknown token encountered: `{lexeme}`")]
+    UnknownCharacter { lexeme: String, span: Span },
+    /// A string or bytes literal missing its closing quote(s).
+    #[error("Unterminated string literal")]
+    UnterminatedString { span: Span },
+    /// An invalid escape sequence within a string or bytes literal (e.g. `\d`).
+    #[error("{error}")]
+    InvalidEscape { error: EscapeError, span: Span },
+    /// A line which is dedented to a width not matching any enclosing block.
+    #[error("Inconsistent dedent: unindent does not match any outer indentation level")]
+    InconsistentDedent { expected: usize, span: Span },
+}
+
+impl LexError {
+    /// The **stable** error code of this kind of error (e.g. `E0000`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexError::UnknownCharacter { .. } => "E0000",
+            LexError::InconsistentDedent { .. } => "E0001",
+            LexError::UnterminatedString { .. } => "E0002",
+            LexError::InvalidEscape { .. } => "E0003",
+        }
+    }
+
+    /// The **span** of the offending source text.
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnknownCharacter { span, .. }
+            | LexError::UnterminatedString { span }
+            | LexError::InvalidEscape { span, .. }
+            | LexError::InconsistentDedent { span, .. } => *span,
+        }
+    }
+
+    /// Moves the error by the given (possibly negative) number of bytes.
+    pub(crate) fn shifted(mut self, delta: isize) -> Self {
+        match &mut self {
+            LexError::UnknownCharacter { span, .. }
+            | LexError::UnterminatedString { span }
+            | LexError::InvalidEscape { span, .. }
+            | LexError::InconsistentDedent { span, .. } => *span = span.shift(delta),
+        }
+
+        self
+    }
+
+    /// Converts the error into an **uncolored** [`Diagnostic`] for the file
+    /// with the given `file_id`. Any coloring is left to the renderer.
+    pub fn to_diagnostic(&self, file_id: FileId) -> Diagnostic<FileId> {
+        let range = self.span().range();
+        let diagnostic = Diagnostic::error()
+            .with_code(self.code())
+            .with_message(self.to_string());
+
+        match self {
+            LexError::UnknownCharacter { lexeme, .. } => diagnostic
+                .with_notes(vec![
+                    format!("The parser encountered an unknown token: `{lexeme}`"),
+                    "This may be due to a typo or an unsupported character in the input."
+                        .to_string(),
+                    "Please check the input and make sure it contains ONLY supported tokens."
+                        .to_string(),
+                    "For more information on supported tokens, please refer to the Starlark Language Specification."
+                        .to_string(),
+                ])
+                .with_labels(vec![
+                    Label::primary(file_id, range.clone())
+                        .with_message(format!("Unknown token found here: `{lexeme}`")),
+                    Label::secondary(file_id, range)
+                        .with_message("Valid tokens should be used exclusively in the input."),
+                ]),
+            LexError::UnterminatedString { .. } => diagnostic
+                .with_notes(vec![
+                    "Single-quoted literals must be closed on the same line, whereas triple-quoted literals may span multiple lines.".to_string(),
+                ])
+                .with_labels(vec![Label::primary(file_id, range)
+                    .with_message("Missing closing quote(s) for this literal.")]),
+            LexError::InvalidEscape { .. } => diagnostic.with_labels(vec![Label::primary(
+                file_id, range,
+            )
+            .with_message("Invalid escape sequence found here.")]),
+            LexError::InconsistentDedent { expected, .. } => diagnostic
+                .with_notes(vec![format!(
+                    "The enclosing block is indented by {expected} columns."
+                )])
+                .with_labels(vec![
+                    Label::primary(file_id, range).with_message("Dedent found here.")
+                ]),
+        }
+    }
+}
@@ -0,0 +1,1184 @@
+#![allow(unused)]
+
+mod indentation;
+
+use crate::{
+    unescape::{self, Mode},
+    LexError,
+};
+use anyhow::{anyhow, Result};
+use codespan_reporting::{
+    files::{self, Files, SimpleFile, SimpleFiles},
+    term::{
+        self,
+        termcolor::{ColorChoice, StandardStream, WriteColor},
+    },
+};
+use derive_more::Display;
+use getset::{Getters, MutGetters, Setters};
+use indentation::Indentation;
+use itertools::Itertools;
+use logos::Logos;
+use owo_colors::OwoColorize;
+use std::{
+    cmp::Ordering,
+    fmt::{self, write, Debug, Display},
+    fs::File,
+    io::Read,
+    ops::Range,
+    path::PathBuf,
+};
+use text_size::TextRange;
+use typed_builder::TypedBuilder;
+
+/// The **virtual file name** given to sources lexed via [`StarlarkLexer::tokenize`].
+pub const STDIN: &str = "STDIN";
+
+pub type FileId = usize;
+
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[display(fmt = "{start}..{end}")]
+pub struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns true if the span overlaps with the given range
+    /// inclusively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sky_lexer::Span;
+    ///
+    /// let span = Span::new(2, 3);
+    /// assert!(span.overlaps(2..3));
+    /// assert!(span.overlaps(2..4));
+    /// assert!(span.overlaps(1..3));
+    /// assert!(span.overlaps(1..4));
+    /// assert!(!span.overlaps(0..1));
+    /// assert!(!span.overlaps(4..5));
+    /// ```
+    pub fn overlaps(&self, range: Range<usize>) -> bool {
+        self.start <= range.end && range.start <= self.end
+    }
+
+    fn merge(&self, range: Range<usize>) -> Self {
+        Self {
+            start: self.start.min(range.start),
+            end: self.end.max(range.end),
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Moves the span by the given (possibly negative) number of bytes.
+    pub(crate) fn shift(&self, delta: isize) -> Self {
+        Self {
+            start: (self.start as isize + delta) as usize,
+            end: (self.end as isize + delta) as usize,
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Getters, MutGetters, Setters, TypedBuilder,
+)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct Token {
+    kind: TokenKind,
+    lexeme: String,
+    span: Span,
+}
+
+impl Token {
+    pub fn new(kind: TokenKind, lexeme: String, span: Span) -> Self {
+        Self { kind, lexeme, span }
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.lexeme = text;
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.span.start..self.span.end
+    }
+
+    pub fn pretty_print(&self) -> String {
+        format!("{} {} {}", self.kind.blue(), self.lexeme, self.span.red(),)
+    }
+
+    pub fn new_eof() -> Self {
+        Self {
+            kind: TokenKind::EOF,
+            lexeme: String::new(),
+            span: Span::new(0, 0),
+        }
+    }
+
+    pub fn is_keyword(&self, kw: &str) -> bool {
+        self.lexeme().eq(kw)
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self.kind(),
+            TokenKind::WHITESPACE | TokenKind::COMMENT // | TokenKind::NEWLINE
+                                                       // | TokenKind::INDENT
+                                                       // | TokenKind::OUTDENT
+        )
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lexeme)
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Getters, MutGetters, Setters, TypedBuilder,
+)]
+pub struct TokenStream {
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    text: String,
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    tokens: Vec<Token>,
+    #[builder(default = 0)]
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    cursor: usize,
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    file_name: PathBuf,
+    file_id: FileId,
+}
+
+impl TokenStream {
+    fn new(file_id: FileId, file_name: PathBuf) -> Self {
+        let mut file = File::open(&file_name).expect("Unable to open file for lexing");
+        let mut text = String::new();
+        file.read_to_string(&mut text)
+            .expect("Unable to read file for lexing");
+
+        Self {
+            tokens: Vec::new(),
+            cursor: 0,
+            file_id,
+            file_name,
+            text,
+        }
+    }
+
+    /// Creates an **empty** token stream for the given **in-memory** source,
+    /// identified by a (possibly virtual) `file_name`. The filesystem is never
+    /// accessed.
+    fn from_source(file_id: FileId, file_name: PathBuf, text: String) -> Self {
+        Self {
+            tokens: Vec::new(),
+            cursor: 0,
+            file_id,
+            file_name,
+            text,
+        }
+    }
+
+    fn from_db_file(file_id: FileId, db_file: SimpleFile<&str, String>) -> Self {
+        let text = db_file.source().to_string();
+
+        Self {
+            tokens: Vec::new(),
+            cursor: 0,
+            file_id,
+            file_name: db_file.name().into(),
+            text,
+        }
+    }
+
+    fn push(&mut self, token: Token) {
+        self.tokens.push(token);
+    }
+
+    pub fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.cursor + n)
+    }
+
+    pub fn lookahead_nth(&self, n: usize) -> Option<&Token> {
+        self.peek_nth(n)
+    }
+
+    fn peek_next(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor + 1)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        self.cursor += 1;
+        self.tokens.get(self.cursor)
+    }
+
+    pub fn advance_n(&mut self, n: usize) -> Option<&Token> {
+        self.cursor += n;
+        self.tokens.get(self.cursor)
+    }
+
+    fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn is_eof(&self) -> bool {
+        self.cursor >= self.len()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.cursor == self.len() - 1
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|token| token.lexeme == keyword)
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn source(&self) -> &str {
+        &self.text
+    }
+
+    pub fn current_range(&self) -> Range<usize> {
+        self.peek().map_or(0..0, |token| token.range())
+    }
+
+    pub fn current(&self) -> Option<&Token> {
+        self.peek()
+    }
+
+    pub fn bump(&mut self) -> Option<&Token> {
+        self.advance()
+    }
+
+    pub fn empty_stream() -> Self {
+        Self {
+            text: String::new(),
+            tokens: Vec::new(),
+            cursor: 0,
+            file_id: 0,
+            file_name: "empty_stream".into(),
+        }
+    }
+
+    /// Removes all **trivia** (i.e. whitespace and comments) from the stream.
+    pub fn remove_trivia(&mut self) {
+        self.tokens.retain(|token| !token.is_trivia());
+    }
+}
+
+impl Iterator for TokenStream {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor += 1;
+        self.tokens.get(self.cursor).cloned()
+    }
+}
+
+impl Display for TokenStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        for token in &self.tokens {
+            output.push_str(&token.lexeme);
+        }
+
+        write!(f, "{output}")
+    }
+}
+
+/// A [`TokenStream`] carries its own source text, and as such can act as the
+/// **file database** used to render its diagnostics.
+impl<'a> Files<'a> for TokenStream {
+    type FileId = FileId;
+    type Name = String;
+    type Source = &'a str;
+
+    fn name(&'a self, _: FileId) -> Result<Self::Name, files::Error> {
+        Ok(self.file_name.display().to_string())
+    }
+
+    fn source(&'a self, _: FileId) -> Result<Self::Source, files::Error> {
+        Ok(&self.text)
+    }
+
+    fn line_index(&'a self, _: FileId, byte_index: usize) -> Result<usize, files::Error> {
+        Ok(files::line_starts(&self.text)
+            .take_while(|&line_start| line_start <= byte_index)
+            .count()
+            .saturating_sub(1))
+    }
+
+    fn line_range(&'a self, _: FileId, line_index: usize) -> Result<Range<usize>, files::Error> {
+        let line_starts = files::line_starts(&self.text).collect_vec();
+        let line_start = |line_index: usize| match line_index.cmp(&line_starts.len()) {
+            Ordering::Less => Ok(line_starts[line_index]),
+            Ordering::Equal => Ok(self.text.len()),
+            Ordering::Greater => Err(files::Error::LineTooLarge {
+                given: line_index,
+                max: line_starts.len() - 1,
+            }),
+        };
+
+        Ok(line_start(line_index)?..line_start(line_index + 1)?)
+    }
+}
+
+impl From<&str> for TokenStream {
+    fn from(input: &str) -> Self {
+        StarlarkLexer::lex_source(0, STDIN, input).tokens
+    }
+}
+
+#[derive(Debug, Clone, Getters, MutGetters, Setters, TypedBuilder)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct StarlarkLexer {
+    files: SimpleFiles<String, String>,
+    file_ids: Vec<FileId>,
+    token_sink: TokenSink,
+}
+
+impl Default for StarlarkLexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StarlarkLexer {
+    pub fn new() -> Self {
+        let mut files = SimpleFiles::new();
+        let mut file_ids = Vec::new();
+
+        let token_sink = TokenSink::empty_sink();
+
+        Self {
+            files,
+            file_ids,
+            token_sink,
+        }
+    }
+
+    fn num_errors(&self) -> usize {
+        self.token_sink.lexical_errors().len()
+    }
+
+    pub fn from_file(path: PathBuf) -> Result<(Self, FileId)> {
+        let mut file = File::open(path.clone())?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let mut files = SimpleFiles::new();
+        let file_id = files.add(path.to_string_lossy().to_string(), contents.clone());
+
+        Ok((
+            Self {
+                files,
+                file_ids: vec![file_id],
+                token_sink: TokenSink::new(file_id, path),
+            },
+            file_id,
+        ))
+    }
+
+    pub fn add_file(&mut self, path: PathBuf) -> Result<FileId> {
+        let mut file = File::open(&path)?;
+
+        let mut input = String::new();
+        file.read_to_string(&mut input)?;
+
+        let file_id = self
+            .files
+            .add(path.to_string_lossy().to_string(), input.clone());
+
+        self.file_ids.push(file_id);
+
+        Ok(file_id)
+    }
+
+    /// Adds an **in-memory** source to the lexer under the given (possibly
+    /// virtual) file `name`, which is used when reporting diagnostics. Returns
+    /// the [`FileId`] with which the source can later be lexed.
+    pub fn add_source(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        let file_id = self.files.add(name.into(), source.into());
+        self.file_ids.push(file_id);
+
+        file_id
+    }
+
+    /// Lexes the file with the given `file_id`, previously registered via
+    /// [`add_source`](Self::add_source) or [`add_file`](Self::add_file).
+    ///
+    /// Lexing only requires **shared access** to the lexer, so a single lexer
+    /// can lex many files **concurrently** (e.g. from a thread pool).
+    pub fn lex(&self, file_id: FileId) -> Result<TokenSink> {
+        let file = self.get_db_file(file_id)?;
+
+        Ok(Self::lex_source(file_id, file.name(), file.source()))
+    }
+
+    /// Lexes the given **in-memory** `source`, identified by `file_id` and a
+    /// (possibly virtual) `file_name`, without touching the filesystem or any
+    /// shared state.
+    pub fn lex_source(file_id: FileId, file_name: impl Into<PathBuf>, source: &str) -> TokenSink {
+        let mut token_sink = TokenSink::from_source(file_id, file_name.into(), source.to_string());
+        lex_into(file_id, source, &mut token_sink);
+
+        token_sink
+    }
+
+    /// **Incrementally** relexes the (lossless) token stream `old` after the
+    /// text within `edit` has been replaced by `new_text`, returning the token
+    /// stream of the edited source.
+    ///
+    /// Only the _damaged_ region is relexed. Lexing restarts at the last
+    /// **top-level** logical line beginning before the edit (where the offside
+    /// rule holds no state), and resynchronizes with the previous tokens at the
+    /// first top-level logical line following the edit. Tokens on either side
+    /// are reused (shifted past the edit as needed). Edits which open or close
+    /// a string or comment simply push the point of resynchronization further
+    /// out, up to the end of the file.
+    ///
+    /// Lexical errors are _not_ reported, see [`relex_sink`](Self::relex_sink)
+    /// for that. Relexing always produces the same tokens as lexing the edited
+    /// source from scratch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `edit` is out of bounds of, or doesn't fall on character
+    /// boundaries within, the text of `old`.
+    pub fn relex(old: &TokenStream, edit: TextRange, new_text: &str) -> TokenStream {
+        relex_region(old, edit, new_text).tokens
+    }
+
+    /// **Incrementally** relexes the token sink `old`, like
+    /// [`relex`](Self::relex), while also keeping its **lexical errors** up to
+    /// date. Errors within the relexed region are reported anew, whereas those
+    /// on either side of it are reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`relex`](Self::relex).
+    pub fn relex_sink(old: &TokenSink, edit: TextRange, new_text: &str) -> TokenSink {
+        let Relexed {
+            tokens,
+            errors,
+            old_region,
+            new_region,
+        } = relex_region(&old.tokens, edit, new_text);
+        let delta = new_region.end as isize - old_region.end as isize;
+
+        let mut lexical_errors = old
+            .lexical_errors
+            .iter()
+            .filter(|error| error.span().start() < old_region.start)
+            .cloned()
+            .collect_vec();
+        lexical_errors.extend(
+            errors
+                .into_iter()
+                .filter(|error| error.span().start() < new_region.end),
+        );
+        lexical_errors.extend(
+            old.lexical_errors
+                .iter()
+                .filter(|error| error.span().start() >= old_region.end)
+                .map(|error| error.clone().shifted(delta)),
+        );
+
+        TokenSink {
+            tokens,
+            lexical_errors,
+        }
+    }
+
+    /// Lexes the given `source` into **raw** tokens, i.e. without applying the
+    /// offside rule: there are no `INDENT` or `OUTDENT` tokens, every newline
+    /// is kept as a `NEWLINE` token, and no `EOF` token is appended. Useful for
+    /// lexing a _fragment_ of a source, such as a single token.
+    pub fn lex_raw(source: &str) -> (Vec<Token>, Vec<LexError>) {
+        let mut raw_tokens = RawTokens::new(source, 0);
+        let tokens = raw_tokens.by_ref().collect_vec();
+
+        (tokens, raw_tokens.errors)
+    }
+
+    pub fn lex_db_file(&mut self, file_id: FileId) -> Result<TokenSink> {
+        let token_sink = self.lex(file_id)?;
+        self.set_token_sink(token_sink.clone());
+
+        Ok(token_sink)
+    }
+
+    /// Emits **rustc-style** error messages for the lexical errors of the
+    /// most recently tokenized source to `stderr`, colored according to the
+    /// given `color_choice`.
+    pub fn emit_errors(&self, color_choice: ColorChoice) {
+        tracing::debug!(
+            "Emitting {} lexically invalid tokens",
+            self.token_sink.lexical_errors().len()
+        );
+
+        self.token_sink.emit_errors(color_choice);
+
+        tracing::info!(
+            "{} lexically invalid tokens emitted",
+            self.token_sink.lexical_errors().len()
+        );
+    }
+
+    fn get_db_file(&self, file_id: usize) -> Result<&SimpleFile<String, String>> {
+        if let Ok(file) = self.files.get(file_id) {
+            Ok(file)
+        } else {
+            Err(anyhow!("File not found"))
+            // Err(SkylarkError::FileNotFound(file_id).into())
+        }
+    }
+
+    /// Tokenizes the given `source` **in-memory** under the virtual file name
+    /// [`STDIN`]. See [`tokenize_as`](Self::tokenize_as).
+    pub fn tokenize(&mut self, source: &str) -> TokenSink {
+        self.tokenize_as(STDIN, source)
+    }
+
+    /// Tokenizes the given `source` **in-memory** under the caller-provided
+    /// virtual file `name`, retaining the resulting [`TokenSink`] so that any
+    /// lexical errors can later be emitted via [`emit_errors`](Self::emit_errors).
+    /// The filesystem is never accessed.
+    pub fn tokenize_as(&mut self, name: impl Into<String>, source: &str) -> TokenSink {
+        let name = name.into();
+        let file_id = self.add_source(name.clone(), source);
+        let token_sink = Self::lex_source(file_id, name, source);
+        self.set_token_sink(token_sink.clone());
+
+        token_sink
+    }
+}
+
+/// Lexes the given `input` into the `token_sink`, applying the **offside
+/// rule** (see [`indentation`]) to the raw tokens produced by [`logos`] and
+/// terminating the stream with an `EOF` token.
+///
+/// Consecutive unrecognized characters are glued together into a single
+/// `UNKNOWN` token, each of which is reported as a lexical error. Errors are
+/// reported in source order.
+fn lex_into(file_id: FileId, input: &str, token_sink: &mut TokenSink) {
+    let mut raw_tokens = RawTokens::new(input, 0);
+    let tokens = raw_tokens.by_ref().collect_vec();
+    token_sink.lexical_errors.extend(raw_tokens.errors);
+
+    let end = input.len();
+    let (tokens, indentation_errors) = Indentation::new().process(tokens, end);
+    token_sink.lexical_errors.extend(indentation_errors);
+    token_sink
+        .lexical_errors
+        .sort_by_key(|error| error.span().start());
+
+    for token in tokens {
+        token_sink.tokens.push(token);
+    }
+
+    token_sink.tokens.push(Token::new(
+        TokenKind::EOF,
+        "".to_string(),
+        Span::new(end, end),
+    ));
+}
+
+/// The result of relexing the _damaged_ region of a token stream, see
+/// [`StarlarkLexer::relex`].
+struct Relexed {
+    tokens: TokenStream,
+    /// The lexical errors found within (and possibly just past) `new_region`.
+    errors: Vec<LexError>,
+    /// The byte range of the text which was relexed, before the edit.
+    old_region: Range<usize>,
+    /// The byte range of the text which was relexed, after the edit.
+    new_region: Range<usize>,
+}
+
+fn relex_region(old: &TokenStream, edit: TextRange, new_text: &str) -> Relexed {
+    let (start, end) = (usize::from(edit.start()), usize::from(edit.end()));
+    let mut text = old.text.clone();
+    text.replace_range(start..end, new_text);
+
+    let delta = new_text.len() as isize - (end - start) as isize;
+    let edit_end = start + new_text.len();
+
+    let restart = old.tokens[..old.tokens.partition_point(|token| token.span.start < start)]
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, _)| index)
+        .find(|&index| starts_top_level_line(&old.tokens, &old.text, index));
+    let (reused, offset) = restart.map_or((0, 0), |index| (index, old.tokens[index].span.start));
+    tracing::debug!("Relexing {:?} from offset {}", edit, offset);
+
+    let mut tokens = old.tokens[..reused].to_vec();
+    let mut indentation = Indentation::new();
+    let mut raw_tokens = RawTokens::new(&text, offset);
+
+    while let Some(token) = raw_tokens.next() {
+        if token.span.start >= edit_end && indentation.starts_top_level_line(&token) {
+            let old_start = (token.span.start as isize - delta) as usize;
+            let index = old
+                .tokens
+                .partition_point(|token| token.span.start < old_start);
+            let index = index
+                + old.tokens[index..]
+                    .iter()
+                    .take_while(|token| token.kind == TokenKind::OUTDENT)
+                    .count();
+
+            if matches!(old.tokens.get(index), Some(token) if token.span.start == old_start)
+                && starts_top_level_line(&old.tokens, &old.text, index)
+            {
+                tracing::debug!("Resynchronized with the previous tokens at {}", old_start);
+                let new_start = token.span.start;
+
+                // Dedenting to the top level may close a different number of
+                // blocks than before the edit, so the `OUTDENT`s are kept.
+                indentation.push(token);
+                let (relexed, mut errors) = indentation.into_parts();
+                tokens.extend(relexed);
+                tokens.pop();
+                tokens.extend(old.tokens[index..].iter().map(|token| {
+                    Token::new(token.kind, token.lexeme.clone(), token.span.shift(delta))
+                }));
+                errors.extend(raw_tokens.errors);
+                errors.sort_by_key(|error| error.span().start());
+
+                return Relexed {
+                    tokens: TokenStream {
+                        tokens,
+                        cursor: 0,
+                        file_id: old.file_id,
+                        file_name: old.file_name.clone(),
+                        text,
+                    },
+                    errors,
+                    old_region: offset..old_start,
+                    new_region: offset..new_start,
+                };
+            }
+        }
+
+        indentation.push(token);
+    }
+
+    let end = text.len();
+    let (relexed, mut errors) = indentation.process(Vec::new(), end);
+    tokens.extend(relexed);
+    tokens.push(Token::new(
+        TokenKind::EOF,
+        String::new(),
+        Span::new(end, end),
+    ));
+    errors.extend(raw_tokens.errors);
+    errors.sort_by_key(|error| error.span().start());
+
+    Relexed {
+        tokens: TokenStream {
+            tokens,
+            cursor: 0,
+            file_id: old.file_id,
+            file_name: old.file_name.clone(),
+            text,
+        },
+        errors,
+        old_region: offset..old.text.len(),
+        new_region: offset..end,
+    }
+}
+
+/// Whether the token at `index` begins a **top-level** logical line of the
+/// given (lossless) `tokens`, i.e. one which isn't indented, continued, or
+/// nested within brackets.
+fn starts_top_level_line(tokens: &[Token], text: &str, index: usize) -> bool {
+    let token = &tokens[index];
+    let start = token.span.start;
+
+    !token.is_trivia()
+        && !matches!(
+            token.kind,
+            TokenKind::NEWLINE | TokenKind::INDENT | TokenKind::OUTDENT | TokenKind::EOF
+        )
+        && (start == 0 || text[..start].ends_with('\n'))
+        && matches!(
+            tokens[..index]
+                .iter()
+                .rev()
+                .find(|token| !token.is_trivia() && token.kind != TokenKind::OUTDENT),
+            None | Some(Token {
+                kind: TokenKind::NEWLINE,
+                ..
+            })
+        )
+}
+
+/// The **raw** tokens of a source (i.e. before the offside rule is applied),
+/// starting from a given offset. Runs of unrecognized characters are glued
+/// together into a single `UNKNOWN` token, whereas unterminated string
+/// literals make up an `UNKNOWN` token of their own.
+///
+/// Any lexical errors are collected along the way.
+struct RawTokens<'a> {
+    lexer: logos::Lexer<'a, TokenKind>,
+    current_unknown_token: Option<Token>,
+    next_token: Option<Token>,
+    errors: Vec<LexError>,
+}
+
+impl<'a> RawTokens<'a> {
+    fn new(input: &'a str, offset: usize) -> Self {
+        let mut lexer = TokenKind::lexer(input);
+        lexer.bump(offset);
+
+        Self {
+            lexer,
+            current_unknown_token: None,
+            next_token: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Returns the flushed `unknown_token` (if any), queueing up `token` to be
+    /// returned next, or `token` itself otherwise.
+    fn after_unknown_token(&mut self, unknown_token: Option<Token>, token: Token) -> Token {
+        match unknown_token {
+            Some(unknown_token) => {
+                self.next_token = Some(token);
+                unknown_token
+            }
+            None => token,
+        }
+    }
+
+    fn flush_unknown_token(&mut self) -> Option<Token> {
+        let unknown_token = self.current_unknown_token.take()?;
+        self.errors.push(LexError::UnknownCharacter {
+            lexeme: unknown_token.lexeme.clone(),
+            span: unknown_token.span,
+        });
+
+        Some(unknown_token)
+    }
+}
+
+impl Iterator for RawTokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = self.next_token.take() {
+            return Some(token);
+        }
+
+        while let Some(token_result) = self.lexer.next() {
+            let lexer = &self.lexer;
+            match token_result {
+                Ok(kind) => {
+                    let token = Token::new(kind, lexer.slice().to_string(), lexer.span().into());
+                    let unknown_token = self.flush_unknown_token();
+
+                    let mode = match kind {
+                        TokenKind::STRING => Some(Mode::Str),
+                        TokenKind::BYTES => Some(Mode::Bytes),
+                        _ => None,
+                    };
+                    for (range, error) in mode
+                        .map(|mode| unescape::invalid_escapes(&token.lexeme, mode))
+                        .unwrap_or_default()
+                    {
+                        let start = token.span.start;
+                        self.errors.push(LexError::InvalidEscape {
+                            error,
+                            span: Span::new(start + range.start, start + range.end),
+                        });
+                    }
+
+                    return Some(self.after_unknown_token(unknown_token, token));
+                }
+                Err(()) if is_unterminated_string(lexer.slice()) => {
+                    let token = Token::new(
+                        TokenKind::UNKNOWN,
+                        lexer.slice().to_string(),
+                        lexer.span().into(),
+                    );
+                    let unknown_token = self.flush_unknown_token();
+                    tracing::debug!("Unterminated string literal at {}", token.span);
+                    self.errors
+                        .push(LexError::UnterminatedString { span: token.span });
+
+                    return Some(self.after_unknown_token(unknown_token, token));
+                }
+                Err(()) => {
+                    if let Some(unknown_token) = self.current_unknown_token.clone() {
+                        let Token {
+                            kind: _,
+                            span,
+                            lexeme,
+                        } = unknown_token;
+
+                        let span = span.merge(lexer.span());
+                        let updated_lexeme = format!("{}{}", lexeme, lexer.slice());
+
+                        tracing::debug!(
+                            "Gluing together unknown tokens {} and {} to form {} at {}",
+                            lexeme,
+                            lexer.slice(),
+                            updated_lexeme,
+                            span
+                        );
+
+                        self.current_unknown_token =
+                            Some(Token::new(TokenKind::UNKNOWN, updated_lexeme, span));
+                    } else {
+                        tracing::debug!(
+                            "Creating new unknown token {} at {:?}",
+                            lexer.slice(),
+                            lexer.span()
+                        );
+
+                        self.current_unknown_token = Some(Token::new(
+                            TokenKind::UNKNOWN,
+                            lexer.slice().to_string(),
+                            lexer.span().into(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        self.flush_unknown_token()
+    }
+}
+
+/// Whether the given unrecognized text is an **unterminated** string or bytes
+/// literal, i.e. starts with an (optionally prefixed) quote.
+fn is_unterminated_string(text: &str) -> bool {
+    matches!(
+        text.find(['"', '\'']),
+        Some(quote) if quote <= 2 && text[..quote].chars().all(|c| "bBrR".contains(c))
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters, Setters, MutGetters, TypedBuilder)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
+pub struct TokenSink {
+    pub tokens: TokenStream,
+    pub lexical_errors: Vec<LexError>,
+}
+
+impl TokenSink {
+    pub fn new(file_id: FileId, file_name: PathBuf) -> Self {
+        Self {
+            tokens: TokenStream::new(file_id, file_name),
+            lexical_errors: Vec::new(),
+        }
+    }
+
+    /// Creates an empty sink for the given **in-memory** source, identified by
+    /// a (possibly virtual) `file_name`.
+    pub fn from_source(file_id: FileId, file_name: PathBuf, text: String) -> Self {
+        Self {
+            tokens: TokenStream::from_source(file_id, file_name, text),
+            lexical_errors: Vec::new(),
+        }
+    }
+
+    pub fn empty_sink() -> Self {
+        Self {
+            tokens: TokenStream::empty_stream(),
+            lexical_errors: Vec::new(),
+        }
+    }
+
+    pub fn from_db_file(file_id: FileId, db_file: &SimpleFile<&str, String>) -> Self {
+        Self {
+            tokens: TokenStream::from_db_file(file_id, db_file.clone()),
+            lexical_errors: Vec::new(),
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.lexical_errors.is_empty()
+    }
+
+    pub fn add_error(&mut self, error: LexError) {
+        self.lexical_errors.push(error);
+    }
+
+    /// Emits **rustc-style** error messages for any lexical errors to `stderr`,
+    /// colored according to the given `color_choice`.
+    pub fn emit_errors(&self, color_choice: ColorChoice) {
+        let mut writer = StandardStream::stderr(color_choice);
+
+        self.write_errors(&mut writer)
+            .expect("Could not emit error");
+    }
+
+    /// Renders **rustc-style** error messages for any lexical errors to the
+    /// given `writer`. Colors are only used if the writer supports them.
+    pub fn write_errors(&self, writer: &mut dyn WriteColor) -> Result<(), files::Error> {
+        let config = codespan_reporting::term::Config::default();
+
+        for error in &self.lexical_errors {
+            let diagnostic = error.to_diagnostic(self.tokens.file_id);
+            term::emit(writer, &config, &self.tokens, &diagnostic)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Logos, Debug, Display, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TokenKind {
+    // Punctuation
+    // #[regex("[\\+\\-\\*/%&|^<>]=?")]
+    // #[regex("(==)|(!=)|(<=)|(>=)|(\\*\\*)|<<|>>|=")]
+    // Punctuation
+    #[token("+")]
+    PLUS,
+    #[token("-")]
+    MINUS,
+    #[token("*")]
+    STAR,
+    #[token("/")]
+    SLASH,
+    #[token("//")]
+    DSLASH,
+    #[token("%")]
+    PERCENT,
+    #[token("**")]
+    DSTAR,
+    #[token("~")]
+    TILDE,
+    #[token("&")]
+    AMP,
+    #[token("|")]
+    PIPE,
+    #[token("^")]
+    CARET,
+    #[token("<<")]
+    LSHIFT,
+    #[token(">>")]
+    RSHIFT,
+    #[token("=")]
+    EQ,
+    #[token("<")]
+    LT,
+    #[token(">")]
+    GT,
+    #[token(">=")]
+    GE,
+    #[token("<=")]
+    LE,
+    #[token("==")]
+    EQEQ,
+    #[token("!=")]
+    NE,
+    #[token("+=")]
+    PLUSEQ,
+    #[token("-=")]
+    MINUSEQ,
+    #[token("*=")]
+    STAREQ,
+    #[token("/=")]
+    SLASHEQ,
+    #[token("//=")]
+    DSLASHEQ,
+    #[token("%=")]
+    PERCENTEQ,
+    #[token("&=")]
+    AMPEQ,
+    #[token("|=")]
+    PIPEEQ,
+    #[token("^=")]
+    CARETEQ,
+    #[token("<<=")]
+    LSHIFTEQ,
+    #[token(">>=")]
+    RSHIFTEQ,
+    #[token("->")]
+    THIN_ARROW,
+
+    #[token(".")]
+    DOT,
+    #[token(",")]
+    COMMA,
+    #[token(";")]
+    SEMICOLON,
+    #[token(":")]
+    COLON,
+    #[token("(")]
+    LPAREN,
+    #[token(")")]
+    RPAREN,
+    #[token("[")]
+    LBRACKET,
+    #[token("]")]
+    RBRACKET,
+    #[token("{")]
+    LBRACE,
+    #[token("}")]
+    RBRACE,
+
+    // Keywords
+    #[token("and")]
+    AND_KW,
+    #[token("else")]
+    ELSE_KW,
+    #[token("load")]
+    LOAD_KW,
+    #[token("break")]
+    BREAK_KW,
+    #[token("for")]
+    FOR_KW,
+    #[token("not")]
+    NOT_KW,
+    #[token("continue")]
+    CONTINUE_KW,
+    #[token("if")]
+    IF_KW,
+    #[token("or")]
+    OR_KW,
+    #[token("def")]
+    DEF_KW,
+    #[token("in")]
+    IN_KW,
+    #[token("pass")]
+    PASS_KW,
+    #[token("elif")]
+    ELIF_KW,
+    #[token("lambda")]
+    LAMBDA_KW,
+    #[token("return")]
+    RETURN_KW,
+
+    // Identifiers and literals
+    #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
+    IDENTIFIER,
+    // Digits outside of the radix (e.g. `0o8`) and decimals with leading
+    // zeros (e.g. `012`) are lexed as a single INT and reported on validation.
+    #[regex("[0-9]+|0[xX][0-9a-fA-F]*|0[oO][0-9]*|0[bB][0-9]*")]
+    INT,
+    #[regex("[0-9]+\\.[0-9]*([eE][+-]?[0-9]+)?|\\.[0-9]+([eE][+-]?[0-9]+)?|[0-9]+[eE][+-]?[0-9]+")]
+    FLOAT,
+    #[regex(r#"[rR]?["']"#, string_literal)]
+    STRING,
+    #[regex(r#"([bB][rR]?|[rR][bB])["']"#, string_literal)]
+    BYTES,
+
+    // Whitespace and special tokens
+    #[regex("#[^\n]*")]
+    COMMENT,
+    /// Spaces, tabs and form feeds, along with **line continuations** (i.e. a
+    /// backslash immediately followed by a line break), which join the next
+    /// line onto the current one.
+    #[regex("([ \t\x0C]|\\\\\r?\n)+")]
+    WHITESPACE,
+    #[regex("\r?\n")]
+    NEWLINE,
+
+    INDENT,
+    OUTDENT,
+    UNKNOWN,
+    #[end]
+    EOF,
+}
+
+/// Lexes the **remainder** of a string or bytes literal, given that its
+/// (optional) prefix and opening quote have already been matched.
+///
+/// Both **single-quoted** (`"..."`, `'...'`) and **triple-quoted**
+/// (`"""..."""`, `'''...'''`) literals are supported, the latter of which
+/// may span multiple lines. A backslash always escapes the following
+/// character, even in **raw** literals (e.g. `r"\""`), which is why raw and
+/// regular literals share the same scanning logic.
+///
+/// Returns `false` if the literal is **unterminated**, in which case the
+/// consumed input is reported as an unknown token.
+fn string_literal(lexer: &mut logos::Lexer<TokenKind>) -> bool {
+    let quote = *lexer
+        .slice()
+        .as_bytes()
+        .last()
+        .expect("string literals start with a quote");
+    let remainder = lexer.remainder().as_bytes();
+    let triple = remainder.starts_with(&[quote, quote]);
+
+    let mut i = if triple { 2 } else { 0 };
+    while let Some(&c) = remainder.get(i) {
+        match c {
+            b'\\' => i += 2,
+            b'\n' if !triple => break,
+            c if c == quote && !triple => {
+                lexer.bump(i + 1);
+                return true;
+            }
+            c if c == quote && remainder[i..].starts_with(&[quote; 3]) => {
+                lexer.bump(i + 3);
+                return true;
+            }
+            _ => i += 1,
+        }
+    }
+
+    lexer.bump(i.min(remainder.len()));
+    false
+}
+
+impl TokenKind {
+    pub fn is_whitespace(self) -> bool {
+        matches!(
+            self,
+            TokenKind::WHITESPACE // TokenKind::WHITESPACE | TokenKind::NEWLINE | TokenKind::INDENT | TokenKind::OUTDENT
+        )
+    }
+}
@@ -0,0 +1,245 @@
+//! The **offside rule** for Starlark.
+//!
+//! The raw token stream produced by [`logos`] knows nothing about the
+//! _significance_ of leading whitespace. This module post-processes that
+//! stream, tracking an **indentation stack** and synthesizing the
+//! `INDENT` and `OUTDENT` tokens the grammar relies on to delimit blocks
+//! (see `grammar::decl::suite` in `sky_syntax`).
+//!
+//! The pass is **lossless**: no text is dropped or invented. Leading
+//! whitespace that opens a block becomes the lexeme of the `INDENT` token,
+//! whereas `OUTDENT` tokens (and the `NEWLINE` terminating a file without a
+//! trailing line break) are zero-width. Newlines which carry no meaning to the
+//! parser (e.g. _blank lines_, _comment-only lines_, and line breaks _inside
+//! brackets_) are demoted to `WHITESPACE` trivia. Explicit **line
+//! continuations** (`\` followed by a line break) are already lexed as
+//! `WHITESPACE`, and as such never terminate a logical line.
+
+use super::{Span, Token, TokenKind};
+use crate::LexError;
+
+/// The number of columns a **tab** advances the indentation to (i.e. the
+/// next multiple of `TAB_WIDTH`).
+const TAB_WIDTH: usize = 8;
+
+/// A single level of the **indentation stack**.
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    /// The width of the indentation, in columns.
+    width: usize,
+    /// Whether an `INDENT` token was emitted when entering this level. Levels
+    /// pushed while recovering from an inconsistent dedent are not, and as such
+    /// don't produce an `OUTDENT` token when popped, keeping the two balanced.
+    indented: bool,
+}
+
+/// Tracks **indentation** and **bracket nesting** over a raw token stream.
+#[derive(Debug)]
+pub(super) struct Indentation {
+    stack: Vec<Level>,
+    depth: usize,
+    at_line_start: bool,
+    leading_whitespace: Option<Token>,
+    tokens: Vec<Token>,
+    errors: Vec<LexError>,
+}
+
+impl Indentation {
+    pub(super) fn new() -> Self {
+        Self {
+            stack: vec![Level {
+                width: 0,
+                indented: false,
+            }],
+            depth: 0,
+            at_line_start: true,
+            leading_whitespace: None,
+            tokens: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Applies the **offside rule** to the given raw tokens (excluding `EOF`),
+    /// returning the resulting tokens along with any **inconsistent dedents**
+    /// encountered along the way.
+    pub(super) fn process(
+        mut self,
+        raw_tokens: Vec<Token>,
+        end: usize,
+    ) -> (Vec<Token>, Vec<LexError>) {
+        for token in raw_tokens {
+            self.push(token);
+        }
+        self.finish(end);
+
+        (self.tokens, self.errors)
+    }
+
+    /// The tokens produced, and errors found, so far.
+    pub(super) fn into_parts(self) -> (Vec<Token>, Vec<LexError>) {
+        (self.tokens, self.errors)
+    }
+
+    /// Whether the given (raw) token would begin a **top-level** logical line,
+    /// i.e. one which isn't indented. Pushing such a token resets the
+    /// indentation stack, so the state of the pass no longer depends on any of
+    /// the tokens preceding it.
+    pub(super) fn starts_top_level_line(&self, token: &Token) -> bool {
+        self.at_line_start
+            && self.leading_whitespace.is_none()
+            && !matches!(
+                token.kind,
+                TokenKind::WHITESPACE | TokenKind::COMMENT | TokenKind::NEWLINE
+            )
+    }
+
+    pub(super) fn push(&mut self, token: Token) {
+        if self.at_line_start {
+            match token.kind {
+                TokenKind::WHITESPACE => {
+                    self.leading_whitespace = Some(token);
+                    return;
+                }
+                TokenKind::COMMENT => {
+                    // Comment-only lines don't participate in indentation.
+                    self.flush_leading_whitespace();
+                    self.tokens.push(token);
+                    return;
+                }
+                TokenKind::NEWLINE => {
+                    // Neither do blank lines.
+                    self.flush_leading_whitespace();
+                    self.tokens.push(into_trivia(token));
+                    return;
+                }
+                _ => {
+                    self.indent(token.span.start);
+                    self.at_line_start = false;
+                }
+            }
+        }
+
+        match token.kind {
+            TokenKind::LPAREN | TokenKind::LBRACKET | TokenKind::LBRACE => self.depth += 1,
+            TokenKind::RPAREN | TokenKind::RBRACKET | TokenKind::RBRACE => {
+                self.depth = self.depth.saturating_sub(1)
+            }
+            TokenKind::NEWLINE if self.depth > 0 => {
+                tracing::debug!("Suppressing newline within brackets at {}", token.span);
+                self.tokens.push(into_trivia(token));
+                return;
+            }
+            TokenKind::NEWLINE => self.at_line_start = true,
+            _ => (),
+        }
+
+        self.tokens.push(token);
+    }
+
+    /// Compares the indentation of the current line (i.e. its leading
+    /// whitespace) against the top of the **indentation stack**, emitting
+    /// `INDENT` or `OUTDENT` tokens as required.
+    fn indent(&mut self, offset: usize) {
+        let (width, span) = self
+            .leading_whitespace
+            .as_ref()
+            .map_or((0, Span::new(offset, offset)), |whitespace| {
+                (measure(&whitespace.lexeme), whitespace.span)
+            });
+        let current = self.current_width();
+
+        if width > current {
+            tracing::debug!("Indenting from {} to {} columns", current, width);
+            self.stack.push(Level {
+                width,
+                indented: true,
+            });
+
+            let indent = match self.leading_whitespace.take() {
+                Some(whitespace) => {
+                    Token::new(TokenKind::INDENT, whitespace.lexeme, whitespace.span)
+                }
+                None => Token::new(TokenKind::INDENT, String::new(), Span::new(offset, offset)),
+            };
+            self.tokens.push(indent);
+            return;
+        }
+
+        self.flush_leading_whitespace();
+
+        while width < self.current_width() {
+            let level = self.stack.pop().expect("indentation stack is never empty");
+            tracing::debug!("Outdenting from {} columns", level.width);
+            if level.indented {
+                self.tokens.push(outdent(offset));
+            }
+        }
+
+        if width > self.current_width() {
+            tracing::debug!(
+                "Inconsistent dedent to {} columns (expected {})",
+                width,
+                self.current_width()
+            );
+            self.errors.push(LexError::InconsistentDedent {
+                expected: self.current_width(),
+                span,
+            });
+            self.stack.push(Level {
+                width,
+                indented: false,
+            });
+        }
+    }
+
+    /// Terminates the final logical line (if necessary) and closes any blocks
+    /// which are still open at the end of the file.
+    fn finish(&mut self, end: usize) {
+        self.flush_leading_whitespace();
+
+        if !self.at_line_start {
+            self.tokens.push(Token::new(
+                TokenKind::NEWLINE,
+                String::new(),
+                Span::new(end, end),
+            ));
+        }
+
+        while let Some(level) = self.stack.pop() {
+            if level.indented {
+                self.tokens.push(outdent(end));
+            }
+        }
+    }
+
+    fn current_width(&self) -> usize {
+        self.stack.last().map_or(0, |level| level.width)
+    }
+
+    fn flush_leading_whitespace(&mut self) {
+        if let Some(whitespace) = self.leading_whitespace.take() {
+            self.tokens.push(whitespace);
+        }
+    }
+}
+
+/// Measures the **width** of the given leading whitespace, in columns. Only
+/// the first physical line counts towards the indentation of a line which is
+/// continued with a backslash.
+fn measure(whitespace: &str) -> usize {
+    whitespace
+        .chars()
+        .take_while(|&c| c != '\\')
+        .fold(0, |width, c| match c {
+            '\t' => (width / TAB_WIDTH + 1) * TAB_WIDTH,
+            _ => width + 1,
+        })
+}
+
+fn outdent(offset: usize) -> Token {
+    Token::new(TokenKind::OUTDENT, String::new(), Span::new(offset, offset))
+}
+
+fn into_trivia(token: Token) -> Token {
+    Token::new(TokenKind::WHITESPACE, token.lexeme, token.span)
+}
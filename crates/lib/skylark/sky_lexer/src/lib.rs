@@ -1,6 +1,3 @@
-// TODO: refactor from syntax into lexer
-// currently all the logic is housed in the syntax crate
-
 //! `StarlarkLexer` is a lexer for the Starlark language.
 //!
 //! Starlark is a dialect of Python that is designed for use in configuration files and build systems.
@@ -12,66 +9,95 @@
 //!
 //! Basic usage:
 //!
-//! ```rust,ignore
-//! use sky_lexer::StarlarkLexer;
+//! ```rust
+//! use sky_lexer::{ColorChoice, StarlarkLexer};
+//! # use std::path::PathBuf;
 //!
+//! # fn main() -> anyhow::Result<()> {
 //! let source_code = "load(\"@bazel_tools//tools/build_defs/repo:git.bzl\", \"git_repository\")";
+//! # let source_file = std::env::temp_dir().join("sky_lexer_example.bzl");
+//! # std::fs::write(&source_file, source_code)?;
 //!
-//! let source_file = Pathbuf::from("example.bzl")?;
 //! let mut lexer = StarlarkLexer::new();
-//! let file_id = lexer.add_source(source_code.to_string()); // Add raw source code text to the lexer. (useful for repl-like environments, or just one-off processing)
-//! let file_id = lexer.add_file(source_file); // Add a file to the lexer. (useful for batch processing)
-//! let token_sink = lexer.lex(file_id);
+//! // Add raw source code text to the lexer under a virtual file name.
+//! // (useful for repl-like environments, or just one-off processing)
+//! let file_id = lexer.add_source("example.bzl", source_code);
+//! // Add a file to the lexer. (useful for batch processing)
+//! let other_file_id = lexer.add_file(source_file)?;
+//! let token_sink = lexer.lex(file_id)?;
 //!
 //! // Iterate over the tokens and print them.
 //! // Tokens have a lot of associated metadata packed into them, including the token kind,
 //! // the token text (lexeme), and the token location (span).
-//! for token in token_sink.tokens() {
+//! for token in token_sink.tokens().tokens() {
 //!     println!("{}", token);
 //! }
 //!
 //! // Emit rustc-style error messages for unknown tokens.
 //! if token_sink.has_errors() {
-//!    token_sink.emit_errors();
+//!     token_sink.emit_errors(ColorChoice::Auto);
 //! }
+//! # assert_eq!(token_sink.tokens().to_string(), source_code);
+//! # assert_eq!(lexer.lex(other_file_id)?.tokens().to_string(), source_code);
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! # Features
 //!
 //! - Tokenizes Starlark source code into a sequence of tokens
 //! - Handles tokenizing multiple files and assigns unique FileIds to each file
+//! - Lexes entirely **in-memory**, and only requires shared access to the lexer, so
+//!   many files can be lexed **concurrently**
+//! - Supports **incremental** relexing of edited text ranges via `StarlarkLexer::relex`
+//!   (or `StarlarkLexer::relex_sink`, which also keeps lexical errors up to date),
+//!   reusing the tokens on either side of an edit
 //! - Allows for easy retrieval of tokens and their associated metadata
 //! - Provides detailed error reporting for unknown tokens encountered during lexing
 //!
 //! - Using Starlark lexer you get a sequence of tokens from which you can build off from,
-//! as well as detailed rustc-styleerror reporting for unknown tokens for free, just
-//! call `emit_errors` on the `TokenSink` returned by `lex`.
+//!   as well as detailed rustc-style error reporting for unknown tokens for free, just
+//!   call `emit_errors` on the `TokenSink` returned by `lex`.
 //!
 //! # Limitations
 //!
 //! - TokenKind::UNKNOWN represents unknown tokens, and error handling can be improved
 //! - Tokenization performance can be further optimized, right now we are storing
-//! a lot of metadata in each token, which is not necessary for all use cases
-//! - Tokenization is not incremental, and is done in one pass
+//!   a lot of metadata in each token, which is not necessary for all use cases
 //! - Tokenization is not configurable, and is done according to the Starlark language spec
 //!
 //! # Error Reporting
 //!
-//! In case an unknown token is encountered during lexing, the lexer emits detailed error messages
-//! with information about the invalid token and its location in the source code. These error messages
-//! can be emitted to stderr using the `emit_errors` method.
+//! Lexical errors (e.g. unknown characters, unterminated strings, invalid escape sequences,
+//! or inconsistent dedents) are reported as structured [`LexError`]s, each with a stable
+//! error code and the span of the offending text. They can be rendered as rustc-style
+//! error messages using the `emit_errors` method, with colors applied only at render time
+//! according to the given [`ColorChoice`] (e.g. `ColorChoice::Never` when piping to a file).
 //!
 //! ```
-//! use starlark_lex::StarlarkLexer;
+//! use sky_lexer::{ColorChoice, LexError, StarlarkLexer};
 //!
-//! let source_code = "load(\"@bazel_tools//tools/build_defs/repo:git.bzl\", \"git_repository\")";
+//! let source_code = "x = 'unterminated";
 //! let mut lexer = StarlarkLexer::new();
-//! let file_id = lexer.add_file(source_code.to_string()).unwrap();
-//! let token_sink = lexer.lex(file_id).unwrap();
+//! let token_sink = lexer.tokenize(source_code);
+//!
+//! let error = &token_sink.lexical_errors()[0];
+//! assert!(matches!(error, LexError::UnterminatedString { .. }));
+//! assert_eq!(error.code(), "E0002");
 //!
 //! // Emit any errors found during lexing.
-//! lexer.emit_errors().unwrap();
+//! lexer.emit_errors(ColorChoice::Never);
 //! ```
 //!
 //! For more information on the Starlark language and its grammar, please refer to the
 //! [Starlark Language Specification](https://github.com/bazelbuild/starlark/blob/master/spec.md).
+
+mod error;
+mod lexer;
+pub mod unescape;
+
+pub use crate::{
+    error::LexError,
+    lexer::{FileId, Span, StarlarkLexer, Token, TokenKind, TokenSink, TokenStream, STDIN},
+};
+pub use codespan_reporting::term::termcolor::ColorChoice;
@@ -0,0 +1,217 @@
+//! **Decoding** of the escape sequences within string and bytes literals.
+//!
+//! This lives alongside the lexer so that invalid escape sequences can be
+//! reported as [`LexError`]s, while the syntax tree reuses it to compute the
+//! values of literals.
+//!
+//! [`LexError`]: crate::LexError
+
+use std::ops::Range;
+
+use text_size::{TextRange, TextSize};
+use thiserror::Error;
+
+/// The **anatomy** of a string or bytes literal, e.g. `rb'''contents'''`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralParts {
+    /// Whether the literal is **raw** (e.g. `r"\d+"`).
+    pub is_raw: bool,
+    /// The number of quotes delimiting the literal (i.e. `1` or `3`).
+    pub quote_len: usize,
+    /// The range of the contents, relative to the start of the literal.
+    pub contents: Range<usize>,
+}
+
+impl LiteralParts {
+    pub fn new(text: &str) -> Self {
+        let prefix_len = text.find(['"', '\'']).unwrap_or(text.len());
+        let is_raw = text[..prefix_len].contains(['r', 'R']);
+
+        let rest = &text[prefix_len..];
+        let quote_len = match rest.chars().next() {
+            Some(quote) if rest.len() >= 6 && rest.starts_with(&quote.to_string().repeat(3)) => 3,
+            Some(_) => 1,
+            None => 0,
+        };
+
+        let start = (prefix_len + quote_len).min(text.len());
+        let end = text.len().saturating_sub(quote_len).max(start);
+
+        Self {
+            is_raw,
+            quote_len,
+            contents: start..end,
+        }
+    }
+
+    /// The range of the contents, relative to the start of the file, given
+    /// the `offset` of the literal.
+    pub fn absolute_range(&self, offset: TextSize) -> TextRange {
+        TextRange::new(
+            offset + TextSize::from(self.contents.start as u32),
+            offset + TextSize::from(self.contents.end as u32),
+        )
+    }
+}
+
+/// Whether escape sequences are being decoded for a **string** or a **bytes**
+/// literal, which determines the range of permitted `\x` and octal escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Str,
+    Bytes,
+}
+
+/// A single **unit** of a decoded literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unescaped {
+    Char(char),
+    Byte(u8),
+}
+
+/// The ways in which an **escape sequence** can be invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum EscapeError {
+    /// An unknown escape sequence, e.g. `\d`.
+    #[error("Invalid escape sequence `\\{0}`")]
+    InvalidEscape(char),
+    /// A lone backslash at the end of the literal.
+    #[error("Invalid trailing backslash")]
+    LoneBackslash,
+    /// A `\x` escape not followed by exactly two hex digits.
+    #[error("Invalid `\\x` escape, expected exactly two hex digits")]
+    InvalidHexEscape,
+    /// A `\x` or octal escape above `0x7f` in a string literal.
+    #[error("Non-ASCII `\\x` or octal escape in string literal, use `\\u` instead")]
+    NonAsciiEscape,
+    /// An octal escape above `0o377`.
+    #[error("Octal escape out of range, expected at most `\\377`")]
+    OctalOutOfRange,
+    /// A `\u` or `\U` escape not followed by exactly four or eight hex digits.
+    #[error("Invalid unicode escape, expected `\\uXXXX` or `\\UXXXXXXXX`")]
+    InvalidUnicodeEscape,
+    /// A `\u` or `\U` escape denoting a surrogate or a value above `0x10ffff`.
+    #[error("Invalid unicode code point `{0:#x}`")]
+    InvalidCodePoint(u32),
+}
+
+/// Checks the escape sequences of the given string or bytes `literal`,
+/// returning the range (relative to the start of the literal) of each invalid
+/// one along with its error.
+pub fn invalid_escapes(literal: &str, mode: Mode) -> Vec<(Range<usize>, EscapeError)> {
+    let parts = LiteralParts::new(literal);
+    let mut errors = Vec::new();
+    if parts.is_raw {
+        return errors;
+    }
+
+    let offset = parts.contents.start;
+    unescape(&literal[parts.contents], mode, &mut |range, result| {
+        if let Err(error) = result {
+            errors.push((offset + range.start..offset + range.end, error));
+        }
+    });
+
+    errors
+}
+
+/// **Decodes** the escape sequences in the contents of a (non-raw) literal,
+/// invoking `callback` with the range (relative to the contents) and the
+/// result of decoding each unit.
+pub fn unescape(
+    contents: &str,
+    mode: Mode,
+    callback: &mut impl FnMut(Range<usize>, Result<Unescaped, EscapeError>),
+) {
+    let mut chars = contents.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            callback(start..start + c.len_utf8(), Ok(Unescaped::Char(c)));
+            continue;
+        }
+
+        let Some((_, escape)) = chars.next() else {
+            callback(start..contents.len(), Err(EscapeError::LoneBackslash));
+            break;
+        };
+
+        let simple = match escape {
+            'a' => Some('\x07'),
+            'b' => Some('\x08'),
+            'f' => Some('\x0c'),
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            'v' => Some('\x0b'),
+            '\\' | '\'' | '"' => Some(escape),
+            _ => None,
+        };
+        if let Some(c) = simple {
+            callback(start..start + 2, Ok(Unescaped::Char(c)));
+            continue;
+        }
+
+        let result = match escape {
+            // A backslash at the end of a line joins it with the next.
+            '\n' => continue,
+            '\r' => {
+                chars.next_if(|&(_, c)| c == '\n');
+                continue;
+            }
+            '0'..='7' => {
+                let mut value = escape.to_digit(8).expect("octal digit");
+                for _ in 0..2 {
+                    match chars.next_if(|&(_, c)| c.is_digit(8)) {
+                        Some((_, digit)) => {
+                            value = value * 8 + digit.to_digit(8).expect("octal digit")
+                        }
+                        None => break,
+                    }
+                }
+                byte_escape(value, mode).ok_or(if value > 0o377 {
+                    EscapeError::OctalOutOfRange
+                } else {
+                    EscapeError::NonAsciiEscape
+                })
+            }
+            'x' => match hex_digits(&mut chars, 2) {
+                Some(value) => byte_escape(value, mode).ok_or(EscapeError::NonAsciiEscape),
+                None => Err(EscapeError::InvalidHexEscape),
+            },
+            'u' | 'U' => {
+                let len = if escape == 'u' { 4 } else { 8 };
+                match hex_digits(&mut chars, len) {
+                    Some(value) => char::from_u32(value)
+                        .map(Unescaped::Char)
+                        .ok_or(EscapeError::InvalidCodePoint(value)),
+                    None => Err(EscapeError::InvalidUnicodeEscape),
+                }
+            }
+            _ => Err(EscapeError::InvalidEscape(escape)),
+        };
+
+        let end = chars.peek().map_or(contents.len(), |&(end, _)| end);
+        callback(start..end, result);
+    }
+}
+
+/// Decodes a `\x` or octal escape, which denotes a **byte** in bytes literals
+/// but is restricted to **ASCII** in string literals.
+fn byte_escape(value: u32, mode: Mode) -> Option<Unescaped> {
+    match mode {
+        Mode::Str if value <= 0x7f => Some(Unescaped::Char(char::from(value as u8))),
+        Mode::Bytes if value <= 0xff => Some(Unescaped::Byte(value as u8)),
+        _ => None,
+    }
+}
+
+/// Consumes exactly `len` **hex digits**, returning their value.
+fn hex_digits(chars: &mut std::iter::Peekable<std::str::CharIndices>, len: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..len {
+        let (_, digit) = chars.next_if(|&(_, c)| c.is_ascii_hexdigit())?;
+        value = value * 16 + digit.to_digit(16).expect("hex digit");
+    }
+    Some(value)
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod errors_test_suite {
+    use codespan_reporting::term::termcolor::Buffer;
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_lexer::{unescape::EscapeError, LexError, Span, StarlarkLexer};
+
+    fn lexical_errors(source: &str) -> Vec<LexError> {
+        StarlarkLexer::new()
+            .tokenize(source)
+            .lexical_errors()
+            .clone()
+    }
+
+    #[rstest]
+    #[case::unknown_character(
+        "x = $$ + 1\n",
+        LexError::UnknownCharacter { lexeme: "$$".to_string(), span: Span::new(4, 6) },
+        "E0000",
+    )]
+    #[case::inconsistent_dedent(
+        "if x:\n    pass\n  pass\n",
+        LexError::InconsistentDedent { expected: 0, span: Span::new(15, 17) },
+        "E0001",
+    )]
+    #[case::unterminated_string(
+        "x = 'abc\ny = 1\n",
+        LexError::UnterminatedString { span: Span::new(4, 8) },
+        "E0002",
+    )]
+    #[case::unterminated_triple_quoted_bytes(
+        "x = b'''abc\ny = 1\n",
+        LexError::UnterminatedString { span: Span::new(4, 18) },
+        "E0002",
+    )]
+    #[case::invalid_escape(
+        "x = 'a\\d'\n",
+        LexError::InvalidEscape { error: EscapeError::InvalidEscape('d'), span: Span::new(6, 8) },
+        "E0003",
+    )]
+    fn test_lexical_errors(#[case] source: &str, #[case] expected: LexError, #[case] code: &str) {
+        let errors = lexical_errors(source);
+
+        assert_eq!(errors, vec![expected]);
+        assert_eq!(errors[0].code(), code);
+    }
+
+    #[test]
+    fn test_unterminated_string_is_not_glued_to_unknown_characters() {
+        assert_eq!(
+            lexical_errors("$'abc"),
+            vec![
+                LexError::UnknownCharacter {
+                    lexeme: "$".to_string(),
+                    span: Span::new(0, 1)
+                },
+                LexError::UnterminatedString {
+                    span: Span::new(1, 5)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_strings_have_no_invalid_escapes() {
+        assert_eq!(lexical_errors("x = r'\\d'\n"), vec![]);
+    }
+
+    #[test]
+    fn test_errors_are_uncolored_until_rendered() {
+        let token_sink = StarlarkLexer::new().tokenize_as("BUILD", "x = $\n");
+
+        let error = &token_sink.lexical_errors()[0];
+        let diagnostic = error.to_diagnostic(token_sink.tokens().file_id());
+        assert!(!diagnostic.message.contains('\u{1b}'));
+        assert!(diagnostic
+            .notes
+            .iter()
+            .chain(diagnostic.labels.iter().map(|label| &label.message))
+            .all(|text| !text.contains('\u{1b}')));
+
+        let mut plain = Buffer::no_color();
+        token_sink.write_errors(&mut plain).unwrap();
+        let plain = String::from_utf8(plain.into_inner()).unwrap();
+        assert!(plain.contains("error[E0000]: Unknown token encountered: `$`"));
+        assert!(plain.contains("BUILD:1:5"));
+        assert!(!plain.contains('\u{1b}'));
+
+        let mut colored = Buffer::ansi();
+        token_sink.write_errors(&mut colored).unwrap();
+        assert!(String::from_utf8(colored.into_inner())
+            .unwrap()
+            .contains('\u{1b}'));
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod lexer_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_lexer::{StarlarkLexer, TokenKind, TokenKind::*};
+    use std::path::Path;
+
+    /// Lexes the given source, discarding any trivia.
+    fn significant_kinds(source: &str) -> Vec<TokenKind> {
+        StarlarkLexer::new()
+            .tokenize(source)
+            .tokens()
+            .tokens()
+            .iter()
+            .filter(|token| !token.is_trivia())
+            .map(|token| *token.kind())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::flat("x = y\n", vec![IDENTIFIER, EQ, IDENTIFIER, NEWLINE, EOF])]
+    #[case::no_trailing_newline("pass", vec![PASS_KW, NEWLINE, EOF])]
+    #[case::block(
+        "def f():\n    pass\n",
+        vec![DEF_KW, IDENTIFIER, LPAREN, RPAREN, COLON, NEWLINE, INDENT, PASS_KW, NEWLINE, OUTDENT, EOF],
+    )]
+    #[case::nested_blocks(
+        "def f():\n  for x in y:\n    if x:\n      pass\n  return\n",
+        vec![
+            DEF_KW, IDENTIFIER, LPAREN, RPAREN, COLON, NEWLINE,
+            INDENT, FOR_KW, IDENTIFIER, IN_KW, IDENTIFIER, COLON, NEWLINE,
+            INDENT, IF_KW, IDENTIFIER, COLON, NEWLINE,
+            INDENT, PASS_KW, NEWLINE,
+            OUTDENT, OUTDENT, RETURN_KW, NEWLINE,
+            OUTDENT, EOF,
+        ],
+    )]
+    #[case::tabs(
+        "if x:\n\tpass\n        pass\n",
+        vec![IF_KW, IDENTIFIER, COLON, NEWLINE, INDENT, PASS_KW, NEWLINE, PASS_KW, NEWLINE, OUTDENT, EOF],
+    )]
+    #[case::blank_and_comment_lines(
+        "if x:\n\n    # comment\n    pass\n# trailing\n",
+        vec![IF_KW, IDENTIFIER, COLON, NEWLINE, INDENT, PASS_KW, NEWLINE, OUTDENT, EOF],
+    )]
+    #[case::brackets(
+        "f(\n    x,\n  y,\n)\n",
+        vec![IDENTIFIER, LPAREN, IDENTIFIER, COMMA, IDENTIFIER, COMMA, RPAREN, NEWLINE, EOF],
+    )]
+    #[case::unterminated_block("if x:\n    pass", vec![IF_KW, IDENTIFIER, COLON, NEWLINE, INDENT, PASS_KW, NEWLINE, OUTDENT, EOF])]
+    #[case::nested_brackets(
+        "x = [\n    {\n        y: (\n            z),\n    },\n]\n",
+        vec![IDENTIFIER, EQ, LBRACKET, LBRACE, IDENTIFIER, COLON, LPAREN, IDENTIFIER, RPAREN, COMMA, RBRACE, COMMA, RBRACKET, NEWLINE, EOF],
+    )]
+    #[case::comments_within_brackets(
+        "f(  # first\n    # second\n    x,\n)\n",
+        vec![IDENTIFIER, LPAREN, IDENTIFIER, COMMA, RPAREN, NEWLINE, EOF],
+    )]
+    #[case::line_continuation(
+        "x = \\\n    y\n",
+        vec![IDENTIFIER, EQ, IDENTIFIER, NEWLINE, EOF],
+    )]
+    #[case::crlf_line_continuation(
+        "x = \\\r\ny\r\n",
+        vec![IDENTIFIER, EQ, IDENTIFIER, NEWLINE, EOF],
+    )]
+    #[case::line_continuation_within_block(
+        "if x:\n    y = \\\nz\n    pass\n",
+        vec![IF_KW, IDENTIFIER, COLON, NEWLINE, INDENT, IDENTIFIER, EQ, IDENTIFIER, NEWLINE, PASS_KW, NEWLINE, OUTDENT, EOF],
+    )]
+    fn test_indentation(#[case] source: &str, #[case] expected: Vec<TokenKind>) {
+        assert_eq!(significant_kinds(source), expected);
+    }
+
+    #[rstest]
+    #[case::flat("x = y\n")]
+    #[case::nested("def f():\n  if x:\n\tpass\n\n  # done\n  return\n")]
+    #[case::brackets("f(\n    x,\n  y,\n)\n")]
+    #[case::line_continuation("if x:\n    y = \\\r\n  z\n")]
+    fn test_indentation_is_lossless(#[case] source: &str) {
+        assert_eq!(
+            StarlarkLexer::new().tokenize(source).tokens().to_string(),
+            source
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_dedent() {
+        let token_sink = StarlarkLexer::new().tokenize("if x:\n    pass\n  pass\n");
+
+        assert_eq!(token_sink.lexical_errors().len(), 1);
+        assert_eq!(token_sink.lexical_errors()[0].code(), "E0001");
+        assert_eq!(token_sink.lexical_errors()[0].span().range(), 15..17);
+    }
+
+    #[test]
+    fn test_tokenize_as_virtual_file() {
+        let source = "x = 'unterminated\n";
+        let token_sink = StarlarkLexer::new().tokenize_as("<virtual>/BUILD", source);
+
+        assert_eq!(
+            token_sink.tokens().file_name(),
+            Path::new("<virtual>/BUILD")
+        );
+        assert_eq!(token_sink.tokens().to_string(), source);
+        assert!(token_sink.has_errors());
+    }
+
+    #[test]
+    fn test_concurrent_lexing() {
+        let mut lexer = StarlarkLexer::new();
+        let sources = (0..16)
+            .map(|i| format!("def f{i}():\n    return {i}\n"))
+            .collect::<Vec<_>>();
+        let file_ids = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| lexer.add_source(format!("file_{i}.star"), source.as_str()))
+            .collect::<Vec<_>>();
+
+        let lexer = &lexer;
+        std::thread::scope(|scope| {
+            let handles = file_ids
+                .iter()
+                .map(|&file_id| scope.spawn(move || lexer.lex(file_id).unwrap()))
+                .collect::<Vec<_>>();
+
+            for ((handle, source), file_id) in handles.into_iter().zip(&sources).zip(&file_ids) {
+                let token_sink = handle.join().unwrap();
+
+                assert_eq!(token_sink.tokens().file_id(), *file_id);
+                assert_eq!(token_sink.tokens().to_string(), *source);
+                assert!(!token_sink.has_errors());
+            }
+        });
+    }
+
+    #[test]
+    fn test_lex_unknown_file() {
+        assert!(StarlarkLexer::new().lex(42).is_err());
+    }
+}
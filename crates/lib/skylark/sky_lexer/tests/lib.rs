@@ -0,0 +1,3 @@
+mod errors;
+mod lexer;
+mod relex;
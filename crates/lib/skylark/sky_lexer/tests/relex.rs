@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod relex_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_lexer::{StarlarkLexer, TokenSink, TokenStream};
+    use text_size::{TextRange, TextSize};
+
+    const SOURCE: &str = r#"load("//foo:bar.bzl", "baz")
+
+def f(x):
+    if x:
+        return "yes"  # comment
+    return [
+        1,
+        2]
+
+y = f(1)
+"#;
+
+    const SOURCE_WITH_ERRORS: &str = r#"x = "\d" $
+
+def f():
+    if x:
+        pass
+  return 'y
+"#;
+
+    fn lex(source: &str) -> TokenStream {
+        StarlarkLexer::new().tokenize(source).tokens().clone()
+    }
+
+    fn lex_sink(source: &str) -> TokenSink {
+        StarlarkLexer::new().tokenize(source)
+    }
+
+    /// Relexes `SOURCE` after replacing the first occurrence of `before` by
+    /// `after`, and checks that the result matches lexing from scratch.
+    fn assert_relex(source: &str, before: &str, after: &str) {
+        let start = source.find(before).expect("edited text not found");
+        let edit = TextRange::at(
+            TextSize::from(start as u32),
+            TextSize::from(before.len() as u32),
+        );
+        let edited = source.replacen(before, after, 1);
+
+        let relexed = StarlarkLexer::relex(&lex(source), edit, after);
+
+        assert_eq!(relexed.source(), edited);
+        assert_eq!(relexed.tokens(), lex(&edited).tokens());
+    }
+
+    #[rstest]
+    #[case::rename("return \"yes\"", "return \"no\"")]
+    #[case::insert_statement("y = f(1)\n", "y = f(1)\nz = y\n")]
+    #[case::delete_statement("y = f(1)\n", "")]
+    #[case::open_block("y = f(1)", "if y:\n    y = f(1)")]
+    #[case::indent_line("    return [", "        return [")]
+    #[case::dedent_block("        return \"yes\"", "    return \"yes\"")]
+    #[case::inconsistent_dedent("    return [", "  return [")]
+    #[case::within_brackets("        2]", "        2,\n    3]")]
+    #[case::open_bracket("y = f(1)", "y = f(1")]
+    #[case::close_bracket("        2]", "        2")]
+    #[case::open_string("\"yes\"", "\"\"\"yes")]
+    #[case::close_string("\"yes\"", "'''yes\"")]
+    #[case::open_comment("def f(x):", "# def f(x):")]
+    #[case::close_comment("# comment", " comment")]
+    #[case::line_continuation("y = f(1)", "y = \\\nf(1)")]
+    #[case::join_lines("baz\")\n\ndef", "baz\")def")]
+    #[case::unknown_character("y = f(1)", "y = f(1) $")]
+    #[case::start_of_file("load", "  load")]
+    #[case::end_of_file("y = f(1)\n", "y = f(1)")]
+    fn test_relex_matches_full_lex(#[case] before: &str, #[case] after: &str) {
+        assert_relex(SOURCE, before, after);
+    }
+
+    #[test]
+    fn test_relex_reopened_string() {
+        let source = "x = '''a\n\ny = 1\n";
+
+        assert_relex(source, "'''a", "'''a'''");
+        assert_relex(&source.replace("'''a", "'''a'''"), "'''a'''", "'''a");
+    }
+
+    #[test]
+    fn test_relex_every_single_character_deletion() {
+        for (start, c) in SOURCE.char_indices() {
+            let before = &SOURCE[start..start + c.len_utf8()];
+            let edit = TextRange::at(
+                TextSize::from(start as u32),
+                TextSize::from(before.len() as u32),
+            );
+            let edited = format!("{}{}", &SOURCE[..start], &SOURCE[start + before.len()..]);
+
+            assert_eq!(
+                StarlarkLexer::relex(&lex(SOURCE), edit, "").tokens(),
+                lex(&edited).tokens(),
+                "after deleting {:?} at {}",
+                before,
+                start
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::add_unknown_character("pass", "pass $")]
+    #[case::remove_unknown_character(" $", "")]
+    #[case::fix_escape("\\d", "\\n")]
+    #[case::fix_dedent("  return", "    return")]
+    #[case::fix_string("'y", "'y'")]
+    #[case::shift_errors("x = ", "xs = [] + ")]
+    fn test_relex_sink_matches_full_lex(#[case] before: &str, #[case] after: &str) {
+        let start = SOURCE_WITH_ERRORS
+            .find(before)
+            .expect("edited text not found");
+        let edit = TextRange::at(
+            TextSize::from(start as u32),
+            TextSize::from(before.len() as u32),
+        );
+        let edited = SOURCE_WITH_ERRORS.replacen(before, after, 1);
+
+        let relexed = StarlarkLexer::relex_sink(&lex_sink(SOURCE_WITH_ERRORS), edit, after);
+
+        assert_eq!(relexed.tokens().tokens(), lex(&edited).tokens());
+        assert_eq!(relexed.lexical_errors(), lex_sink(&edited).lexical_errors());
+    }
+
+    #[test]
+    fn test_relex_sink_every_single_character_deletion() {
+        for (start, c) in SOURCE_WITH_ERRORS.char_indices() {
+            let edit = TextRange::at(
+                TextSize::from(start as u32),
+                TextSize::from(c.len_utf8() as u32),
+            );
+            let edited = format!(
+                "{}{}",
+                &SOURCE_WITH_ERRORS[..start],
+                &SOURCE_WITH_ERRORS[start + c.len_utf8()..]
+            );
+
+            assert_eq!(
+                StarlarkLexer::relex_sink(&lex_sink(SOURCE_WITH_ERRORS), edit, ""),
+                lex_sink(&edited),
+                "after deleting {:?} at {}",
+                c,
+                start
+            );
+        }
+    }
+}
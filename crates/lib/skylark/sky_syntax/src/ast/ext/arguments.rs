@@ -0,0 +1,43 @@
+//! **Extensions** to the generated AST for the **arguments** of a call, telling
+//! apart the positional arguments from the keyword ones.
+
+use crate::ast;
+
+impl ast::Argument {
+    /// Returns `true` if the argument is passed **by name**, i.e. `name =
+    /// value` or `**kwargs`.
+    pub fn is_keyword(&self) -> bool {
+        self.name().is_some() || self.starstar_token().is_some()
+    }
+}
+
+impl ast::Arguments {
+    /// The **positional** arguments (e.g. `x` and `*args` in `f(x, *args, y =
+    /// 1)`), in order.
+    pub fn positional(&self) -> impl Iterator<Item = ast::Argument> {
+        self.arguments().filter(|argument| !argument.is_keyword())
+    }
+
+    /// The **keyword** arguments (e.g. `y = 1` and `**kwargs` in `f(x, y = 1,
+    /// **kwargs)`), in order.
+    pub fn keyword(&self) -> impl Iterator<Item = ast::Argument> {
+        self.arguments().filter(ast::Argument::is_keyword)
+    }
+}
+
+impl ast::CallSuffix {
+    /// The **positional** arguments of the call, see
+    /// [`ast::Arguments::positional`].
+    pub fn positional(&self) -> impl Iterator<Item = ast::Argument> {
+        self.arguments()
+            .into_iter()
+            .flat_map(|arguments| arguments.positional())
+    }
+
+    /// The **keyword** arguments of the call, see [`ast::Arguments::keyword`].
+    pub fn keyword(&self) -> impl Iterator<Item = ast::Argument> {
+        self.arguments()
+            .into_iter()
+            .flat_map(|arguments| arguments.keyword())
+    }
+}
@@ -0,0 +1,563 @@
+//! **Extensions** to the generated AST for **editing** syntax trees in place,
+//! e.g. to implement refactorings à la [buildozer].
+//!
+//! All of these require a **mutable** tree (see [`AstNode::clone_for_update`])
+//! and keep it **lossless**: only the edited parts change, while the rest of
+//! the text (including comments) is preserved as is. Inserted items follow
+//! the **layout** of the list they're inserted into, i.e. they're placed on a
+//! line of their own (at the same indentation) if the items already are, and
+//! any trailing comma is kept.
+//!
+//! [buildozer]: https://github.com/bazelbuild/buildtools/tree/master/buildozer
+
+use rowan::Direction;
+
+use crate::{
+    ast::{self, make, AstNode, AstToken},
+    lang::{SyntaxElement, SyntaxNode, SyntaxToken},
+    ted::{self, Position},
+    SyntaxKind, T,
+};
+
+impl ast::CallSuffix {
+    /// The **keyword argument** with the given name, if any.
+    pub fn keyword_argument(&self, name: &str) -> Option<ast::Argument> {
+        self.arguments()?
+            .arguments()
+            .find(|argument| matches!(argument.name(), Some(it) if it.text() == name))
+    }
+
+    /// **Inserts** the given argument at the given index, or after all other
+    /// arguments if the index is out of bounds.
+    pub fn insert_argument(&self, index: usize, argument: ast::Argument) {
+        let Some(arguments) = self.arguments() else {
+            let arguments = make::call_expr(make::name_expr("f"), [argument])
+                .call_suffix()
+                .and_then(|it| it.arguments())
+                .unwrap();
+            ted::insert(Position::after(self.l_paren_token().unwrap()), arguments);
+            return;
+        };
+
+        let items = arguments
+            .arguments()
+            .map(|it| it.syntax().clone().into())
+            .collect::<Vec<_>>();
+        insert_item(&items, index, argument.syntax());
+    }
+
+    /// **Appends** the given argument after all other arguments.
+    pub fn push_argument(&self, argument: ast::Argument) {
+        self.insert_argument(usize::MAX, argument);
+    }
+
+    /// **Sets** the value of the keyword argument with the given name,
+    /// replacing that of an existing argument, or appending a new one.
+    pub fn set_keyword_argument(&self, name: &str, value: ast::Test) {
+        let argument = make::keyword_argument(name, value);
+        match self.keyword_argument(name) {
+            Some(existing) => replace_item(&existing.syntax().clone().into(), argument.syntax()),
+            None => self.push_argument(argument),
+        }
+    }
+
+    /// **Removes** the keyword argument with the given name, returning it
+    /// (detached from the tree), if any.
+    pub fn remove_keyword_argument(&self, name: &str) -> Option<ast::Argument> {
+        let argument = self.keyword_argument(name)?;
+        self.remove_argument(&argument);
+
+        Some(argument)
+    }
+
+    /// **Removes** the given argument, along with its separating comma. A
+    /// trailing comma is removed as well, if no arguments are left.
+    pub fn remove_argument(&self, argument: &ast::Argument) {
+        remove_item(&argument.syntax().clone().into());
+
+        if let Some(arguments) = self.arguments() {
+            if arguments.arguments().next().is_none() {
+                ted::remove(arguments);
+                if let Some(comma) = self.comma_token() {
+                    ted::remove(comma);
+                }
+            }
+        }
+    }
+}
+
+impl ast::ListExpr {
+    /// **Appends** the given element after all other elements.
+    pub fn push_element(&self, element: ast::Test) {
+        let Some(expression) = self.expression() else {
+            let expression = make::list_expr([element])
+                .syntax()
+                .descendants()
+                .find_map(ast::Expression::cast)
+                .unwrap();
+            ted::insert(Position::after(self.l_brack_token().unwrap()), expression);
+            return;
+        };
+
+        let items = expression
+            .tests()
+            .map(|it| list_item(&it).into())
+            .collect::<Vec<_>>();
+        let element = make::list_expr([element])
+            .syntax()
+            .descendants()
+            .find(|it| it.kind() == SyntaxKind::TEST)
+            .unwrap();
+        insert_item(&items, usize::MAX, &element);
+    }
+
+    /// **Replaces** the given element with another one, keeping its leading
+    /// trivia (e.g. the comment on the line above).
+    pub fn replace_element(&self, old: &ast::Test, new: ast::Test) {
+        replace_item(&old.syntax().clone().into(), new.syntax());
+    }
+
+    /// **Removes** the given element, along with its separating comma. The
+    /// list is left as `[]` if no elements are left.
+    pub fn remove_element(&self, element: &ast::Test) {
+        remove_item(&list_item(element).into());
+
+        if !matches!(self.expression(), Some(it) if it.tests().next().is_some()) {
+            let (Some(l_brack), Some(r_brack)) = (self.l_brack_token(), self.r_brack_token())
+            else {
+                return;
+            };
+            let first = l_brack.next_sibling_or_token();
+            let last = r_brack.prev_sibling_or_token();
+            if let (Some(first), Some(last)) = (first, last) {
+                if first.index() <= last.index() {
+                    ted::remove_all(first..=last);
+                }
+            }
+        }
+    }
+}
+
+/// The **comments** attached to an element of a list: those on the lines
+/// above it (e.g. `# keep sorted`), and the one trailing it on its line.
+#[derive(Debug, Default)]
+pub struct ElementComments {
+    leading: Vec<SyntaxToken>,
+    trailing: Option<SyntaxToken>,
+}
+
+impl ast::ListExpr {
+    /// **Removes** the given element as [`ast::ListExpr::remove_element`]
+    /// does, returning the comments attached to it (detached from the tree).
+    pub fn remove_element_with_comments(&self, element: &ast::Test) -> ElementComments {
+        let item = SyntaxElement::from(list_item(element));
+        let trailing = match adjacent_comma(&item, Direction::Next) {
+            Some(comma) => trailing_comment(&comma),
+            None => trailing_comment(&last_token(&item)),
+        };
+
+        let trivia = leading_trivia(&item);
+        let leading = match trivia.iter().position(|it| it.text().contains('\n')) {
+            Some(newline) => trivia[newline..]
+                .iter()
+                .filter(|it| it.kind() == T![comment])
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        for token in &leading {
+            ted::remove(token.clone());
+        }
+
+        self.remove_element(element);
+
+        ElementComments {
+            leading,
+            trailing: trailing.last().cloned(),
+        }
+    }
+
+    /// **Appends** the given element after all other elements, along with the
+    /// given comments. As comments end their line, the elements are put on
+    /// lines of their own if they aren't already.
+    pub fn push_element_with_comments(&self, element: ast::Test, comments: ElementComments) {
+        self.push_element(element);
+        if comments.leading.is_empty() && comments.trailing.is_none() {
+            return;
+        }
+        self.break_lines();
+
+        let Some(item) = self
+            .expression()
+            .and_then(|it| it.tests().last())
+            .map(|it| SyntaxElement::from(list_item(&it)))
+        else {
+            return;
+        };
+        let separator = separator_before(&item);
+        if let Some(first) = item.as_node().and_then(|it| it.first_token()) {
+            let leading = comments
+                .leading
+                .into_iter()
+                .flat_map(|it| [it.into(), make::tokens::whitespace(&separator).into()])
+                .collect();
+            ted::insert_all(Position::before(first), leading);
+        }
+        if let Some(comment) = comments.trailing {
+            let anchor =
+                adjacent_comma(&item, Direction::Next).map_or_else(|| item.clone(), Into::into);
+            ted::insert_all(
+                Position::after(anchor),
+                vec![make::tokens::whitespace("  ").into(), comment.into()],
+            );
+        }
+    }
+
+    /// Puts each element on a **line of its own**, indented one level deeper
+    /// than the line of the opening bracket, with a trailing comma.
+    fn break_lines(&self) {
+        let (Some(l_brack), Some(r_brack)) = (self.l_brack_token(), self.r_brack_token()) else {
+            return;
+        };
+        let items = self
+            .expression()
+            .map(|it| {
+                it.tests()
+                    .map(|it| SyntaxElement::from(list_item(&it)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if items.is_empty() || separator_before(&items[0]).starts_with('\n') {
+            return;
+        }
+
+        let indentation = line_indentation(&l_brack);
+        for item in &items {
+            let trivia = leading_trivia(item);
+            if trivia.iter().any(|it| it.kind() == T![comment]) {
+                continue;
+            }
+            for token in trivia {
+                ted::remove(token);
+            }
+            remove_whitespace_before(item);
+            let separator = make::tokens::whitespace(&format!("\n{indentation}    "));
+            ted::insert(Position::before(item.clone()), separator);
+        }
+
+        let last = items.last().unwrap();
+        if adjacent_comma(last, Direction::Next).is_none() {
+            ted::insert(Position::after(last.clone()), make::tokens::comma());
+        }
+
+        remove_whitespace_before(&r_brack.clone().into());
+        let separator = make::tokens::whitespace(&format!("\n{indentation}"));
+        ted::insert(Position::before(r_brack), separator);
+    }
+}
+
+impl ast::LoadStmt {
+    /// **Appends** the given symbol after all other symbols, unless the very
+    /// same symbol is already loaded (under the same alias, if any).
+    pub fn add_symbol(&self, symbol: ast::LoadSymbol) {
+        let key = |symbol: &ast::LoadSymbol| {
+            (
+                symbol.alias().map(|it| it.text().to_string()),
+                symbol.symbol().and_then(|it| it.value().ok()),
+            )
+        };
+        if self.load_symbols().any(|it| key(&it) == key(&symbol)) {
+            return;
+        }
+
+        // The module is separated from the symbols just like the symbols are
+        // from one another, so it's treated as the first item of the list.
+        let items = self
+            .module()
+            .map(|it| it.syntax().clone().into())
+            .into_iter()
+            .chain(self.load_symbols().map(|it| it.syntax().clone().into()))
+            .collect::<Vec<_>>();
+        insert_item(&items, usize::MAX, symbol.syntax());
+    }
+}
+
+impl ast::Identifier {
+    /// **Renames** the identifier, replacing its token in the tree (and as
+    /// such detaching `self` from it).
+    pub fn rename(&self, name: &str) {
+        ted::replace(self.syntax().clone(), make::tokens::identifier(name));
+    }
+}
+
+/// **Inserts** an item into a (non-empty) comma-separated list of items at
+/// the given index, following the layout of the existing items.
+fn insert_item(items: &[SyntaxElement], index: usize, item: &SyntaxNode) {
+    let item = SyntaxElement::from(without_leading_trivia(item));
+    if index == 0 {
+        // The first item's leading trivia (e.g. the newline after the opening
+        // delimiter) is moved in front of the new item.
+        let first = &items[0];
+        let separator = make::tokens::whitespace(&separator_before(first));
+        let trivia = leading_trivia(first);
+        for token in &trivia {
+            ted::remove(token.clone());
+        }
+
+        let mut elements = trivia.into_iter().map(Into::into).collect::<Vec<_>>();
+        elements.extend([item, make::tokens::comma().into(), separator.into()]);
+        ted::insert_all(Position::before(first.clone()), elements);
+    } else {
+        let previous = &items[index.min(items.len()) - 1];
+        let separator = make::tokens::whitespace(&separator_before(items.last().unwrap()));
+
+        // The new item goes after the previous item's comma, and after the
+        // comment trailing it (if any), which stays with the previous item.
+        let comma = adjacent_comma(previous, Direction::Next);
+        match comma.clone().filter(|it| it.parent() == previous.parent()) {
+            Some(comma) => {
+                let comment = trailing_comment(&comma);
+                let anchor = comment.last().cloned().unwrap_or_else(|| comma.clone());
+                let comment = comment.into_iter().map(Into::into).collect();
+                ted::insert_all(Position::after(comma), comment);
+                ted::insert_all(
+                    Position::after(anchor),
+                    vec![separator.into(), item, make::tokens::comma().into()],
+                );
+            }
+            None => {
+                // The last item's trailing comma (if any) belongs to the list's
+                // parent, and as such becomes that of the new item.
+                let comment = match comma {
+                    Some(comma) => trailing_comment(&comma),
+                    None => trailing_comment(&last_token(previous)),
+                };
+                let mut elements = vec![make::tokens::comma().into()];
+                elements.extend(comment.into_iter().map(Into::into));
+                elements.extend([separator.into(), item]);
+                ted::insert_all(Position::after(previous.clone()), elements);
+            }
+        }
+    }
+}
+
+/// The **last token** of the given element.
+fn last_token(element: &SyntaxElement) -> SyntaxToken {
+    match element {
+        SyntaxElement::Node(node) => node.last_token().unwrap(),
+        SyntaxElement::Token(token) => token.clone(),
+    }
+}
+
+/// **Removes** the whitespace in front of the given element, among its
+/// siblings.
+fn remove_whitespace_before(element: &SyntaxElement) {
+    let mut previous = element.prev_sibling_or_token();
+    while let Some(it) = previous.filter(|it| it.kind() == T![whitespace]) {
+        previous = it.prev_sibling_or_token();
+        ted::remove(it);
+    }
+}
+
+/// The **indentation** of the line the given token is on.
+fn line_indentation(token: &SyntaxToken) -> String {
+    let mut line = String::new();
+    let mut previous = token.prev_token();
+    while let Some(it) = previous {
+        match it.text().rfind('\n') {
+            Some(newline) => {
+                line.insert_str(0, &it.text()[newline + 1..]);
+                break;
+            }
+            None => line.insert_str(0, it.text()),
+        }
+        previous = it.prev_token();
+    }
+
+    line.chars()
+        .take_while(|it| *it == ' ' || *it == '\t')
+        .collect()
+}
+
+/// **Detaches** the comment trailing the given token on its line (along with
+/// the whitespace in front of it), if any. Such a comment may well be part of
+/// the leading trivia of the next item.
+fn trailing_comment(token: &SyntaxToken) -> Vec<SyntaxToken> {
+    let mut trivia = Vec::new();
+    let mut next = token.next_token();
+    while let Some(it) =
+        next.filter(|it| is_trivia(&it.clone().into()) && !it.text().contains('\n'))
+    {
+        next = it.next_token();
+        trivia.push(it);
+    }
+
+    let Some(comment) = trivia.iter().rposition(|it| it.kind() == T![comment]) else {
+        return Vec::new();
+    };
+    trivia.truncate(comment + 1);
+    for token in &trivia {
+        ted::remove(token.clone());
+    }
+
+    trivia
+}
+
+/// **Replaces** an item of a comma-separated list, keeping its leading trivia.
+fn replace_item(old: &SyntaxElement, new: &SyntaxNode) {
+    let new = without_leading_trivia(new);
+    if let Some(first) = new.first_token() {
+        let trivia = leading_trivia(old).into_iter().map(Into::into).collect();
+        ted::insert_all(Position::before(first), trivia);
+    }
+
+    ted::replace(old.clone(), new);
+}
+
+/// Returns a **mutable** copy of the given node without its leading trivia,
+/// e.g. the whitespace it was indented by in its original tree.
+fn without_leading_trivia(node: &SyntaxNode) -> SyntaxNode {
+    let node = node.clone_subtree().clone_for_update();
+    for token in leading_trivia(&node.clone().into()) {
+        ted::remove(token);
+    }
+
+    node
+}
+
+/// The **item** of a list for the given element, i.e. its `TEST` wrapper
+/// node (which [`ast::Test`] looks through), if any.
+fn list_item(element: &ast::Test) -> SyntaxNode {
+    let node = element.syntax();
+    match node.parent() {
+        Some(parent) if parent.kind() == SyntaxKind::TEST => parent,
+        _ => node.clone(),
+    }
+}
+
+/// **Removes** an item from a comma-separated list, along with its separating
+/// comma. An item on a line of its own is removed along with its line (and
+/// the comma following it, if any). Other items are separated from the
+/// previous item, if any, and otherwise from the next one, in which case
+/// their leading trivia is kept. Either way, a comment trailing the line
+/// before the item (e.g. that of the previous item) is kept.
+fn remove_item(item: &SyntaxElement) {
+    let previous = adjacent_comma(item, Direction::Prev);
+    let next = adjacent_comma(item, Direction::Next);
+
+    let trivia = leading_trivia(item);
+    let newline = trivia.iter().position(|it| it.text().contains('\n'));
+    let comment = match newline {
+        Some(newline) if trivia[..newline].iter().any(|it| it.kind() == T![comment]) => {
+            trivia[..newline].to_vec()
+        }
+        _ => Vec::new(),
+    };
+    let keep = |tokens: Vec<SyntaxToken>, position: Position| {
+        ted::insert_all(position, tokens.into_iter().map(Into::into).collect());
+    };
+
+    match (previous, next) {
+        (_, Some(comma)) if newline.is_some() => {
+            keep(comment, Position::before(item.clone()));
+            ted::remove(item.clone());
+            ted::remove(comma);
+        }
+        (Some(comma), _) => {
+            keep(comment, Position::before(comma.clone()));
+            ted::remove(comma);
+            ted::remove(item.clone());
+        }
+        (None, Some(comma)) => {
+            // The next item (if the comma isn't a trailing one) takes over the
+            // item's leading trivia, e.g. the space after the opening delimiter.
+            let next_item = comma
+                .siblings_with_tokens(Direction::Next)
+                .skip(1)
+                .find(|it| !is_trivia(it))
+                .filter(|_| comma.parent() == item.parent());
+
+            ted::remove(item.clone());
+            ted::remove(comma);
+            if let Some(next_item) = next_item {
+                let next_trivia = leading_trivia(&next_item);
+                if next_trivia.iter().all(|it| it.kind() == T![whitespace]) {
+                    for token in next_trivia {
+                        ted::remove(token);
+                    }
+                    let first = next_item
+                        .as_node()
+                        .map_or_else(|| next_item.clone(), |it| it.first_token().unwrap().into());
+                    keep(trivia, Position::before(first));
+                }
+            }
+        }
+        (None, None) => ted::remove(item.clone()),
+    }
+}
+
+/// The comma **separating** the given item from its previous (or next) item,
+/// if any. The comma following the last item is a trailing comma, which may
+/// belong to the list's parent (e.g. that of a call's arguments).
+fn adjacent_comma(item: &SyntaxElement, direction: Direction) -> Option<SyntaxToken> {
+    let (first, last) = match item {
+        SyntaxElement::Node(node) => (node.first_token()?, node.last_token()?),
+        SyntaxElement::Token(token) => (token.clone(), token.clone()),
+    };
+    let step = |it: &SyntaxToken| match direction {
+        Direction::Prev => it.prev_token(),
+        Direction::Next => it.next_token(),
+    };
+
+    let mut token = match direction {
+        Direction::Prev => step(&first),
+        Direction::Next => step(&last),
+    };
+    while let Some(it) = token.as_ref().filter(|it| is_trivia(&(*it).clone().into())) {
+        token = step(it);
+    }
+
+    token.filter(|it| it.kind() == T![,])
+}
+
+/// The **separator** (i.e. whitespace) to put in front of an item inserted
+/// next to the given one: a newline and the item's indentation if it's on a
+/// line of its own, and a single space otherwise.
+fn separator_before(item: &SyntaxElement) -> String {
+    let first = match item {
+        SyntaxElement::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .find(|it| !is_trivia(&it.clone().into())),
+        SyntaxElement::Token(token) => Some(token.clone()),
+    };
+
+    let mut trivia = String::new();
+    let mut token = first.and_then(|it| it.prev_token());
+    while let Some(it) = token.filter(|it| is_trivia(&it.clone().into())) {
+        trivia.insert_str(0, it.text());
+        token = it.prev_token();
+    }
+
+    match trivia.rfind('\n') {
+        Some(newline) => format!("\n{}", &trivia[newline + 1..]),
+        None => " ".to_string(),
+    }
+}
+
+/// The **trivia** (i.e. whitespace and comments) at the start of the given
+/// element.
+fn leading_trivia(item: &SyntaxElement) -> Vec<SyntaxToken> {
+    match item {
+        SyntaxElement::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .take_while(|it| is_trivia(&it.clone().into()))
+            .collect(),
+        SyntaxElement::Token(_) => Vec::new(),
+    }
+}
+
+fn is_trivia(element: &SyntaxElement) -> bool {
+    matches!(element.kind(), T![whitespace] | T![comment])
+}
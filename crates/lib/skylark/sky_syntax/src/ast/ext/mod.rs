@@ -1,8 +1,12 @@
 //! **Extensions** to the AST. These are additional functionality that is not
 //! generated from the grammar, but is instead manually implemented.
 
+pub(crate) mod arguments;
+pub(crate) mod edit;
 pub(crate) mod kinds;
 pub(crate) mod nodes;
+pub(crate) mod operators;
+pub(crate) mod tokens;
 
 // /// `Parse` is the result of the parsing: a syntax tree and a collection of errors.
 // ///
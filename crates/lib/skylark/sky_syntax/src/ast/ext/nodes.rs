@@ -4,7 +4,7 @@ use crate::{
     ast::{AstNode, File},
     parsing,
     syntax_error::SyntaxError,
-    Parse, SyntaxKind, SyntaxNode,
+    validation, Parse, SyntaxKind, SyntaxNode, TextEdit,
 };
 
 impl Parse<SyntaxNode> {
@@ -22,6 +22,24 @@ impl Parse<SyntaxNode> {
 }
 
 impl Parse<File> {
+    /// Reparses the file after the given `edit` of its text, reusing as much of
+    /// the existing syntax tree as possible. Only if the edit can't be confined
+    /// to a single token, or to a block-like node (e.g. a suite or a list), is
+    /// the whole file parsed anew.
+    ///
+    /// The result is always the same as parsing the edited text from scratch.
+    pub fn reparse(&self, edit: TextEdit) -> Parse<File> {
+        match parsing::incremental_reparse(&self.syntax_node(), &edit, &self.errors) {
+            Some((green, errors)) => Parse::new(green, errors),
+            None => {
+                let mut text = self.syntax_node().text().to_string();
+                edit.apply(&mut text);
+
+                File::parse(&text)
+            }
+        }
+    }
+
     pub fn debug_dump(&self) -> String {
         // let mut buf = format!("{:#?}", self.tree().syntax());
         // for err in self.errors.iter() {
@@ -46,13 +64,14 @@ impl Parse<File> {
 
 impl File {
     pub fn parse(text: &str) -> Parse<File> {
-        let (green, errors) = parsing::parse_text(text);
+        let (green, mut errors) = parsing::parse_text(text);
         tracing::trace!("Parsed GreenNode {:#?}", green);
         tracing::debug!("Completed parsing. Found {} errors", errors.len());
 
         let root = SyntaxNode::new_root(green.clone());
 
-        // // errors.extend(validation::validate(&root));
+        errors.extend(validation::validate(&root));
+        errors.sort_by_key(|error| error.range().start());
 
         assert!(
             root.kind() == SyntaxKind::FILE,
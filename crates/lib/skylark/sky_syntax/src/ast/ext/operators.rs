@@ -0,0 +1,164 @@
+//! **Extensions** to the generated AST for **unary** and **binary
+//! expressions**, exposing the **kind** of their operator.
+
+use crate::{
+    ast::{self, AstNode},
+    SyntaxToken, T,
+};
+
+/// The **operator** of a [`ast::BinaryExpr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    /// `or`
+    Or,
+    /// `and`
+    And,
+    /// `==`
+    Eq,
+    /// `!=`
+    NotEq,
+    /// `<`
+    Less,
+    /// `>`
+    Greater,
+    /// `<=`
+    LessEq,
+    /// `>=`
+    GreaterEq,
+    /// `in`
+    In,
+    /// `not in`
+    NotIn,
+    /// `|`
+    BitOr,
+    /// `^`
+    BitXor,
+    /// `&`
+    BitAnd,
+    /// `<<`
+    Shl,
+    /// `>>`
+    Shr,
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `//`
+    FloorDiv,
+    /// `%`
+    Rem,
+}
+
+impl BinaryOp {
+    /// Returns the **precedence** of the operator, from `1` for `or` to `10`
+    /// for the multiplicative operators. Operators of a **higher** precedence
+    /// bind **tighter**.
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Eq
+            | BinaryOp::NotEq
+            | BinaryOp::Less
+            | BinaryOp::Greater
+            | BinaryOp::LessEq
+            | BinaryOp::GreaterEq
+            | BinaryOp::In
+            | BinaryOp::NotIn => 4,
+            BinaryOp::BitOr => 5,
+            BinaryOp::BitXor => 6,
+            BinaryOp::BitAnd => 7,
+            BinaryOp::Shl | BinaryOp::Shr => 8,
+            BinaryOp::Add | BinaryOp::Sub => 9,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::FloorDiv | BinaryOp::Rem => 10,
+        }
+    }
+
+    /// Returns `true` if the operator is a **comparison** (including `in` and
+    /// `not in`), which can't be chained.
+    pub fn is_comparison(self) -> bool {
+        self.precedence() == 4
+    }
+}
+
+/// The **operator** of a [`ast::UnaryExpr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `~`
+    Invert,
+    /// `not`
+    Not,
+}
+
+impl ast::BinaryExpr {
+    /// The **kind** of the operator, if any.
+    pub fn op_kind(&self) -> Option<BinaryOp> {
+        let binop = self.binop()?;
+        let mut tokens = binop
+            .syntax()
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| !matches!(token.kind(), T![whitespace] | T![comment]));
+
+        let op = match tokens.next()?.kind() {
+            T![or] => BinaryOp::Or,
+            T![and] => BinaryOp::And,
+            T![==] => BinaryOp::Eq,
+            T![!=] => BinaryOp::NotEq,
+            T![<] => BinaryOp::Less,
+            T![>] => BinaryOp::Greater,
+            T![<=] => BinaryOp::LessEq,
+            T![>=] => BinaryOp::GreaterEq,
+            T![in] => BinaryOp::In,
+            T![not] => match tokens.next()?.kind() {
+                T![in] => BinaryOp::NotIn,
+                _ => return None,
+            },
+            T![|] => BinaryOp::BitOr,
+            T![^] => BinaryOp::BitXor,
+            T![&] => BinaryOp::BitAnd,
+            T![<<] => BinaryOp::Shl,
+            T![>>] => BinaryOp::Shr,
+            T![+] => BinaryOp::Add,
+            T![-] => BinaryOp::Sub,
+            T![*] => BinaryOp::Mul,
+            T![/] => BinaryOp::Div,
+            T![dslash] => BinaryOp::FloorDiv,
+            T![%] => BinaryOp::Rem,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+}
+
+impl ast::UnaryExpr {
+    /// The **operator** token.
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| matches!(token.kind(), T![+] | T![-] | T![~] | T![not]))
+    }
+
+    /// The **kind** of the operator, if any.
+    pub fn op_kind(&self) -> Option<UnaryOp> {
+        let op = match self.op_token()?.kind() {
+            T![+] => UnaryOp::Plus,
+            T![-] => UnaryOp::Minus,
+            T![~] => UnaryOp::Invert,
+            T![not] => UnaryOp::Not,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+}
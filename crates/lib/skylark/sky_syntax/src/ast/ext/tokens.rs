@@ -0,0 +1,242 @@
+//! **Extensions** to the generated AST tokens, such as **decoding** the
+//! values of string, bytes and numeric literals.
+
+use std::ops::Range;
+
+use num_bigint::BigInt;
+use rowan::{TextRange, TextSize};
+
+use crate::{
+    ast::{self, AstToken},
+    syntax_error::SyntaxError,
+};
+use sky_lexer::unescape::{unescape, EscapeError, LiteralParts, Mode, Unescaped};
+
+impl ast::String {
+    /// Returns `true` if the string literal is **raw** (e.g. `r"\d+"`), in
+    /// which case escape sequences are left uninterpreted.
+    pub fn is_raw(&self) -> bool {
+        LiteralParts::new(self.text()).is_raw
+    }
+
+    /// Returns `true` if the string literal is **triple-quoted** (e.g.
+    /// `"""docstring"""`).
+    pub fn is_triple_quoted(&self) -> bool {
+        LiteralParts::new(self.text()).quote_len == 3
+    }
+
+    /// Returns the **range** of the contents of the string literal (i.e. the
+    /// text between the quotes), relative to the start of the file.
+    pub fn text_range_between_quotes(&self) -> TextRange {
+        LiteralParts::new(self.text()).absolute_range(self.syntax().text_range().start())
+    }
+
+    /// Returns the **decoded value** of the string literal, interpreting any
+    /// escape sequences (e.g. `\n`, `\x41`, `\u00e9`, `\U0001F600` or `\101`).
+    ///
+    /// Invalid escape sequences are reported as [`SyntaxError`]s spanning the
+    /// offending escape sequence.
+    ///
+    /// ## Examples
+    ///
+    /// ```starlark
+    /// "caf\u00e9"  # => café
+    /// r"\d+"        # => \d+
+    /// ```
+    pub fn value(&self) -> Result<std::string::String, Vec<SyntaxError>> {
+        let parts = LiteralParts::new(self.text());
+        let contents = &self.text()[parts.contents.clone()];
+        if parts.is_raw {
+            return Ok(contents.to_string());
+        }
+
+        let offset = parts
+            .absolute_range(self.syntax().text_range().start())
+            .start();
+        let mut value = std::string::String::with_capacity(contents.len());
+        let mut errors = Vec::new();
+
+        unescape(contents, Mode::Str, &mut |range, result| match result {
+            Ok(Unescaped::Char(c)) => value.push(c),
+            Ok(Unescaped::Byte(b)) => value.push(char::from(b)),
+            Err(error) => errors.push(escape_error(error, range, offset)),
+        });
+
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl ast::Bytes {
+    /// Returns `true` if the bytes literal is **raw** (e.g. `rb"\d+"`), in
+    /// which case escape sequences are left uninterpreted.
+    pub fn is_raw(&self) -> bool {
+        LiteralParts::new(self.text()).is_raw
+    }
+
+    /// Returns `true` if the bytes literal is **triple-quoted** (e.g.
+    /// `b"""bytes"""`).
+    pub fn is_triple_quoted(&self) -> bool {
+        LiteralParts::new(self.text()).quote_len == 3
+    }
+
+    /// Returns the **range** of the contents of the bytes literal (i.e. the
+    /// text between the quotes), relative to the start of the file.
+    pub fn text_range_between_quotes(&self) -> TextRange {
+        LiteralParts::new(self.text()).absolute_range(self.syntax().text_range().start())
+    }
+
+    /// Returns the **decoded value** of the bytes literal, interpreting any
+    /// escape sequences. Unlike string literals, `\x` and octal escapes may
+    /// denote arbitrary bytes (e.g. `b"\xff"`), whereas non-ASCII characters
+    /// (including `\u` and `\U` escapes) are encoded as **UTF-8**.
+    ///
+    /// Invalid escape sequences are reported as [`SyntaxError`]s spanning the
+    /// offending escape sequence.
+    pub fn value(&self) -> Result<Vec<u8>, Vec<SyntaxError>> {
+        let parts = LiteralParts::new(self.text());
+        let contents = &self.text()[parts.contents.clone()];
+        if parts.is_raw {
+            return Ok(contents.as_bytes().to_vec());
+        }
+
+        let offset = parts
+            .absolute_range(self.syntax().text_range().start())
+            .start();
+        let mut value = Vec::with_capacity(contents.len());
+        let mut errors = Vec::new();
+
+        unescape(contents, Mode::Bytes, &mut |range, result| match result {
+            Ok(Unescaped::Char(c)) => {
+                value.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())
+            }
+            Ok(Unescaped::Byte(b)) => value.push(b),
+            Err(error) => errors.push(escape_error(error, range, offset)),
+        });
+
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl ast::Int {
+    /// Returns the **radix** of the integer literal, i.e. `16` for `0x2a`,
+    /// `8` for `0o52`, `2` for `0b101010` and `10` for `42`.
+    pub fn radix(&self) -> u32 {
+        self.split_into_parts().0
+    }
+
+    /// Returns the **value** of the integer literal as an [`i64`].
+    ///
+    /// Starlark integers are _arbitrary precision_, so literals that don't fit
+    /// into an `i64` are reported as an **overflow**. Use
+    /// [`big_value`](Self::big_value) to retrieve their exact value instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```starlark
+    /// 42                  # => 42
+    /// 0x7fffffffffffffff  # => i64::MAX
+    /// ```
+    pub fn value(&self) -> Result<i64, SyntaxError> {
+        let value = self.big_value()?;
+        i64::try_from(&value).map_err(|_| {
+            SyntaxError::new(
+                format!(
+                    "Integer literal `{}` is too large to fit in a 64-bit integer",
+                    self.text()
+                ),
+                self.syntax().text_range(),
+            )
+        })
+    }
+
+    /// Returns the exact, **arbitrary precision** value of the integer literal.
+    ///
+    /// Malformed literals (e.g. `0x`, `0o8`, `0b102` or `012`) are reported as
+    /// [`SyntaxError`]s spanning the literal.
+    pub fn big_value(&self) -> Result<BigInt, SyntaxError> {
+        let (radix, digits) = self.split_into_parts();
+        let malformed = |reason: &str| {
+            SyntaxError::new(
+                format!("Malformed integer literal `{}`: {reason}", self.text()),
+                self.syntax().text_range(),
+            )
+        };
+
+        if digits.is_empty() {
+            return Err(malformed("expected at least one digit"));
+        }
+        if radix == 10 && digits.len() > 1 && digits.starts_with('0') {
+            return Err(malformed(
+                "leading zeros are not permitted, use the `0o` prefix for octal literals",
+            ));
+        }
+        if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+            return Err(malformed(&format!(
+                "invalid digit `{digit}` for base {radix}"
+            )));
+        }
+
+        BigInt::parse_bytes(digits.as_bytes(), radix)
+            .ok_or_else(|| malformed("expected an integer"))
+    }
+
+    fn split_into_parts(&self) -> (u32, &str) {
+        let text = self.text();
+        let radix = match text.get(..2) {
+            Some("0x" | "0X") => 16,
+            Some("0o" | "0O") => 8,
+            Some("0b" | "0B") => 2,
+            _ => return (10, text),
+        };
+
+        (radix, &text[2..])
+    }
+}
+
+impl ast::Float {
+    /// Returns the **value** of the floating-point literal.
+    ///
+    /// Literals too large to be represented by an [`f64`] (e.g. `1e999`) are
+    /// reported as [`SyntaxError`]s spanning the literal.
+    ///
+    /// ## Examples
+    ///
+    /// ```starlark
+    /// 1.5    # => 1.5
+    /// 1.     # => 1.0
+    /// .5e-3  # => 0.0005
+    /// 1e10   # => 10000000000.0
+    /// ```
+    pub fn value(&self) -> Result<f64, SyntaxError> {
+        let malformed = |reason: &str| {
+            SyntaxError::new(
+                format!("Malformed float literal `{}`: {reason}", self.text()),
+                self.syntax().text_range(),
+            )
+        };
+
+        match self.text().parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            Ok(_) => Err(malformed("value out of range")),
+            Err(error) => Err(malformed(&error.to_string())),
+        }
+    }
+}
+
+fn escape_error(error: EscapeError, range: Range<usize>, offset: TextSize) -> SyntaxError {
+    SyntaxError::new(
+        error.to_string(),
+        TextRange::new(
+            offset + TextSize::from(range.start as u32),
+            offset + TextSize::from(range.end as u32),
+        ),
+    )
+}
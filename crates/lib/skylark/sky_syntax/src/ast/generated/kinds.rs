@@ -90,12 +90,14 @@ pub enum SyntaxKind {
     MINUSEQ,
     STAREQ,
     SLASHEQ,
+    DSLASHEQ,
     PERCENTEQ,
     AMPEQ,
     PIPEEQ,
     CARETEQ,
     LSHIFTEQ,
     RSHIFTEQ,
+    THIN_ARROW,
     #[doc = "Nodes (e.g. FILE, MODULE, FUNCTION, etc.)"]
     FILE,
     STATEMENT,
@@ -116,6 +118,7 @@ pub enum SyntaxKind {
     ASSIGN_STMT,
     EXPR_STMT,
     LOAD_STMT,
+    LOAD_SYMBOL,
     TEST,
     BIN_OP,
     IF_EXPR,
@@ -139,6 +142,8 @@ pub enum SyntaxKind {
     LAMBDA_EXPR,
     EXPRESSION,
     LOOP_VARIABLES,
+    TYPE_ANNOTATION,
+    ERROR,
     #[doc(hidden)]
     __LAST,
 }
@@ -203,12 +208,14 @@ impl SyntaxKind {
                 | MINUSEQ
                 | STAREQ
                 | SLASHEQ
+                | DSLASHEQ
                 | PERCENTEQ
                 | AMPEQ
                 | PIPEEQ
                 | CARETEQ
                 | LSHIFTEQ
                 | RSHIFTEQ
+                | THIN_ARROW
         )
     }
     pub fn is_literal(self) -> bool {
@@ -308,12 +315,14 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::MINUSEQ => MINUSEQ,
             TokenKind::STAREQ => STAREQ,
             TokenKind::SLASHEQ => SLASHEQ,
+            TokenKind::DSLASHEQ => DSLASHEQ,
             TokenKind::PERCENTEQ => PERCENTEQ,
             TokenKind::AMPEQ => AMPEQ,
             TokenKind::PIPEEQ => PIPEEQ,
             TokenKind::CARETEQ => CARETEQ,
             TokenKind::LSHIFTEQ => LSHIFTEQ,
             TokenKind::RSHIFTEQ => RSHIFTEQ,
+            TokenKind::THIN_ARROW => THIN_ARROW,
             TokenKind::AND_KW => AND_KW,
             TokenKind::ELSE_KW => ELSE_KW,
             TokenKind::LOAD_KW => LOAD_KW,
@@ -337,16 +346,13 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::NEWLINE => NEWLINE,
             TokenKind::INDENT => INDENT,
             TokenKind::OUTDENT => OUTDENT,
-            TokenKind::NumericLiteral => INT,
             TokenKind::COMMENT => COMMENT,
             TokenKind::WHITESPACE => WHITESPACE,
             TokenKind::UNKNOWN => UNKNOWN,
             TokenKind::EOF => EOF,
-            TokenKind::DSLASHEQ => todo!(),
-            // TokenKind::DSLASHEQ => DSLASHEQ,
         }
     }
 }
 #[macro_export]
-macro_rules ! T { [+] => { $ crate :: SyntaxKind :: PLUS } ; [-] => { $ crate :: SyntaxKind :: MINUS } ; [*] => { $ crate :: SyntaxKind :: STAR } ; [/] => { $ crate :: SyntaxKind :: SLASH } ; [dslash] => { $ crate :: SyntaxKind :: DSLASH } ; [%] => { $ crate :: SyntaxKind :: PERCENT } ; [**] => { $ crate :: SyntaxKind :: DSTAR } ; [~] => { $ crate :: SyntaxKind :: TILDE } ; [&] => { $ crate :: SyntaxKind :: AMP } ; [|] => { $ crate :: SyntaxKind :: PIPE } ; [^] => { $ crate :: SyntaxKind :: CARET } ; [<<] => { $ crate :: SyntaxKind :: LSHIFT } ; [>>] => { $ crate :: SyntaxKind :: RSHIFT } ; [.] => { $ crate :: SyntaxKind :: DOT } ; [,] => { $ crate :: SyntaxKind :: COMMA } ; [=] => { $ crate :: SyntaxKind :: EQ } ; [;] => { $ crate :: SyntaxKind :: SEMICOLON } ; [:] => { $ crate :: SyntaxKind :: COLON } ; ['('] => { $ crate :: SyntaxKind :: LPAREN } ; [')'] => { $ crate :: SyntaxKind :: RPAREN } ; ['['] => { $ crate :: SyntaxKind :: LBRACKET } ; [']'] => { $ crate :: SyntaxKind :: RBRACKET } ; ['{'] => { $ crate :: SyntaxKind :: LBRACE } ; ['}'] => { $ crate :: SyntaxKind :: RBRACE } ; [<] => { $ crate :: SyntaxKind :: LT } ; [>] => { $ crate :: SyntaxKind :: GT } ; [>=] => { $ crate :: SyntaxKind :: GE } ; [<=] => { $ crate :: SyntaxKind :: LE } ; [==] => { $ crate :: SyntaxKind :: EQEQ } ; [!=] => { $ crate :: SyntaxKind :: NE } ; [+=] => { $ crate :: SyntaxKind :: PLUSEQ } ; [-=] => { $ crate :: SyntaxKind :: MINUSEQ } ; [*=] => { $ crate :: SyntaxKind :: STAREQ } ; [/=] => { $ crate :: SyntaxKind :: SLASHEQ } ; [%=] => { $ crate :: SyntaxKind :: PERCENTEQ } ; [&=] => { $ crate :: SyntaxKind :: AMPEQ } ; [|=] => { $ crate :: SyntaxKind :: PIPEEQ } ; [^=] => { $ crate :: SyntaxKind :: CARETEQ } ; [<<=] => { $ crate :: SyntaxKind :: LSHIFTEQ } ; [>>=] => { $ crate :: SyntaxKind :: RSHIFTEQ } ; [and] => { $ crate :: SyntaxKind :: AND_KW } ; [else] => { $ crate :: SyntaxKind :: ELSE_KW } ; [load] => { $ crate :: SyntaxKind :: LOAD_KW } ; [break] => { $ crate :: SyntaxKind :: BREAK_KW } ; [for] => { $ crate :: SyntaxKind :: FOR_KW } ; [not] => { $ crate :: SyntaxKind :: NOT_KW } ; [continue] => { $ crate :: SyntaxKind :: CONTINUE_KW } ; [if] => { $ crate :: SyntaxKind :: IF_KW } ; [or] => { $ crate :: SyntaxKind :: OR_KW } ; [def] => { $ crate :: SyntaxKind :: DEF_KW } ; [in] => { $ crate :: SyntaxKind :: IN_KW } ; [pass] => { $ crate :: SyntaxKind :: PASS_KW } ; [elif] => { $ crate :: SyntaxKind :: ELIF_KW } ; [lambda] => { $ crate :: SyntaxKind :: LAMBDA_KW } ; [return] => { $ crate :: SyntaxKind :: RETURN_KW } ; [identifier] => { $ crate :: SyntaxKind :: IDENTIFIER } ; [eof] => { $ crate :: SyntaxKind :: EOF } ; [newline] => { $ crate :: SyntaxKind :: NEWLINE } ; [indent] => { $ crate :: SyntaxKind :: INDENT } ; [outdent] => { $ crate :: SyntaxKind :: OUTDENT } ; [dslash] => { $ crate :: SyntaxKind :: DSLASH } ; [string] => { $ crate :: SyntaxKind :: STRING } ; [bytes] => { $ crate :: SyntaxKind :: BYTES } ; [int] => { $ crate :: SyntaxKind :: INT } ; [float] => { $ crate :: SyntaxKind :: FLOAT } ; [comment] => { $ crate :: SyntaxKind :: COMMENT } ; [whitespace] => { $ crate :: SyntaxKind :: WHITESPACE } ; [error] => { $ crate :: SyntaxKind :: ERROR } ; }
+macro_rules ! T { [+] => { $ crate :: SyntaxKind :: PLUS } ; [-] => { $ crate :: SyntaxKind :: MINUS } ; [*] => { $ crate :: SyntaxKind :: STAR } ; [/] => { $ crate :: SyntaxKind :: SLASH } ; [dslash] => { $ crate :: SyntaxKind :: DSLASH } ; [%] => { $ crate :: SyntaxKind :: PERCENT } ; [**] => { $ crate :: SyntaxKind :: DSTAR } ; [~] => { $ crate :: SyntaxKind :: TILDE } ; [&] => { $ crate :: SyntaxKind :: AMP } ; [|] => { $ crate :: SyntaxKind :: PIPE } ; [^] => { $ crate :: SyntaxKind :: CARET } ; [<<] => { $ crate :: SyntaxKind :: LSHIFT } ; [>>] => { $ crate :: SyntaxKind :: RSHIFT } ; [.] => { $ crate :: SyntaxKind :: DOT } ; [,] => { $ crate :: SyntaxKind :: COMMA } ; [=] => { $ crate :: SyntaxKind :: EQ } ; [;] => { $ crate :: SyntaxKind :: SEMICOLON } ; [:] => { $ crate :: SyntaxKind :: COLON } ; ['('] => { $ crate :: SyntaxKind :: LPAREN } ; [')'] => { $ crate :: SyntaxKind :: RPAREN } ; ['['] => { $ crate :: SyntaxKind :: LBRACKET } ; [']'] => { $ crate :: SyntaxKind :: RBRACKET } ; ['{'] => { $ crate :: SyntaxKind :: LBRACE } ; ['}'] => { $ crate :: SyntaxKind :: RBRACE } ; [<] => { $ crate :: SyntaxKind :: LT } ; [>] => { $ crate :: SyntaxKind :: GT } ; [>=] => { $ crate :: SyntaxKind :: GE } ; [<=] => { $ crate :: SyntaxKind :: LE } ; [==] => { $ crate :: SyntaxKind :: EQEQ } ; [!=] => { $ crate :: SyntaxKind :: NE } ; [+=] => { $ crate :: SyntaxKind :: PLUSEQ } ; [-=] => { $ crate :: SyntaxKind :: MINUSEQ } ; [*=] => { $ crate :: SyntaxKind :: STAREQ } ; [/=] => { $ crate :: SyntaxKind :: SLASHEQ } ; [dslasheq] => { $ crate :: SyntaxKind :: DSLASHEQ } ; [%=] => { $ crate :: SyntaxKind :: PERCENTEQ } ; [&=] => { $ crate :: SyntaxKind :: AMPEQ } ; [|=] => { $ crate :: SyntaxKind :: PIPEEQ } ; [^=] => { $ crate :: SyntaxKind :: CARETEQ } ; [<<=] => { $ crate :: SyntaxKind :: LSHIFTEQ } ; [>>=] => { $ crate :: SyntaxKind :: RSHIFTEQ } ; [->] => { $ crate :: SyntaxKind :: THIN_ARROW } ; [and] => { $ crate :: SyntaxKind :: AND_KW } ; [else] => { $ crate :: SyntaxKind :: ELSE_KW } ; [load] => { $ crate :: SyntaxKind :: LOAD_KW } ; [break] => { $ crate :: SyntaxKind :: BREAK_KW } ; [for] => { $ crate :: SyntaxKind :: FOR_KW } ; [not] => { $ crate :: SyntaxKind :: NOT_KW } ; [continue] => { $ crate :: SyntaxKind :: CONTINUE_KW } ; [if] => { $ crate :: SyntaxKind :: IF_KW } ; [or] => { $ crate :: SyntaxKind :: OR_KW } ; [def] => { $ crate :: SyntaxKind :: DEF_KW } ; [in] => { $ crate :: SyntaxKind :: IN_KW } ; [pass] => { $ crate :: SyntaxKind :: PASS_KW } ; [elif] => { $ crate :: SyntaxKind :: ELIF_KW } ; [lambda] => { $ crate :: SyntaxKind :: LAMBDA_KW } ; [return] => { $ crate :: SyntaxKind :: RETURN_KW } ; [identifier] => { $ crate :: SyntaxKind :: IDENTIFIER } ; [eof] => { $ crate :: SyntaxKind :: EOF } ; [newline] => { $ crate :: SyntaxKind :: NEWLINE } ; [indent] => { $ crate :: SyntaxKind :: INDENT } ; [outdent] => { $ crate :: SyntaxKind :: OUTDENT } ; [dslash] => { $ crate :: SyntaxKind :: DSLASH } ; [string] => { $ crate :: SyntaxKind :: STRING } ; [bytes] => { $ crate :: SyntaxKind :: BYTES } ; [int] => { $ crate :: SyntaxKind :: INT } ; [float] => { $ crate :: SyntaxKind :: FLOAT } ; [comment] => { $ crate :: SyntaxKind :: COMMENT } ; [whitespace] => { $ crate :: SyntaxKind :: WHITESPACE } ; [error] => { $ crate :: SyntaxKind :: ERROR } ; }
 pub use T;
@@ -16,19 +16,22 @@
 
 #![allow(non_snake_case)]
 use crate::{
-    ast::{self, support, AstChildren, AstNode},
+    ast::{self, support, AstChildren, AstNode, AstToken},
     SyntaxKind::{self, *},
     SyntaxNode, SyntaxToken, T,
 };
 #[doc = "Node defs"]
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct File {
     pub(crate) syntax: SyntaxNode,
 }
 impl File {
-    pub fn statements(&self) -> AstChildren<Statement> { support::children(&self.syntax) }
-    pub fn eof_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![eof]) }
+    pub fn statements(&self) -> AstChildren<Statement> {
+        support::children(&self.syntax)
+    }
+    pub fn eof_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![eof])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -36,16 +39,36 @@ pub struct DefStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl DefStmt {
-    pub fn def_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![def]) }
-    pub fn identifier_token(&self) -> Option<SyntaxToken> {
-        support::token(&self.syntax, T![identifier])
+    pub fn def_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![def])
+    }
+    pub fn name(&self) -> Option<ast::Identifier> {
+        support::token(&self.syntax, T![identifier]).and_then(ast::Identifier::cast)
+    }
+    pub fn l_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['('])
+    }
+    pub fn parameters(&self) -> Option<Parameters> {
+        support::child(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![')'])
+    }
+    pub fn thin_arrow_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![->])
+    }
+    pub fn return_type(&self) -> Option<TypeAnnotation> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Suite> {
+        support::child(&self.syntax)
     }
-    pub fn l_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['(']) }
-    pub fn parameters(&self) -> Option<Parameters> { support::child(&self.syntax) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![')']) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn suite(&self) -> Option<Suite> { support::child(&self.syntax) }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -53,12 +76,24 @@ pub struct IfStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl IfStmt {
-    pub fn if_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![if]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn suite(&self) -> Option<Suite> { support::child(&self.syntax) }
-    pub fn elif_clauseses(&self) -> AstChildren<ElifClauses> { support::children(&self.syntax) }
-    pub fn else_clause(&self) -> Option<ElseClause> { support::child(&self.syntax) }
+    pub fn if_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![if])
+    }
+    pub fn condition(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Suite> {
+        support::child(&self.syntax)
+    }
+    pub fn elif_clauses(&self) -> AstChildren<ElifClauses> {
+        support::children(&self.syntax)
+    }
+    pub fn else_clause(&self) -> Option<ElseClause> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -66,12 +101,24 @@ pub struct ForStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl ForStmt {
-    pub fn for_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![for]) }
-    pub fn loop_variables(&self) -> Option<LoopVariables> { support::child(&self.syntax) }
-    pub fn in_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![in]) }
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn suite(&self) -> Option<Suite> { support::child(&self.syntax) }
+    pub fn for_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![for])
+    }
+    pub fn loop_variables(&self) -> Option<LoopVariables> {
+        support::child(&self.syntax)
+    }
+    pub fn in_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![in])
+    }
+    pub fn iterable(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Suite> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -79,7 +126,9 @@ pub struct SimpleStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl SimpleStmt {
-    pub fn small_stmts(&self) -> AstChildren<SmallStmt> { support::children(&self.syntax) }
+    pub fn small_stmts(&self) -> AstChildren<SmallStmt> {
+        support::children(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -87,7 +136,9 @@ pub struct Parameters {
     pub(crate) syntax: SyntaxNode,
 }
 impl Parameters {
-    pub fn parameter(&self) -> Option<Parameter> { support::child(&self.syntax) }
+    pub fn parameters(&self) -> AstChildren<Parameter> {
+        support::children(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -95,11 +146,21 @@ pub struct Suite {
     pub(crate) syntax: SyntaxNode,
 }
 impl Suite {
-    pub fn newline_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![newline]) }
-    pub fn indent_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![indent]) }
-    pub fn statements(&self) -> AstChildren<Statement> { support::children(&self.syntax) }
-    pub fn outdent_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![outdent]) }
-    pub fn simple_stmt(&self) -> Option<SimpleStmt> { support::child(&self.syntax) }
+    pub fn newline_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![newline])
+    }
+    pub fn indent_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![indent])
+    }
+    pub fn statements(&self) -> AstChildren<Statement> {
+        support::children(&self.syntax)
+    }
+    pub fn outdent_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![outdent])
+    }
+    pub fn simple_stmt(&self) -> Option<SimpleStmt> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -107,13 +168,27 @@ pub struct Parameter {
     pub(crate) syntax: SyntaxNode,
 }
 impl Parameter {
-    pub fn identifier_token(&self) -> Option<SyntaxToken> {
-        support::token(&self.syntax, T![identifier])
+    pub fn name(&self) -> Option<ast::Identifier> {
+        support::token(&self.syntax, T![identifier]).and_then(ast::Identifier::cast)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn annotation(&self) -> Option<TypeAnnotation> {
+        support::child(&self.syntax)
+    }
+    pub fn eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![=])
+    }
+    pub fn default(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn star_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![*])
+    }
+    pub fn starstar_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![**])
     }
-    pub fn eq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![=]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn star_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![*]) }
-    pub fn starstar_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![**]) }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -121,10 +196,18 @@ pub struct ElifClauses {
     pub(crate) syntax: SyntaxNode,
 }
 impl ElifClauses {
-    pub fn elif_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![elif]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn suite(&self) -> Option<Suite> { support::child(&self.syntax) }
+    pub fn elif_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![elif])
+    }
+    pub fn condition(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Suite> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -132,9 +215,15 @@ pub struct ElseClause {
     pub(crate) syntax: SyntaxNode,
 }
 impl ElseClause {
-    pub fn else_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![else]) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn suite(&self) -> Option<Suite> { support::child(&self.syntax) }
+    pub fn else_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![else])
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Suite> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -142,7 +231,19 @@ pub struct LoopVariables {
     pub(crate) syntax: SyntaxNode,
 }
 impl LoopVariables {
-    pub fn primary_expr(&self) -> Option<PrimaryExpr> { support::child(&self.syntax) }
+    pub fn primary_exprs(&self) -> AstChildren<PrimaryExpr> {
+        support::children(&self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeAnnotation {
+    pub(crate) syntax: SyntaxNode,
+}
+impl TypeAnnotation {
+    pub fn test(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -150,7 +251,9 @@ pub struct Expression {
     pub(crate) syntax: SyntaxNode,
 }
 impl Expression {
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
+    pub fn tests(&self) -> AstChildren<Test> {
+        support::children(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -158,8 +261,12 @@ pub struct ReturnStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl ReturnStmt {
-    pub fn return_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![return]) }
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
+    pub fn return_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![return])
+    }
+    pub fn expression(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -167,7 +274,9 @@ pub struct BreakStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl BreakStmt {
-    pub fn break_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![break]) }
+    pub fn break_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![break])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -185,7 +294,9 @@ pub struct PassStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl PassStmt {
-    pub fn pass_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![pass]) }
+    pub fn pass_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![pass])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -193,21 +304,48 @@ pub struct AssignStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl AssignStmt {
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
-    pub fn eq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![=]) }
-    pub fn pluseq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![+=]) }
-    pub fn minuseq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![-=]) }
-    pub fn stareq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![*=]) }
-    pub fn slasheq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![/=]) }
+    pub fn lhs(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
+    pub fn eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![=])
+    }
+    pub fn pluseq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![+=])
+    }
+    pub fn minuseq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![-=])
+    }
+    pub fn stareq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![*=])
+    }
+    pub fn slasheq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![/=])
+    }
     pub fn slashslasheq_token(&self) -> Option<SyntaxToken> {
-        support::token(&self.syntax, T![dslash])
+        support::token(&self.syntax, T![dslasheq])
+    }
+    pub fn percenteq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![%=])
+    }
+    pub fn ampeq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![&=])
+    }
+    pub fn pipeeq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![|=])
+    }
+    pub fn careteq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![^=])
+    }
+    pub fn shl_eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![<<=])
+    }
+    pub fn shr_eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![>>=])
+    }
+    pub fn rhs(&self) -> Option<Expression> {
+        support::children(&self.syntax).nth(1)
     }
-    pub fn percenteq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![%=]) }
-    pub fn ampeq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![&=]) }
-    pub fn pipeeq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![|=]) }
-    pub fn careteq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![^=]) }
-    pub fn shl_eq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![<<=]) }
-    pub fn shr_eq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![>>=]) }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -215,7 +353,9 @@ pub struct ExprStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl ExprStmt {
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
+    pub fn expression(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -223,10 +363,40 @@ pub struct LoadStmt {
     pub(crate) syntax: SyntaxNode,
 }
 impl LoadStmt {
-    pub fn load_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![load]) }
-    pub fn l_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['(']) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![')']) }
+    pub fn load_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![load])
+    }
+    pub fn l_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['('])
+    }
+    pub fn module(&self) -> Option<ast::String> {
+        support::token(&self.syntax, T![string]).and_then(ast::String::cast)
+    }
+    pub fn load_symbols(&self) -> AstChildren<LoadSymbol> {
+        support::children(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![')'])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoadSymbol {
+    pub(crate) syntax: SyntaxNode,
+}
+impl LoadSymbol {
+    pub fn alias(&self) -> Option<ast::Identifier> {
+        support::token(&self.syntax, T![identifier]).and_then(ast::Identifier::cast)
+    }
+    pub fn eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![=])
+    }
+    pub fn symbol(&self) -> Option<ast::String> {
+        support::token(&self.syntax, T![string]).and_then(ast::String::cast)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -234,9 +404,21 @@ pub struct IfExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl IfExpr {
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn if_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![if]) }
-    pub fn else_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![else]) }
+    pub fn then_branch(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn if_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![if])
+    }
+    pub fn condition(&self) -> Option<Test> {
+        support::children(&self.syntax).nth(1)
+    }
+    pub fn else_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![else])
+    }
+    pub fn else_branch(&self) -> Option<Test> {
+        support::children(&self.syntax).nth(2)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -244,11 +426,21 @@ pub struct PrimaryExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl PrimaryExpr {
-    pub fn operand(&self) -> Option<Operand> { support::child(&self.syntax) }
-    pub fn primary_expr(&self) -> Option<PrimaryExpr> { support::child(&self.syntax) }
-    pub fn dot_suffix(&self) -> Option<DotSuffix> { support::child(&self.syntax) }
-    pub fn call_suffix(&self) -> Option<CallSuffix> { support::child(&self.syntax) }
-    pub fn slice_suffix(&self) -> Option<SliceSuffix> { support::child(&self.syntax) }
+    pub fn operand(&self) -> Option<Operand> {
+        support::child(&self.syntax)
+    }
+    pub fn primary_expr(&self) -> Option<PrimaryExpr> {
+        support::child(&self.syntax)
+    }
+    pub fn dot_suffix(&self) -> Option<DotSuffix> {
+        support::child(&self.syntax)
+    }
+    pub fn call_suffix(&self) -> Option<CallSuffix> {
+        support::child(&self.syntax)
+    }
+    pub fn slice_suffix(&self) -> Option<SliceSuffix> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -256,11 +448,21 @@ pub struct UnaryExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl UnaryExpr {
-    pub fn plus_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![+]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn minus_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![-]) }
-    pub fn tilde_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![~]) }
-    pub fn not_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![not]) }
+    pub fn plus_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![+])
+    }
+    pub fn operand(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn minus_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![-])
+    }
+    pub fn tilde_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![~])
+    }
+    pub fn not_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![not])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -268,8 +470,15 @@ pub struct BinaryExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl BinaryExpr {
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn binops(&self) -> AstChildren<Binop> { support::children(&self.syntax) }
+    pub fn lhs(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn binop(&self) -> Option<Binop> {
+        support::child(&self.syntax)
+    }
+    pub fn rhs(&self) -> Option<Test> {
+        support::children(&self.syntax).nth(1)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -277,10 +486,18 @@ pub struct LambdaExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl LambdaExpr {
-    pub fn lambda_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![lambda]) }
-    pub fn parameters(&self) -> Option<Parameters> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
+    pub fn lambda_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![lambda])
+    }
+    pub fn parameters(&self) -> Option<Parameters> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn body(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -291,14 +508,30 @@ impl Operand {
     pub fn identifier_token(&self) -> Option<SyntaxToken> {
         support::token(&self.syntax, T![identifier])
     }
-    pub fn list_expr(&self) -> Option<ListExpr> { support::child(&self.syntax) }
-    pub fn list_comp(&self) -> Option<ListComp> { support::child(&self.syntax) }
-    pub fn dict_expr(&self) -> Option<DictExpr> { support::child(&self.syntax) }
-    pub fn dict_comp(&self) -> Option<DictComp> { support::child(&self.syntax) }
-    pub fn l_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['(']) }
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![')']) }
+    pub fn list_expr(&self) -> Option<ListExpr> {
+        support::child(&self.syntax)
+    }
+    pub fn list_comp(&self) -> Option<ListComp> {
+        support::child(&self.syntax)
+    }
+    pub fn dict_expr(&self) -> Option<DictExpr> {
+        support::child(&self.syntax)
+    }
+    pub fn dict_comp(&self) -> Option<DictComp> {
+        support::child(&self.syntax)
+    }
+    pub fn l_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['('])
+    }
+    pub fn expression(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![')'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -306,9 +539,11 @@ pub struct DotSuffix {
     pub(crate) syntax: SyntaxNode,
 }
 impl DotSuffix {
-    pub fn dot_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![.]) }
-    pub fn identifier_token(&self) -> Option<SyntaxToken> {
-        support::token(&self.syntax, T![identifier])
+    pub fn dot_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![.])
+    }
+    pub fn name(&self) -> Option<ast::Identifier> {
+        support::token(&self.syntax, T![identifier]).and_then(ast::Identifier::cast)
     }
 }
 
@@ -317,10 +552,18 @@ pub struct CallSuffix {
     pub(crate) syntax: SyntaxNode,
 }
 impl CallSuffix {
-    pub fn l_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['(']) }
-    pub fn arguments(&self) -> Option<Arguments> { support::child(&self.syntax) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_paren_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![')']) }
+    pub fn l_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['('])
+    }
+    pub fn arguments(&self) -> Option<Arguments> {
+        support::child(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_paren_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![')'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -328,11 +571,31 @@ pub struct SliceSuffix {
     pub(crate) syntax: SyntaxNode,
 }
 impl SliceSuffix {
-    pub fn l_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['[']) }
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn r_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![']']) }
+    pub fn l_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['['])
+    }
+    pub fn start(&self) -> Option<Expression> {
+        support::token(&self.syntax, T![:])?;
+        support::child_after(&self.syntax, T![:], 0)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn end(&self) -> Option<Test> {
+        support::child_after(&self.syntax, T![:], 1)
+    }
+    pub fn step(&self) -> Option<Test> {
+        support::child_after(&self.syntax, T![:], 2)
+    }
+    pub fn r_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![']'])
+    }
+    pub fn index(&self) -> Option<Expression> {
+        if support::token(&self.syntax, T![:]).is_some() {
+            return None;
+        }
+        support::child_after(&self.syntax, T![:], 0)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -340,10 +603,18 @@ pub struct ListExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl ListExpr {
-    pub fn l_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['[']) }
-    pub fn expression(&self) -> Option<Expression> { support::child(&self.syntax) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![']']) }
+    pub fn l_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['['])
+    }
+    pub fn expression(&self) -> Option<Expression> {
+        support::child(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![']'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -351,10 +622,18 @@ pub struct ListComp {
     pub(crate) syntax: SyntaxNode,
 }
 impl ListComp {
-    pub fn l_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['[']) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn comp_clauses(&self) -> AstChildren<CompClause> { support::children(&self.syntax) }
-    pub fn r_brack_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![']']) }
+    pub fn l_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['['])
+    }
+    pub fn element(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn comp_clauses(&self) -> AstChildren<CompClause> {
+        support::children(&self.syntax)
+    }
+    pub fn r_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![']'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -362,10 +641,18 @@ pub struct DictExpr {
     pub(crate) syntax: SyntaxNode,
 }
 impl DictExpr {
-    pub fn l_curly_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['{']) }
-    pub fn entries(&self) -> Option<Entries> { support::child(&self.syntax) }
-    pub fn comma_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![,]) }
-    pub fn r_curly_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['}']) }
+    pub fn l_curly_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['{'])
+    }
+    pub fn entries(&self) -> Option<Entries> {
+        support::child(&self.syntax)
+    }
+    pub fn comma_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![,])
+    }
+    pub fn r_curly_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['}'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -373,10 +660,18 @@ pub struct DictComp {
     pub(crate) syntax: SyntaxNode,
 }
 impl DictComp {
-    pub fn l_curly_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['{']) }
-    pub fn entry(&self) -> Option<Entry> { support::child(&self.syntax) }
-    pub fn comp_clauses(&self) -> AstChildren<CompClause> { support::children(&self.syntax) }
-    pub fn r_curly_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T!['}']) }
+    pub fn l_curly_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['{'])
+    }
+    pub fn entry(&self) -> Option<Entry> {
+        support::child(&self.syntax)
+    }
+    pub fn comp_clauses(&self) -> AstChildren<CompClause> {
+        support::children(&self.syntax)
+    }
+    pub fn r_curly_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T!['}'])
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -384,7 +679,9 @@ pub struct Arguments {
     pub(crate) syntax: SyntaxNode,
 }
 impl Arguments {
-    pub fn argument(&self) -> Option<Argument> { support::child(&self.syntax) }
+    pub fn arguments(&self) -> AstChildren<Argument> {
+        support::children(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -392,13 +689,21 @@ pub struct Argument {
     pub(crate) syntax: SyntaxNode,
 }
 impl Argument {
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn identifier_token(&self) -> Option<SyntaxToken> {
-        support::token(&self.syntax, T![identifier])
+    pub fn value(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn name(&self) -> Option<ast::Identifier> {
+        support::token(&self.syntax, T![identifier]).and_then(ast::Identifier::cast)
+    }
+    pub fn eq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![=])
+    }
+    pub fn star_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![*])
+    }
+    pub fn starstar_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![**])
     }
-    pub fn eq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![=]) }
-    pub fn star_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![*]) }
-    pub fn starstar_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![**]) }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -406,11 +711,24 @@ pub struct CompClause {
     pub(crate) syntax: SyntaxNode,
 }
 impl CompClause {
-    pub fn for_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![for]) }
-    pub fn loop_variables(&self) -> Option<LoopVariables> { support::child(&self.syntax) }
-    pub fn in_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![in]) }
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn if_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![if]) }
+    pub fn for_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![for])
+    }
+    pub fn loop_variables(&self) -> Option<LoopVariables> {
+        support::child(&self.syntax)
+    }
+    pub fn in_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![in])
+    }
+    pub fn iterable(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn if_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![if])
+    }
+    pub fn condition(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -418,7 +736,9 @@ pub struct Entries {
     pub(crate) syntax: SyntaxNode,
 }
 impl Entries {
-    pub fn entry(&self) -> Option<Entry> { support::child(&self.syntax) }
+    pub fn entries(&self) -> AstChildren<Entry> {
+        support::children(&self.syntax)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -426,8 +746,15 @@ pub struct Entry {
     pub(crate) syntax: SyntaxNode,
 }
 impl Entry {
-    pub fn test(&self) -> Option<Test> { support::child(&self.syntax) }
-    pub fn colon_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![:]) }
+    pub fn key(&self) -> Option<Test> {
+        support::child(&self.syntax)
+    }
+    pub fn colon_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![:])
+    }
+    pub fn value(&self) -> Option<Test> {
+        support::children(&self.syntax).nth(1)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -435,30 +762,71 @@ pub struct Binop {
     pub(crate) syntax: SyntaxNode,
 }
 impl Binop {
-    pub fn or_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![or]) }
-    pub fn and_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![and]) }
-    pub fn eqeq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![==]) }
-    pub fn noteq_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![!=]) }
-    pub fn l_angle_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![<]) }
-    pub fn r_angle_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![>]) }
-    pub fn le_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![<=]) }
-    pub fn ge_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![>=]) }
-    pub fn in_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![in]) }
-    pub fn not_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![not]) }
-    pub fn pipe_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![|]) }
-    pub fn caret_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![^]) }
-    pub fn amp_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![&]) }
-    pub fn shl_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![<<]) }
-    pub fn shr_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![>>]) }
-    pub fn minus_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![-]) }
-    pub fn plus_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![+]) }
-    pub fn star_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![*]) }
-    pub fn percent_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![%]) }
-    pub fn slash_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![/]) }
-    pub fn dslash_token(&self) -> Option<SyntaxToken> { support::token(&self.syntax, T![dslash]) }
+    pub fn or_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![or])
+    }
+    pub fn and_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![and])
+    }
+    pub fn eqeq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![==])
+    }
+    pub fn noteq_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![!=])
+    }
+    pub fn l_angle_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![<])
+    }
+    pub fn r_angle_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![>])
+    }
+    pub fn le_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![<=])
+    }
+    pub fn ge_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![>=])
+    }
+    pub fn in_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![in])
+    }
+    pub fn not_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![not])
+    }
+    pub fn pipe_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![|])
+    }
+    pub fn caret_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![^])
+    }
+    pub fn amp_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![&])
+    }
+    pub fn shl_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![<<])
+    }
+    pub fn shr_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![>>])
+    }
+    pub fn minus_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![-])
+    }
+    pub fn plus_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![+])
+    }
+    pub fn star_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![*])
+    }
+    pub fn percent_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![%])
+    }
+    pub fn slash_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![/])
+    }
+    pub fn dslash_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, T![dslash])
+    }
 }
 #[doc = "Enum defs"]
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Statement {
     DefStmt(DefStmt),
@@ -489,7 +857,9 @@ pub enum SmallStmt {
 #[doc = "Any node defs"]
 #[doc = "Node boilerplate"]
 impl AstNode for File {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FILE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == FILE
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -497,10 +867,14 @@ impl AstNode for File {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for DefStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DEF_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DEF_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -508,10 +882,14 @@ impl AstNode for DefStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for IfStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == IF_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -519,10 +897,14 @@ impl AstNode for IfStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ForStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FOR_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == FOR_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -530,10 +912,14 @@ impl AstNode for ForStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for SimpleStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SIMPLE_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SIMPLE_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -541,10 +927,14 @@ impl AstNode for SimpleStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Parameters {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PARAMETERS }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PARAMETERS
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -552,10 +942,14 @@ impl AstNode for Parameters {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Suite {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SUITE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SUITE
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -563,10 +957,14 @@ impl AstNode for Suite {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Parameter {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PARAMETER }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PARAMETER
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -574,10 +972,14 @@ impl AstNode for Parameter {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ElifClauses {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELIF_CLAUSES }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ELIF_CLAUSES
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -585,10 +987,14 @@ impl AstNode for ElifClauses {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ElseClause {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_CLAUSE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ELSE_CLAUSE
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -596,10 +1002,29 @@ impl AstNode for ElseClause {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for LoopVariables {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LOOP_VARIABLES }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LOOP_VARIABLES
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl AstNode for TypeAnnotation {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == TYPE_ANNOTATION
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -607,10 +1032,14 @@ impl AstNode for LoopVariables {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Expression {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == EXPRESSION }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == EXPRESSION
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -618,10 +1047,14 @@ impl AstNode for Expression {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ReturnStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RETURN_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RETURN_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -629,10 +1062,14 @@ impl AstNode for ReturnStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for BreakStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BREAK_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == BREAK_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -640,10 +1077,14 @@ impl AstNode for BreakStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ContinueStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CONTINUE_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CONTINUE_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -651,10 +1092,14 @@ impl AstNode for ContinueStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for PassStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PASS_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PASS_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -662,10 +1107,14 @@ impl AstNode for PassStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for AssignStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ASSIGN_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ASSIGN_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -673,10 +1122,14 @@ impl AstNode for AssignStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ExprStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == EXPR_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == EXPR_STMT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -684,10 +1137,29 @@ impl AstNode for ExprStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for LoadStmt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LOAD_STMT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LOAD_STMT
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl AstNode for LoadSymbol {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LOAD_SYMBOL
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -695,10 +1167,14 @@ impl AstNode for LoadStmt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for IfExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == IF_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -706,10 +1182,14 @@ impl AstNode for IfExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for PrimaryExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PRIMARY_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PRIMARY_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -717,10 +1197,14 @@ impl AstNode for PrimaryExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for UnaryExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == UNARY_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == UNARY_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -728,10 +1212,14 @@ impl AstNode for UnaryExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for BinaryExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BINARY_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == BINARY_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -739,10 +1227,14 @@ impl AstNode for BinaryExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for LambdaExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LAMBDA_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LAMBDA_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -750,10 +1242,14 @@ impl AstNode for LambdaExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Operand {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == OPERAND }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == OPERAND
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -761,10 +1257,14 @@ impl AstNode for Operand {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for DotSuffix {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DOT_SUFFIX }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DOT_SUFFIX
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -772,10 +1272,14 @@ impl AstNode for DotSuffix {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for CallSuffix {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CALL_SUFFIX }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CALL_SUFFIX
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -783,10 +1287,14 @@ impl AstNode for CallSuffix {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for SliceSuffix {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SLICE_SUFFIX }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SLICE_SUFFIX
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -794,10 +1302,14 @@ impl AstNode for SliceSuffix {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ListExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LIST_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LIST_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -805,10 +1317,14 @@ impl AstNode for ListExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for ListComp {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LIST_COMP }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LIST_COMP
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -816,10 +1332,14 @@ impl AstNode for ListComp {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for DictExpr {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DICT_EXPR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DICT_EXPR
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -827,10 +1347,14 @@ impl AstNode for DictExpr {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for DictComp {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DICT_COMP }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DICT_COMP
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -838,10 +1362,14 @@ impl AstNode for DictComp {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Arguments {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ARGUMENTS }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ARGUMENTS
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -849,10 +1377,14 @@ impl AstNode for Arguments {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Argument {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ARGUMENT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ARGUMENT
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -860,10 +1392,14 @@ impl AstNode for Argument {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for CompClause {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == COMP_CLAUSE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == COMP_CLAUSE
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -871,10 +1407,14 @@ impl AstNode for CompClause {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Entries {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ENTRIES }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ENTRIES
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -882,10 +1422,14 @@ impl AstNode for Entries {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Entry {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ENTRY }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ENTRY
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -893,10 +1437,14 @@ impl AstNode for Entry {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 impl AstNode for Binop {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BINOP }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == BINOP
+    }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -904,27 +1452,41 @@ impl AstNode for Binop {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxNode { &self.syntax }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
 }
 #[doc = "Enum boilerplate"]
 impl From<DefStmt> for Statement {
-    fn from(node: DefStmt) -> Statement { Statement::DefStmt(node) }
+    fn from(node: DefStmt) -> Statement {
+        Statement::DefStmt(node)
+    }
 }
 impl From<IfStmt> for Statement {
-    fn from(node: IfStmt) -> Statement { Statement::IfStmt(node) }
+    fn from(node: IfStmt) -> Statement {
+        Statement::IfStmt(node)
+    }
 }
 impl From<ForStmt> for Statement {
-    fn from(node: ForStmt) -> Statement { Statement::ForStmt(node) }
+    fn from(node: ForStmt) -> Statement {
+        Statement::ForStmt(node)
+    }
 }
 impl From<SimpleStmt> for Statement {
-    fn from(node: SimpleStmt) -> Statement { Statement::SimpleStmt(node) }
+    fn from(node: SimpleStmt) -> Statement {
+        Statement::SimpleStmt(node)
+    }
 }
 impl AstNode for Statement {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, DEF_STMT | IF_STMT | FOR_STMT | SIMPLE_STMT)
+        matches!(
+            kind,
+            STATEMENT | DEF_STMT | IF_STMT | FOR_STMT | SIMPLE_STMT
+        )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         let res = match syntax.kind() {
+            STATEMENT => return syntax.children().find_map(Self::cast),
             DEF_STMT => Statement::DefStmt(DefStmt { syntax }),
             IF_STMT => Statement::IfStmt(IfStmt { syntax }),
             FOR_STMT => Statement::ForStmt(ForStmt { syntax }),
@@ -943,29 +1505,40 @@ impl AstNode for Statement {
     }
 }
 impl From<IfExpr> for Test {
-    fn from(node: IfExpr) -> Test { Test::IfExpr(node) }
+    fn from(node: IfExpr) -> Test {
+        Test::IfExpr(node)
+    }
 }
 impl From<PrimaryExpr> for Test {
-    fn from(node: PrimaryExpr) -> Test { Test::PrimaryExpr(node) }
+    fn from(node: PrimaryExpr) -> Test {
+        Test::PrimaryExpr(node)
+    }
 }
 impl From<UnaryExpr> for Test {
-    fn from(node: UnaryExpr) -> Test { Test::UnaryExpr(node) }
+    fn from(node: UnaryExpr) -> Test {
+        Test::UnaryExpr(node)
+    }
 }
 impl From<BinaryExpr> for Test {
-    fn from(node: BinaryExpr) -> Test { Test::BinaryExpr(node) }
+    fn from(node: BinaryExpr) -> Test {
+        Test::BinaryExpr(node)
+    }
 }
 impl From<LambdaExpr> for Test {
-    fn from(node: LambdaExpr) -> Test { Test::LambdaExpr(node) }
+    fn from(node: LambdaExpr) -> Test {
+        Test::LambdaExpr(node)
+    }
 }
 impl AstNode for Test {
     fn can_cast(kind: SyntaxKind) -> bool {
         matches!(
             kind,
-            IF_EXPR | PRIMARY_EXPR | UNARY_EXPR | BINARY_EXPR | LAMBDA_EXPR
+            TEST | IF_EXPR | PRIMARY_EXPR | UNARY_EXPR | BINARY_EXPR | LAMBDA_EXPR
         )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         let res = match syntax.kind() {
+            TEST => return syntax.children().find_map(Self::cast),
             IF_EXPR => Test::IfExpr(IfExpr { syntax }),
             PRIMARY_EXPR => Test::PrimaryExpr(PrimaryExpr { syntax }),
             UNARY_EXPR => Test::UnaryExpr(UnaryExpr { syntax }),
@@ -986,31 +1559,46 @@ impl AstNode for Test {
     }
 }
 impl From<ReturnStmt> for SmallStmt {
-    fn from(node: ReturnStmt) -> SmallStmt { SmallStmt::ReturnStmt(node) }
+    fn from(node: ReturnStmt) -> SmallStmt {
+        SmallStmt::ReturnStmt(node)
+    }
 }
 impl From<BreakStmt> for SmallStmt {
-    fn from(node: BreakStmt) -> SmallStmt { SmallStmt::BreakStmt(node) }
+    fn from(node: BreakStmt) -> SmallStmt {
+        SmallStmt::BreakStmt(node)
+    }
 }
 impl From<ContinueStmt> for SmallStmt {
-    fn from(node: ContinueStmt) -> SmallStmt { SmallStmt::ContinueStmt(node) }
+    fn from(node: ContinueStmt) -> SmallStmt {
+        SmallStmt::ContinueStmt(node)
+    }
 }
 impl From<PassStmt> for SmallStmt {
-    fn from(node: PassStmt) -> SmallStmt { SmallStmt::PassStmt(node) }
+    fn from(node: PassStmt) -> SmallStmt {
+        SmallStmt::PassStmt(node)
+    }
 }
 impl From<AssignStmt> for SmallStmt {
-    fn from(node: AssignStmt) -> SmallStmt { SmallStmt::AssignStmt(node) }
+    fn from(node: AssignStmt) -> SmallStmt {
+        SmallStmt::AssignStmt(node)
+    }
 }
 impl From<ExprStmt> for SmallStmt {
-    fn from(node: ExprStmt) -> SmallStmt { SmallStmt::ExprStmt(node) }
+    fn from(node: ExprStmt) -> SmallStmt {
+        SmallStmt::ExprStmt(node)
+    }
 }
 impl From<LoadStmt> for SmallStmt {
-    fn from(node: LoadStmt) -> SmallStmt { SmallStmt::LoadStmt(node) }
+    fn from(node: LoadStmt) -> SmallStmt {
+        SmallStmt::LoadStmt(node)
+    }
 }
 impl AstNode for SmallStmt {
     fn can_cast(kind: SyntaxKind) -> bool {
         matches!(
             kind,
-            RETURN_STMT
+            SMALL_STMT
+                | RETURN_STMT
                 | BREAK_STMT
                 | CONTINUE_STMT
                 | PASS_STMT
@@ -1021,6 +1609,7 @@ impl AstNode for SmallStmt {
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         let res = match syntax.kind() {
+            SMALL_STMT => return syntax.children().find_map(Self::cast),
             RETURN_STMT => SmallStmt::ReturnStmt(ReturnStmt { syntax }),
             BREAK_STMT => SmallStmt::BreakStmt(BreakStmt { syntax }),
             CONTINUE_STMT => SmallStmt::ContinueStmt(ContinueStmt { syntax }),
@@ -1116,6 +1705,11 @@ impl std::fmt::Display for LoopVariables {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::Display::fmt(self.syntax(), f)
@@ -1156,6 +1750,11 @@ impl std::fmt::Display for LoadStmt {
         std::fmt::Display::fmt(self.syntax(), f)
     }
 }
+impl std::fmt::Display for LoadSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.syntax(), f)
+    }
+}
 impl std::fmt::Display for IfExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::Display::fmt(self.syntax(), f)
@@ -8,7 +8,6 @@
 //! `syntaxgen/sourcegen.rs`.
 //!
 /// A syntax token.
-
 use crate::{
     ast::AstToken,
     SyntaxKind::{self, *},
@@ -25,7 +24,9 @@ impl std::fmt::Display for Whitespace {
     }
 }
 impl AstToken for Whitespace {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == WHITESPACE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == WHITESPACE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -33,7 +34,9 @@ impl AstToken for Whitespace {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -46,7 +49,9 @@ impl std::fmt::Display for Comment {
     }
 }
 impl AstToken for Comment {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == COMMENT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == COMMENT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -54,7 +59,9 @@ impl AstToken for Comment {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -67,7 +74,9 @@ impl std::fmt::Display for Newline {
     }
 }
 impl AstToken for Newline {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == NEWLINE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == NEWLINE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -75,7 +84,9 @@ impl AstToken for Newline {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -88,7 +99,9 @@ impl std::fmt::Display for Indent {
     }
 }
 impl AstToken for Indent {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == INDENT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == INDENT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -96,7 +109,9 @@ impl AstToken for Indent {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -109,7 +124,9 @@ impl std::fmt::Display for Outdent {
     }
 }
 impl AstToken for Outdent {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == OUTDENT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == OUTDENT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -117,7 +134,9 @@ impl AstToken for Outdent {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -130,7 +149,9 @@ impl std::fmt::Display for Unknown {
     }
 }
 impl AstToken for Unknown {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == UNKNOWN }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == UNKNOWN
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -138,7 +159,9 @@ impl AstToken for Unknown {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -151,7 +174,9 @@ impl std::fmt::Display for Identifier {
     }
 }
 impl AstToken for Identifier {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IDENTIFIER }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == IDENTIFIER
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -159,7 +184,9 @@ impl AstToken for Identifier {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -172,7 +199,9 @@ impl std::fmt::Display for Int {
     }
 }
 impl AstToken for Int {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == INT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == INT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -180,7 +209,9 @@ impl AstToken for Int {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -193,7 +224,9 @@ impl std::fmt::Display for Float {
     }
 }
 impl AstToken for Float {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FLOAT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == FLOAT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -201,7 +234,9 @@ impl AstToken for Float {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -214,7 +249,9 @@ impl std::fmt::Display for String {
     }
 }
 impl AstToken for String {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == STRING }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == STRING
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -222,7 +259,9 @@ impl AstToken for String {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -235,7 +274,9 @@ impl std::fmt::Display for Bytes {
     }
 }
 impl AstToken for Bytes {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BYTES }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == BYTES
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -243,7 +284,9 @@ impl AstToken for Bytes {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -256,7 +299,9 @@ impl std::fmt::Display for And {
     }
 }
 impl AstToken for And {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == AND_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == AND_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -264,7 +309,9 @@ impl AstToken for And {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -277,7 +324,9 @@ impl std::fmt::Display for Else {
     }
 }
 impl AstToken for Else {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELSE_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ELSE_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -285,7 +334,9 @@ impl AstToken for Else {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -298,7 +349,9 @@ impl std::fmt::Display for Load {
     }
 }
 impl AstToken for Load {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LOAD_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LOAD_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -306,7 +359,9 @@ impl AstToken for Load {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -319,7 +374,9 @@ impl std::fmt::Display for Break {
     }
 }
 impl AstToken for Break {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == BREAK_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == BREAK_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -327,7 +384,9 @@ impl AstToken for Break {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -340,7 +399,9 @@ impl std::fmt::Display for For {
     }
 }
 impl AstToken for For {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == FOR_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == FOR_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -348,7 +409,9 @@ impl AstToken for For {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -361,7 +424,9 @@ impl std::fmt::Display for Not {
     }
 }
 impl AstToken for Not {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == NOT_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == NOT_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -369,7 +434,9 @@ impl AstToken for Not {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -382,7 +449,9 @@ impl std::fmt::Display for Continue {
     }
 }
 impl AstToken for Continue {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CONTINUE_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CONTINUE_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -390,7 +459,9 @@ impl AstToken for Continue {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -403,7 +474,9 @@ impl std::fmt::Display for If {
     }
 }
 impl AstToken for If {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IF_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == IF_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -411,7 +484,9 @@ impl AstToken for If {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -424,7 +499,9 @@ impl std::fmt::Display for Or {
     }
 }
 impl AstToken for Or {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == OR_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == OR_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -432,7 +509,9 @@ impl AstToken for Or {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -445,7 +524,9 @@ impl std::fmt::Display for Def {
     }
 }
 impl AstToken for Def {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DEF_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DEF_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -453,7 +534,9 @@ impl AstToken for Def {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -466,7 +549,9 @@ impl std::fmt::Display for In {
     }
 }
 impl AstToken for In {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == IN_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == IN_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -474,7 +559,9 @@ impl AstToken for In {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -487,7 +574,9 @@ impl std::fmt::Display for Pass {
     }
 }
 impl AstToken for Pass {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PASS_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PASS_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -495,7 +584,9 @@ impl AstToken for Pass {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -508,7 +599,9 @@ impl std::fmt::Display for Elif {
     }
 }
 impl AstToken for Elif {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == ELIF_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == ELIF_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -516,7 +609,9 @@ impl AstToken for Elif {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -529,7 +624,9 @@ impl std::fmt::Display for Lambda {
     }
 }
 impl AstToken for Lambda {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LAMBDA_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LAMBDA_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -537,7 +634,9 @@ impl AstToken for Lambda {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -550,7 +649,9 @@ impl std::fmt::Display for Return {
     }
 }
 impl AstToken for Return {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RETURN_KW }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RETURN_KW
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -558,7 +659,9 @@ impl AstToken for Return {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -571,7 +674,9 @@ impl std::fmt::Display for Plus {
     }
 }
 impl AstToken for Plus {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PLUS }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PLUS
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -579,7 +684,9 @@ impl AstToken for Plus {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -592,7 +699,9 @@ impl std::fmt::Display for Minus {
     }
 }
 impl AstToken for Minus {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == MINUS }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == MINUS
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -600,7 +709,9 @@ impl AstToken for Minus {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -613,7 +724,9 @@ impl std::fmt::Display for Star {
     }
 }
 impl AstToken for Star {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == STAR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == STAR
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -621,7 +734,9 @@ impl AstToken for Star {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -634,7 +749,9 @@ impl std::fmt::Display for Slash {
     }
 }
 impl AstToken for Slash {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SLASH }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SLASH
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -642,7 +759,9 @@ impl AstToken for Slash {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -655,7 +774,9 @@ impl std::fmt::Display for Dslash {
     }
 }
 impl AstToken for Dslash {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DSLASH }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DSLASH
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -663,7 +784,9 @@ impl AstToken for Dslash {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -676,7 +799,9 @@ impl std::fmt::Display for Percent {
     }
 }
 impl AstToken for Percent {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PERCENT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PERCENT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -684,7 +809,9 @@ impl AstToken for Percent {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -697,7 +824,9 @@ impl std::fmt::Display for Dstar {
     }
 }
 impl AstToken for Dstar {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DSTAR }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DSTAR
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -705,7 +834,9 @@ impl AstToken for Dstar {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -718,7 +849,9 @@ impl std::fmt::Display for Tilde {
     }
 }
 impl AstToken for Tilde {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == TILDE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == TILDE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -726,7 +859,9 @@ impl AstToken for Tilde {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -739,7 +874,9 @@ impl std::fmt::Display for Amp {
     }
 }
 impl AstToken for Amp {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == AMP }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == AMP
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -747,7 +884,9 @@ impl AstToken for Amp {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -760,7 +899,9 @@ impl std::fmt::Display for Pipe {
     }
 }
 impl AstToken for Pipe {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PIPE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PIPE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -768,7 +909,9 @@ impl AstToken for Pipe {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -781,7 +924,9 @@ impl std::fmt::Display for Caret {
     }
 }
 impl AstToken for Caret {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CARET }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CARET
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -789,7 +934,9 @@ impl AstToken for Caret {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -802,7 +949,9 @@ impl std::fmt::Display for Lshift {
     }
 }
 impl AstToken for Lshift {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LSHIFT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LSHIFT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -810,7 +959,9 @@ impl AstToken for Lshift {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -823,7 +974,9 @@ impl std::fmt::Display for Rshift {
     }
 }
 impl AstToken for Rshift {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RSHIFT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RSHIFT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -831,7 +984,9 @@ impl AstToken for Rshift {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -844,7 +999,9 @@ impl std::fmt::Display for Dot {
     }
 }
 impl AstToken for Dot {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == DOT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DOT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -852,7 +1009,9 @@ impl AstToken for Dot {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -865,7 +1024,9 @@ impl std::fmt::Display for Comma {
     }
 }
 impl AstToken for Comma {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == COMMA }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == COMMA
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -873,7 +1034,9 @@ impl AstToken for Comma {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -886,7 +1049,9 @@ impl std::fmt::Display for Eq {
     }
 }
 impl AstToken for Eq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == EQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == EQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -894,7 +1059,9 @@ impl AstToken for Eq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -907,7 +1074,9 @@ impl std::fmt::Display for Semicolon {
     }
 }
 impl AstToken for Semicolon {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SEMICOLON }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SEMICOLON
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -915,7 +1084,9 @@ impl AstToken for Semicolon {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -928,7 +1099,9 @@ impl std::fmt::Display for Colon {
     }
 }
 impl AstToken for Colon {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == COLON }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == COLON
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -936,7 +1109,9 @@ impl AstToken for Colon {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -949,7 +1124,9 @@ impl std::fmt::Display for Lparen {
     }
 }
 impl AstToken for Lparen {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LPAREN }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LPAREN
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -957,7 +1134,9 @@ impl AstToken for Lparen {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -970,7 +1149,9 @@ impl std::fmt::Display for Rparen {
     }
 }
 impl AstToken for Rparen {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RPAREN }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RPAREN
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -978,7 +1159,9 @@ impl AstToken for Rparen {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -991,7 +1174,9 @@ impl std::fmt::Display for Lbracket {
     }
 }
 impl AstToken for Lbracket {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LBRACKET }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LBRACKET
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -999,7 +1184,9 @@ impl AstToken for Lbracket {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1012,7 +1199,9 @@ impl std::fmt::Display for Rbracket {
     }
 }
 impl AstToken for Rbracket {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RBRACKET }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RBRACKET
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1020,7 +1209,9 @@ impl AstToken for Rbracket {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1033,7 +1224,9 @@ impl std::fmt::Display for Lbrace {
     }
 }
 impl AstToken for Lbrace {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LBRACE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LBRACE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1041,7 +1234,9 @@ impl AstToken for Lbrace {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1054,7 +1249,9 @@ impl std::fmt::Display for Rbrace {
     }
 }
 impl AstToken for Rbrace {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RBRACE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RBRACE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1062,7 +1259,9 @@ impl AstToken for Rbrace {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1075,7 +1274,9 @@ impl std::fmt::Display for Lt {
     }
 }
 impl AstToken for Lt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1083,7 +1284,9 @@ impl AstToken for Lt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1096,7 +1299,9 @@ impl std::fmt::Display for Gt {
     }
 }
 impl AstToken for Gt {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == GT }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == GT
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1104,7 +1309,9 @@ impl AstToken for Gt {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1117,7 +1324,9 @@ impl std::fmt::Display for Ge {
     }
 }
 impl AstToken for Ge {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == GE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == GE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1125,7 +1334,9 @@ impl AstToken for Ge {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1138,7 +1349,9 @@ impl std::fmt::Display for Le {
     }
 }
 impl AstToken for Le {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1146,7 +1359,9 @@ impl AstToken for Le {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1159,7 +1374,9 @@ impl std::fmt::Display for Eqeq {
     }
 }
 impl AstToken for Eqeq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == EQEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == EQEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1167,7 +1384,9 @@ impl AstToken for Eqeq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1180,7 +1399,9 @@ impl std::fmt::Display for Ne {
     }
 }
 impl AstToken for Ne {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == NE }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == NE
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1188,7 +1409,9 @@ impl AstToken for Ne {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1201,7 +1424,9 @@ impl std::fmt::Display for Pluseq {
     }
 }
 impl AstToken for Pluseq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PLUSEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PLUSEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1209,7 +1434,9 @@ impl AstToken for Pluseq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1222,7 +1449,9 @@ impl std::fmt::Display for Minuseq {
     }
 }
 impl AstToken for Minuseq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == MINUSEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == MINUSEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1230,7 +1459,9 @@ impl AstToken for Minuseq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1243,7 +1474,9 @@ impl std::fmt::Display for Stareq {
     }
 }
 impl AstToken for Stareq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == STAREQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == STAREQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1251,7 +1484,9 @@ impl AstToken for Stareq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1264,7 +1499,34 @@ impl std::fmt::Display for Slasheq {
     }
 }
 impl AstToken for Slasheq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == SLASHEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == SLASHEQ
+    }
+    fn cast(syntax: SyntaxToken) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dslasheq {
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for Dslasheq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for Dslasheq {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DSLASHEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1272,7 +1534,9 @@ impl AstToken for Slasheq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1285,7 +1549,9 @@ impl std::fmt::Display for Percenteq {
     }
 }
 impl AstToken for Percenteq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PERCENTEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PERCENTEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1293,7 +1559,9 @@ impl AstToken for Percenteq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1306,7 +1574,9 @@ impl std::fmt::Display for Ampeq {
     }
 }
 impl AstToken for Ampeq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == AMPEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == AMPEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1314,7 +1584,9 @@ impl AstToken for Ampeq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1327,7 +1599,9 @@ impl std::fmt::Display for Pipeeq {
     }
 }
 impl AstToken for Pipeeq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == PIPEEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == PIPEEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1335,7 +1609,9 @@ impl AstToken for Pipeeq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1348,7 +1624,9 @@ impl std::fmt::Display for Careteq {
     }
 }
 impl AstToken for Careteq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == CARETEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CARETEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1356,7 +1634,9 @@ impl AstToken for Careteq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1369,7 +1649,9 @@ impl std::fmt::Display for Lshifteq {
     }
 }
 impl AstToken for Lshifteq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == LSHIFTEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LSHIFTEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1377,7 +1659,9 @@ impl AstToken for Lshifteq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1390,7 +1674,9 @@ impl std::fmt::Display for Rshifteq {
     }
 }
 impl AstToken for Rshifteq {
-    fn can_cast(kind: SyntaxKind) -> bool { kind == RSHIFTEQ }
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RSHIFTEQ
+    }
     fn cast(syntax: SyntaxToken) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
             Some(Self { syntax })
@@ -1398,5 +1684,32 @@ impl AstToken for Rshifteq {
             None
         }
     }
-    fn syntax(&self) -> &SyntaxToken { &self.syntax }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThinArrow {
+    pub(crate) syntax: SyntaxToken,
+}
+impl std::fmt::Display for ThinArrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.syntax, f)
+    }
+}
+impl AstToken for ThinArrow {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == THIN_ARROW
+    }
+    fn cast(syntax: SyntaxToken) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxToken {
+        &self.syntax
+    }
 }
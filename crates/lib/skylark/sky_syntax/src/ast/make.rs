@@ -0,0 +1,392 @@
+//! **Factory functions** for constructing **well-formed** AST nodes, so that
+//! tools generating Starlark (e.g. `BUILD` files) can do so through the AST
+//! rather than by formatting strings.
+//!
+//! Much like rust-analyzer's `make` module, every node is built by rendering
+//! its **canonical text** and parsing it, guaranteeing that the resulting tree
+//! is exactly the one the parser would produce for that text. The nodes passed
+//! in are copied by text, with any **leading whitespace** stripped, so that
+//! nodes taken from an existing tree can be freely reused.
+//!
+//! All functions **panic** if given an invalid identifier (or a node which
+//! doesn't parse in the given position), as that's a bug in the caller.
+//!
+//! # Example
+//!
+//! ```
+//! use sky_syntax::ast::{make, AstNode};
+//!
+//! let call = make::call_expr(
+//!     make::name_expr("cc_library"),
+//!     [
+//!         make::keyword_argument("name", make::string_expr("foo").into()),
+//!         make::keyword_argument(
+//!             "srcs",
+//!             make::list_expr([make::string_expr("foo.cc").into()]).into(),
+//!         ),
+//!     ],
+//! );
+//!
+//! assert_eq!(
+//!     call.syntax().to_string(),
+//!     r#"cc_library(name = "foo", srcs = ["foo.cc"])"#
+//! );
+//! ```
+
+use itertools::Itertools;
+
+use crate::{
+    ast::{self, AstNode},
+    File, SyntaxKind, T,
+};
+
+/// The indentation used for the bodies of compound statements.
+const INDENT: &str = "    ";
+
+/// An **identifier** expression (e.g. `foo`).
+pub fn name_expr(name: &str) -> ast::PrimaryExpr {
+    assert_identifier(name);
+    expr_from_text(name)
+}
+
+/// A **string literal** token for the given value, **quoted** and **escaped**
+/// as needed (e.g. `"foo\n"`).
+pub fn string_literal(value: &str) -> ast::String {
+    let text = format!("{}\n", quote(value));
+    let parse = File::parse(&text);
+    assert!(
+        parse.errors().is_empty(),
+        "Failed to make a string literal from {value:?}"
+    );
+
+    parse
+        .syntax_node()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find_map(<ast::String as ast::AstToken>::cast)
+        .unwrap()
+}
+
+/// A **string literal** expression for the given value (e.g. `"foo"`).
+pub fn string_expr(value: &str) -> ast::PrimaryExpr {
+    expr_from_text(&quote(value))
+}
+
+/// An **integer literal** expression (e.g. `42`).
+///
+/// Negative values are rendered as a **unary** `-` applied to the literal,
+/// as that's how they're parsed.
+pub fn int_expr(value: i64) -> ast::Test {
+    expr_from_text(&value.to_string())
+}
+
+/// A `True` or `False` expression.
+pub fn bool_expr(value: bool) -> ast::PrimaryExpr {
+    expr_from_text(if value { "True" } else { "False" })
+}
+
+/// A **list** expression of the given elements (e.g. `[a, b]`).
+pub fn list_expr(elements: impl IntoIterator<Item = ast::Test>) -> ast::PrimaryExpr {
+    expr_from_text(&format!("[{}]", join(elements)))
+}
+
+/// A **dict** expression of the given entries (e.g. `{a: b}`).
+pub fn dict_expr(entries: impl IntoIterator<Item = ast::Entry>) -> ast::PrimaryExpr {
+    expr_from_text(&format!("{{{}}}", join(entries)))
+}
+
+/// A **dict entry** (e.g. `a: b`).
+pub fn entry(key: ast::Test, value: ast::Test) -> ast::Entry {
+    expr_from_text(&format!("{{{}: {}}}", text(&key), text(&value)))
+}
+
+/// A **call** of the given callee with the given arguments (e.g. `f(a, b = c)`).
+pub fn call_expr(
+    callee: ast::PrimaryExpr,
+    arguments: impl IntoIterator<Item = ast::Argument>,
+) -> ast::PrimaryExpr {
+    expr_from_text(&format!("{}({})", text(&callee), join(arguments)))
+}
+
+/// A **positional** argument (e.g. `a`).
+pub fn argument(value: ast::Test) -> ast::Argument {
+    expr_from_text(&format!("f({})", text(&value)))
+}
+
+/// A **keyword** argument (e.g. `name = value`).
+pub fn keyword_argument(name: &str, value: ast::Test) -> ast::Argument {
+    assert_identifier(name);
+    expr_from_text(&format!("f({name} = {})", text(&value)))
+}
+
+/// A **dot** access of the given attribute (e.g. `a.b`).
+pub fn dot_expr(receiver: ast::PrimaryExpr, name: &str) -> ast::PrimaryExpr {
+    assert_identifier(name);
+    expr_from_text(&format!("{}.{name}", text(&receiver)))
+}
+
+/// A **parameter** without a default value (e.g. `a`).
+pub fn parameter(name: &str) -> ast::Parameter {
+    assert_identifier(name);
+    parameter_from_text(name)
+}
+
+/// A **parameter** with a default value (e.g. `a = 1`).
+pub fn default_parameter(name: &str, default: ast::Test) -> ast::Parameter {
+    assert_identifier(name);
+    parameter_from_text(&format!("{name} = {}", text(&default)))
+}
+
+/// A **variadic** parameter (e.g. `*args`).
+pub fn args_parameter(name: &str) -> ast::Parameter {
+    assert_identifier(name);
+    parameter_from_text(&format!("*{name}"))
+}
+
+/// A **keyword variadic** parameter (e.g. `**kwargs`).
+pub fn kwargs_parameter(name: &str) -> ast::Parameter {
+    assert_identifier(name);
+    parameter_from_text(&format!("**{name}"))
+}
+
+/// An **expression statement** (e.g. `f(a)`).
+pub fn expr_stmt(expr: ast::Test) -> ast::Statement {
+    statement_from_text(&format!("{}\n", text(&expr)))
+}
+
+/// An **assignment** (e.g. `a = b`).
+pub fn assign_stmt(lhs: ast::Test, rhs: ast::Test) -> ast::Statement {
+    statement_from_text(&format!("{} = {}\n", text(&lhs), text(&rhs)))
+}
+
+/// A `return` statement, with an optional value.
+pub fn return_stmt(value: Option<ast::Test>) -> ast::Statement {
+    match value {
+        Some(value) => statement_from_text(&format!("return {}\n", text(&value))),
+        None => statement_from_text("return\n"),
+    }
+}
+
+/// A `pass` statement.
+pub fn pass_stmt() -> ast::Statement {
+    statement_from_text("pass\n")
+}
+
+/// A **load symbol**, optionally bound to an alias (e.g. `"foo"` or
+/// `bar = "foo"`).
+pub fn load_symbol(alias: Option<&str>, symbol: &str) -> ast::LoadSymbol {
+    let symbol = quote(symbol);
+    let text = match alias {
+        Some(alias) => {
+            assert_identifier(alias);
+            format!("load(\"\",{alias} = {symbol})\n")
+        }
+        None => format!("load(\"\",{symbol})\n"),
+    };
+
+    node_from_text(&text)
+}
+
+/// A `load` statement of the given symbols from the given module (e.g.
+/// `load("//foo:bar.bzl", "baz")`).
+pub fn load_stmt(
+    module: &str,
+    symbols: impl IntoIterator<Item = ast::LoadSymbol>,
+) -> ast::Statement {
+    let symbols = symbols
+        .into_iter()
+        .map(|symbol| format!(", {}", text(&symbol)))
+        .collect::<String>();
+
+    statement_from_text(&format!("load({}{symbols})\n", quote(module)))
+}
+
+/// A **function definition** with the given parameters and body. An **empty**
+/// body is rendered as a single `pass` statement.
+pub fn def_stmt(
+    name: &str,
+    parameters: impl IntoIterator<Item = ast::Parameter>,
+    body: impl IntoIterator<Item = ast::Statement>,
+) -> ast::Statement {
+    assert_identifier(name);
+
+    let mut body = body.into_iter().peekable();
+    let body = if body.peek().is_none() {
+        indent(&pass_stmt())
+    } else {
+        body.map(|statement| indent(&statement)).collect()
+    };
+
+    statement_from_text(&format!("def {name}({}):\n{body}", join(parameters)))
+}
+
+/// A **file** of the given statements.
+pub fn file(statements: impl IntoIterator<Item = ast::Statement>) -> File {
+    let text = statements
+        .into_iter()
+        .map(|statement| text(&statement).to_string())
+        .collect::<String>();
+
+    node_from_text(&text)
+}
+
+/// Returns the **text** of the given node, without any leading whitespace.
+fn text(node: &impl AstNode) -> String {
+    node.syntax().to_string().trim_start().to_string()
+}
+
+/// Joins the **text** of the given nodes with commas.
+fn join<N: AstNode>(nodes: impl IntoIterator<Item = N>) -> String {
+    nodes.into_iter().map(|node| text(&node)).join(", ")
+}
+
+/// **Indents** every line of the given statement (without its leading
+/// whitespace), except for those within (triple-quoted) string literals,
+/// whose values would otherwise change.
+fn indent(statement: &ast::Statement) -> String {
+    let mut buf = String::new();
+    let mut at_line_start = true;
+
+    for token in statement
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .skip_while(|token| matches!(token.kind(), T![whitespace] | T![indent]))
+    {
+        let text = token.text();
+        if matches!(token.kind(), T![string] | T![bytes]) {
+            if at_line_start {
+                buf.push_str(INDENT);
+            }
+            buf.push_str(text);
+            at_line_start = false;
+            continue;
+        }
+
+        for c in text.chars() {
+            if at_line_start && c != '\n' {
+                buf.push_str(INDENT);
+            }
+            buf.push(c);
+            at_line_start = c == '\n';
+        }
+    }
+
+    buf
+}
+
+/// **Quotes** the given value as a double-quoted string literal, escaping
+/// any characters which can't appear in one verbatim.
+fn quote(value: &str) -> String {
+    let mut buf = String::with_capacity(value.len() + 2);
+    buf.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c.is_ascii_control() => buf.push_str(&format!("\\x{:02x}", c as u32)),
+            c if c.is_control() => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+/// Whether the given name is a valid **identifier**, i.e. neither empty nor
+/// a keyword, and made up of only ASCII letters, digits and underscores (not
+/// starting with a digit), as lexed.
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        && SyntaxKind::from_keyword(name).is_none()
+}
+
+fn assert_identifier(name: &str) {
+    assert!(
+        is_identifier(name),
+        "Expected an identifier, but got {name:?}"
+    );
+}
+
+/// Parses the given **expression**, returning its **outermost** node of type
+/// `N`.
+fn expr_from_text<N: AstNode>(text: &str) -> N {
+    node_from_text(&format!("{text}\n"))
+}
+
+fn parameter_from_text(text: &str) -> ast::Parameter {
+    node_from_text(&format!("def f({text}):\n    pass\n"))
+}
+
+fn statement_from_text(text: &str) -> ast::Statement {
+    node_from_text(text)
+}
+
+/// Parses the given **source**, returning its **first** node of type `N`
+/// (in preorder) as the **root** of a new tree.
+fn node_from_text<N: AstNode>(text: &str) -> N {
+    let parse = File::parse(text);
+    assert!(
+        parse.errors().is_empty(),
+        "Failed to make {} from {text:?}: {:?}",
+        std::any::type_name::<N>(),
+        parse.errors()
+    );
+
+    let node = parse
+        .syntax_node()
+        .descendants()
+        .find_map(N::cast)
+        .unwrap_or_else(|| {
+            panic!(
+                "Failed to make {} from {text:?}",
+                std::any::type_name::<N>()
+            )
+        });
+
+    N::cast(node.syntax().clone_subtree()).unwrap()
+}
+
+/// Factory functions for **tokens**, e.g. for the separators and trivia
+/// inserted when editing a tree in place.
+pub mod tokens {
+    use rowan::{GreenNode, GreenToken, NodeOrToken};
+
+    use super::assert_identifier;
+    use crate::{SyntaxKind, SyntaxNode, SyntaxToken, T};
+
+    /// A `,` token.
+    pub fn comma() -> SyntaxToken {
+        token(T![,], ",")
+    }
+
+    /// An **identifier** token.
+    pub fn identifier(name: &str) -> SyntaxToken {
+        assert_identifier(name);
+        token(T![identifier], name)
+    }
+
+    /// A **whitespace** token, e.g. `" "` or `"\n    "`.
+    pub fn whitespace(text: &str) -> SyntaxToken {
+        assert!(
+            !text.is_empty() && text.chars().all(char::is_whitespace),
+            "Expected whitespace, but got {text:?}"
+        );
+        token(T![whitespace], text)
+    }
+
+    fn token(kind: SyntaxKind, text: &str) -> SyntaxToken {
+        let raw = |kind: SyntaxKind| rowan::SyntaxKind(u16::from(kind));
+        let green = GreenNode::new(
+            raw(SyntaxKind::ERROR),
+            [NodeOrToken::Token(GreenToken::new(raw(kind), text))],
+        );
+
+        SyntaxNode::new_root(green).first_token().unwrap()
+    }
+}
@@ -1,10 +1,15 @@
 pub(crate) mod ext;
 pub(crate) mod generated;
+pub mod make;
 pub(crate) mod traits;
 
 use crate::lang::{SyntaxNode, SyntaxNodeChildren, SyntaxToken};
 use dyn_clone::DynClone;
 use either::Either;
+pub use ext::{
+    edit::ElementComments,
+    operators::{BinaryOp, UnaryOp},
+};
 pub use generated::{kinds::*, nodes::*, tokens::*};
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -45,28 +50,26 @@ pub trait AstNode: DynClone + Debug {
     ///
     /// Returns a **new** [`AstNode`] with the **same** [`SyntaxNode`].
     ///
-    /// # Example
+    /// Mutable trees can be edited in place, either through the **typed** editing API
+    /// (e.g. [`CallSuffix::set_keyword_argument`]), or through the primitive
+    /// operations in [`ted`][crate::ted].
     ///
-    /// ```rust,ignore
-    /// // Create a new node.
-    /// let node: SomeAstNode = ...;
-    ///
-    /// // Clone the node for update.
-    /// let updated_node = node.clone_for_update();
+    /// # Example
     ///
-    /// // Now, you can update properties on the `updated_node` without affecting
-    /// // the original `node`.
+    /// ```rust
+    /// use sky_syntax::{ast::{self, make, AstNode}, File};
     ///
-    /// // For example, you can update the text of the node.
-    /// updated_node.set_text("new text");
+    /// let file = File::parse("cc_library(name = \"foo\")\n").tree();
     ///
-    /// // Or, you can add a child to the node.
-    /// updated_node.add_child(SomeAstNode::new());
+    /// // Clone the file for update.
+    /// let updated = file.clone_for_update();
     ///
-    /// // This is the cornerstone for the immutability of the AST. You can
-    /// // update the AST without affecting the original AST.
+    /// // Now, you can update the `updated` file without affecting the original `file`.
+    /// let call = updated.syntax().descendants().find_map(ast::CallSuffix::cast).unwrap();
+    /// call.set_keyword_argument("srcs", make::list_expr([]).into());
     ///
-    /// assert!(!original_node.text().contains("new text"));
+    /// assert_eq!(updated.syntax().to_string(), "cc_library(name = \"foo\", srcs = [])\n");
+    /// assert_eq!(file.syntax().to_string(), "cc_library(name = \"foo\")\n");
     /// ```
     fn clone_for_update(&self) -> Self
     where
@@ -285,4 +288,33 @@ mod support {
             .filter_map(|it| it.into_token())
             .find(|it| it.kind() == kind)
     }
+
+    /// Returns the **first child** with the **specified AST type `N`** after
+    /// exactly `count` separator tokens of the given kind, e.g. the end of a
+    /// slice, which is the first test after the first colon.
+    pub(super) fn child_after<N: AstNode>(
+        parent: &SyntaxNode,
+        separator: SyntaxKind,
+        count: usize,
+    ) -> Option<N> {
+        let mut seen = 0;
+        for element in parent.children_with_tokens() {
+            match element {
+                rowan::NodeOrToken::Token(token) if token.kind() == separator => {
+                    seen += 1;
+                    if seen > count {
+                        return None;
+                    }
+                }
+                rowan::NodeOrToken::Node(node) if seen == count => {
+                    if let Some(node) = N::cast(node) {
+                        return Some(node);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
 }
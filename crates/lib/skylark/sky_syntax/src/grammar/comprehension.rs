@@ -1,6 +1,13 @@
-use crate::{parser::Parser, SyntaxKind::*, T};
+use crate::{
+    parser::{Marker, Parser},
+    SyntaxKind::*,
+    T,
+};
 
-use super::{decl, expr};
+use super::{
+    decl,
+    expr::{self, expect_closing_delimiter},
+};
 
 /// A **list comprehension expression**. In Starlark, **list comprehension
 /// expressions** are shorthand for creating a list in a _terse_ manner.
@@ -11,7 +18,7 @@ use super::{decl, expr};
 /// ## Ungrammar
 ///
 /// ```
-/// ListComp = '[' Test CompClause* ']'
+/// ListComp = '[' element:Test CompClause* ']'
 /// ```
 ///
 /// ## Examples
@@ -19,17 +26,17 @@ use super::{decl, expr};
 /// ```starlark
 /// [x * 2 for x in range(10) if x % 2 == 0]
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn list_comp(p: &mut Parser) {
+///
+/// Parses the rest of the comprehension, once its element has been parsed
+/// (see [`expr::list`]), given the marker started before the opening bracket.
+#[tracing::instrument(level = "debug", skip(p, m))]
+pub(super) fn list_comp(p: &mut Parser, m: Marker) {
     tracing::debug!("Parsing list comprehension expression");
-    let m = p.start();
 
-    p.expect(T!['[']);
-    expr::test(p);
     while p.at(T![for]) || p.at(T![if]) {
         comp_clause(p);
     }
-    p.expect(T![']']);
+    expect_closing_delimiter(p, T![']']);
 
     tracing::debug!("Finished parsing list comprehension expression");
     m.complete(p, LIST_COMP);
@@ -52,22 +59,28 @@ pub(super) fn list_comp(p: &mut Parser) {
 /// ```starlark
 /// {"x": 1, "y": 2}
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn dict_expr(p: &mut Parser) {
+///
+/// Parses the rest of the dictionary, once its first entry has been parsed
+/// (see [`expr::dict`]), given the markers started before the opening brace
+/// and the first entry.
+#[tracing::instrument(level = "debug", skip(p, m, first))]
+pub(super) fn dict_expr(p: &mut Parser, m: Marker, first: Marker) {
     tracing::debug!("Parsing dictionary expression");
-    let m = p.start();
-    p.expect(T!['{']);
 
-    if !p.at(T!['}']) {
-        entries(p);
-        while p.eat(T![,]) {
-            if p.at(T!['}']) {
-                break;
-            }
-            entries(p);
+    // The rest of the first entries, as in `entries`.
+    while p.at(T![,]) && !p.nth_at(1, T!['}']) {
+        p.bump(T![,]);
+        entry(p);
+    }
+    first.complete(p, ENTRIES);
+
+    while p.eat(T![,]) {
+        if p.at(T!['}']) {
+            break;
         }
+        entries(p);
     }
-    p.expect(T!['}']);
+    expect_closing_delimiter(p, T!['}']);
 
     tracing::debug!("Finished parsing dictionary expression");
     m.complete(p, DICT_EXPR);
@@ -89,19 +102,17 @@ pub(super) fn dict_expr(p: &mut Parser) {
 /// ```starlark
 /// {x: x * 2 for x in range(10) if x % 2 == 0}
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn dict_comp(p: &mut Parser) {
+///
+/// Parses the rest of the comprehension, once its entry has been parsed (see
+/// [`expr::dict`]), given the marker started before the opening brace.
+#[tracing::instrument(level = "debug", skip(p, m))]
+pub(super) fn dict_comp(p: &mut Parser, m: Marker) {
     tracing::debug!("Parsing dictionary comprehension expression");
-    let m = p.start();
-
-    p.expect(T!['{']);
-    entry(p);
 
     while p.at(T![for]) || p.at(T![if]) {
         comp_clause(p);
     }
-
-    p.expect(T!['}']);
+    expect_closing_delimiter(p, T!['}']);
 
     tracing::debug!("Finished parsing dictionary comprehension expression");
     m.complete(p, DICT_COMP);
@@ -128,7 +139,8 @@ pub(super) fn entries(p: &mut Parser) {
     let m = p.start();
 
     entry(p);
-    while p.eat(T![,]) {
+    while p.at(T![,]) && !p.nth_at(1, T!['}']) {
+        p.bump(T![,]);
         entry(p);
     }
 
@@ -144,7 +156,7 @@ pub(super) fn entries(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// Entry = Test ':' Test
+/// Entry = key:Test ':' value:Test
 /// ```
 ///
 /// ## Examples
@@ -173,7 +185,7 @@ pub(super) fn entry(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// CompClause = 'for' LoopVariables 'in' Test | 'if' Test
+/// CompClause = 'for' LoopVariables 'in' iterable:Test | 'if' condition:Test
 /// ```
 ///
 /// ## Examples
@@ -192,11 +204,11 @@ pub(super) fn comp_clause(p: &mut Parser) {
         p.bump(T![for]);
         decl::loop_variables(p);
         p.expect(T![in]);
-        expr::test(p);
+        expr::test_no_cond(p);
         m.complete(p, COMP_CLAUSE);
     } else if p.at(T![if]) {
         p.bump(T![if]);
-        expr::test(p);
+        expr::test_no_cond(p);
         m.complete(p, COMP_CLAUSE);
     } else {
         // p.error("expected 'for' or 'if'");
@@ -2,9 +2,6 @@ use crate::{parser::Parser, SyntaxKind::*, TokenSet, T};
 
 use super::{expr, statements};
 
-pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![def], T![load], T![if], T![for], T![in], T![return]]);
-
 /// Represents **one or more** variables in a **loop statement**.
 ///
 /// See [`expr::primary_expr`] for more information.
@@ -68,14 +65,21 @@ pub(super) fn suite(p: &mut Parser) {
     if p.at(T![newline]) {
         tracing::debug!("Found newline. Bumping to next token");
         p.bump(T![newline]);
-        p.expect(T![indent]);
 
-        while !p.at(T![outdent]) {
-            tracing::debug!("Not reached outdent yet. Parsing statement");
-            statements::statement(p);
-        }
+        // Without an indentation, the following statements belong to the
+        // enclosing block instead.
+        if p.expect(T![indent]) {
+            while !p.at(T![outdent]) && !p.at(EOF) {
+                tracing::debug!("Not reached outdent yet. Parsing statement");
+                if p.at(T![indent]) {
+                    unexpected_block(p);
+                } else {
+                    statements::statement(p);
+                }
+            }
 
-        p.expect(T![outdent]);
+            p.expect(T![outdent]);
+        }
     } else {
         tracing::debug!("No newline found. Parsing simple statement");
         statements::simple_stmt(p);
@@ -84,3 +88,26 @@ pub(super) fn suite(p: &mut Parser) {
     tracing::debug!("Finished parsing suite");
     m.complete(p, SUITE);
 }
+
+/// An **unexpected indented block**, e.g. following an over-indented line,
+/// which is reported and wrapped in an `ERROR` node along with its statements
+/// (keeping its `indent` and `outdent` together).
+pub(super) fn unexpected_block(p: &mut Parser) {
+    tracing::debug!("Parsing unexpected block. Current token: {:?}", p.current());
+    assert!(p.at(T![indent])); // precondition (enforced by caller)
+    let m = p.start();
+
+    p.error(p.unexpected(TokenSet::EMPTY));
+    p.bump(T![indent]);
+    while !p.at(T![outdent]) && !p.at(EOF) {
+        if p.at(T![indent]) {
+            unexpected_block(p);
+        } else {
+            statements::statement(p);
+        }
+    }
+    p.eat(T![outdent]);
+
+    tracing::debug!("Finished parsing unexpected block");
+    m.complete(p, ERROR);
+}
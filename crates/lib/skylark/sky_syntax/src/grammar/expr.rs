@@ -1,39 +1,63 @@
 use super::{
-    comprehension::{dict_comp, dict_expr, list_comp},
-    statements::{self, PARAM_START},
+    comprehension::{dict_comp, dict_expr, entry, list_comp},
+    statements::{self, PARAM_START, STATEMENT_RECOVERY_SET},
 };
 use crate::{
-    lexer::{Span, Token},
-    parser::{ParseError, Parser},
-    SyntaxKind::*,
+    parser::{CompletedMarker, Marker, Parser},
+    SyntaxKind::{self, *},
     TokenSet, T,
 };
 
-pub static UNARY_OP: TokenSet = TokenSet::new(&[T![not], T![-], T![+], T![~]]);
-
-pub const BIN_OP: TokenSet = TokenSet::new(&[
-    T![+],
-    T![-],
-    T![*],
-    T![/],
-    T![dslash],
-    T![%],
-    T![**],
-    T![<<],
-    T![>>],
-    T![&],
-    T![|],
-    T![^],
-    T![<],
-    T![>],
-    T![<=],
-    T![>=],
-    T![==],
-    T![!=],
+/// The tokens at which to resume parsing after an **invalid expression**,
+/// i.e. those which commonly follow an expression (or begin a statement).
+pub(super) const EXPR_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![newline],
+    T![;],
+    T![def],
+    T![if],
+    T![for],
     T![in],
-    T![not],
+    T![,],
+    T![:],
+    T![=],
+    T![')'],
+    T![']'],
+    T!['}'],
 ]);
 
+/// The tokens at which to resume parsing after **unexpected tokens** before a
+/// closing bracket. As newlines are insignificant within brackets, a missing
+/// closing bracket leaves the following statements within them, so the start
+/// of a statement is a synchronisation point as well.
+const CLOSING_DELIMITER_RECOVERY_SET: TokenSet = STATEMENT_RECOVERY_SET.union(CLOSING_DELIMITERS);
+
+const CLOSING_DELIMITERS: TokenSet = TokenSet::new(&[T![')'], T![']'], T!['}']]);
+
+const OPERAND_START: TokenSet = TokenSet::new(&[
+    T![identifier],
+    T![int],
+    T![float],
+    T![string],
+    T![bytes],
+    T!['('],
+    T!['['],
+    T!['{'],
+]);
+
+/// The **prefix operators** binding tighter than any binary operator (as
+/// opposed to `not`, see [`binary_op`]).
+const UNARY_OP: TokenSet = TokenSet::new(&[T![-], T![+], T![~]]);
+
+/// The lowest precedence of any operator, i.e. that of `or`.
+const PREC_OR: u8 = 1;
+/// The precedence of the unary `not` operator.
+const PREC_NOT: u8 = 3;
+/// The precedence of the (non-associative) comparison operators.
+const PREC_COMPARISON: u8 = 4;
+/// The precedence of the unary `-`, `+` and `~` operators, binding tighter than
+/// any binary operator.
+const PREC_UNARY: u8 = 11;
+
 /// An **expression**. In Starlark, **expressions** are
 /// **comma-separated lists** of [`test`]'s. **Expressions** are the
 /// **basis** of **statements** and **comprehensions**.
@@ -59,7 +83,9 @@ pub(super) fn expression(p: &mut Parser) {
     let m = p.start();
 
     test(p);
-    while p.eat(T![,]) {
+    // A trailing comma is left to the enclosing brackets
+    while p.at(T![,]) && !CLOSING_DELIMITERS.contains(p.nth(1)) {
+        p.bump(T![,]);
         test(p);
     }
 
@@ -96,19 +122,33 @@ pub(super) fn test(p: &mut Parser) {
     let m = p.start();
     tracing::debug!("Parsing test. Current token: {:?}", p.current());
 
-    if p.at(T![if]) {
-        if_expr(p);
-    } else if p.at(T![lambda]) {
+    if p.at(T![lambda]) {
         lambda_expr(p);
-    } else if p.at_ts(UNARY_OP) {
-        unary_expr(p);
     } else {
-        primary_expr(p);
+        binary_expr(p, PREC_OR);
     }
 
-    // TODO: Add support for binary expressions
-
     tracing::debug!("Completed test");
+    let test = m.complete(p, TEST);
+
+    // An if expression only becomes apparent after its first operand
+    if p.at(T![if]) {
+        if_expr(p, test).precede(p).complete(p, TEST);
+    }
+}
+
+/// A [`test`] which isn't an _inline_ [`if_expr`], as in the clauses of
+/// comprehensions (e.g. `xs` in `[x for x in xs if x]`), where an `if`
+/// starts the next clause instead.
+pub(super) fn test_no_cond(p: &mut Parser) {
+    let m = p.start();
+
+    if p.at(T![lambda]) {
+        lambda_expr(p);
+    } else {
+        binary_expr(p, PREC_OR);
+    }
+
     m.complete(p, TEST);
 }
 
@@ -135,8 +175,7 @@ pub(super) fn test(p: &mut Parser) {
 /// x.y
 /// x.foo()
 /// ```
-pub(super) fn primary_expr(p: &mut Parser) {
-    // TODO: This is not correct.
+pub(super) fn primary_expr(p: &mut Parser) -> CompletedMarker {
     let m = p.start();
 
     operand(p);
@@ -152,7 +191,7 @@ pub(super) fn primary_expr(p: &mut Parser) {
         }
     }
 
-    m.complete(p, PRIMARY_EXPR);
+    m.complete(p, PRIMARY_EXPR)
 }
 
 /// An **operand** in an **expression**. In Starlark, **operands** are
@@ -199,74 +238,76 @@ pub(super) fn operand(p: &mut Parser) {
                 expression(p);
                 p.eat(T![,]);
             }
-            p.expect(T![')']);
-        }
-        T!['['] => {
-            tracing::debug!(
-                "Parsing list expression or comprehension. Current token: {:?}",
-                p.current()
-            );
-            if p.nth_at(1, T![for]) {
-                tracing::debug!("Parsing list comprehension");
-                list_comp(p);
-            } else {
-                tracing::debug!("Parsing list expression");
-                list_expr(p);
-            }
-        }
-        T!['{'] => {
-            if p.nth_at(1, T![for]) {
-                tracing::debug!("Parsing dict comprehension");
-                dict_comp(p);
-            } else {
-                tracing::debug!("Parsing dict expression");
-                dict_expr(p);
-            }
-        }
-        _ => {
-            p.error(ParseError::UnexpectedToken {
-                expected: TokenSet::new(&[
-                    T![identifier],
-                    T![int],
-                    T![float],
-                    T![string],
-                    T![bytes],
-                    T!['('],
-                    T!['['],
-                    T!['{'],
-                ]),
-                found: Token::new(
-                    p.current().tk(),
-                    String::from("expected an operand"),
-                    Span::new(0, 0),
-                    // p.current().text.clone(),
-                    // p.current().span
-                ),
-            });
-            // p.err_recover("operand error", |p| {
-            //     p.at(T![identifier])
-            //         || p.at(T![int])
-            //         || p.at(T![float])
-            //         || p.at(T![string])
-            //         || p.at(T![bytes])
-            //         || p.at(T!['('])
-            //         || p.at(T!['['])
-            //         || p.at(T!['{'])
-            // });
+            expect_closing_delimiter(p, T![')']);
         }
+        T!['['] => list(p),
+        T!['{'] => dict(p),
+        _ => p.error_recover(p.unexpected(OPERAND_START), EXPR_RECOVERY_SET),
     }
 
     tracing::debug!("Finished parsing operand");
     m.complete(p, OPERAND);
 }
 
+/// Either a [`list_expr`] or a [`list_comp`], depending on whether the first
+/// element is followed by a `for` clause.
+pub(super) fn list(p: &mut Parser) {
+    tracing::debug!(
+        "Parsing list expression or comprehension. Current token: {:?}",
+        p.current()
+    );
+    let m = p.start();
+    p.expect(T!['[']);
+    if p.at(T![']']) {
+        expect_closing_delimiter(p, T![']']);
+        m.complete(p, LIST_EXPR);
+        return;
+    }
+
+    // The elements of a list expression are wrapped in an expression, while
+    // the element of a comprehension isn't.
+    let elements = p.start();
+    test(p);
+    if p.at(T![for]) {
+        tracing::debug!("Parsing list comprehension");
+        elements.abandon(p);
+        list_comp(p, m);
+    } else {
+        tracing::debug!("Parsing list expression");
+        list_expr(p, m, elements);
+    }
+}
+
+/// Either a [`dict_expr`] or a [`dict_comp`], depending on whether the first
+/// entry is followed by a `for` clause.
+pub(super) fn dict(p: &mut Parser) {
+    let m = p.start();
+    p.expect(T!['{']);
+    if p.at(T!['}']) {
+        expect_closing_delimiter(p, T!['}']);
+        m.complete(p, DICT_EXPR);
+        return;
+    }
+
+    let entries = p.start();
+    entry(p);
+    if p.at(T![for]) {
+        tracing::debug!("Parsing dict comprehension");
+        entries.abandon(p);
+        dict_comp(p, m);
+    } else {
+        tracing::debug!("Parsing dict expression");
+        dict_expr(p, m, entries);
+    }
+}
+
 /// A **dot suffix** node in a **primary expression**. In Starlark, **dot suffixes** are
 /// **syntax nodes** that represent the **access** of a **primary expression**.
 ///
 /// ## Ungrammar
 ///
 /// ```
-/// DotSuffix = '.' 'identifier'
+/// DotSuffix = '.' name:'identifier'
 /// ```
 ///
 /// ## Examples
@@ -296,8 +337,8 @@ pub(super) fn dot_suffix(p: &mut Parser) {
 ///
 /// ```
 /// SliceSuffix =
-///     '[' Expression? ':' Test? (':' (Test)?)? ']'
-///   | '[' Expression ']'
+///     '[' start:Expression? ':' end:Test? (':' (step:Test)?)? ']'
+///   | '[' index:Expression ']'
 /// ```
 ///
 /// ## Examples
@@ -305,6 +346,7 @@ pub(super) fn dot_suffix(p: &mut Parser) {
 /// ```starlark
 /// x[1:3]
 /// x[1:]
+/// x[::2]
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
 pub(super) fn slice_suffix(p: &mut Parser) {
@@ -312,21 +354,18 @@ pub(super) fn slice_suffix(p: &mut Parser) {
     tracing::debug!("Parsing slice suffix");
 
     p.expect(T!['[']);
+    if !p.at(T![:]) {
+        expression(p);
+    }
     if p.eat(T![:]) {
-        test(p);
-        if p.eat(T![:]) {
+        if !p.at(T![:]) && !p.at(T![']']) {
             test(p);
         }
-    } else {
-        expression(p);
-        if p.eat(T![:]) {
+        if p.eat(T![:]) && !p.at(T![']']) {
             test(p);
-            if p.eat(T![:]) {
-                test(p);
-            }
         }
     }
-    p.expect(T![']']);
+    expect_closing_delimiter(p, T![']']);
 
     tracing::debug!("Finished parsing slice suffix");
     m.complete(p, SLICE_SUFFIX);
@@ -357,7 +396,7 @@ pub(super) fn call_suffix(p: &mut Parser) {
         arguments(p);
         p.eat(T![,]);
     }
-    p.expect(T![')']);
+    expect_closing_delimiter(p, T![')']);
 
     tracing::debug!("Finished parsing call suffix");
     m.complete(p, CALL_SUFFIX);
@@ -386,7 +425,8 @@ pub(super) fn arguments(p: &mut Parser) {
     tracing::debug!("Parsing arguments");
 
     argument(p);
-    while p.eat(T![,]) {
+    while p.at(T![,]) && !p.nth_at(1, T![')']) {
+        p.bump(T![,]);
         argument(p);
     }
 
@@ -403,7 +443,7 @@ pub(super) fn arguments(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// Argument = Test | 'identifier' '=' Test | '*' Test | '**' Test
+/// Argument = value:Test | name:'identifier' '=' value:Test | '*' value:Test | '**' value:Test
 /// ```
 ///
 /// ## Examples
@@ -453,22 +493,27 @@ pub(super) fn argument(p: &mut Parser) {
 /// ```starlark
 /// [1, 2, 3]
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn list_expr(p: &mut Parser) {
-    let m = p.start();
+/// Parses the rest of a list expression, once its first element has been
+/// parsed (see [`list`]), given the markers started before the opening
+/// bracket and the first element.
+#[tracing::instrument(level = "debug", skip(p, m, elements))]
+pub(super) fn list_expr(p: &mut Parser, m: Marker, elements: Marker) {
     tracing::debug!("Parsing list expression");
 
-    p.expect(T!['[']);
-    if !p.at(T![']']) {
-        expression(p);
-        while p.eat(T![,]) {
-            if p.at(T![']']) {
-                break;
-            }
-            expression(p);
+    // The rest of the first expression, as in `expression`.
+    while p.at(T![,]) && !CLOSING_DELIMITERS.contains(p.nth(1)) {
+        p.bump(T![,]);
+        test(p);
+    }
+    elements.complete(p, EXPRESSION);
+
+    while p.eat(T![,]) {
+        if p.at(T![']']) {
+            break;
         }
+        expression(p);
     }
-    p.expect(T![']']);
+    expect_closing_delimiter(p, T![']']);
 
     tracing::debug!("Finished parsing list expression");
     m.complete(p, LIST_EXPR);
@@ -477,14 +522,16 @@ pub(super) fn list_expr(p: &mut Parser) {
 /// A **unary expression**. In Starlark, **unary expressions** are
 /// **expressions** that are prefixed with a **unary operator**.
 ///
-/// Unary operators are `+`, `-`, `not`, and `~`.
+/// Unary operators are `+`, `-`, `~`, which bind tighter than any binary
+/// operator, and `not`, which binds looser than comparisons (see
+/// [`binary_expr`]). Parses a [`primary_expr`] if there's no prefix operator.
 ///
 /// See [`expression`] for more usage examples.
 ///
 /// ## Ungrammar
 ///
 /// ```
-/// UnaryExpr = ('+' | '-' | 'not' | '~') Test
+/// UnaryExpr = ('+' | '-' | 'not' | '~') operand:Test
 /// ```
 ///
 /// ## Examples
@@ -495,46 +542,56 @@ pub(super) fn list_expr(p: &mut Parser) {
 /// not x
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn unary_expr(p: &mut Parser) {
+pub(super) fn unary_expr(p: &mut Parser) -> CompletedMarker {
+    if !p.at_ts(UNARY_OP) {
+        return primary_expr(p);
+    }
+
     let m = p.start();
     tracing::debug!("Parsing unary_expr");
 
-    if p.at_ts(UNARY_OP) {
-        p.bump_any();
-        test(p);
-    } else {
-        // TODO: add diganostic and error recovery
-        // p.error("expected a unary operator");
-        // p.err_recover("unary operator error", |p| {
-        //     p.at(T![+]) || p.at(T![-]) || p.at(T![not]) || p.at(T![~])
-        // });
-    }
+    p.bump_any();
+    unary_expr(p);
 
     tracing::debug!("Finished parsing unary_expr");
-    m.complete(p, UNARY_EXPR);
+    m.complete(p, UNARY_EXPR)
+}
+
+/// An expression of operators of the given **precedence** `prec` or higher
+/// (see [`binary_op`]), i.e. a [`binary_expr`], or a [`unary_expr`] for the
+/// highest precedence.
+fn expr_prec(p: &mut Parser, prec: u8) -> CompletedMarker {
+    if prec >= PREC_UNARY {
+        return unary_expr(p);
+    }
+
+    if prec == PREC_NOT && p.at(T![not]) {
+        let m = p.start();
+        p.bump(T![not]);
+        expr_prec(p, PREC_NOT);
+        return m.complete(p, UNARY_EXPR);
+    }
+
+    binary_expr(p, prec)
 }
 
 /// A **binary expression**. In Starlark, **binary expressions** are
 /// **two expressions** separated by a **binary operator**.
-/// Binary expressions are left-associative.
-/// The precedence of binary operators is as follows:
 ///
-/// 1. `*`, `/`, `%`, `//`
-/// 2. `+`, `-`
-/// 3. `<<`, `>>`
-/// 4. `&`
-/// 5. `^`
-/// 6. `|`
+/// Binary expressions are parsed by **precedence climbing**, such that each
+/// application of an operator becomes a [`BINARY_EXPR`] of its own, nested
+/// according to the precedence of the operators (see [`binary_op`]). All
+/// binary operators are **left-associative**, except for comparisons, which
+/// are **non-associative**: chaining them (e.g. `a < b < c`) is an error.
+///
+/// Parses the operands of operators of at least precedence `prec`.
 ///
 /// See [`expression`] for more usage examples.
-/// See [`bin_op`] for more information on binary operators.
-/// // TODO: precedence table
-/// See [`precedence`] for more information on operator precedence.
 ///
 /// ## Ungrammar
 ///
 /// ```
-/// BinaryExpr = Test (BinOp Test)*
+/// BinaryExpr = lhs:Test Binop rhs:Test
 /// ```
 ///
 /// ## Examples
@@ -542,28 +599,55 @@ pub(super) fn unary_expr(p: &mut Parser) {
 /// ```starlark
 /// 1 + 2
 /// x * y
-/// a and b
+/// a or b and c == d + e * f
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn binary_expr(p: &mut Parser) {
-    let m = p.start();
-    tracing::debug!("Parsing binary_expr");
+pub(super) fn binary_expr(p: &mut Parser, prec: u8) -> CompletedMarker {
+    let mut lhs = expr_prec(p, prec + 1);
 
-    test(p);
+    let mut first = true;
+    while let Some((op_prec, op_tokens)) = binary_op(p) {
+        if op_prec < prec {
+            break;
+        }
+        tracing::debug!("Parsing binary_expr. Current token: {:?}", p.current());
 
-    while p.at_ts(BIN_OP) || (p.at(T![not]) && p.nth_at(1, T![in])) {
-        bin_op(p);
-        test(p);
+        if !first && op_prec == PREC_COMPARISON {
+            p.error(p.chained_comparison());
+        }
+
+        let m = lhs.precede(p);
+        let op = p.start();
+        for _ in 0..op_tokens {
+            p.bump_any();
+        }
+        op.complete(p, BINOP);
+        expr_prec(p, op_prec + 1);
+        lhs = m.complete(p, BINARY_EXPR);
+
+        first = false;
     }
 
-    tracing::debug!("Finished parsing binary_expr");
-    m.complete(p, BINARY_EXPR);
+    lhs
 }
 
-/// A **binary operator**. In Starlark, **binary operators** are
-/// `+`, `-`, `*`, `/`, `%`, `//`, `<<`, `>>`, `&`, `^`, `|`, `in`, `not in`.
+/// The **precedence** of the **binary operator** at the current token, if
+/// any, along with the number of tokens it consists of (i.e. two for `not
+/// in`).
 ///
-/// See [`binary_expr`] for more information on binary expressions.
+/// From lowest to highest precedence, the operators are:
+///
+/// 1. `or`
+/// 2. `and`
+/// 3. `not` (unary)
+/// 4. `==`, `!=`, `<`, `>`, `<=`, `>=`, `in`, `not in`
+/// 5. `|`
+/// 6. `^`
+/// 7. `&`
+/// 8. `<<`, `>>`
+/// 9. `+`, `-`
+/// 10. `*`, `/`, `//`, `%`
+/// 11. `+`, `-`, `~` (unary)
 ///
 /// ## Ungrammar
 ///
@@ -579,31 +663,22 @@ pub(super) fn binary_expr(p: &mut Parser) {
 ///  | '-' | '+'
 ///  | '*' | '%' | '/' | '//'
 /// ```
-///
-/// ## Examples
-///
-/// ```starlark
-/// x + y
-/// a * b
-/// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn bin_op(p: &mut Parser) {
-    tracing::debug!("Parsing bin_op");
-
-    if p.at_ts(BIN_OP) {
-        p.bump_any();
-    } else if p.at(T![not]) && p.nth_at(1, T![in]) {
-        p.bump(T![not]);
-        p.bump(T![in]);
-    } else {
-        // TODO: add diganostic and error recovery
-        // p.error("expected a binary operator");
-        // p.err_recover("binary operator error", |p| {
-        //     p.at_ts(&BINARY_OPERATORS) || (p.at(T![not]) && p.nth_at(1, T![in]))
-        // });
-    }
-
-    tracing::debug!("Finished parsing bin_op");
+fn binary_op(p: &Parser) -> Option<(u8, usize)> {
+    let prec = match p.current() {
+        T![or] => 1,
+        T![and] => 2,
+        T![not] if p.nth_at(1, T![in]) => return Some((PREC_COMPARISON, 2)),
+        T![==] | T![!=] | T![<] | T![>] | T![<=] | T![>=] | T![in] => PREC_COMPARISON,
+        T![|] => 5,
+        T![^] => 6,
+        T![&] => 7,
+        T![<<] | T![>>] => 8,
+        T![+] | T![-] => 9,
+        T![*] | T![/] | T![dslash] | T![%] => 10,
+        _ => return None,
+    };
+
+    Some((prec, 1))
 }
 
 /// A **lambda expression**. In Starlark, **lambda expressions** are
@@ -615,7 +690,7 @@ pub(super) fn bin_op(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// LambdaExpr = 'lambda' Parameters? ':' Test
+/// LambdaExpr = 'lambda' Parameters? ':' body:Test
 /// ```
 ///
 /// ## Examples
@@ -630,7 +705,7 @@ pub(super) fn lambda_expr(p: &mut Parser) {
 
     p.expect(T![lambda]);
     if p.at_ts(PARAM_START) {
-        statements::parameters(p);
+        statements::parameters(p, false);
     }
     p.expect(T![:]);
     test(p);
@@ -647,7 +722,7 @@ pub(super) fn lambda_expr(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// IfExpr = Test 'if' Test 'else' Test
+/// IfExpr = then_branch:Test 'if' condition:Test 'else' else_branch:Test
 /// ```
 ///
 /// ## Examples
@@ -655,17 +730,33 @@ pub(super) fn lambda_expr(p: &mut Parser) {
 /// ```starlark
 /// x if x > 0 else -x
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn if_expr(p: &mut Parser) {
-    let m = p.start();
+///
+/// The first operand (i.e. `x` above) has already been parsed by the caller,
+/// as `then_branch`.
+#[tracing::instrument(level = "debug", skip(p, then_branch))]
+pub(super) fn if_expr(p: &mut Parser, then_branch: CompletedMarker) -> CompletedMarker {
+    let m = then_branch.precede(p);
     tracing::debug!("Parsing if_expr");
 
-    test(p);
-    p.expect(T![if]);
+    p.bump(T![if]);
     test(p);
     p.expect(T![else]);
     test(p);
 
     tracing::debug!("Finished parsing if_expr");
-    m.complete(p, IF_EXPR);
+    m.complete(p, IF_EXPR)
+}
+
+/// Expects the `closing` bracket of a bracketed node, skipping any unexpected
+/// tokens before it (see [`CLOSING_DELIMITER_RECOVERY_SET`]).
+pub(super) fn expect_closing_delimiter(p: &mut Parser, closing: SyntaxKind) {
+    if p.eat(closing) {
+        return;
+    }
+
+    p.error_recover(
+        p.unexpected(TokenSet::new(&[closing])),
+        CLOSING_DELIMITER_RECOVERY_SET,
+    );
+    p.eat(closing);
 }
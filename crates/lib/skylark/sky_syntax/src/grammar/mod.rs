@@ -3,7 +3,11 @@ mod decl;
 mod expr;
 mod statements;
 
-use crate::{parser::Parser, SyntaxKind, T};
+use crate::{
+    parser::Parser,
+    SyntaxKind::{self, *},
+    TokenSet, T,
+};
 
 /// The **root** of a **Starlark file**. In Starlark, **files** are
 /// the **root** of the **syntax tree**.
@@ -23,6 +27,24 @@ pub(crate) fn root(p: &mut Parser) {
     m.complete(p, SyntaxKind::FILE);
 }
 
+/// The grammar rule with which a **block-like** node of the given `kind` can
+/// be reparsed on its own, i.e. without the surrounding syntax tree. Each such
+/// node is delimited by tokens of its own (brackets, or a `newline` and an
+/// `indent` for suites), so its extent doesn't depend on what follows it.
+///
+/// See [`crate::parsing::reparsing`] for more information.
+pub(crate) fn reparser(kind: SyntaxKind) -> Option<fn(&mut Parser)> {
+    let reparser: fn(&mut Parser) = match kind {
+        SUITE => decl::suite,
+        LIST_EXPR | LIST_COMP => expr::list,
+        DICT_EXPR | DICT_COMP => expr::dict,
+        CALL_SUFFIX => expr::call_suffix,
+        _ => return None,
+    };
+
+    Some(reparser)
+}
+
 /// A **file**. In Starlark, **files** are **comma-separated lists** of
 /// [`statements::statement`]'s.
 ///
@@ -47,6 +69,13 @@ pub(super) fn file(p: &mut Parser) {
     while !p.at(T![eof]) {
         if p.at(T![newline]) {
             p.bump(T![newline]);
+        } else if p.at(T![indent]) {
+            // An unexpected indent (e.g. at the start of the file) can't open a
+            // block at the top level, so report it and move on.
+            decl::unexpected_block(p);
+        } else if p.at(T![outdent]) {
+            p.error(p.unexpected(TokenSet::EMPTY));
+            p.bump_any();
         } else {
             statements::statement(p);
         }
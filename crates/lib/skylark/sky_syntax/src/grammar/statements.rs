@@ -1,14 +1,20 @@
-use super::expr;
-use crate::{
-    grammar::decl,
-    lexer::{Span, Token},
-    parser::{ParseError, Parser},
-    SyntaxKind::*,
-    TokenSet, T,
-};
-
-pub(super) const STATEMENT_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![def], T![load], T![if], T![for], T![in], T![return]]);
+use super::expr::{self, expect_closing_delimiter, EXPR_RECOVERY_SET};
+use crate::{grammar::decl, parser::{Marker, Parser}, SyntaxKind::*, TokenSet, T};
+
+/// The tokens at which to resume parsing after an **invalid statement**, i.e.
+/// the end of a line and the tokens which commonly begin a statement.
+pub(super) const STATEMENT_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![newline],
+    T![def],
+    T![load],
+    T![if],
+    T![for],
+    T![return],
+    T![pass],
+    T![break],
+    T![continue],
+    T![identifier],
+]);
 
 pub(super) const ASSIGNMENT_OPERATOR: TokenSet = TokenSet::new(&[
     T![=],
@@ -16,7 +22,7 @@ pub(super) const ASSIGNMENT_OPERATOR: TokenSet = TokenSet::new(&[
     T![-=],
     T![*=],
     T![/=],
-    // T![slash_slash_eq], TODO: add support for operator
+    T![dslasheq],
     T![%=],
     T![&=],
     T![|=],
@@ -72,9 +78,9 @@ pub(super) fn statement(p: &mut Parser) {
 ///
 /// ```
 /// DefStmt =
-/// 'def' 'identifier' '('
+/// 'def' name:'identifier' '('
 ///   (Parameters (',')?)?
-/// ')' ':' Suite
+/// ')' ('->' return_type:TypeAnnotation)? ':' body:Suite
 /// ```
 ///
 /// ## Examples
@@ -82,6 +88,9 @@ pub(super) fn statement(p: &mut Parser) {
 /// ```starlark
 /// def foo(x):
 ///     return x * 2
+///
+/// def bar(x: int) -> str:
+///     return str(x)
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
 pub(super) fn def_stmt(p: &mut Parser) {
@@ -93,10 +102,13 @@ pub(super) fn def_stmt(p: &mut Parser) {
     p.expect(T![identifier]);
     p.expect(T!['(']);
     if !p.at(T![')']) {
-        parameters(p);
+        parameters(p, true);
         p.eat(T![,]);
     }
-    p.expect(T![')']);
+    expect_closing_delimiter(p, T![')']);
+    if p.eat(T![->]) {
+        type_annotation(p);
+    }
     p.expect(T![:]);
     decl::suite(p);
 
@@ -104,7 +116,9 @@ pub(super) fn def_stmt(p: &mut Parser) {
     m.complete(p, DEF_STMT);
 }
 
-/// A **comma-separated list** of **function parameters**.
+/// A **comma-separated list** of **function parameters**, which may have
+/// **type annotations** unless they belong to a lambda (whose body follows a
+/// `:` too).
 ///
 /// ## Ungrammar
 ///
@@ -121,13 +135,14 @@ pub(super) fn def_stmt(p: &mut Parser) {
 ///    return x + y + z
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn parameters(p: &mut Parser) {
+pub(super) fn parameters(p: &mut Parser, annotated: bool) {
     tracing::debug!("Parsing parameters. Current token: {:?}", p.current());
     let m = p.start();
 
-    parameter(p);
-    while p.eat(T![,]) {
-        parameter(p);
+    parameter(p, annotated);
+    while p.at(T![,]) && !p.nth_at(1, T![')']) {
+        p.bump(T![,]);
+        parameter(p, annotated);
     }
 
     tracing::debug!("Finished parsing parameters");
@@ -139,11 +154,11 @@ pub(super) fn parameters(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// Parameter = 'identifier'
-///   | ('identifier' '=' Test)
+/// Parameter =
+///    (name:'identifier' (':' annotation:TypeAnnotation)? ('=' default:Test)?)
 ///  | '*'
-///  | ('*' 'identifier')
-///  | ('**' 'identifier')
+///  | ('*' name:'identifier' (':' annotation:TypeAnnotation)?)
+///  | ('**' name:'identifier' (':' annotation:TypeAnnotation)?)
 /// ```
 ///
 /// ## Examples
@@ -161,13 +176,23 @@ pub(super) fn parameters(p: &mut Parser) {
 /// def sum(*args):
 ///  return sum(args)
 /// ```
+///
+/// or `x: int = 0` in
+///
+/// ```starlark
+/// def inc(x: int = 0):
+///   return x + 1
+/// ```
 #[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn parameter(p: &mut Parser) {
+pub(super) fn parameter(p: &mut Parser, annotated: bool) {
     tracing::debug!("Parsing parameter. Current token: {:?}", p.current());
     let m = p.start();
     match p.current() {
         T![identifier] => {
             p.bump(T![identifier]);
+            if annotated && p.eat(T![:]) {
+                type_annotation(p);
+            }
             tracing::debug!(
                 "Found identifier. Parsing test if current token is '='. Current token: {:?}",
                 p.current()
@@ -181,36 +206,60 @@ pub(super) fn parameter(p: &mut Parser) {
             tracing::debug!("Found '*'. Parsing identifier if current token is 'identifier'. Current token: {:?}", p.current());
             if p.at(T![identifier]) {
                 p.bump(T![identifier]);
+                if annotated && p.eat(T![:]) {
+                    type_annotation(p);
+                }
             }
         }
         T![**] => {
             p.bump(T![**]);
             p.expect(T![identifier]);
+            if annotated && p.eat(T![:]) {
+                type_annotation(p);
+            }
         }
-        _ => p.error(ParseError::UnexpectedToken {
-            expected: TokenSet::from(vec![T![identifier].tk()]),
-            found: Token::new(
-                p.current().tk(),
-                String::from("TODO: expected parameter"),
-                Span::default(),
-                // p.current().text().to_string(),
-                // p.current().span(),
-            ),
-        }),
-        // _ => p.error("expected parameter"),
+        _ => p.error_recover(p.unexpected(PARAM_START), EXPR_RECOVERY_SET),
     }
 
     tracing::debug!("Finished parsing parameter");
     m.complete(p, PARAMETER);
 }
 
+/// The **type annotation** of a parameter or of the return value of a
+/// function, which is an expression (e.g. `list[str]` or `int | None`).
+///
+/// ## Ungrammar
+///
+/// ```
+/// TypeAnnotation = Test
+/// ```
+///
+/// ## Examples
+///
+/// `int` and `dict[str, int]` in
+///
+/// ```starlark
+/// def count(x: int) -> dict[str, int]:
+///   return {"x": x}
+/// ```
+#[tracing::instrument(level = "debug", skip(p))]
+pub(super) fn type_annotation(p: &mut Parser) {
+    tracing::debug!("Parsing type annotation. Current token: {:?}", p.current());
+    let m = p.start();
+
+    expr::test(p);
+
+    tracing::debug!("Finished parsing type annotation");
+    m.complete(p, TYPE_ANNOTATION);
+}
+
 /// An **elif clause**. Elif clauses are used to **conditionally execute
 /// statements** within an **if statement**.
 ///
 /// ## Ungrammar
 ///
 /// ```
-/// ElifClause = 'elif' Test ':' Suite
+/// ElifClauses = 'elif' condition:Test ':' body:Suite
 /// ```
 ///
 /// ## Examples
@@ -247,7 +296,7 @@ pub(super) fn elif_clauses(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// ElseClause = 'else' ':' Suite
+/// ElseClause = 'else' ':' body:Suite
 /// ```
 ///
 /// ## Examples
@@ -283,7 +332,7 @@ pub(super) fn else_clause(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// ForStmt = 'for' LoopVariables 'in' Expression ':' Suite
+/// ForStmt = 'for' LoopVariables 'in' iterable:Expression ':' body:Suite
 /// ```
 ///
 /// ## Examples
@@ -310,12 +359,14 @@ pub(super) fn for_stmt(p: &mut Parser) {
     m.complete(p, FOR_STMT);
 }
 
-/// A **simple statement**, which can be executed on a single line.
+/// A **simple statement**, which can be executed on a single line. Simple
+/// statements are terminated by a **newline**, which the lexer guarantees is
+/// present even at the end of a file lacking a trailing line break.
 ///
 /// ## Ungrammar
 ///
 /// ```
-/// SimpleStmt = SmallStmt (';' SmallStmt)* ';'?
+/// SimpleStmt = SmallStmt (';' SmallStmt)* ';'? 'newline'
 /// ```
 ///
 /// ## Examples
@@ -336,6 +387,16 @@ pub(super) fn simple_stmt(p: &mut Parser) {
         }
         small_stmt(p);
     }
+    if !p.at(EOF) && !p.eat(T![newline]) {
+        // Skip the rest of the line, unless another statement follows right
+        // away (e.g. after an unclosed bracket, within which newlines are
+        // insignificant).
+        p.error_recover(
+            p.unexpected(TokenSet::new(&[T![newline]])),
+            STATEMENT_RECOVERY_SET,
+        );
+        p.eat(T![newline]);
+    }
 
     tracing::debug!("Finished parsing simple statement");
     m.complete(p, SIMPLE_STMT);
@@ -395,9 +456,9 @@ pub(super) fn small_stmt(p: &mut Parser) {
         _ => {
             if p.at(T![load]) {
                 load_stmt(p);
-            } else if p.at(T![identifier]) && p.nth_at(1, T![=]) {
-                assign_stmt(p);
             } else {
+                // An assignment only becomes apparent after its targets
+                // (e.g. `x[0] += 1`), see `expr_stmt`.
                 expr_stmt(p);
             }
         }
@@ -537,29 +598,17 @@ pub(super) fn pass_stmt(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// AssignStmt = Expression ('=' | '+=' | '-=' | '*=' | '/=' | '//=' | '%=' | '&=' | '|=' | '^=' | '<<=' | '>>=') Expression
+/// AssignStmt = lhs:Expression ('=' | '+=' | '-=' | '*=' | '/=' | '//=' | '%=' | '&=' | '|=' | '^=' | '<<=' | '>>=') rhs:Expression
 /// ```
-#[tracing::instrument(level = "debug", skip(p))]
-pub(super) fn assign_stmt(p: &mut Parser) {
+///
+/// Parses the rest of the statement, once its targets have been parsed (see
+/// [`expr_stmt`]), given the marker started before them.
+#[tracing::instrument(level = "debug", skip(p, m))]
+pub(super) fn assign_stmt(p: &mut Parser, m: Marker) {
     tracing::debug!("Parsing assign statement. Current token: {:?}", p.current());
-    let m = p.start();
 
-    expr::expression(p);
-    if !p.at_ts(ASSIGNMENT_OPERATOR) {
-        p.error(ParseError::UnexpectedToken {
-            expected: ASSIGNMENT_OPERATOR,
-            found: Token::new(
-                p.current().tk(),
-                String::from("TODO: assignment operator"),
-                Span::new(0, 0),
-                // p.current().text().to_owned(),
-                // p.current().span(), TODO: refactor to use this for better error messages
-            ),
-        });
-    } else {
-        assert!(p.at_ts(ASSIGNMENT_OPERATOR));
-        p.bump_any();
-    }
+    assert!(p.at_ts(ASSIGNMENT_OPERATOR));
+    p.bump_any();
     expr::expression(p);
 
     tracing::debug!("Finished parsing assign statement");
@@ -568,7 +617,8 @@ pub(super) fn assign_stmt(p: &mut Parser) {
 
 /// An **expression statement**. An expression statement is a statement
 /// that evaluates an [`expr::expression`] and discards the result. This is
-/// typically used for function calls and assignments.
+/// typically used for function calls. If the expression is followed by an
+/// assignment operator, it's the targets of an [`assign_stmt`] instead.
 ///
 /// ## Ungrammar
 ///
@@ -579,8 +629,8 @@ pub(super) fn assign_stmt(p: &mut Parser) {
 /// ## Examples
 ///
 /// ```starlark
-/// x = 1 # an expression statement
-/// foo() # another expression statement
+/// foo() # an expression statement
+/// x.append(1) # another expression statement
 /// ```
 #[tracing::instrument(level = "debug", skip(p))]
 pub(super) fn expr_stmt(p: &mut Parser) {
@@ -591,6 +641,10 @@ pub(super) fn expr_stmt(p: &mut Parser) {
     let m = p.start();
 
     expr::expression(p);
+    if p.at_ts(ASSIGNMENT_OPERATOR) {
+        assign_stmt(p, m);
+        return;
+    }
 
     tracing::debug!("Finished parsing expression statement");
     m.complete(p, EXPR_STMT);
@@ -603,7 +657,7 @@ pub(super) fn expr_stmt(p: &mut Parser) {
 /// ## Ungrammar
 ///
 /// ```
-/// LoadStmt = 'load' '(' 'string' (',' ('identifier' '=')? 'string')* ','? ')'
+/// LoadStmt = 'load' '(' module:'string' (',' LoadSymbol)* ','? ')'
 /// ```
 ///
 /// ## Examples
@@ -621,20 +675,48 @@ pub(super) fn load_stmt(p: &mut Parser) {
     p.bump(T![load]);
     p.expect(T!['(']);
     p.expect(T![string]);
-    while p.eat(T![,]) {
-        if p.at(T![identifier]) {
-            p.bump(T![identifier]);
-            p.expect(T![=]);
-        }
-        p.expect(T![string]);
+    while p.at(T![,]) && !p.nth_at(1, T![')']) {
+        p.bump(T![,]);
+        load_symbol(p);
     }
     p.eat(T![,]);
-    p.expect(T![')']);
+    expect_closing_delimiter(p, T![')']);
 
     tracing::debug!("Finished parsing load statement");
     m.complete(p, LOAD_STMT);
 }
 
+/// A **load symbol**. Load symbols are the **symbols** imported by a **load
+/// statement**, optionally bound to an **alias** in the current file.
+///
+/// ## Ungrammar
+///
+/// ```
+/// LoadSymbol = (alias:'identifier' '=')? symbol:'string'
+/// ```
+///
+/// ## Examples
+///
+/// `_git_repository = "git_repository"` and `"strncpy"` in
+///
+/// ```starlark
+/// load("@bazel_tools//tools/build_defs/repo:git.bzl",
+/// _git_repository = "git_repository") # usage with alias
+/// load("@stdlib//strings.bzl", "strncpy") # regular usage
+/// ```
+#[tracing::instrument(level = "debug", skip(p))]
+fn load_symbol(p: &mut Parser) {
+    let m = p.start();
+
+    if p.at(T![identifier]) {
+        p.bump(T![identifier]);
+        p.expect(T![=]);
+    }
+    p.expect(T![string]);
+
+    m.complete(p, LOAD_SYMBOL);
+}
+
 /// An **if statement**. If statements are used to **conditionally execute
 /// statements**. They can **optionally** include **else clauses** and
 /// **elif clauses**.
@@ -643,8 +725,8 @@ pub(super) fn load_stmt(p: &mut Parser) {
 ///
 /// ```
 /// IfStmt =
-///  'if' Test ':' Suite
-/// ElifClauses*
+///  'if' condition:Test ':' body:Suite
+///  elif_clauses:ElifClauses*
 ///  ElseClause?
 /// ```
 ///
@@ -669,15 +751,10 @@ pub(super) fn if_stmt(p: &mut Parser) {
     p.expect(T![:]);
     decl::suite(p);
     while p.at(T![elif]) {
-        p.bump(T![elif]);
-        expr::test(p);
-        p.expect(T![:]);
-        decl::suite(p);
+        elif_clauses(p);
     }
     if p.at(T![else]) {
-        p.bump(T![else]);
-        p.expect(T![:]);
-        decl::suite(p);
+        else_clause(p);
     }
 
     tracing::debug!("Finished parsing if statement");
@@ -32,7 +32,7 @@
 //! even for **invalid** source code. This allows us to **report** and **recover** from errors in a
 //! **graceful** manner.
 
-mod ast;
+pub mod ast;
 mod event;
 mod grammar;
 mod lang;
@@ -42,22 +42,26 @@ mod parser;
 mod parsing;
 mod syntax_error;
 mod syntax_tree;
+pub mod ted;
+mod text_edit;
 mod token_set;
+mod validation;
 
 pub use crate::{
     ast::File,
     ast::SyntaxKind,
-    lang::{SyntaxNode, SyntaxToken},
-    lexer::{StarlarkLexer, TokenKind, TokenSink},
+    lang::{SyntaxElement, SyntaxNode, SyntaxToken},
+    lexer::{StarlarkLexer, TokenKind, TokenKindExt, TokenSink},
     logging::init_logging,
     parser::StarlarkParser,
     parser::TextTreeSink,
+    syntax_error::SyntaxError,
+    text_edit::TextEdit,
     token_set::TokenSet,
 };
 use ast::AstNode;
 use rowan::GreenNode;
 use std::{marker::PhantomData, sync::Arc};
-use syntax_error::SyntaxError;
 
 /// The [`Parse`] represents the **result** of a **parsing operation** on a **piece of source code**.
 /// It contains a _syntax tree_ (`green`), a _collection of syntax errors_ (`errors`), and a
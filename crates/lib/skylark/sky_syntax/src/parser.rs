@@ -2,7 +2,7 @@ use crate::ast::AstNode;
 use crate::ast::SyntaxKind::*;
 use crate::event::{self, Event};
 pub use crate::lang::{SyntaxElement, SyntaxNode, SyntaxToken};
-use crate::lexer::{FileId, Span, Token, TokenStream};
+use crate::lexer::{FileId, Token, TokenKindExt, TokenStream};
 use crate::parsing::{TokenSource, TreeSink};
 use crate::syntax_tree::SyntaxTreeBuilder;
 use crate::token_set::TokenSet;
@@ -86,6 +86,9 @@ use typed_builder::TypedBuilder;
 pub enum ParseError {
     UnexpectedToken { expected: TokenSet, found: Token },
     UnexpectedEof,
+    /// A comparison operator applied to the result of another comparison (e.g.
+    /// `a < b < c`), as comparisons are non-associative in Starlark.
+    ChainedComparison { found: Token },
 }
 
 impl ParseError {
@@ -114,6 +117,14 @@ impl ParseError {
                 .with_message("Syntax error: Unexpected end of file")
                 .with_labels(vec![Label::primary(file_id, usize::MAX..usize::MAX)
                     .with_message("Unexpected end of file")]),
+            ParseError::ChainedComparison { found } => Diagnostic::error()
+                .with_message("Syntax error: Chained comparison")
+                .with_labels(vec![Label::primary(file_id, found.range())
+                    .with_message("Comparison operators are non-associative")])
+                .with_notes(vec![
+                    "Use parentheses or `and` to combine comparisons, e.g. `a < b and b < c`"
+                        .to_string(),
+                ]),
         }
     }
 }
@@ -328,48 +339,69 @@ impl<'t> Parser<'t> {
     /// Consume the next token if it is `kind` or emit an error
     /// otherwise.
     pub(crate) fn expect(&mut self, kind: SyntaxKind) -> bool {
-        let current = self.current();
-        // if current == kind {
-        //     self.bump(Any);
-        //     return true;
-        // }
         if self.eat(kind) {
             return true;
         }
 
-        self.push_event(Event::Error(ParseError::UnexpectedToken {
-            expected: TokenSet::from(vec![kind.tk()]),
-            found: Token::new(
-                current.tk(),
-                String::from("TODO: Improve diagnostics"),
-                Span::new(0, 0),
-            ),
-        }));
+        self.error(self.unexpected(TokenSet::new(&[kind])));
         false
     }
 
+    /// A [`ParseError::UnexpectedToken`] error for the current token, when
+    /// one of the `expected` kinds of tokens was expected instead.
+    pub(crate) fn unexpected(&self, expected: TokenSet) -> ParseError {
+        ParseError::UnexpectedToken {
+            expected,
+            found: self.token_source.lookahead_nth(0),
+        }
+    }
+
+    /// A [`ParseError::ChainedComparison`] error for the comparison operator
+    /// at the current token.
+    pub(crate) fn chained_comparison(&self) -> ParseError {
+        ParseError::ChainedComparison { found: self.token_source.lookahead_nth(0) }
+    }
+
+    /// Reports the given `error` at the current token, unless an error has
+    /// been reported there already (as any later ones are merely caused by
+    /// the first, e.g. both a missing operand and a missing newline at `)`).
     pub(crate) fn error(&mut self, error: ParseError) {
-        self.push_event(Event::Error(error));
-    }
-
-    // TODO:
-    // Create an error node and consume the next token.
-    // pub(crate) fn error_recover(&mut self, message: &str, recovery: TokenSet) {
-    //     if self.at(T!['{']) || self.at(T!['}']) || self.at_ts(recovery) {
-    //         self.error(message);
-    //     } else {
-    //         let m = self.start();
-    //         self.error(message);
-    //         self.bump_any();
-    //         m.complete(self, ERROR);
-    //     }
-    // }
-
-    // /// Emit error with the `message`
-    // pub(crate) fn error<T: Into<String>>(&mut self, message: T) {
-    //     let msg = ParseError(message.into());
-    //     self.push_event(Event::Error { msg });
-    // }
+        let reported = self
+            .events
+            .iter()
+            .rev()
+            .find(|event| matches!(event, Event::Token { .. } | Event::Error(_)))
+            .map(|event| matches!(event, Event::Error(_)))
+            .unwrap_or(false);
+
+        if !reported {
+            self.push_event(Event::Error(error));
+        }
+    }
+
+    /// Reports the given `error` and **recovers** from it by skipping ahead
+    /// to the next token in the `recovery` set (or the end of the file). The
+    /// skipped tokens, if any, are wrapped in an [`ERROR`] node, so that they
+    /// don't derail the parsing of the remaining input.
+    ///
+    /// ## Example
+    ///
+    /// ```starlark
+    /// x = $ $ 1   =>   x = $ $ 1
+    ///     ^ error_recover       ^ new current token (`newline` being in `recovery`)
+    /// ```
+    pub(crate) fn error_recover(&mut self, error: ParseError, recovery: TokenSet) {
+        self.error(error);
+        if self.at(EOF) || self.at_ts(recovery) {
+            return;
+        }
+
+        let m = self.start();
+        while !self.at(EOF) && !self.at_ts(recovery) {
+            self.bump_any();
+        }
+        m.complete(self, ERROR);
+    }
 }
 
 /// Parse given tokens into a syntax tree using the provided `TreeSink`.
@@ -377,6 +409,16 @@ pub(crate) fn parse(token_source: &mut dyn TokenSource, tree_sink: &mut dyn Tree
     parse_from_tokens(token_source, tree_sink, grammar::root);
 }
 
+/// Parse given tokens into a single node using the given grammar rule (see
+/// [`grammar::reparser`]), rather than into a whole file.
+pub(crate) fn reparse(
+    token_source: &mut dyn TokenSource,
+    tree_sink: &mut dyn TreeSink,
+    reparser: fn(&mut Parser),
+) {
+    parse_from_tokens(token_source, tree_sink, reparser);
+}
+
 fn parse_from_tokens<F>(token_source: &mut dyn TokenSource, tree_sink: &mut dyn TreeSink, f: F)
 where
     F: FnOnce(&mut Parser),
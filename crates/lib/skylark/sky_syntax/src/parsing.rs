@@ -1,6 +1,9 @@
+mod reparsing;
 mod text_token_source;
 mod text_tree_sink;
 
+pub(crate) use reparsing::incremental_reparse;
+
 use crate::{
     lexer::Token,
     parser::{self, ParseError},
@@ -10,16 +13,11 @@ use crate::{
 use rowan::GreenNode;
 
 pub(crate) fn parse_text(text: &str) -> (GreenNode, Vec<SyntaxError>) {
-    // Tokenize the source into a token stream and a list of errors (i.e. unrecognized tokens)
-    let mut lexer = StarlarkLexer::new();
-
-    let token_sink = lexer.tokenize(text);
+    // Tokenize the source into a token stream and a list of errors (i.e. unrecognized tokens),
+    // the latter of which are reported alongside any syntax errors rather than emitted here
+    let token_sink = StarlarkLexer::new().tokenize(text);
     let tokens = token_sink.tokens();
 
-    if token_sink.has_errors() {
-        lexer.emit_errors();
-    }
-
     tracing::debug!("Tokens: {:#?}", tokens);
 
     let mut token_source = text_token_source::TextTokenSource::new(tokens.clone());
@@ -29,7 +27,16 @@ pub(crate) fn parse_text(text: &str) -> (GreenNode, Vec<SyntaxError>) {
 
     parser::parse(&mut token_source, &mut tree_sink);
 
-    tree_sink.finish()
+    let (green, mut errors) = tree_sink.finish();
+    errors.extend(
+        token_sink
+            .lexical_errors()
+            .iter()
+            .cloned()
+            .map(SyntaxError::from),
+    );
+
+    (green, errors)
 }
 
 /// The `TokenSource` trait provides an abstraction over the source of tokens, allowing for
@@ -0,0 +1,348 @@
+//! **Incremental reparsing** of a syntax tree after an edit of its text.
+//!
+//! Reparsing is attempted at two levels of granularity:
+//!
+//! - An edit _within_ a single token (e.g. renaming an identifier, or editing a
+//!   string or comment) relexes just that token, provided it remains a single
+//!   token of the same kind which doesn't merge with its neighbors.
+//! - Otherwise, the edited text is relexed (see [`StarlarkLexer::relex_sink`])
+//!   and the smallest enclosing **block-like** node (see [`grammar::reparser`])
+//!   whose surroundings are left untouched by the edit is reparsed on its own.
+//!
+//! If neither applies, the caller has to fall back to a full parse. Either way,
+//! the result is the same as parsing the edited text from scratch.
+
+use std::iter;
+
+use rowan::{GreenNode, GreenToken, Language, TextRange, TextSize};
+
+use crate::{
+    grammar,
+    lang::Starlark,
+    lexer::{Span, Token, TokenKind, TokenKindExt, TokenSink, TokenStream, STDIN},
+    parser,
+    syntax_error::SyntaxError,
+    text_edit::TextEdit,
+    validation, StarlarkLexer, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+
+use super::{text_token_source::TextTokenSource, text_tree_sink::TextTreeSink};
+
+/// Reparses the syntax tree `root` with the given `errors` after `edit`,
+/// returning the new tree along with its errors, or [`None`] if the tree has
+/// to be parsed from scratch.
+pub(crate) fn incremental_reparse(
+    root: &SyntaxNode,
+    edit: &TextEdit,
+    errors: &[SyntaxError],
+) -> Option<(GreenNode, Vec<SyntaxError>)> {
+    let (green, mut errors) =
+        reparse_token(root, edit, errors).or_else(|| reparse_block(root, edit, errors))?;
+    errors.sort_by_key(|error| error.range().start());
+
+    Some((green, errors))
+}
+
+/// Reparses an edit within a **single token** by relexing just that token.
+fn reparse_token(
+    root: &SyntaxNode,
+    edit: &TextEdit,
+    errors: &[SyntaxError],
+) -> Option<(GreenNode, Vec<SyntaxError>)> {
+    let token = root.covering_element(edit.delete).into_token()?;
+    let kind = token.kind();
+    let range = token.text_range();
+
+    match kind {
+        SyntaxKind::COMMENT
+        | SyntaxKind::IDENTIFIER
+        | SyntaxKind::STRING
+        | SyntaxKind::BYTES
+        | SyntaxKind::INT
+        | SyntaxKind::FLOAT => (),
+        // Leading whitespace determines the indentation of a line, whereas
+        // newlines and line continuations join or split lines.
+        SyntaxKind::WHITESPACE
+            if !starts_line(&token)
+                && !token.text().contains(['\n', '\\'])
+                && !edit.insert.contains(['\n', '\\']) => {}
+        _ => return None,
+    }
+    if touches_errors(errors, range) {
+        return None;
+    }
+
+    let mut text = token.text().to_string();
+    TextEdit::replace(edit.delete - range.start(), edit.insert.as_str()).apply(&mut text);
+
+    // Lex the token along with its neighbors, in order to detect it merging
+    // with either of them (e.g. `1` followed by `e5` becoming a float).
+    let prev = non_empty_tokens(token.prev_token(), SyntaxToken::prev_token).next();
+    let next = non_empty_tokens(token.next_token(), SyntaxToken::next_token).next();
+    let prev_text = prev.as_ref().map_or("", |prev| prev.text());
+    let next_text = next.as_ref().map_or("", |next| next.text());
+    let window = format!("{prev_text}{text}{next_text}");
+
+    let start = prev_text.len();
+    let end = start + text.len();
+    let (raw_tokens, lexical_errors) = StarlarkLexer::lex_raw(&window);
+    if !raw_tokens
+        .iter()
+        .any(|raw| raw.span() == &Span::new(start, end) && raw.kind().to_syntax() == kind)
+    {
+        return None;
+    }
+
+    let green = token.replace_with(GreenToken::new(Starlark::kind_to_raw(kind), &text));
+    let new_range = TextRange::at(range.start(), TextSize::of(text.as_str()));
+    let new_token = SyntaxNode::new_root(green.clone())
+        .covering_element(new_range)
+        .into_token()?;
+
+    let offset = usize::from(range.start()) as isize - start as isize;
+    let mut new_errors = shifted_errors(errors, range, new_range);
+    new_errors.extend(
+        lexical_errors
+            .into_iter()
+            .filter(|error| error.span().start() >= start && error.span().end() <= end)
+            .map(|error| shifted(SyntaxError::from(error), offset)),
+    );
+    new_errors.extend(validation::validate_token(new_token));
+
+    Some((green, new_errors))
+}
+
+/// Relexes the edited text, and reparses the smallest **block-like** node
+/// around the edit whose surrounding tokens are unaffected by it.
+fn reparse_block(
+    root: &SyntaxNode,
+    edit: &TextEdit,
+    errors: &[SyntaxError],
+) -> Option<(GreenNode, Vec<SyntaxError>)> {
+    let old_tokens = root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .collect::<Vec<_>>();
+    let text = root.text().to_string();
+    let end = text.len();
+
+    let mut tokens = old_tokens
+        .iter()
+        .map(|token| {
+            let range = token.text_range();
+            Token::new(
+                TokenKind::from(&token.kind()),
+                token.text().to_string(),
+                Span::new(range.start().into(), range.end().into()),
+            )
+        })
+        .collect::<Vec<_>>();
+    tokens.push(Token::new(
+        TokenKind::EOF,
+        String::new(),
+        Span::new(end, end),
+    ));
+    let old = TokenSink {
+        tokens: TokenStream::builder()
+            .text(text)
+            .tokens(tokens)
+            .file_name(STDIN.into())
+            .file_id(0)
+            .build(),
+        lexical_errors: Vec::new(),
+    };
+
+    let relexed = StarlarkLexer::relex_sink(&old, edit.delete, &edit.insert);
+    let new_tokens = relexed.tokens.tokens();
+    let delta = edit.insert.len() as isize - usize::from(edit.delete.len()) as isize;
+
+    // The numbers of tokens on either end left untouched by the edit.
+    let same_prefix = old_tokens
+        .iter()
+        .zip(new_tokens)
+        .take_while(|(old, new)| same_token(old, new, 0))
+        .count();
+    let same_suffix = old_tokens
+        .iter()
+        .rev()
+        .zip(new_tokens.iter().rev().skip(1))
+        .take_while(|(old, new)| same_token(old, new, delta))
+        .count();
+
+    let element = root.covering_element(edit.delete);
+    for node in element.ancestors() {
+        let Some(reparser) = grammar::reparser(node.kind()) else {
+            continue;
+        };
+
+        let (Some(first), Some(last)) = (node.first_token(), node.last_token()) else {
+            continue;
+        };
+        let start = token_index(&old_tokens, &first);
+        let suffix = old_tokens.len() - token_index(&old_tokens, &last) - 1;
+        if start > same_prefix
+            || suffix > same_suffix
+            || start + suffix >= new_tokens.len() - 1
+            || touches_errors(errors, node.text_range())
+        {
+            continue;
+        }
+
+        let fragment = &new_tokens[start..new_tokens.len() - 1 - suffix];
+        if !same_delimiters(&node, fragment) {
+            continue;
+        }
+
+        let Some(green) = reparse_fragment(&relexed.tokens, fragment, reparser) else {
+            continue;
+        };
+        if green.kind() != Starlark::kind_to_raw(node.kind()) {
+            continue;
+        }
+
+        tracing::debug!("Reparsed {:?} at {:?}", node.kind(), node.text_range());
+        let range = node.text_range();
+        let new_range = TextRange::at(range.start(), green.text_len());
+        let offset = usize::from(range.start()) as isize;
+
+        let mut new_errors = shifted_errors(errors, range, new_range);
+        new_errors.extend(
+            relexed
+                .lexical_errors
+                .iter()
+                .cloned()
+                .map(SyntaxError::from)
+                .filter(|error| new_range.contains_range(error.range())),
+        );
+        new_errors.extend(
+            validation::validate(&SyntaxNode::new_root(green.clone()))
+                .into_iter()
+                .map(|error| shifted(error, offset)),
+        );
+
+        return Some((node.replace_with(green), new_errors));
+    }
+
+    None
+}
+
+/// Parses the (lossless) `fragment` of the `tokens` using the given grammar
+/// rule, provided it consumes the whole fragment without any syntax errors.
+fn reparse_fragment(
+    tokens: &TokenStream,
+    fragment: &[Token],
+    reparser: fn(&mut parser::Parser),
+) -> Option<GreenNode> {
+    let mut tokens = tokens.clone();
+    tokens.set_tokens(fragment.to_vec());
+
+    let mut token_source = TextTokenSource::new(tokens.clone());
+    let mut tree_sink = TextTreeSink::new(tokens);
+    parser::reparse(&mut token_source, &mut tree_sink, reparser);
+
+    let (green, errors) = tree_sink.finish();
+    let len = fragment
+        .iter()
+        .map(|token| token.lexeme().len())
+        .sum::<usize>();
+
+    (errors.is_empty() && usize::from(green.text_len()) == len).then_some(green)
+}
+
+/// Whether the reparsed `fragment` begins and ends with the same kinds of
+/// (significant) tokens as the `node` it replaces, so that the surrounding
+/// grammar rules make the same decisions about it as before.
+fn same_delimiters(node: &SyntaxNode, fragment: &[Token]) -> bool {
+    let significant =
+        |kind: SyntaxKind| !matches!(kind, SyntaxKind::WHITESPACE | SyntaxKind::COMMENT);
+
+    let old_first = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .map(|token| token.kind())
+        .find(|&kind| significant(kind));
+    let new_first = fragment
+        .iter()
+        .map(|token| token.kind().to_syntax())
+        .find(|&kind| significant(kind));
+    let old_last = node.last_token().map(|token| token.kind());
+    let new_last = fragment.last().map(|token| token.kind().to_syntax());
+
+    old_first.is_some() && old_first == new_first && old_last == new_last
+}
+
+/// Whether the old token and the relexed token are the same, after shifting
+/// the former by `delta` bytes.
+fn same_token(old: &SyntaxToken, new: &Token, delta: isize) -> bool {
+    let range = old.text_range();
+    let start = (usize::from(range.start()) as isize + delta) as usize;
+
+    old.kind() == new.kind().to_syntax()
+        && old.text() == new.lexeme()
+        && new.span() == &Span::new(start, start + usize::from(range.len()))
+}
+
+/// The index of the `token` within all `tokens` of the tree.
+fn token_index(tokens: &[SyntaxToken], token: &SyntaxToken) -> usize {
+    let start = tokens.partition_point(|it| it.text_range().start() < token.text_range().start());
+
+    start
+        + tokens[start..]
+            .iter()
+            .position(|it| it == token)
+            .expect("token belongs to the tree")
+}
+
+/// Whether the given token begins a line (i.e. only follows a newline, if
+/// anything).
+fn starts_line(token: &SyntaxToken) -> bool {
+    non_empty_tokens(token.prev_token(), SyntaxToken::prev_token)
+        .next()
+        .map(|prev| prev.text().ends_with('\n'))
+        .unwrap_or(true)
+}
+
+/// The tokens from `first` onwards (in either direction), skipping those
+/// without any text (e.g. `INDENT` and `OUTDENT` tokens).
+fn non_empty_tokens(
+    first: Option<SyntaxToken>,
+    successor: fn(&SyntaxToken) -> Option<SyntaxToken>,
+) -> impl Iterator<Item = SyntaxToken> {
+    iter::successors(first, successor).filter(|token| !token.text().is_empty())
+}
+
+/// Whether any of the `errors` intersects (or touches) the given `range`.
+fn touches_errors(errors: &[SyntaxError], range: TextRange) -> bool {
+    errors
+        .iter()
+        .any(|error| error.range().intersect(range).is_some())
+}
+
+/// The `errors` outside the replaced `range`, with those following it moved
+/// past the `new_range` replacing it.
+fn shifted_errors(
+    errors: &[SyntaxError],
+    range: TextRange,
+    new_range: TextRange,
+) -> Vec<SyntaxError> {
+    let delta = usize::from(new_range.end()) as isize - usize::from(range.end()) as isize;
+
+    errors
+        .iter()
+        .cloned()
+        .map(|error| {
+            if error.range().start() >= range.end() {
+                shifted(error, delta)
+            } else {
+                error
+            }
+        })
+        .collect()
+}
+
+fn shifted(error: SyntaxError, delta: isize) -> SyntaxError {
+    let range = error.range();
+    let shift = |offset: TextSize| TextSize::from((usize::from(offset) as isize + delta) as u32);
+
+    error.with_range(TextRange::new(shift(range.start()), shift(range.end())))
+}
@@ -1,11 +1,13 @@
 use std::mem;
 
-use logos::Source;
 use rowan::{GreenNode, TextRange, TextSize};
 
 use crate::{
-    lexer::TokenStream, parser::ParseError, syntax_error::SyntaxError,
-    syntax_tree::SyntaxTreeBuilder, SyntaxKind,
+    lexer::{TokenKindExt, TokenStream},
+    parser::ParseError,
+    syntax_error::SyntaxError,
+    syntax_tree::SyntaxTreeBuilder,
+    SyntaxKind,
 };
 
 use super::TreeSink;
@@ -60,30 +62,20 @@ impl TextTreeSink {
             if !token.is_trivia() {
                 break;
             }
-            self.do_token(
-                token.kind().to_syntax(),
-                TextSize::from(token.span().len() as u32),
-                1,
-            );
+            self.do_token(token.kind().to_syntax(), 1);
         }
     }
 
-    fn do_token(&mut self, kind: SyntaxKind, len: TextSize, n_tokens: usize) {
-        let range = TextRange::at(self.text_pos, len);
-        let text = self
-            .tokens
-            .text()
-            .slice(range.into())
-            .expect("invalid range")
-            .to_string();
-        self.text_pos += len;
-        self.tokens.advance_n(n_tokens);
-        self.inner.add_raw_token(kind, &text);
-
-        // let text = &self.text[range];
-        // self.text_pos += len;
-        // self.token_pos += n_tokens;
-        // self.inner.token(kind, text);
+    fn do_token(&mut self, kind: SyntaxKind, n_tokens: usize) {
+        for _ in 0..n_tokens {
+            let Some(token) = self.tokens.peek().cloned() else {
+                break;
+            };
+
+            self.text_pos += TextSize::from(token.lexeme().len() as u32);
+            self.tokens.advance_n(1);
+            self.inner.add_raw_token(kind, token.lexeme());
+        }
     }
 }
 
@@ -95,14 +87,9 @@ impl TreeSink for TextTreeSink {
             State::Normal => (),
         }
 
-        // Get the token from the token stream
-        let token = self.tokens.next().unwrap(); // TODO refactor here a bit
-
-        // Add the token to the syntax tree
-        self.inner.add_token(&token);
-
-        // Advance the text position
-        self.text_pos += TextSize::from(token.lexeme().len() as u32);
+        // Attach any leading trivia before adding the token(s) to the syntax tree
+        self.eat_trivias();
+        self.do_token(kind, n_tokens as usize);
     }
 
     fn start_node(&mut self, kind: SyntaxKind) {
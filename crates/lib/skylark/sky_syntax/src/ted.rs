@@ -0,0 +1,179 @@
+//! **Primitive operations** for editing **mutable** syntax trees in place, i.e.
+//! trees obtained via [`AstNode::clone_for_update`].
+//!
+//! These operate on untyped syntax elements and don't try to be clever about
+//! trivia, so the **whitespace** around inserted (or removed) elements is up to
+//! the caller. The typed, layout-aware editing API (e.g.
+//! [`ast::CallSuffix::set_keyword_argument`]) is built on top of them.
+//!
+//! Elements which are inserted may come from anywhere: **immutable** nodes
+//! (e.g. from [`ast::make`]) are copied, while elements of a mutable tree are
+//! **detached** from it first, i.e. moved.
+//!
+//! [`ast`]: crate::ast
+//! [`ast::make`]: crate::ast::make
+//! [`ast::CallSuffix::set_keyword_argument`]: crate::ast::CallSuffix::set_keyword_argument
+
+use std::ops::RangeInclusive;
+
+use rowan::{GreenNode, NodeOrToken};
+
+use crate::{
+    ast::AstNode,
+    lang::{SyntaxElement, SyntaxNode, SyntaxToken},
+    SyntaxKind,
+};
+
+/// Anything which can be converted into a [`SyntaxElement`], so that nodes,
+/// tokens and typed AST nodes can all be edited alike.
+pub trait Element {
+    fn syntax_element(self) -> SyntaxElement;
+}
+
+impl<N: AstNode> Element for N {
+    fn syntax_element(self) -> SyntaxElement {
+        self.syntax().clone().into()
+    }
+}
+
+impl Element for SyntaxNode {
+    fn syntax_element(self) -> SyntaxElement {
+        self.into()
+    }
+}
+
+impl Element for SyntaxToken {
+    fn syntax_element(self) -> SyntaxElement {
+        self.into()
+    }
+}
+
+impl Element for SyntaxElement {
+    fn syntax_element(self) -> SyntaxElement {
+        self
+    }
+}
+
+/// A **position** in a mutable syntax tree, relative to an existing element.
+#[derive(Debug)]
+pub struct Position {
+    parent: SyntaxNode,
+    index: usize,
+}
+
+impl Position {
+    /// The position right **after** the given element.
+    pub fn after(elem: impl Element) -> Position {
+        let elem = elem.syntax_element();
+        Position {
+            parent: parent_of(&elem),
+            index: elem.index() + 1,
+        }
+    }
+
+    /// The position right **before** the given element.
+    pub fn before(elem: impl Element) -> Position {
+        let elem = elem.syntax_element();
+        Position {
+            parent: parent_of(&elem),
+            index: elem.index(),
+        }
+    }
+
+    /// The position of the **first child** of the given node.
+    pub fn first_child_of(node: &SyntaxNode) -> Position {
+        Position {
+            parent: node.clone(),
+            index: 0,
+        }
+    }
+
+    /// The position of the **last child** of the given node, i.e. after all of
+    /// its children.
+    pub fn last_child_of(node: &SyntaxNode) -> Position {
+        Position {
+            parent: node.clone(),
+            index: node.children_with_tokens().count(),
+        }
+    }
+}
+
+/// **Inserts** the given element at the given position.
+pub fn insert(position: Position, elem: impl Element) {
+    insert_all(position, vec![elem.syntax_element()]);
+}
+
+/// **Inserts** the given elements, in order, at the given position.
+pub fn insert_all(position: Position, elements: Vec<SyntaxElement>) {
+    let elements = elements.into_iter().map(into_mutable).collect();
+    position
+        .parent
+        .splice_children(position.index..position.index, elements);
+}
+
+/// **Removes** the given element from its tree.
+pub fn remove(elem: impl Element) {
+    elem.syntax_element().detach();
+}
+
+/// **Removes** the given (inclusive) range of **siblings** from their tree.
+pub fn remove_all(range: RangeInclusive<SyntaxElement>) {
+    replace_all(range, Vec::new());
+}
+
+/// **Replaces** the given element with another one.
+pub fn replace(old: impl Element, new: impl Element) {
+    let old = old.syntax_element();
+    replace_all(old.clone()..=old, vec![new.syntax_element()]);
+}
+
+/// **Replaces** the given (inclusive) range of **siblings** with the given
+/// elements.
+pub fn replace_all(range: RangeInclusive<SyntaxElement>, new: Vec<SyntaxElement>) {
+    let (start, end) = range.into_inner();
+    let parent = parent_of(&start);
+    assert_eq!(
+        Some(&parent),
+        end.parent().as_ref(),
+        "Expected the range to be made up of siblings"
+    );
+
+    let new = new.into_iter().map(into_mutable).collect();
+    parent.splice_children(start.index()..end.index() + 1, new);
+}
+
+fn parent_of(elem: &SyntaxElement) -> SyntaxNode {
+    elem.parent()
+        .unwrap_or_else(|| panic!("Expected {elem:?} to have a parent"))
+}
+
+/// Returns a **detached**, **mutable** version of the given element, which
+/// can be inserted into a mutable tree.
+fn into_mutable(elem: SyntaxElement) -> SyntaxElement {
+    match elem {
+        NodeOrToken::Node(node) if node.is_mutable() => {
+            node.detach();
+            node.into()
+        }
+        NodeOrToken::Node(node) => node.clone_subtree().clone_for_update().into(),
+        NodeOrToken::Token(token) => match token.parent() {
+            Some(parent) if parent.is_mutable() => {
+                token.detach();
+                token.into()
+            }
+            Some(_) => {
+                // Tokens can't be cloned on their own, so the token is copied
+                // into a (mutable) tree of its own, from which it's detached.
+                let root = SyntaxNode::new_root(GreenNode::new(
+                    rowan::SyntaxKind(u16::from(SyntaxKind::ERROR)),
+                    [NodeOrToken::Token(token.green().to_owned())],
+                ))
+                .clone_for_update();
+                let token = root.first_token().unwrap();
+                token.detach();
+                token.into()
+            }
+            None => token.into(),
+        },
+    }
+}
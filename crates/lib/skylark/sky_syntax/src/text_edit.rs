@@ -0,0 +1,44 @@
+//! **Text edits**, i.e. the replacement of a range of text by some other text,
+//! as used for [incremental reparsing](crate::Parse::reparse).
+
+use rowan::{TextRange, TextSize};
+
+/// A single **edit** of a source text, replacing the text within `delete`
+/// by `insert`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    /// The range of the text to be replaced (empty for a pure insertion).
+    pub delete: TextRange,
+    /// The replacement text (empty for a pure deletion).
+    pub insert: String,
+}
+
+impl TextEdit {
+    /// Creates an edit replacing the text within `range` by `text`.
+    pub fn replace(range: TextRange, text: impl Into<String>) -> Self {
+        Self {
+            delete: range,
+            insert: text.into(),
+        }
+    }
+
+    /// Creates an edit inserting `text` at the given `offset`.
+    pub fn insert(offset: TextSize, text: impl Into<String>) -> Self {
+        Self::replace(TextRange::empty(offset), text)
+    }
+
+    /// Creates an edit deleting the text within `range`.
+    pub fn delete(range: TextRange) -> Self {
+        Self::replace(range, String::new())
+    }
+
+    /// Applies the edit to the given `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deleted range is out of bounds of, or doesn't fall on
+    /// character boundaries within, `text`.
+    pub fn apply(&self, text: &mut String) {
+        text.replace_range(std::ops::Range::<usize>::from(self.delete), &self.insert);
+    }
+}
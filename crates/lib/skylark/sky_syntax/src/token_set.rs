@@ -24,7 +24,10 @@
 //! [`SyntaxKind`]: crate::SyntaxKind
 //! [`TokenKind`]: crate::TokenKind
 
-use crate::{lexer::TokenKind, SyntaxKind};
+use crate::{
+    lexer::{TokenKind, TokenKindExt},
+    SyntaxKind,
+};
 
 /// A _memory-efficient_ **bit-set** for _managing_ and _querying_ a **collection** of [`SyntaxKind`]s /
 /// [`TokenKind`]s.
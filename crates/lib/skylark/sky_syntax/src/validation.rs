@@ -0,0 +1,27 @@
+//! **Validation** of the syntax tree, reporting errors which are not caught
+//! by the parser itself (e.g. **malformed numbers** in literals). Invalid
+//! escape sequences in string and bytes literals are already reported by the
+//! lexer.
+
+use crate::{
+    ast::{self, AstToken},
+    syntax_error::SyntaxError,
+    SyntaxKind, SyntaxNode, SyntaxToken,
+};
+
+/// Validates the given **syntax tree**, returning any errors found.
+pub(crate) fn validate(root: &SyntaxNode) -> Vec<SyntaxError> {
+    root.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter_map(validate_token)
+        .collect()
+}
+
+/// Validates a single **token**, returning the error found (if any).
+pub(crate) fn validate_token(token: SyntaxToken) -> Option<SyntaxError> {
+    match token.kind() {
+        SyntaxKind::INT => ast::Int::cast(token)?.big_value().err(),
+        SyntaxKind::FLOAT => ast::Float::cast(token)?.value().err(),
+        _ => None,
+    }
+}
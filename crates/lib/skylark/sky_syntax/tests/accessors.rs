@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod accessors_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{
+        ast::{self, AstNode, AstToken},
+        File,
+    };
+
+    /// Parses the given (valid) source, returning the first node of type `N`.
+    fn node<N: AstNode>(source: &str) -> N {
+        let parse = File::parse(source);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+
+        parse.syntax_node().descendants().find_map(N::cast).unwrap()
+    }
+
+    /// The text of the given node, if any, without any leading trivia.
+    fn text<N: AstNode>(node: Option<N>) -> Option<String> {
+        node.map(|node| node.syntax().text().to_string().trim().to_string())
+    }
+
+    #[test]
+    fn test_def_stmt() {
+        let def = node::<ast::DefStmt>("def f(x, y = 1, *args, **kwargs):\n    return x\n");
+
+        assert_eq!(def.name().unwrap().text(), "f");
+        assert_eq!(
+            def.parameters()
+                .unwrap()
+                .parameters()
+                .map(|parameter| {
+                    (
+                        parameter.name().unwrap().text().to_string(),
+                        text(parameter.default()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                ("x".to_string(), None),
+                ("y".to_string(), Some("1".to_string())),
+                ("args".to_string(), None),
+                ("kwargs".to_string(), None),
+            ]
+        );
+        assert_eq!(def.body().unwrap().statements().count(), 1);
+    }
+
+    #[test]
+    fn test_type_annotations() {
+        let def = node::<ast::DefStmt>(
+            "def f(x: str, y: list[int] = [], *args: int, **kwargs) -> dict[str, int]:\n    return {}\n",
+        );
+
+        assert_eq!(
+            def.parameters()
+                .unwrap()
+                .parameters()
+                .map(|parameter| {
+                    (
+                        parameter.name().unwrap().text().to_string(),
+                        text(parameter.annotation()),
+                        text(parameter.default()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                ("x".to_string(), Some("str".to_string()), None),
+                (
+                    "y".to_string(),
+                    Some("list[int]".to_string()),
+                    Some("[]".to_string())
+                ),
+                ("args".to_string(), Some("int".to_string()), None),
+                ("kwargs".to_string(), None, None),
+            ]
+        );
+        assert_eq!(text(def.return_type()), Some("dict[str, int]".to_string()));
+
+        // The parameters of a lambda can't be annotated, as its body follows
+        // a colon.
+        let lambda = node::<ast::LambdaExpr>("f = lambda x: x\n");
+        let parameter = lambda.parameters().unwrap().parameters().next().unwrap();
+        assert_eq!(text(parameter.annotation()), None);
+        assert_eq!(text(lambda.body()), Some("x".to_string()));
+    }
+
+    #[test]
+    fn test_load_stmt() {
+        let load = node::<ast::LoadStmt>("load(\"//foo:bar.bzl\", \"baz\", qux = \"quux\")\n");
+
+        assert_eq!(load.module().unwrap().value().unwrap(), "//foo:bar.bzl");
+        assert_eq!(
+            load.load_symbols()
+                .map(|symbol| {
+                    (
+                        symbol.alias().map(|alias| alias.text().to_string()),
+                        symbol.symbol().unwrap().value().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (None, "baz".to_string()),
+                (Some("qux".to_string()), "quux".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_suffix() {
+        let call = node::<ast::CallSuffix>("f(x, y = 1, *args, **kwargs)\n");
+        let (keyword, positional): (Vec<_>, Vec<_>) = call
+            .arguments()
+            .unwrap()
+            .arguments()
+            .partition(|argument| argument.name().is_some());
+
+        assert_eq!(
+            positional
+                .into_iter()
+                .map(|argument| text(argument.value()).unwrap())
+                .collect::<Vec<_>>(),
+            vec!["x", "args", "kwargs"]
+        );
+        assert_eq!(
+            keyword
+                .into_iter()
+                .map(|argument| argument.name().unwrap().text().to_string())
+                .collect::<Vec<_>>(),
+            vec!["y"]
+        );
+    }
+
+    #[rstest]
+    #[case::mixed("f(x, y = 1, *args, **kwargs)\n", &["x", "*args"], &["y = 1", "**kwargs"])]
+    #[case::positional("f(x, y,)\n", &["x", "y"], &[])]
+    #[case::keyword("f(y = 1)\n", &[], &["y = 1"])]
+    #[case::empty("f()\n", &[], &[])]
+    fn test_call_arguments(
+        #[case] source: &str,
+        #[case] positional: &[&str],
+        #[case] keyword: &[&str],
+    ) {
+        let call = node::<ast::CallSuffix>(source);
+
+        assert_eq!(
+            call.positional()
+                .map(|argument| text(Some(argument)).unwrap())
+                .collect::<Vec<_>>(),
+            positional
+        );
+        assert_eq!(
+            call.keyword()
+                .map(|argument| text(Some(argument)).unwrap())
+                .collect::<Vec<_>>(),
+            keyword
+        );
+    }
+
+    #[test]
+    fn test_if_stmt() {
+        let if_stmt = node::<ast::IfStmt>(
+            "if a:\n    pass\nelif b:\n    pass\nelif c:\n    pass\nelse:\n    pass\n",
+        );
+
+        assert_eq!(text(if_stmt.condition()).unwrap(), "a");
+        assert_eq!(
+            if_stmt
+                .elif_clauses()
+                .map(|clause| text(clause.condition()).unwrap())
+                .collect::<Vec<_>>(),
+            vec!["b", "c"]
+        );
+        assert!(if_stmt.else_clause().unwrap().body().is_some());
+    }
+
+    #[test]
+    fn test_positional_children_of_the_same_type() {
+        let if_expr = node::<ast::IfExpr>("x = a if b else c\n");
+        assert_eq!(text(if_expr.then_branch()).unwrap(), "a");
+        assert_eq!(text(if_expr.condition()).unwrap(), "b");
+        assert_eq!(text(if_expr.else_branch()).unwrap(), "c");
+
+        let entry = node::<ast::Entry>("x = {a: b}\n");
+        assert_eq!(text(entry.key()).unwrap(), "a");
+        assert_eq!(text(entry.value()).unwrap(), "b");
+
+        let assign = node::<ast::AssignStmt>("x = 1, 2\n");
+        assert_eq!(text(assign.lhs()).unwrap(), "x");
+        assert_eq!(assign.rhs().unwrap().tests().count(), 2);
+    }
+
+    #[rstest]
+    #[case::augmented("x += 1\n", "x", "1")]
+    #[case::floor_division("x //= 2\n", "x", "2")]
+    #[case::index("x[0] = 1\n", "x[0]", "1")]
+    #[case::attribute("x.y -= 1\n", "x.y", "1")]
+    #[case::tuple("a, b = b, a\n", "a, b", "b, a")]
+    fn test_assign_stmt_targets(#[case] source: &str, #[case] lhs: &str, #[case] rhs: &str) {
+        let assign = node::<ast::AssignStmt>(source);
+        assert_eq!(text(assign.lhs()).unwrap(), lhs);
+        assert_eq!(text(assign.rhs()).unwrap(), rhs);
+    }
+
+    #[rstest]
+    #[case::index("x[a]\n", Some("a"), None, None, None)]
+    #[case::full("x[a:b:c]\n", None, Some("a"), Some("b"), Some("c"))]
+    #[case::start("x[a:]\n", None, Some("a"), None, None)]
+    #[case::end("x[:b]\n", None, None, Some("b"), None)]
+    #[case::step("x[::c]\n", None, None, None, Some("c"))]
+    #[case::empty("x[:]\n", None, None, None, None)]
+    fn test_slice_suffix(
+        #[case] source: &str,
+        #[case] index: Option<&str>,
+        #[case] start: Option<&str>,
+        #[case] end: Option<&str>,
+        #[case] step: Option<&str>,
+    ) {
+        let slice = node::<ast::SliceSuffix>(source);
+
+        assert_eq!(text(slice.index()).as_deref(), index);
+        assert_eq!(text(slice.start()).as_deref(), start);
+        assert_eq!(text(slice.end()).as_deref(), end);
+        assert_eq!(text(slice.step()).as_deref(), step);
+    }
+
+    #[test]
+    fn test_enums_look_through_wrapper_nodes() {
+        let file = File::parse("def f():\n    pass\nx = 1; y = 2\n").tree();
+        let statements = file.statements().collect::<Vec<_>>();
+
+        assert!(matches!(statements[0], ast::Statement::DefStmt(_)));
+        let ast::Statement::SimpleStmt(simple) = &statements[1] else {
+            panic!("Expected a simple statement, but got {:?}", statements[1]);
+        };
+        assert!(simple
+            .small_stmts()
+            .all(|small| matches!(small, ast::SmallStmt::AssignStmt(_))));
+        assert_eq!(simple.small_stmts().count(), 2);
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(test)]
+mod edit_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{
+        ast::{self, make, AstNode, AstToken},
+        File,
+    };
+
+    /// Applies the given edit to the first node of type `N` in a mutable copy
+    /// of the given source, returning the edited text. The edited text must
+    /// parse without errors, and the original tree must be left untouched.
+    fn edit<N: AstNode>(source: &str, f: impl FnOnce(N)) -> String {
+        let file = File::parse(source).tree();
+        let updated = file.clone_for_update();
+
+        f(updated.syntax().descendants().find_map(N::cast).unwrap());
+
+        let text = updated.syntax().to_string();
+        let parse = File::parse(&text);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+        assert_eq!(file.syntax().to_string(), source);
+
+        text
+    }
+
+    fn string(value: &str) -> ast::Test {
+        make::string_expr(value).into()
+    }
+
+    #[rstest]
+    #[case::replace(
+        "cc_library(name = \"foo\", srcs = [\"a.cc\"])\n",
+        "cc_library(name = \"foo\", srcs = \"b.cc\")\n"
+    )]
+    #[case::replace_multiline(
+        "cc_library(\n    srcs = [\"a.cc\"],  # comment\n    name = \"foo\",\n)\n",
+        "cc_library(\n    srcs = \"b.cc\",  # comment\n    name = \"foo\",\n)\n"
+    )]
+    #[case::insert(
+        "cc_library(name = \"foo\")\n",
+        "cc_library(name = \"foo\", srcs = \"b.cc\")\n"
+    )]
+    #[case::insert_trailing_comma(
+        "cc_library(name = \"foo\",)\n",
+        "cc_library(name = \"foo\", srcs = \"b.cc\",)\n"
+    )]
+    #[case::insert_multiline(
+        "cc_library(\n    name = \"foo\",\n)\n",
+        "cc_library(\n    name = \"foo\",\n    srcs = \"b.cc\",\n)\n"
+    )]
+    #[case::insert_multiline_without_trailing_comma(
+        "cc_library(\n    name = \"foo\"\n)\n",
+        "cc_library(\n    name = \"foo\",\n    srcs = \"b.cc\"\n)\n"
+    )]
+    #[case::insert_empty("cc_library()\n", "cc_library(srcs = \"b.cc\")\n")]
+    fn test_set_keyword_argument(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |call: ast::CallSuffix| {
+            call.set_keyword_argument("srcs", string("b.cc"));
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[rstest]
+    #[case::single_line("f(a, b)\n", "f(x, a, b)\n")]
+    #[case::multiline("f(\n    a,\n    b,\n)\n", "f(\n    x,\n    a,\n    b,\n)\n")]
+    fn test_insert_first_argument(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |call: ast::CallSuffix| {
+            call.insert_argument(0, make::argument(make::name_expr("x").into()));
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_insert_argument_in_the_middle() {
+        let text = edit("f(a, c)\n", |call: ast::CallSuffix| {
+            call.insert_argument(1, make::argument(make::name_expr("b").into()));
+        });
+
+        assert_eq!(text, "f(a, b, c)\n");
+    }
+
+    #[test]
+    fn test_insert_argument_after_trailing_comment() {
+        let text = edit(
+            "f(\n    a,  # comment\n    c,\n)\n",
+            |call: ast::CallSuffix| {
+                call.insert_argument(1, make::argument(make::name_expr("b").into()));
+            },
+        );
+
+        assert_eq!(text, "f(\n    a,  # comment\n    b,\n    c,\n)\n");
+    }
+
+    #[rstest]
+    #[case::middle("f(a, b = 1, c)\n", "f(a, c)\n")]
+    #[case::first("f(b = 1, c)\n", "f(c)\n")]
+    #[case::last("f(a, b = 1)\n", "f(a)\n")]
+    #[case::only("f(b = 1)\n", "f()\n")]
+    #[case::only_trailing_comma("f(b = 1,)\n", "f()\n")]
+    #[case::multiline_first("f(\n    b = 1,\n    c,\n)\n", "f(\n    c,\n)\n")]
+    #[case::multiline_last("f(\n    a,\n    b = 1,\n)\n", "f(\n    a,\n)\n")]
+    fn test_remove_keyword_argument(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |call: ast::CallSuffix| {
+            let removed = call.remove_keyword_argument("b").unwrap();
+            assert_eq!(removed.name().unwrap().text(), "b");
+            assert!(call.keyword_argument("b").is_none());
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_remove_missing_keyword_argument() {
+        let text = edit("f(a, c = 1)\n", |call: ast::CallSuffix| {
+            assert!(call.remove_keyword_argument("b").is_none());
+        });
+
+        assert_eq!(text, "f(a, c = 1)\n");
+    }
+
+    #[rstest]
+    #[case::empty("x = []\n", "x = [\"c\"]\n")]
+    #[case::single_line("x = [\"a\", \"b\"]\n", "x = [\"a\", \"b\", \"c\"]\n")]
+    #[case::trailing_comma("x = [\"a\",]\n", "x = [\"a\", \"c\",]\n")]
+    #[case::multiline(
+        "x = [\n    \"a\",  # comment\n    \"b\",\n]\n",
+        "x = [\n    \"a\",  # comment\n    \"b\",\n    \"c\",\n]\n"
+    )]
+    #[case::multiline_without_trailing_comma(
+        "x = [\n    \"a\",\n    \"b\"\n]\n",
+        "x = [\n    \"a\",\n    \"b\",\n    \"c\"\n]\n"
+    )]
+    #[case::trailing_comment(
+        "x = [\n    \"a\",\n    \"b\",  # comment\n]\n",
+        "x = [\n    \"a\",\n    \"b\",  # comment\n    \"c\",\n]\n"
+    )]
+    #[case::trailing_comment_without_trailing_comma(
+        "x = [\n    \"a\",\n    \"b\"  # comment\n]\n",
+        "x = [\n    \"a\",\n    \"b\",  # comment\n    \"c\"\n]\n"
+    )]
+    fn test_push_element(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |list: ast::ListExpr| list.push_element(string("c")));
+
+        assert_eq!(text, expected);
+    }
+
+    /// The first element of the given list which is the given string.
+    fn element(list: &ast::ListExpr, value: &str) -> ast::Test {
+        list.expression()
+            .unwrap()
+            .tests()
+            .find(|it| it.syntax().last_token().unwrap().text() == format!("{value:?}"))
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case::middle("x = [\"a\", \"b\", \"c\"]\n", "x = [\"a\", \"c\"]\n")]
+    #[case::first("x = [\"b\", \"c\"]\n", "x = [\"c\"]\n")]
+    #[case::only("x = [\"b\"]\n", "x = []\n")]
+    #[case::only_trailing_comma("x = [\"b\",]\n", "x = []\n")]
+    #[case::only_multiline("x = [\n    \"b\",\n]\n", "x = []\n")]
+    #[case::multiline(
+        "x = [\n    \"a\",\n    # comment\n    \"b\",\n    \"c\",\n]\n",
+        "x = [\n    \"a\",\n    \"c\",\n]\n"
+    )]
+    #[case::keeps_comment_of_previous_line(
+        "x = [\n    \"a\",  # comment\n    \"b\",\n]\n",
+        "x = [\n    \"a\",  # comment\n]\n"
+    )]
+    #[case::keeps_comment_of_previous_line_without_trailing_comma(
+        "x = [\n    \"a\",  # comment\n    \"b\"\n]\n",
+        "x = [\n    \"a\"  # comment\n]\n"
+    )]
+    fn test_remove_element(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |list: ast::ListExpr| {
+            list.remove_element(&element(&list, "b"));
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[rstest]
+    #[case::single_line(
+        "x = [\n    # keep sorted\n    \"b\",  # TODO\n    \"c\",\n]\ny = [\"a\"]\n",
+        "x = [\n    \"c\",\n]\ny = [\n    \"a\",\n    # keep sorted\n    \"b\",  # TODO\n]\n"
+    )]
+    #[case::multiline(
+        "x = [\n    \"a\",\n    \"b\"  # TODO\n]\ny = [\n    \"c\",\n]\n",
+        "x = [\n    \"a\"\n]\ny = [\n    \"c\",\n    \"b\",  # TODO\n]\n"
+    )]
+    fn test_move_element_with_comments(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |file: File| {
+            let lists = file
+                .syntax()
+                .descendants()
+                .filter_map(ast::ListExpr::cast)
+                .collect::<Vec<_>>();
+            let moved = element(&lists[0], "b");
+            let comments = lists[0].remove_element_with_comments(&moved);
+            lists[1].push_element_with_comments(moved, comments);
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[rstest]
+    #[case::single_line("x = [\"a\", \"b\"]\n", "x = [\"a\", \"c\"]\n")]
+    #[case::multiline(
+        "x = [\n    # comment\n    \"b\",\n]\n",
+        "x = [\n    # comment\n    \"c\",\n]\n"
+    )]
+    fn test_replace_element(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |list: ast::ListExpr| {
+            list.replace_element(&element(&list, "b"), string("c"));
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[rstest]
+    #[case::single_line(
+        "load(\"//:defs.bzl\", \"a\")\n",
+        "load(\"//:defs.bzl\", \"a\", \"b\")\n"
+    )]
+    #[case::only_module("load(\"//:defs.bzl\")\n", "load(\"//:defs.bzl\", \"b\")\n")]
+    #[case::multiline(
+        "load(\n    \"//:defs.bzl\",\n    \"a\",\n)\n",
+        "load(\n    \"//:defs.bzl\",\n    \"a\",\n    \"b\",\n)\n"
+    )]
+    #[case::already_loaded(
+        "load(\"//:defs.bzl\", \"a\", \"b\")\n",
+        "load(\"//:defs.bzl\", \"a\", \"b\")\n"
+    )]
+    fn test_add_symbol(#[case] source: &str, #[case] expected: &str) {
+        let text = edit(source, |load: ast::LoadStmt| {
+            load.add_symbol(make::load_symbol(None, "b"));
+        });
+
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_add_aliased_symbol() {
+        let text = edit("load(\"//:defs.bzl\", \"b\")\n", |load: ast::LoadStmt| {
+            load.add_symbol(make::load_symbol(Some("c"), "b"));
+        });
+
+        assert_eq!(text, "load(\"//:defs.bzl\", \"b\", c = \"b\")\n");
+    }
+
+    #[test]
+    fn test_rename() {
+        let text = edit(
+            "def foo(x):  # comment\n    return x\n",
+            |def: ast::DefStmt| {
+                def.name().unwrap().rename("bar");
+                assert_eq!(def.name().unwrap().text(), "bar");
+            },
+        );
+
+        assert_eq!(text, "def bar(x):  # comment\n    return x\n");
+    }
+
+    #[test]
+    fn test_edits_compose() {
+        let text = edit(
+            "cc_library(\n    name = \"foo\",\n    srcs = [\"foo.cc\"],\n    deps = [],\n)\n",
+            |call: ast::CallSuffix| {
+                let srcs = call.keyword_argument("srcs").unwrap().value().unwrap();
+                let ast::Test::PrimaryExpr(srcs) = srcs else {
+                    panic!("Expected a list, but got {srcs:?}");
+                };
+                srcs.operand()
+                    .and_then(|it| it.list_expr())
+                    .unwrap()
+                    .push_element(string("bar.cc"));
+
+                call.remove_keyword_argument("deps");
+                call.set_keyword_argument("visibility", make::list_expr([]).into());
+            },
+        );
+
+        assert_eq!(
+            text,
+            "cc_library(\n    name = \"foo\",\n    srcs = [\"foo.cc\", \"bar.cc\"],\n    visibility = [],\n)\n"
+        );
+    }
+}
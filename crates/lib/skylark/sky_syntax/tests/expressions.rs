@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod expressions_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{
+        ast::{self, AstNode, BinaryOp, UnaryOp},
+        File,
+    };
+
+    /// Renders the given expression fully parenthesized, making the structure of
+    /// any (nested) unary and binary expressions explicit.
+    fn parenthesized(test: ast::Test) -> String {
+        match test {
+            ast::Test::BinaryExpr(binary) => format!(
+                "({} {} {})",
+                parenthesized(binary.lhs().unwrap()),
+                binary.binop().unwrap().syntax().text().to_string().trim(),
+                parenthesized(binary.rhs().unwrap())
+            ),
+            ast::Test::UnaryExpr(unary) => format!(
+                "({} {})",
+                unary.op_token().unwrap().text(),
+                parenthesized(unary.operand().unwrap())
+            ),
+            test => test.syntax().text().to_string().trim().to_string(),
+        }
+    }
+
+    /// The outermost expression of the given single-expression source.
+    fn expression(source: &str) -> ast::Test {
+        let parse = File::parse(source);
+
+        assert_eq!(parse.syntax_node().to_string(), source);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+
+        parse.syntax_node().descendants().find_map(ast::Test::cast).unwrap()
+    }
+
+    #[rstest]
+    #[case::or_and("a or b and c", "(a or (b and c))")]
+    #[case::and_not("not a and b", "((not a) and b)")]
+    #[case::not_comparison("not a == b", "(not (a == b))")]
+    #[case::comparison_bitor("a == b | c", "(a == (b | c))")]
+    #[case::in_bitor("a in b | c", "(a in (b | c))")]
+    #[case::not_in("a not in b + c", "(a not in (b + c))")]
+    #[case::bitor_bitxor("a | b ^ c", "(a | (b ^ c))")]
+    #[case::bitxor_bitand("a ^ b & c", "(a ^ (b & c))")]
+    #[case::bitand_shift("a & b << c", "(a & (b << c))")]
+    #[case::shift_additive("a >> b - c", "(a >> (b - c))")]
+    #[case::additive_multiplicative("a + b * c", "(a + (b * c))")]
+    #[case::multiplicative_unary("-a * ~b", "((- a) * (~ b))")]
+    #[case::floor_div_rem("a // b % c", "((a // b) % c)")]
+    #[case::left_associative("a - b - c", "((a - b) - c)")]
+    #[case::left_associative_or("a or b or c", "((a or b) or c)")]
+    #[case::nested_unary("- - a", "(- (- a))")]
+    #[case::nested_not("not not a", "(not (not a))")]
+    #[case::parentheses("(a + b) * c", "((a + b) * c)")]
+    #[case::operands("f(x) + y[0] * z.w", "(f(x) + (y[0] * z.w))")]
+    #[case::every_level(
+        "a or b and not c < d | e ^ f & g << h + i * -j",
+        "(a or (b and (not (c < (d | (e ^ (f & (g << (h + (i * (- j)))))))))))"
+    )]
+    fn test_precedence(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(parenthesized(expression(source)), expected);
+    }
+
+    #[rstest]
+    #[case::or("a or b", BinaryOp::Or)]
+    #[case::not_in("a not  in b", BinaryOp::NotIn)]
+    #[case::floor_div("a // b", BinaryOp::FloorDiv)]
+    #[case::ge("a >= b", BinaryOp::GreaterEq)]
+    fn test_binary_op_kind(#[case] source: &str, #[case] expected: BinaryOp) {
+        let ast::Test::BinaryExpr(binary) = expression(source) else {
+            panic!("Expected a binary expression for {source:?}");
+        };
+
+        assert_eq!(binary.op_kind(), Some(expected));
+    }
+
+    #[rstest]
+    #[case::plus("+a", UnaryOp::Plus)]
+    #[case::minus("-a", UnaryOp::Minus)]
+    #[case::invert("~a", UnaryOp::Invert)]
+    #[case::not("not a", UnaryOp::Not)]
+    fn test_unary_op_kind(#[case] source: &str, #[case] expected: UnaryOp) {
+        let ast::Test::UnaryExpr(unary) = expression(source) else {
+            panic!("Expected a unary expression for {source:?}");
+        };
+
+        assert_eq!(unary.op_kind(), Some(expected));
+    }
+
+    #[rstest]
+    #[case::less("a < b < c\n")]
+    #[case::equal("a == b == c\n")]
+    #[case::in_("a in b in c\n")]
+    #[case::mixed("a < b not in c\n")]
+    #[case::in_call("f(a < b <= c)\n")]
+    fn test_chained_comparisons_are_rejected(#[case] source: &str) {
+        let parse = File::parse(source);
+
+        assert_eq!(parse.syntax_node().to_string(), source);
+        assert_eq!(parse.errors().len(), 1, "{}", parse.debug_dump());
+    }
+
+    #[rstest]
+    #[case::and("a < b and b < c\n")]
+    #[case::parenthesized("(a < b) < c\n")]
+    #[case::not("not a < b\n")]
+    fn test_unchained_comparisons_are_accepted(#[case] source: &str) {
+        let parse = File::parse(source);
+
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+    }
+
+    #[test]
+    fn test_not_binds_looser_than_comparisons() {
+        let parse = File::parse("a == not b\n");
+
+        assert_eq!(parse.syntax_node().to_string(), "a == not b\n");
+        assert!(!parse.errors().is_empty(), "{}", parse.debug_dump());
+    }
+
+    #[rstest]
+    #[case::list("[x * 2 for x in xs]\n", "x * 2", &["for x in xs"])]
+    #[case::conditions("[x for x in xs if x if not y]\n", "x", &["for x in xs", "if x", "if not y"])]
+    #[case::nested("[x for y in z for x in y]\n", "x", &["for y in z", "for x in y"])]
+    #[case::if_expr_element("[a if b else c for a in d]\n", "a if b else c", &["for a in d"])]
+    fn test_list_comprehensions(
+        #[case] source: &str,
+        #[case] element: &str,
+        #[case] clauses: &[&str],
+    ) {
+        let comp = expression(source)
+            .syntax()
+            .descendants()
+            .find_map(ast::ListComp::cast)
+            .unwrap();
+
+        assert_eq!(comp.element().unwrap().syntax().text().to_string(), element);
+        assert_eq!(
+            comp.comp_clauses()
+                .map(|it| it.syntax().text().to_string().trim().to_string())
+                .collect::<Vec<_>>(),
+            clauses
+        );
+    }
+
+    #[rstest]
+    #[case::dict_comprehension("{k: v for k, v in e}\n", "DICT_COMP")]
+    #[case::dict("{k: v, \"a\": 1,}\n", "DICT_EXPR")]
+    #[case::empty_dict("{}\n", "DICT_EXPR")]
+    #[case::list("[a, b, c,]\n", "LIST_EXPR")]
+    #[case::empty_list("[]\n", "LIST_EXPR")]
+    fn test_brackets(#[case] source: &str, #[case] kind: &str) {
+        let operand = expression(source)
+            .syntax()
+            .descendants()
+            .find_map(ast::Operand::cast)
+            .unwrap();
+
+        assert_eq!(
+            format!("{:?}", operand.syntax().first_child().unwrap().kind()),
+            kind
+        );
+    }
+}
@@ -1,8 +1,16 @@
+mod accessors;
+mod edit;
+mod expressions;
+mod literals;
+mod make;
+mod recovery;
+mod reparse;
 mod token_set;
 #[cfg(test)]
 mod syntax_test_suite {
     use pretty_assertions_sorted::assert_eq;
-    use sky_syntax::File;
+    use rstest::rstest;
+    use sky_syntax::{File, SyntaxKind};
 
     #[test]
     fn test_syntax() {
@@ -11,6 +19,62 @@ mod syntax_test_suite {
 ";
         let ast = File::parse(source);
         println!("ast: {:#?}", ast);
-        assert_eq!(ast.debug_dump(), source);
+        assert_eq!(ast.syntax_node().to_string(), source);
+        assert!(ast.errors().is_empty(), "{}", ast.debug_dump());
+    }
+
+    #[rstest]
+    #[case::nested_def(
+        "def f(x):\n    for y in x:\n        if y:\n            return y\n    return x\n",
+        3
+    )]
+    #[case::if_elif_else("if a:\n  pass\nelif b:\n  pass\nelse:\n  pass\n", 3)]
+    #[case::blank_lines_and_comments(
+        "def f():\n\n    # comment\n    pass\n\n\ndef g():\n\tpass\n",
+        2
+    )]
+    #[case::single_line_suite("if a: pass\n", 0)]
+    #[case::no_trailing_newline("def f():\n    pass", 1)]
+    fn test_nested_blocks(#[case] source: &str, #[case] indented_suites: usize) {
+        let ast = File::parse(source);
+
+        assert_eq!(ast.syntax_node().to_string(), source);
+        assert!(ast.errors().is_empty(), "{}", ast.debug_dump());
+        assert_eq!(
+            ast.syntax_node()
+                .descendants_with_tokens()
+                .filter(|element| element.kind() == SyntaxKind::INDENT)
+                .count(),
+            indented_suites
+        );
+    }
+
+    #[rstest]
+    #[case::multiline_call(
+        "cc_library(\n    name = \"foo\",\n    srcs = [\n        \"foo.cc\",  # comment\n\n        \"bar.cc\"\n    ]\n)\n"
+    )]
+    #[case::multiline_load("load(\n    \"//foo:bar.bzl\",\n    \"baz\"\n)\n")]
+    #[case::line_continuation("def f():\n    return \\\n        x\n")]
+    fn test_implicit_and_explicit_line_joining(#[case] source: &str) {
+        let ast = File::parse(source);
+
+        assert_eq!(ast.syntax_node().to_string(), source);
+        assert!(ast.errors().is_empty(), "{}", ast.debug_dump());
+        assert_eq!(
+            ast.syntax_node()
+                .descendants_with_tokens()
+                .filter(|element| element.kind() == SyntaxKind::STATEMENT)
+                .count(),
+            if source.starts_with("def") { 2 } else { 1 }
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_dedent_is_reported() {
+        let source = "if a:\n    pass\n  pass\n";
+        let ast = File::parse(source);
+
+        assert_eq!(ast.syntax_node().to_string(), source);
+        assert!(!ast.errors().is_empty());
     }
 }
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod literals_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{
+        ast::{self, AstToken},
+        File, StarlarkLexer, SyntaxToken, TokenKind,
+    };
+
+    /// Lexes the given source, returning the kinds and lexemes of all
+    /// non-trivia tokens (excluding the trailing `NEWLINE` and `EOF`).
+    fn lex(source: &str) -> Vec<(TokenKind, String)> {
+        StarlarkLexer::new()
+            .tokenize(source)
+            .tokens()
+            .tokens()
+            .iter()
+            .filter(|token| {
+                !token.is_trivia() && !matches!(token.kind(), TokenKind::NEWLINE | TokenKind::EOF)
+            })
+            .map(|token| (*token.kind(), token.lexeme().to_string()))
+            .collect()
+    }
+
+    /// Parses the given source, returning the first literal token of the given kind.
+    fn literal<T: AstToken>(source: &str) -> T {
+        File::parse(source)
+            .syntax_node()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find_map(|token: SyntaxToken| T::cast(token))
+            .expect("literal not found")
+    }
+
+    #[rstest]
+    #[case::double_quoted(r#""hello""#, TokenKind::STRING)]
+    #[case::single_quoted(r#"'it\'s'"#, TokenKind::STRING)]
+    #[case::escaped_quote(r#""a\"b""#, TokenKind::STRING)]
+    #[case::triple_quoted("\"\"\"doc\n\"string\"\n\"\"\"", TokenKind::STRING)]
+    #[case::triple_single_quoted("'''a '' b'''", TokenKind::STRING)]
+    #[case::raw(r#"r"\d+""#, TokenKind::STRING)]
+    #[case::raw_escaped_quote(r#"R"\"""#, TokenKind::STRING)]
+    #[case::bytes(r#"b"\xff""#, TokenKind::BYTES)]
+    #[case::raw_bytes(r#"rb'\d'"#, TokenKind::BYTES)]
+    #[case::bytes_raw(r#"Br'\d'"#, TokenKind::BYTES)]
+    #[case::triple_quoted_bytes("b'''\n'''", TokenKind::BYTES)]
+    fn test_lex_literal(#[case] source: &str, #[case] kind: TokenKind) {
+        assert_eq!(lex(source), vec![(kind, source.to_string())]);
+    }
+
+    #[rstest]
+    #[case::single_quoted("\"abc\nx")]
+    #[case::triple_quoted("'''abc\n")]
+    fn test_lex_unterminated(#[case] source: &str) {
+        let token_sink = StarlarkLexer::new().tokenize(source);
+
+        assert_eq!(*token_sink.tokens().tokens()[0].kind(), TokenKind::UNKNOWN);
+        assert!(token_sink.has_errors());
+    }
+
+    #[test]
+    fn test_lex_prefix_is_not_identifier() {
+        assert_eq!(
+            lex("r + rb'x'"),
+            vec![
+                (TokenKind::IDENTIFIER, "r".to_string()),
+                (TokenKind::PLUS, "+".to_string()),
+                (TokenKind::BYTES, "rb'x'".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::plain(r#""hello""#, "hello")]
+    #[case::simple_escapes(r#""a\n\t\\\"b""#, "a\n\t\\\"b")]
+    #[case::hex(r#""\x41""#, "A")]
+    #[case::unicode(r#""caf\u00e9""#, "café")]
+    #[case::long_unicode(r#""\U0001F600""#, "😀")]
+    #[case::octal(r#""\101\0""#, "A\0")]
+    #[case::line_continuation("\"a\\\nb\"", "ab")]
+    #[case::triple_quoted("'''a\n\"b\"'''", "a\n\"b\"")]
+    #[case::raw(r#"r"\d+\n""#, r"\d+\n")]
+    fn test_string_value(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(literal::<ast::String>(source).value().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::plain(r#"b"abc""#, b"abc".to_vec())]
+    #[case::hex(r#"b"\xff\x00""#, vec![0xff, 0x00])]
+    #[case::octal(r#"b"\377""#, vec![0xff])]
+    #[case::unicode(r#"b"\u00e9""#, "é".as_bytes().to_vec())]
+    #[case::raw(r#"rb"\xff""#, br"\xff".to_vec())]
+    fn test_bytes_value(#[case] source: &str, #[case] expected: Vec<u8>) {
+        assert_eq!(literal::<ast::Bytes>(source).value().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_escape(r#"x = "a\d""#, vec![6..8])]
+    #[case::short_hex(r#"x = "\x4""#, vec![5..8])]
+    #[case::non_ascii_hex(r#"x = "\xff""#, vec![5..9])]
+    #[case::octal_out_of_range(r#"x = b"\777""#, vec![6..10])]
+    #[case::surrogate(r#"x = "\ud800""#, vec![5..11])]
+    #[case::multiple(r#"x = "\q \w""#, vec![5..7, 8..10])]
+    fn test_invalid_escapes(#[case] source: &str, #[case] expected: Vec<std::ops::Range<u32>>) {
+        let parse = File::parse(source);
+        let ranges = parse
+            .errors()
+            .iter()
+            .map(|error| error.range().start().into()..error.range().end().into())
+            .collect::<Vec<std::ops::Range<u32>>>();
+
+        assert_eq!(ranges, expected);
+    }
+
+    #[test]
+    fn test_literal_anatomy() {
+        let docstring = literal::<ast::String>("'''doc'''");
+        assert!(docstring.is_triple_quoted());
+        assert!(!docstring.is_raw());
+        assert_eq!(
+            u32::from(docstring.text_range_between_quotes().start())
+                ..u32::from(docstring.text_range_between_quotes().end()),
+            3..6
+        );
+
+        let raw = literal::<ast::Bytes>("rb'x'");
+        assert!(raw.is_raw());
+        assert!(!raw.is_triple_quoted());
+    }
+
+    #[rstest]
+    #[case::decimal("42", TokenKind::INT)]
+    #[case::zero("0", TokenKind::INT)]
+    #[case::hex("0xDEADbeef", TokenKind::INT)]
+    #[case::octal("0o755", TokenKind::INT)]
+    #[case::binary("0b1010", TokenKind::INT)]
+    #[case::leading_zero("012", TokenKind::INT)]
+    #[case::float("1.5", TokenKind::FLOAT)]
+    #[case::trailing_dot("1.", TokenKind::FLOAT)]
+    #[case::leading_dot(".5", TokenKind::FLOAT)]
+    #[case::exponent("1e10", TokenKind::FLOAT)]
+    #[case::signed_exponent("2.5E-3", TokenKind::FLOAT)]
+    fn test_lex_number(#[case] source: &str, #[case] kind: TokenKind) {
+        assert_eq!(lex(source), vec![(kind, source.to_string())]);
+    }
+
+    #[rstest]
+    #[case::decimal("42", 10, 42)]
+    #[case::hex("0x2A", 16, 42)]
+    #[case::octal("0o52", 8, 42)]
+    #[case::binary("0b101010", 2, 42)]
+    #[case::zero("0", 10, 0)]
+    #[case::max("9223372036854775807", 10, i64::MAX)]
+    fn test_int_value(#[case] source: &str, #[case] radix: u32, #[case] expected: i64) {
+        let int = literal::<ast::Int>(source);
+
+        assert_eq!(int.radix(), radix);
+        assert_eq!(int.value().unwrap(), expected);
+        assert_eq!(int.big_value().unwrap(), expected.into());
+    }
+
+    #[test]
+    fn test_int_overflow() {
+        let int = literal::<ast::Int>("0x10000000000000000");
+
+        assert!(int.value().is_err());
+        assert_eq!(int.big_value().unwrap().to_string(), "18446744073709551616");
+    }
+
+    #[rstest]
+    #[case::float("1.5", 1.5)]
+    #[case::trailing_dot("1.", 1.0)]
+    #[case::leading_dot(".5", 0.5)]
+    #[case::exponent("1e10", 1e10)]
+    #[case::signed_exponent("2.5E-3", 2.5e-3)]
+    fn test_float_value(#[case] source: &str, #[case] expected: f64) {
+        assert_eq!(literal::<ast::Float>(source).value().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::leading_zero("x = 012", 4..7)]
+    #[case::empty_hex("x = 0x", 4..6)]
+    #[case::invalid_octal_digit("x = 0o8", 4..7)]
+    #[case::invalid_binary_digit("x = 0b102", 4..9)]
+    #[case::float_out_of_range("x = 1e999", 4..9)]
+    fn test_malformed_numbers(#[case] source: &str, #[case] expected: std::ops::Range<u32>) {
+        let parse = File::parse(source);
+        let ranges = parse
+            .errors()
+            .iter()
+            .map(|error| error.range().start().into()..error.range().end().into())
+            .collect::<Vec<std::ops::Range<u32>>>();
+
+        assert_eq!(ranges, vec![expected]);
+    }
+}
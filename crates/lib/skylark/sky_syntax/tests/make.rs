@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod make_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{
+        ast::{self, make, AstNode, AstToken},
+        File,
+    };
+
+    /// Asserts that the given node renders as `expected`, and that parsing
+    /// its text yields a file without errors.
+    fn assert_renders(node: &impl AstNode, expected: &str) {
+        let text = node.syntax().to_string();
+        assert_eq!(text, expected);
+
+        let parse = File::parse(&text);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+    }
+
+    #[rstest]
+    #[case::plain("foo", r#""foo""#)]
+    #[case::quotes(r#"say "hi""#, r#""say \"hi\"""#)]
+    #[case::backslash(r"C:\dir", r#""C:\\dir""#)]
+    #[case::newline("a\nb\tc\r", r#""a\nb\tc\r""#)]
+    #[case::control("\x00\x7f", r#""\x00\x7f""#)]
+    #[case::unicode("café ☕", r#""café ☕""#)]
+    fn test_string_literal(#[case] value: &str, #[case] expected: &str) {
+        let literal = make::string_literal(value);
+
+        assert_eq!(literal.text(), expected);
+        assert_eq!(literal.value().unwrap(), value);
+    }
+
+    #[test]
+    fn test_call_expr() {
+        let call = make::call_expr(
+            make::name_expr("cc_library"),
+            [
+                make::keyword_argument("name", make::string_expr("foo").into()),
+                make::keyword_argument(
+                    "srcs",
+                    make::list_expr([
+                        make::string_expr("foo.cc").into(),
+                        make::string_expr("bar.cc").into(),
+                    ])
+                    .into(),
+                ),
+                make::argument(make::int_expr(-1)),
+            ],
+        );
+
+        assert_renders(
+            &call,
+            r#"cc_library(name = "foo", srcs = ["foo.cc", "bar.cc"], -1)"#,
+        );
+        assert!(call.call_suffix().is_some());
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_renders(&make::list_expr([]), "[]");
+        assert_renders(
+            &make::dict_expr([
+                make::entry(make::string_expr("a").into(), make::int_expr(1)),
+                make::entry(make::string_expr("b").into(), make::bool_expr(true).into()),
+            ]),
+            r#"{"a": 1, "b": True}"#,
+        );
+        assert_renders(
+            &make::dot_expr(make::name_expr("native"), "glob"),
+            "native.glob",
+        );
+    }
+
+    #[test]
+    fn test_assign_stmt() {
+        let assign = make::assign_stmt(
+            make::name_expr("VERSION").into(),
+            make::string_expr("1.0.0").into(),
+        );
+
+        assert_renders(&assign, "VERSION = \"1.0.0\"\n");
+    }
+
+    #[test]
+    fn test_load_stmt() {
+        let load = make::load_stmt(
+            "//tools:defs.bzl",
+            [
+                make::load_symbol(None, "foo"),
+                make::load_symbol(Some("bar"), "baz"),
+            ],
+        );
+
+        assert_renders(
+            &load,
+            "load(\"//tools:defs.bzl\", \"foo\", bar = \"baz\")\n",
+        );
+
+        let load = load
+            .syntax()
+            .descendants()
+            .find_map(ast::LoadStmt::cast)
+            .unwrap();
+        assert_eq!(load.module().unwrap().value().unwrap(), "//tools:defs.bzl");
+        assert_eq!(load.load_symbols().count(), 2);
+    }
+
+    #[test]
+    fn test_def_stmt() {
+        let def = make::def_stmt(
+            "macro",
+            [
+                make::parameter("name"),
+                make::default_parameter("visibility", make::name_expr("None").into()),
+                make::kwargs_parameter("kwargs"),
+            ],
+            [
+                make::expr_stmt(
+                    make::call_expr(
+                        make::dot_expr(make::name_expr("native"), "filegroup"),
+                        [make::keyword_argument(
+                            "name",
+                            make::name_expr("name").into(),
+                        )],
+                    )
+                    .into(),
+                ),
+                make::return_stmt(None),
+            ],
+        );
+
+        assert_renders(
+            &def,
+            "def macro(name, visibility = None, **kwargs):\n    native.filegroup(name = name)\n    return\n",
+        );
+    }
+
+    #[test]
+    fn test_def_stmt_with_empty_body() {
+        assert_renders(&make::def_stmt("f", [], []), "def f():\n    pass\n");
+    }
+
+    #[test]
+    fn test_def_stmt_reindents_nested_statements() {
+        let parse = File::parse("if a:\n    x = \"\"\"\nb\n\"\"\"\n");
+        let if_stmt = parse.tree().statements().next().unwrap();
+
+        assert_renders(
+            &make::def_stmt("f", [make::args_parameter("args")], [if_stmt]),
+            "def f(*args):\n    if a:\n        x = \"\"\"\nb\n\"\"\"\n",
+        );
+    }
+
+    #[test]
+    fn test_nodes_from_other_trees_are_reused_without_leading_whitespace() {
+        let parse = File::parse("x = 1 + 2\n");
+        let rhs = parse
+            .syntax_node()
+            .descendants()
+            .find_map(ast::AssignStmt::cast)
+            .unwrap()
+            .rhs()
+            .unwrap()
+            .tests()
+            .next()
+            .unwrap();
+
+        assert_renders(
+            &make::assign_stmt(make::name_expr("y").into(), rhs),
+            "y = 1 + 2\n",
+        );
+    }
+
+    #[test]
+    fn test_file() {
+        let file = make::file([
+            make::load_stmt("//:defs.bzl", [make::load_symbol(None, "foo")]),
+            make::expr_stmt(make::call_expr(make::name_expr("foo"), []).into()),
+        ]);
+
+        assert_renders(&file, "load(\"//:defs.bzl\", \"foo\")\nfoo()\n");
+        assert_eq!(file.statements().count(), 2);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::keyword("def")]
+    #[case::digit("1x")]
+    #[case::punctuation("a.b")]
+    #[case::non_ascii("größe")]
+    #[should_panic(expected = "Expected an identifier")]
+    fn test_invalid_identifiers_panic(#[case] name: &str) {
+        assert!(!make::is_identifier(name));
+        make::parameter(name);
+    }
+
+    #[rstest]
+    #[case::ascii("foo_1")]
+    #[case::underscore("_")]
+    #[case::keyword_prefix("define")]
+    fn test_valid_identifiers(#[case] name: &str) {
+        assert!(make::is_identifier(name));
+        assert_renders(&make::parameter(name), name);
+    }
+}
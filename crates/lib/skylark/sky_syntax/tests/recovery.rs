@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod recovery_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rstest::rstest;
+    use sky_syntax::{File, Parse, SyntaxKind};
+
+    const TARGETS: &str = r#"cc_library(
+    name = "foo",
+    srcs = ["foo.cc"],
+)
+
+cc_binary(
+    name = "bar",
+    deps = [":foo"],
+)
+"#;
+
+    /// The top-level calls (e.g. BUILD targets) which were parsed, by name.
+    fn calls(parse: &Parse<File>) -> Vec<String> {
+        parse
+            .syntax_node()
+            .children()
+            .filter(|node| node.kind() == SyntaxKind::STATEMENT)
+            .filter(|node| {
+                node.descendants()
+                    .any(|node| node.kind() == SyntaxKind::CALL_SUFFIX)
+            })
+            .filter_map(|node| {
+                node.descendants_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .find(|token| token.kind() == SyntaxKind::IDENTIFIER)
+            })
+            .map(|token| token.text().to_string())
+            .collect()
+    }
+
+    fn errors(parse: &Parse<File>) -> usize {
+        parse.errors().len()
+    }
+
+    #[rstest]
+    #[case::stray_token("x = $\ny = 1\n", 2)]
+    #[case::stray_closing_bracket("x = 1)\ny = 2\n", 2)]
+    #[case::stray_keyword("else:\n    x = 1\ny = 2\n", 2)]
+    #[case::missing_operand("x = \ny = 2\n", 2)]
+    #[case::missing_argument("f(x, , y)\ng()\n", 2)]
+    #[case::extra_tokens("x = 1 2 3\ny = 2\n", 2)]
+    #[case::missing_colon("def f()\n    pass\ny = 2\n", 2)]
+    #[case::missing_indent("def f():\npass\ny = 2\n", 3)]
+    #[case::unexpected_indent("x = 1\n    y = 2\nz = 3\n", 2)]
+    #[case::unclosed_list("x = [1, 2\ny = 3\n", 2)]
+    #[case::unclosed_string("x = 'foo\ny = 2\n", 2)]
+    fn test_recovers_at_next_statement(#[case] source: &str, #[case] statements: usize) {
+        let parse = File::parse(source);
+
+        assert_eq!(parse.syntax_node().to_string(), source);
+        assert!(!parse.errors().is_empty(), "{}", parse.debug_dump());
+        assert!(
+            parse
+                .syntax_node()
+                .children()
+                .filter(|node| node.kind() == SyntaxKind::STATEMENT)
+                .count()
+                >= statements,
+            "{}",
+            parse.debug_dump()
+        );
+    }
+
+    #[test]
+    fn test_stray_tokens_are_wrapped_in_error_nodes() {
+        let parse = File::parse("x = $ $\ny = )\n");
+        let error_nodes = parse
+            .syntax_node()
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::ERROR)
+            .map(|node| node.text().to_string().trim().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(error_nodes, vec!["$ $", ")"]);
+        assert_eq!(parse.errors().len(), 4, "{}", parse.debug_dump());
+    }
+
+    #[rstest]
+    #[case::missing_closing_paren("\"foo.cc\"],\n)", "\"foo.cc\"]\n")]
+    #[case::missing_comma("name = \"foo\",", "name = \"foo\"")]
+    #[case::unclosed_list("[\"foo.cc\"]", "[\"foo.cc\"")]
+    #[case::stray_token("name = \"foo\"", "name = $ \"foo\"")]
+    fn test_broken_target_doesnt_hide_the_rest(#[case] before: &str, #[case] after: &str) {
+        let source = TARGETS.replacen(before, after, 1);
+        let parse = File::parse(&source);
+
+        assert_eq!(parse.syntax_node().to_string(), source);
+        assert!(!parse.errors().is_empty());
+        assert_eq!(calls(&parse).last().map(String::as_str), Some("cc_binary"));
+
+        // Errors in the targets following the broken one are still reported
+        let source = source.replacen("\"bar\"", "\"bar\" $", 1);
+        let broken_twice = File::parse(&source);
+        assert!(errors(&broken_twice) > errors(&parse));
+    }
+
+    #[rstest]
+    #[case::call("f(x, y,)\n")]
+    #[case::load("load(\"//foo:bar.bzl\", \"baz\",)\n")]
+    #[case::def("def f(x, y,):\n    pass\n")]
+    #[case::list("x = [1, 2,]\n")]
+    #[case::dict("x = {1: 2, 3: 4,}\n")]
+    #[case::tuple("x = (1, 2,)\n")]
+    fn test_trailing_commas(#[case] source: &str) {
+        let parse = File::parse(source);
+
+        assert_eq!(parse.syntax_node().to_string(), source);
+        assert!(parse.errors().is_empty(), "{}", parse.debug_dump());
+    }
+}
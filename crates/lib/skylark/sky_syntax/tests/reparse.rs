@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod reparse_test_suite {
+    use pretty_assertions_sorted::assert_eq;
+    use rowan::{TextRange, TextSize};
+    use rstest::rstest;
+    use sky_syntax::{File, Parse, SyntaxKind, TextEdit};
+
+    const SOURCE: &str = r#"load("//foo:bar.bzl", "baz")
+
+def f(x, y = 1):
+    # comment
+    if x:
+        return [x, y, 2]
+    return {"a": x, "b": g(y, z = 3)}
+
+cc_library(
+    name = "foo",
+    srcs = ["foo.cc", "bar.cc"]
+)
+"#;
+
+    /// Reparses `source` after replacing the first occurrence of `before` by
+    /// `after`, and checks that the result matches parsing from scratch.
+    fn assert_reparse(source: &str, before: &str, after: &str) -> (Parse<File>, Parse<File>) {
+        let start = source.find(before).expect("edited text not found");
+        let edit = TextEdit::replace(
+            TextRange::at(
+                TextSize::from(start as u32),
+                TextSize::from(before.len() as u32),
+            ),
+            after,
+        );
+
+        let old = File::parse(source);
+        let new = old.reparse(edit);
+        assert_same_parse(&new, &File::parse(&source.replacen(before, after, 1)));
+
+        (old, new)
+    }
+
+    fn assert_same_parse(reparsed: &Parse<File>, parsed: &Parse<File>) {
+        assert_eq!(reparsed.debug_dump(), parsed.debug_dump());
+        assert_eq!(reparsed.errors(), parsed.errors());
+    }
+
+    /// Whether any top-level statement of the old and the new file share the
+    /// very same green node, i.e. whether the file was reparsed incrementally.
+    fn reuses_statements(old: &Parse<File>, new: &Parse<File>) -> bool {
+        let statements = |parse: &Parse<File>| {
+            parse
+                .syntax_node()
+                .children()
+                .filter(|node| node.kind() == SyntaxKind::STATEMENT)
+                .collect::<Vec<_>>()
+        };
+        let new_statements = statements(new);
+
+        statements(old).iter().any(|old| {
+            new_statements
+                .iter()
+                .any(|new| std::ptr::eq(&*old.green(), &*new.green()))
+        })
+    }
+
+    #[rstest]
+    #[case::rename_identifier("cc_library", "cc_binary")]
+    #[case::edit_string("\"foo.cc\"", "\"main.cc\"")]
+    #[case::edit_comment("# comment", "# a longer comment")]
+    #[case::edit_number("z = 3", "z = 42")]
+    #[case::widen_whitespace(" ", "   ")]
+    #[case::add_list_element("[x, y, 2]", "[x, y, 2, 3]")]
+    #[case::add_argument("g(y, z = 3)", "g(y, *args, z = 3)")]
+    #[case::add_dict_entry("\"b\": g", "\"c\": 1, \"b\": g")]
+    #[case::add_statement("return [x, y, 2]", "y = x\n        return [x, y, 2]")]
+    #[case::reindent_suite("        return [x", "            return [x")]
+    #[case::dedent_suite("        return [x, y, 2]\n", "        pass\n    x = 1\n")]
+    #[case::multiline_list(
+        "[\"foo.cc\", \"bar.cc\"]",
+        "[\n        \"foo.cc\",\n        \"bar.cc\"]"
+    )]
+    fn test_incremental_reparse(#[case] before: &str, #[case] after: &str) {
+        let (old, new) = assert_reparse(SOURCE, before, after);
+
+        assert!(reuses_statements(&old, &new));
+    }
+
+    #[rstest]
+    #[case::add_function("cc_library(", "def g():\n    pass\n\ncc_library(")]
+    #[case::delete_colon("if x:", "if x")]
+    #[case::unclose_list("2]", "2")]
+    #[case::merge_tokens("z = 3", "z = 3.")]
+    #[case::invalid_escape("\"foo.cc\"", "\"foo\\d.cc\"")]
+    #[case::invalid_number("z = 3", "z = 0x")]
+    #[case::join_lines("\n    return {", " return {")]
+    fn test_reparse_matches_full_parse(#[case] before: &str, #[case] after: &str) {
+        assert_reparse(SOURCE, before, after);
+    }
+
+    #[test]
+    fn test_reparse_fixes_errors() {
+        let broken = SOURCE.replace("\"foo.cc\"", "\"foo\\d.cc\"");
+
+        assert_reparse(&broken, "\\d", "\\n");
+        assert_reparse(&broken, "z = 3", "z = 0x");
+    }
+
+    #[test]
+    fn test_reparse_every_edit_within_tokens() {
+        let old = File::parse(SOURCE);
+
+        for token in old
+            .syntax_node()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+        {
+            let range = token.text_range();
+            let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+            let (inner, inserted) = match token.kind() {
+                SyntaxKind::IDENTIFIER => (start..end, "x"),
+                SyntaxKind::INT => (start..end, "1"),
+                SyntaxKind::STRING => (start + 1..end - 1, "x"),
+                SyntaxKind::COMMENT => (start + 1..end, "x"),
+                _ => continue,
+            };
+
+            for offset in inner.clone() {
+                let edited = format!("{}{}{}", &SOURCE[..offset], inserted, &SOURCE[offset..]);
+                let edit = TextEdit::insert(TextSize::from(offset as u32), inserted);
+                assert_same_parse(&old.reparse(edit), &File::parse(&edited));
+
+                if inner.len() > 1 {
+                    let edited = format!("{}{}", &SOURCE[..offset], &SOURCE[offset + 1..]);
+                    let edit = TextEdit::delete(TextRange::at(
+                        TextSize::from(offset as u32),
+                        TextSize::from(1),
+                    ));
+                    assert_same_parse(&old.reparse(edit), &File::parse(&edited));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reparse_every_block() {
+        let old = File::parse(SOURCE);
+
+        for node in old.syntax_node().descendants() {
+            let inserted = match node.kind() {
+                SyntaxKind::LIST_EXPR | SyntaxKind::CALL_SUFFIX => "0, ".to_string(),
+                SyntaxKind::DICT_EXPR => "0: 0, ".to_string(),
+                SyntaxKind::SUITE => match node
+                    .children_with_tokens()
+                    .find(|element| element.kind() == SyntaxKind::INDENT)
+                {
+                    Some(indent) => format!("pass\n{}", indent),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            // Insert right after the opening bracket, or the indentation
+            let offset = node
+                .children_with_tokens()
+                .find(|element| {
+                    matches!(
+                        element.kind(),
+                        SyntaxKind::LBRACKET
+                            | SyntaxKind::LBRACE
+                            | SyntaxKind::LPAREN
+                            | SyntaxKind::INDENT
+                    )
+                })
+                .expect("block has an opening delimiter")
+                .text_range()
+                .end();
+
+            let edited = format!(
+                "{}{}{}",
+                &SOURCE[..usize::from(offset)],
+                inserted,
+                &SOURCE[usize::from(offset)..]
+            );
+            let new = old.reparse(TextEdit::insert(offset, inserted));
+
+            assert_same_parse(&new, &File::parse(&edited));
+            assert!(reuses_statements(&old, &new), "{}", edited);
+        }
+    }
+
+    #[test]
+    fn test_reparse_every_single_character_edit() {
+        let old = File::parse(SOURCE);
+
+        for offset in 0..=SOURCE.len() {
+            for inserted in ["$", "(", ")", "[", "]", ":", ",", "'", "\n", " ", "x"] {
+                let edited = format!("{}{}{}", &SOURCE[..offset], inserted, &SOURCE[offset..]);
+                let edit = TextEdit::insert(TextSize::from(offset as u32), inserted);
+                assert_same_parse(&old.reparse(edit), &File::parse(&edited));
+            }
+
+            if offset < SOURCE.len() {
+                let edited = format!("{}{}", &SOURCE[..offset], &SOURCE[offset + 1..]);
+                let edit = TextEdit::delete(TextRange::at(
+                    TextSize::from(offset as u32),
+                    TextSize::from(1),
+                ));
+                assert_same_parse(&old.reparse(edit), &File::parse(&edited));
+            }
+        }
+    }
+}
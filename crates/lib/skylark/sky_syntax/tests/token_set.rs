@@ -2,7 +2,7 @@
 mod token_set_test_suite {
     use pretty_assertions_sorted::assert_eq;
     use rstest::rstest;
-    use sky_syntax::{TokenKind, TokenSet};
+    use sky_syntax::{TokenKind, TokenKindExt, TokenSet};
 
     #[rstest]
     #[case::contains(TokenSet::from(vec![TokenKind::IDENTIFIER, TokenKind::AND_KW]), TokenKind::IDENTIFIER, true)]